@@ -371,6 +371,13 @@ enum Frame {
         len: u16,
     },
     DcStatelessResetTokens,
+    AckFrequency {
+        sequence_number: u64,
+        ack_eliciting_threshold: u64,
+        request_max_ack_delay: u64,
+        reordering_threshold: u64,
+    },
+    ImmediateAck,
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -581,6 +588,25 @@ impl<'a> IntoEvent<builder::Frame> for &crate::frame::DcStatelessResetTokens<'a>
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::AckFrequency {
+            sequence_number: self.sequence_number.as_u64(),
+            ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+            request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+            reordering_threshold: self.reordering_threshold.as_u64(),
+        }
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ImmediateAck {}
+    }
+}
+
 #[derive(Clone)]
 struct ConnectionCloseFrame<'a> {
     error_code: u64,
@@ -991,6 +1017,16 @@ enum PathChallengeStatus {
     Abandoned,
 }
 
+/// The progress of a connection migration requested by the application
+enum LocalMigrationStatus {
+    /// The new path is being validated
+    Started,
+    /// The new path was validated and is now the active path
+    Completed,
+    /// The new path failed validation and the connection remains on the previous path
+    Abandoned,
+}
+
 /// The reason the slow start congestion controller state has been exited
 enum SlowStartExitCause {
     /// A packet was determined lost
@@ -1021,6 +1057,8 @@ enum MtuUpdatedCause {
     InitialMtuPacketAcknowledged,
     /// MTU probes larger than the current MTU were not acknowledged
     LargerProbesLost,
+    /// An ICMP message reported that packets exceeded the MTU of the path
+    PacketTooBig,
 }
 
 /// A bandwidth delivery rate estimate with associated metadata
@@ -1058,6 +1096,14 @@ enum BbrState {
     ProbeRtt,
 }
 
+/// The server's decision on the early data sent by a client
+enum ZeroRttStatus {
+    /// The server processed the 0-RTT packets
+    Accepted,
+    /// The server discarded the 0-RTT packets and their data was retransmitted in 1-RTT packets
+    Rejected,
+}
+
 enum DcState {
     VersionNegotiated { version: u32 },
     NoVersionNegotiated,
@@ -233,6 +233,8 @@ struct KeySpaceDiscarded {
 /// Connection started
 struct ConnectionStarted<'a> {
     path: Path<'a>,
+    /// The destination connection ID of the client's first Initial packet
+    original_destination_connection_id: ConnectionId<'a>,
 }
 
 #[event("transport:duplicate_packet")]
@@ -357,6 +359,14 @@ struct PathChallengeUpdated<'a> {
     challenge_data: &'a [u8],
 }
 
+#[event("connectivity:local_migration_updated")]
+/// A connection migration requested by the application made progress
+struct LocalMigrationUpdated<'a> {
+    #[nominal_counter("status")]
+    status: LocalMigrationStatus,
+    path: Path<'a>,
+}
+
 #[event("tls:client_hello")]
 #[checkpoint("latency")]
 struct TlsClientHello<'a> {
@@ -461,6 +471,13 @@ struct DcPathCreated<'a> {
     path: &'a (dyn core::any::Any + Send + 'static),
 }
 
+#[event("transport:zero_rtt_status_updated")]
+/// The server accepted or rejected the early data sent by the client
+struct ZeroRttStatusUpdated {
+    #[nominal_counter("status")]
+    status: ZeroRttStatus,
+}
+
 // NOTE - This event MUST come last, since connection-level aggregation depends on it
 #[event("connectivity:connection_closed")]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.1.3
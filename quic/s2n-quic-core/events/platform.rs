@@ -148,3 +148,22 @@ struct PlatformEventLoopStarted<'a> {
     /// The local address of the socket
     local_address: SocketAddress<'a>,
 }
+
+#[event("platform:icmp_error")]
+#[subject(endpoint)]
+/// Emitted when the platform reports an ICMP error for a previously transmitted datagram
+struct PlatformIcmpError<'a> {
+    /// The address the datagram that triggered the error was sent to
+    remote_address: SocketAddress<'a>,
+    error: IcmpError,
+}
+
+enum IcmpError {
+    /// The datagram exceeded the MTU of a link on the path
+    PacketTooBig {
+        /// The MTU of the link, including the IP and UDP headers
+        mtu: u16,
+    },
+    /// The peer host is not listening on the destination port
+    PortUnreachable,
+}
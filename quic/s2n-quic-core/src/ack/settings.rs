@@ -25,6 +25,9 @@ pub struct Settings {
     /// The ACK delay exponent is an integer value indicating an exponent used
     /// to decode the ACK Delay field in the ACK frame
     pub ack_delay_exponent: u8,
+    /// The minimum ACK delay an endpoint is willing to use when the peer requests it
+    /// with an ACK_FREQUENCY frame, or `None` if the ACK frequency extension is disabled
+    pub min_ack_delay: Option<Duration>,
 
     //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.4
     //# A receiver that sends only non-ack-eliciting packets, such as ACK
@@ -70,6 +73,7 @@ impl Settings {
     pub const RECOMMENDED: Self = Self {
         max_ack_delay: MaxAckDelay::RECOMMENDED.as_duration(),
         ack_delay_exponent: AckDelayExponent::RECOMMENDED.as_u8(),
+        min_ack_delay: None,
         ack_elicitation_interval: RECOMMENDED_ELICITATION_INTERVAL,
         ack_ranges_limit: RECOMMENDED_RANGES_LIMIT,
     };
@@ -401,6 +401,15 @@ pub trait Generator {
     fn rotate_handshake_connection_id(&self) -> bool {
         true
     }
+
+    /// If true, the first byte of each generated connection ID carries information
+    /// that must be preserved, such as the config rotation bits read by a load balancer.
+    /// The first byte is not reserved by default, which allows it to be rewritten by
+    /// wrappers like the sharded server.
+    #[inline]
+    fn is_first_byte_reserved(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -5,12 +5,13 @@ use crate::application::ServerName;
 use crate::{
     ack,
     event::{api::SocketAddress, IntoEvent},
-    inet, recovery, stream,
+    inet::{self, Unspecified},
+    recovery, stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
         InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
         InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, TransportParameters,
+        MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, MinAckDelay, TransportParameters,
     },
 };
 #[cfg(feature = "alloc")]
@@ -98,6 +99,7 @@ pub struct Limits {
     pub(crate) max_open_remote_bidirectional_streams: InitialMaxStreamsBidi,
     pub(crate) max_open_remote_unidirectional_streams: InitialMaxStreamsUni,
     pub(crate) max_ack_delay: MaxAckDelay,
+    pub(crate) min_ack_delay: Option<MinAckDelay>,
     pub(crate) ack_delay_exponent: AckDelayExponent,
     pub(crate) max_active_connection_ids: ActiveConnectionIdLimit,
     pub(crate) ack_elicitation_interval: u8,
@@ -111,6 +113,8 @@ pub struct Limits {
     pub(crate) anti_amplification_multiplier: u8,
     pub(crate) stream_batch_size: u8,
     pub(crate) pto_jitter_percentage: u8,
+    pub(crate) preferred_ipv4_address: Option<inet::SocketAddressV4>,
+    pub(crate) preferred_ipv6_address: Option<inet::SocketAddressV6>,
 }
 
 impl Default for Limits {
@@ -146,6 +150,7 @@ impl Limits {
             max_open_remote_bidirectional_streams: InitialMaxStreamsBidi::RECOMMENDED,
             max_open_remote_unidirectional_streams: InitialMaxStreamsUni::RECOMMENDED,
             max_ack_delay: MaxAckDelay::RECOMMENDED,
+            min_ack_delay: None,
             ack_delay_exponent: AckDelayExponent::RECOMMENDED,
             max_active_connection_ids: ActiveConnectionIdLimit::RECOMMENDED,
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
@@ -159,6 +164,8 @@ impl Limits {
             anti_amplification_multiplier: ANTI_AMPLIFICATION_MULTIPLIER,
             stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
             pto_jitter_percentage: DEFAULT_PTO_JITTER_PERCENTAGE,
+            preferred_ipv4_address: None,
+            preferred_ipv6_address: None,
         }
     }
 
@@ -256,6 +263,24 @@ impl Limits {
         u64
     );
     setter!(with_max_ack_delay, max_ack_delay, Duration);
+
+    /// Enables the ACK frequency extension with the given minimum ACK delay (default: disabled)
+    ///
+    /// The `min_ack_delay` transport parameter is sent to the peer, allowing it to request
+    /// that ACKs are delayed by up to its own `max_ack_delay` with an ACK_FREQUENCY frame.
+    /// Requests for a delay smaller than `value` are rejected. The value must not exceed
+    /// the configured max ACK delay.
+    pub fn with_min_ack_delay(mut self, value: Duration) -> Result<Self, ValidationError> {
+        ensure!(
+            value <= self.max_ack_delay.as_duration(),
+            Err(ValidationError(
+                "min_ack_delay cannot be greater than max_ack_delay"
+            ))
+        );
+
+        self.min_ack_delay = Some(MinAckDelay::new(value)?);
+        Ok(self)
+    }
     setter!(
         with_max_active_connection_ids,
         max_active_connection_ids,
@@ -361,6 +386,58 @@ impl Limits {
         self.pto_jitter_percentage
     }
 
+    /// Sets the preferred address advertised by a server endpoint (default: none)
+    ///
+    /// Clients that support the `preferred_address` transport parameter will migrate to this
+    /// address once the handshake is confirmed. One address can be configured for each
+    /// address family; calling this method again with an address of the same family replaces
+    /// the previous value.
+    ///
+    /// The endpoint must be able to receive packets on the preferred address for the
+    /// migration to succeed.
+    pub fn with_preferred_address<A: Into<inet::SocketAddress>>(
+        mut self,
+        address: A,
+    ) -> Result<Self, ValidationError> {
+        let address = address.into().unmap();
+
+        ensure!(
+            !address.ip().is_unspecified() && address.port() != 0,
+            Err(ValidationError(
+                "preferred address must have a specified IP and port"
+            ))
+        );
+
+        match address {
+            inet::SocketAddress::IpV4(address) => self.preferred_ipv4_address = Some(address),
+            inet::SocketAddress::IpV6(address) => self.preferred_ipv6_address = Some(address),
+        }
+
+        Ok(self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn preferred_ipv4_address(&self) -> Option<inet::SocketAddressV4> {
+        self.preferred_ipv4_address
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn preferred_ipv6_address(&self) -> Option<inet::SocketAddressV6> {
+        self.preferred_ipv6_address
+    }
+
+    /// Returns `true` if the given local address is one of the configured preferred addresses
+    #[doc(hidden)]
+    #[inline]
+    pub fn is_preferred_address(&self, address: &inet::SocketAddress) -> bool {
+        match address.unmap() {
+            inet::SocketAddress::IpV4(address) => self.preferred_ipv4_address == Some(address),
+            inet::SocketAddress::IpV6(address) => self.preferred_ipv6_address == Some(address),
+        }
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -376,6 +453,10 @@ impl Limits {
         ack::Settings {
             ack_delay_exponent: self.ack_delay_exponent.as_u8(),
             max_ack_delay: self.max_ack_delay.as_duration(),
+            min_ack_delay: match self.min_ack_delay {
+                Some(min_ack_delay) => Some(min_ack_delay.as_duration()),
+                None => None,
+            },
             ack_ranges_limit: self.ack_ranges_limit,
             ack_elicitation_interval: self.ack_elicitation_interval,
         }
@@ -517,6 +598,57 @@ mod tests {
         assert!(limits.with_unidirectional_data_window(data).is_ok());
     }
 
+    #[test]
+    fn preferred_address_validation() {
+        let limits = Limits::default();
+        assert!(limits.preferred_ipv4_address().is_none());
+        assert!(limits.preferred_ipv6_address().is_none());
+
+        let unspecified = inet::SocketAddressV4::new([0, 0, 0, 0], 443);
+        assert!(limits.with_preferred_address(unspecified).is_err());
+        let no_port = inet::SocketAddressV4::new([192, 0, 2, 1], 0);
+        assert!(limits.with_preferred_address(no_port).is_err());
+
+        let v4 = inet::SocketAddressV4::new([192, 0, 2, 1], 443);
+        let v6 = inet::SocketAddressV6::new([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1], 443);
+        let limits = limits
+            .with_preferred_address(v4)
+            .unwrap()
+            .with_preferred_address(v6)
+            .unwrap();
+        assert_eq!(limits.preferred_ipv4_address(), Some(v4));
+        assert_eq!(limits.preferred_ipv6_address(), Some(v6));
+        assert!(limits.is_preferred_address(&v4.into()));
+        assert!(limits.is_preferred_address(&v4.to_ipv6_mapped().into()));
+        assert!(limits.is_preferred_address(&v6.into()));
+        assert!(!limits.is_preferred_address(&no_port.into()));
+    }
+
+    #[test]
+    fn min_ack_delay_validation() {
+        let limits = Limits::default();
+        assert!(limits.ack_settings().min_ack_delay.is_none());
+
+        let max_ack_delay = limits.max_ack_delay.as_duration();
+        assert!(limits
+            .with_min_ack_delay(max_ack_delay + Duration::from_micros(1))
+            .is_err());
+
+        let limits = limits.with_min_ack_delay(Duration::from_millis(1)).unwrap();
+        assert_eq!(
+            limits.ack_settings().min_ack_delay,
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            limits
+                .with_min_ack_delay(max_ack_delay)
+                .unwrap()
+                .ack_settings()
+                .min_ack_delay,
+            Some(max_ack_delay)
+        );
+    }
+
     // Limits can be updated through the UpdatableLimits wrapper
     #[test]
     fn updatable_limits() {
@@ -5,7 +5,11 @@ pub mod close;
 pub mod error;
 pub mod id;
 pub mod limits;
+#[cfg(feature = "alloc")]
+pub mod stats;
 
 pub use error::{Error, ProcessingError};
 pub use id::{InitialId, LocalId, PeerId, UnboundedId};
 pub use limits::Limits;
+#[cfg(feature = "alloc")]
+pub use stats::{PathStats, Stats};
@@ -0,0 +1,102 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Point-in-time statistics for a connection and its paths
+
+use crate::{event::api::EcnState, inet::SocketAddress};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// A snapshot of the statistics for a connection
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// The total number of packets sent on all paths
+    pub packets_sent: u64,
+    /// The total number of packets declared lost on all paths
+    pub packets_lost: u64,
+    /// The total number of lost packets whose contents were queued for retransmission
+    pub packets_retransmitted: u64,
+    /// The total number of UDP payload bytes sent on all paths
+    pub bytes_sent: u64,
+    /// The total number of UDP payload bytes received on all paths
+    pub bytes_received: u64,
+    /// The number of bytes the local endpoint can still send on streams before being
+    /// blocked by the peer's connection flow control limit
+    pub send_credit: u64,
+    /// The number of bytes the peer can still send on streams before being blocked by the
+    /// local connection flow control limit
+    pub receive_credit: u64,
+    /// The statistics for each path the connection has used
+    pub paths: Vec<PathStats>,
+}
+
+impl Stats {
+    /// Returns the statistics for the path currently used for transmission
+    #[inline]
+    pub fn active_path(&self) -> Option<&PathStats> {
+        self.paths.iter().find(|path| path.is_active)
+    }
+}
+
+/// A snapshot of the statistics for a single path
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct PathStats {
+    /// The identifier of the path, as reported in events
+    pub id: u64,
+    pub local_address: SocketAddress,
+    pub remote_address: SocketAddress,
+    /// True if the path is currently used for transmission
+    pub is_active: bool,
+    /// True if the path has passed address validation
+    pub is_validated: bool,
+    pub smoothed_rtt: Duration,
+    pub min_rtt: Duration,
+    pub latest_rtt: Duration,
+    /// The congestion window, in bytes
+    pub congestion_window: u32,
+    pub bytes_in_flight: u32,
+    /// The rate at which packets are paced, in bytes per second
+    ///
+    /// This is `None` if the congestion controller is not currently pacing packets.
+    pub pacing_rate: Option<u64>,
+    /// The maximum QUIC datagram size, not including UDP and IP headers
+    pub mtu: u16,
+    /// True if the search for the maximum MTU has completed for now
+    pub mtu_search_complete: bool,
+    /// The ECN validation state of the path
+    pub ecn_state: EcnState,
+    pub packets_sent: u64,
+    pub packets_lost: u64,
+    pub packets_retransmitted: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+impl Default for PathStats {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            id: 0,
+            local_address: SocketAddress::default(),
+            remote_address: SocketAddress::default(),
+            is_active: false,
+            is_validated: false,
+            smoothed_rtt: Duration::ZERO,
+            min_rtt: Duration::ZERO,
+            latest_rtt: Duration::ZERO,
+            congestion_window: 0,
+            bytes_in_flight: 0,
+            pacing_rate: None,
+            mtu: 0,
+            mtu_search_complete: false,
+            ecn_state: EcnState::Testing {},
+            packets_sent: 0,
+            packets_lost: 0,
+            packets_retransmitted: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::crypto;
+use crate::{crypto, version::Version};
 use hex_literal::hex;
 
 /// Types for which are able to perform initial cryptography.
@@ -12,8 +12,8 @@ use hex_literal::hex;
 pub trait InitialKey: crypto::Key + Sized {
     type HeaderKey: crypto::HeaderKey;
 
-    fn new_server(connection_id: &[u8]) -> (Self, Self::HeaderKey);
-    fn new_client(connection_id: &[u8]) -> (Self, Self::HeaderKey);
+    fn new_server(version: Version, connection_id: &[u8]) -> (Self, Self::HeaderKey);
+    fn new_client(version: Version, connection_id: &[u8]) -> (Self, Self::HeaderKey);
 }
 
 /// Types for which are able to perform initial header cryptography.
@@ -28,6 +28,10 @@ pub trait InitialHeaderKey: crypto::HeaderKey {}
 
 pub const INITIAL_SALT: [u8; 20] = hex!("38762cf7f55934b34d179ae6a4c80cadccbb7f0a");
 
+/// The salt used to derive QUIC version 2 Initial secrets, as defined in
+/// <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.1>
+pub const INITIAL_SALT_V2: [u8; 20] = hex!("0dede3def700a6db819381be6e269dcbf9bd2ed9");
+
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.2
 //# client_initial_secret = HKDF-Expand-Label(initial_secret,
 //#                                           "client in", "",
@@ -36,12 +36,15 @@ pub trait Key: Send {
 
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
-    use crate::crypto::{
-        packet_protection,
-        retry::{IntegrityTag, INTEGRITY_TAG_LEN},
-        scatter, HandshakeHeaderKey, HandshakeKey, HeaderKey as CryptoHeaderKey,
-        HeaderProtectionMask, InitialHeaderKey, InitialKey, OneRttHeaderKey, OneRttKey, RetryKey,
-        ZeroRttHeaderKey, ZeroRttKey,
+    use crate::{
+        crypto::{
+            packet_protection,
+            retry::{IntegrityTag, INTEGRITY_TAG_LEN},
+            scatter, HandshakeHeaderKey, HandshakeKey, HeaderKey as CryptoHeaderKey,
+            HeaderProtectionMask, InitialHeaderKey, InitialKey, OneRttHeaderKey, OneRttKey,
+            RetryKey, ZeroRttHeaderKey, ZeroRttKey,
+        },
+        version::Version,
     };
 
     #[derive(Debug)]
@@ -119,11 +122,11 @@ pub mod testing {
     impl InitialKey for Key {
         type HeaderKey = HeaderKey;
 
-        fn new_server(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_server(_version: Version, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
 
-        fn new_client(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_client(_version: Version, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
     }
@@ -140,10 +143,14 @@ pub mod testing {
     }
     impl ZeroRttKey for Key {}
     impl RetryKey for Key {
-        fn generate_tag(_payload: &[u8]) -> IntegrityTag {
+        fn generate_tag(_version: Version, _payload: &[u8]) -> IntegrityTag {
             [0u8; INTEGRITY_TAG_LEN]
         }
-        fn validate(_payload: &[u8], _tag: IntegrityTag) -> Result<(), packet_protection::Error> {
+        fn validate(
+            _version: Version,
+            _payload: &[u8],
+            _tag: IntegrityTag,
+        ) -> Result<(), packet_protection::Error> {
             Ok(())
         }
     }
@@ -1,15 +1,19 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::crypto::packet_protection;
+use crate::{crypto::packet_protection, version::Version};
 use hex_literal::hex;
 
 pub const INTEGRITY_TAG_LEN: usize = 16;
 pub type IntegrityTag = [u8; INTEGRITY_TAG_LEN];
 
 pub trait RetryKey {
-    fn generate_tag(payload: &[u8]) -> IntegrityTag;
-    fn validate(payload: &[u8], tag: IntegrityTag) -> Result<(), packet_protection::Error>;
+    fn generate_tag(version: Version, payload: &[u8]) -> IntegrityTag;
+    fn validate(
+        version: Version,
+        payload: &[u8],
+        tag: IntegrityTag,
+    ) -> Result<(), packet_protection::Error>;
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.8
@@ -26,6 +30,14 @@ pub const SECRET_KEY_BYTES: [u8; 16] = hex!("be0c690b9f66575a1d766b54e368c84e");
 
 pub const NONCE_BYTES: [u8; 12] = hex!("461599d35d632bf2239825bb");
 
+/// The Retry Integrity Tag key for QUIC version 2, as defined in
+/// <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.3>
+pub const SECRET_KEY_BYTES_V2: [u8; 16] = hex!("8fb4b01b56ac48e260fbcbcead7ccc92");
+
+/// The Retry Integrity Tag nonce for QUIC version 2, as defined in
+/// <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.3>
+pub const NONCE_BYTES_V2: [u8; 12] = hex!("d86969bc2d7c6d9990efb04a");
+
 pub mod example {
     use super::*;
 
@@ -58,6 +70,20 @@ pub mod example {
 
     pub const EXPECTED_TAG: [u8; 16] = hex!("04a265ba2eff4d829058fb3f0f2496ba");
 
+    /// The QUIC version 2 Retry packet from
+    /// <https://www.rfc-editor.org/rfc/rfc9369#appendix-A.4>
+    pub const PACKET_V2: [u8; PACKET_LEN] = hex!(
+        "
+        cf6b3343cf0008f067a5502a4262b574 6f6b656ec8646ce8bfe33952d9555436
+        65dcc7b6
+        "
+    );
+
+    pub const PSEUDO_PACKET_V2: [u8; 29] =
+        hex!("088394c8f03e515708 cf6b3343cf 00 08f067a5502a4262b5 746f6b656e");
+
+    pub const EXPECTED_TAG_V2: [u8; 16] = hex!("c8646ce8bfe33952d955543665dcc7b6");
+
     // The server sends an empty destination connection ID back to the client
     pub const DCID: [u8; 0] = hex!("");
 
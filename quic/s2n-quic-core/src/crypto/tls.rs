@@ -112,6 +112,14 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         server_params: &mut alloc::vec::Vec<u8>,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Returns the QUIC version of the connection
+    ///
+    /// The version determines the labels used to derive the packet protection keys from the
+    /// TLS secrets.
+    fn quic_version(&self) -> crate::version::Version {
+        crate::version::VERSION_1
+    }
+
     fn on_handshake_keys(
         &mut self,
         key: Crypto::HandshakeKey,
@@ -132,6 +140,25 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_parameters: ApplicationParameters,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called by the client with the server's decision on the early data
+    ///
+    /// This is only called if 0-RTT keys were previously provided, and before the 1-RTT keys
+    /// are installed. If the TLS provider doesn't report the decision, the early data is
+    /// considered rejected.
+    fn on_zero_rtt_status(&mut self, accepted: bool) -> Result<(), crate::transport::Error> {
+        let _ = accepted;
+        Ok(())
+    }
+
+    /// Returns `true` if the server may accept the early data offered in the ClientHello
+    ///
+    /// The transport decides once the ClientHello has been received, so this should be
+    /// checked after the first call to `receive_initial` returns data and before the data is
+    /// passed to the TLS library. If `false` is returned, the server must not provide 0-RTT keys.
+    fn can_accept_early_data(&self) -> bool {
+        true
+    }
+
     fn on_server_name(
         &mut self,
         server_name: crate::application::ServerName,
@@ -216,6 +243,22 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Returns `true` if sessions are able to switch to a compatible QUIC version after the
+    /// handshake has started
+    ///
+    /// This is required for the transport to perform compatible version negotiation. Sessions
+    /// that support it must use [`Context::quic_version`] each time keys are derived.
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if client sessions are able to send early data when resuming a session
+    ///
+    /// Connection attempts that request 0-RTT are rejected if this returns `false`.
+    fn supports_zero_rtt(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -387,10 +430,14 @@ macro_rules! handshake_type {
 //#     finished(20)
 //#     (255)
 //# } HandshakeType;
+
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4
+//# new_session_ticket(4),
 handshake_type!(
     HelloRequest(0),
     ClientHello(1),
     ServerHello(2),
+    NewSessionTicket(4),
     Certificate(11),
     ServerKeyExchange(12),
     CertificateRequest(13),
@@ -319,7 +319,7 @@ pub mod server {
 
 mod key {
     use super::*;
-    use crate::crypto::scatter;
+    use crate::{crypto::scatter, version::Version};
 
     #[derive(Debug)]
     pub struct NoCrypto;
@@ -405,12 +405,12 @@ mod key {
         type HeaderKey = NoCrypto;
 
         #[inline(always)]
-        fn new_server(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_server(_version: Version, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (NoCrypto, NoCrypto)
         }
 
         #[inline(always)]
-        fn new_client(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_client(_version: Version, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (NoCrypto, NoCrypto)
         }
     }
@@ -432,12 +432,13 @@ mod key {
 
     impl crypto::RetryKey for NoCrypto {
         #[inline(always)]
-        fn generate_tag(_payload: &[u8]) -> crypto::retry::IntegrityTag {
+        fn generate_tag(_version: Version, _payload: &[u8]) -> crypto::retry::IntegrityTag {
             Default::default()
         }
 
         #[inline(always)]
         fn validate(
+            _version: Version,
             _payload: &[u8],
             _tag: crypto::retry::IntegrityTag,
         ) -> Result<(), crypto::packet_protection::Error> {
@@ -102,6 +102,10 @@ where
     fn max_tag_length(&self) -> usize {
         self.inner.max_tag_length()
     }
+
+    fn supports_zero_rtt(&self) -> bool {
+        self.inner.supports_zero_rtt()
+    }
 }
 
 #[derive(Debug)]
@@ -134,8 +138,6 @@ impl<S: tls::Session + 'static> OffloadSession<S> {
                 match send_to_quic.poll_slice(ctx) {
                     Poll::Ready(res) => match res {
                         Ok(send_slice) => {
-                            let allowed_to_send = *allowed_to_send.lock().unwrap();
-
                             let mut context = RemoteContext {
                                 send_to_quic: send_slice,
                                 waker: ctx.waker().clone(),
@@ -143,7 +145,7 @@ impl<S: tls::Session + 'static> OffloadSession<S> {
                                 handshake_data: &mut handshake_data,
                                 application_data: &mut application_data,
                                 exporter_handler: exporter.clone(),
-                                allowed_to_send,
+                                allowed_to_send: *allowed_to_send.lock().unwrap(),
                                 error: None,
                             };
 
@@ -173,6 +175,11 @@ impl<S: tls::Session + 'static> OffloadSession<S> {
                                 }
                             }
 
+                            // The early data decision is made before the ClientHello is sent to
+                            // us, so refresh the state after receiving it
+                            context.allowed_to_send.can_accept_early_data =
+                                allowed_to_send.lock().unwrap().can_accept_early_data;
+
                             let res = inner.poll(&mut context);
                             // Either there was an error or the handshake has finished if TLS returned Poll::Ready.
                             // Notify the QUIC side accordingly.
@@ -255,6 +262,9 @@ impl<S: tls::Session> tls::Session for OffloadSession<S> {
                                     transport_parameters: &transport_parameters,
                                 },
                             )?,
+                            Request::ZeroRttStatus(accepted) => {
+                                context.on_zero_rtt_status(accepted)?;
+                            }
                             Request::SendHandshake(bytes) => {
                                 context.send_handshake(bytes);
                             }
@@ -296,11 +306,14 @@ impl<S: tls::Session> tls::Session for OffloadSession<S> {
         if allowed_to_send.can_send_initial != context.can_send_initial()
             || allowed_to_send.can_send_handshake != context.can_send_handshake()
             || allowed_to_send.can_send_application != context.can_send_application()
+            || allowed_to_send.quic_version != context.quic_version()
         {
             *allowed_to_send = AllowedToSend {
                 can_send_initial: context.can_send_initial(),
                 can_send_handshake: context.can_send_handshake(),
                 can_send_application: context.can_send_application(),
+                quic_version: context.quic_version(),
+                can_accept_early_data: allowed_to_send.can_accept_early_data,
             };
             state_change = true;
         }
@@ -311,6 +324,17 @@ impl<S: tls::Session> tls::Session for OffloadSession<S> {
             Poll::Ready(res) => match res {
                 Ok(mut slice) => {
                     if let Some(resp) = context.receive_initial(None) {
+                        // The transport decides on the early data once the ClientHello is
+                        // received, so make sure the decision is visible to the TLS task
+                        // before it processes the ClientHello
+                        let can_accept_early_data = context.can_accept_early_data();
+                        let mut allowed_to_send = self.allowed_to_send.lock().unwrap();
+                        if allowed_to_send.can_accept_early_data != can_accept_early_data {
+                            allowed_to_send.can_accept_early_data = can_accept_early_data;
+                            state_change = true;
+                        }
+                        drop(allowed_to_send);
+
                         let _ = slice.push(Response::Initial(resp));
                     }
 
@@ -350,11 +374,25 @@ impl<S: tls::Session> CryptoSuite for OffloadSession<S> {
     type RetryKey = <S as CryptoSuite>::RetryKey;
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 struct AllowedToSend {
     can_send_initial: bool,
     can_send_handshake: bool,
     can_send_application: bool,
+    quic_version: crate::version::Version,
+    can_accept_early_data: bool,
+}
+
+impl Default for AllowedToSend {
+    fn default() -> Self {
+        Self {
+            can_send_initial: false,
+            can_send_handshake: false,
+            can_send_application: false,
+            quic_version: crate::version::VERSION_1,
+            can_accept_early_data: true,
+        }
+    }
 }
 
 const SLICE_ERROR: crate::transport::Error =
@@ -373,6 +411,14 @@ struct RemoteContext<'a, Request, H> {
 }
 
 impl<S: CryptoSuite, H: ExporterHandler> tls::Context<S> for RemoteContext<'_, Request<S>, H> {
+    fn quic_version(&self) -> crate::version::Version {
+        self.allowed_to_send.quic_version
+    }
+
+    fn can_accept_early_data(&self) -> bool {
+        self.allowed_to_send.can_accept_early_data
+    }
+
     fn on_client_application_params(
         &mut self,
         client_params: tls::ApplicationParameters,
@@ -437,6 +483,14 @@ impl<S: CryptoSuite, H: ExporterHandler> tls::Context<S> for RemoteContext<'_, R
         Ok(())
     }
 
+    fn on_zero_rtt_status(&mut self, accepted: bool) -> Result<(), crate::transport::Error> {
+        match self.send_to_quic.push(Request::ZeroRttStatus(accepted)) {
+            Ok(_) => (),
+            Err(_) => self.error = Some(SLICE_ERROR),
+        }
+        Ok(())
+    }
+
     fn on_server_name(
         &mut self,
         server_name: crate::application::ServerName,
@@ -611,6 +665,7 @@ enum Request<S: CryptoSuite> {
         <S as CryptoSuite>::OneRttHeaderKey,
         Vec<u8>,
     ),
+    ZeroRttStatus(bool),
     HandshakeComplete,
     TlsDone,
     TlsContext(Box<dyn Any + Send>),
@@ -636,6 +691,7 @@ impl<S: CryptoSuite> alloc::fmt::Debug for Request<S> {
             Request::ApplicationProtocol(_) => write!(f, "ApplicationProtocol"),
             Request::KeyExchangeGroup(_) => write!(f, "KeyExchangeGroup"),
             Request::OneRttKeys(_, _, _) => write!(f, "OneRttKeys"),
+            Request::ZeroRttStatus(_) => write!(f, "ZeroRttStatus"),
             Request::HandshakeComplete => write!(f, "HandshakeComplete"),
             Request::TlsDone => write!(f, "TlsDone"),
             Request::ZeroRtt(_, _, _) => write!(f, "ZeroRtt"),
@@ -51,6 +51,14 @@ impl<E: tls::Endpoint> tls::Endpoint for SlowEndpoint<E> {
     fn max_tag_length(&self) -> usize {
         self.endpoint.max_tag_length()
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        self.endpoint.supports_compatible_version_negotiation()
+    }
+
+    fn supports_zero_rtt(&self) -> bool {
+        self.endpoint.supports_zero_rtt()
+    }
 }
 
 // SlowSession is a test TLS provider that is slow, namely, for each call to poll,
@@ -102,6 +110,14 @@ impl<I, S: tls::Session> tls::Context<S> for SlowContext<'_, I>
 where
     I: tls::Context<SlowSession<S>>,
 {
+    fn quic_version(&self) -> crate::version::Version {
+        self.0.quic_version()
+    }
+
+    fn can_accept_early_data(&self) -> bool {
+        self.0.can_accept_early_data()
+    }
+
     fn on_client_application_params(
         &mut self,
         client_params: tls::ApplicationParameters,
@@ -139,6 +155,10 @@ where
             .on_one_rtt_keys(key, header_key, application_parameters)
     }
 
+    fn on_zero_rtt_status(&mut self, accepted: bool) -> Result<(), transport::Error> {
+        self.0.on_zero_rtt_status(accepted)
+    }
+
     fn on_server_name(
         &mut self,
         server_name: application::ServerName,
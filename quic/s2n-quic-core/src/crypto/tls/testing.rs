@@ -179,6 +179,25 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
         client_endpoint: &mut CE,
         server_name: ServerName,
     ) -> Self
+    where
+        SE: tls::Endpoint<Session = S>,
+        CE: tls::Endpoint<Session = C>,
+    {
+        Self::with_version(
+            server_endpoint,
+            client_endpoint,
+            server_name,
+            crate::version::VERSION_1,
+        )
+    }
+
+    /// Creates a pair of sessions which derive keys for the given QUIC version
+    pub fn with_version<SE, CE>(
+        server_endpoint: &mut SE,
+        client_endpoint: &mut CE,
+        server_name: ServerName,
+        quic_version: crate::version::Version,
+    ) -> Self
     where
         SE: tls::Endpoint<Session = S>,
         CE: tls::Endpoint<Session = C>,
@@ -188,11 +207,19 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
         let server = server_endpoint.new_server_session(&&server_params()[..]);
         let mut server_context =
             Context::new(endpoint::Type::Server, ServerState::WaitingClientHello);
-        server_context.initial.crypto = Some(S::InitialKey::new_server(server_name.as_bytes()));
+        server_context.quic_version = quic_version;
+        server_context.initial.crypto = Some(S::InitialKey::new_server(
+            quic_version,
+            server_name.as_bytes(),
+        ));
 
         let client = client_endpoint.new_client_session(&&client_params()[..], server_name.clone());
         let mut client_context = Context::new(endpoint::Type::Client, ClientState::ClientHelloSent);
-        client_context.initial.crypto = Some(C::InitialKey::new_client(server_name.as_bytes()));
+        client_context.quic_version = quic_version;
+        client_context.initial.crypto = Some(C::InitialKey::new_client(
+            quic_version,
+            server_name.as_bytes(),
+        ));
 
         Self {
             server: TlsEndpoint::new(server, server_context),
@@ -404,6 +431,7 @@ where
     pub application_protocol: Option<Bytes>,
     pub key_exchange_group: Option<NamedGroup>,
     pub transport_parameters: Option<Bytes>,
+    pub quic_version: crate::version::Version,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -425,6 +453,7 @@ where
             .field("application_protocol", &self.application_protocol)
             .field("key_exchange_group", &self.key_exchange_group)
             .field("transport_parameters", &self.transport_parameters)
+            .field("quic_version", &self.quic_version)
             .field("endpoint", &self.endpoint)
             .finish()
     }
@@ -446,6 +475,7 @@ where
             application_protocol: None,
             key_exchange_group: None,
             transport_parameters: None,
+            quic_version: crate::version::VERSION_1,
             endpoint,
             state,
             waker,
@@ -668,6 +698,10 @@ impl<C: CryptoSuite, State: Debug, Params> tls::Context<C> for Context<C, State,
 where
     for<'a> Params: DecoderValue<'a>,
 {
+    fn quic_version(&self) -> crate::version::Version {
+        self.quic_version
+    }
+
     fn on_client_application_params(
         &mut self,
         _client_params: ApplicationParameters,
@@ -0,0 +1,455 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Server-side decisions on accepting early data sent by clients in 0-RTT packets
+//!
+//! Early data isn't protected against replay by the TLS handshake. A [`Validator`] is given the
+//! chance to refuse early data for each ClientHello that attempts to send it, which can be used
+//! to implement the anti-replay mechanisms described in
+//! [RFC 8446 Section 8](https://www.rfc-editor.org/rfc/rfc8446#section-8).
+
+use crate::{
+    crypto::tls::{HandshakeHeader, HandshakeType},
+    event::{api::SocketAddress, IntoEvent},
+    inet,
+    time::Timestamp,
+};
+use core::time::Duration;
+use s2n_codec::{DecoderBuffer, DecoderError};
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Request<'a> {
+    /// The address of the client attempting to send early data
+    pub remote_address: SocketAddress<'a>,
+    /// The `random` field of the ClientHello
+    pub client_random: &'a [u8],
+    /// The first PSK identity offered by the client, which is the session ticket being resumed
+    pub psk_identity: &'a [u8],
+    /// The binder of the first PSK identity
+    ///
+    /// The binder authenticates the ClientHello with the PSK, so it can only be produced by a
+    /// client that holds the session ticket. This makes it suitable for detecting replayed
+    /// ClientHello messages before the TLS provider has verified it, as a ClientHello with a
+    /// forged binder fails the handshake and can't match the binder of a legitimate one.
+    pub psk_binder: &'a [u8],
+    /// The obfuscated age of the session ticket, as reported by the client
+    pub obfuscated_ticket_age: u32,
+    /// The time at which the ClientHello was received
+    pub timestamp: Timestamp,
+}
+
+impl<'a> Request<'a> {
+    #[inline]
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        hello: &ClientHello<'a>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            client_random: hello.random,
+            psk_identity: hello.psk_identity,
+            psk_binder: hello.psk_binder,
+            obfuscated_ticket_age: hello.obfuscated_ticket_age,
+            timestamp,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Outcome {
+    /// Allows the TLS provider to accept the early data
+    ///
+    /// Note that the TLS provider may still reject the early data, for example, if the session
+    /// ticket can't be resumed.
+    Accept,
+
+    /// Rejects the early data
+    ///
+    /// The handshake continues without processing any 0-RTT packets. The client retransmits
+    /// the data in 1-RTT packets once the handshake completes.
+    Reject,
+}
+
+/// Decides if the early data in a ClientHello should be accepted
+pub trait Validator: 'static + Send {
+    /// Called for each ClientHello that attempts to send early data
+    ///
+    /// This is only called on servers and only for ClientHello messages that contain both the
+    /// `early_data` and `pre_shared_key` extensions. The ClientHello hasn't been authenticated
+    /// by the TLS provider at this point, so anything recorded about it should be keyed on
+    /// [`Request::psk_binder`], which can't be forged without the PSK.
+    fn on_early_data_request(&mut self, request: &Request) -> Outcome;
+
+    /// Called for each session ticket issued by the server
+    ///
+    /// The `ticket_age_add` value of the ticket allows the ticket age reported by a later
+    /// ClientHello to be checked, as described in
+    /// [RFC 8446 Section 8.3](https://www.rfc-editor.org/rfc/rfc8446#section-8.3).
+    fn on_session_ticket_issued(&mut self, _ticket: &SessionTicket) {}
+}
+
+pub mod disabled {
+    use super::*;
+
+    /// Rejects all early data
+    #[derive(Debug, Default)]
+    pub struct Validator;
+
+    impl super::Validator for Validator {
+        #[inline]
+        fn on_early_data_request(&mut self, _request: &Request) -> Outcome {
+            Outcome::Reject
+        }
+    }
+}
+
+pub mod allow_all {
+    use super::*;
+
+    /// Allows the TLS provider to accept all early data, without any protection against replay
+    #[derive(Debug, Default)]
+    pub struct Validator;
+
+    impl super::Validator for Validator {
+        #[inline]
+        fn on_early_data_request(&mut self, _request: &Request) -> Outcome {
+            Outcome::Accept
+        }
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4.2
+//# pre_shared_key(41),                         /* RFC 8446 */
+//# early_data(42),                             /* RFC 8446 */
+const PRE_SHARED_KEY: u16 = 41;
+const EARLY_DATA: u16 = 42;
+
+/// The fields of a ClientHello that are needed to decide on early data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientHello<'a> {
+    pub random: &'a [u8],
+    pub psk_identity: &'a [u8],
+    pub obfuscated_ticket_age: u32,
+    pub psk_binder: &'a [u8],
+}
+
+impl<'a> ClientHello<'a> {
+    /// Parses a ClientHello message, including the handshake header
+    ///
+    /// Returns `None` if the message doesn't attempt to send early data or can't be parsed. In
+    /// the latter case, the TLS provider will fail the handshake.
+    pub fn decode(message: &'a [u8]) -> Option<Self> {
+        Self::decode_message(DecoderBuffer::new(message)).ok()?
+    }
+
+    fn decode_message(buffer: DecoderBuffer<'a>) -> Result<Option<Self>, DecoderError> {
+        let (header, buffer) = buffer.decode::<HandshakeHeader>()?;
+
+        if header.msg_type() != Some(HandshakeType::ClientHello) {
+            return Ok(None);
+        }
+
+        let (buffer, _) = buffer.decode_slice(header.len())?;
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.1.2
+        //# struct {
+        //#     ProtocolVersion legacy_version = 0x0303;    /* TLS v1.2 */
+        //#     Random random;
+        //#     opaque legacy_session_id<0..32>;
+        //#     CipherSuite cipher_suites<2..2^16-2>;
+        //#     opaque legacy_compression_methods<1..2^8-1>;
+        //#     Extension extensions<8..2^16-1>;
+        //# } ClientHello;
+        let buffer = buffer.skip(2)?;
+        let (random, buffer) = buffer.decode_slice(32)?;
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+        let buffer = buffer.skip_with_len_prefix::<u16>()?;
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+        let (mut extensions, _) = buffer.decode_slice_with_len_prefix::<u16>()?;
+
+        let mut early_data = false;
+        let mut psk = None;
+
+        while !extensions.is_empty() {
+            let (extension_type, remaining) = extensions.decode::<u16>()?;
+            let (data, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+            extensions = remaining;
+
+            match extension_type {
+                EARLY_DATA => early_data = true,
+                PRE_SHARED_KEY => psk = Some(data),
+                _ => {}
+            }
+        }
+
+        let Some(psk) = psk.filter(|_| early_data) else {
+            return Ok(None);
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.2.11
+        //# struct {
+        //#     opaque identity<1..2^16-1>;
+        //#     uint32 obfuscated_ticket_age;
+        //# } PskIdentity;
+        let (identities, psk) = psk.decode_slice_with_len_prefix::<u16>()?;
+        let (psk_identity, identities) = identities.decode_slice_with_len_prefix::<u16>()?;
+        let (obfuscated_ticket_age, _) = identities.decode::<u32>()?;
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.2.11
+        //# struct {
+        //#     PskIdentity identities<7..2^16-1>;
+        //#     PskBinderEntry binders<33..2^16-1>;
+        //# } OfferedPsks;
+        //
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.2.10
+        //# The PSK used to encrypt the
+        //# early data MUST be the first PSK listed in the client's
+        //# "pre_shared_key" extension.
+        let (binders, _) = psk.decode_slice_with_len_prefix::<u16>()?;
+        let (psk_binder, _) = binders.decode_slice_with_len_prefix::<u8>()?;
+
+        Ok(Some(Self {
+            random: random.into_less_safe_slice(),
+            psk_identity: psk_identity.into_less_safe_slice(),
+            obfuscated_ticket_age,
+            psk_binder: psk_binder.into_less_safe_slice(),
+        }))
+    }
+}
+
+/// A session ticket issued by the server in a NewSessionTicket message
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SessionTicket<'a> {
+    /// The ticket, which the client sends as its PSK identity when resuming the session
+    pub identity: &'a [u8],
+    /// The value the client adds to the age of the ticket to obfuscate it
+    pub ticket_age_add: u32,
+    /// The lifetime of the ticket advertised to the client
+    pub lifetime: Duration,
+    /// The time at which the ticket was issued
+    pub timestamp: Timestamp,
+}
+
+impl<'a> SessionTicket<'a> {
+    #[inline]
+    #[doc(hidden)]
+    pub fn new(
+        identity: &'a [u8],
+        ticket_age_add: u32,
+        lifetime: Duration,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            identity,
+            ticket_age_add,
+            lifetime,
+            timestamp,
+        }
+    }
+
+    /// Parses each NewSessionTicket message in a sequence of handshake messages
+    ///
+    /// Other messages are skipped. Parsing stops at the first message that is incomplete or
+    /// can't be parsed.
+    #[doc(hidden)]
+    pub fn decode_all(messages: &'a [u8], timestamp: Timestamp, mut on_ticket: impl FnMut(&Self)) {
+        let mut buffer = DecoderBuffer::new(messages);
+
+        while !buffer.is_empty() {
+            let Ok((ticket, remaining)) = Self::decode_message(buffer, timestamp) else {
+                return;
+            };
+
+            if let Some(ticket) = ticket {
+                on_ticket(&ticket);
+            }
+
+            buffer = remaining;
+        }
+    }
+
+    fn decode_message(
+        buffer: DecoderBuffer<'a>,
+        timestamp: Timestamp,
+    ) -> Result<(Option<Self>, DecoderBuffer<'a>), DecoderError> {
+        let (header, buffer) = buffer.decode::<HandshakeHeader>()?;
+        let (message, remaining) = buffer.decode_slice(header.len())?;
+
+        if header.msg_type() != Some(HandshakeType::NewSessionTicket) {
+            return Ok((None, remaining));
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.6.1
+        //# struct {
+        //#     uint32 ticket_lifetime;
+        //#     uint32 ticket_age_add;
+        //#     opaque ticket_nonce<0..255>;
+        //#     opaque ticket<1..2^16-1>;
+        //#     Extension extensions<0..2^16-2>;
+        //# } NewSessionTicket;
+        let (lifetime, message) = message.decode::<u32>()?;
+        let (ticket_age_add, message) = message.decode::<u32>()?;
+        let message = message.skip_with_len_prefix::<u8>()?;
+        let (identity, _) = message.decode_slice_with_len_prefix::<u16>()?;
+
+        let ticket = Self {
+            identity: identity.into_less_safe_slice(),
+            ticket_age_add,
+            lifetime: Duration::from_secs(lifetime as u64),
+            timestamp,
+        };
+
+        Ok((Some(ticket), remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock as _, NoopClock};
+    use bolero::check;
+
+    fn client_hello(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut extension_bytes = vec![];
+        for (extension_type, data) in extensions {
+            extension_bytes.extend_from_slice(&extension_type.to_be_bytes());
+            extension_bytes.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            extension_bytes.extend_from_slice(data);
+        }
+
+        let mut payload = vec![0x03, 0x03];
+        payload.extend_from_slice(&[7; 32]);
+        // legacy_session_id
+        payload.extend_from_slice(&[2, 0xaa, 0xbb]);
+        // cipher_suites
+        payload.extend_from_slice(&[0, 2, 0x13, 0x01]);
+        // legacy_compression_methods
+        payload.extend_from_slice(&[1, 0]);
+        payload.extend_from_slice(&(extension_bytes.len() as u16).to_be_bytes());
+        payload.extend_from_slice(&extension_bytes);
+
+        let mut message = vec![HandshakeType::ClientHello as u8];
+        message.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+        message.extend_from_slice(&payload);
+        message
+    }
+
+    fn pre_shared_key(identity: &[u8], age: u32) -> Vec<u8> {
+        let mut identities = vec![];
+        identities.extend_from_slice(&(identity.len() as u16).to_be_bytes());
+        identities.extend_from_slice(identity);
+        identities.extend_from_slice(&age.to_be_bytes());
+
+        let mut data = vec![];
+        data.extend_from_slice(&(identities.len() as u16).to_be_bytes());
+        data.extend_from_slice(&identities);
+        // binders
+        data.extend_from_slice(&[0, 33, 32]);
+        data.extend_from_slice(&[1; 32]);
+        data
+    }
+
+    #[test]
+    fn early_data_request_test() {
+        let psk = pre_shared_key(b"ticket", 1234);
+        let message = client_hello(&[(0, b"\x00"), (EARLY_DATA, &[]), (PRE_SHARED_KEY, &psk)]);
+
+        assert_eq!(
+            ClientHello::decode(&message),
+            Some(ClientHello {
+                random: &[7; 32],
+                psk_identity: b"ticket",
+                obfuscated_ticket_age: 1234,
+                psk_binder: &[1; 32],
+            })
+        );
+    }
+
+    #[test]
+    fn no_early_data_test() {
+        let psk = pre_shared_key(b"ticket", 1234);
+
+        // resumption without early data
+        let message = client_hello(&[(PRE_SHARED_KEY, &psk)]);
+        assert_eq!(ClientHello::decode(&message), None);
+
+        // early data without a PSK isn't possible
+        let message = client_hello(&[(EARLY_DATA, &[])]);
+        assert_eq!(ClientHello::decode(&message), None);
+
+        // only ClientHello messages are parsed
+        let mut message = client_hello(&[(EARLY_DATA, &[]), (PRE_SHARED_KEY, &psk)]);
+        message[0] = HandshakeType::ServerHello as u8;
+        assert_eq!(ClientHello::decode(&message), None);
+
+        // truncated messages can't be parsed
+        let message = client_hello(&[(EARLY_DATA, &[]), (PRE_SHARED_KEY, &psk)]);
+        assert_eq!(ClientHello::decode(&message[..message.len() - 1]), None);
+    }
+
+    fn new_session_ticket(lifetime: u32, age_add: u32, ticket: &[u8]) -> Vec<u8> {
+        let mut payload = vec![];
+        payload.extend_from_slice(&lifetime.to_be_bytes());
+        payload.extend_from_slice(&age_add.to_be_bytes());
+        // ticket_nonce
+        payload.extend_from_slice(&[1, 0]);
+        payload.extend_from_slice(&(ticket.len() as u16).to_be_bytes());
+        payload.extend_from_slice(ticket);
+        // extensions
+        payload.extend_from_slice(&[0, 4, 0, 42, 0, 0]);
+
+        let mut message = vec![HandshakeType::NewSessionTicket as u8];
+        message.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+        message.extend_from_slice(&payload);
+        message
+    }
+
+    fn session_tickets(messages: &[u8]) -> Vec<(Vec<u8>, u32, Duration)> {
+        let mut tickets = vec![];
+        SessionTicket::decode_all(messages, NoopClock.get_time(), |ticket| {
+            tickets.push((
+                ticket.identity.to_vec(),
+                ticket.ticket_age_add,
+                ticket.lifetime,
+            ));
+        });
+        tickets
+    }
+
+    #[test]
+    fn session_ticket_test() {
+        let mut messages = new_session_ticket(7200, 1234, b"ticket 1");
+        // other handshake messages are skipped
+        messages.extend_from_slice(&client_hello(&[(EARLY_DATA, &[])]));
+        messages.extend_from_slice(&new_session_ticket(60, 5678, b"ticket 2"));
+
+        assert_eq!(
+            session_tickets(&messages),
+            [
+                (b"ticket 1".to_vec(), 1234, Duration::from_secs(7200)),
+                (b"ticket 2".to_vec(), 5678, Duration::from_secs(60)),
+            ]
+        );
+
+        // parsing stops at an incomplete message
+        assert_eq!(
+            session_tickets(&messages[..messages.len() - 1]),
+            [(b"ticket 1".to_vec(), 1234, Duration::from_secs(7200))]
+        );
+    }
+
+    /// make sure the ClientHello and NewSessionTicket parsers don't panic on arbitrary inputs
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn decode_test() {
+        check!().for_each(|bytes| {
+            let _ = ClientHello::decode(bytes);
+            SessionTicket::decode_all(bytes, NoopClock.get_time(), |_| {});
+        });
+    }
+}
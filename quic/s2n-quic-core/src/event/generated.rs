@@ -419,6 +419,15 @@ pub mod api {
         Datagram { len: u16 },
         #[non_exhaustive]
         DcStatelessResetTokens {},
+        #[non_exhaustive]
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
+        #[non_exhaustive]
+        ImmediateAck {},
     }
     impl aggregate::AsVariant for Frame {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -532,6 +541,16 @@ pub mod api {
                 id: 21usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ACK_FREQUENCY\0"),
+                id: 22usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("IMMEDIATE_ACK\0"),
+                id: 23usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -558,6 +577,8 @@ pub mod api {
                 Self::HandshakeDone { .. } => 19usize,
                 Self::Datagram { .. } => 20usize,
                 Self::DcStatelessResetTokens { .. } => 21usize,
+                Self::AckFrequency { .. } => 22usize,
+                Self::ImmediateAck { .. } => 23usize,
             }
         }
     }
@@ -1546,6 +1567,47 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The progress of a connection migration requested by the application"]
+    pub enum LocalMigrationStatus {
+        #[non_exhaustive]
+        #[doc = " The new path is being validated"]
+        Started {},
+        #[non_exhaustive]
+        #[doc = " The new path was validated and is now the active path"]
+        Completed {},
+        #[non_exhaustive]
+        #[doc = " The new path failed validation and the connection remains on the previous path"]
+        Abandoned {},
+    }
+    impl aggregate::AsVariant for LocalMigrationStatus {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("STARTED\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("COMPLETED\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ABANDONED\0"),
+                id: 2usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::Started { .. } => 0usize,
+                Self::Completed { .. } => 1usize,
+                Self::Abandoned { .. } => 2usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The reason the slow start congestion controller state has been exited"]
     pub enum SlowStartExitCause {
         #[non_exhaustive]
@@ -1620,6 +1682,9 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " MTU probes larger than the current MTU were not acknowledged"]
         LargerProbesLost {},
+        #[non_exhaustive]
+        #[doc = " An ICMP message reported that packets exceeded the MTU of the path"]
+        PacketTooBig {},
     }
     impl aggregate::AsVariant for MtuUpdatedCause {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -1653,6 +1718,11 @@ pub mod api {
                 id: 5usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PACKET_TOO_BIG\0"),
+                id: 6usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -1663,6 +1733,7 @@ pub mod api {
                 Self::InitialMtuPacketLost { .. } => 3usize,
                 Self::InitialMtuPacketAcknowledged { .. } => 4usize,
                 Self::LargerProbesLost { .. } => 5usize,
+                Self::PacketTooBig { .. } => 6usize,
             }
         }
     }
@@ -1737,6 +1808,38 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The server's decision on the early data sent by a client"]
+    pub enum ZeroRttStatus {
+        #[non_exhaustive]
+        #[doc = " The server processed the 0-RTT packets"]
+        Accepted {},
+        #[non_exhaustive]
+        #[doc = " The server discarded the 0-RTT packets and their data was retransmitted in 1-RTT packets"]
+        Rejected {},
+    }
+    impl aggregate::AsVariant for ZeroRttStatus {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ACCEPTED\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("REJECTED\0"),
+                id: 1usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::Accepted { .. } => 0usize,
+                Self::Rejected { .. } => 1usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub enum DcState {
         #[non_exhaustive]
         VersionNegotiated { version: u32 },
@@ -2232,12 +2335,18 @@ pub mod api {
     #[doc = " Connection started"]
     pub struct ConnectionStarted<'a> {
         pub path: Path<'a>,
+        #[doc = " The destination connection ID of the client's first Initial packet"]
+        pub original_destination_connection_id: ConnectionId<'a>,
     }
     #[cfg(any(test, feature = "testing"))]
     impl<'a> crate::event::snapshot::Fmt for ConnectionStarted<'a> {
         fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
             let mut fmt = fmt.debug_struct("ConnectionStarted");
             fmt.field("path", &self.path);
+            fmt.field(
+                "original_destination_connection_id",
+                &self.original_destination_connection_id,
+            );
             fmt.finish()
         }
     }
@@ -2505,6 +2614,25 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A connection migration requested by the application made progress"]
+    pub struct LocalMigrationUpdated<'a> {
+        pub status: LocalMigrationStatus,
+        pub path: Path<'a>,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl<'a> crate::event::snapshot::Fmt for LocalMigrationUpdated<'a> {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("LocalMigrationUpdated");
+            fmt.field("status", &self.status);
+            fmt.field("path", &self.path);
+            fmt.finish()
+        }
+    }
+    impl<'a> Event for LocalMigrationUpdated<'a> {
+        const NAME: &'static str = "connectivity:local_migration_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -2730,6 +2858,23 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The server accepted or rejected the early data sent by the client"]
+    pub struct ZeroRttStatusUpdated {
+        pub status: ZeroRttStatus,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for ZeroRttStatusUpdated {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("ZeroRttStatusUpdated");
+            fmt.field("status", &self.status);
+            fmt.finish()
+        }
+    }
+    impl Event for ZeroRttStatusUpdated {
+        const NAME: &'static str = "transport:zero_rtt_status_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
@@ -3074,6 +3219,26 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " Emitted when the platform reports an ICMP error for a previously transmitted datagram"]
+    pub struct PlatformIcmpError<'a> {
+        #[doc = " The address the datagram that triggered the error was sent to"]
+        pub remote_address: SocketAddress<'a>,
+        pub error: IcmpError,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl<'a> crate::event::snapshot::Fmt for PlatformIcmpError<'a> {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("PlatformIcmpError");
+            fmt.field("remote_address", &self.remote_address);
+            fmt.field("error", &self.error);
+            fmt.finish()
+        }
+    }
+    impl<'a> Event for PlatformIcmpError<'a> {
+        const NAME: &'static str = "platform:icmp_error";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub enum PlatformFeatureConfiguration {
         #[non_exhaustive]
         #[doc = " Emitted when segment offload was configured"]
@@ -3144,6 +3309,40 @@ pub mod api {
             }
         }
     }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    pub enum IcmpError {
+        #[non_exhaustive]
+        #[doc = " The datagram exceeded the MTU of a link on the path"]
+        PacketTooBig {
+            #[doc = " The MTU of the link, including the IP and UDP headers"]
+            mtu: u16,
+        },
+        #[non_exhaustive]
+        #[doc = " The peer host is not listening on the destination port"]
+        PortUnreachable {},
+    }
+    impl aggregate::AsVariant for IcmpError {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PACKET_TOO_BIG\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PORT_UNREACHABLE\0"),
+                id: 1usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::PacketTooBig { .. } => 0usize,
+                Self::PortUnreachable { .. } => 1usize,
+            }
+        }
+    }
     impl<'a> IntoEvent<builder::PreferredAddress<'a>>
         for &'a crate::transport::parameters::PreferredAddress
     {
@@ -3519,6 +3718,23 @@ pub mod api {
             builder::Frame::DcStatelessResetTokens {}
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::AckFrequency {
+                sequence_number: self.sequence_number.as_u64(),
+                ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+                request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+                reordering_threshold: self.reordering_threshold.as_u64(),
+            }
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ImmediateAck {}
+        }
+    }
     #[cfg(feature = "alloc")]
     impl<'a> ConnectionCloseFrame<'a> {
         #[doc = " Converts the reason to a UTF-8 `str`, including invalid characters"]
@@ -3980,8 +4196,11 @@ pub mod tracing {
             event: &api::ConnectionStarted,
         ) {
             let id = context.id();
-            let api::ConnectionStarted { path } = event;
-            tracing :: event ! (target : "connection_started" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) });
+            let api::ConnectionStarted {
+                path,
+                original_destination_connection_id,
+            } = event;
+            tracing :: event ! (target : "connection_started" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) , original_destination_connection_id = tracing :: field :: debug (original_destination_connection_id) });
         }
         #[inline]
         fn on_duplicate_packet(
@@ -4153,6 +4372,17 @@ pub mod tracing {
             tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , { path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data) });
         }
         #[inline]
+        fn on_local_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::LocalMigrationUpdated,
+        ) {
+            let id = context.id();
+            let api::LocalMigrationUpdated { status, path } = event;
+            tracing :: event ! (target : "local_migration_updated" , parent : id , tracing :: Level :: DEBUG , { status = tracing :: field :: debug (status) , path = tracing :: field :: debug (path) });
+        }
+        #[inline]
         fn on_tls_client_hello(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4302,6 +4532,17 @@ pub mod tracing {
             tracing :: event ! (target : "dc_path_created" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) });
         }
         #[inline]
+        fn on_zero_rtt_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ZeroRttStatusUpdated,
+        ) {
+            let id = context.id();
+            let api::ZeroRttStatusUpdated { status } = event;
+            tracing :: event ! (target : "zero_rtt_status_updated" , parent : id , tracing :: Level :: DEBUG , { status = tracing :: field :: debug (status) });
+        }
+        #[inline]
         fn on_connection_closed(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4483,6 +4724,19 @@ pub mod tracing {
             let api::PlatformEventLoopStarted { local_address } = event;
             tracing :: event ! (target : "platform_event_loop_started" , parent : parent , tracing :: Level :: DEBUG , { local_address = tracing :: field :: debug (local_address) });
         }
+        #[inline]
+        fn on_platform_icmp_error(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformIcmpError,
+        ) {
+            let parent = self.parent(meta);
+            let api::PlatformIcmpError {
+                remote_address,
+                error,
+            } = event;
+            tracing :: event ! (target : "platform_icmp_error" , parent : parent , tracing :: Level :: DEBUG , { remote_address = tracing :: field :: debug (remote_address) , error = tracing :: field :: debug (error) });
+        }
     }
 }
 pub mod builder {
@@ -4899,6 +5153,13 @@ pub mod builder {
             len: u16,
         },
         DcStatelessResetTokens,
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
+        ImmediateAck,
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -4994,6 +5255,18 @@ pub mod builder {
                     len: len.into_event(),
                 },
                 Self::DcStatelessResetTokens => DcStatelessResetTokens {},
+                Self::AckFrequency {
+                    sequence_number,
+                    ack_eliciting_threshold,
+                    request_max_ack_delay,
+                    reordering_threshold,
+                } => AckFrequency {
+                    sequence_number: sequence_number.into_event(),
+                    ack_eliciting_threshold: ack_eliciting_threshold.into_event(),
+                    request_max_ack_delay: request_max_ack_delay.into_event(),
+                    reordering_threshold: reordering_threshold.into_event(),
+                },
+                Self::ImmediateAck => ImmediateAck {},
             }
         }
     }
@@ -5537,6 +5810,27 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The progress of a connection migration requested by the application"]
+    pub enum LocalMigrationStatus {
+        #[doc = " The new path is being validated"]
+        Started,
+        #[doc = " The new path was validated and is now the active path"]
+        Completed,
+        #[doc = " The new path failed validation and the connection remains on the previous path"]
+        Abandoned,
+    }
+    impl IntoEvent<api::LocalMigrationStatus> for LocalMigrationStatus {
+        #[inline]
+        fn into_event(self) -> api::LocalMigrationStatus {
+            use api::LocalMigrationStatus::*;
+            match self {
+                Self::Started => Started {},
+                Self::Completed => Completed {},
+                Self::Abandoned => Abandoned {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The reason the slow start congestion controller state has been exited"]
     pub enum SlowStartExitCause {
         #[doc = " A packet was determined lost"]
@@ -5579,6 +5873,8 @@ pub mod builder {
         InitialMtuPacketAcknowledged,
         #[doc = " MTU probes larger than the current MTU were not acknowledged"]
         LargerProbesLost,
+        #[doc = " An ICMP message reported that packets exceeded the MTU of the path"]
+        PacketTooBig,
     }
     impl IntoEvent<api::MtuUpdatedCause> for MtuUpdatedCause {
         #[inline]
@@ -5591,6 +5887,7 @@ pub mod builder {
                 Self::InitialMtuPacketLost => InitialMtuPacketLost {},
                 Self::InitialMtuPacketAcknowledged => InitialMtuPacketAcknowledged {},
                 Self::LargerProbesLost => LargerProbesLost {},
+                Self::PacketTooBig => PacketTooBig {},
             }
         }
     }
@@ -5620,6 +5917,24 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The server's decision on the early data sent by a client"]
+    pub enum ZeroRttStatus {
+        #[doc = " The server processed the 0-RTT packets"]
+        Accepted,
+        #[doc = " The server discarded the 0-RTT packets and their data was retransmitted in 1-RTT packets"]
+        Rejected,
+    }
+    impl IntoEvent<api::ZeroRttStatus> for ZeroRttStatus {
+        #[inline]
+        fn into_event(self) -> api::ZeroRttStatus {
+            use api::ZeroRttStatus::*;
+            match self {
+                Self::Accepted => Accepted {},
+                Self::Rejected => Rejected {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum DcState {
         VersionNegotiated { version: u32 },
         NoVersionNegotiated,
@@ -6078,13 +6393,19 @@ pub mod builder {
     #[doc = " Connection started"]
     pub struct ConnectionStarted<'a> {
         pub path: Path<'a>,
+        #[doc = " The destination connection ID of the client's first Initial packet"]
+        pub original_destination_connection_id: ConnectionId<'a>,
     }
     impl<'a> IntoEvent<api::ConnectionStarted<'a>> for ConnectionStarted<'a> {
         #[inline]
         fn into_event(self) -> api::ConnectionStarted<'a> {
-            let ConnectionStarted { path } = self;
+            let ConnectionStarted {
+                path,
+                original_destination_connection_id,
+            } = self;
             api::ConnectionStarted {
                 path: path.into_event(),
+                original_destination_connection_id: original_destination_connection_id.into_event(),
             }
         }
     }
@@ -6337,6 +6658,22 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A connection migration requested by the application made progress"]
+    pub struct LocalMigrationUpdated<'a> {
+        pub status: LocalMigrationStatus,
+        pub path: Path<'a>,
+    }
+    impl<'a> IntoEvent<api::LocalMigrationUpdated<'a>> for LocalMigrationUpdated<'a> {
+        #[inline]
+        fn into_event(self) -> api::LocalMigrationUpdated<'a> {
+            let LocalMigrationUpdated { status, path } = self;
+            api::LocalMigrationUpdated {
+                status: status.into_event(),
+                path: path.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -6543,6 +6880,20 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The server accepted or rejected the early data sent by the client"]
+    pub struct ZeroRttStatusUpdated {
+        pub status: ZeroRttStatus,
+    }
+    impl IntoEvent<api::ZeroRttStatusUpdated> for ZeroRttStatusUpdated {
+        #[inline]
+        fn into_event(self) -> api::ZeroRttStatusUpdated {
+            let ZeroRttStatusUpdated { status } = self;
+            api::ZeroRttStatusUpdated {
+                status: status.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
@@ -6865,6 +7216,25 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub struct PlatformIcmpError<'a> {
+        #[doc = " The address the datagram that triggered the error was sent to"]
+        pub remote_address: SocketAddress<'a>,
+        pub error: IcmpError,
+    }
+    impl<'a> IntoEvent<api::PlatformIcmpError<'a>> for PlatformIcmpError<'a> {
+        #[inline]
+        fn into_event(self) -> api::PlatformIcmpError<'a> {
+            let PlatformIcmpError {
+                remote_address,
+                error,
+            } = self;
+            api::PlatformIcmpError {
+                remote_address: remote_address.into_event(),
+                error: error.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum PlatformFeatureConfiguration {
         #[doc = " Emitted when segment offload was configured"]
         Gso {
@@ -6910,6 +7280,28 @@ pub mod builder {
             }
         }
     }
+    #[derive(Clone, Debug)]
+    pub enum IcmpError {
+        #[doc = " The datagram exceeded the MTU of a link on the path"]
+        PacketTooBig {
+            #[doc = " The MTU of the link, including the IP and UDP headers"]
+            mtu: u16,
+        },
+        #[doc = " The peer host is not listening on the destination port"]
+        PortUnreachable,
+    }
+    impl IntoEvent<api::IcmpError> for IcmpError {
+        #[inline]
+        fn into_event(self) -> api::IcmpError {
+            use api::IcmpError::*;
+            match self {
+                Self::PacketTooBig { mtu } => PacketTooBig {
+                    mtu: mtu.into_event(),
+                },
+                Self::PortUnreachable => PortUnreachable {},
+            }
+        }
+    }
 }
 pub mod supervisor {
     #![doc = r" This module contains the `supervisor::Outcome` and `supervisor::Context` for use"]
@@ -7475,6 +7867,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `LocalMigrationUpdated` event is triggered"]
+        #[inline]
+        fn on_local_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalMigrationUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `TlsClientHello` event is triggered"]
         #[inline]
         fn on_tls_client_hello(
@@ -7619,6 +8023,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ZeroRttStatusUpdated` event is triggered"]
+        #[inline]
+        fn on_zero_rtt_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ZeroRttStatusUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `ConnectionClosed` event is triggered"]
         #[inline]
         fn on_connection_closed(
@@ -7775,6 +8191,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PlatformIcmpError` event is triggered"]
+        #[inline]
+        fn on_platform_icmp_error(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformIcmpError,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = r" Called for each event that relates to the endpoint and all connections"]
         #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
@@ -8225,6 +8651,16 @@ mod traits {
             (self.1).on_path_challenge_updated(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_local_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalMigrationUpdated,
+        ) {
+            (self.0).on_local_migration_updated(&mut context.0, meta, event);
+            (self.1).on_local_migration_updated(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_tls_client_hello(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -8345,6 +8781,16 @@ mod traits {
             (self.1).on_dc_path_created(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_zero_rtt_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ZeroRttStatusUpdated,
+        ) {
+            (self.0).on_zero_rtt_status_updated(&mut context.0, meta, event);
+            (self.1).on_zero_rtt_status_updated(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_connection_closed(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -8483,6 +8929,15 @@ mod traits {
             (self.1).on_platform_event_loop_started(meta, event);
         }
         #[inline]
+        fn on_platform_icmp_error(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformIcmpError,
+        ) {
+            (self.0).on_platform_icmp_error(meta, event);
+            (self.1).on_platform_icmp_error(meta, event);
+        }
+        #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
             self.0.on_event(meta, event);
             self.1.on_event(meta, event);
@@ -8557,6 +9012,8 @@ mod traits {
         fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep);
         #[doc = "Publishes a `PlatformEventLoopStarted` event to the publisher's subscriber"]
         fn on_platform_event_loop_started(&mut self, event: builder::PlatformEventLoopStarted);
+        #[doc = "Publishes a `PlatformIcmpError` event to the publisher's subscriber"]
+        fn on_platform_icmp_error(&mut self, event: builder::PlatformIcmpError);
         #[doc = r" Returns the QUIC version, if any"]
         fn quic_version(&self) -> Option<u32>;
     }
@@ -8701,6 +9158,12 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_platform_icmp_error(&mut self, event: builder::PlatformIcmpError) {
+            let event = event.into_event();
+            self.subscriber.on_platform_icmp_error(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> Option<u32> {
             self.quic_version
         }
@@ -8785,6 +9248,8 @@ mod traits {
         fn on_tls_handshake_failed(&mut self, event: builder::TlsHandshakeFailed);
         #[doc = "Publishes a `PathChallengeUpdated` event to the publisher's subscriber"]
         fn on_path_challenge_updated(&mut self, event: builder::PathChallengeUpdated);
+        #[doc = "Publishes a `LocalMigrationUpdated` event to the publisher's subscriber"]
+        fn on_local_migration_updated(&mut self, event: builder::LocalMigrationUpdated);
         #[doc = "Publishes a `TlsClientHello` event to the publisher's subscriber"]
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello);
         #[doc = "Publishes a `TlsServerHello` event to the publisher's subscriber"]
@@ -8809,6 +9274,8 @@ mod traits {
         fn on_dc_state_changed(&mut self, event: builder::DcStateChanged);
         #[doc = "Publishes a `DcPathCreated` event to the publisher's subscriber"]
         fn on_dc_path_created(&mut self, event: builder::DcPathCreated);
+        #[doc = "Publishes a `ZeroRttStatusUpdated` event to the publisher's subscriber"]
+        fn on_zero_rtt_status_updated(&mut self, event: builder::ZeroRttStatusUpdated);
         #[doc = "Publishes a `ConnectionClosed` event to the publisher's subscriber"]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
@@ -9179,6 +9646,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_local_migration_updated(&mut self, event: builder::LocalMigrationUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_local_migration_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
             let event = event.into_event();
             self.subscriber
@@ -9287,6 +9763,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_zero_rtt_status_updated(&mut self, event: builder::ZeroRttStatusUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_zero_rtt_status_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
             let event = event.into_event();
             self.subscriber
@@ -9330,6 +9815,7 @@ pub mod testing {
             pub platform_event_loop_wakeup: u64,
             pub platform_event_loop_sleep: u64,
             pub platform_event_loop_started: u64,
+            pub platform_icmp_error: u64,
         }
         impl Drop for Subscriber {
             fn drop(&mut self) {
@@ -9377,6 +9863,7 @@ pub mod testing {
                     platform_event_loop_wakeup: 0,
                     platform_event_loop_sleep: 0,
                     platform_event_loop_started: 0,
+                    platform_icmp_error: 0,
                 }
             }
         }
@@ -9556,6 +10043,17 @@ pub mod testing {
                 let out = format!("{meta:?} {event:?}");
                 self.output.push(out);
             }
+            fn on_platform_icmp_error(
+                &mut self,
+                meta: &api::EndpointMeta,
+                event: &api::PlatformIcmpError,
+            ) {
+                self.platform_icmp_error += 1;
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
         }
     }
     #[derive(Debug)]
@@ -9597,6 +10095,7 @@ pub mod testing {
         pub tls_exporter_ready: u64,
         pub tls_handshake_failed: u64,
         pub path_challenge_updated: u64,
+        pub local_migration_updated: u64,
         pub tls_client_hello: u64,
         pub tls_server_hello: u64,
         pub rx_stream_progress: u64,
@@ -9609,6 +10108,7 @@ pub mod testing {
         pub bbr_state_changed: u64,
         pub dc_state_changed: u64,
         pub dc_path_created: u64,
+        pub zero_rtt_status_updated: u64,
         pub connection_closed: u64,
         pub version_information: u64,
         pub endpoint_packet_sent: u64,
@@ -9626,6 +10126,7 @@ pub mod testing {
         pub platform_event_loop_wakeup: u64,
         pub platform_event_loop_sleep: u64,
         pub platform_event_loop_started: u64,
+        pub platform_icmp_error: u64,
     }
     impl Drop for Subscriber {
         fn drop(&mut self) {
@@ -9692,6 +10193,7 @@ pub mod testing {
                 tls_exporter_ready: 0,
                 tls_handshake_failed: 0,
                 path_challenge_updated: 0,
+                local_migration_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
                 rx_stream_progress: 0,
@@ -9704,6 +10206,7 @@ pub mod testing {
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
                 dc_path_created: 0,
+                zero_rtt_status_updated: 0,
                 connection_closed: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
@@ -9721,6 +10224,7 @@ pub mod testing {
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
                 platform_event_loop_started: 0,
+                platform_icmp_error: 0,
             }
         }
     }
@@ -10223,6 +10727,20 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_local_migration_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalMigrationUpdated,
+        ) {
+            self.local_migration_updated += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_tls_client_hello(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -10391,6 +10909,20 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_zero_rtt_status_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ZeroRttStatusUpdated,
+        ) {
+            self.zero_rtt_status_updated += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_connection_closed(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -10565,6 +11097,17 @@ pub mod testing {
             let out = format!("{meta:?} {event:?}");
             self.output.push(out);
         }
+        fn on_platform_icmp_error(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformIcmpError,
+        ) {
+            self.platform_icmp_error += 1;
+            let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+            let event = crate::event::snapshot::Fmt::to_snapshot(event);
+            let out = format!("{meta:?} {event:?}");
+            self.output.push(out);
+        }
     }
     #[derive(Debug)]
     pub struct Publisher {
@@ -10605,6 +11148,7 @@ pub mod testing {
         pub tls_exporter_ready: u64,
         pub tls_handshake_failed: u64,
         pub path_challenge_updated: u64,
+        pub local_migration_updated: u64,
         pub tls_client_hello: u64,
         pub tls_server_hello: u64,
         pub rx_stream_progress: u64,
@@ -10617,6 +11161,7 @@ pub mod testing {
         pub bbr_state_changed: u64,
         pub dc_state_changed: u64,
         pub dc_path_created: u64,
+        pub zero_rtt_status_updated: u64,
         pub connection_closed: u64,
         pub version_information: u64,
         pub endpoint_packet_sent: u64,
@@ -10634,6 +11179,7 @@ pub mod testing {
         pub platform_event_loop_wakeup: u64,
         pub platform_event_loop_sleep: u64,
         pub platform_event_loop_started: u64,
+        pub platform_icmp_error: u64,
     }
     impl Publisher {
         #[doc = r" Creates a publisher with snapshot assertions enabled"]
@@ -10690,6 +11236,7 @@ pub mod testing {
                 tls_exporter_ready: 0,
                 tls_handshake_failed: 0,
                 path_challenge_updated: 0,
+                local_migration_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
                 rx_stream_progress: 0,
@@ -10702,6 +11249,7 @@ pub mod testing {
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
                 dc_path_created: 0,
+                zero_rtt_status_updated: 0,
                 connection_closed: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
@@ -10719,6 +11267,7 @@ pub mod testing {
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
                 platform_event_loop_started: 0,
+                platform_icmp_error: 0,
             }
         }
     }
@@ -10841,6 +11390,13 @@ pub mod testing {
             let out = format!("{event:?}");
             self.output.push(out);
         }
+        fn on_platform_icmp_error(&mut self, event: builder::PlatformIcmpError) {
+            self.platform_icmp_error += 1;
+            let event = event.into_event();
+            let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+            let out = format!("{event:?}");
+            self.output.push(out);
+        }
         fn quic_version(&self) -> Option<u32> {
             Some(1)
         }
@@ -11174,6 +11730,15 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_local_migration_updated(&mut self, event: builder::LocalMigrationUpdated) {
+            self.local_migration_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
             self.tls_client_hello += 1;
             let event = event.into_event();
@@ -11282,6 +11847,15 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_zero_rtt_status_updated(&mut self, event: builder::ZeroRttStatusUpdated) {
+            self.zero_rtt_status_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
             self.connection_closed += 1;
             let event = event.into_event();
@@ -61,6 +61,7 @@ pub struct Context<R: Recorder> {
     tls_exporter_ready: u64,
     tls_handshake_failed: u64,
     path_challenge_updated: u64,
+    local_migration_updated: u64,
     tls_client_hello: u64,
     tls_server_hello: u64,
     rx_stream_progress: u64,
@@ -73,6 +74,7 @@ pub struct Context<R: Recorder> {
     bbr_state_changed: u64,
     dc_state_changed: u64,
     dc_path_created: u64,
+    zero_rtt_status_updated: u64,
     connection_closed: u64,
 }
 impl<R: Recorder> Context<R> {
@@ -130,6 +132,7 @@ where
             tls_exporter_ready: 0,
             tls_handshake_failed: 0,
             path_challenge_updated: 0,
+            local_migration_updated: 0,
             tls_client_hello: 0,
             tls_server_hello: 0,
             rx_stream_progress: 0,
@@ -142,6 +145,7 @@ where
             bbr_state_changed: 0,
             dc_state_changed: 0,
             dc_path_created: 0,
+            zero_rtt_status_updated: 0,
             connection_closed: 0,
         }
     }
@@ -535,6 +539,17 @@ where
             .on_path_challenge_updated(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_local_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::LocalMigrationUpdated,
+    ) {
+        context.local_migration_updated += 1;
+        self.subscriber
+            .on_local_migration_updated(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_tls_client_hello(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -667,6 +682,17 @@ where
             .on_dc_path_created(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_zero_rtt_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ZeroRttStatusUpdated,
+    ) {
+        context.zero_rtt_status_updated += 1;
+        self.subscriber
+            .on_zero_rtt_status_updated(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_connection_closed(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -762,6 +788,8 @@ impl<R: Recorder> Drop for Context<R> {
             .increment_counter("tls_handshake_failed", self.tls_handshake_failed as _);
         self.recorder
             .increment_counter("path_challenge_updated", self.path_challenge_updated as _);
+        self.recorder
+            .increment_counter("local_migration_updated", self.local_migration_updated as _);
         self.recorder
             .increment_counter("tls_client_hello", self.tls_client_hello as _);
         self.recorder
@@ -788,6 +816,8 @@ impl<R: Recorder> Drop for Context<R> {
             .increment_counter("dc_state_changed", self.dc_state_changed as _);
         self.recorder
             .increment_counter("dc_path_created", self.dc_path_created as _);
+        self.recorder
+            .increment_counter("zero_rtt_status_updated", self.zero_rtt_status_updated as _);
         self.recorder
             .increment_counter("connection_closed", self.connection_closed as _);
     }
@@ -13,7 +13,7 @@ use crate::event::{
     },
 };
 use alloc::{boxed::Box, vec::Vec};
-static INFO: &[Info; 166usize] = &[
+static INFO: &[Info; 171usize] = &[
     info::Builder {
         id: 0usize,
         name: Str::new("application_protocol_information\0"),
@@ -520,496 +520,526 @@ static INFO: &[Info; 166usize] = &[
     .build(),
     info::Builder {
         id: 84usize,
-        name: Str::new("tls_client_hello\0"),
+        name: Str::new("local_migration_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 85usize,
+        name: Str::new("local_migration_updated.status\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 86usize,
+        name: Str::new("tls_client_hello\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 87usize,
         name: Str::new("tls_client_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 86usize,
+        id: 88usize,
         name: Str::new("tls_server_hello\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 87usize,
+        id: 89usize,
         name: Str::new("tls_server_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 88usize,
+        id: 90usize,
         name: Str::new("rx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 89usize,
+        id: 91usize,
         name: Str::new("rx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 90usize,
+        id: 92usize,
         name: Str::new("rx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 91usize,
+        id: 93usize,
         name: Str::new("tx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 92usize,
+        id: 94usize,
         name: Str::new("tx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 93usize,
+        id: 95usize,
         name: Str::new("tx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 94usize,
+        id: 96usize,
         name: Str::new("keep_alive_timer_expired\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 95usize,
+        id: 97usize,
         name: Str::new("mtu_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 96usize,
+        id: 98usize,
         name: Str::new("mtu_updated.mtu\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 97usize,
+        id: 99usize,
         name: Str::new("mtu_updated.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 98usize,
+        id: 100usize,
         name: Str::new("mtu_updated.search_complete\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 99usize,
+        id: 101usize,
         name: Str::new("slow_start_exited\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 100usize,
+        id: 102usize,
         name: Str::new("slow_start_exited.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 101usize,
+        id: 103usize,
         name: Str::new("slow_start_exited.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 102usize,
+        id: 104usize,
         name: Str::new("slow_start_exited.congestion_window\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 103usize,
+        id: 105usize,
         name: Str::new("delivery_rate_sampled\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 104usize,
+        id: 106usize,
         name: Str::new("pacing_rate_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 105usize,
+        id: 107usize,
         name: Str::new("pacing_rate_updated.bytes_per_second\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 106usize,
+        id: 108usize,
         name: Str::new("pacing_rate_updated.burst_size\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 107usize,
+        id: 109usize,
         name: Str::new("pacing_rate_updated.pacing_gain\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 108usize,
+        id: 110usize,
         name: Str::new("bbr_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 109usize,
+        id: 111usize,
         name: Str::new("bbr_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 110usize,
+        id: 112usize,
         name: Str::new("dc_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 111usize,
+        id: 113usize,
         name: Str::new("dc_state_changed.version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 112usize,
+        id: 114usize,
         name: Str::new("dc_state_changed.no_version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 113usize,
+        id: 115usize,
         name: Str::new("dc_state_changed.path_secrets.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 114usize,
+        id: 116usize,
         name: Str::new("dc_state_changed.complete.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 115usize,
+        id: 117usize,
         name: Str::new("dc_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 116usize,
+        id: 118usize,
         name: Str::new("dc_path_created\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 117usize,
+        id: 119usize,
+        name: Str::new("zero_rtt_status_updated\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 120usize,
+        name: Str::new("zero_rtt_status_updated.status\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 121usize,
         name: Str::new("connection_closed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 118usize,
+        id: 122usize,
         name: Str::new("connection_closed.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 119usize,
+        id: 123usize,
         name: Str::new("connection_closed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 120usize,
+        id: 124usize,
         name: Str::new("version_information\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 121usize,
+        id: 125usize,
         name: Str::new("endpoint_packet_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 122usize,
+        id: 126usize,
         name: Str::new("endpoint_packet_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 123usize,
+        id: 127usize,
         name: Str::new("endpoint_datagram_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 124usize,
+        id: 128usize,
         name: Str::new("endpoint_datagram_sent.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 125usize,
+        id: 129usize,
         name: Str::new("endpoint_datagram_sent.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 126usize,
+        id: 130usize,
         name: Str::new("endpoint_datagram_sent.gso_offset\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 127usize,
+        id: 131usize,
         name: Str::new("endpoint_datagram_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 128usize,
+        id: 132usize,
         name: Str::new("endpoint_datagram_received.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 129usize,
+        id: 133usize,
         name: Str::new("endpoint_datagram_received.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 130usize,
+        id: 134usize,
         name: Str::new("endpoint_datagram_dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 131usize,
+        id: 135usize,
         name: Str::new("endpoint_datagram_dropped.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 132usize,
+        id: 136usize,
         name: Str::new("endpoint_datagram_dropped.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 133usize,
+        id: 137usize,
         name: Str::new("endpoint_datagram_dropped.reason\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 134usize,
+        id: 138usize,
         name: Str::new("endpoint_connection_attempt_failed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 135usize,
+        id: 139usize,
         name: Str::new("endpoint_connection_attempt_failed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 136usize,
+        id: 140usize,
         name: Str::new("endpoint_connection_attempt_deduplicated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 137usize,
+        id: 141usize,
         name: Str::new("platform_tx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 138usize,
+        id: 142usize,
         name: Str::new("platform_tx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 139usize,
+        id: 143usize,
         name: Str::new("platform_tx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 140usize,
+        id: 144usize,
         name: Str::new("platform_tx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 141usize,
+        id: 145usize,
         name: Str::new("platform_tx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 142usize,
+        id: 146usize,
         name: Str::new("platform_tx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 143usize,
+        id: 147usize,
         name: Str::new("platform_tx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 144usize,
+        id: 148usize,
         name: Str::new("platform_tx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 145usize,
+        id: 149usize,
         name: Str::new("platform_tx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 146usize,
+        id: 150usize,
         name: Str::new("platform_tx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 147usize,
+        id: 151usize,
         name: Str::new("platform_tx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 148usize,
+        id: 152usize,
         name: Str::new("platform_tx_error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 149usize,
+        id: 153usize,
         name: Str::new("platform_rx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 150usize,
+        id: 154usize,
         name: Str::new("platform_rx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 151usize,
+        id: 155usize,
         name: Str::new("platform_rx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 152usize,
+        id: 156usize,
         name: Str::new("platform_rx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 153usize,
+        id: 157usize,
         name: Str::new("platform_rx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 154usize,
+        id: 158usize,
         name: Str::new("platform_rx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 155usize,
+        id: 159usize,
         name: Str::new("platform_rx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 156usize,
+        id: 160usize,
         name: Str::new("platform_rx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 157usize,
+        id: 161usize,
         name: Str::new("platform_rx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 158usize,
+        id: 162usize,
         name: Str::new("platform_rx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 159usize,
+        id: 163usize,
         name: Str::new("platform_rx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 160usize,
+        id: 164usize,
         name: Str::new("platform_rx_error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 161usize,
+        id: 165usize,
         name: Str::new("platform_feature_configured\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 162usize,
+        id: 166usize,
         name: Str::new("platform_event_loop_wakeup\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 163usize,
+        id: 167usize,
         name: Str::new("platform_event_loop_sleep\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 164usize,
+        id: 168usize,
         name: Str::new("platform_event_loop_sleep.processing_duration\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 165usize,
+        id: 169usize,
         name: Str::new("platform_event_loop_started\0"),
         units: Units::None,
     }
     .build(),
+    info::Builder {
+        id: 170usize,
+        name: Str::new("platform_icmp_error\0"),
+        units: Units::None,
+    }
+    .build(),
 ];
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -1018,13 +1048,13 @@ pub struct ConnectionContext {
 }
 pub struct Subscriber<R: Registry> {
     #[allow(dead_code)]
-    counters: Box<[R::Counter; 80usize]>,
+    counters: Box<[R::Counter; 83usize]>,
     #[allow(dead_code)]
     bool_counters: Box<[R::BoolCounter; 3usize]>,
     #[allow(dead_code)]
     nominal_counters: Box<[R::NominalCounter]>,
     #[allow(dead_code)]
-    nominal_counter_offsets: Box<[usize; 29usize]>,
+    nominal_counter_offsets: Box<[usize; 31usize]>,
     #[allow(dead_code)]
     measures: Box<[R::Measure; 38usize]>,
     #[allow(dead_code)]
@@ -1053,10 +1083,10 @@ impl<R: Registry> Subscriber<R> {
     #[allow(unused_mut)]
     #[inline]
     pub fn new(registry: R) -> Self {
-        let mut counters = Vec::with_capacity(80usize);
+        let mut counters = Vec::with_capacity(83usize);
         let mut bool_counters = Vec::with_capacity(3usize);
-        let mut nominal_counters = Vec::with_capacity(29usize);
-        let mut nominal_counter_offsets = Vec::with_capacity(29usize);
+        let mut nominal_counters = Vec::with_capacity(31usize);
+        let mut nominal_counter_offsets = Vec::with_capacity(31usize);
         let mut measures = Vec::with_capacity(38usize);
         let mut gauges = Vec::with_capacity(0usize);
         let mut timers = Vec::with_capacity(15usize);
@@ -1104,47 +1134,50 @@ impl<R: Registry> Subscriber<R> {
         counters.push(registry.register_counter(&INFO[84usize]));
         counters.push(registry.register_counter(&INFO[86usize]));
         counters.push(registry.register_counter(&INFO[88usize]));
-        counters.push(registry.register_counter(&INFO[89usize]));
+        counters.push(registry.register_counter(&INFO[90usize]));
         counters.push(registry.register_counter(&INFO[91usize]));
-        counters.push(registry.register_counter(&INFO[92usize]));
+        counters.push(registry.register_counter(&INFO[93usize]));
         counters.push(registry.register_counter(&INFO[94usize]));
-        counters.push(registry.register_counter(&INFO[95usize]));
-        counters.push(registry.register_counter(&INFO[99usize]));
-        counters.push(registry.register_counter(&INFO[103usize]));
-        counters.push(registry.register_counter(&INFO[104usize]));
-        counters.push(registry.register_counter(&INFO[108usize]));
+        counters.push(registry.register_counter(&INFO[96usize]));
+        counters.push(registry.register_counter(&INFO[97usize]));
+        counters.push(registry.register_counter(&INFO[101usize]));
+        counters.push(registry.register_counter(&INFO[105usize]));
+        counters.push(registry.register_counter(&INFO[106usize]));
         counters.push(registry.register_counter(&INFO[110usize]));
-        counters.push(registry.register_counter(&INFO[116usize]));
-        counters.push(registry.register_counter(&INFO[117usize]));
-        counters.push(registry.register_counter(&INFO[120usize]));
+        counters.push(registry.register_counter(&INFO[112usize]));
+        counters.push(registry.register_counter(&INFO[118usize]));
+        counters.push(registry.register_counter(&INFO[119usize]));
         counters.push(registry.register_counter(&INFO[121usize]));
-        counters.push(registry.register_counter(&INFO[122usize]));
-        counters.push(registry.register_counter(&INFO[123usize]));
+        counters.push(registry.register_counter(&INFO[124usize]));
+        counters.push(registry.register_counter(&INFO[125usize]));
+        counters.push(registry.register_counter(&INFO[126usize]));
         counters.push(registry.register_counter(&INFO[127usize]));
-        counters.push(registry.register_counter(&INFO[130usize]));
+        counters.push(registry.register_counter(&INFO[131usize]));
         counters.push(registry.register_counter(&INFO[134usize]));
-        counters.push(registry.register_counter(&INFO[136usize]));
-        counters.push(registry.register_counter(&INFO[137usize]));
         counters.push(registry.register_counter(&INFO[138usize]));
         counters.push(registry.register_counter(&INFO[140usize]));
+        counters.push(registry.register_counter(&INFO[141usize]));
         counters.push(registry.register_counter(&INFO[142usize]));
         counters.push(registry.register_counter(&INFO[144usize]));
         counters.push(registry.register_counter(&INFO[146usize]));
         counters.push(registry.register_counter(&INFO[148usize]));
-        counters.push(registry.register_counter(&INFO[149usize]));
         counters.push(registry.register_counter(&INFO[150usize]));
         counters.push(registry.register_counter(&INFO[152usize]));
+        counters.push(registry.register_counter(&INFO[153usize]));
         counters.push(registry.register_counter(&INFO[154usize]));
         counters.push(registry.register_counter(&INFO[156usize]));
         counters.push(registry.register_counter(&INFO[158usize]));
         counters.push(registry.register_counter(&INFO[160usize]));
-        counters.push(registry.register_counter(&INFO[161usize]));
         counters.push(registry.register_counter(&INFO[162usize]));
-        counters.push(registry.register_counter(&INFO[163usize]));
+        counters.push(registry.register_counter(&INFO[164usize]));
         counters.push(registry.register_counter(&INFO[165usize]));
+        counters.push(registry.register_counter(&INFO[166usize]));
+        counters.push(registry.register_counter(&INFO[167usize]));
+        counters.push(registry.register_counter(&INFO[169usize]));
+        counters.push(registry.register_counter(&INFO[170usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[24usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[34usize]));
-        bool_counters.push(registry.register_bool_counter(&INFO[98usize]));
+        bool_counters.push(registry.register_bool_counter(&INFO[100usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1390,12 +1423,23 @@ impl<R: Registry> Subscriber<R> {
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
                 nominal_counter_offsets.push(offset);
             }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <LocalMigrationStatus as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[85usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
                 for variant in <MtuUpdatedCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[97usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[99usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1406,7 +1450,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[100usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[102usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1417,7 +1461,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <BbrState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[109usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[111usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1428,7 +1472,18 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DcState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[115usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[117usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <ZeroRttStatus as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[120usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1439,7 +1494,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[119usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[123usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1450,7 +1505,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DatagramDropReason as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[133usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[137usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1461,7 +1516,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[135usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[139usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1482,30 +1537,30 @@ impl<R: Registry> Subscriber<R> {
         measures.push(registry.register_measure(&INFO[61usize]));
         measures.push(registry.register_measure(&INFO[64usize]));
         measures.push(registry.register_measure(&INFO[67usize]));
-        measures.push(registry.register_measure(&INFO[90usize]));
-        measures.push(registry.register_measure(&INFO[93usize]));
-        measures.push(registry.register_measure(&INFO[96usize]));
-        measures.push(registry.register_measure(&INFO[102usize]));
-        measures.push(registry.register_measure(&INFO[105usize]));
-        measures.push(registry.register_measure(&INFO[106usize]));
+        measures.push(registry.register_measure(&INFO[92usize]));
+        measures.push(registry.register_measure(&INFO[95usize]));
+        measures.push(registry.register_measure(&INFO[98usize]));
+        measures.push(registry.register_measure(&INFO[104usize]));
         measures.push(registry.register_measure(&INFO[107usize]));
-        measures.push(registry.register_measure(&INFO[124usize]));
-        measures.push(registry.register_measure(&INFO[125usize]));
-        measures.push(registry.register_measure(&INFO[126usize]));
+        measures.push(registry.register_measure(&INFO[108usize]));
+        measures.push(registry.register_measure(&INFO[109usize]));
         measures.push(registry.register_measure(&INFO[128usize]));
         measures.push(registry.register_measure(&INFO[129usize]));
-        measures.push(registry.register_measure(&INFO[131usize]));
+        measures.push(registry.register_measure(&INFO[130usize]));
         measures.push(registry.register_measure(&INFO[132usize]));
-        measures.push(registry.register_measure(&INFO[139usize]));
-        measures.push(registry.register_measure(&INFO[141usize]));
+        measures.push(registry.register_measure(&INFO[133usize]));
+        measures.push(registry.register_measure(&INFO[135usize]));
+        measures.push(registry.register_measure(&INFO[136usize]));
         measures.push(registry.register_measure(&INFO[143usize]));
         measures.push(registry.register_measure(&INFO[145usize]));
         measures.push(registry.register_measure(&INFO[147usize]));
+        measures.push(registry.register_measure(&INFO[149usize]));
         measures.push(registry.register_measure(&INFO[151usize]));
-        measures.push(registry.register_measure(&INFO[153usize]));
         measures.push(registry.register_measure(&INFO[155usize]));
         measures.push(registry.register_measure(&INFO[157usize]));
         measures.push(registry.register_measure(&INFO[159usize]));
+        measures.push(registry.register_measure(&INFO[161usize]));
+        measures.push(registry.register_measure(&INFO[163usize]));
         timers.push(registry.register_timer(&INFO[48usize]));
         timers.push(registry.register_timer(&INFO[49usize]));
         timers.push(registry.register_timer(&INFO[50usize]));
@@ -1513,14 +1568,14 @@ impl<R: Registry> Subscriber<R> {
         timers.push(registry.register_timer(&INFO[76usize]));
         timers.push(registry.register_timer(&INFO[77usize]));
         timers.push(registry.register_timer(&INFO[78usize]));
-        timers.push(registry.register_timer(&INFO[85usize]));
         timers.push(registry.register_timer(&INFO[87usize]));
-        timers.push(registry.register_timer(&INFO[111usize]));
-        timers.push(registry.register_timer(&INFO[112usize]));
+        timers.push(registry.register_timer(&INFO[89usize]));
         timers.push(registry.register_timer(&INFO[113usize]));
         timers.push(registry.register_timer(&INFO[114usize]));
-        timers.push(registry.register_timer(&INFO[118usize]));
-        timers.push(registry.register_timer(&INFO[164usize]));
+        timers.push(registry.register_timer(&INFO[115usize]));
+        timers.push(registry.register_timer(&INFO[116usize]));
+        timers.push(registry.register_timer(&INFO[122usize]));
+        timers.push(registry.register_timer(&INFO[168usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1528,7 +1583,7 @@ impl<R: Registry> Subscriber<R> {
                 let offset = nominal_timers.len();
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
-                    nominal_timers.push(registry.register_nominal_timer(&INFO[101usize], variant));
+                    nominal_timers.push(registry.register_nominal_timer(&INFO[103usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1607,44 +1662,47 @@ impl<R: Registry> Subscriber<R> {
                 39usize => (&INFO[84usize], entry),
                 40usize => (&INFO[86usize], entry),
                 41usize => (&INFO[88usize], entry),
-                42usize => (&INFO[89usize], entry),
+                42usize => (&INFO[90usize], entry),
                 43usize => (&INFO[91usize], entry),
-                44usize => (&INFO[92usize], entry),
+                44usize => (&INFO[93usize], entry),
                 45usize => (&INFO[94usize], entry),
-                46usize => (&INFO[95usize], entry),
-                47usize => (&INFO[99usize], entry),
-                48usize => (&INFO[103usize], entry),
-                49usize => (&INFO[104usize], entry),
-                50usize => (&INFO[108usize], entry),
+                46usize => (&INFO[96usize], entry),
+                47usize => (&INFO[97usize], entry),
+                48usize => (&INFO[101usize], entry),
+                49usize => (&INFO[105usize], entry),
+                50usize => (&INFO[106usize], entry),
                 51usize => (&INFO[110usize], entry),
-                52usize => (&INFO[116usize], entry),
-                53usize => (&INFO[117usize], entry),
-                54usize => (&INFO[120usize], entry),
+                52usize => (&INFO[112usize], entry),
+                53usize => (&INFO[118usize], entry),
+                54usize => (&INFO[119usize], entry),
                 55usize => (&INFO[121usize], entry),
-                56usize => (&INFO[122usize], entry),
-                57usize => (&INFO[123usize], entry),
-                58usize => (&INFO[127usize], entry),
-                59usize => (&INFO[130usize], entry),
-                60usize => (&INFO[134usize], entry),
-                61usize => (&INFO[136usize], entry),
-                62usize => (&INFO[137usize], entry),
-                63usize => (&INFO[138usize], entry),
-                64usize => (&INFO[140usize], entry),
+                56usize => (&INFO[124usize], entry),
+                57usize => (&INFO[125usize], entry),
+                58usize => (&INFO[126usize], entry),
+                59usize => (&INFO[127usize], entry),
+                60usize => (&INFO[131usize], entry),
+                61usize => (&INFO[134usize], entry),
+                62usize => (&INFO[138usize], entry),
+                63usize => (&INFO[140usize], entry),
+                64usize => (&INFO[141usize], entry),
                 65usize => (&INFO[142usize], entry),
                 66usize => (&INFO[144usize], entry),
                 67usize => (&INFO[146usize], entry),
                 68usize => (&INFO[148usize], entry),
-                69usize => (&INFO[149usize], entry),
-                70usize => (&INFO[150usize], entry),
-                71usize => (&INFO[152usize], entry),
+                69usize => (&INFO[150usize], entry),
+                70usize => (&INFO[152usize], entry),
+                71usize => (&INFO[153usize], entry),
                 72usize => (&INFO[154usize], entry),
                 73usize => (&INFO[156usize], entry),
                 74usize => (&INFO[158usize], entry),
                 75usize => (&INFO[160usize], entry),
-                76usize => (&INFO[161usize], entry),
-                77usize => (&INFO[162usize], entry),
-                78usize => (&INFO[163usize], entry),
-                79usize => (&INFO[165usize], entry),
+                76usize => (&INFO[162usize], entry),
+                77usize => (&INFO[164usize], entry),
+                78usize => (&INFO[165usize], entry),
+                79usize => (&INFO[166usize], entry),
+                80usize => (&INFO[167usize], entry),
+                81usize => (&INFO[169usize], entry),
+                82usize => (&INFO[170usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1664,7 +1722,7 @@ impl<R: Registry> Subscriber<R> {
             .map(|(idx, entry)| match idx {
                 0usize => (&INFO[24usize], entry),
                 1usize => (&INFO[34usize], entry),
-                2usize => (&INFO[98usize], entry),
+                2usize => (&INFO[100usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1820,45 +1878,57 @@ impl<R: Registry> Subscriber<R> {
                 }
                 22usize => {
                     let offset = *entry;
-                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
+                    let variants = <LocalMigrationStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[97usize], entries, variants)
+                    (&INFO[85usize], entries, variants)
                 }
                 23usize => {
                     let offset = *entry;
-                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
+                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[100usize], entries, variants)
+                    (&INFO[99usize], entries, variants)
                 }
                 24usize => {
                     let offset = *entry;
-                    let variants = <BbrState as AsVariant>::VARIANTS;
+                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[109usize], entries, variants)
+                    (&INFO[102usize], entries, variants)
                 }
                 25usize => {
                     let offset = *entry;
-                    let variants = <DcState as AsVariant>::VARIANTS;
+                    let variants = <BbrState as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[115usize], entries, variants)
+                    (&INFO[111usize], entries, variants)
                 }
                 26usize => {
                     let offset = *entry;
-                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let variants = <DcState as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[119usize], entries, variants)
+                    (&INFO[117usize], entries, variants)
                 }
                 27usize => {
                     let offset = *entry;
-                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let variants = <ZeroRttStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[133usize], entries, variants)
+                    (&INFO[120usize], entries, variants)
                 }
                 28usize => {
                     let offset = *entry;
                     let variants = <crate::connection::Error as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[135usize], entries, variants)
+                    (&INFO[123usize], entries, variants)
+                }
+                29usize => {
+                    let offset = *entry;
+                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[137usize], entries, variants)
+                }
+                30usize => {
+                    let offset = *entry;
+                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[139usize], entries, variants)
                 }
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
@@ -1892,30 +1962,30 @@ impl<R: Registry> Subscriber<R> {
                 11usize => (&INFO[61usize], entry),
                 12usize => (&INFO[64usize], entry),
                 13usize => (&INFO[67usize], entry),
-                14usize => (&INFO[90usize], entry),
-                15usize => (&INFO[93usize], entry),
-                16usize => (&INFO[96usize], entry),
-                17usize => (&INFO[102usize], entry),
-                18usize => (&INFO[105usize], entry),
-                19usize => (&INFO[106usize], entry),
-                20usize => (&INFO[107usize], entry),
-                21usize => (&INFO[124usize], entry),
-                22usize => (&INFO[125usize], entry),
-                23usize => (&INFO[126usize], entry),
-                24usize => (&INFO[128usize], entry),
-                25usize => (&INFO[129usize], entry),
-                26usize => (&INFO[131usize], entry),
-                27usize => (&INFO[132usize], entry),
-                28usize => (&INFO[139usize], entry),
-                29usize => (&INFO[141usize], entry),
-                30usize => (&INFO[143usize], entry),
-                31usize => (&INFO[145usize], entry),
-                32usize => (&INFO[147usize], entry),
-                33usize => (&INFO[151usize], entry),
-                34usize => (&INFO[153usize], entry),
-                35usize => (&INFO[155usize], entry),
-                36usize => (&INFO[157usize], entry),
-                37usize => (&INFO[159usize], entry),
+                14usize => (&INFO[92usize], entry),
+                15usize => (&INFO[95usize], entry),
+                16usize => (&INFO[98usize], entry),
+                17usize => (&INFO[104usize], entry),
+                18usize => (&INFO[107usize], entry),
+                19usize => (&INFO[108usize], entry),
+                20usize => (&INFO[109usize], entry),
+                21usize => (&INFO[128usize], entry),
+                22usize => (&INFO[129usize], entry),
+                23usize => (&INFO[130usize], entry),
+                24usize => (&INFO[132usize], entry),
+                25usize => (&INFO[133usize], entry),
+                26usize => (&INFO[135usize], entry),
+                27usize => (&INFO[136usize], entry),
+                28usize => (&INFO[143usize], entry),
+                29usize => (&INFO[145usize], entry),
+                30usize => (&INFO[147usize], entry),
+                31usize => (&INFO[149usize], entry),
+                32usize => (&INFO[151usize], entry),
+                33usize => (&INFO[155usize], entry),
+                34usize => (&INFO[157usize], entry),
+                35usize => (&INFO[159usize], entry),
+                36usize => (&INFO[161usize], entry),
+                37usize => (&INFO[163usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1952,14 +2022,14 @@ impl<R: Registry> Subscriber<R> {
                 4usize => (&INFO[76usize], entry),
                 5usize => (&INFO[77usize], entry),
                 6usize => (&INFO[78usize], entry),
-                7usize => (&INFO[85usize], entry),
-                8usize => (&INFO[87usize], entry),
-                9usize => (&INFO[111usize], entry),
-                10usize => (&INFO[112usize], entry),
-                11usize => (&INFO[113usize], entry),
-                12usize => (&INFO[114usize], entry),
-                13usize => (&INFO[118usize], entry),
-                14usize => (&INFO[164usize], entry),
+                7usize => (&INFO[87usize], entry),
+                8usize => (&INFO[89usize], entry),
+                9usize => (&INFO[113usize], entry),
+                10usize => (&INFO[114usize], entry),
+                11usize => (&INFO[115usize], entry),
+                12usize => (&INFO[116usize], entry),
+                13usize => (&INFO[122usize], entry),
+                14usize => (&INFO[168usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -2593,6 +2663,21 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
         let _ = event;
     }
     #[inline]
+    fn on_local_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::LocalMigrationUpdated,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(84usize, 39usize, 1usize);
+        self.count_nominal(85usize, 22usize, &event.status);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
     fn on_tls_client_hello(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -2601,9 +2686,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(84usize, 39usize, 1usize);
+        self.count(86usize, 40usize, 1usize);
         self.time(
-            85usize,
+            87usize,
             7usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2620,9 +2705,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(86usize, 40usize, 1usize);
+        self.count(88usize, 41usize, 1usize);
         self.time(
-            87usize,
+            89usize,
             8usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2639,9 +2724,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(88usize, 41usize, 1usize);
-        self.count(89usize, 42usize, event.bytes);
-        self.measure(90usize, 14usize, event.bytes);
+        self.count(90usize, 42usize, 1usize);
+        self.count(91usize, 43usize, event.bytes);
+        self.measure(92usize, 14usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2655,9 +2740,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(91usize, 43usize, 1usize);
-        self.count(92usize, 44usize, event.bytes);
-        self.measure(93usize, 15usize, event.bytes);
+        self.count(93usize, 44usize, 1usize);
+        self.count(94usize, 45usize, event.bytes);
+        self.measure(95usize, 15usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2671,7 +2756,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(94usize, 45usize, 1usize);
+        self.count(96usize, 46usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2685,10 +2770,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(95usize, 46usize, 1usize);
-        self.measure(96usize, 16usize, event.mtu);
-        self.count_nominal(97usize, 22usize, &event.cause);
-        self.count_bool(98usize, 2usize, event.search_complete);
+        self.count(97usize, 47usize, 1usize);
+        self.measure(98usize, 16usize, event.mtu);
+        self.count_nominal(99usize, 23usize, &event.cause);
+        self.count_bool(100usize, 2usize, event.search_complete);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2702,15 +2787,15 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(99usize, 47usize, 1usize);
-        self.count_nominal(100usize, 23usize, &event.cause);
+        self.count(101usize, 48usize, 1usize);
+        self.count_nominal(102usize, 24usize, &event.cause);
         self.time_nominal(
-            101usize,
+            103usize,
             0usize,
             &event.cause,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.measure(102usize, 17usize, event.congestion_window);
+        self.measure(104usize, 17usize, event.congestion_window);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2724,7 +2809,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(103usize, 48usize, 1usize);
+        self.count(105usize, 49usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2738,10 +2823,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(104usize, 49usize, 1usize);
-        self.measure(105usize, 18usize, event.bytes_per_second);
-        self.measure(106usize, 19usize, event.burst_size);
-        self.measure(107usize, 20usize, event.pacing_gain);
+        self.count(106usize, 50usize, 1usize);
+        self.measure(107usize, 18usize, event.bytes_per_second);
+        self.measure(108usize, 19usize, event.burst_size);
+        self.measure(109usize, 20usize, event.pacing_gain);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2755,8 +2840,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(108usize, 50usize, 1usize);
-        self.count_nominal(109usize, 24usize, &event.state);
+        self.count(110usize, 51usize, 1usize);
+        self.count_nominal(111usize, 25usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2770,14 +2855,14 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(110usize, 51usize, 1usize);
+        self.count(112usize, 52usize, 1usize);
         {
             fn check(evt: &api::DcStateChanged) -> bool {
                 matches!(evt.state, DcState::VersionNegotiated { .. })
             }
             if check(event) {
                 self.time(
-                    111usize,
+                    113usize,
                     9usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2789,7 +2874,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    112usize,
+                    114usize,
                     10usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2801,7 +2886,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    113usize,
+                    115usize,
                     11usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2813,13 +2898,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    114usize,
+                    116usize,
                     12usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(115usize, 25usize, &event.state);
+        self.count_nominal(117usize, 26usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2833,7 +2918,22 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(116usize, 52usize, 1usize);
+        self.count(118usize, 53usize, 1usize);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_zero_rtt_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ZeroRttStatusUpdated,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(119usize, 54usize, 1usize);
+        self.count_nominal(120usize, 27usize, &event.status);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2847,13 +2947,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(117usize, 53usize, 1usize);
+        self.count(121usize, 55usize, 1usize);
         self.time(
-            118usize,
+            122usize,
             13usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.count_nominal(119usize, 26usize, &event.error);
+        self.count_nominal(123usize, 28usize, &event.error);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2866,7 +2966,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(120usize, 54usize, 1usize);
+        self.count(124usize, 56usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2878,7 +2978,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(121usize, 55usize, 1usize);
+        self.count(125usize, 57usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2890,7 +2990,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(122usize, 56usize, 1usize);
+        self.count(126usize, 58usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2902,10 +3002,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(123usize, 57usize, 1usize);
-        self.measure(124usize, 21usize, event.len);
-        self.measure(125usize, 22usize, event.len);
-        self.measure(126usize, 23usize, event.gso_offset);
+        self.count(127usize, 59usize, 1usize);
+        self.measure(128usize, 21usize, event.len);
+        self.measure(129usize, 22usize, event.len);
+        self.measure(130usize, 23usize, event.gso_offset);
         let _ = event;
         let _ = meta;
     }
@@ -2917,9 +3017,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(127usize, 58usize, 1usize);
-        self.measure(128usize, 24usize, event.len);
-        self.measure(129usize, 25usize, event.len);
+        self.count(131usize, 60usize, 1usize);
+        self.measure(132usize, 24usize, event.len);
+        self.measure(133usize, 25usize, event.len);
         let _ = event;
         let _ = meta;
     }
@@ -2931,10 +3031,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(130usize, 59usize, 1usize);
-        self.measure(131usize, 26usize, event.len);
-        self.measure(132usize, 27usize, event.len);
-        self.count_nominal(133usize, 27usize, &event.reason);
+        self.count(134usize, 61usize, 1usize);
+        self.measure(135usize, 26usize, event.len);
+        self.measure(136usize, 27usize, event.len);
+        self.count_nominal(137usize, 29usize, &event.reason);
         let _ = event;
         let _ = meta;
     }
@@ -2946,8 +3046,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(134usize, 60usize, 1usize);
-        self.count_nominal(135usize, 28usize, &event.error);
+        self.count(138usize, 62usize, 1usize);
+        self.count_nominal(139usize, 30usize, &event.error);
         let _ = event;
         let _ = meta;
     }
@@ -2959,7 +3059,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(136usize, 61usize, 1usize);
+        self.count(140usize, 63usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2967,17 +3067,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(137usize, 62usize, 1usize);
-        self.count(138usize, 63usize, event.count);
-        self.measure(139usize, 28usize, event.count);
-        self.count(140usize, 64usize, event.syscalls);
-        self.measure(141usize, 29usize, event.syscalls);
-        self.count(142usize, 65usize, event.blocked_syscalls);
-        self.measure(143usize, 30usize, event.blocked_syscalls);
-        self.count(144usize, 66usize, event.total_errors);
-        self.measure(145usize, 31usize, event.total_errors);
-        self.count(146usize, 67usize, event.dropped_errors);
-        self.measure(147usize, 32usize, event.dropped_errors);
+        self.count(141usize, 64usize, 1usize);
+        self.count(142usize, 65usize, event.count);
+        self.measure(143usize, 28usize, event.count);
+        self.count(144usize, 66usize, event.syscalls);
+        self.measure(145usize, 29usize, event.syscalls);
+        self.count(146usize, 67usize, event.blocked_syscalls);
+        self.measure(147usize, 30usize, event.blocked_syscalls);
+        self.count(148usize, 68usize, event.total_errors);
+        self.measure(149usize, 31usize, event.total_errors);
+        self.count(150usize, 69usize, event.dropped_errors);
+        self.measure(151usize, 32usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -2985,7 +3085,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(148usize, 68usize, 1usize);
+        self.count(152usize, 70usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2993,17 +3093,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(149usize, 69usize, 1usize);
-        self.count(150usize, 70usize, event.count);
-        self.measure(151usize, 33usize, event.count);
-        self.count(152usize, 71usize, event.syscalls);
-        self.measure(153usize, 34usize, event.syscalls);
-        self.count(154usize, 72usize, event.blocked_syscalls);
-        self.measure(155usize, 35usize, event.blocked_syscalls);
-        self.count(156usize, 73usize, event.total_errors);
-        self.measure(157usize, 36usize, event.total_errors);
-        self.count(158usize, 74usize, event.dropped_errors);
-        self.measure(159usize, 37usize, event.dropped_errors);
+        self.count(153usize, 71usize, 1usize);
+        self.count(154usize, 72usize, event.count);
+        self.measure(155usize, 33usize, event.count);
+        self.count(156usize, 73usize, event.syscalls);
+        self.measure(157usize, 34usize, event.syscalls);
+        self.count(158usize, 74usize, event.blocked_syscalls);
+        self.measure(159usize, 35usize, event.blocked_syscalls);
+        self.count(160usize, 75usize, event.total_errors);
+        self.measure(161usize, 36usize, event.total_errors);
+        self.count(162usize, 76usize, event.dropped_errors);
+        self.measure(163usize, 37usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -3011,7 +3111,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(160usize, 75usize, 1usize);
+        self.count(164usize, 77usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -3023,7 +3123,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(161usize, 76usize, 1usize);
+        self.count(165usize, 78usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -3035,7 +3135,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(162usize, 77usize, 1usize);
+        self.count(166usize, 79usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -3047,8 +3147,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(163usize, 78usize, 1usize);
-        self.time(164usize, 14usize, event.processing_duration);
+        self.count(167usize, 80usize, 1usize);
+        self.time(168usize, 14usize, event.processing_duration);
         let _ = event;
         let _ = meta;
     }
@@ -3060,7 +3160,15 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(165usize, 79usize, 1usize);
+        self.count(169usize, 81usize, 1usize);
+        let _ = event;
+        let _ = meta;
+    }
+    #[inline]
+    fn on_platform_icmp_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformIcmpError) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(170usize, 82usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -58,47 +58,50 @@ mod counter {
                 80usize => Self(tls_exporter_ready),
                 81usize => Self(tls_handshake_failed),
                 82usize => Self(path_challenge_updated),
-                84usize => Self(tls_client_hello),
-                86usize => Self(tls_server_hello),
-                88usize => Self(rx_stream_progress),
-                89usize => Self(rx_stream_progress__bytes__total),
-                91usize => Self(tx_stream_progress),
-                92usize => Self(tx_stream_progress__bytes__total),
-                94usize => Self(keep_alive_timer_expired),
-                95usize => Self(mtu_updated),
-                99usize => Self(slow_start_exited),
-                103usize => Self(delivery_rate_sampled),
-                104usize => Self(pacing_rate_updated),
-                108usize => Self(bbr_state_changed),
-                110usize => Self(dc_state_changed),
-                116usize => Self(dc_path_created),
-                117usize => Self(connection_closed),
-                120usize => Self(version_information),
-                121usize => Self(endpoint_packet_sent),
-                122usize => Self(endpoint_packet_received),
-                123usize => Self(endpoint_datagram_sent),
-                127usize => Self(endpoint_datagram_received),
-                130usize => Self(endpoint_datagram_dropped),
-                134usize => Self(endpoint_connection_attempt_failed),
-                136usize => Self(endpoint_connection_attempt_deduplicated),
-                137usize => Self(platform_tx),
-                138usize => Self(platform_tx__packets__total),
-                140usize => Self(platform_tx__syscalls__total),
-                142usize => Self(platform_tx__syscalls__blocked__total),
-                144usize => Self(platform_tx__errors__total),
-                146usize => Self(platform_tx__errors__dropped__total),
-                148usize => Self(platform_tx_error),
-                149usize => Self(platform_rx),
-                150usize => Self(platform_rx__packets__total),
-                152usize => Self(platform_rx__syscalls__total),
-                154usize => Self(platform_rx__syscalls__blocked__total),
-                156usize => Self(platform_rx__errors__total),
-                158usize => Self(platform_rx__errors__dropped__total),
-                160usize => Self(platform_rx_error),
-                161usize => Self(platform_feature_configured),
-                162usize => Self(platform_event_loop_wakeup),
-                163usize => Self(platform_event_loop_sleep),
-                165usize => Self(platform_event_loop_started),
+                84usize => Self(local_migration_updated),
+                86usize => Self(tls_client_hello),
+                88usize => Self(tls_server_hello),
+                90usize => Self(rx_stream_progress),
+                91usize => Self(rx_stream_progress__bytes__total),
+                93usize => Self(tx_stream_progress),
+                94usize => Self(tx_stream_progress__bytes__total),
+                96usize => Self(keep_alive_timer_expired),
+                97usize => Self(mtu_updated),
+                101usize => Self(slow_start_exited),
+                105usize => Self(delivery_rate_sampled),
+                106usize => Self(pacing_rate_updated),
+                110usize => Self(bbr_state_changed),
+                112usize => Self(dc_state_changed),
+                118usize => Self(dc_path_created),
+                119usize => Self(zero_rtt_status_updated),
+                121usize => Self(connection_closed),
+                124usize => Self(version_information),
+                125usize => Self(endpoint_packet_sent),
+                126usize => Self(endpoint_packet_received),
+                127usize => Self(endpoint_datagram_sent),
+                131usize => Self(endpoint_datagram_received),
+                134usize => Self(endpoint_datagram_dropped),
+                138usize => Self(endpoint_connection_attempt_failed),
+                140usize => Self(endpoint_connection_attempt_deduplicated),
+                141usize => Self(platform_tx),
+                142usize => Self(platform_tx__packets__total),
+                144usize => Self(platform_tx__syscalls__total),
+                146usize => Self(platform_tx__syscalls__blocked__total),
+                148usize => Self(platform_tx__errors__total),
+                150usize => Self(platform_tx__errors__dropped__total),
+                152usize => Self(platform_tx_error),
+                153usize => Self(platform_rx),
+                154usize => Self(platform_rx__packets__total),
+                156usize => Self(platform_rx__syscalls__total),
+                158usize => Self(platform_rx__syscalls__blocked__total),
+                160usize => Self(platform_rx__errors__total),
+                162usize => Self(platform_rx__errors__dropped__total),
+                164usize => Self(platform_rx_error),
+                165usize => Self(platform_feature_configured),
+                166usize => Self(platform_event_loop_wakeup),
+                167usize => Self(platform_event_loop_sleep),
+                169usize => Self(platform_event_loop_started),
+                170usize => Self(platform_icmp_error),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -188,6 +191,8 @@ mod counter {
             fn tls_handshake_failed(value: u64);
             # [link_name = s2n_quic__event__counter__path_challenge_updated]
             fn path_challenge_updated(value: u64);
+            # [link_name = s2n_quic__event__counter__local_migration_updated]
+            fn local_migration_updated(value: u64);
             # [link_name = s2n_quic__event__counter__tls_client_hello]
             fn tls_client_hello(value: u64);
             # [link_name = s2n_quic__event__counter__tls_server_hello]
@@ -216,6 +221,8 @@ mod counter {
             fn dc_state_changed(value: u64);
             # [link_name = s2n_quic__event__counter__dc_path_created]
             fn dc_path_created(value: u64);
+            # [link_name = s2n_quic__event__counter__zero_rtt_status_updated]
+            fn zero_rtt_status_updated(value: u64);
             # [link_name = s2n_quic__event__counter__connection_closed]
             fn connection_closed(value: u64);
             # [link_name = s2n_quic__event__counter__version_information]
@@ -270,6 +277,8 @@ mod counter {
             fn platform_event_loop_sleep(value: u64);
             # [link_name = s2n_quic__event__counter__platform_event_loop_started]
             fn platform_event_loop_started(value: u64);
+            # [link_name = s2n_quic__event__counter__platform_icmp_error]
+            fn platform_icmp_error(value: u64);
         }
     );
     pub mod bool {
@@ -281,7 +290,7 @@ mod counter {
                 match info.id {
                     24usize => Self(packet_lost__is_mtu_probe),
                     34usize => Self(recovery_metrics__congestion_limited),
-                    98usize => Self(mtu_updated__search_complete),
+                    100usize => Self(mtu_updated__search_complete),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -332,13 +341,15 @@ mod counter {
                     74usize => Self(connection_migration_denied__reason),
                     79usize => Self(handshake_status_updated__status),
                     83usize => Self(path_challenge_updated__status),
-                    97usize => Self(mtu_updated__cause),
-                    100usize => Self(slow_start_exited__cause),
-                    109usize => Self(bbr_state_changed__state),
-                    115usize => Self(dc_state_changed__state),
-                    119usize => Self(connection_closed__error),
-                    133usize => Self(endpoint_datagram_dropped__reason),
-                    135usize => Self(endpoint_connection_attempt_failed__error),
+                    85usize => Self(local_migration_updated__status),
+                    99usize => Self(mtu_updated__cause),
+                    102usize => Self(slow_start_exited__cause),
+                    111usize => Self(bbr_state_changed__state),
+                    117usize => Self(dc_state_changed__state),
+                    120usize => Self(zero_rtt_status_updated__status),
+                    123usize => Self(connection_closed__error),
+                    137usize => Self(endpoint_datagram_dropped__reason),
+                    139usize => Self(endpoint_connection_attempt_failed__error),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -415,6 +426,12 @@ mod counter {
                     variant: u64,
                     variant_name: &info::Str,
                 );
+                # [link_name = s2n_quic__event__counter__nominal__local_migration_updated__status]
+                fn local_migration_updated__status(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__mtu_updated__cause]
                 fn mtu_updated__cause(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__slow_start_exited__cause]
@@ -423,6 +440,12 @@ mod counter {
                 fn bbr_state_changed__state(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__dc_state_changed__state]
                 fn dc_state_changed__state(value: u64, variant: u64, variant_name: &info::Str);
+                # [link_name = s2n_quic__event__counter__nominal__zero_rtt_status_updated__status]
+                fn zero_rtt_status_updated__status(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__connection_closed__error]
                 fn connection_closed__error(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__endpoint_datagram_dropped__reason]
@@ -463,30 +486,30 @@ mod measure {
                 61usize => Self(datagram_sent__gso_offset),
                 64usize => Self(datagram_received__bytes),
                 67usize => Self(datagram_dropped__bytes),
-                90usize => Self(rx_stream_progress__bytes),
-                93usize => Self(tx_stream_progress__bytes),
-                96usize => Self(mtu_updated__mtu),
-                102usize => Self(slow_start_exited__congestion_window),
-                105usize => Self(pacing_rate_updated__bytes_per_second),
-                106usize => Self(pacing_rate_updated__burst_size),
-                107usize => Self(pacing_rate_updated__pacing_gain),
-                124usize => Self(endpoint_datagram_sent__bytes),
-                125usize => Self(endpoint_datagram_sent__bytes__total),
-                126usize => Self(endpoint_datagram_sent__gso_offset),
-                128usize => Self(endpoint_datagram_received__bytes),
-                129usize => Self(endpoint_datagram_received__bytes__total),
-                131usize => Self(endpoint_datagram_dropped__bytes),
-                132usize => Self(endpoint_datagram_dropped__bytes__total),
-                139usize => Self(platform_tx__packets),
-                141usize => Self(platform_tx__syscalls),
-                143usize => Self(platform_tx__syscalls__blocked),
-                145usize => Self(platform_tx__errors),
-                147usize => Self(platform_tx__errors__dropped),
-                151usize => Self(platform_rx__packets),
-                153usize => Self(platform_rx__syscalls),
-                155usize => Self(platform_rx__syscalls__blocked),
-                157usize => Self(platform_rx__errors),
-                159usize => Self(platform_rx__errors__dropped),
+                92usize => Self(rx_stream_progress__bytes),
+                95usize => Self(tx_stream_progress__bytes),
+                98usize => Self(mtu_updated__mtu),
+                104usize => Self(slow_start_exited__congestion_window),
+                107usize => Self(pacing_rate_updated__bytes_per_second),
+                108usize => Self(pacing_rate_updated__burst_size),
+                109usize => Self(pacing_rate_updated__pacing_gain),
+                128usize => Self(endpoint_datagram_sent__bytes),
+                129usize => Self(endpoint_datagram_sent__bytes__total),
+                130usize => Self(endpoint_datagram_sent__gso_offset),
+                132usize => Self(endpoint_datagram_received__bytes),
+                133usize => Self(endpoint_datagram_received__bytes__total),
+                135usize => Self(endpoint_datagram_dropped__bytes),
+                136usize => Self(endpoint_datagram_dropped__bytes__total),
+                143usize => Self(platform_tx__packets),
+                145usize => Self(platform_tx__syscalls),
+                147usize => Self(platform_tx__syscalls__blocked),
+                149usize => Self(platform_tx__errors),
+                151usize => Self(platform_tx__errors__dropped),
+                155usize => Self(platform_rx__packets),
+                157usize => Self(platform_rx__syscalls),
+                159usize => Self(platform_rx__syscalls__blocked),
+                161usize => Self(platform_rx__errors),
+                163usize => Self(platform_rx__errors__dropped),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -608,14 +631,14 @@ mod timer {
                 76usize => Self(handshake_status_updated__complete__latency),
                 77usize => Self(handshake_status_updated__confirmed__latency),
                 78usize => Self(handshake_status_updated__handshake_done_acked__latency),
-                85usize => Self(tls_client_hello__latency),
-                87usize => Self(tls_server_hello__latency),
-                111usize => Self(dc_state_changed__version_negotiated__latency),
-                112usize => Self(dc_state_changed__no_version_negotiated__latency),
-                113usize => Self(dc_state_changed__path_secrets__latency),
-                114usize => Self(dc_state_changed__complete__latency),
-                118usize => Self(connection_closed__latency),
-                164usize => Self(platform_event_loop_sleep__processing_duration),
+                87usize => Self(tls_client_hello__latency),
+                89usize => Self(tls_server_hello__latency),
+                113usize => Self(dc_state_changed__version_negotiated__latency),
+                114usize => Self(dc_state_changed__no_version_negotiated__latency),
+                115usize => Self(dc_state_changed__path_secrets__latency),
+                116usize => Self(dc_state_changed__complete__latency),
+                122usize => Self(connection_closed__latency),
+                168usize => Self(platform_event_loop_sleep__processing_duration),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -667,7 +690,7 @@ mod timer {
         impl Recorder {
             pub(crate) fn new(info: &'static Info, _variant: &'static info::Variant) -> Self {
                 match info.id {
-                    101usize => Self(slow_start_exited__latency),
+                    103usize => Self(slow_start_exited__latency),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
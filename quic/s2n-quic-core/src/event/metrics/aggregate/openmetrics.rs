@@ -0,0 +1,519 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-memory [`Registry`](super::Registry) which renders the recorded metrics in the
+//! [OpenMetrics](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md)
+//! text format
+//!
+//! The registry can be used with any generated aggregate `Subscriber`, including those
+//! generated for other crates' events.
+//!
+//! ```rust,ignore
+//! use s2n_quic_core::event::metrics::aggregate::{openmetrics::Registry, Subscriber};
+//!
+//! let registry = Registry::default().with_prefix("s2n_quic");
+//! let subscriber = Subscriber::new(registry.clone());
+//!
+//! // serve the rendered metrics on `/metrics`
+//! let body = registry.to_string();
+//! ```
+
+use super::{info, Info, Metric, Units};
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::sync::Mutex;
+
+/// The label used to distinguish the variants of nominal metrics
+const VARIANT_LABEL: &str = "variant";
+
+/// The label used to distinguish the values of boolean counters
+const BOOL_LABEL: &str = "value";
+
+/// Records aggregate metrics in memory and renders them as OpenMetrics text
+///
+/// The registry can be shared by multiple subscribers, including subscribers for
+/// different endpoints. Metrics with the same name are recorded in the same series.
+#[derive(Clone, Debug, Default)]
+pub struct Registry(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    prefix: String,
+    families: Mutex<BTreeMap<String, Family>>,
+}
+
+impl Registry {
+    /// Sets the prefix prepended to each metric name
+    ///
+    /// # Panics
+    ///
+    /// Panics if metrics have already been registered with the registry
+    pub fn with_prefix<P: Into<String>>(self, prefix: P) -> Self {
+        let mut inner =
+            Arc::try_unwrap(self.0).expect("the prefix must be set before the registry is shared");
+        inner.prefix = sanitize(&prefix.into());
+        Self(Arc::new(inner))
+    }
+
+    fn register(&self, info: &'static Info, label: Option<Label>, kind: Kind) -> Series {
+        let mut name = sanitize(info.name);
+        if !self.0.prefix.is_empty() {
+            name = format!("{}_{name}", self.0.prefix);
+        }
+
+        match (kind, info.units) {
+            // the `_total` suffix is added to the counter samples when rendering
+            (Kind::Counter, _) => {
+                if name.ends_with("_total") {
+                    name.truncate(name.len() - "_total".len());
+                }
+            }
+            (_, Units::Duration) => {
+                if !name.ends_with("_seconds") {
+                    name.push_str("_seconds");
+                }
+            }
+            _ => {}
+        }
+
+        let mut families = self.0.families.lock().unwrap();
+
+        // Metrics of different kinds can't share a family, so a metric with the same name as
+        // one of a different kind is recorded in a family with the kind appended to the name
+        while families
+            .get(&name)
+            .is_some_and(|family| family.kind != kind)
+        {
+            name.push('_');
+            name.push_str(kind.as_str());
+        }
+
+        let family = families.entry(name).or_insert_with(|| Family {
+            kind,
+            series: BTreeMap::new(),
+        });
+
+        family
+            .series
+            .entry(label)
+            .or_insert_with(|| match kind {
+                Kind::Counter => Series::Counter(Default::default()),
+                Kind::Gauge => Series::Gauge(Default::default()),
+                Kind::Histogram => Series::Histogram(Arc::new(HistogramState::new(info.units))),
+            })
+            .clone()
+    }
+
+    fn register_counter_series(&self, info: &'static Info, label: Option<Label>) -> Counter {
+        match self.register(info, label, Kind::Counter) {
+            Series::Counter(counter) => Counter(counter),
+            // families only contain series of their own kind
+            _ => Counter(Default::default()),
+        }
+    }
+
+    fn register_histogram_series(&self, info: &'static Info, label: Option<Label>) -> Histogram {
+        match self.register(info, label, Kind::Histogram) {
+            Series::Histogram(histogram) => Histogram(histogram),
+            // families only contain series of their own kind
+            _ => Histogram(Arc::new(HistogramState::new(info.units))),
+        }
+    }
+}
+
+impl super::Registry for Registry {
+    type Counter = Counter;
+    type BoolCounter = BoolCounter;
+    type NominalCounter = Counter;
+    type Measure = Histogram;
+    type Gauge = Gauge;
+    type Timer = Histogram;
+    type NominalTimer = Histogram;
+
+    fn register_counter(&self, info: &'static Info) -> Self::Counter {
+        self.register_counter_series(info, None)
+    }
+
+    fn register_bool_counter(&self, info: &'static Info) -> Self::BoolCounter {
+        BoolCounter {
+            on_true: self.register_counter_series(info, Some((BOOL_LABEL, "true"))),
+            on_false: self.register_counter_series(info, Some((BOOL_LABEL, "false"))),
+        }
+    }
+
+    fn register_nominal_counter(
+        &self,
+        info: &'static Info,
+        variant: &'static info::Variant,
+    ) -> Self::NominalCounter {
+        self.register_counter_series(info, Some((VARIANT_LABEL, variant.name)))
+    }
+
+    fn register_measure(&self, info: &'static Info) -> Self::Measure {
+        self.register_histogram_series(info, None)
+    }
+
+    fn register_gauge(&self, info: &'static Info) -> Self::Gauge {
+        match self.register(info, None, Kind::Gauge) {
+            Series::Gauge(gauge) => Gauge(gauge),
+            // families only contain series of their own kind
+            _ => Gauge(Default::default()),
+        }
+    }
+
+    fn register_timer(&self, info: &'static Info) -> Self::Timer {
+        self.register_histogram_series(info, None)
+    }
+
+    fn register_nominal_timer(
+        &self,
+        info: &'static Info,
+        variant: &'static info::Variant,
+    ) -> Self::NominalTimer {
+        self.register_histogram_series(info, Some((VARIANT_LABEL, variant.name)))
+    }
+}
+
+/// Renders the registered metrics in the OpenMetrics text format
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let families = self.0.families.lock().unwrap();
+
+        for (name, family) in families.iter() {
+            writeln!(f, "# TYPE {name} {}", family.kind.as_str())?;
+
+            for (label, series) in family.series.iter() {
+                match series {
+                    Series::Counter(value) => {
+                        let value = value.load(Ordering::Relaxed);
+                        writeln!(f, "{name}_total{} {value}", Labels(*label, None))?;
+                    }
+                    Series::Gauge(value) => {
+                        let value = f64::from_bits(value.load(Ordering::Relaxed));
+                        writeln!(f, "{name}{} {value}", Labels(*label, None))?;
+                    }
+                    Series::Histogram(histogram) => histogram.render(f, name, *label)?,
+                }
+            }
+        }
+
+        writeln!(f, "# EOF")
+    }
+}
+
+type Label = (&'static str, &'static str);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Family {
+    kind: Kind,
+    series: BTreeMap<Option<Label>, Series>,
+}
+
+#[derive(Clone, Debug)]
+enum Series {
+    Counter(Arc<AtomicU64>),
+    /// The bits of the most recently recorded `f64` value
+    Gauge(Arc<AtomicU64>),
+    Histogram(Arc<HistogramState>),
+}
+
+/// Formats the labels for a sample, including the histogram bucket bound, if any
+struct Labels<'a>(Option<Label>, Option<&'a dyn fmt::Display>);
+
+impl fmt::Display for Labels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.0, self.1) {
+            (None, None) => Ok(()),
+            (Some((key, value)), None) => write!(f, "{{{key}=\"{value}\"}}"),
+            (None, Some(le)) => write!(f, "{{le=\"{le}\"}}"),
+            (Some((key, value)), Some(le)) => write!(f, "{{{key}=\"{value}\",le=\"{le}\"}}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl super::Recorder for Counter {
+    #[inline]
+    fn record<T: Metric>(&self, _info: &'static Info, value: T) {
+        self.0.fetch_add(value.as_u64(), Ordering::Relaxed);
+    }
+}
+
+impl super::NominalRecorder for Counter {
+    #[inline]
+    fn record<T: Metric>(&self, _info: &'static Info, _variant: &'static info::Variant, value: T) {
+        self.0.fetch_add(value.as_u64(), Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BoolCounter {
+    on_true: Counter,
+    on_false: Counter,
+}
+
+impl super::BoolRecorder for BoolCounter {
+    #[inline]
+    fn record(&self, _info: &'static Info, value: bool) {
+        let counter = if value { &self.on_true } else { &self.on_false };
+        counter.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Gauge(Arc<AtomicU64>);
+
+impl super::Recorder for Gauge {
+    #[inline]
+    fn record<T: Metric>(&self, info: &'static Info, value: T) {
+        let value = as_f64(info, value);
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Histogram(Arc<HistogramState>);
+
+impl super::Recorder for Histogram {
+    #[inline]
+    fn record<T: Metric>(&self, info: &'static Info, value: T) {
+        self.0.record(as_f64(info, value));
+    }
+}
+
+impl super::NominalRecorder for Histogram {
+    #[inline]
+    fn record<T: Metric>(&self, info: &'static Info, _variant: &'static info::Variant, value: T) {
+        self.0.record(as_f64(info, value));
+    }
+}
+
+#[derive(Debug)]
+struct HistogramState {
+    /// The upper bound of each bucket, in ascending order
+    bounds: Box<[f64]>,
+    /// The number of values in each bucket, with an additional bucket for values
+    /// above the largest bound
+    buckets: Box<[AtomicU64]>,
+    count: AtomicU64,
+    /// The bits of the `f64` sum of all recorded values
+    sum: AtomicU64,
+}
+
+impl HistogramState {
+    fn new(units: Units) -> Self {
+        let bounds: Box<[f64]> = match units {
+            // 1us to ~67s
+            Units::Duration => (0..27).map(|exp| 1e-6 * (1u64 << exp) as f64).collect(),
+            Units::Percent => [1.0, 5.0, 10.0, 25.0, 50.0, 75.0, 90.0, 95.0, 99.0, 100.0].into(),
+            // 1 to ~4G
+            _ => (0..33).map(|exp| (1u64 << exp) as f64).collect(),
+        };
+        let buckets = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+
+        Self {
+            bounds,
+            buckets,
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    #[inline]
+    fn record(&self, value: f64) {
+        let idx = self.bounds.partition_point(|bound| *bound < value);
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .sum
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |sum| {
+                Some((f64::from_bits(sum) + value).to_bits())
+            });
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, name: &str, label: Option<Label>) -> fmt::Result {
+        let mut cumulative = 0;
+        for (bound, bucket) in self.bounds.iter().zip(self.buckets.iter()) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let labels = Labels(label, Some(bound));
+            writeln!(f, "{name}_bucket{labels} {cumulative}")?;
+        }
+
+        // the final bucket must match the count so it's derived from the buckets rather
+        // than loaded separately
+        if let Some(bucket) = self.buckets.last() {
+            cumulative += bucket.load(Ordering::Relaxed);
+        }
+        let labels = Labels(label, Some(&"+Inf"));
+        writeln!(f, "{name}_bucket{labels} {cumulative}")?;
+
+        let sum = f64::from_bits(self.sum.load(Ordering::Relaxed));
+        writeln!(f, "{name}_count{} {cumulative}", Labels(label, None))?;
+        writeln!(f, "{name}_sum{} {sum}", Labels(label, None))
+    }
+}
+
+/// Converts a metric value into the base units used in the output
+///
+/// Durations are rendered in seconds, per the OpenMetrics convention.
+#[inline]
+fn as_f64<T: Metric>(info: &'static Info, value: T) -> f64 {
+    if value.is_duration() || info.units == Units::Duration {
+        value.as_duration().as_secs_f64()
+    } else {
+        value.as_f64()
+    }
+}
+
+/// Replaces any characters which aren't allowed in metric names with `_`
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metrics::aggregate::{
+        info::{Builder, Str},
+        BoolRecorder, NominalRecorder, Recorder, Registry as _,
+    };
+    use core::time::Duration;
+
+    static COUNTER: Info = Builder {
+        id: 0,
+        name: Str::new("packet_sent.bytes.total\0"),
+        units: Units::Bytes,
+    }
+    .build();
+
+    static TIMER: Info = Builder {
+        id: 1,
+        name: Str::new("handshake.latency\0"),
+        units: Units::Duration,
+    }
+    .build();
+
+    static BOOL: Info = Builder {
+        id: 2,
+        name: Str::new("packet_lost.is_mtu_probe\0"),
+        units: Units::None,
+    }
+    .build();
+
+    static NOMINAL: Info = Builder {
+        id: 3,
+        name: Str::new("frame_sent.frame\0"),
+        units: Units::None,
+    }
+    .build();
+
+    static PING: info::Variant = info::variant::Builder {
+        id: 0,
+        name: Str::new("PING\0"),
+    }
+    .build();
+
+    #[test]
+    fn render() {
+        let registry = Registry::default().with_prefix("s2n_quic");
+
+        let counter = registry.register_counter(&COUNTER);
+        Recorder::record(&counter, &COUNTER, 1200u64);
+        Recorder::record(&counter, &COUNTER, 300u64);
+
+        // registering the same metric again records to the same series
+        Recorder::record(&registry.register_counter(&COUNTER), &COUNTER, 1u64);
+
+        let timer = registry.register_timer(&TIMER);
+        Recorder::record(&timer, &TIMER, Duration::from_millis(3));
+        Recorder::record(&timer, &TIMER, Duration::from_secs(100));
+
+        let bool_counter = registry.register_bool_counter(&BOOL);
+        BoolRecorder::record(&bool_counter, &BOOL, true);
+        BoolRecorder::record(&bool_counter, &BOOL, false);
+        BoolRecorder::record(&bool_counter, &BOOL, false);
+
+        let nominal = registry.register_nominal_counter(&NOMINAL, &PING);
+        NominalRecorder::record(&nominal, &NOMINAL, &PING, 1u64);
+
+        let output = registry.to_string();
+
+        assert!(output.contains("# TYPE s2n_quic_packet_sent_bytes counter\n"));
+        assert!(output.contains("s2n_quic_packet_sent_bytes_total 1501\n"));
+
+        assert!(output.contains("# TYPE s2n_quic_handshake_latency_seconds histogram\n"));
+        assert!(output.contains("s2n_quic_handshake_latency_seconds_bucket{le=\"0.002048\"} 0\n"));
+        assert!(output.contains("s2n_quic_handshake_latency_seconds_bucket{le=\"0.004096\"} 1\n"));
+        assert!(output.contains("s2n_quic_handshake_latency_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(output.contains("s2n_quic_handshake_latency_seconds_count 2\n"));
+        assert!(output.contains("s2n_quic_handshake_latency_seconds_sum 100.003\n"));
+
+        assert!(output.contains("s2n_quic_packet_lost_is_mtu_probe_total{value=\"false\"} 2\n"));
+        assert!(output.contains("s2n_quic_packet_lost_is_mtu_probe_total{value=\"true\"} 1\n"));
+
+        assert!(output.contains("s2n_quic_frame_sent_frame_total{variant=\"PING\"} 1\n"));
+
+        assert!(output.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn kind_mismatch() {
+        let registry = Registry::default();
+
+        let counter = registry.register_counter(&NOMINAL);
+        Recorder::record(&counter, &NOMINAL, 1u64);
+
+        // the same name can't be used for a family of a different kind
+        let measure = registry.register_measure(&NOMINAL);
+        Recorder::record(&measure, &NOMINAL, 3u64);
+
+        let output = registry.to_string();
+        assert!(output.contains("# TYPE frame_sent_frame counter\n"));
+        assert!(output.contains("frame_sent_frame_total 1\n"));
+        assert!(output.contains("# TYPE frame_sent_frame_histogram histogram\n"));
+        assert!(output.contains("frame_sent_frame_histogram_count 1\n"));
+    }
+
+    #[test]
+    fn subscriber() {
+        let registry = Registry::default();
+        let _subscriber = crate::event::metrics::aggregate::Subscriber::new(registry.clone());
+
+        // all of the generated metrics are registered on initialization
+        let output = registry.to_string();
+        for line in output.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let name_len = line
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap();
+            assert!(name_len > 0, "invalid sample: {line}");
+        }
+        assert!(output.contains("# TYPE connection_started counter\n"));
+    }
+}
@@ -67,6 +67,7 @@ impl<AckRanges> AckElicitable for crate::frame::Ack<AckRanges> {
         AckElicitation::NonEliciting
     }
 }
+impl AckElicitable for crate::frame::AckFrequency {}
 impl AckElicitable for crate::frame::ConnectionClose<'_> {
     #[inline]
     fn ack_elicitation(&self) -> AckElicitation {
@@ -84,6 +85,7 @@ impl AckElicitable for crate::frame::DataBlocked {}
 //# an ACK frame to be sent.
 impl AckElicitable for crate::frame::DcStatelessResetTokens<'_> {}
 impl AckElicitable for crate::frame::HandshakeDone {}
+impl AckElicitable for crate::frame::ImmediateAck {}
 impl AckElicitable for crate::frame::MaxData {}
 impl AckElicitable for crate::frame::MaxStreamData {}
 impl AckElicitable for crate::frame::MaxStreams {}
@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// An ACK_FREQUENCY frame is defined in the QUIC Acknowledgment Frequency extension
+// (draft-ietf-quic-ack-frequency). It allows the sender of the frame to request
+// that the peer adjusts how often it sends acknowledgements.
+
+const TAG: VarInt = VarInt::from_u8(0xaf);
+
+macro_rules! ack_frequency_tag {
+    () => {
+        0xafu64
+    };
+}
+
+// ACK_FREQUENCY Frame {
+//   Type (i) = 0xaf,
+//   Sequence Number (i),
+//   Ack-Eliciting Threshold (i),
+//   Requested Max Ack Delay (i),
+//   Reordering Threshold (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AckFrequency {
+    /// A sequence number assigned to the frame by the sender, used by the receiver
+    /// to ignore frames which arrive out of order
+    pub sequence_number: VarInt,
+
+    /// The maximum number of ack-eliciting packets the recipient of this frame
+    /// receives before sending an acknowledgement
+    pub ack_eliciting_threshold: VarInt,
+
+    /// The value, in microseconds, to which the recipient of this frame should
+    /// update its maximum acknowledgement delay
+    pub request_max_ack_delay: VarInt,
+
+    /// The maximum packet reordering before the recipient of this frame
+    /// immediately sends an acknowledgement. A value of `0` disables immediate
+    /// acknowledgements of reordered packets.
+    pub reordering_threshold: VarInt,
+}
+
+impl AckFrequency {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+
+    /// Returns the requested maximum ACK delay as a `Duration`
+    #[inline]
+    pub fn request_max_ack_delay(&self) -> core::time::Duration {
+        core::time::Duration::from_micros(self.request_max_ack_delay.as_u64())
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> AckFrequency {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (sequence_number, buffer) = buffer.decode()?;
+            let (ack_eliciting_threshold, buffer) = buffer.decode()?;
+            let (request_max_ack_delay, buffer) = buffer.decode()?;
+            let (reordering_threshold, buffer) = buffer.decode()?;
+
+            let frame = AckFrequency {
+                sequence_number,
+                ack_eliciting_threshold,
+                request_max_ack_delay,
+                reordering_threshold,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for AckFrequency {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+        buffer.encode(&self.sequence_number);
+        buffer.encode(&self.ack_eliciting_threshold);
+        buffer.encode(&self.request_max_ack_delay);
+        buffer.encode(&self.reordering_threshold);
+    }
+}
@@ -19,6 +19,7 @@ impl<AckRanges> CongestionControlled for crate::frame::Ack<AckRanges> {
         false
     }
 }
+impl CongestionControlled for crate::frame::AckFrequency {}
 impl CongestionControlled for crate::frame::ConnectionClose<'_> {}
 impl<Data> CongestionControlled for crate::frame::Crypto<Data> {}
 //= https://www.rfc-editor.org/rfc/rfc9221#section-5.4
@@ -30,6 +31,7 @@ impl CongestionControlled for crate::frame::DataBlocked {}
 //# an ACK frame to be sent.
 impl CongestionControlled for crate::frame::DcStatelessResetTokens<'_> {}
 impl CongestionControlled for crate::frame::HandshakeDone {}
+impl CongestionControlled for crate::frame::ImmediateAck {}
 impl CongestionControlled for crate::frame::MaxData {}
 impl CongestionControlled for crate::frame::MaxStreamData {}
 impl CongestionControlled for crate::frame::MaxStreams {}
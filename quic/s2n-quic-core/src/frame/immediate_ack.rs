@@ -0,0 +1,27 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+// An IMMEDIATE_ACK frame is defined in the QUIC Acknowledgment Frequency extension
+// (draft-ietf-quic-ack-frequency). It requests that the peer sends an acknowledgement
+// immediately upon receipt.
+
+macro_rules! immediate_ack_tag {
+    () => {
+        0x1fu8
+    };
+}
+
+// IMMEDIATE_ACK Frame {
+//   Type (i) = 0x1f,
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImmediateAck;
+
+impl ImmediateAck {
+    pub const fn tag(self) -> u8 {
+        immediate_ack_tag!()
+    }
+}
+
+simple_frame_codec!(ImmediateAck {}, immediate_ack_tag!());
@@ -263,6 +263,8 @@ frames! {
     [connection_close_tag] => connection_close, handle_connection_close_frame, ConnectionClose['a];
     [handshake_done_tag] => handshake_done, handle_handshake_done_frame, HandshakeDone;
     [datagram_tag] => datagram, handle_datagram_frame, Datagram[Data];
+    [immediate_ack_tag] => immediate_ack, handle_immediate_ack_frame, ImmediateAck;
+    extension[ack_frequency_tag] => ack_frequency, handle_ack_frequency_frame, AckFrequency;
     extension[dc_stateless_reset_tokens_tag] => dc_stateless_reset_tokens, handle_dc_stateless_reset_tokens_frame, DcStatelessResetTokens['a];
 }
 
@@ -65,12 +65,14 @@ pub trait Probing {
 //# PATH_CHALLENGE, PATH_RESPONSE, NEW_CONNECTION_ID, and PADDING frames
 //# are "probing frames", and all other frames are "non-probing frames".
 impl<AckRanges> Probing for crate::frame::Ack<AckRanges> {}
+impl Probing for crate::frame::AckFrequency {}
 impl Probing for crate::frame::ConnectionClose<'_> {}
 impl<Data> Probing for crate::frame::Crypto<Data> {}
 impl<Data> Probing for crate::frame::Datagram<Data> {}
 impl Probing for crate::frame::DataBlocked {}
 impl Probing for crate::frame::DcStatelessResetTokens<'_> {}
 impl Probing for crate::frame::HandshakeDone {}
+impl Probing for crate::frame::ImmediateAck {}
 impl Probing for crate::frame::MaxData {}
 impl Probing for crate::frame::MaxStreamData {}
 impl Probing for crate::frame::MaxStreams {}
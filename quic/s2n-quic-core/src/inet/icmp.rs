@@ -0,0 +1,50 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(any(test, feature = "generator"))]
+use bolero_generator::prelude::*;
+
+/// An ICMP error reported by the network for a previously transmitted datagram
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "generator"), derive(TypeGenerator))]
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+pub enum Error {
+    /// The datagram exceeded the MTU of a link on the path
+    ///
+    /// This is reported by ICMP "fragmentation needed" and ICMPv6 "packet too big" messages.
+    PacketTooBig {
+        /// The MTU of the link, including the IP and UDP headers
+        mtu: u16,
+    },
+    /// The peer host is not listening on the destination port
+    PortUnreachable,
+}
+
+impl Error {
+    /// Returns the IP-level MTU reported by a "packet too big" error
+    #[inline]
+    pub fn mtu(&self) -> Option<u16> {
+        match self {
+            Self::PacketTooBig { mtu } => Some(*mtu),
+            Self::PortUnreachable => None,
+        }
+    }
+
+    /// Returns `true` if the error indicates the peer can no longer be reached on the path
+    #[inline]
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, Self::PortUnreachable)
+    }
+}
+
+impl crate::event::IntoEvent<crate::event::builder::IcmpError> for Error {
+    #[inline]
+    fn into_event(self) -> crate::event::builder::IcmpError {
+        use crate::event::builder::IcmpError;
+
+        match self {
+            Self::PacketTooBig { mtu } => IcmpError::PacketTooBig { mtu },
+            Self::PortUnreachable => IcmpError::PortUnreachable,
+        }
+    }
+}
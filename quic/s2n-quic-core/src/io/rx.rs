@@ -1,7 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, inet::datagram, path};
+use crate::{
+    event,
+    inet::{datagram, icmp},
+    path,
+};
 use core::task::{Context, Poll};
 
 pub mod pair;
@@ -54,6 +58,33 @@ pub trait Queue {
     /// Iterates over all of the packets in the receive queue and processes them
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, on_packet: F);
 
+    /// Iterates over all of the entries in the receive queue and processes them
+    ///
+    /// In addition to the packets passed to [`Self::for_each`], this includes any ICMP errors
+    /// that were reported for previously transmitted datagrams. Queues that are unable to read
+    /// ICMP errors only return packets.
+    #[inline]
+    fn for_each_entry<F: FnMut(Entry<Self::Handle>)>(&mut self, mut on_entry: F) {
+        self.for_each(|header, payload| on_entry(Entry::Packet { header, payload }))
+    }
+
     /// Returns if there are items in the queue or not
     fn is_empty(&self) -> bool;
 }
+
+/// An entry in a receive queue
+#[derive(Debug)]
+pub enum Entry<'a, Handle> {
+    /// A packet was received from the peer
+    Packet {
+        header: datagram::Header<Handle>,
+        payload: &'a mut [u8],
+    },
+    /// The network reported an ICMP error for a datagram previously transmitted on `path`
+    Error {
+        path: Handle,
+        error: icmp::Error,
+        /// The beginning of the datagram that triggered the error, as quoted by the ICMP message
+        payload: &'a [u8],
+    },
+}
@@ -125,6 +125,13 @@ where
         self.b.for_each(&mut on_packet);
     }
 
+    #[inline]
+    fn for_each_entry<F: FnMut(super::Entry<Self::Handle>)>(&mut self, mut on_entry: F) {
+        // drain both of the channels
+        self.a.for_each_entry(&mut on_entry);
+        self.b.for_each_entry(&mut on_entry);
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         self.a.is_empty() && self.b.is_empty()
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, inet::ExplicitCongestionNotification, path};
+use crate::{event, inet::ExplicitCongestionNotification, path, time::Timestamp};
 use core::{
     task::{Context, Poll},
     time::Duration,
@@ -107,6 +107,15 @@ pub trait Queue {
     fn has_capacity(&self) -> bool {
         self.capacity() != 0
     }
+
+    /// Returns how far ahead of its departure time a message can be pushed into the queue
+    ///
+    /// Queues that offload pacing, e.g. to the kernel with `SO_TXTIME`, hold messages until
+    /// their [`Message::delay`] has passed, which allows them to be queued early.
+    #[inline]
+    fn pacing_horizon(&self) -> Duration {
+        Duration::ZERO
+    }
 }
 
 pub struct Outcome {
@@ -146,6 +155,15 @@ pub trait Message {
     /// This is used in scenarios where packets need to be paced.
     fn delay(&mut self) -> Duration;
 
+    /// Returns the time at which the message should be sent, if it was paced into the future
+    ///
+    /// This is only queried by queues with a non-zero [`Queue::pacing_horizon`]. The timestamp
+    /// comes from the endpoint's clock so the queue can convert it without reading the time again.
+    #[inline]
+    fn departure_time(&mut self) -> Option<Timestamp> {
+        None
+    }
+
     /// Returns the IPv6 flow label for the message
     fn ipv6_flow_label(&mut self) -> u32;
 
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, inet::ExplicitCongestionNotification, io::tx, path};
+use crate::{event, inet::ExplicitCongestionNotification, io::tx, path, time::Timestamp};
 use core::{
     marker::PhantomData,
     task::{Context, Poll},
@@ -113,6 +113,11 @@ where
     fn has_capacity(&self) -> bool {
         self.tx.has_capacity()
     }
+
+    #[inline]
+    fn pacing_horizon(&self) -> Duration {
+        self.tx.pacing_horizon()
+    }
 }
 
 pub struct Message<M, Handle> {
@@ -143,6 +148,11 @@ where
         self.inner.delay()
     }
 
+    #[inline]
+    fn departure_time(&mut self) -> Option<Timestamp> {
+        self.inner.departure_time()
+    }
+
     #[inline]
     fn ipv6_flow_label(&mut self) -> u32 {
         self.inner.ipv6_flow_label()
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{event, io::tx, path};
-use core::task::{Context, Poll};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 /// Defines how to route a message between two different channels
 pub trait Router {
@@ -166,6 +169,12 @@ where
         // we only have capacity if both channels do
         self.a.has_capacity() && self.b.has_capacity()
     }
+
+    #[inline]
+    fn pacing_horizon(&self) -> Duration {
+        // take the minimum of the horizons, since we don't know where the next message will go
+        self.a.pacing_horizon().min(self.b.pacing_horizon())
+    }
 }
 
 #[cfg(test)]
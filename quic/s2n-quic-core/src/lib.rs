@@ -24,6 +24,7 @@ pub mod ct;
 pub mod datagram;
 #[cfg(feature = "alloc")]
 pub mod dc;
+pub mod early_data;
 pub mod endpoint;
 pub mod event;
 pub mod frame;
@@ -50,6 +51,7 @@ pub mod token;
 pub mod transmission;
 pub mod transport;
 pub mod varint;
+pub mod version;
 pub mod xdp;
 
 #[cfg(any(test, feature = "testing"))]
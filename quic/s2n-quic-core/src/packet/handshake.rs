@@ -10,7 +10,7 @@ use crate::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
         long::{
-            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor,
+            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor, PacketType,
             SourceConnectionIdLen, Version,
         },
         number::{
@@ -41,14 +41,6 @@ use s2n_codec::{CheckedRange, DecoderBufferMut, DecoderBufferMutResult, Encoder,
 //#   Packet Payload (..),
 //# }
 
-//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.4
-//# A Handshake packet uses long headers with a type value of 0x2
-macro_rules! handshake_tag {
-    () => {
-        0b1110u8
-    };
-}
-
 #[derive(Debug)]
 pub struct Handshake<DCID, SCID, PacketNumber, Payload> {
     pub version: Version,
@@ -226,7 +218,7 @@ impl<DCID: EncoderValue, SCID: EncoderValue, PacketNumber, Payload>
     Handshake<DCID, SCID, PacketNumber, Payload>
 {
     fn encode_header<E: Encoder>(&self, packet_number_len: PacketNumberLen, encoder: &mut E) {
-        let mut tag: u8 = handshake_tag!() << 4;
+        let mut tag: u8 = PacketType::Handshake.into_tag(self.version);
         tag |= packet_number_len.into_packet_tag_mask();
         tag.encode(encoder);
 
@@ -7,7 +7,7 @@ use crate::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
         long::{
-            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor,
+            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor, PacketType,
             SourceConnectionIdLen, Version,
         },
         number::{
@@ -39,14 +39,6 @@ use s2n_codec::{CheckedRange, DecoderBufferMut, DecoderBufferMutResult, Encoder,
 //#   Packet Payload (..),
 //# }
 
-//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.2
-//# An Initial packet uses long headers with a type value of 0x0.
-macro_rules! initial_tag {
-    () => {
-        0b1100u8
-    };
-}
-
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.2
 //# Token Length:  A variable-length integer specifying the length of the
 //# Token field, in bytes.  This value is 0 if no token is present.
@@ -253,7 +245,7 @@ impl<DCID: EncoderValue, SCID: EncoderValue, Token: EncoderValue, PacketNumber,
     Initial<DCID, SCID, Token, PacketNumber, Payload>
 {
     fn encode_header<E: Encoder>(&self, packet_number_len: PacketNumberLen, encoder: &mut E) {
-        let mut tag: u8 = initial_tag!() << 4;
+        let mut tag: u8 = PacketType::Initial.into_tag(self.version);
         tag |= packet_number_len.into_packet_tag_mask();
         tag.encode(encoder);
 
@@ -4,6 +4,7 @@
 use crate::{
     packet::{encoding::PacketPayloadLenCursor, number::TruncatedPacketNumber},
     varint::VarInt,
+    version,
 };
 use s2n_codec::{
     decoder_invariant, CheckedRange, DecoderError, Encoder, EncoderBuffer, EncoderValue,
@@ -46,7 +47,7 @@ const PACKET_TYPE_OFFSET: u8 = 4;
 //#    byte.  This field indicates the version of QUIC that is in use and
 //#    determines how the rest of the protocol fields are interpreted.
 
-pub(crate) type Version = u32;
+pub(crate) type Version = version::Version;
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
 //# Destination Connection ID Length:  The byte following the version
@@ -135,9 +136,17 @@ pub(crate) fn validate_source_connection_id_len(len: usize) -> Result<(), Decode
 #[repr(u8)]
 #[derive(Clone, Copy, Debug)]
 pub enum PacketType {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.2
+    //# An Initial packet uses long headers with a type value of 0x0.
     Initial = 0x0,
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+    //# A 0-RTT packet uses long headers with a type value of 0x1,
     ZeroRtt = 0x1,
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.4
+    //# A Handshake packet uses long headers with a type value of 0x2
     Handshake = 0x2,
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5
+    //# a Retry packet uses a long packet header with a type value of 0x03.
     Retry = 0x3,
 }
 
@@ -149,6 +158,52 @@ impl PacketType {
     pub fn from_bits(bits: u8) -> Self {
         (bits & (PACKET_TYPE_MASK >> PACKET_TYPE_OFFSET)).into()
     }
+
+    /// Returns the first byte of a long header for the packet type in the given version,
+    /// without the type-specific bits
+    ///
+    /// QUIC version 2 rotates the packet type codepoints, as described in
+    /// <https://www.rfc-editor.org/rfc/rfc9369#section-3.2>:
+    ///
+    /// | Type      | Version 1 | Version 2 |
+    /// |-----------|-----------|-----------|
+    /// | Initial   | 0b00      | 0b01      |
+    /// | 0-RTT     | 0b01      | 0b10      |
+    /// | Handshake | 0b10      | 0b11      |
+    /// | Retry     | 0b11      | 0b00      |
+    #[inline]
+    pub const fn into_tag(self, version: Version) -> u8 {
+        let bits = if version::is_v2(version) {
+            (self as u8 + 1) & 0b11
+        } else {
+            self as u8
+        };
+        LONG_HEADER_TAG | (bits << PACKET_TYPE_OFFSET)
+    }
+
+    /// Decodes the packet type from the first byte of a long header in the given version
+    #[inline]
+    pub fn from_tag(version: Version, tag: u8) -> Self {
+        let bits = (tag & PACKET_TYPE_MASK) >> PACKET_TYPE_OFFSET;
+        let bits = if version::is_v2(version) {
+            bits.wrapping_sub(1) & 0b11
+        } else {
+            bits
+        };
+        bits.into()
+    }
+}
+
+/// The Header Form and Fixed Bit of a long header
+const LONG_HEADER_TAG: u8 = 0xc0;
+
+/// Matches the high nibble of any long header packet with the Fixed Bit set
+///
+/// The packet type is version-dependent and resolved with [`PacketType::from_tag`].
+macro_rules! long_tag {
+    () => {
+        0b1100u8..=0b1111u8
+    };
 }
 
 impl From<u8> for PacketType {
@@ -262,3 +317,24 @@ impl EncoderValue for LongPayloadLenCursor {
         self.max_value.encode(encoder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{VERSION_1, VERSION_2};
+
+    #[test]
+    fn packet_type_tag_test() {
+        for (ty, v1, v2) in [
+            (PacketType::Initial, 0xc0, 0xd0),
+            (PacketType::ZeroRtt, 0xd0, 0xe0),
+            (PacketType::Handshake, 0xe0, 0xf0),
+            (PacketType::Retry, 0xf0, 0xc0),
+        ] {
+            assert_eq!(ty.into_tag(VERSION_1), v1);
+            assert_eq!(ty.into_tag(VERSION_2), v2);
+            assert_eq!(PacketType::from_tag(VERSION_1, v1 | 0x0f) as u8, ty as u8);
+            assert_eq!(PacketType::from_tag(VERSION_2, v2 | 0x0f) as u8, ty as u8);
+        }
+    }
+}
@@ -23,6 +23,7 @@ pub mod decoding;
 pub mod encoding;
 pub mod interceptor;
 pub mod key_phase;
+#[macro_use]
 pub mod long;
 
 pub mod number;
@@ -212,15 +213,10 @@ pub trait PacketDecoder<'a> {
         }
 
         macro_rules! long_packet {
-            ($struct:ident, $handler:ident) => {{
-                let (version, _peek) = peek.decode()?;
-                if version == version_negotiation::VERSION {
-                    version_negotiation!(version)
-                } else {
-                    let (packet, buffer) = $struct::decode(tag, version, buffer)?;
-                    let output = self.$handler(packet)?;
-                    Ok((output, buffer))
-                }
+            ($version:ident, $struct:ident, $handler:ident) => {{
+                let (packet, buffer) = $struct::decode(tag, $version, buffer)?;
+                let output = self.$handler(packet)?;
+                Ok((output, buffer))
             }};
         }
 
@@ -243,10 +239,28 @@ pub trait PacketDecoder<'a> {
                 );
                 version_negotiation!(version)
             }
-            initial_tag!() => long_packet!(ProtectedInitial, handle_initial_packet),
-            zero_rtt_tag!() => long_packet!(ProtectedZeroRtt, handle_zero_rtt_packet),
-            handshake_tag!() => long_packet!(ProtectedHandshake, handle_handshake_packet),
-            retry_tag!() => long_packet!(ProtectedRetry, handle_retry_packet),
+            long_tag!() => {
+                let (version, _peek) = peek.decode()?;
+                if version == version_negotiation::VERSION {
+                    return version_negotiation!(version);
+                }
+
+                // The long packet type codepoints depend on the version of the packet
+                match long::PacketType::from_tag(version, tag) {
+                    long::PacketType::Initial => {
+                        long_packet!(version, ProtectedInitial, handle_initial_packet)
+                    }
+                    long::PacketType::ZeroRtt => {
+                        long_packet!(version, ProtectedZeroRtt, handle_zero_rtt_packet)
+                    }
+                    long::PacketType::Handshake => {
+                        long_packet!(version, ProtectedHandshake, handle_handshake_packet)
+                    }
+                    long::PacketType::Retry => {
+                        long_packet!(version, ProtectedRetry, handle_retry_packet)
+                    }
+                }
+            }
             _ => Err(DecoderError::InvariantViolation("invalid packet").into()),
         }
     }
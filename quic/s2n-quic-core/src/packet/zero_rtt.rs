@@ -7,7 +7,7 @@ use crate::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
         long::{
-            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor,
+            DestinationConnectionIdLen, LongPayloadEncoder, LongPayloadLenCursor, PacketType,
             SourceConnectionIdLen, Version,
         },
         number::{
@@ -37,14 +37,6 @@ use s2n_codec::{CheckedRange, DecoderBufferMut, DecoderBufferMutResult, Encoder,
 //#   Packet Payload (..),
 //# }
 
-//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
-//# A 0-RTT packet uses long headers with a type value of 0x1,
-macro_rules! zero_rtt_tag {
-    () => {
-        0b1101u8
-    };
-}
-
 #[derive(Debug)]
 pub struct ZeroRtt<DCID, SCID, PacketNumber, Payload> {
     pub version: Version,
@@ -204,7 +196,7 @@ impl<DCID: EncoderValue, SCID: EncoderValue, PacketNumber, Payload>
     ZeroRtt<DCID, SCID, PacketNumber, Payload>
 {
     fn encode_header<E: Encoder>(&self, packet_number_len: PacketNumberLen, encoder: &mut E) {
-        let mut tag: u8 = zero_rtt_tag!() << 4;
+        let mut tag: u8 = PacketType::ZeroRtt.into_tag(self.version);
         tag |= packet_number_len.into_packet_tag_mask();
         tag.encode(encoder);
 
@@ -86,9 +86,31 @@ pub struct Controller {
     // The largest acknowledged packet sent with an ECN marking. Used when tracking
     // packets that have been lost for the purpose of detecting a black hole.
     last_acked_ecn_packet_timestamp: Option<Timestamp>,
+    // Mark packets with ECT(1) rather than ECT(0) to identify an L4S congestion controller
+    l4s: bool,
 }
 
 impl Controller {
+    /// Creates a controller that marks packets with the ECT(1) codepoint
+    ///
+    /// ECT(1) identifies packets sent by a scalable congestion controller to L4S capable
+    /// network nodes, as described in [RFC 9331](https://www.rfc-editor.org/rfc/rfc9331).
+    #[inline]
+    pub fn l4s() -> Self {
+        Self {
+            l4s: true,
+            ..Default::default()
+        }
+    }
+
+    /// Sets whether packets are marked with the ECT(1) codepoint rather than ECT(0)
+    ///
+    /// This is used when the congestion controller on the path changes during the connection.
+    #[inline]
+    pub fn set_l4s(&mut self, l4s: bool) {
+        self.l4s = l4s;
+    }
+
     /// Restart testing of ECN capability
     #[inline]
     pub fn restart<Pub: event::ConnectionPublisher>(
@@ -144,7 +166,7 @@ impl Controller {
             //# On paths with a "testing" or "capable" state, the endpoint
             //# sends packets with an ECT marking -- ECT(0) by default;
             //# otherwise, the endpoint sends unmarked packets.
-            State::Testing(_) => ExplicitCongestionNotification::Ect0,
+            State::Testing(_) => self.ect(),
             State::Capable(ref mut ce_suppression_timer) => {
                 if ce_suppression_timer.poll_expiration(now).is_ready() {
                     //= https://www.rfc-editor.org/rfc/rfc9002#section-8.3
@@ -156,7 +178,7 @@ impl Controller {
                     //# Upon successful validation, an endpoint MAY continue to set an ECT
                     //# codepoint in subsequent packets it sends, with the expectation that
                     //# the path is ECN-capable.
-                    ExplicitCongestionNotification::Ect0
+                    self.ect()
                 }
             }
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.2
@@ -167,6 +189,16 @@ impl Controller {
         }
     }
 
+    /// Returns the ECT codepoint used to mark packets on an ECN capable path
+    #[inline]
+    fn ect(&self) -> ExplicitCongestionNotification {
+        if self.l4s {
+            ExplicitCongestionNotification::Ect1
+        } else {
+            ExplicitCongestionNotification::Ect0
+        }
+    }
+
     /// Returns a duration based on a randomly generated value in the CE_SUPPRESSION_TESTING_RTT_MULTIPLIER
     /// range multiplied by the given round trip time. This duration represents the amount of time
     /// to wait before an ECN-CE marked packet should be sent, to test if CE reports are being
@@ -199,6 +231,13 @@ impl Controller {
         matches!(self.state, State::Capable(_))
     }
 
+    /// Returns the current ECN validation state of the path
+    #[inline]
+    pub fn state(&self) -> event::api::EcnState {
+        let state: event::builder::EcnState = (&self.state).into_event();
+        state.into_event()
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.2
     //# Network routing and path elements can change mid-connection; an endpoint
     //# MUST disable ECN if validation later fails.
@@ -272,7 +311,8 @@ impl Controller {
         //# (see Section 13.4.2.1) causes the ECN state for the path to become "capable",
         //# unless no marked packet has been acknowledged.
         if matches!(self.state, State::Unknown)
-            && newly_acked_ecn_counts.ect_0_count > VarInt::from_u8(0)
+            && (newly_acked_ecn_counts.ect_0_count > VarInt::from_u8(0)
+                || newly_acked_ecn_counts.ect_1_count > VarInt::from_u8(0))
         {
             // Arm the ce suppression timer to send a ECN-CE marked packet to test for
             // CE suppression by the peer.
@@ -298,7 +338,14 @@ impl Controller {
         let ect_0_increase = incremental_ecn_counts
             .ect_0_count
             .saturating_add(incremental_ecn_counts.ce_count);
+
+        // The same check applies to packets sent with ECT(1) by L4S congestion controllers
+        let ect_1_increase = incremental_ecn_counts
+            .ect_1_count
+            .saturating_add(incremental_ecn_counts.ce_count);
+
         ect_0_increase < newly_acked_ecn_counts.ect_0_count
+            || ect_1_increase < newly_acked_ecn_counts.ect_1_count
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
@@ -343,8 +390,8 @@ impl Controller {
         publisher: &mut Pub,
     ) {
         debug_assert!(
-            !matches!(ecn, ExplicitCongestionNotification::Ect1),
-            "Ect1 is not used"
+            !matches!(ecn, ExplicitCongestionNotification::Ect1) || self.l4s,
+            "Ect1 is only used by L4S congestion controllers"
         );
 
         if let (true, State::Testing(ref mut packet_count)) = (ecn.using_ecn(), &mut self.state) {
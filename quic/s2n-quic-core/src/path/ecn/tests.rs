@@ -191,6 +191,49 @@ fn ecn() {
     }
 }
 
+#[test]
+fn ecn_l4s() {
+    let now = time::now();
+    let mut controller = Controller::l4s();
+
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+
+    let mut ce_suppression_timer = Timer::default();
+    ce_suppression_timer.set(now + Duration::from_secs(10));
+    controller.state = State::Capable(ce_suppression_timer);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+
+    controller.state = State::Unknown;
+    assert_eq!(
+        ExplicitCongestionNotification::NotEct,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+}
+
+#[test]
+fn set_l4s() {
+    let now = time::now();
+    let mut controller = Controller::default();
+
+    controller.set_l4s(true);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+
+    controller.set_l4s(false);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect0,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+}
+
 #[test]
 fn ecn_ce_suppression() {
     let now = time::now();
@@ -342,6 +385,28 @@ fn validate_ecn_ce_remarking() {
     assert!(matches!(controller.state, State::Failed(_)));
 }
 
+#[test]
+fn validate_ect_1_ce_remarking() {
+    let mut publisher = Publisher::snapshot();
+    let mut controller = Controller::l4s();
+    let now = time::now();
+    let expected_ecn_counts = helper_ecn_counts(0, 1, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(0, 1, 0);
+    let outcome = controller.validate(
+        expected_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(EcnCounts::default()),
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Failed, outcome);
+    assert!(matches!(controller.state, State::Failed(_)));
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
 //= type=test
 //# ECN validation can fail if the received total count for either ECT(0) or ECT(1)
@@ -562,6 +627,36 @@ fn validate_capable() {
     }
 }
 
+#[test]
+fn validate_capable_l4s() {
+    let mut publisher = Publisher::snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Controller::l4s()
+    };
+    let now = time::now();
+    let expected_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let ack_frame_ecn_counts = helper_ecn_counts(0, 1, 1);
+    let sent_packet_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let outcome = controller.validate(
+        expected_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::from_millis(50),
+        Path::test(),
+        &mut publisher,
+    );
+
+    // ECT(1) packets being marked CE by an L4S node is not a validation failure
+    assert_eq!(
+        ValidationOutcome::CongestionExperienced(VarInt::from_u8(1)),
+        outcome
+    );
+    assert!(controller.is_capable());
+}
+
 #[test]
 fn validate_capable_congestion_experienced() {
     let mut publisher = Publisher::snapshot();
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    event,
+    connection, event,
     event::{
         api::{Path, SocketAddress},
         IntoEvent,
@@ -106,6 +106,62 @@ impl IntoEvent<event::builder::ConnectionMigrationDenied> for DenyReason {
     }
 }
 
+/// The reason a migration requested by the application could not be started
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Only clients are allowed to initiate a migration
+    NotSupported,
+    /// The handshake has not been confirmed yet
+    HandshakeNotConfirmed,
+    /// The peer sent the `disable_active_migration` transport parameter
+    DisabledByPeer,
+    /// The connection is already using the requested local address
+    AddressUnchanged,
+    /// Another migration is still being validated
+    InProgress,
+    /// The peer has not provided an unused connection ID for the new path
+    InsufficientConnectionIds,
+    /// The maximum number of paths per connection was exceeded
+    PathLimitExceeded,
+    /// The connection was closed
+    Connection(connection::Error),
+}
+
+impl core::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "only clients can initiate a connection migration"),
+            Self::HandshakeNotConfirmed => write!(
+                f,
+                "a connection migration cannot be initiated before the handshake is confirmed"
+            ),
+            Self::DisabledByPeer => write!(f, "the peer disabled active connection migration"),
+            Self::AddressUnchanged => {
+                write!(f, "the connection is already using the local address")
+            }
+            Self::InProgress => write!(f, "a connection migration is already in progress"),
+            Self::InsufficientConnectionIds => write!(
+                f,
+                "the peer has not provided a connection ID that can be used for a new path"
+            ),
+            Self::PathLimitExceeded => {
+                write!(f, "the maximum number of paths per connection was exceeded")
+            }
+            Self::Connection(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<connection::Error> for Error {
+    #[inline]
+    fn from(error: connection::Error) -> Self {
+        Self::Connection(error)
+    }
+}
+
 /// Validates a path migration attempt from an active path to another
 pub trait Validator: 'static + Send {
     /// Called on each connection migration attempt for a connection
@@ -778,6 +778,7 @@ impl Controller {
 
                 if self.black_hole_counter > BLACK_HOLE_THRESHOLD {
                     return self.on_black_hole_detected(
+                        MtuUpdatedCause::Blackhole,
                         now,
                         congestion_controller,
                         path_id,
@@ -790,6 +791,110 @@ impl Controller {
         MtuResult::NoChange
     }
 
+    //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+    //# PTB messages that have been validated MAY be utilized by the DPLPMTUD
+    //# algorithm but MUST NOT be used directly to set the PLPMTU.
+    /// This method gets called when the network reports that a packet sent on the path was
+    /// too big for a link on the path
+    ///
+    /// `mtu` is the link MTU reported by the ICMP message, including IP and UDP headers. The
+    /// caller is responsible for validating that the ICMP message quoted a packet that was sent
+    /// on this path.
+    #[inline]
+    pub fn on_packet_too_big<CC: CongestionController, Pub: event::ConnectionPublisher>(
+        &mut self,
+        mtu: u16,
+        peer_socket_address: &inet::SocketAddress,
+        now: Timestamp,
+        congestion_controller: &mut CC,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) -> MtuResult {
+        let min_ip_header_len = match peer_socket_address {
+            inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+            inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+        };
+        let ptb_size = mtu.saturating_sub(UDP_HEADER_LEN + min_ip_header_len);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# An endpoint MUST ignore an ICMP message that claims the PMTU has
+        //# decreased below QUIC's smallest allowed maximum datagram size.
+        ensure!(ptb_size >= self.base_plpmtu, MtuResult::NoChange);
+
+        match self.state {
+            State::Disabled => {}
+            State::EarlySearchRequested => {
+                ensure!(ptb_size < self.plpmtu, MtuResult::NoChange);
+
+                // The configured initial MTU is too large for the path, so drop
+                // back down to the base PLPMTU as if an initial packet was lost
+                self.plpmtu = self.base_plpmtu;
+                self.max_probe_size = self.max_probe_size.min(ptb_size);
+                self.update_probed_size();
+
+                congestion_controller.on_mtu_update(
+                    self.plpmtu,
+                    &mut congestion_controller::PathPublisher::new(publisher, path_id),
+                );
+
+                if self.is_next_probe_size_above_threshold() {
+                    self.state = State::Disabled;
+                } else {
+                    self.state = State::SearchComplete;
+                }
+
+                publisher.on_mtu_updated(event::builder::MtuUpdated {
+                    path_id: path_id.into_event(),
+                    mtu: self.plpmtu,
+                    cause: MtuUpdatedCause::PacketTooBig,
+                    search_complete: self.state.is_search_complete(),
+                });
+
+                return MtuResult::MtuUpdated(self.plpmtu);
+            }
+            State::Searching(_, _) | State::SearchComplete | State::SearchRequested => {
+                if ptb_size < self.plpmtu {
+                    //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+                    //# The PLPMTU SHOULD
+                    //# be set to BASE_PLPMTU (the PLPMTU is reduced to the BASE_PLPMTU
+                    //# to avoid unnecessary packet loss when a black hole is
+                    //# encountered).
+
+                    // Packets of the current PLPMTU are not making it through, so treat
+                    // this the same as a black hole without waiting for the losses
+                    return self.on_black_hole_detected(
+                        MtuUpdatedCause::PacketTooBig,
+                        now,
+                        congestion_controller,
+                        path_id,
+                        publisher,
+                    );
+                }
+
+                //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+                //# *  The PL can use the reported PL_PTB_SIZE from the PTB message as
+                //#    the next search point when it resumes the search algorithm.
+                if !self.state.is_search_complete() && ptb_size < self.probed_size {
+                    // Limit the search to sizes the path has reported it can carry
+                    self.max_probe_size = ptb_size;
+                    self.update_probed_size();
+                    self.request_new_search(None);
+
+                    if self.is_search_completed() {
+                        publisher.on_mtu_updated(event::builder::MtuUpdated {
+                            path_id: path_id.into_event(),
+                            mtu: self.plpmtu,
+                            cause: MtuUpdatedCause::PacketTooBig,
+                            search_complete: true,
+                        })
+                    }
+                }
+            }
+        }
+
+        MtuResult::NoChange
+    }
+
     /// Gets the currently validated maximum QUIC datagram size
     ///
     /// This does not include the size of UDP and IP headers.
@@ -851,9 +956,11 @@ impl Controller {
     }
 
     /// Called when an excessive number of packets larger than the BASE_PLPMTU have been lost
+    /// or the network reported that packets of the current PLPMTU are too big for the path
     #[inline]
     fn on_black_hole_detected<CC: CongestionController, Pub: event::ConnectionPublisher>(
         &mut self,
+        cause: MtuUpdatedCause,
         now: Timestamp,
         congestion_controller: &mut CC,
         path_id: path::Id,
@@ -875,7 +982,7 @@ impl Controller {
         publisher.on_mtu_updated(event::builder::MtuUpdated {
             path_id: path_id.into_event(),
             mtu: self.plpmtu,
-            cause: MtuUpdatedCause::Blackhole,
+            cause,
             search_complete: self.state.is_search_complete(),
         });
 
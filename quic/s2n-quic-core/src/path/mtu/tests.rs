@@ -891,6 +891,126 @@ fn on_packet_loss_initial_mtu_configured() {
     }
 }
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+//= type=test
+//# An endpoint MUST ignore an ICMP message that claims the PMTU has
+//# decreased below QUIC's smallest allowed maximum datagram size.
+#[test]
+fn on_packet_too_big_below_base_plpmtu() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::snapshot();
+    controller.plpmtu = 1472;
+    controller.enable();
+    let state = controller.state.clone();
+    let addr =
+        inet::SocketAddress::IpV4(SocketAddressV4::new(IpV4Address::new([127, 0, 0, 1]), 443));
+
+    // 20 byte IPv4 header + 8 byte UDP header
+    let result = controller.on_packet_too_big(
+        MINIMUM_MAX_DATAGRAM_SIZE + 28 - 1,
+        &addr,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(MtuResult::NoChange, result);
+    assert_eq!(0, cc.on_mtu_update);
+    assert_eq!(1472, controller.plpmtu);
+    assert_eq!(state, controller.state);
+}
+
+//= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+//= type=test
+//# The PLPMTU SHOULD
+//# be set to BASE_PLPMTU (the PLPMTU is reduced to the BASE_PLPMTU
+//# to avoid unnecessary packet loss when a black hole is
+//# encountered).
+#[test]
+fn on_packet_too_big_black_hole() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::snapshot();
+    controller.plpmtu = 1472;
+    controller.enable();
+    let base_plpmtu = controller.base_plpmtu;
+    let addr =
+        inet::SocketAddress::IpV4(SocketAddressV4::new(IpV4Address::new([127, 0, 0, 1]), 443));
+
+    let result = controller.on_packet_too_big(
+        1400,
+        &addr,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(MtuResult::MtuUpdated(base_plpmtu), result);
+    assert_eq!(1, cc.on_mtu_update);
+    assert_eq!(base_plpmtu, controller.plpmtu);
+    assert_eq!(State::SearchComplete, controller.state);
+    assert_eq!(
+        Some(now + BLACK_HOLE_COOL_OFF_DURATION),
+        controller.pmtu_raise_timer.next_expiration()
+    );
+}
+
+//= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+//= type=test
+//# *  The PL can use the reported PL_PTB_SIZE from the PTB message as
+//#    the next search point when it resumes the search algorithm.
+#[test]
+fn on_packet_too_big_probe() {
+    let mut controller = new_controller(1500);
+    let pn = pn(1);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::snapshot();
+    controller.enable();
+    controller.state = State::Searching(pn, now);
+    assert_eq!(1472, controller.probed_size);
+    let addr =
+        inet::SocketAddress::IpV4(SocketAddressV4::new(IpV4Address::new([127, 0, 0, 1]), 443));
+
+    let result = controller.on_packet_too_big(
+        1400,
+        &addr,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(MtuResult::NoChange, result);
+    assert_eq!(0, cc.on_mtu_update);
+    assert_eq!(MINIMUM_MAX_DATAGRAM_SIZE, controller.plpmtu);
+    assert_eq!(1372, controller.max_probe_size);
+    assert_eq!(
+        MINIMUM_MAX_DATAGRAM_SIZE + (1372 - MINIMUM_MAX_DATAGRAM_SIZE) / 2,
+        controller.probed_size
+    );
+    assert_eq!(State::SearchRequested, controller.state);
+
+    // A packet too big message within the probe threshold of the PLPMTU completes the search
+    let result = controller.on_packet_too_big(
+        MINIMUM_MAX_DATAGRAM_SIZE + 28 + PROBE_THRESHOLD - 1,
+        &addr,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(MtuResult::NoChange, result);
+    assert_eq!(0, cc.on_mtu_update);
+    assert!(controller.is_search_completed());
+}
+
 //= https://www.rfc-editor.org/rfc/rfc8899#section-5.2
 //= type=test
 //# When used with an
@@ -60,6 +60,10 @@ const HEADROOM: Ratio<u64> = Ratio::new_raw(85, 100);
 //# that follow an "ACK every other packet" delayed-ACK policy: 4 * SMSS.
 const MIN_PIPE_CWND_PACKETS: u16 = 4;
 
+/// The number of acknowledgements requested from the peer per congestion window once the
+/// bottleneck bandwidth has been estimated
+const ACKS_PER_CWND: u32 = 4;
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.1.1
 //# The following state transition diagram summarizes the flow of control and the relationship between the different states:
 //#
@@ -565,6 +569,27 @@ impl CongestionController for BbrCongestionController {
     fn send_quantum(&self) -> Option<usize> {
         Some(self.pacer.send_quantum())
     }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        Some(self.pacer.pacing_rate())
+    }
+
+    #[inline]
+    fn ack_eliciting_threshold(&self) -> Option<u32> {
+        // Timely acknowledgements are needed while the bandwidth is still being probed in
+        // Startup and while recovering from loss, so the default frequency is used until then
+        if !self.full_pipe_estimator.filled_pipe() || self.recovery_state.in_recovery() {
+            return None;
+        }
+
+        let cwnd_packets = self.cwnd / self.max_datagram_size as u32;
+        let threshold = (cwnd_packets / ACKS_PER_CWND).max(1);
+
+        // Round down to a power of two so small changes in the congestion window
+        // don't result in a new request to the peer
+        Some(1 << threshold.ilog2())
+    }
 }
 
 impl BbrCongestionController {
@@ -1139,7 +1164,7 @@ impl BbrCongestionController {
 }
 
 #[non_exhaustive]
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Endpoint {
     app_settings: ApplicationSettings,
 }
@@ -100,7 +100,7 @@ impl State {
     }
 
     /// True if currently in recovery
-    #[cfg(test)]
+    #[inline]
     pub fn in_recovery(&self) -> bool {
         *self != State::Recovered
     }
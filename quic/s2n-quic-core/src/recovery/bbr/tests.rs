@@ -664,6 +664,32 @@ fn set_cwnd_filled_pipe() {
     assert!(bbr.try_fast_path);
 }
 
+#[test]
+fn ack_eliciting_threshold() {
+    let mut bbr = BbrCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+    let now = NoopClock.get_time();
+
+    // the default ACK frequency is used until the pipe is filled
+    bbr.cwnd = 100 * MINIMUM_MAX_DATAGRAM_SIZE as u32;
+    assert_eq!(None, bbr.ack_eliciting_threshold());
+
+    bbr.full_pipe_estimator.set_filled_pipe_for_test(true);
+    // 100 packets / 4 = 25, rounded down to 16
+    assert_eq!(Some(16), bbr.ack_eliciting_threshold());
+
+    bbr.cwnd = 4 * MINIMUM_MAX_DATAGRAM_SIZE as u32;
+    assert_eq!(Some(1), bbr.ack_eliciting_threshold());
+
+    bbr.cwnd = MINIMUM_MAX_DATAGRAM_SIZE as u32;
+    assert_eq!(Some(1), bbr.ack_eliciting_threshold());
+
+    // the default ACK frequency is used while in recovery
+    bbr.cwnd = 100 * MINIMUM_MAX_DATAGRAM_SIZE as u32;
+    bbr.recovery_state.on_congestion_event(now);
+    assert!(bbr.recovery_state.in_recovery());
+    assert_eq!(None, bbr.ack_eliciting_threshold());
+}
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.4.6
 //= type=test
 //#     else if (cwnd < BBR.max_inflight || C.delivered < InitialCwnd)
@@ -32,6 +32,7 @@ pub trait Endpoint: 'static + Debug + Send {
 pub struct PathInfo<'a> {
     pub remote_address: SocketAddress<'a>,
     pub application_protocol: Option<&'a [u8]>,
+    pub server_name: Option<&'a str>,
     pub max_datagram_size: u16,
 }
 
@@ -41,9 +42,24 @@ impl<'a> PathInfo<'a> {
         Self {
             remote_address: remote_address.into_event(),
             application_protocol: None,
+            server_name: None,
             max_datagram_size: mtu_config.initial_mtu().max_datagram_size(remote_address),
         }
     }
+
+    /// Sets the application protocol negotiated during the handshake
+    #[inline]
+    pub fn with_application_protocol(mut self, application_protocol: Option<&'a [u8]>) -> Self {
+        self.application_protocol = application_protocol;
+        self
+    }
+
+    /// Sets the server name negotiated during the handshake
+    #[inline]
+    pub fn with_server_name(mut self, server_name: Option<&'a str>) -> Self {
+        self.server_name = server_name;
+        self
+    }
 }
 
 pub trait Publisher {
@@ -243,6 +259,42 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// The rate at which the congestion controller is currently pacing packets.
+    ///
+    /// If the value is `None`, the congestion controller is not pacing packets.
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        None
+    }
+
+    /// The number of ack-eliciting packets the peer may receive before sending an acknowledgement.
+    ///
+    /// If the value is `None`, the peer uses its default acknowledgement frequency. The value
+    /// is only sent to peers that support the ACK frequency extension.
+    fn ack_eliciting_threshold(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns `true` if the congestion controller implements a scalable congestion response
+    /// suitable for Low Latency, Low Loss, and Scalable throughput (L4S) networks
+    ///
+    /// Packets sent by L4S congestion controllers are marked with the ECT(1) codepoint rather
+    /// than ECT(0).
+    fn is_l4s(&self) -> bool {
+        false
+    }
+
+    /// Invoked once the application protocol and server name have been negotiated
+    /// during the handshake
+    fn on_handshake_info(&mut self, _application_protocol: &[u8], _server_name: Option<&str>) {}
+
+    /// Invoked on the congestion controller of a new path with the congestion controller
+    /// of the active path
+    ///
+    /// This allows settings changed by the application on the active path, such as the
+    /// congestion control algorithm, to be carried over to the new path. Congestion state,
+    /// such as the congestion window, should not be carried over.
+    fn inherit_settings(&mut self, _active_path: &Self) {}
 }
 
 // Prevent implementation of the `CongestionController` trait if the
@@ -261,6 +313,10 @@ mod private {
             // Otherwise only allow the included CUBIC and BBRv2 congestion controllers
             impl Sealed for crate::recovery::CubicCongestionController {}
             impl Sealed for crate::recovery::bbr::BbrCongestionController {}
+            #[cfg(feature = "alloc")]
+            impl Sealed for crate::recovery::dynamic::DynamicCongestionController {}
+            impl Sealed for crate::recovery::prague::PragueCongestionController {}
+            impl Sealed for crate::recovery::reno::RenoCongestionController {}
         }
     );
 }
@@ -420,6 +476,7 @@ pub mod testing {
             pub app_limited: Option<bool>,
             pub slow_start: bool,
             pub remote_address: RemoteAddress,
+            pub earliest_departure_time: Option<Timestamp>,
         }
 
         impl Default for CongestionController {
@@ -439,6 +496,7 @@ pub mod testing {
                     app_limited: None,
                     slow_start: true,
                     remote_address: RemoteAddress::default(),
+                    earliest_departure_time: None,
                 }
             }
         }
@@ -558,7 +616,7 @@ pub mod testing {
             }
 
             fn earliest_departure_time(&self) -> Option<Timestamp> {
-                None
+                self.earliest_departure_time
             }
         }
     }
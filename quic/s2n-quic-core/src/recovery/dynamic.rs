@@ -0,0 +1,606 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A congestion controller that allows the congestion control algorithm to be
+//! selected for each path, and changed by the application during the connection.
+
+use crate::{
+    application::ServerName,
+    event::IntoEvent,
+    inet, path, random,
+    recovery::{
+        bandwidth,
+        bbr::{self, BbrCongestionController},
+        congestion_controller::{self, CongestionController, Publisher},
+        cubic::{self, CubicCongestionController},
+        prague::{self, PragueCongestionController},
+        reno::{self, RenoCongestionController},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use alloc::sync::Arc;
+use bytes::Bytes;
+use core::fmt::Debug;
+
+pub use congestion_controller::PathInfo;
+
+/// A congestion control algorithm, along with any application provided overrides
+///
+/// Each variant contains the endpoint for the algorithm, which can be configured
+/// with the corresponding builder.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum Algorithm {
+    Cubic(cubic::Endpoint),
+    Bbr(bbr::Endpoint),
+    Reno(reno::Endpoint),
+    Prague(prague::Endpoint),
+}
+
+impl Default for Algorithm {
+    #[inline]
+    fn default() -> Self {
+        Self::Cubic(Default::default())
+    }
+}
+
+impl From<cubic::Endpoint> for Algorithm {
+    #[inline]
+    fn from(endpoint: cubic::Endpoint) -> Self {
+        Self::Cubic(endpoint)
+    }
+}
+
+impl From<bbr::Endpoint> for Algorithm {
+    #[inline]
+    fn from(endpoint: bbr::Endpoint) -> Self {
+        Self::Bbr(endpoint)
+    }
+}
+
+impl From<reno::Endpoint> for Algorithm {
+    #[inline]
+    fn from(endpoint: reno::Endpoint) -> Self {
+        Self::Reno(endpoint)
+    }
+}
+
+impl From<prague::Endpoint> for Algorithm {
+    #[inline]
+    fn from(endpoint: prague::Endpoint) -> Self {
+        Self::Prague(endpoint)
+    }
+}
+
+impl Algorithm {
+    #[inline]
+    fn new_controller(&mut self, path_info: PathInfo) -> Controller {
+        use congestion_controller::Endpoint as _;
+
+        match self {
+            Self::Cubic(endpoint) => {
+                Controller::Cubic(endpoint.new_congestion_controller(path_info))
+            }
+            Self::Bbr(endpoint) => Controller::Bbr(endpoint.new_congestion_controller(path_info)),
+            Self::Reno(endpoint) => Controller::Reno(endpoint.new_congestion_controller(path_info)),
+            Self::Prague(endpoint) => {
+                Controller::Prague(endpoint.new_congestion_controller(path_info))
+            }
+        }
+    }
+}
+
+/// Selects the congestion control algorithm used on a path
+///
+/// The selector is invoked with the remote address and MTU of each path as it is created.
+/// The application protocol and server name are only included for paths created after the
+/// handshake has completed. For the initial path, the selector is invoked again once the
+/// handshake completes, unless an algorithm was already set with
+/// [`DynamicCongestionController::set_algorithm`]. If the same kind of algorithm is selected
+/// again, the current controller is kept.
+pub trait Selector: 'static + Send + Sync + Debug {
+    fn select(&self, path_info: &PathInfo) -> Algorithm;
+}
+
+/// Selects the same algorithm for every path
+impl Selector for Algorithm {
+    #[inline]
+    fn select(&self, _path_info: &PathInfo) -> Algorithm {
+        self.clone()
+    }
+}
+
+/// A congestion controller endpoint that creates a [`DynamicCongestionController`]
+/// for each path using the algorithm chosen by the `Selector`
+#[derive(Debug, Default)]
+pub struct Endpoint<S: Selector = Algorithm> {
+    selector: Arc<S>,
+}
+
+impl<S: Selector> Endpoint<S> {
+    #[inline]
+    pub fn new(selector: S) -> Self {
+        Self {
+            selector: Arc::new(selector),
+        }
+    }
+}
+
+impl<S: Selector> congestion_controller::Endpoint for Endpoint<S> {
+    type CongestionController = DynamicCongestionController;
+
+    fn new_congestion_controller(&mut self, path_info: PathInfo) -> Self::CongestionController {
+        let algorithm = self.selector.select(&path_info);
+        let mut controller = DynamicCongestionController::new(algorithm, path_info);
+        controller.selector = Some(self.selector.clone());
+        controller
+    }
+}
+
+// The controller is stored inline on each path, the same as when using BBR directly, to avoid
+// an allocation and indirection on every congestion control event.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+enum Controller {
+    Cubic(CubicCongestionController),
+    Bbr(BbrCongestionController),
+    Reno(RenoCongestionController),
+    Prague(PragueCongestionController),
+}
+
+/// Forwards a method call to the congestion controller for the current algorithm
+macro_rules! dispatch {
+    ($controller:expr, $cc:ident => $call:expr) => {
+        match $controller {
+            Controller::Cubic($cc) => $call,
+            Controller::Bbr($cc) => $call,
+            Controller::Reno($cc) => $call,
+            Controller::Prague($cc) => $call,
+        }
+    };
+}
+
+/// Metadata tracked for each sent packet
+#[derive(Clone, Copy, Debug)]
+pub struct PacketInfo {
+    /// The generation of the congestion controller that sent the packet
+    generation: u16,
+    /// Bandwidth-related data, if the packet was sent by BBR
+    bbr: Option<bandwidth::PacketInfo>,
+}
+
+/// A congestion controller that delegates to the algorithm selected for the path
+///
+/// The algorithm can be changed by the application at any time with
+/// [`Self::set_algorithm`]. The change takes effect when the next packet is sent,
+/// at which point the bytes in flight are carried over to the new controller.
+#[derive(Clone, Debug)]
+pub struct DynamicCongestionController {
+    controller: Controller,
+    /// The algorithm to switch to when the next packet is sent
+    pending: Option<Algorithm>,
+    /// The algorithm most recently set by the application, which is carried over to new paths
+    selected: Option<Algorithm>,
+    /// The selector that chose the initial algorithm, if created by an [`Endpoint`]
+    selector: Option<Arc<dyn Selector>>,
+    /// Incremented each time the algorithm is switched to identify
+    /// packets sent by a previous controller
+    generation: u16,
+    /// The packet info returned by the current controller for the bytes in flight
+    /// carried over from the previous controller
+    inherited_packet_info: Option<bandwidth::PacketInfo>,
+    remote_address: path::RemoteAddress,
+    application_protocol: Option<Bytes>,
+    server_name: Option<ServerName>,
+    max_datagram_size: u16,
+}
+
+impl CongestionController for DynamicCongestionController {
+    type PacketInfo = PacketInfo;
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        dispatch!(&self.controller, cc => cc.congestion_window())
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        dispatch!(&self.controller, cc => cc.bytes_in_flight())
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        dispatch!(&self.controller, cc => cc.is_congestion_limited())
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        dispatch!(&self.controller, cc => cc.requires_fast_retransmission())
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        sent_bytes: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) -> Self::PacketInfo {
+        if let Some(algorithm) = self.pending.take() {
+            self.switch(algorithm, time_sent, app_limited, rtt_estimator, publisher);
+        }
+
+        let bbr = self.controller.on_packet_sent(
+            time_sent,
+            sent_bytes,
+            app_limited,
+            rtt_estimator,
+            publisher,
+        );
+
+        PacketInfo {
+            generation: self.generation,
+            bbr,
+        }
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(&mut self.controller, cc => cc.on_rtt_update(time_sent, now, rtt_estimator, publisher))
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        newest_acked_packet_info: Self::PacketInfo,
+        rtt_estimator: &RttEstimator,
+        random_generator: &mut dyn random::Generator,
+        ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        match &mut self.controller {
+            Controller::Bbr(cc) => match Self::bbr_packet_info(
+                self.generation,
+                self.inherited_packet_info,
+                newest_acked_packet_info,
+            ) {
+                Some(packet_info) => cc.on_ack(
+                    newest_acked_time_sent,
+                    bytes_acknowledged,
+                    packet_info,
+                    rtt_estimator,
+                    random_generator,
+                    ack_receive_time,
+                    publisher,
+                ),
+                // Without packet info the acknowledgement can't be used to estimate the
+                // bandwidth, but the bytes are still removed from the bytes in flight
+                None => cc.on_packet_discarded(bytes_acknowledged, publisher),
+            },
+            Controller::Cubic(cc) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            Controller::Reno(cc) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            Controller::Prague(cc) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        new_loss_burst: bool,
+        random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        match &mut self.controller {
+            Controller::Bbr(cc) => {
+                match Self::bbr_packet_info(
+                    self.generation,
+                    self.inherited_packet_info,
+                    packet_info,
+                ) {
+                    Some(packet_info) => cc.on_packet_lost(
+                        lost_bytes,
+                        packet_info,
+                        persistent_congestion,
+                        new_loss_burst,
+                        random_generator,
+                        timestamp,
+                        publisher,
+                    ),
+                    None => cc.on_packet_discarded(lost_bytes as usize, publisher),
+                }
+            }
+            Controller::Cubic(cc) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            Controller::Reno(cc) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            Controller::Prague(cc) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+        }
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(&mut self.controller, cc => cc.on_explicit_congestion(ce_count, event_time, publisher))
+    }
+
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_datagram_size: u16, publisher: &mut Pub) {
+        self.max_datagram_size = max_datagram_size;
+        dispatch!(&mut self.controller, cc => cc.on_mtu_update(max_datagram_size, publisher))
+    }
+
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, publisher: &mut Pub) {
+        dispatch!(&mut self.controller, cc => cc.on_packet_discarded(bytes_sent, publisher))
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        dispatch!(&self.controller, cc => cc.earliest_departure_time())
+    }
+
+    #[inline]
+    fn send_quantum(&self) -> Option<usize> {
+        dispatch!(&self.controller, cc => cc.send_quantum())
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<bandwidth::Bandwidth> {
+        dispatch!(&self.controller, cc => CongestionController::pacing_rate(cc))
+    }
+
+    #[inline]
+    fn ack_eliciting_threshold(&self) -> Option<u32> {
+        dispatch!(&self.controller, cc => cc.ack_eliciting_threshold())
+    }
+
+    #[inline]
+    fn is_l4s(&self) -> bool {
+        // Packets are marked for the pending algorithm, since it takes
+        // effect before the next packet is sent
+        if let Some(algorithm) = &self.pending {
+            return matches!(algorithm, Algorithm::Prague(_));
+        }
+
+        dispatch!(&self.controller, cc => cc.is_l4s())
+    }
+
+    #[inline]
+    fn on_handshake_info(&mut self, application_protocol: &[u8], server_name: Option<&str>) {
+        self.application_protocol = Some(Bytes::copy_from_slice(application_protocol));
+        self.server_name = server_name.map(ServerName::from);
+
+        // The initial algorithm was selected before the application protocol and server
+        // name were known, so give the selector another chance unless the application
+        // already chose an algorithm
+        if self.selected.is_some() {
+            return;
+        }
+
+        if let Some(selector) = self.selector.clone() {
+            let algorithm = selector.select(&self.path_info());
+            if !self.controller.is_algorithm(&algorithm) {
+                self.pending = Some(algorithm);
+            }
+        }
+    }
+
+    #[inline]
+    fn inherit_settings(&mut self, active_path: &Self) {
+        if let Some(algorithm) = &active_path.selected {
+            self.set_algorithm(algorithm.clone());
+        }
+    }
+}
+
+impl DynamicCongestionController {
+    pub fn new(mut algorithm: Algorithm, path_info: PathInfo) -> Self {
+        let remote_address = path_info.remote_address.clone().into();
+        let application_protocol = path_info.application_protocol.map(Bytes::copy_from_slice);
+        let server_name = path_info.server_name.map(ServerName::from);
+        let max_datagram_size = path_info.max_datagram_size;
+
+        Self {
+            controller: algorithm.new_controller(path_info),
+            pending: None,
+            selected: None,
+            selector: None,
+            generation: 0,
+            inherited_packet_info: None,
+            remote_address,
+            application_protocol,
+            server_name,
+            max_datagram_size,
+        }
+    }
+
+    /// Changes the congestion control algorithm used on the path
+    ///
+    /// The new algorithm takes effect when the next packet is sent. Any
+    /// state learned by the current algorithm, such as the congestion window
+    /// and bandwidth estimate, is discarded.
+    ///
+    /// Paths created after this call start with the same algorithm.
+    #[inline]
+    pub fn set_algorithm<A: Into<Algorithm>>(&mut self, algorithm: A) {
+        let algorithm = algorithm.into();
+        self.selected = Some(algorithm.clone());
+        self.pending = Some(algorithm);
+    }
+
+    /// Returns `true` if the algorithm has been changed but has not yet taken effect
+    #[inline]
+    pub fn is_algorithm_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Replaces the current controller with one using the given `algorithm`
+    #[inline]
+    fn switch<Pub: Publisher>(
+        &mut self,
+        mut algorithm: Algorithm,
+        now: Timestamp,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        let bytes_in_flight = self.bytes_in_flight();
+        let mut controller = algorithm.new_controller(self.path_info());
+
+        // Carry over the bytes in flight so acknowledgements and losses of packets sent
+        // by the previous controller are accounted for by the new controller
+        self.inherited_packet_info = controller.on_packet_sent(
+            now,
+            bytes_in_flight as usize,
+            app_limited,
+            rtt_estimator,
+            publisher,
+        );
+        self.controller = controller;
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    #[inline]
+    fn path_info(&self) -> PathInfo<'_> {
+        let remote_address: &inet::SocketAddress = &self.remote_address.0;
+        PathInfo {
+            remote_address: remote_address.into_event(),
+            application_protocol: self.application_protocol.as_deref(),
+            server_name: self.server_name.as_deref(),
+            max_datagram_size: self.max_datagram_size,
+        }
+    }
+
+    /// Returns the BBR packet info for a packet acknowledged or lost while BBR is the
+    /// current algorithm, if it is known
+    #[inline]
+    fn bbr_packet_info(
+        generation: u16,
+        inherited_packet_info: Option<bandwidth::PacketInfo>,
+        packet_info: PacketInfo,
+    ) -> Option<bandwidth::PacketInfo> {
+        if packet_info.generation == generation {
+            // After the generation wraps around, a packet sent by an older controller
+            // can have the current generation, in which case it has no BBR packet info
+            // and falls back to the inherited info
+            packet_info.bbr.or(inherited_packet_info)
+        } else {
+            // the packet was sent by a previous controller and is
+            // part of the inherited bytes in flight
+            inherited_packet_info
+        }
+    }
+}
+
+impl Controller {
+    /// Returns `true` if the controller implements the given algorithm
+    #[inline]
+    fn is_algorithm(&self, algorithm: &Algorithm) -> bool {
+        matches!(
+            (self, algorithm),
+            (Self::Cubic(_), Algorithm::Cubic(_))
+                | (Self::Bbr(_), Algorithm::Bbr(_))
+                | (Self::Reno(_), Algorithm::Reno(_))
+                | (Self::Prague(_), Algorithm::Prague(_))
+        )
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        sent_bytes: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) -> Option<bandwidth::PacketInfo> {
+        match self {
+            Self::Bbr(cc) => Some(cc.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+            Self::Cubic(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                None
+            }
+            Self::Reno(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                None
+            }
+            Self::Prague(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,320 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    event,
+    inet::{IpV4Address, IpV6Address, SocketAddress},
+    path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    recovery::congestion_controller::{Endpoint as _, PathPublisher},
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MDS: usize = MINIMUM_MAX_DATAGRAM_SIZE as usize;
+
+fn new_controller(algorithm: Algorithm) -> DynamicCongestionController {
+    let remote_address = SocketAddress::default();
+    let path_info = PathInfo::new(&path::Config::default(), &remote_address);
+    DynamicCongestionController::new(algorithm, path_info)
+}
+
+/// Selects BBR for IPv6 peers and CUBIC otherwise
+#[derive(Debug)]
+struct TestSelector;
+
+impl Selector for TestSelector {
+    fn select(&self, path_info: &PathInfo) -> Algorithm {
+        match path_info.remote_address {
+            event::api::SocketAddress::IpV6 { .. } => bbr::Endpoint::default().into(),
+            event::api::SocketAddress::IpV4 { .. } => cubic::Endpoint::default().into(),
+        }
+    }
+}
+
+#[test]
+fn selector_test() {
+    let mut endpoint = Endpoint::new(TestSelector);
+    let mtu_config = path::Config::default();
+
+    let remote_address = IpV4Address::new([127, 0, 0, 1]).with_port(443).into();
+    let cc = endpoint.new_congestion_controller(PathInfo::new(&mtu_config, &remote_address));
+    assert!(matches!(cc.controller, Controller::Cubic(_)));
+
+    let remote_address = IpV6Address::new([1; 16]).with_port(443).into();
+    let cc = endpoint.new_congestion_controller(PathInfo::new(&mtu_config, &remote_address));
+    assert!(matches!(cc.controller, Controller::Bbr(_)));
+}
+
+/// Selects BBR for connections that negotiated the `bbr` application protocol
+#[derive(Debug)]
+struct AlpnSelector;
+
+impl Selector for AlpnSelector {
+    fn select(&self, path_info: &PathInfo) -> Algorithm {
+        match path_info.application_protocol {
+            Some(b"bbr") => bbr::Endpoint::default().into(),
+            _ => cubic::Endpoint::default().into(),
+        }
+    }
+}
+
+#[test]
+fn handshake_selector_test() {
+    let mut endpoint = Endpoint::new(AlpnSelector);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+    let remote_address = SocketAddress::default();
+    let path_info = || PathInfo::new(&path::Config::default(), &remote_address);
+
+    // the selector is invoked again once the application protocol is known
+    let mut cc = endpoint.new_congestion_controller(path_info());
+    assert!(matches!(cc.controller, Controller::Cubic(_)));
+    cc.on_handshake_info(b"bbr", None);
+    assert!(cc.is_algorithm_pending());
+    cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert!(matches!(cc.controller, Controller::Bbr(_)));
+
+    // the controller is kept if the selector chooses the same algorithm
+    let mut cc = endpoint.new_congestion_controller(path_info());
+    cc.on_handshake_info(b"h3", None);
+    assert!(!cc.is_algorithm_pending());
+
+    // the algorithm selected by the application takes precedence
+    let mut cc = endpoint.new_congestion_controller(path_info());
+    cc.set_algorithm(reno::Endpoint::default());
+    cc.on_handshake_info(b"bbr", None);
+    cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert!(matches!(cc.controller, Controller::Reno(_)));
+}
+
+#[test]
+fn default_endpoint_test() {
+    let mut endpoint: Endpoint = Endpoint::default();
+    let remote_address = SocketAddress::default();
+    let cc = endpoint
+        .new_congestion_controller(PathInfo::new(&path::Config::default(), &remote_address));
+    assert!(matches!(cc.controller, Controller::Cubic(_)));
+}
+
+#[test]
+fn switch_test() {
+    let mut cc = new_controller(Algorithm::default());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    let old_packets: Vec<_> = (0..3)
+        .map(|_| cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher))
+        .collect();
+    assert_eq!(cc.bytes_in_flight(), 3 * MDS as u32);
+
+    // the change takes effect when the next packet is sent
+    cc.set_algorithm(bbr::Endpoint::default());
+    assert!(cc.is_algorithm_pending());
+    assert!(matches!(cc.controller, Controller::Cubic(_)));
+
+    let now = now + Duration::from_millis(10);
+    let new_packet = cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!cc.is_algorithm_pending());
+    assert!(matches!(cc.controller, Controller::Bbr(_)));
+    assert_eq!(cc.generation, 1);
+
+    // the bytes in flight are carried over to the new controller
+    assert_eq!(cc.bytes_in_flight(), 4 * MDS as u32);
+
+    // packets sent by the previous controller can be acknowledged and lost
+    let ack_time = now + Duration::from_millis(100);
+    cc.on_ack(
+        now,
+        MDS,
+        old_packets[0],
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    cc.on_packet_lost(
+        MDS as u32,
+        old_packets[1],
+        false,
+        true,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_eq!(cc.bytes_in_flight(), 2 * MDS as u32);
+
+    cc.on_ack(
+        now,
+        2 * MDS,
+        new_packet,
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_eq!(cc.bytes_in_flight(), 0);
+}
+
+#[test]
+fn switch_back_test() {
+    let mut cc = new_controller(bbr::Endpoint::default().into());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    let bbr_packet = cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+
+    cc.set_algorithm(cubic::Endpoint::default());
+    let cubic_packet = cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+
+    // a new BBR controller does not use packet info from the previous BBR controller
+    cc.set_algorithm(bbr::Endpoint::default());
+    cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(cc.generation, 2);
+    assert_eq!(cc.bytes_in_flight(), 3 * MDS as u32);
+
+    let ack_time = now + Duration::from_millis(100);
+    for packet_info in [bbr_packet, cubic_packet] {
+        cc.on_ack(
+            now,
+            MDS,
+            packet_info,
+            &rtt_estimator,
+            random,
+            ack_time,
+            &mut publisher,
+        );
+    }
+    assert_eq!(cc.bytes_in_flight(), MDS as u32);
+}
+
+#[test]
+fn is_l4s_test() {
+    let mut cc = new_controller(Algorithm::default());
+    assert!(!cc.is_l4s());
+
+    // packets are marked for the pending algorithm
+    cc.set_algorithm(prague::Endpoint::default());
+    assert!(cc.is_l4s());
+
+    assert!(new_controller(prague::Endpoint::default().into()).is_l4s());
+}
+
+#[test]
+fn on_mtu_update_test() {
+    let mut cc = new_controller(Algorithm::default());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_mtu_update(1400, &mut publisher);
+    assert_eq!(cc.max_datagram_size, 1400);
+
+    // the new controller is created with the current max datagram size
+    cc.set_algorithm(reno::Endpoint::default());
+    cc.on_packet_sent(now, 0, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(cc.congestion_window(), 10 * 1400);
+}
+
+#[test]
+fn handshake_info_test() {
+    let mut cc = new_controller(Algorithm::default());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    assert!(cc.application_protocol.is_none());
+    assert!(cc.server_name.is_none());
+
+    cc.on_handshake_info(b"h3", Some("example.com"));
+
+    // the negotiated values are kept for the controllers created when switching algorithms
+    cc.set_algorithm(bbr::Endpoint::default());
+    cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(cc.application_protocol.as_deref(), Some(&b"h3"[..]));
+    assert_eq!(cc.server_name.as_deref(), Some("example.com"));
+
+    // paths created after the handshake include the values in the path info
+    let remote_address = SocketAddress::default();
+    let path_info = PathInfo::new(&path::Config::default(), &remote_address)
+        .with_application_protocol(Some(b"h3"))
+        .with_server_name(Some("example.com"));
+    let cc = DynamicCongestionController::new(Algorithm::default(), path_info);
+    assert_eq!(cc.application_protocol.as_deref(), Some(&b"h3"[..]));
+    assert_eq!(cc.server_name.as_deref(), Some("example.com"));
+}
+
+#[test]
+fn inherit_settings_test() {
+    let mut active = new_controller(Algorithm::default());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    // the endpoint's algorithm is used if the application hasn't selected one
+    let mut cc = new_controller(Algorithm::default());
+    cc.inherit_settings(&active);
+    assert!(!cc.is_algorithm_pending());
+
+    // the selection is carried over after it has taken effect on the active path
+    active.set_algorithm(bbr::Endpoint::default());
+    active.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!active.is_algorithm_pending());
+
+    let mut cc = new_controller(Algorithm::default());
+    cc.inherit_settings(&active);
+    assert!(cc.is_algorithm_pending());
+    cc.on_packet_sent(now, MDS, Some(false), &rtt_estimator, &mut publisher);
+    assert!(matches!(cc.controller, Controller::Bbr(_)));
+    assert_eq!(cc.bytes_in_flight(), MDS as u32);
+}
+
+#[test]
+fn generation_wraparound_test() {
+    let mut cc = new_controller(bbr::Endpoint::default().into());
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(now, 2 * MDS, Some(false), &rtt_estimator, &mut publisher);
+
+    // a packet sent by a non-BBR controller whose generation matches after wrapping around
+    let packet_info = PacketInfo {
+        generation: cc.generation,
+        bbr: None,
+    };
+
+    let ack_time = now + Duration::from_millis(100);
+    cc.on_ack(
+        now,
+        MDS,
+        packet_info,
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    cc.on_packet_lost(
+        MDS as u32,
+        packet_info,
+        false,
+        true,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_eq!(cc.bytes_in_flight(), 0);
+}
@@ -11,11 +11,15 @@ pub mod bandwidth;
 pub mod bbr;
 pub mod congestion_controller;
 pub mod cubic;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
 mod hybrid_slow_start;
 pub mod loss;
 mod pacing;
 pub mod persistent_congestion;
+pub mod prague;
 mod pto;
+pub mod reno;
 mod rtt_estimator;
 mod sent_packets;
 
@@ -39,6 +39,8 @@ pub struct Pacer {
     capacity: Counter<u32, Saturating>,
     // The time the next packet should be transmitted
     next_packet_departure_time: Option<Timestamp>,
+    // The rate used to calculate the most recent departure time
+    pacing_rate: Option<Bandwidth>,
 }
 
 impl Pacer {
@@ -55,12 +57,13 @@ impl Pacer {
         publisher: &mut Pub,
     ) {
         if rtt_estimator.smoothed_rtt() < MINIMUM_PACING_RTT {
+            self.pacing_rate = None;
             return;
         }
 
         if self.capacity == 0 {
             if let Some(next_packet_departure_time) = self.next_packet_departure_time {
-                let interval = Self::interval(
+                let (pacing_rate, interval) = Self::interval(
                     rtt_estimator.smoothed_rtt(),
                     congestion_window,
                     max_datagram_size,
@@ -69,6 +72,7 @@ impl Pacer {
                 );
                 self.next_packet_departure_time =
                     Some((next_packet_departure_time + interval).max(now));
+                self.pacing_rate = Some(pacing_rate);
             } else {
                 self.next_packet_departure_time = Some(now + INITIAL_INTERVAL);
             }
@@ -85,7 +89,14 @@ impl Pacer {
         self.next_packet_departure_time
     }
 
-    // Recalculate the interval between bursts of paced packets
+    /// Returns the rate at which packets are currently being paced
+    ///
+    /// If the value is `None`, packets are not being paced.
+    pub fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacing_rate
+    }
+
+    // Recalculate the pacing rate and the interval between bursts of paced packets
     #[inline]
     fn interval<Pub: Publisher>(
         rtt: Duration,
@@ -93,7 +104,7 @@ impl Pacer {
         max_datagram_size: u16,
         slow_start: bool,
         publisher: &mut Pub,
-    ) -> Duration {
+    ) -> (Bandwidth, Duration) {
         debug_assert_ne!(congestion_window, 0);
 
         let n = if slow_start { SLOW_START_N } else { N };
@@ -114,7 +125,7 @@ impl Pacer {
 
         publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
 
-        packet_size as u64 / pacing_rate
+        (pacing_rate, packet_size as u64 / pacing_rate)
     }
 }
 
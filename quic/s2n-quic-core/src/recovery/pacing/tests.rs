@@ -7,6 +7,7 @@ use crate::{
     path,
     path::MINIMUM_MAX_DATAGRAM_SIZE,
     recovery::{
+        bandwidth::Bandwidth,
         congestion_controller::PathPublisher,
         pacing::{Pacer, INITIAL_INTERVAL, N, SLOW_START_N},
         RttEstimator, MAX_BURST_PACKETS,
@@ -71,6 +72,64 @@ fn on_packet_sent_large_bytes_sent() {
     );
 }
 
+#[test]
+fn pacing_rate() {
+    let mut pacer = Pacer::default();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = 12000;
+    let burst_size = (MAX_BURST_PACKETS * MINIMUM_MAX_DATAGRAM_SIZE as u32) as usize;
+
+    // The first burst is sent without calculating a pacing rate
+    pacer.on_packet_sent(
+        now,
+        burst_size,
+        &rtt,
+        cwnd,
+        MINIMUM_MAX_DATAGRAM_SIZE,
+        false,
+        &mut publisher,
+    );
+    assert_eq!(None, pacer.pacing_rate());
+
+    pacer.on_packet_sent(
+        now,
+        MINIMUM_MAX_DATAGRAM_SIZE as usize,
+        &rtt,
+        cwnd,
+        MINIMUM_MAX_DATAGRAM_SIZE,
+        false,
+        &mut publisher,
+    );
+    assert_eq!(
+        Some(Bandwidth::new(cwnd as u64, rtt.smoothed_rtt()) * N),
+        pacer.pacing_rate()
+    );
+
+    // Packets are not paced when the RTT is below the minimum pacing RTT
+    let mut rtt = RttEstimator::new(Duration::from_millis(1));
+    rtt.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(1),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    pacer.on_packet_sent(
+        now,
+        MINIMUM_MAX_DATAGRAM_SIZE as usize,
+        &rtt,
+        cwnd,
+        MINIMUM_MAX_DATAGRAM_SIZE,
+        false,
+        &mut publisher,
+    );
+    assert_eq!(None, pacer.pacing_rate());
+}
+
 #[test]
 fn slow_start() {
     test_one_rtt(true);
@@ -280,7 +339,7 @@ fn interval_differential_test() {
             let mut publisher = event::testing::Publisher::no_snapshot();
             let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
             let rtt = Duration::from_nanos(rtt as _);
-            let actual = Pacer::interval(
+            let (_, actual) = Pacer::interval(
                 rtt,
                 congestion_window,
                 max_datagram_size,
@@ -0,0 +1,271 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    event::builder::SlowStartExitCause,
+    random,
+    recovery::{
+        bandwidth::Bandwidth,
+        congestion_controller::{self, CongestionController, Publisher},
+        reno::{self, RenoCongestionController},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+
+/// The gain used when updating the moving average of the fraction of CE marked bytes
+///
+/// This matches the value of `g` used by DCTCP, see
+/// [RFC 8257 Section 4.2](https://www.rfc-editor.org/rfc/rfc8257#section-4.2)
+const ALPHA_GAIN: f32 = 1.0 / 16.0;
+
+/// A congestion controller that implements the scalable congestion response of TCP Prague
+/// for use with Low Latency, Low Loss, and Scalable throughput (L4S) networks
+/// described in [RFC 9330](https://www.rfc-editor.org/rfc/rfc9330)
+///
+/// Packets sent on a path using this controller are marked with the ECT(1) codepoint to
+/// identify the flow as L4S capable. Rather than halving the congestion window for each
+/// round with a CE mark, the window is reduced in proportion to the moving average of the
+/// fraction of CE marked bytes, as in DCTCP. Loss and persistent congestion are handled
+/// with the NewReno response.
+#[derive(Clone, Debug)]
+pub struct PragueCongestionController {
+    reno: RenoCongestionController,
+    /// Moving average of the fraction of bytes that were CE marked
+    alpha: f32,
+    /// The number of bytes to acknowledge before the current round ends
+    round_target: u32,
+    /// Bytes acknowledged in the current round
+    round_bytes_acked: u64,
+    /// Bytes marked CE in the current round
+    round_bytes_marked: u64,
+}
+
+impl CongestionController for PragueCongestionController {
+    type PacketInfo = ();
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        self.reno.congestion_window()
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        self.reno.bytes_in_flight()
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        self.reno.is_congestion_limited()
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        self.reno.requires_fast_retransmission()
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        bytes_sent: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        self.reno
+            .on_packet_sent(time_sent, bytes_sent, app_limited, rtt_estimator, publisher)
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        self.reno
+            .on_rtt_update(time_sent, now, rtt_estimator, publisher)
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        newest_acked_packet_info: Self::PacketInfo,
+        rtt_estimator: &RttEstimator,
+        random_generator: &mut dyn random::Generator,
+        ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.reno.on_ack(
+            newest_acked_time_sent,
+            bytes_acknowledged,
+            newest_acked_packet_info,
+            rtt_estimator,
+            random_generator,
+            ack_receive_time,
+            publisher,
+        );
+
+        self.round_bytes_acked += bytes_acknowledged as u64;
+
+        // A round ends once a congestion window worth of data has been acknowledged
+        if self.round_bytes_acked >= self.round_target as u64 {
+            self.update_alpha();
+            self.round_target = self.congestion_window();
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        new_loss_burst: bool,
+        random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.reno.on_packet_lost(
+            lost_bytes,
+            packet_info,
+            persistent_congestion,
+            new_loss_burst,
+            random_generator,
+            timestamp,
+            publisher,
+        )
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.round_bytes_marked = self
+            .round_bytes_marked
+            .saturating_add(ce_count.saturating_mul(self.reno.max_datagram_size() as u64));
+
+        if self.reno.is_slow_start() {
+            publisher.on_slow_start_exited(SlowStartExitCause::Ecn, self.congestion_window());
+        }
+
+        // The window is reduced at most once per round, since the NewReno recovery
+        // period lasts until a packet sent after the congestion event is acknowledged
+        self.reno
+            .on_congestion_event(1.0 - self.alpha / 2.0, event_time);
+    }
+
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_datagram_size: u16, publisher: &mut Pub) {
+        self.reno.on_mtu_update(max_datagram_size, publisher)
+    }
+
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, publisher: &mut Pub) {
+        self.reno.on_packet_discarded(bytes_sent, publisher)
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        self.reno.earliest_departure_time()
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.reno.pacing_rate()
+    }
+
+    #[inline]
+    fn is_l4s(&self) -> bool {
+        true
+    }
+}
+
+impl PragueCongestionController {
+    // max_datagram_size is the current max_datagram_size, and is
+    // expected to be 1200 when the congestion controller is created.
+    pub fn new(max_datagram_size: u16, app_settings: reno::ApplicationSettings) -> Self {
+        let reno = RenoCongestionController::new(max_datagram_size, app_settings);
+        Self {
+            round_target: reno.congestion_window(),
+            reno,
+            // Start with the most conservative response until the CE fraction is measured
+            alpha: 1.0,
+            round_bytes_acked: 0,
+            round_bytes_marked: 0,
+        }
+    }
+
+    /// Returns the moving average of the fraction of bytes that were CE marked
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Updates `alpha` with the fraction of CE marked bytes in the round that just ended
+    #[inline]
+    fn update_alpha(&mut self) {
+        let fraction = if self.round_bytes_acked == 0 {
+            0.0
+        } else {
+            (self.round_bytes_marked as f32 / self.round_bytes_acked as f32).min(1.0)
+        };
+
+        self.alpha = (1.0 - ALPHA_GAIN) * self.alpha + ALPHA_GAIN * fraction;
+        self.round_bytes_acked = 0;
+        self.round_bytes_marked = 0;
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct Endpoint {
+    app_settings: reno::ApplicationSettings,
+}
+
+impl congestion_controller::Endpoint for Endpoint {
+    type CongestionController = PragueCongestionController;
+
+    fn new_congestion_controller(
+        &mut self,
+        path_info: congestion_controller::PathInfo,
+    ) -> Self::CongestionController {
+        PragueCongestionController::new(path_info.max_datagram_size, self.app_settings)
+    }
+}
+
+pub mod builder {
+    use super::Endpoint;
+    use crate::recovery::reno::ApplicationSettings;
+
+    /// Build the congestion controller endpoint with application provided overrides
+    #[derive(Default)]
+    pub struct Builder {
+        initial_congestion_window: Option<u32>,
+    }
+
+    impl Builder {
+        /// Set the initial congestion window in bytes.
+        pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
+            self.initial_congestion_window = Some(initial_congestion_window);
+            self
+        }
+
+        pub fn build(self) -> Endpoint {
+            let app_settings = ApplicationSettings {
+                initial_congestion_window: self.initial_congestion_window,
+            };
+            Endpoint { app_settings }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,136 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    event, path,
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
+    recovery::congestion_controller::PathPublisher,
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MDS: u32 = MINIMUM_MAX_DATAGRAM_SIZE as u32;
+
+fn new_controller() -> PragueCongestionController {
+    PragueCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default())
+}
+
+/// Sends and acknowledges the remainder of the current round without growing the window
+fn round<Pub: Publisher>(cc: &mut PragueCongestionController, now: Timestamp, publisher: &mut Pub) {
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let remaining = cc.round_target as u64 - cc.round_bytes_acked;
+    let packets = remaining.div_ceil(MDS as u64);
+
+    // Send the round in two halves so the window is underutilized and does not grow
+    for half in [packets / 2, packets - packets / 2] {
+        for _ in 0..half {
+            cc.on_packet_sent(now, MDS as usize, Some(true), &rtt_estimator, publisher);
+        }
+        for _ in 0..half {
+            cc.on_ack(
+                now,
+                MDS as usize,
+                (),
+                &rtt_estimator,
+                random,
+                now + Duration::from_millis(100),
+                publisher,
+            );
+        }
+    }
+}
+
+#[test]
+fn is_l4s_test() {
+    assert!(new_controller().is_l4s());
+    assert!(!RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default()).is_l4s());
+}
+
+#[test]
+fn alpha_decay_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    assert_eq!(cc.alpha(), 1.0);
+
+    // a round without any CE marks reduces alpha
+    round(&mut cc, now, &mut publisher);
+    assert_eq!(cc.alpha(), 1.0 - ALPHA_GAIN);
+
+    round(&mut cc, now, &mut publisher);
+    assert_eq!(cc.alpha(), (1.0 - ALPHA_GAIN) * (1.0 - ALPHA_GAIN));
+    assert_eq!(cc.congestion_window(), 10 * MDS);
+}
+
+#[test]
+fn alpha_ce_fraction_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    round(&mut cc, now, &mut publisher);
+    let alpha = cc.alpha();
+
+    // mark half of the packets in the round
+    let now = now + Duration::from_millis(100);
+    cc.on_explicit_congestion(5, now, &mut publisher);
+    round(&mut cc, now, &mut publisher);
+
+    let expected = (1.0 - ALPHA_GAIN) * alpha + ALPHA_GAIN * 0.5;
+    assert!((cc.alpha() - expected).abs() < 0.001, "{}", cc.alpha());
+}
+
+#[test]
+fn explicit_congestion_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    // decay alpha over several rounds without CE marks
+    for _ in 0..20 {
+        round(&mut cc, now, &mut publisher);
+    }
+    let alpha = cc.alpha();
+    assert!(alpha < 0.5);
+
+    let cwnd = cc.congestion_window();
+    cc.on_explicit_congestion(1, now, &mut publisher);
+
+    // the window is reduced in proportion to alpha rather than halved
+    let expected = (cwnd as f32 * (1.0 - alpha / 2.0)) as u32;
+    assert_eq!(cc.congestion_window(), expected);
+    assert!(cc.congestion_window() > cwnd / 2);
+    assert!(!cc.reno.is_slow_start());
+
+    // additional CE marks in the same round don't reduce the window further
+    cc.on_explicit_congestion(1, now, &mut publisher);
+    assert_eq!(cc.congestion_window(), expected);
+}
+
+#[test]
+fn packet_loss_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    cc.on_packet_lost(MDS, (), false, false, random, now, &mut publisher);
+
+    // loss is handled with the NewReno response
+    assert_eq!(cc.congestion_window(), 5 * MDS);
+}
@@ -0,0 +1,461 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counter::Counter,
+    event::builder::SlowStartExitCause,
+    random,
+    recovery::{
+        bandwidth::Bandwidth,
+        congestion_controller::{self, CongestionController, Publisher},
+        pacing::Pacer,
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use core::cmp::{max, min};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    SlowStart,
+    Recovery(Timestamp, FastRetransmission),
+    CongestionAvoidance,
+}
+
+impl State {
+    /// Returns true if the state is `SlowStart`
+    fn is_slow_start(&self) -> bool {
+        matches!(self, State::SlowStart)
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+//# If the congestion window is reduced immediately, a
+//# single packet can be sent prior to reduction.  This speeds up loss
+//# recovery if the data in the lost packet is retransmitted and is
+//# similar to TCP as described in Section 5 of [RFC6675].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FastRetransmission {
+    Idle,
+    RequiresTransmission,
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+//# On entering a recovery period, a sender MUST set the slow start
+//# threshold to half the value of the congestion window when loss is
+//# detected.
+const LOSS_REDUCTION_FACTOR: f32 = 0.5;
+
+/// A congestion controller that implements the NewReno algorithm described in
+/// [RFC 9002 Section 7](https://www.rfc-editor.org/rfc/rfc9002#section-7)
+///
+/// This controller follows the reference behavior of the specification and is mainly intended
+/// as a baseline for comparing other congestion controllers.
+#[derive(Clone, Debug)]
+pub struct RenoCongestionController {
+    max_datagram_size: u16,
+    congestion_window: u32,
+    //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.2
+    //# Slow start threshold in bytes.  When the congestion window
+    //# is below ssthresh, the mode is slow start and the window grows by
+    //# the number of bytes acknowledged.
+    slow_start_threshold: u32,
+    state: State,
+    bytes_in_flight: Counter<u32>,
+    // Bytes acknowledged in congestion avoidance that have not yet increased the window
+    bytes_acked: u32,
+    pacer: Pacer,
+    under_utilized: bool,
+    app_settings: ApplicationSettings,
+}
+
+impl CongestionController for RenoCongestionController {
+    type PacketInfo = ();
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        self.congestion_window
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        *self.bytes_in_flight
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        let available_congestion_window =
+            self.congestion_window.saturating_sub(*self.bytes_in_flight);
+        available_congestion_window < self.max_datagram_size as u32
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        matches!(
+            self.state,
+            State::Recovery(_, FastRetransmission::RequiresTransmission)
+        )
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        bytes_sent: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        if bytes_sent == 0 {
+            // Packet was not congestion controlled
+            return;
+        }
+
+        self.bytes_in_flight
+            .try_add(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+        //# When bytes in flight is smaller than the congestion window and
+        //# sending is not pacing limited, the congestion window is
+        //# underutilized.
+        self.under_utilized = app_limited.unwrap_or(false) && !self.is_congestion_limited();
+
+        if let State::Recovery(recovery_start_time, FastRetransmission::RequiresTransmission) =
+            self.state
+        {
+            // A packet has been sent since we entered recovery (fast retransmission)
+            // so flip the state back to idle.
+            self.state = State::Recovery(recovery_start_time, FastRetransmission::Idle);
+        }
+
+        self.pacer.on_packet_sent(
+            time_sent,
+            bytes_sent,
+            rtt_estimator,
+            self.congestion_window,
+            self.max_datagram_size,
+            self.state.is_slow_start(),
+            publisher,
+        );
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        _time_sent: Timestamp,
+        _now: Timestamp,
+        _rtt_estimator: &RttEstimator,
+        _publisher: &mut Pub,
+    ) {
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        _newest_acked_packet_info: Self::PacketInfo,
+        _rtt_estimator: &RttEstimator,
+        _random_generator: &mut dyn random::Generator,
+        _ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.bytes_in_flight
+            .try_sub(bytes_acknowledged)
+            .expect("bytes_acknowledged should not exceed u32::MAX");
+
+        if let State::Recovery(recovery_start_time, _) = self.state {
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+            //# A recovery period ends and the sender enters congestion avoidance
+            //# when a packet sent during the recovery period is acknowledged.
+            if newest_acked_time_sent <= recovery_start_time {
+                // Do not increase the congestion window in the recovery period
+                return;
+            }
+            self.state = State::CongestionAvoidance;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+        //# When this occurs, the congestion window
+        //# SHOULD NOT be increased in either slow start or congestion avoidance.
+        if self.under_utilized {
+            return;
+        }
+
+        let bytes_acknowledged = bytes_acknowledged as u32;
+
+        if self.state.is_slow_start() {
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# While a sender is in slow start, the congestion window increases by
+            //# the number of bytes acknowledged when each acknowledgment is
+            //# processed.  This results in exponential growth of the congestion
+            //# window.
+            self.congestion_window = self.congestion_window.saturating_add(bytes_acknowledged);
+
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# The sender MUST exit slow start and enter a recovery period when a
+            //# packet is lost or when the ECN-CE count reported by its peer
+            //# increases.
+
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# A sender re-enters slow start any time the congestion window is
+            //# less than the slow start threshold, which only occurs after
+            //# persistent congestion is declared.
+            if self.congestion_window >= self.slow_start_threshold {
+                publisher.on_slow_start_exited(SlowStartExitCause::Other, self.congestion_window);
+                self.state = State::CongestionAvoidance;
+            }
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+        //# In this implementation and in Reno [RFC6582], the congestion
+        //# avoidance phase uses an Additive Increase Multiplicative Decrease
+        //# (AIMD) approach that increases the congestion window by one maximum
+        //# packet size per congestion window acknowledged.
+        self.bytes_acked = self.bytes_acked.saturating_add(bytes_acknowledged);
+        if self.bytes_acked >= self.congestion_window {
+            self.bytes_acked -= self.congestion_window;
+            self.congestion_window = self
+                .congestion_window
+                .saturating_add(self.max_datagram_size as u32);
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        _packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        _new_loss_burst: bool,
+        _random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(lost_bytes > 0);
+
+        self.bytes_in_flight -= lost_bytes;
+
+        if self.state.is_slow_start() && !persistent_congestion {
+            publisher.on_slow_start_exited(SlowStartExitCause::PacketLoss, self.congestion_window);
+        }
+
+        self.on_congestion_event(LOSS_REDUCTION_FACTOR, timestamp);
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+        //# When persistent congestion is declared, the sender's congestion
+        //# window MUST be reduced to the minimum congestion window
+        //# (kMinimumWindow), similar to a TCP sender's response on an RTO
+        //# [RFC5681].
+        if persistent_congestion {
+            self.congestion_window = self.minimum_window();
+            self.bytes_acked = 0;
+            self.state = State::SlowStart;
+        }
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        _ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        if self.state.is_slow_start() {
+            publisher.on_slow_start_exited(SlowStartExitCause::Ecn, self.congestion_window);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.1
+        //# If a path has been validated to support Explicit Congestion
+        //# Notification (ECN) [RFC3168] [RFC8311], QUIC treats a Congestion
+        //# Experienced (CE) codepoint in the IP header as a signal of
+        //# congestion.
+        self.on_congestion_event(LOSS_REDUCTION_FACTOR, event_time);
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# If the maximum datagram size is decreased in order to complete the
+    //# handshake, the congestion window SHOULD be set to the new initial
+    //# congestion window.
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_datagram_size: u16, _publisher: &mut Pub) {
+        let old_max_datagram_size = self.max_datagram_size;
+        self.max_datagram_size = max_datagram_size;
+
+        let congestion_window = (self.congestion_window as u64 * max_datagram_size as u64
+            / old_max_datagram_size as u64) as u32;
+        let initial_window = Self::initial_window(max_datagram_size, &self.app_settings);
+
+        self.congestion_window = max(congestion_window, initial_window);
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.4
+    //# When Initial and Handshake packet protection keys are discarded (see
+    //# Section 4.9 of [QUIC-TLS]), all packets that were sent with those
+    //# keys can no longer be acknowledged because their acknowledgments
+    //# cannot be processed.  The sender MUST discard all recovery state
+    //# associated with those packets and MUST remove them from the count of
+    //# bytes in flight.
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, _publisher: &mut Pub) {
+        self.bytes_in_flight
+            .try_sub(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        if let State::Recovery(recovery_start_time, FastRetransmission::RequiresTransmission) =
+            self.state
+        {
+            // If any of the discarded packets were lost, they will no longer be retransmitted
+            // so flip the Recovery status back to Idle so it is not waiting for a
+            // retransmission that may never come.
+            self.state = State::Recovery(recovery_start_time, FastRetransmission::Idle);
+        }
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        self.pacer.earliest_departure_time()
+    }
+
+    #[inline]
+    fn pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacer.pacing_rate()
+    }
+}
+
+impl RenoCongestionController {
+    // max_datagram_size is the current max_datagram_size, and is
+    // expected to be 1200 when the congestion controller is created.
+    pub fn new(max_datagram_size: u16, app_settings: ApplicationSettings) -> Self {
+        Self {
+            max_datagram_size,
+            congestion_window: Self::initial_window(max_datagram_size, &app_settings),
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# A NewReno sender is in slow start any time the congestion window is
+            //# below the slow start threshold.  A sender begins in slow start
+            //# because the slow start threshold is initialized to an infinite
+            //# value.
+            slow_start_threshold: u32::MAX,
+            state: State::SlowStart,
+            bytes_in_flight: Counter::new(0),
+            bytes_acked: 0,
+            pacer: Pacer::default(),
+            under_utilized: true,
+            app_settings,
+        }
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# Endpoints SHOULD use an initial congestion
+    //# window of ten times the maximum datagram size (max_datagram_size),
+    //# while limiting the window to the larger of 14,720 bytes or twice the
+    //# maximum datagram size.
+    #[inline]
+    fn initial_window(max_datagram_size: u16, app_settings: &ApplicationSettings) -> u32 {
+        const INITIAL_WINDOW_LIMIT: u32 = 14720;
+        let default = min(
+            10 * max_datagram_size as u32,
+            max(INITIAL_WINDOW_LIMIT, 2 * max_datagram_size as u32),
+        );
+        let initial_window = app_settings.initial_congestion_window.unwrap_or(default);
+
+        max(initial_window, 2 * max_datagram_size as u32)
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# The minimum congestion window is the smallest value the congestion
+    //# window can attain in response to loss, an increase in the peer-
+    //# reported ECN-CE count, or persistent congestion.  The RECOMMENDED
+    //# value is 2 * max_datagram_size.
+    #[inline]
+    pub(crate) fn minimum_window(&self) -> u32 {
+        2 * self.max_datagram_size as u32
+    }
+
+    #[inline]
+    pub(crate) fn max_datagram_size(&self) -> u16 {
+        self.max_datagram_size
+    }
+
+    /// Returns true if the controller is currently in slow start
+    #[inline]
+    pub(crate) fn is_slow_start(&self) -> bool {
+        self.state.is_slow_start()
+    }
+
+    /// Enters a recovery period and multiplies the congestion window by the given `factor`
+    ///
+    /// No further reduction occurs if the controller is already in a recovery period.
+    #[inline]
+    pub(crate) fn on_congestion_event(&mut self, factor: f32, event_time: Timestamp) {
+        // No reaction if already in a recovery period
+        if matches!(self.state, State::Recovery(..)) {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+        //# On entering a recovery period, a sender MUST set the slow start
+        //# threshold to half the value of the congestion window when loss is
+        //# detected.  The congestion window MUST be set to the reduced value of
+        //# the slow start threshold before exiting the recovery period.
+        self.state = State::Recovery(event_time, FastRetransmission::RequiresTransmission);
+        self.slow_start_threshold = max(
+            (self.congestion_window as f32 * factor) as u32,
+            self.minimum_window(),
+        );
+        self.congestion_window = self.slow_start_threshold;
+        self.bytes_acked = 0;
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ApplicationSettings {
+    pub(crate) initial_congestion_window: Option<u32>,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct Endpoint {
+    app_settings: ApplicationSettings,
+}
+
+impl congestion_controller::Endpoint for Endpoint {
+    type CongestionController = RenoCongestionController;
+
+    fn new_congestion_controller(
+        &mut self,
+        path_info: congestion_controller::PathInfo,
+    ) -> Self::CongestionController {
+        RenoCongestionController::new(path_info.max_datagram_size, self.app_settings)
+    }
+}
+
+pub mod builder {
+    use super::{ApplicationSettings, Endpoint};
+
+    /// Build the congestion controller endpoint with application provided overrides
+    #[derive(Default)]
+    pub struct Builder {
+        initial_congestion_window: Option<u32>,
+    }
+
+    impl Builder {
+        /// Set the initial congestion window in bytes.
+        pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
+            self.initial_congestion_window = Some(initial_congestion_window);
+            self
+        }
+
+        pub fn build(self) -> Endpoint {
+            let app_settings = ApplicationSettings {
+                initial_congestion_window: self.initial_congestion_window,
+            };
+            Endpoint { app_settings }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
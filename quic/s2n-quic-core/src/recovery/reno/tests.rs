@@ -0,0 +1,207 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    event, path,
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
+    recovery::congestion_controller::PathPublisher,
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MDS: u32 = MINIMUM_MAX_DATAGRAM_SIZE as u32;
+
+fn new_controller() -> RenoCongestionController {
+    RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default())
+}
+
+#[test]
+fn slow_start_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    assert_eq!(cc.congestion_window(), 10 * MDS);
+
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    cc.on_ack(
+        now,
+        MDS as usize,
+        (),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+
+    // the window grows by the number of bytes acknowledged
+    assert_eq!(cc.congestion_window(), 11 * MDS);
+    assert!(cc.is_slow_start());
+}
+
+#[test]
+fn congestion_avoidance_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    cc.on_packet_lost(MDS, (), false, false, random, now, &mut publisher);
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+    //= type=test
+    //# On entering a recovery period, a sender MUST set the slow start
+    //# threshold to half the value of the congestion window when loss is
+    //# detected.
+    assert_eq!(cc.congestion_window(), 5 * MDS);
+    assert!(cc.requires_fast_retransmission());
+
+    // a second loss in the same recovery period doesn't reduce the window further
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    assert!(!cc.requires_fast_retransmission());
+    cc.on_packet_lost(MDS, (), false, false, random, now, &mut publisher);
+    assert_eq!(cc.congestion_window(), 5 * MDS);
+
+    // acknowledging a packet sent after the start of recovery exits recovery
+    let now = now + Duration::from_millis(10);
+    for _ in 0..5 {
+        cc.on_packet_sent(
+            now,
+            MDS as usize,
+            Some(false),
+            &rtt_estimator,
+            &mut publisher,
+        );
+    }
+    for _ in 0..5 {
+        cc.on_ack(
+            now,
+            MDS as usize,
+            (),
+            &rtt_estimator,
+            random,
+            now,
+            &mut publisher,
+        );
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+    //= type=test
+    //# the congestion
+    //# avoidance phase uses an Additive Increase Multiplicative Decrease
+    //# (AIMD) approach that increases the congestion window by one maximum
+    //# packet size per congestion window acknowledged.
+    assert_eq!(cc.congestion_window(), 6 * MDS);
+    assert!(matches!(cc.state, State::CongestionAvoidance));
+}
+
+#[test]
+fn persistent_congestion_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    cc.on_packet_lost(MDS, (), true, false, random, now, &mut publisher);
+
+    assert_eq!(cc.congestion_window(), cc.minimum_window());
+    assert!(cc.is_slow_start());
+}
+
+#[test]
+fn explicit_congestion_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.on_explicit_congestion(1, now, &mut publisher);
+
+    assert_eq!(cc.congestion_window(), 5 * MDS);
+    assert!(!cc.is_slow_start());
+}
+
+#[test]
+fn app_limited_test() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(
+        now,
+        MDS as usize,
+        Some(true),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    cc.on_ack(
+        now,
+        MDS as usize,
+        (),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+    //= type=test
+    //# When this occurs, the congestion window
+    //# SHOULD NOT be increased in either slow start or congestion avoidance.
+    assert_eq!(cc.congestion_window(), 10 * MDS);
+}
+
+#[test]
+fn mtu_update_test() {
+    let app_settings = ApplicationSettings {
+        initial_congestion_window: Some(20 * MDS),
+    };
+    let mut cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, app_settings);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.on_explicit_congestion(1, now, &mut publisher);
+    assert_eq!(cc.congestion_window(), 10 * MDS);
+
+    // the scaled window is below the configured initial window so the initial window is used
+    cc.on_mtu_update(1500, &mut publisher);
+    assert_eq!(cc.congestion_window(), 20 * MDS);
+}
@@ -8,7 +8,8 @@ use crate::{
     path::MINIMUM_MAX_DATAGRAM_SIZE,
     random,
     recovery::{
-        congestion_controller::PathPublisher, CongestionController, CubicCongestionController,
+        congestion_controller::PathPublisher, prague::PragueCongestionController,
+        reno::RenoCongestionController, CongestionController, CubicCongestionController,
         RttEstimator,
     },
     time::{Clock, NoopClock, Timestamp},
@@ -68,6 +69,70 @@ fn loss_at_3mb_and_2_75mb_test() {
     loss_at_3mb_and_2_75mb(cc, 120).finish();
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reno_slow_start_unlimited_test() {
+    let cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    slow_start_unlimited(cc, 12).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reno_loss_at_3mb_test() {
+    let cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    loss_at_3mb(cc, 135).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reno_app_limited_1mb_test() {
+    let cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    app_limited_1mb(cc, 120).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reno_minimum_window_test() {
+    let cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    minimum_window(cc, 10).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reno_ce_marking_above_1mb_test() {
+    let cc = RenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    ce_marking_above_1mb(cc, 120).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn prague_slow_start_unlimited_test() {
+    let cc = PragueCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    slow_start_unlimited(cc, 12).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn prague_loss_at_3mb_test() {
+    let cc = PragueCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    loss_at_3mb(cc, 135).finish();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn prague_ce_marking_above_1mb_test() {
+    let cc = PragueCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    ce_marking_above_1mb(cc, 120).finish();
+}
+
 #[derive(Debug)]
 struct Simulation {
     name: &'static str,
@@ -287,6 +352,20 @@ fn loss_at_3mb_and_2_75mb<CC: CongestionController>(
     }
 }
 
+/// Simulates a network that marks a portion of packets with ECN-CE once the congestion
+/// window exceeds 1MB, as an L4S bottleneck would
+fn ce_marking_above_1mb<CC: CongestionController>(
+    mut congestion_controller: CC,
+    num_rounds: usize,
+) -> Simulation {
+    Simulation {
+        name: "CE Marking Above 1MB",
+        description: "Full congestion window utilization with 10% of packets marked CE above ~1MB",
+        cc: type_name::<CC>(),
+        rounds: simulate_ce_marking(&mut congestion_controller, 1_000_000, 10, num_rounds),
+    }
+}
+
 /// Simulate the given number of rounds with drops occurring at the given congestion window sizes
 /// and limited to the given app limit
 fn simulate_constant_rtt<CC: CongestionController>(
@@ -359,6 +438,61 @@ fn simulate_constant_rtt<CC: CongestionController>(
     rounds
 }
 
+/// Simulate the given number of rounds with the given percentage of packets marked with ECN-CE
+/// in each round the congestion window is at least `ce_threshold`
+fn simulate_ce_marking<CC: CongestionController>(
+    congestion_controller: &mut CC,
+    ce_threshold: u32,
+    ce_percentage: u64,
+    num_rounds: usize,
+) -> Vec<Round> {
+    let time_zero = NoopClock.get_time();
+    let mut rtt_estimator = RttEstimator::default();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    // Update the rtt with 200 ms
+    rtt_estimator.update_rtt(
+        Duration::from_millis(0),
+        Duration::from_millis(200),
+        time_zero,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    let mut round_start = NoopClock.get_time() + Duration::from_millis(1);
+    let mut rounds = Vec::with_capacity(num_rounds);
+
+    for round in 0..num_rounds {
+        let congestion_window = congestion_controller.congestion_window();
+        rounds.push(Round {
+            number: round,
+            cwnd: congestion_window,
+        });
+
+        round_start += Duration::from_millis(200);
+
+        // Send and ack the full congestion window
+        send_and_ack(
+            congestion_controller,
+            &rtt_estimator,
+            round_start,
+            congestion_window as usize,
+        );
+
+        if congestion_window >= ce_threshold {
+            let packets = (congestion_window / MINIMUM_MAX_DATAGRAM_SIZE as u32) as u64;
+            // The CE marks were reported for packets sent during this round
+            congestion_controller.on_explicit_congestion(
+                (packets * ce_percentage / 100).max(1),
+                round_start,
+                &mut publisher,
+            );
+        }
+    }
+
+    rounds
+}
+
 /// Send and acknowledge the given amount of bytes using the given congestion controller
 fn send_and_ack<CC: CongestionController>(
     congestion_controller: &mut CC,
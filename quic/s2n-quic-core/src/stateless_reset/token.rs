@@ -107,6 +107,16 @@ pub trait Generator: 'static + Send {
     /// dropped with no further action.
     const ENABLED: bool = true;
 
+    /// Returns `true` if stateless reset packets should be sent with the tokens
+    /// generated by this Generator
+    ///
+    /// This allows a Generator to enable stateless resets based on its configuration.
+    /// Stateless resets are only sent if both `ENABLED` and `is_enabled` are `true`.
+    #[inline]
+    fn is_enabled(&self) -> bool {
+        Self::ENABLED
+    }
+
     /// Generates a stateless reset token.
     ///
     /// The stateless reset token MUST be difficult to guess.
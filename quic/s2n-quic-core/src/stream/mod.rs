@@ -7,12 +7,15 @@ pub mod iter;
 pub mod limits;
 #[cfg(feature = "alloc")]
 pub mod ops;
+mod priority;
+pub mod scheduler;
 pub mod state;
 mod type_;
 
 pub use error::*;
 pub use id::*;
 pub use limits::Limits;
+pub use priority::*;
 pub use type_::*;
 
 #[cfg(any(test, feature = "testing"))]
@@ -48,6 +48,12 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Requests data to be copied from a slice onto the tx stream
+    pub fn send_from(&mut self, data: &'a [u8]) -> &mut Self {
+        self.tx_mut().data = Some(data);
+        self
+    }
+
     /// Resets the tx stream with an error code
     pub fn reset(&mut self, error: application::Error) -> &mut Self {
         self.tx_mut().reset = Some(error);
@@ -66,12 +72,24 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Updates the priority of the tx stream
+    pub fn with_priority(&mut self, priority: stream::Priority) -> &mut Self {
+        self.tx_mut().priority = Some(priority);
+        self
+    }
+
     /// Requests data on the rx stream to be received into the provided slice of chunks
     pub fn receive(&mut self, chunks: &'a mut [bytes::Bytes]) -> &mut Self {
         self.rx_mut().chunks = Some(chunks);
         self
     }
 
+    /// Requests data on the rx stream to be copied into the provided buffer
+    pub fn receive_into(&mut self, buffer: &'a mut bytes::buf::UninitSlice) -> &mut Self {
+        self.rx_mut().buffer = Some(buffer);
+        self
+    }
+
     /// Requests the peer to stop sending data on the rx stream
     pub fn stop_sending(&mut self, error: application::Error) -> &mut Self {
         self.rx_mut().stop_sending = Some(error);
@@ -184,6 +202,13 @@ pub mod tx {
         /// this slice.
         pub chunks: Option<&'a mut [bytes::Bytes]>,
 
+        /// Optionally copy data from a slice onto the stream
+        ///
+        /// As much of the slice as the transmission buffer can currently hold is copied, and the
+        /// slice is advanced past the copied bytes. The response will indicate how many bytes
+        /// were consumed from the slice.
+        pub data: Option<&'a [u8]>,
+
         /// Optionally reset the stream with an error
         pub reset: Option<application::Error>,
 
@@ -196,6 +221,9 @@ pub mod tx {
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Optionally updates the priority of the stream
+        pub priority: Option<stream::Priority>,
     }
 
     /// The result of a tx request
@@ -253,6 +281,12 @@ pub mod rx {
         /// bytes were consumed from the stream into the provided slice.
         pub chunks: Option<&'a mut [bytes::Bytes]>,
 
+        /// Optionally copy received data directly into a caller-provided buffer
+        ///
+        /// The buffer is advanced past the copied bytes. The response will indicate how many
+        /// bytes were copied into the buffer.
+        pub buffer: Option<&'a mut bytes::buf::UninitSlice>,
+
         /// Sets the low watermark for the rx stream
         ///
         /// If the watermark is set to `0`, the caller will be notified as soon as there is data
@@ -280,6 +314,7 @@ pub mod rx {
         fn default() -> Self {
             Self {
                 chunks: None,
+                buffer: None,
                 low_watermark: 0,
                 high_watermark: usize::MAX,
                 stop_sending: None,
@@ -303,6 +338,9 @@ pub mod rx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// Indicates if any of the data on the stream was received in 0-RTT packets
+        pub early_data: bool,
     }
 
     impl Default for Response {
@@ -312,6 +350,7 @@ pub mod rx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                early_data: false,
             }
         }
     }
@@ -499,6 +538,7 @@ mod tests {
             .finish()
             .flush()
             .reset(application::Error::new(1).unwrap())
+            .with_priority(stream::Priority::new(0, false).unwrap())
             .receive(&mut receive_chunks)
             .with_watermark(5, 10)
             .stop_sending(application::Error::new(2).unwrap());
@@ -508,13 +548,16 @@ mod tests {
             Request {
                 tx: Some(tx::Request {
                     chunks: Some(tx_chunks),
+                    data: None,
                     finish: true,
                     flush: true,
                     reset: Some(reset),
                     detached: false,
+                    priority: Some(priority),
                 }),
                 rx: Some(rx::Request {
                     chunks: Some(rx_chunks),
+                    buffer: None,
                     low_watermark: 5,
                     high_watermark: 10,
                     stop_sending: Some(stop_sending),
@@ -522,6 +565,7 @@ mod tests {
                 })
             } if reset == application::Error::new(1).unwrap()
               && stop_sending == application::Error::new(2).unwrap()
+              && priority.urgency() == 0
               && tx_chunks.len() == 1
               && rx_chunks.len() == 2
         ));
@@ -0,0 +1,121 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stream priorities, modeled after the Extensible Prioritization Scheme for HTTP
+//!
+//! See [RFC 9218](https://www.rfc-editor.org/rfc/rfc9218) for the scheme this is based on.
+
+use core::fmt;
+
+/// The priority of a stream when scheduling data for transmission
+///
+/// Priorities are made up of an `urgency`, from `0` (most urgent) to `7` (least urgent), and
+/// an `incremental` flag, which indicates if the stream can make use of data as it is received
+/// and therefore may be interleaved with other streams of the same urgency.
+///
+/// How the priority is interpreted is up to the configured stream
+/// [`Scheduler`](crate::stream::scheduler::Scheduler).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Priority {
+    urgency: u8,
+    incremental: bool,
+}
+
+impl Default for Priority {
+    /// Returns the default priority
+    ///
+    /// Note that, unlike RFC 9218, streams are incremental by default. This preserves the
+    /// behavior of interleaving streams with pending data.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            urgency: Self::DEFAULT_URGENCY,
+            incremental: true,
+        }
+    }
+}
+
+impl Priority {
+    /// The most urgent priority level
+    pub const MIN_URGENCY: u8 = 0;
+
+    /// The least urgent priority level
+    pub const MAX_URGENCY: u8 = 7;
+
+    /// The urgency assigned to streams which don't specify a priority
+    pub const DEFAULT_URGENCY: u8 = 3;
+
+    /// Creates a new `Priority` with the given urgency and incremental flag
+    #[inline]
+    pub const fn new(urgency: u8, incremental: bool) -> Result<Self, UrgencyError> {
+        if urgency > Self::MAX_URGENCY {
+            return Err(UrgencyError);
+        }
+
+        Ok(Self {
+            urgency,
+            incremental,
+        })
+    }
+
+    /// Sets the urgency of the priority
+    #[inline]
+    pub const fn with_urgency(self, urgency: u8) -> Result<Self, UrgencyError> {
+        Self::new(urgency, self.incremental)
+    }
+
+    /// Sets the incremental flag of the priority
+    #[inline]
+    pub const fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Returns the urgency of the priority, where lower values are more urgent
+    #[inline]
+    pub const fn urgency(&self) -> u8 {
+        self.urgency
+    }
+
+    /// Returns `true` if the stream may be interleaved with other streams of the same urgency
+    #[inline]
+    pub const fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+}
+
+/// Returned when an urgency exceeds [`Priority::MAX_URGENCY`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UrgencyError;
+
+impl fmt::Display for UrgencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "urgency must be no greater than {}",
+            Priority::MAX_URGENCY
+        )
+    }
+}
+
+impl core::error::Error for UrgencyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urgency_validation() {
+        for urgency in Priority::MIN_URGENCY..=Priority::MAX_URGENCY {
+            let priority = Priority::new(urgency, false).unwrap();
+            assert_eq!(priority.urgency(), urgency);
+            assert!(!priority.is_incremental());
+        }
+
+        assert_eq!(
+            Priority::new(Priority::MAX_URGENCY + 1, true),
+            Err(UrgencyError)
+        );
+        assert_eq!(Priority::default().with_urgency(u8::MAX), Err(UrgencyError));
+    }
+}
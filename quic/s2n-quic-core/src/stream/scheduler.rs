@@ -0,0 +1,185 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Controls the order in which streams with pending data are given the opportunity to transmit
+//!
+//! Streams which are waiting to transmit are kept in a queue which is sorted with
+//! [`Scheduler::cmp`]. When transmitting, streams at the front of the queue go first. After a
+//! stream has transmitted [`Scheduler::quantum`] times, it is moved behind the other queued streams
+//! which compare as [`Ordering::Equal`] to it.
+
+use crate::stream::{Priority, StreamId};
+use core::cmp::Ordering;
+
+/// Creates a [`Scheduler`] for each connection
+pub trait Endpoint: 'static + Send {
+    type Scheduler: Scheduler;
+
+    /// Returns a new scheduler for a connection
+    fn new_scheduler(&mut self) -> Self::Scheduler;
+}
+
+/// Orders streams which are waiting to transmit data
+pub trait Scheduler: 'static + Send + core::fmt::Debug {
+    /// Compares two streams which are waiting to transmit
+    ///
+    /// Streams which compare as [`Ordering::Less`] will transmit first. Streams which compare as
+    /// [`Ordering::Equal`] take turns transmitting, as determined by [`Scheduler::quantum`].
+    fn cmp(&self, a: &StreamInfo, b: &StreamInfo) -> Ordering;
+
+    /// Returns the number of times a stream may transmit before it yields to other streams
+    /// of the same order
+    ///
+    /// `stream_batch_size` is the value configured on the connection limits. If `None` is
+    /// returned, the stream transmits until it runs out of data or is blocked.
+    fn quantum(&self, stream: &StreamInfo, stream_batch_size: u8) -> Option<u16>;
+}
+
+/// Information about a stream which is waiting to transmit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StreamInfo {
+    /// The ID of the stream
+    pub id: StreamId,
+    /// The priority the application assigned to the stream
+    pub priority: Priority,
+}
+
+impl StreamInfo {
+    #[doc(hidden)]
+    pub fn new(id: StreamId, priority: Priority) -> Self {
+        Self { id, priority }
+    }
+}
+
+macro_rules! impl_endpoint {
+    ($ty:ident) => {
+        impl Endpoint for $ty {
+            type Scheduler = Self;
+
+            #[inline]
+            fn new_scheduler(&mut self) -> Self::Scheduler {
+                *self
+            }
+        }
+    };
+}
+
+/// Transmits streams in order of urgency
+///
+/// Streams of the same urgency are interleaved if they are incremental. Non-incremental streams
+/// are sent ahead of incremental streams of the same urgency and transmit one at a time, in the
+/// order they became ready.
+///
+/// This is the default scheduler.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct StrictPriority;
+
+impl Scheduler for StrictPriority {
+    #[inline]
+    fn cmp(&self, a: &StreamInfo, b: &StreamInfo) -> Ordering {
+        let a = (a.priority.urgency(), a.priority.is_incremental());
+        let b = (b.priority.urgency(), b.priority.is_incremental());
+        a.cmp(&b)
+    }
+
+    #[inline]
+    fn quantum(&self, stream: &StreamInfo, stream_batch_size: u8) -> Option<u16> {
+        if stream.priority.is_incremental() {
+            Some(stream_batch_size as u16)
+        } else {
+            None
+        }
+    }
+}
+
+impl_endpoint!(StrictPriority);
+
+/// Interleaves all streams, giving more urgent streams a larger share of the connection
+///
+/// Each turn, a stream is allowed to transmit `(8 - urgency) * stream_batch_size` times. A stream
+/// with an urgency of `0` therefore transmits 8 times as often as a stream with an urgency of `7`.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct WeightedRoundRobin;
+
+impl Scheduler for WeightedRoundRobin {
+    #[inline]
+    fn cmp(&self, _a: &StreamInfo, _b: &StreamInfo) -> Ordering {
+        Ordering::Equal
+    }
+
+    #[inline]
+    fn quantum(&self, stream: &StreamInfo, stream_batch_size: u8) -> Option<u16> {
+        let weight = (Priority::MAX_URGENCY + 1 - stream.priority.urgency()) as u16;
+        Some(weight * stream_batch_size.max(1) as u16)
+    }
+}
+
+impl_endpoint!(WeightedRoundRobin);
+
+/// Transmits streams one at a time, in the order they became ready, ignoring their priorities
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct Fifo;
+
+impl Scheduler for Fifo {
+    #[inline]
+    fn cmp(&self, _a: &StreamInfo, _b: &StreamInfo) -> Ordering {
+        Ordering::Equal
+    }
+
+    #[inline]
+    fn quantum(&self, _stream: &StreamInfo, _stream_batch_size: u8) -> Option<u16> {
+        None
+    }
+}
+
+impl_endpoint!(Fifo);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{endpoint, stream::StreamType};
+
+    fn info(id: u64, urgency: u8, incremental: bool) -> StreamInfo {
+        let id = StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, id).unwrap();
+        StreamInfo::new(id, Priority::new(urgency, incremental).unwrap())
+    }
+
+    #[test]
+    fn strict_priority_test() {
+        let scheduler = StrictPriority;
+
+        assert_eq!(
+            scheduler.cmp(&info(1, 0, true), &info(0, 1, true)),
+            Ordering::Less
+        );
+        assert_eq!(
+            scheduler.cmp(&info(0, 3, false), &info(1, 3, true)),
+            Ordering::Less
+        );
+        assert_eq!(
+            scheduler.cmp(&info(0, 3, true), &info(1, 3, true)),
+            Ordering::Equal
+        );
+
+        assert_eq!(scheduler.quantum(&info(0, 3, true), 2), Some(2));
+        assert_eq!(scheduler.quantum(&info(0, 3, false), 2), None);
+    }
+
+    #[test]
+    fn weighted_round_robin_test() {
+        let scheduler = WeightedRoundRobin;
+
+        assert_eq!(
+            scheduler.cmp(&info(0, 0, false), &info(1, 7, true)),
+            Ordering::Equal
+        );
+        assert_eq!(scheduler.quantum(&info(0, 0, false), 1), Some(8));
+        assert_eq!(scheduler.quantum(&info(0, 7, false), 1), Some(1));
+        assert_eq!(scheduler.quantum(&info(0, 3, true), 2), Some(10));
+        assert_eq!(scheduler.quantum(&info(0, 7, true), 0), Some(1));
+    }
+}
@@ -403,6 +403,10 @@ impl_errors! {
     /// confidentiality or integrity limit for the AEAD algorithm used by
     /// the given connection.
     AEAD_LIMIT_REACHED = 0xf.with_frame_type(UNKNOWN_FRAME_TYPE),
+
+    /// An endpoint detected an error while performing compatible version
+    /// negotiation, as defined in <https://www.rfc-editor.org/rfc/rfc9368#section-10.2.2>
+    VERSION_NEGOTIATION_ERROR = 0x11.with_frame_type(UNKNOWN_FRAME_TYPE),
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-20.1
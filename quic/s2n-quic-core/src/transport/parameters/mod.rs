@@ -153,6 +153,7 @@ decoder_value!(
             let len = buffer.len();
             let (slice, buffer) = buffer.decode_slice(len)?;
             let parameters = Self::decode_parameters(slice.peek())?;
+            parameters.validate_ack_delays()?;
             Ok((parameters, buffer))
         }
     }
@@ -164,6 +165,7 @@ decoder_value!(
             let len = buffer.len();
             let (slice, buffer) = buffer.decode_slice(len)?;
             let parameters = Self::decode_parameters(slice.peek())?;
+            parameters.validate_ack_delays()?;
             Ok((parameters, buffer))
         }
     }
@@ -858,6 +860,75 @@ impl TransportParameterValidator for MaxAckDelay {
     }
 }
 
+// min_ack_delay (0xff04de1b): The minimum amount of time, in microseconds, that the endpoint
+//    sending this value is willing to delay an acknowledgment. This transport parameter is
+//    defined by the QUIC Acknowledgment Frequency extension (draft-ietf-quic-ack-frequency)
+//    and indicates the endpoint supports receiving ACK_FREQUENCY and IMMEDIATE_ACK frames.
+
+optional_transport_parameter!(MinAckDelay);
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct MinAckDelay(VarInt);
+
+impl MinAckDelay {
+    /// The largest value of `min_ack_delay`, in microseconds, that can be sent
+    pub const MAX_MICROS: u64 = (1 << 24) - 1;
+
+    /// Creates a `MinAckDelay` with the given `Duration`
+    pub fn new(value: Duration) -> Result<Self, ValidationError> {
+        let value: VarInt = value.as_micros().try_into()?;
+        Ok(Self(value).validate()?)
+    }
+
+    /// Converts the `min_ack_delay` into a `core::time::Duration`
+    pub const fn as_duration(self) -> Duration {
+        Duration::from_micros(self.0.as_u64())
+    }
+}
+
+impl TransportParameter for MinAckDelay {
+    type CodecValue = Self;
+
+    const ID: TransportParameterId = TransportParameterId::from_u32(0xff04de1b);
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!("MinAckDelay is an optional transport parameter, so the default is None")
+    }
+}
+
+impl TransportParameterValidator for MinAckDelay {
+    fn validate(self) -> Result<Self, DecoderError> {
+        decoder_invariant!(
+            *self.0 <= Self::MAX_MICROS,
+            "min_ack_delay must be less than 2^24"
+        );
+        Ok(self)
+    }
+}
+
+decoder_value!(
+    impl<'a> MinAckDelay {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (value, buffer) = buffer.decode()?;
+            Ok((Self(value), buffer))
+        }
+    }
+);
+
+impl EncoderValue for MinAckDelay {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        self.0.encode(buffer)
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# disable_active_migration (0x0c): The disable active migration
 //#    transport parameter is included if the endpoint does not support
@@ -1074,6 +1145,133 @@ impl From<connection::id::LocalId> for InitialSourceConnectionId {
 connection_id_parameter!(RetrySourceConnectionId, LocalId, 0x10);
 optional_transport_parameter!(RetrySourceConnectionId);
 
+/// Used by endpoints to perform compatible version negotiation
+///
+/// The parameter contains the version the endpoint chose for the connection, followed by the
+/// versions the endpoint is willing to use, as defined in
+/// <https://www.rfc-editor.org/rfc/rfc9368#section-3>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct VersionInformation {
+    chosen_version: u32,
+    len: u8,
+    available_versions: [u32; VERSION_INFORMATION_MAX_LEN as usize],
+}
+// The maximum number of available versions that will be stored from
+// the `VersionInformation` transport parameter
+const VERSION_INFORMATION_MAX_LEN: u8 = 8;
+
+impl VersionInformation {
+    /// Create a `VersionInformation` for the `chosen_version` and the `available_versions`
+    /// the endpoint supports
+    pub fn new<I: IntoIterator<Item = u32>>(chosen_version: u32, available_versions: I) -> Self {
+        debug_assert_ne!(chosen_version, 0, "the chosen version cannot be 0");
+
+        let mut versions = [0; VERSION_INFORMATION_MAX_LEN as usize];
+        let mut len = 0;
+
+        for version in available_versions {
+            debug_assert!(
+                len < VERSION_INFORMATION_MAX_LEN,
+                "Only {VERSION_INFORMATION_MAX_LEN} available versions are supported"
+            );
+            ensure!(len < VERSION_INFORMATION_MAX_LEN, break);
+
+            versions[len as usize] = version;
+            len += 1;
+        }
+
+        Self {
+            chosen_version,
+            len,
+            available_versions: versions,
+        }
+    }
+
+    /// The version the endpoint chose for the connection
+    ///
+    /// Returns `None` if the parameter was not sent by the peer
+    #[inline]
+    pub fn chosen_version(&self) -> Option<u32> {
+        Some(self.chosen_version).filter(|version| *version != 0)
+    }
+
+    /// The versions the endpoint is willing to use, in order of preference
+    #[inline]
+    pub fn available_versions(&self) -> &[u32] {
+        &self.available_versions[..self.len as usize]
+    }
+}
+
+impl TransportParameter for VersionInformation {
+    const ID: TransportParameterId = TransportParameterId::from_u32(0x11);
+    type CodecValue = Self;
+
+    fn from_codec_value(value: Self::CodecValue) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self::CodecValue> {
+        if *self == Self::default_value() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl EncoderValue for VersionInformation {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        self.chosen_version.encode(buffer);
+        for version in self.available_versions() {
+            version.encode(buffer);
+        }
+    }
+}
+
+decoder_value!(
+    impl<'a> VersionInformation {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            // A chosen or available version equal to zero is a parsing failure
+            // See https://www.rfc-editor.org/rfc/rfc9368#section-3
+            let (chosen_version, mut buffer) = buffer.decode::<u32>()?;
+            decoder_invariant!(chosen_version != 0, "the chosen version cannot be 0");
+
+            let mut available_versions = [0; VERSION_INFORMATION_MAX_LEN as usize];
+            let mut len = 0;
+
+            // the remaining bytes must be a list of 32-bit versions, which is enforced
+            // by failing to decode a partial version
+            while !buffer.is_empty() {
+                let (version, remaining) = buffer.decode::<u32>()?;
+                buffer = remaining;
+
+                decoder_invariant!(version != 0, "an available version cannot be 0");
+
+                // only store up to the max number of versions and ignore the rest
+                if len < VERSION_INFORMATION_MAX_LEN as usize {
+                    available_versions[len] = version;
+                    len += 1;
+                }
+            }
+
+            Ok((
+                Self {
+                    chosen_version,
+                    len: len as u8,
+                    available_versions,
+                },
+                buffer,
+            ))
+        }
+    }
+);
+
+impl TransportParameterValidator for VersionInformation {}
+
 /// Used by the client to indicate which versions of s2n-quic-dc it supports
 /// and by the server to indicate which version it is using
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
@@ -1299,12 +1497,14 @@ impl<
     pub fn ack_settings(&self) -> ack::Settings {
         let Self {
             max_ack_delay,
+            min_ack_delay,
             ack_delay_exponent,
             ..
         } = self;
 
         ack::Settings {
             max_ack_delay: max_ack_delay.as_duration(),
+            min_ack_delay: min_ack_delay.map(MinAckDelay::as_duration),
             ack_delay_exponent: **ack_delay_exponent,
             ..Default::default()
         }
@@ -1558,6 +1758,7 @@ impl_transport_parameters!(
         max_datagram_frame_size: MaxDatagramFrameSize,
         ack_delay_exponent: AckDelayExponent,
         max_ack_delay: MaxAckDelay,
+        min_ack_delay: Option<MinAckDelay>,
         migration_support: MigrationSupport,
         active_connection_id_limit: ActiveConnectionIdLimit,
         original_destination_connection_id: OriginalDestinationConnectionId,
@@ -1565,6 +1766,7 @@ impl_transport_parameters!(
         preferred_address: PreferredAddress,
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
+        version_information: VersionInformation,
         dc_supported_versions: DcSupportedVersions,
     }
 );
@@ -1610,9 +1812,22 @@ impl<
             initial_max_streams_uni
         );
         load!(max_ack_delay, max_ack_delay);
+        load!(min_ack_delay, min_ack_delay);
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
         load!(migration_support, migration_support);
     }
+
+    /// Ensures the `min_ack_delay` does not exceed the `max_ack_delay`
+    fn validate_ack_delays(&self) -> Result<(), DecoderError> {
+        if let Some(min_ack_delay) = self.min_ack_delay {
+            decoder_invariant!(
+                min_ack_delay.as_duration() <= self.max_ack_delay.as_duration(),
+                "min_ack_delay cannot be greater than max_ack_delay"
+            );
+        }
+
+        Ok(())
+    }
 }
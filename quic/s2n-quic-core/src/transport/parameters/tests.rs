@@ -68,6 +68,7 @@ fn server_transport_parameters() -> ServerTransportParameters {
         max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
+        min_ack_delay: Some(MinAckDelay::new(Duration::from_millis(1)).unwrap()),
         migration_support: MigrationSupport::Disabled,
         active_connection_id_limit: integer_value.try_into().unwrap(),
         original_destination_connection_id: Some([1, 2, 3, 4, 5, 6, 7, 8][..].try_into().unwrap()),
@@ -80,6 +81,7 @@ fn server_transport_parameters() -> ServerTransportParameters {
         }),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
+        version_information: VersionInformation::new(2, [2, 1]),
         dc_supported_versions: DcSupportedVersions {
             len: 1,
             versions: [3, 0, 0, 0],
@@ -114,6 +116,7 @@ fn client_transport_parameters() -> ClientTransportParameters {
         max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
+        min_ack_delay: Some(MinAckDelay::new(Duration::from_millis(1)).unwrap()),
         migration_support: MigrationSupport::Disabled,
         active_connection_id_limit: integer_value.try_into().unwrap(),
         original_destination_connection_id: Default::default(),
@@ -121,6 +124,7 @@ fn client_transport_parameters() -> ClientTransportParameters {
         preferred_address: Default::default(),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Default::default(),
+        version_information: VersionInformation::new(1, [1, 2]),
         dc_supported_versions: DcSupportedVersions {
             len: 4,
             versions: [1, 2, 3, 4],
@@ -188,6 +192,26 @@ fn ignore_unknown_parameter() {
     assert_eq!(0, remaining.len());
 }
 
+#[test]
+fn min_ack_delay_validation() {
+    assert!(MinAckDelay::new(Duration::from_micros(MinAckDelay::MAX_MICROS)).is_ok());
+    assert!(MinAckDelay::new(Duration::from_micros(MinAckDelay::MAX_MICROS + 1)).is_err());
+
+    // min_ack_delay cannot be greater than max_ack_delay
+    let mut value = client_transport_parameters();
+    value.min_ack_delay = Some(MinAckDelay::new(value.max_ack_delay.as_duration()).unwrap());
+    assert_codec_round_trip_value!(ClientTransportParameters, value);
+
+    value.min_ack_delay = Some(
+        MinAckDelay::new(value.max_ack_delay.as_duration() + Duration::from_micros(1)).unwrap(),
+    );
+    let mut buffer = vec![0; 32 * 1024];
+    let mut encoder = s2n_codec::EncoderBuffer::new(&mut buffer);
+    encoder.encode(&value);
+    let (encoded, _) = encoder.split_off();
+    assert!(ClientTransportParameters::decode(DecoderBuffer::new(encoded)).is_err());
+}
+
 #[test]
 fn compute_data_window_test() {
     assert_eq!(
@@ -307,6 +331,63 @@ fn future_larger_supported_versions() {
     assert_eq!(0, remaining.len());
 }
 
+#[test]
+fn version_information() {
+    for len in 0..=VERSION_INFORMATION_MAX_LEN {
+        let versions: Vec<u32> = (1..=len as u32).collect();
+
+        let version_information = VersionInformation::new(1, versions.iter().copied());
+        assert_eq!(Some(1), version_information.chosen_version());
+        assert_eq!(&versions[..], version_information.available_versions());
+
+        let encoded = version_information.encode_to_vec();
+        assert_eq!((len as usize + 1) * 4, encoded.len());
+
+        let decoder = DecoderBuffer::new(encoded.as_slice());
+        let (decoded, remaining) = VersionInformation::decode(decoder).expect("Decoding succeeds");
+        assert!(remaining.is_empty());
+        assert_eq!(version_information, decoded);
+    }
+
+    // the parameter isn't sent by default
+    assert_eq!(None, VersionInformation::default().chosen_version());
+}
+
+#[test]
+fn version_information_invalid() {
+    let invalid: &[&[u8]] = &[
+        // empty parameter
+        &[],
+        // zero chosen version
+        &[0, 0, 0, 0, 0, 0, 0, 1],
+        // zero available version
+        &[0, 0, 0, 1, 0, 0, 0, 0],
+        // available versions is not a multiple of 4 bytes
+        &[0, 0, 0, 1, 0, 0, 1],
+    ];
+
+    for bytes in invalid {
+        let decoder = DecoderBuffer::new(bytes);
+        assert!(VersionInformation::decode(decoder).is_err(), "{bytes:?}");
+    }
+}
+
+#[test]
+fn version_information_more_than_max_len() {
+    let mut bytes = vec![];
+    for version in 1..=(VERSION_INFORMATION_MAX_LEN as u32 + 2) {
+        bytes.extend_from_slice(&version.to_be_bytes());
+    }
+
+    let decoder = DecoderBuffer::new(&bytes);
+    let (decoded, remaining) = VersionInformation::decode(decoder).expect("Decoding succeeds");
+    assert!(remaining.is_empty());
+    assert_eq!(Some(1), decoded.chosen_version());
+    // the extra versions are ignored
+    let expected: Vec<u32> = (2..=VERSION_INFORMATION_MAX_LEN as u32 + 1).collect();
+    assert_eq!(&expected[..], decoded.available_versions());
+}
+
 #[test]
 #[should_panic]
 fn dc_selected_versions_for_client_too_big() {
@@ -0,0 +1,307 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC version identifiers and the endpoint version configuration
+
+use core::fmt;
+
+/// A 32-bit QUIC version identifier
+pub type Version = u32;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-15
+//# This version of the specification is identified by the number
+//# 0x00000001.
+pub const VERSION_1: Version = 0x0000_0001;
+
+/// QUIC version 2, as defined in <https://www.rfc-editor.org/rfc/rfc9369#section-3.1>
+pub const VERSION_2: Version = 0x6b33_43cf;
+
+/// Returns `true` if the version is implemented by this library
+#[inline]
+pub const fn is_known(version: Version) -> bool {
+    matches!(version, VERSION_1 | VERSION_2)
+}
+
+/// Returns `true` if the version uses the QUIC version 2 wire image and key derivation
+#[inline]
+pub const fn is_v2(version: Version) -> bool {
+    version == VERSION_2
+}
+
+/// Returns `true` if a connection started with the `original` version can be upgraded to the
+/// `negotiated` version without an extra round trip
+///
+/// Every version implemented by this library uses the same TLS handshake, which makes them
+/// compatible with each other, as defined in <https://www.rfc-editor.org/rfc/rfc9368#section-2.2>.
+#[inline]
+pub const fn is_compatible(original: Version, negotiated: Version) -> bool {
+    is_known(original) && is_known(negotiated)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The version is not implemented by this library
+    UnknownVersion(Version),
+    /// The preferred version is not enabled
+    PreferredVersionDisabled(Version),
+    /// All of the versions were disabled
+    NoVersionsEnabled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownVersion(version) => {
+                write!(f, "QUIC version {version:#010x} is not supported")
+            }
+            Self::PreferredVersionDisabled(version) => write!(
+                f,
+                "the preferred QUIC version {version:#010x} must also be enabled"
+            ),
+            Self::NoVersionsEnabled => write!(f, "at least one QUIC version must be enabled"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// The set of QUIC versions an endpoint accepts and the version it prefers to use
+///
+/// Servers accept Initial packets for any of the enabled versions. Clients open connections
+/// with the preferred version. If the client makes the server's preferred version available,
+/// the server switches the connection to it with compatible version negotiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    version_1: bool,
+    version_2: bool,
+    preferred: Version,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version_1: true,
+            version_2: false,
+            preferred: VERSION_1,
+        }
+    }
+}
+
+impl Config {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// The version used by clients when opening a new connection
+    #[inline]
+    pub fn preferred_version(&self) -> Version {
+        self.preferred
+    }
+
+    /// Returns `true` if the version is enabled on the endpoint
+    #[inline]
+    pub fn is_supported(&self, version: Version) -> bool {
+        match version {
+            VERSION_1 => self.version_1,
+            VERSION_2 => self.version_2,
+            _ => false,
+        }
+    }
+
+    /// The enabled versions, ordered by preference
+    #[inline]
+    pub fn supported_versions(&self) -> &'static [Version] {
+        match (self.version_1, self.version_2, self.preferred) {
+            (true, true, VERSION_2) => &[VERSION_2, VERSION_1],
+            (true, true, _) => &[VERSION_1, VERSION_2],
+            (false, true, _) => &[VERSION_2],
+            _ => &[VERSION_1],
+        }
+    }
+
+    /// Selects the version for a connection with compatible version negotiation
+    ///
+    /// The first enabled version, in order of preference, that is compatible with the
+    /// `original_version` and available to the peer is selected. `None` is returned if the
+    /// connection should remain on the `original_version`.
+    #[inline]
+    pub fn select_compatible_version(
+        &self,
+        original_version: Version,
+        peer_versions: &[Version],
+    ) -> Option<Version> {
+        for &version in self.supported_versions() {
+            // the original version is preferred over the rest of the versions
+            ensure!(version != original_version, None);
+
+            if is_compatible(original_version, version) && peer_versions.contains(&version) {
+                return Some(version);
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Builder {
+    version_1: Option<bool>,
+    version_2: Option<bool>,
+    preferred: Option<Version>,
+}
+
+impl Builder {
+    /// Enables or disables QUIC version 1 (default: enabled)
+    pub fn with_version_1(mut self, enabled: bool) -> Result<Self, Error> {
+        self.version_1 = Some(enabled);
+        Ok(self)
+    }
+
+    /// Enables or disables QUIC version 2 (default: disabled)
+    pub fn with_version_2(mut self, enabled: bool) -> Result<Self, Error> {
+        self.version_2 = Some(enabled);
+        Ok(self)
+    }
+
+    /// Sets the version to use when opening connections (default: QUIC version 1)
+    ///
+    /// Setting the preferred version implicitly enables it, unless it was explicitly
+    /// disabled.
+    pub fn with_preferred_version(mut self, version: Version) -> Result<Self, Error> {
+        ensure!(is_known(version), Err(Error::UnknownVersion(version)));
+        self.preferred = Some(version);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Config, Error> {
+        let preferred = self.preferred.unwrap_or(VERSION_1);
+        let version_1 = self.version_1.unwrap_or(true);
+        let version_2 = self.version_2.unwrap_or(self.preferred == Some(VERSION_2));
+
+        let config = Config {
+            version_1,
+            version_2,
+            preferred,
+        };
+
+        ensure!(version_1 || version_2, Err(Error::NoVersionsEnabled));
+        ensure!(
+            config.is_supported(preferred),
+            Err(Error::PreferredVersionDisabled(preferred))
+        );
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_test() {
+        let config = Config::default();
+        assert_eq!(config, Config::builder().build().unwrap());
+        assert_eq!(config.preferred_version(), VERSION_1);
+        assert_eq!(config.supported_versions(), &[VERSION_1]);
+        assert!(!config.is_supported(VERSION_2));
+    }
+
+    #[test]
+    fn prefer_v2_test() {
+        let config = Config::builder()
+            .with_preferred_version(VERSION_2)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.preferred_version(), VERSION_2);
+        assert_eq!(config.supported_versions(), &[VERSION_2, VERSION_1]);
+    }
+
+    #[test]
+    fn v2_only_test() {
+        let config = Config::builder()
+            .with_version_1(false)
+            .unwrap()
+            .with_preferred_version(VERSION_2)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.supported_versions(), &[VERSION_2]);
+        assert!(!config.is_supported(VERSION_1));
+    }
+
+    #[test]
+    fn select_compatible_version_test() {
+        let prefer_v2 = Config::builder()
+            .with_preferred_version(VERSION_2)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            prefer_v2.select_compatible_version(VERSION_1, &[VERSION_1, VERSION_2]),
+            Some(VERSION_2)
+        );
+        // the peer didn't make version 2 available
+        assert_eq!(
+            prefer_v2.select_compatible_version(VERSION_1, &[VERSION_1]),
+            None
+        );
+        // the connection is already using the preferred version
+        assert_eq!(
+            prefer_v2.select_compatible_version(VERSION_2, &[VERSION_2, VERSION_1]),
+            None
+        );
+
+        let prefer_v1 = Config::builder()
+            .with_version_2(true)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // the original version is preferred over the other enabled versions
+        assert_eq!(
+            prefer_v1.select_compatible_version(VERSION_1, &[VERSION_1, VERSION_2]),
+            None
+        );
+        assert_eq!(
+            prefer_v1.select_compatible_version(VERSION_2, &[VERSION_2, VERSION_1]),
+            Some(VERSION_1)
+        );
+
+        // version 2 isn't enabled
+        assert_eq!(
+            Config::default().select_compatible_version(VERSION_1, &[VERSION_1, VERSION_2]),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_test() {
+        assert_eq!(
+            Config::builder().with_preferred_version(0xff00_001d).err(),
+            Some(Error::UnknownVersion(0xff00_001d))
+        );
+
+        assert_eq!(
+            Config::builder()
+                .with_version_1(false)
+                .unwrap()
+                .build()
+                .err(),
+            Some(Error::NoVersionsEnabled)
+        );
+
+        assert_eq!(
+            Config::builder()
+                .with_version_2(false)
+                .unwrap()
+                .with_preferred_version(VERSION_2)
+                .unwrap()
+                .build()
+                .err(),
+            Some(Error::PreferredVersionDisabled(VERSION_2))
+        );
+    }
+}
@@ -6,6 +6,7 @@ use core::fmt;
 use s2n_quic_core::{
     assume,
     crypto::{label, packet_protection, scatter},
+    version::{self, Version},
 };
 use zeroize::{Zeroize, Zeroizing};
 
@@ -15,6 +16,14 @@ use self::ring as platform;
 
 pub use negotiated::NegotiatedCipherSuite;
 
+/// The HKDF labels used to derive packet protection keys for a QUIC version
+struct Labels {
+    key: &'static [u8],
+    iv: &'static [u8],
+    hp: &'static [u8],
+    key_update: &'static [u8],
+}
+
 macro_rules! impl_cipher_suite {
     (
         $name:ident,
@@ -27,6 +36,10 @@ macro_rules! impl_cipher_suite {
         $iv_label:expr,
         $hp_label:expr,
         $key_update_label:expr,
+        $v2_key_label:expr,
+        $v2_iv_label:expr,
+        $v2_hp_label:expr,
+        $v2_key_update_label:expr,
         $confidentiality_limit:expr,
         $integrity_limit:expr,
         $test_name:ident
@@ -40,24 +53,51 @@ macro_rules! impl_cipher_suite {
 
             type Key = platform::$lower::Key;
 
+            const V1_LABELS: Labels = Labels {
+                key: &$key_label,
+                iv: &$iv_label,
+                hp: &$hp_label,
+                key_update: &$key_update_label,
+            };
+
+            /// See <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.2>
+            const V2_LABELS: Labels = Labels {
+                key: &$v2_key_label,
+                iv: &$v2_iv_label,
+                hp: &$v2_hp_label,
+                key_update: &$v2_key_update_label,
+            };
+
             // ignore casing warnings in order to preserve the IANA name
             #[allow(non_camel_case_types, clippy::all)]
             pub struct $name {
                 secret: hkdf::Prk,
                 iv: iv::Iv,
                 key: Key,
+                labels: &'static Labels,
             }
 
             impl $name {
-                pub fn new(secret: hkdf::Prk) -> (Self, HeaderKey) {
-                    let iv = Self::new_iv(&secret);
+                pub fn new(version: Version, secret: hkdf::Prk) -> (Self, HeaderKey) {
+                    let labels = if version::is_v2(version) {
+                        &V2_LABELS
+                    } else {
+                        &V1_LABELS
+                    };
+
+                    let iv = Self::new_iv(&secret, labels);
                     let key = {
-                        let secret = Self::new_key_secret(&secret);
+                        let secret = Self::new_key_secret(&secret, labels);
                         Key::new(&*secret)
                     };
-                    let header_key = Self::new_header_key(&secret);
+                    let header_key = Self::new_header_key(&secret, labels);
 
-                    let key = Self { secret, iv, key };
+                    let key = Self {
+                        secret,
+                        iv,
+                        key,
+                        labels,
+                    };
 
                     (key, header_key)
                 }
@@ -68,25 +108,31 @@ macro_rules! impl_cipher_suite {
                 pub fn update(&self) -> Self {
                     let secret: hkdf::Prk = self
                         .secret
-                        .expand(&[&$key_update_label], $digest)
+                        .expand(&[self.labels.key_update], $digest)
                         .expect("label size verified")
                         .into();
 
-                    let iv = Self::new_iv(&secret);
+                    let labels = self.labels;
+                    let iv = Self::new_iv(&secret, labels);
                     let key = {
-                        let key = Self::new_key_secret(&secret);
+                        let key = Self::new_key_secret(&secret, labels);
                         // ask the existing key to derive the next one so it can persist any
                         // configuration
                         self.key.update(&*key)
                     };
-                    Self { secret, iv, key }
+                    Self {
+                        secret,
+                        iv,
+                        key,
+                        labels,
+                    }
                 }
 
-                fn new_key_secret(secret: &hkdf::Prk) -> Zeroizing<[u8; KEY_LEN]> {
+                fn new_key_secret(secret: &hkdf::Prk, labels: &Labels) -> Zeroizing<[u8; KEY_LEN]> {
                     let mut key = Zeroizing::new([0u8; KEY_LEN]);
 
                     secret
-                        .expand(&[&$key_label], &$cipher)
+                        .expand(&[labels.key], &$cipher)
                         .expect("label size verified")
                         .fill(&mut key.as_mut())
                         .expect("fill size verified");
@@ -94,12 +140,12 @@ macro_rules! impl_cipher_suite {
                     key
                 }
 
-                fn new_iv(secret: &hkdf::Prk) -> iv::Iv {
-                    iv::Iv::new(secret, &$iv_label)
+                fn new_iv(secret: &hkdf::Prk, labels: &Labels) -> iv::Iv {
+                    iv::Iv::new(secret, labels.iv)
                 }
 
-                fn new_header_key(secret: &hkdf::Prk) -> HeaderKey {
-                    HeaderKey::new::<{ KEY_LEN }>(secret, &$hp_label, &$header_protection)
+                fn new_header_key(secret: &hkdf::Prk, labels: &Labels) -> HeaderKey {
+                    HeaderKey::new::<{ KEY_LEN }>(secret, labels.hp, &$header_protection)
                 }
             }
 
@@ -234,6 +280,33 @@ macro_rules! impl_cipher_suite {
                     $key_update_label,
                     "key update label mismatch"
                 );
+
+                assert_eq!(
+                    compute_vec_label($cipher.key_len(), b"quicv2 key"),
+                    $v2_key_label,
+                    "v2 key label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label(iv::NONCE_LEN, b"quicv2 iv"),
+                    $v2_iv_label,
+                    "v2 iv label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label($header_protection.key_len(), b"quicv2 hp"),
+                    $v2_hp_label,
+                    "v2 hp label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label(
+                        $digest.hmac_algorithm().digest_algorithm().output_len(),
+                        b"quicv2 ku"
+                    ),
+                    $v2_key_update_label,
+                    "v2 key update label mismatch"
+                );
             }
         }
 
@@ -255,6 +328,10 @@ impl_cipher_suite!(
     label::QUIC_IV_12,
     label::QUIC_HP_32,
     label::QUIC_KU_48,
+    label::QUICV2_KEY_32,
+    label::QUICV2_IV_12,
+    label::QUICV2_HP_32,
+    label::QUICV2_KU_48,
     u64::pow(2, 23), // Confidentiality limit
     u64::pow(2, 52), // Integrity limit
     tls_aes_256_gcm_sha384_test
@@ -275,6 +352,10 @@ impl_cipher_suite!(
     label::QUIC_IV_12,
     label::QUIC_HP_32,
     label::QUIC_KU_32,
+    label::QUICV2_KEY_32,
+    label::QUICV2_IV_12,
+    label::QUICV2_HP_32,
+    label::QUICV2_KU_32,
     u64::pow(2, 62), // Confidentiality limit even though specification notes it can be disregarded
     u64::pow(2, 36), // Integrity limit
     tls_chacha20_poly1305_sha256_test
@@ -292,6 +373,10 @@ impl_cipher_suite!(
     label::QUIC_IV_12,
     label::QUIC_HP_16,
     label::QUIC_KU_32,
+    label::QUICV2_KEY_16,
+    label::QUICV2_IV_12,
+    label::QUICV2_HP_16,
+    label::QUICV2_KU_32,
     u64::pow(2, 23), // Confidentiality limit
     u64::pow(2, 52), // Integrity limit
     tls_aes_128_gcm_sha256_test
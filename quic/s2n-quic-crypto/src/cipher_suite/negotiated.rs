@@ -8,7 +8,10 @@ use crate::{
     hkdf,
 };
 use core::fmt;
-use s2n_quic_core::crypto::{self, packet_protection, scatter};
+use s2n_quic_core::{
+    crypto::{self, packet_protection, scatter},
+    version::Version,
+};
 
 // ignore casing warnings in order to preserve the IANA name
 #[allow(non_camel_case_types, clippy::all)]
@@ -48,19 +51,23 @@ impl From<TLS_AES_128_GCM_SHA256> for NegotiatedCipherSuite {
 }
 
 impl NegotiatedCipherSuite {
-    /// Create a cipher_suite with a given negotiated algorithm and secret
-    pub fn new(algorithm: &aead::Algorithm, secret: hkdf::Prk) -> Option<(Self, HeaderKey)> {
+    /// Create a cipher_suite with a given QUIC version, negotiated algorithm and secret
+    pub fn new(
+        version: Version,
+        algorithm: &aead::Algorithm,
+        secret: hkdf::Prk,
+    ) -> Option<(Self, HeaderKey)> {
         Some(match algorithm {
             _ if algorithm == &aead::AES_256_GCM => {
-                let (cipher_suite, header_key) = TLS_AES_256_GCM_SHA384::new(secret);
+                let (cipher_suite, header_key) = TLS_AES_256_GCM_SHA384::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ if algorithm == &aead::CHACHA20_POLY1305 => {
-                let (cipher_suite, header_key) = TLS_CHACHA20_POLY1305_SHA256::new(secret);
+                let (cipher_suite, header_key) = TLS_CHACHA20_POLY1305_SHA256::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ if algorithm == &aead::AES_128_GCM => {
-                let (cipher_suite, header_key) = TLS_AES_128_GCM_SHA256::new(secret);
+                let (cipher_suite, header_key) = TLS_AES_128_GCM_SHA256::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ => return None,
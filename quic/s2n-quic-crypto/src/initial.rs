@@ -6,9 +6,10 @@ use s2n_quic_core::{
     crypto::{
         self,
         label::{CLIENT_IN, SERVER_IN},
-        packet_protection, scatter, Key, INITIAL_SALT,
+        packet_protection, scatter, Key, INITIAL_SALT, INITIAL_SALT_V2,
     },
     endpoint,
+    version::{self, Version},
 };
 
 header_key!(InitialHeaderKey);
@@ -24,12 +25,22 @@ pub struct InitialKey {
 lazy_static::lazy_static! {
     /// Compute the Initial salt once, as the seed is constant
     static ref INITIAL_SIGNING_KEY: hkdf::Salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &INITIAL_SALT);
+    static ref INITIAL_SIGNING_KEY_V2: hkdf::Salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &INITIAL_SALT_V2);
 }
 
 impl InitialKey {
-    fn new(endpoint: endpoint::Type, connection_id: &[u8]) -> (Self, InitialHeaderKey) {
-        let initial_secret = INITIAL_SIGNING_KEY.extract(connection_id);
-        let digest = INITIAL_SIGNING_KEY.algorithm();
+    fn new(
+        endpoint: endpoint::Type,
+        version: Version,
+        connection_id: &[u8],
+    ) -> (Self, InitialHeaderKey) {
+        let signing_key: &hkdf::Salt = if version::is_v2(version) {
+            &INITIAL_SIGNING_KEY_V2
+        } else {
+            &INITIAL_SIGNING_KEY
+        };
+        let initial_secret = signing_key.extract(connection_id);
+        let digest = signing_key.algorithm();
 
         let client_secret = initial_secret
             .expand(&[&CLIENT_IN], digest)
@@ -43,12 +54,12 @@ impl InitialKey {
 
         let (sealer, opener) = match endpoint {
             endpoint::Type::Client => (
-                CipherSuite::new(client_secret),
-                CipherSuite::new(server_secret),
+                CipherSuite::new(version, client_secret),
+                CipherSuite::new(version, server_secret),
             ),
             endpoint::Type::Server => (
-                CipherSuite::new(server_secret),
-                CipherSuite::new(client_secret),
+                CipherSuite::new(version, server_secret),
+                CipherSuite::new(version, client_secret),
             ),
         };
 
@@ -70,12 +81,12 @@ impl InitialKey {
 impl crypto::InitialKey for InitialKey {
     type HeaderKey = InitialHeaderKey;
 
-    fn new_server(connection_id: &[u8]) -> (Self, Self::HeaderKey) {
-        Self::new(endpoint::Type::Server, connection_id)
+    fn new_server(version: Version, connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        Self::new(endpoint::Type::Server, version, connection_id)
     }
 
-    fn new_client(connection_id: &[u8]) -> (Self, Self::HeaderKey) {
-        Self::new(endpoint::Type::Client, connection_id)
+    fn new_client(version: Version, connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        Self::new(endpoint::Type::Client, version, connection_id)
     }
 }
 
@@ -137,13 +148,14 @@ mod tests {
         },
         inet::SocketAddress,
         packet::{encoding::PacketEncoder, initial::CleartextInitial, ProtectedPacket},
+        version::{VERSION_1, VERSION_2},
     };
 
     #[test]
     fn rfc_example_server_test() {
         test_round_trip(
-            &mut InitialKey::new_client(&EXAMPLE_DCID),
-            &InitialKey::new_server(&EXAMPLE_DCID),
+            &mut InitialKey::new_client(VERSION_1, &EXAMPLE_DCID),
+            &InitialKey::new_server(VERSION_1, &EXAMPLE_DCID),
             &EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
             &EXAMPLE_CLIENT_INITIAL_PAYLOAD,
         );
@@ -152,13 +164,37 @@ mod tests {
     #[test]
     fn rfc_example_client_test() {
         test_round_trip(
-            &mut InitialKey::new_server(&EXAMPLE_DCID),
-            &InitialKey::new_client(&EXAMPLE_DCID),
+            &mut InitialKey::new_server(VERSION_1, &EXAMPLE_DCID),
+            &InitialKey::new_client(VERSION_1, &EXAMPLE_DCID),
             &EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
             &EXAMPLE_SERVER_INITIAL_PAYLOAD,
         );
     }
 
+    #[test]
+    fn version_2_key_test() {
+        let header = [0u8; 16];
+        let payload = b"hello, quic v2";
+
+        let (mut client, _) = InitialKey::new_client(VERSION_2, &EXAMPLE_DCID);
+        let mut sealed = payload.to_vec();
+        sealed.resize(payload.len() + client.tag_len(), 0);
+        {
+            let mut buffer = EncoderBuffer::new(&mut sealed);
+            buffer.advance_position(payload.len());
+            let mut buffer = scatter::Buffer::new(buffer);
+            client.encrypt(1, &header, &mut buffer).unwrap();
+        }
+
+        // version 1 keys must not be able to open a version 2 payload
+        let (server_v1, _) = InitialKey::new_server(VERSION_1, &EXAMPLE_DCID);
+        assert!(server_v1.decrypt(1, &header, &mut sealed.clone()).is_err());
+
+        let (server_v2, _) = InitialKey::new_server(VERSION_2, &EXAMPLE_DCID);
+        server_v2.decrypt(1, &header, &mut sealed).unwrap();
+        assert_eq!(&sealed[..payload.len()], payload);
+    }
+
     fn test_round_trip(
         sealer: &mut (InitialKey, InitialHeaderKey),
         opener: &(InitialKey, InitialHeaderKey),
@@ -15,7 +15,7 @@ mod iv;
 
 #[doc(hidden)]
 pub use aws_lc_rs::{
-    aead as aws_lc_aead, aead::MAX_TAG_LEN, constant_time, digest, hkdf, hkdf::Prk, hmac,
+    aead as aws_lc_aead, aead::MAX_TAG_LEN, cipher, constant_time, digest, hkdf, hkdf::Prk, hmac,
 };
 
 #[derive(Clone)]
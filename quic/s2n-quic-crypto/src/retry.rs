@@ -4,23 +4,32 @@
 use crate::{aws_lc_aead as aead, constant_time};
 use s2n_quic_core::crypto::{
     self, packet_protection,
-    retry::{IntegrityTag, NONCE_BYTES, SECRET_KEY_BYTES},
+    retry::{IntegrityTag, NONCE_BYTES, NONCE_BYTES_V2, SECRET_KEY_BYTES, SECRET_KEY_BYTES_V2},
 };
+use s2n_quic_core::version::{self, Version};
 
 lazy_static::lazy_static! {
     /// Compute the Initial salt once, as the seed is constant
     static ref SECRET_KEY: aead::LessSafeKey = aead::LessSafeKey::new(
         aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES).unwrap(),
     );
+    static ref SECRET_KEY_V2: aead::LessSafeKey = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES_V2).unwrap(),
+    );
 }
 
 #[derive(Debug)]
 pub struct RetryKey;
 
 impl crypto::RetryKey for RetryKey {
-    fn generate_tag(pseudo_packet: &[u8]) -> IntegrityTag {
-        let nonce = aead::Nonce::assume_unique_for_key(NONCE_BYTES);
-        let tag = SECRET_KEY
+    fn generate_tag(version: Version, pseudo_packet: &[u8]) -> IntegrityTag {
+        let (key, nonce): (&aead::LessSafeKey, _) = if version::is_v2(version) {
+            (&SECRET_KEY_V2, NONCE_BYTES_V2)
+        } else {
+            (&SECRET_KEY, NONCE_BYTES)
+        };
+        let nonce = aead::Nonce::assume_unique_for_key(nonce);
+        let tag = key
             .seal_in_place_separate_tag(nonce, aead::Aad::from(pseudo_packet), &mut [])
             .expect("in_out len is 0 and should always be less than the nonce max bytes");
 
@@ -29,8 +38,12 @@ impl crypto::RetryKey for RetryKey {
             .expect("AES_128_GCM tag len should always be 128 bits")
     }
 
-    fn validate(pseudo_packet: &[u8], tag: IntegrityTag) -> Result<(), packet_protection::Error> {
-        let expected = Self::generate_tag(pseudo_packet);
+    fn validate(
+        version: Version,
+        pseudo_packet: &[u8],
+        tag: IntegrityTag,
+    ) -> Result<(), packet_protection::Error> {
+        let expected = Self::generate_tag(version, pseudo_packet);
 
         constant_time::verify_slices_are_equal(&expected, &tag)
             .map_err(|_| packet_protection::Error::DECRYPT_ERROR)
@@ -50,17 +63,40 @@ mod tests {
         packet::number::{PacketNumberSpace, TruncatedPacketNumber},
         random, token,
         varint::VarInt,
+        version::{VERSION_1, VERSION_2},
     };
 
     #[test]
     fn test_tag_validation() {
         let invalid_tag: [u8; 16] = hex!("00112233445566778899aabbccddeeff");
 
+        assert!(RetryKey::validate(
+            VERSION_1,
+            &retry::example::PSEUDO_PACKET,
+            retry::example::EXPECTED_TAG
+        )
+        .is_ok());
         assert!(
-            RetryKey::validate(&retry::example::PSEUDO_PACKET, retry::example::EXPECTED_TAG)
-                .is_ok()
+            RetryKey::validate(VERSION_1, &retry::example::PSEUDO_PACKET, invalid_tag).is_err()
         );
-        assert!(RetryKey::validate(&retry::example::PSEUDO_PACKET, invalid_tag).is_err());
+    }
+
+    #[test]
+    fn test_tag_validation_v2() {
+        assert!(RetryKey::validate(
+            VERSION_2,
+            &retry::example::PSEUDO_PACKET_V2,
+            retry::example::EXPECTED_TAG_V2
+        )
+        .is_ok());
+
+        // a version 1 tag is not valid for a version 2 packet
+        assert!(RetryKey::validate(
+            VERSION_2,
+            &retry::example::PSEUDO_PACKET_V2,
+            retry::example::EXPECTED_TAG
+        )
+        .is_err());
     }
 
     fn pn(space: PacketNumberSpace) -> TruncatedPacketNumber {
@@ -70,13 +106,22 @@ mod tests {
 
     #[test]
     fn test_packet_encode() {
+        check_packet_encode(VERSION_1, &retry::example::PACKET);
+    }
+
+    #[test]
+    fn test_packet_encode_v2() {
+        check_packet_encode(VERSION_2, &retry::example::PACKET_V2);
+    }
+
+    fn check_packet_encode(version: u32, expected: &[u8]) {
         let remote_address = inet::ip::SocketAddress::default();
         let mut token_format = token::testing::Format::new();
         // Values are taken from the retry packet example. Since this is the Initial packet that
         // creates the retry, source_connection_id of the Initial is set to the destination
         // connection id of the retry.
         let packet = packet::initial::Initial {
-            version: 0x01,
+            version,
             destination_connection_id: &retry::example::ODCID[..],
             source_connection_id: &retry::example::DCID[..],
             token: &retry::example::TOKEN[..],
@@ -107,7 +152,7 @@ mod tests {
                 &mut token_format,
                 &mut output_buf,
             ) {
-                assert_eq!(&output_buf[range], &retry::example::PACKET[..]);
+                assert_eq!(&output_buf[range], expected);
             }
         }
     }
@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [HTTP Datagrams](https://www.rfc-editor.org/rfc/rfc9297) over s2n-quic's unreliable datagrams
+//!
+//! HTTP Datagrams require the connection to be configured with the
+//! [default datagram endpoint](s2n_quic::provider::datagram::default::Endpoint) on the QUIC side,
+//! and `enable_datagram(true)` on the h3 [server](h3::server::Builder::enable_datagram) or
+//! [client](h3::client::Builder::enable_datagram) builder to send the `SETTINGS_H3_DATAGRAM`
+//! setting. Protocols built on HTTP Datagrams, such as CONNECT-UDP, are established with an
+//! extended CONNECT request, which the h3 server accepts once `enable_extended_connect(true)`
+//! is set on its builder.
+//!
+//! The connection implements h3-datagram's [`DatagramConnectionExt`], so datagrams are received
+//! through h3-datagram's [`HandleDatagramsExt`](h3_datagram::datagram_handler::HandleDatagramsExt).
+//! h3-datagram 0.0.2 encodes a Quarter Stream ID of zero for every datagram it sends, so datagrams
+//! associated with any other request stream are sent with
+//! [`SendDatagramHandler::send_datagram_for`] instead:
+//!
+//! ```rust,ignore
+//! use h3_datagram::{datagram_handler::HandleDatagramsExt, quic_traits::DatagramConnectionExt};
+//!
+//! let conn = s2n_quic_h3::Connection::new(connection);
+//! let mut sender = DatagramConnectionExt::<Bytes>::send_datagram_handler(&conn);
+//! let (mut driver, mut send_request) = h3::client::new(conn).await?;
+//!
+//! // ... open the request `stream`
+//!
+//! sender.send_datagram_for(stream.id(), Bytes::from_static(b"hello"))?;
+//!
+//! let datagram = driver.get_datagram_reader().read_datagram().await?;
+//! assert_eq!(datagram.stream_id(), stream.id());
+//! ```
+
+use crate::{s2n_quic::convert_connection_error, Connection};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::task::{self, Poll};
+use h3::{
+    proto::varint::VarInt,
+    quic::{ConnectionErrorIncoming, StreamId},
+};
+use h3_datagram::{
+    datagram::EncodedDatagram,
+    quic_traits::{DatagramConnectionExt, RecvDatagram, SendDatagram, SendDatagramErrorIncoming},
+};
+use s2n_quic::provider::datagram::default;
+use std::sync::Arc;
+
+impl<B: Buf> DatagramConnectionExt<B> for Connection {
+    type SendDatagramHandler = SendDatagramHandler;
+    type RecvDatagramHandler = RecvDatagramHandler;
+
+    fn send_datagram_handler(&self) -> Self::SendDatagramHandler {
+        SendDatagramHandler {
+            conn: self.handle().clone(),
+        }
+    }
+
+    fn recv_datagram_handler(&self) -> Self::RecvDatagramHandler {
+        RecvDatagramHandler {
+            conn: self.handle().clone(),
+        }
+    }
+}
+
+/// Sends QUIC datagrams with the default datagram provider
+pub struct SendDatagramHandler {
+    conn: s2n_quic::connection::Handle,
+}
+
+impl SendDatagramHandler {
+    /// Enqueues an HTTP Datagram associated with the given request stream
+    ///
+    /// If the send queue is full, the oldest datagram is dropped.
+    pub fn send_datagram_for(
+        &mut self,
+        stream_id: StreamId,
+        payload: Bytes,
+    ) -> Result<(), SendDatagramErrorIncoming> {
+        //= https://www.rfc-editor.org/rfc/rfc9297#section-2.1
+        //# It is equal to the stream ID of the request stream
+        //# divided by four.
+        if !stream_id.is_request() {
+            return Err(SendDatagramErrorIncoming::NotAvailable);
+        }
+        let quarter_stream_id = VarInt::from_u64(stream_id.into_inner() / 4)
+            .expect("stream IDs divided by four are valid varints");
+
+        let mut datagram = BytesMut::with_capacity(quarter_stream_id.size() + payload.len());
+        quarter_stream_id.encode(&mut datagram);
+        datagram.put_slice(&payload);
+
+        self.send(datagram.freeze())
+    }
+
+    fn send(&mut self, datagram: Bytes) -> Result<(), SendDatagramErrorIncoming> {
+        match self
+            .conn
+            .datagram_mut(|sender: &mut default::Sender| sender.send_datagram_forced(datagram))
+        {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => Err(convert_send_error(err)),
+            // the connection wasn't configured with the default datagram provider
+            Err(_) => Err(SendDatagramErrorIncoming::NotAvailable),
+        }
+    }
+}
+
+impl<B: Buf> SendDatagram<B> for SendDatagramHandler {
+    /// Enqueues a datagram to be sent to the peer
+    ///
+    /// If the send queue is full, the oldest datagram is dropped.
+    fn send_datagram<T: Into<EncodedDatagram<B>>>(
+        &mut self,
+        data: T,
+    ) -> Result<(), SendDatagramErrorIncoming> {
+        let mut datagram: EncodedDatagram<B> = data.into();
+        let datagram = datagram.copy_to_bytes(datagram.remaining());
+        self.send(datagram)
+    }
+}
+
+/// Receives QUIC datagrams with the default datagram provider
+pub struct RecvDatagramHandler {
+    conn: s2n_quic::connection::Handle,
+}
+
+impl RecvDatagram for RecvDatagramHandler {
+    type Buffer = Bytes;
+
+    fn poll_incoming_datagram(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Self::Buffer, ConnectionErrorIncoming>> {
+        match self
+            .conn
+            .datagram_mut(|receiver: &mut default::Receiver| receiver.poll_recv_datagram(cx))
+        {
+            Ok(Poll::Ready(Ok(payload))) => Poll::Ready(Ok(payload)),
+            Ok(Poll::Ready(Err(err))) => Poll::Ready(Err(convert_recv_error(err))),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(ConnectionErrorIncoming::Undefined(Arc::new(err)))),
+        }
+    }
+}
+
+fn convert_send_error(err: default::DatagramError) -> SendDatagramErrorIncoming {
+    match err {
+        default::DatagramError::ExceedsPeerTransportLimits { .. } => {
+            SendDatagramErrorIncoming::TooLarge
+        }
+        default::DatagramError::ConnectionError { error, .. } => {
+            SendDatagramErrorIncoming::ConnectionError(convert_connection_error(error))
+        }
+        // the oldest datagram is dropped instead so the queue is never at capacity
+        _ => SendDatagramErrorIncoming::NotAvailable,
+    }
+}
+
+fn convert_recv_error(err: default::DatagramError) -> ConnectionErrorIncoming {
+    match err {
+        default::DatagramError::ConnectionError { error, .. } => convert_connection_error(error),
+        err => ConnectionErrorIncoming::InternalError(err.to_string()),
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod datagram;
 mod s2n_quic;
+pub mod webtransport;
 
 pub use self::s2n_quic::*;
 pub use h3;
@@ -34,6 +34,11 @@ impl Connection {
             recv_acceptor: recv,
         }
     }
+
+    /// Returns the handle to the underlying QUIC connection
+    pub fn handle(&self) -> &s2n_quic::connection::Handle {
+        &self.conn
+    }
 }
 
 impl<B> quic::Connection<B> for Connection
@@ -95,7 +100,9 @@ where
     }
 }
 
-fn convert_connection_error(e: s2n_quic::connection::Error) -> h3::quic::ConnectionErrorIncoming {
+pub(crate) fn convert_connection_error(
+    e: s2n_quic::connection::Error,
+) -> h3::quic::ConnectionErrorIncoming {
     match e {
         s2n_quic::connection::Error::Application { error, .. } => {
             ConnectionErrorIncoming::ApplicationClose {
@@ -278,6 +285,19 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for BidiStream<B>
+where
+    B: Buf,
+{
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, StreamErrorIncoming>> {
+        self.send.poll_send(cx, buf)
+    }
+}
+
 impl<B> From<BidirectionalStream> for BidiStream<B>
 where
     B: Buf,
@@ -466,6 +486,36 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for SendStream<B>
+where
+    B: Buf,
+{
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, StreamErrorIncoming>> {
+        // flush any previously buffered frames before writing unframed data
+        ready!(quic::SendStream::poll_ready(self, cx))?;
+
+        let available = ready!(self.stream.poll_send_ready(cx))
+            .map_err(convert_stream_error_to_h3_stream_error_incoming)?;
+
+        let len = buf.chunk().len().min(available);
+        if len == 0 {
+            return Poll::Ready(Ok(0));
+        }
+
+        let chunk = buf.copy_to_bytes(len);
+        self.stream
+            .send_data(chunk)
+            .map_err(convert_stream_error_to_h3_stream_error_incoming)?;
+
+        Poll::Ready(Ok(len))
+    }
+}
+
 impl<B> From<s2n_quic::stream::SendStream> for SendStream<B>
 where
     B: Buf,
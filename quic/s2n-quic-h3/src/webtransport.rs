@@ -0,0 +1,1214 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [WebTransport over HTTP/3](https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/)
+//! server sessions
+//!
+//! A session is established with an extended CONNECT request using the `webtransport`
+//! protocol. The h3 server must be built with `enable_webtransport(true)`,
+//! `enable_extended_connect(true)`, `enable_datagram(true)` and a non-zero
+//! `max_webtransport_sessions`, and the QUIC server must use the
+//! [default datagram endpoint](s2n_quic::provider::datagram::default::Endpoint) for session
+//! datagrams to be delivered.
+//!
+//! ```rust,ignore
+//! use s2n_quic_h3::webtransport::Server;
+//!
+//! let conn = h3::server::builder()
+//!     .enable_webtransport(true)
+//!     .enable_extended_connect(true)
+//!     .enable_datagram(true)
+//!     .max_webtransport_sessions(8)
+//!     .build(s2n_quic_h3::Connection::new(conn))
+//!     .await?;
+//!
+//! let server = Server::new(conn)?;
+//!
+//! while let Some((request, stream)) = server.accept().await? {
+//!     let session = server.accept_session(request, stream).await?;
+//!
+//!     tokio::spawn(async move {
+//!         while let Some(stream) = session.accept_bi().await? {
+//!             // ...
+//!         }
+//!     });
+//! }
+//! ```
+//!
+//! The [`Server`] takes ownership of the h3 connection, as every stream the peer opens needs to
+//! be inspected to find the session it belongs to. Streams and datagrams are routed to their
+//! session by session ID, while the headers of new streams are read concurrently so a stalled
+//! stream doesn't hold up the others.
+
+use crate::{
+    datagram::{RecvDatagramHandler, SendDatagramHandler},
+    Connection,
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::{
+    fmt,
+    future::poll_fn,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{self, ready, Poll, Waker},
+};
+use futures::{
+    future::{BoxFuture, FutureExt as _},
+    stream::{FuturesUnordered, StreamExt as _},
+};
+use h3::{
+    error::{ConnectionError, StreamError},
+    ext::Protocol,
+    frame::{FrameStream, FrameStreamError},
+    proto::{
+        coding::Encode as _,
+        frame::{Frame, PayloadLen},
+        varint::VarInt,
+    },
+    quic::{RecvStream as _, SendStream as _, StreamId},
+    server,
+    stream::{BidiStreamHeader, BufRecvStream, UniStreamHeader},
+    webtransport::SessionId,
+};
+use h3_datagram::{
+    datagram::Datagram,
+    datagram_handler::{DatagramReader, HandleDatagramsExt as _},
+    quic_traits::{DatagramConnectionExt, SendDatagramErrorIncoming},
+};
+use http::{Method, Request, Response, StatusCode};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    task::Wake,
+};
+
+/// A unidirectional stream opened by the peer
+pub type RecvStream = BufRecvStream<crate::RecvStream, Bytes>;
+
+/// A unidirectional stream opened by the application
+pub type SendStream = BufRecvStream<crate::SendStream<Bytes>, Bytes>;
+
+/// A bidirectional stream opened by either endpoint
+pub type BidiStream = BufRecvStream<crate::BidiStream<Bytes>, Bytes>;
+
+type RequestStream<S> = server::RequestStream<S, Bytes>;
+
+/// An HTTP/3 request that isn't associated with a session
+pub type AcceptedRequest = (Request<()>, RequestStream<crate::BidiStream<Bytes>>);
+
+/// The maximum number of streams buffered for sessions that haven't been accepted yet
+const MAX_BUFFERED_STREAMS: usize = 16;
+
+/// The maximum number of datagrams buffered for each session
+///
+/// The oldest datagram is dropped once a session falls behind.
+const MAX_BUFFERED_DATAGRAMS: usize = 64;
+
+/// The maximum number of closed sessions that are remembered to reject their late streams
+const MAX_CLOSED_SESSIONS: usize = 16;
+
+//= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-9.5
+//# The WEBTRANSPORT_BUFFERED_STREAM_REJECTED error code (0x3994bd84)
+const BUFFERED_STREAM_REJECTED: u64 = 0x3994bd84;
+
+//= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-5
+//# CLOSE_WEBTRANSPORT_SESSION Capsule {
+//#   Type (i) = CLOSE_WEBTRANSPORT_SESSION,
+//#   Length (i),
+//#   Application Error Code (32),
+//#   Application Error Message (..8192),
+//# }
+const CLOSE_WEBTRANSPORT_SESSION: u64 = 0x2843;
+const MAX_REASON_LEN: usize = 1024;
+
+//= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-4.6
+//# DRAIN_WEBTRANSPORT_SESSION Capsule {
+//#   Type (i) = DRAIN_WEBTRANSPORT_SESSION,
+//#   Length (i) = 0
+//# }
+const DRAIN_WEBTRANSPORT_SESSION: u64 = 0x78ae;
+
+/// Accepts WebTransport sessions and HTTP/3 requests on an h3 connection
+pub struct Server {
+    shared: Arc<Shared>,
+}
+
+impl Server {
+    /// Takes ownership of the h3 connection to route its streams to sessions
+    pub fn new(conn: server::Connection<Connection, Bytes>) -> Result<Self, Error> {
+        if !conn.inner.config.settings.enable_webtransport() {
+            return Err(Error::NotEnabled);
+        }
+
+        let wakers = Arc::new(Wakers::default());
+        let waker = Waker::from(wakers.clone());
+        let datagrams = DatagramStream::new(conn.get_datagram_reader());
+
+        let inner = Inner {
+            conn,
+            datagrams,
+            pending: FuturesUnordered::new(),
+            router: Router::default(),
+        };
+
+        Ok(Self {
+            shared: Arc::new(Shared {
+                inner: Mutex::new(inner),
+                wakers,
+                waker,
+            }),
+        })
+    }
+
+    /// Accepts the next HTTP/3 request opened by the peer
+    ///
+    /// WebTransport sessions are established by passing the extended CONNECT requests to
+    /// [`Self::accept_session`]. Returns `None` once the connection is shutting down.
+    pub async fn accept(&self) -> Result<Option<AcceptedRequest>, Error> {
+        poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Polls for the next HTTP/3 request opened by the peer
+    pub fn poll_accept(
+        &self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<AcceptedRequest>, Error>> {
+        self.shared
+            .poll(cx, |router| router.requests.pop_front().map(Ok))
+    }
+
+    /// Accepts a WebTransport session from an extended CONNECT request
+    ///
+    /// A `200` response is sent to the peer once the request is validated. Requests that are
+    /// not WebTransport CONNECT requests are rejected with a `400` response.
+    pub async fn accept_session(
+        &self,
+        request: Request<()>,
+        mut stream: RequestStream<crate::BidiStream<Bytes>>,
+    ) -> Result<WebTransportSession, Error> {
+        let stream_id = stream.id();
+        let session_id = session_id(stream_id);
+
+        if !is_webtransport(&request) {
+            // streams that were buffered for the request's ID will never be accepted
+            self.shared.close_session(session_id);
+
+            let response = Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(())
+                .expect("response is valid");
+            stream.send_response(response).await?;
+            stream.finish().await?;
+            return Err(Error::NotWebTransport);
+        }
+
+        // register the session before responding so the streams and datagrams the peer sends
+        // right after the response are delivered to it
+        let (handle, datagrams) = {
+            let mut inner = self.shared.inner.lock().unwrap();
+            inner.router.accept_session(session_id);
+            (
+                inner.conn.inner.conn.handle().clone(),
+                DatagramConnectionExt::<Bytes>::send_datagram_handler(&inner.conn.inner.conn),
+            )
+        };
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header("sec-webtransport-http3-draft", "draft02")
+            .body(())
+            .expect("response is valid");
+        if let Err(err) = stream.send_response(response).await {
+            self.shared.close_session(session_id);
+            return Err(err.into());
+        }
+
+        let (send, recv) = stream.split();
+
+        Ok(WebTransportSession {
+            session_id,
+            handle,
+            shared: self.shared.clone(),
+            connect_send: futures::lock::Mutex::new(send),
+            connect_recv: futures::lock::Mutex::new(CapsuleReader {
+                stream: recv,
+                decoder: CapsuleDecoder::default(),
+            }),
+            stream_id,
+            datagrams: Mutex::new(datagrams),
+            draining: AtomicBool::new(false),
+        })
+    }
+}
+
+/// A WebTransport session accepted by the server
+pub struct WebTransportSession {
+    session_id: SessionId,
+    handle: s2n_quic::connection::Handle,
+    shared: Arc<Shared>,
+    connect_send: futures::lock::Mutex<RequestStream<crate::SendStream<Bytes>>>,
+    connect_recv: futures::lock::Mutex<CapsuleReader>,
+    /// The ID of the CONNECT stream, which session datagrams are associated with
+    stream_id: StreamId,
+    datagrams: Mutex<SendDatagramHandler>,
+    draining: AtomicBool,
+}
+
+impl WebTransportSession {
+    /// Returns the ID of the session
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// Accepts the next unidirectional stream opened by the peer for this session
+    ///
+    /// Returns `None` once the connection has been closed.
+    pub async fn accept_uni(&self) -> Result<Option<RecvStream>, Error> {
+        poll_fn(|cx| self.poll_accept_uni(cx)).await
+    }
+
+    /// Polls for the next unidirectional stream opened by the peer for this session
+    pub fn poll_accept_uni(
+        &self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<RecvStream>, Error>> {
+        self.shared.poll(cx, |router| {
+            router.session(self.session_id)?.uni.pop_front().map(Ok)
+        })
+    }
+
+    /// Accepts the next bidirectional stream opened by the peer for this session
+    ///
+    /// Returns `None` once the connection has been closed.
+    pub async fn accept_bi(&self) -> Result<Option<BidiStream>, Error> {
+        poll_fn(|cx| self.poll_accept_bi(cx)).await
+    }
+
+    /// Polls for the next bidirectional stream opened by the peer for this session
+    pub fn poll_accept_bi(
+        &self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<BidiStream>, Error>> {
+        self.shared.poll(cx, |router| {
+            router.session(self.session_id)?.bidi.pop_front().map(Ok)
+        })
+    }
+
+    /// Opens a unidirectional stream to the peer for this session
+    pub async fn open_uni(&self) -> Result<SendStream, Error> {
+        let mut stream = self.handle.clone().open_send_stream().await?;
+
+        let mut header = BytesMut::new();
+        UniStreamHeader::WebTransportUni(self.session_id).encode(&mut header);
+        stream.send(header.freeze()).await?;
+
+        Ok(BufRecvStream::new(stream.into()))
+    }
+
+    /// Opens a bidirectional stream to the peer for this session
+    pub async fn open_bi(&self) -> Result<BidiStream, Error> {
+        let mut stream = self.handle.clone().open_bidirectional_stream().await?;
+
+        let mut header = BytesMut::new();
+        BidiStreamHeader::WebTransportBidi(self.session_id).encode(&mut header);
+        stream.send(header.freeze()).await?;
+
+        Ok(BufRecvStream::new(stream.into()))
+    }
+
+    /// Sends a datagram to the peer for this session
+    pub fn send_datagram(&self, payload: Bytes) -> Result<(), Error> {
+        self.datagrams
+            .lock()
+            .unwrap()
+            .send_datagram_for(self.stream_id, payload)?;
+        Ok(())
+    }
+
+    /// Receives the next datagram sent by the peer for this session
+    ///
+    /// Returns `None` once the connection has been closed.
+    pub async fn read_datagram(&self) -> Result<Option<Bytes>, Error> {
+        poll_fn(|cx| self.poll_read_datagram(cx)).await
+    }
+
+    /// Polls for the next datagram sent by the peer for this session
+    pub fn poll_read_datagram(
+        &self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Bytes>, Error>> {
+        self.shared.poll(cx, |router| {
+            if let Some(datagram) = router
+                .session(self.session_id)
+                .and_then(|session| session.datagrams.pop_front())
+            {
+                return Some(Ok(datagram));
+            }
+
+            router.datagram_error.take().map(Err)
+        })
+    }
+
+    /// Asks the peer to gracefully finish using the session
+    ///
+    //= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-4.6
+    //# An endpoint MAY send a DRAIN_WEBTRANSPORT_SESSION capsule to let its peer know
+    //# that it intends to gracefully close the session in the near future.
+    pub async fn drain(&self) -> Result<(), Error> {
+        let capsule = Capsule::Drain.encode()?;
+        self.connect_send.lock().await.send_data(capsule).await?;
+        Ok(())
+    }
+
+    /// Returns `true` if the peer asked to gracefully finish using the session
+    ///
+    /// The flag is updated while [`Self::closed`] is being polled.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// Closes the session with an application error code and reason
+    pub async fn close(self, code: u32, reason: &str) -> Result<(), Error> {
+        let capsule = Capsule::Close {
+            code,
+            reason: reason.to_string(),
+        }
+        .encode()?;
+
+        let mut stream = self.connect_send.lock().await;
+
+        //= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-5
+        //# After sending or receiving either a CLOSE_WEBTRANSPORT_SESSION capsule or a
+        //# FIN on the CONNECT stream, the endpoint MUST close the send side of the
+        //# CONNECT stream.
+        stream.send_data(capsule).await?;
+        stream.finish().await?;
+
+        Ok(())
+    }
+
+    /// Waits for the peer to close the session
+    ///
+    /// A FIN on the CONNECT stream without a `CLOSE_WEBTRANSPORT_SESSION` capsule is reported
+    /// with an error code of `0` and an empty reason.
+    pub async fn closed(&self) -> Result<SessionClosed, Error> {
+        let mut reader = self.connect_recv.lock().await;
+
+        loop {
+            match reader.next().await? {
+                Some(Capsule::Drain) => self.draining.store(true, Ordering::Relaxed),
+                Some(Capsule::Close { code, reason }) => return Ok(SessionClosed { code, reason }),
+                Some(Capsule::Unknown { .. }) => continue,
+                //= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-5
+                //# Cleanly terminating a CONNECT stream without a CLOSE_WEBTRANSPORT_SESSION
+                //# capsule SHALL be semantically equivalent to terminating it with a
+                //# CLOSE_WEBTRANSPORT_SESSION capsule that has an error code of 0 and an empty
+                //# error string.
+                None => {
+                    return Ok(SessionClosed {
+                        code: 0,
+                        reason: String::new(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Drop for WebTransportSession {
+    fn drop(&mut self) {
+        // reject the streams that weren't accepted and any that arrive later
+        self.shared.close_session(self.session_id);
+    }
+}
+
+/// State shared between the [`Server`] and its sessions
+struct Shared {
+    inner: Mutex<Inner>,
+    wakers: Arc<Wakers>,
+    /// Wakes all of the tasks in `wakers`
+    waker: Waker,
+}
+
+impl Shared {
+    /// Routes everything the peer has sent and takes the next value from the router
+    ///
+    /// Any task can end up driving the connection, so it's polled with a waker that wakes every
+    /// task that is waiting on it.
+    fn poll<T>(
+        &self,
+        cx: &mut task::Context<'_>,
+        take: impl FnOnce(&mut Router) -> Option<Result<T, Error>>,
+    ) -> Poll<Result<Option<T>, Error>> {
+        self.wakers.register(cx.waker());
+
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.poll_route(&mut task::Context::from_waker(&self.waker)) {
+            // other tasks may be waiting on the values that were routed
+            self.waker.wake_by_ref();
+        }
+
+        if let Some(value) = take(&mut inner.router) {
+            return Poll::Ready(value.map(Some));
+        }
+
+        if inner.router.is_closed {
+            return Poll::Ready(inner.router.error.take().map_or(Ok(None), Err));
+        }
+
+        Poll::Pending
+    }
+
+    fn close_session(&self, session_id: SessionId) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.router.close_session(session_id);
+        }
+    }
+}
+
+struct Inner {
+    conn: server::Connection<Connection, Bytes>,
+    datagrams: DatagramStream,
+    /// Bidirectional streams whose first frame is being read, and requests being resolved
+    pending: FuturesUnordered<BoxFuture<'static, Incoming>>,
+    router: Router,
+}
+
+impl Inner {
+    /// Accepts all of the streams and datagrams the peer has sent and routes them
+    ///
+    /// Returns `true` if anything was routed.
+    fn poll_route(&mut self, cx: &mut task::Context<'_>) -> bool {
+        let mut is_routed = false;
+
+        while !self.router.is_closed {
+            match self.conn.poll_accept_request_stream(cx) {
+                Poll::Ready(Ok(Some(stream))) => {
+                    let mut stream = FrameStream::new(BufRecvStream::new(stream));
+                    self.pending.push(
+                        async move {
+                            let frame = poll_fn(|cx| stream.poll_next(cx)).await;
+                            Incoming::Frame(stream, frame)
+                        }
+                        .boxed(),
+                    );
+                }
+                Poll::Ready(Ok(None)) => self.router.is_closed = true,
+                Poll::Ready(Err(err)) => self.router.on_connection_error(err),
+                Poll::Pending => break,
+            }
+        }
+
+        while let Poll::Ready(Some(incoming)) = self.pending.poll_next_unpin(cx) {
+            match incoming {
+                //= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-4.2
+                //# The signal value, 0x41, is used by clients and servers to open a
+                //# bidirectional WebTransport stream.
+                Incoming::Frame(stream, Ok(Some(Frame::WebTransportStream(session_id)))) => {
+                    self.router.route_bidi(session_id, stream.into_inner());
+                    is_routed = true;
+                }
+                Incoming::Frame(stream, frame) => {
+                    match self.conn.create_resolver(stream).accept_with_frame(frame) {
+                        Ok(resolved) => self.pending.push(
+                            async move {
+                                Incoming::Request(resolved.resolve().await.map_err(Error::from))
+                            }
+                            .boxed(),
+                        ),
+                        Err(err) => self.router.on_connection_error(err),
+                    }
+                }
+                Incoming::Request(Ok(request)) => {
+                    // streams that were buffered for the request's ID will never be accepted
+                    if !is_webtransport(&request.0) {
+                        self.router.close_session(session_id(request.1.id()));
+                    }
+                    self.router.requests.push_back(request);
+                    is_routed = true;
+                }
+                // h3 already rejected the malformed request so there's nothing to respond to
+                Incoming::Request(Err(_)) => {}
+            }
+        }
+
+        if !self.router.is_closed {
+            if let Err(err) = self.conn.inner.poll_accept_recv(cx) {
+                self.router.on_connection_error(err);
+            }
+
+            let streams = &mut self.conn.inner.accepted_streams_mut().wt_uni_streams;
+            // route streams in the order the peer opened them
+            for (session_id, stream) in streams.drain(..) {
+                self.router.route_uni(session_id, stream);
+                is_routed = true;
+            }
+        }
+
+        while self.router.datagram_error.is_none() {
+            match self.datagrams.poll_next(cx) {
+                Poll::Ready(Ok(datagram)) => {
+                    self.router.route_datagram(datagram);
+                    is_routed = true;
+                }
+                Poll::Ready(Err(err)) => {
+                    self.router.datagram_error = Some(err.into());
+                    is_routed = true;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        is_routed
+    }
+}
+
+/// Reads datagrams from a [`DatagramReader`], which only provides an async API
+struct DatagramStream(BoxFuture<'static, DatagramRead>);
+
+type DatagramRead = (
+    DatagramReader<RecvDatagramHandler>,
+    Result<Datagram<Bytes>, StreamError>,
+);
+
+impl DatagramStream {
+    fn new(reader: DatagramReader<RecvDatagramHandler>) -> Self {
+        Self(Self::read(reader))
+    }
+
+    fn read(mut reader: DatagramReader<RecvDatagramHandler>) -> BoxFuture<'static, DatagramRead> {
+        async move {
+            let result = reader.read_datagram().await;
+            (reader, result)
+        }
+        .boxed()
+    }
+
+    fn poll_next(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Datagram<Bytes>, StreamError>> {
+        let (reader, result) = ready!(self.0.poll_unpin(cx));
+        self.0 = Self::read(reader);
+        Poll::Ready(result)
+    }
+}
+
+/// A bidirectional stream opened by the peer
+enum Incoming {
+    /// The first frame on the stream was read
+    Frame(
+        FrameStream<crate::BidiStream<Bytes>, Bytes>,
+        Result<Option<Frame<PayloadLen>>, FrameStreamError>,
+    ),
+    /// The stream was resolved as an HTTP/3 request
+    Request(Result<AcceptedRequest, Error>),
+}
+
+/// Holds the streams and datagrams for each session until they're accepted
+#[derive(Default)]
+struct Router {
+    requests: VecDeque<AcceptedRequest>,
+    sessions: Vec<SessionQueue>,
+    /// The most recently closed sessions, whose late streams are rejected
+    closed: VecDeque<SessionId>,
+    /// Set once the connection stops accepting streams
+    is_closed: bool,
+    /// The error that closed the connection, which is returned to the first caller
+    error: Option<Error>,
+    /// The error that stopped datagrams from being received
+    datagram_error: Option<Error>,
+}
+
+impl Router {
+    fn on_connection_error(&mut self, err: impl Into<Error>) {
+        let err = err.into();
+        self.is_closed = true;
+
+        if !matches!(&err, Error::Connection(err) if err.is_h3_no_error()) {
+            self.error.get_or_insert(err);
+        }
+    }
+
+    /// Returns the queue for an accepted session
+    fn session(&mut self, session_id: SessionId) -> Option<&mut SessionQueue> {
+        self.sessions
+            .iter_mut()
+            .find(|session| session.session_id == session_id)
+            .filter(|session| session.state == SessionState::Accepted)
+    }
+
+    fn accept_session(&mut self, session_id: SessionId) {
+        if let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|session| session.session_id == session_id)
+        {
+            session.state = SessionState::Accepted;
+        } else {
+            self.sessions
+                .push(SessionQueue::new(session_id, SessionState::Accepted));
+        }
+    }
+
+    fn close_session(&mut self, session_id: SessionId) {
+        if let Some(idx) = self
+            .sessions
+            .iter()
+            .position(|session| session.session_id == session_id)
+        {
+            self.sessions.swap_remove(idx).reject_streams();
+        }
+
+        if !self.closed.contains(&session_id) {
+            if self.closed.len() == MAX_CLOSED_SESSIONS {
+                self.closed.pop_front();
+            }
+            self.closed.push_back(session_id);
+        }
+    }
+
+    /// Returns the queue that a stream for the session should be added to
+    ///
+    /// Streams can arrive before their session is accepted so a limited number of them are
+    /// buffered in the meantime.
+    fn stream_queue(&mut self, session_id: SessionId) -> Option<&mut SessionQueue> {
+        if let Some(idx) = self
+            .sessions
+            .iter()
+            .position(|session| session.session_id == session_id)
+        {
+            return Some(&mut self.sessions[idx]);
+        }
+
+        if self.closed.contains(&session_id) {
+            return None;
+        }
+
+        let buffered: usize = self
+            .sessions
+            .iter()
+            .filter(|session| session.state == SessionState::Buffered)
+            .map(|session| session.uni.len() + session.bidi.len())
+            .sum();
+
+        if buffered >= MAX_BUFFERED_STREAMS {
+            return None;
+        }
+
+        self.sessions
+            .push(SessionQueue::new(session_id, SessionState::Buffered));
+        self.sessions.last_mut()
+    }
+
+    fn route_uni(&mut self, session_id: SessionId, mut stream: RecvStream) {
+        if let Some(session) = self.stream_queue(session_id) {
+            session.uni.push_back(stream);
+        } else {
+            stream.stop_sending(BUFFERED_STREAM_REJECTED);
+        }
+    }
+
+    fn route_bidi(&mut self, session_id: SessionId, mut stream: BidiStream) {
+        if let Some(session) = self.stream_queue(session_id) {
+            session.bidi.push_back(stream);
+        } else {
+            stream.stop_sending(BUFFERED_STREAM_REJECTED);
+            stream.reset(BUFFERED_STREAM_REJECTED);
+        }
+    }
+
+    fn route_datagram(&mut self, datagram: Datagram<Bytes>) {
+        let Ok(session_id) = SessionId::try_from(datagram.stream_id().into_inner()) else {
+            return;
+        };
+
+        // datagrams are unreliable so the ones for sessions that haven't been accepted are
+        // dropped instead of buffered
+        if let Some(session) = self.session(session_id) {
+            if session.datagrams.len() == MAX_BUFFERED_DATAGRAMS {
+                session.datagrams.pop_front();
+            }
+            session.datagrams.push_back(datagram.into_payload());
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SessionState {
+    /// Streams were received for the session before it was accepted
+    Buffered,
+    Accepted,
+}
+
+struct SessionQueue {
+    session_id: SessionId,
+    state: SessionState,
+    uni: VecDeque<RecvStream>,
+    bidi: VecDeque<BidiStream>,
+    datagrams: VecDeque<Bytes>,
+}
+
+impl SessionQueue {
+    fn new(session_id: SessionId, state: SessionState) -> Self {
+        Self {
+            session_id,
+            state,
+            uni: VecDeque::new(),
+            bidi: VecDeque::new(),
+            datagrams: VecDeque::new(),
+        }
+    }
+
+    fn reject_streams(&mut self) {
+        for mut stream in self.uni.drain(..) {
+            stream.stop_sending(BUFFERED_STREAM_REJECTED);
+        }
+        for mut stream in self.bidi.drain(..) {
+            stream.stop_sending(BUFFERED_STREAM_REJECTED);
+            stream.reset(BUFFERED_STREAM_REJECTED);
+        }
+        self.datagrams.clear();
+    }
+}
+
+fn is_webtransport(request: &Request<()>) -> bool {
+    request.method() == Method::CONNECT
+        && request.extensions().get::<Protocol>() == Some(&Protocol::WEB_TRANSPORT)
+}
+
+//= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-2
+//# The session ID is the stream ID of the CONNECT stream
+fn session_id(stream_id: StreamId) -> SessionId {
+    SessionId::try_from(stream_id.into_inner()).expect("stream IDs are valid session IDs")
+}
+
+/// Wakes every task that is waiting on the connection
+///
+/// The connection only stores the last waker it was polled with, which might belong to a task
+/// that has since stopped waiting.
+#[derive(Default)]
+struct Wakers(Mutex<Vec<Waker>>);
+
+impl Wakers {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = core::mem::take(&mut *self.0.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// The reason the peer gave for closing a session
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionClosed {
+    /// The application error code
+    pub code: u32,
+    /// The application error message
+    pub reason: String,
+}
+
+/// Reads capsules from the CONNECT stream
+struct CapsuleReader {
+    stream: RequestStream<crate::RecvStream>,
+    decoder: CapsuleDecoder,
+}
+
+impl CapsuleReader {
+    async fn next(&mut self) -> Result<Option<Capsule>, Error> {
+        loop {
+            if let Some(capsule) = self.decoder.next()? {
+                return Ok(Some(capsule));
+            }
+
+            match self.stream.recv_data().await? {
+                Some(data) => self.decoder.push(data),
+                None if self.decoder.is_empty() => return Ok(None),
+                None => return Err(Error::MalformedCapsule),
+            }
+        }
+    }
+}
+
+/// Decodes the capsules received on the CONNECT stream
+///
+/// Only the capsules that are understood are buffered, and their lengths are bounded, so the
+/// values of unknown capsules are discarded as they're received.
+#[derive(Default)]
+struct CapsuleDecoder {
+    buffer: BytesMut,
+    /// The number of bytes of an unknown capsule that still need to be discarded
+    skip: u64,
+}
+
+impl CapsuleDecoder {
+    fn push(&mut self, mut data: impl Buf) {
+        while data.has_remaining() {
+            let chunk = data.chunk();
+            let skip = self.skip.min(chunk.len() as u64) as usize;
+            self.skip -= skip as u64;
+            self.buffer.put_slice(&chunk[skip..]);
+            let len = chunk.len();
+            data.advance(len);
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Capsule>, Error> {
+        loop {
+            let skip = self.skip.min(self.buffer.len() as u64) as usize;
+            self.buffer.advance(skip);
+            self.skip -= skip as u64;
+
+            if self.skip > 0 {
+                return Ok(None);
+            }
+
+            match Capsule::decode(&mut self.buffer)? {
+                //= https://www.rfc-editor.org/rfc/rfc9297#section-3.2
+                //# Endpoints that receive a capsule with an unknown Capsule Type MUST
+                //# silently drop that capsule
+                Some(Capsule::Unknown { len }) => self.skip = len,
+                capsule => return Ok(capsule),
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buffer.is_empty() && self.skip == 0
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9297#section-3.2
+//# Capsule {
+//#   Capsule Type (i),
+//#   Capsule Length (i),
+//#   Capsule Value (..),
+//# }
+#[derive(Debug, PartialEq, Eq)]
+enum Capsule {
+    Drain,
+    Close {
+        code: u32,
+        reason: String,
+    },
+    /// The header of a capsule with an unknown type, which is followed by `len` bytes
+    Unknown {
+        len: u64,
+    },
+}
+
+impl Capsule {
+    fn encode(&self) -> Result<Bytes, Error> {
+        let (ty, value) = match self {
+            Self::Drain => (DRAIN_WEBTRANSPORT_SESSION, Bytes::new()),
+            Self::Close { code, reason } => {
+                if reason.len() > MAX_REASON_LEN {
+                    return Err(Error::ReasonTooLong);
+                }
+                let mut value = BytesMut::with_capacity(4 + reason.len());
+                value.put_u32(*code);
+                value.put_slice(reason.as_bytes());
+                (CLOSE_WEBTRANSPORT_SESSION, value.freeze())
+            }
+            Self::Unknown { .. } => unreachable!("unknown capsules are never sent"),
+        };
+
+        let mut buffer = BytesMut::new();
+        VarInt::from_u64(ty)
+            .expect("capsule types are valid varints")
+            .encode(&mut buffer);
+        VarInt::try_from(value.len())
+            .expect("capsule lengths are valid varints")
+            .encode(&mut buffer);
+        buffer.put_slice(&value);
+        Ok(buffer.freeze())
+    }
+
+    /// Decodes the next capsule in the buffer, or returns `None` if it is incomplete
+    ///
+    /// Only the header of an unknown capsule is consumed, as its value is never buffered.
+    fn decode(buffer: &mut BytesMut) -> Result<Option<Self>, Error> {
+        let mut cursor = &buffer[..];
+
+        let (ty, len) = match (VarInt::decode(&mut cursor), VarInt::decode(&mut cursor)) {
+            (Ok(ty), Ok(len)) => (u64::from(ty), u64::from(len)),
+            _ => return Ok(None),
+        };
+
+        // the lengths of known capsules are checked before their values are buffered
+        match ty {
+            //= https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3/#section-4.6
+            //# If an endpoint receives a DRAIN_WEBTRANSPORT_SESSION capsule with a non-zero
+            //# length, it MUST close the session with a H3_MESSAGE_ERROR error.
+            DRAIN_WEBTRANSPORT_SESSION if len != 0 => return Err(Error::MalformedCapsule),
+            CLOSE_WEBTRANSPORT_SESSION if !(4..=4 + MAX_REASON_LEN as u64).contains(&len) => {
+                return Err(Error::MalformedCapsule)
+            }
+            DRAIN_WEBTRANSPORT_SESSION | CLOSE_WEBTRANSPORT_SESSION => {}
+            _ => {
+                let header_len = buffer.len() - cursor.len();
+                buffer.advance(header_len);
+                return Ok(Some(Self::Unknown { len }));
+            }
+        }
+
+        // the length was bounded above so it fits in a usize
+        let len = len as usize;
+        if cursor.len() < len {
+            return Ok(None);
+        }
+
+        let header_len = buffer.len() - cursor.len();
+        buffer.advance(header_len);
+        let mut value = buffer.split_to(len);
+
+        let capsule = if ty == DRAIN_WEBTRANSPORT_SESSION {
+            Self::Drain
+        } else {
+            let code = value.get_u32();
+            let reason = String::from_utf8(value.to_vec()).map_err(|_| Error::MalformedCapsule)?;
+            Self::Close { code, reason }
+        };
+
+        Ok(Some(capsule))
+    }
+}
+
+/// An error that occurred on a WebTransport session
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The h3 connection was not built with `enable_webtransport(true)`
+    NotEnabled,
+    /// The request was not a WebTransport extended CONNECT request
+    NotWebTransport,
+    /// The close reason exceeds the maximum length of 1024 bytes
+    ReasonTooLong,
+    /// The peer sent a malformed capsule on the CONNECT stream
+    MalformedCapsule,
+    /// The h3 connection failed
+    Connection(ConnectionError),
+    /// The CONNECT stream failed
+    Stream(StreamError),
+    /// The QUIC connection failed
+    Quic(s2n_quic::stream::Error),
+    /// A session datagram could not be sent
+    Datagram(SendDatagramErrorIncoming),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnabled => write!(f, "WebTransport is not enabled on the connection"),
+            Self::NotWebTransport => write!(f, "the request is not a WebTransport CONNECT request"),
+            Self::ReasonTooLong => write!(f, "the close reason exceeds {MAX_REASON_LEN} bytes"),
+            Self::MalformedCapsule => write!(f, "the peer sent a malformed capsule"),
+            Self::Connection(err) => write!(f, "{err}"),
+            Self::Stream(err) => write!(f, "{err}"),
+            Self::Quic(err) => write!(f, "{err}"),
+            Self::Datagram(SendDatagramErrorIncoming::NotAvailable) => {
+                write!(f, "datagrams are not available on the connection")
+            }
+            Self::Datagram(SendDatagramErrorIncoming::TooLarge) => {
+                write!(f, "the datagram is too large to be sent")
+            }
+            Self::Datagram(SendDatagramErrorIncoming::ConnectionError(err)) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ConnectionError> for Error {
+    fn from(err: ConnectionError) -> Self {
+        Self::Connection(err)
+    }
+}
+
+impl From<StreamError> for Error {
+    fn from(err: StreamError) -> Self {
+        Self::Stream(err)
+    }
+}
+
+impl From<s2n_quic::stream::Error> for Error {
+    fn from(err: s2n_quic::stream::Error) -> Self {
+        Self::Quic(err)
+    }
+}
+
+impl From<s2n_quic::connection::Error> for Error {
+    fn from(err: s2n_quic::connection::Error) -> Self {
+        Self::Quic(err.into())
+    }
+}
+
+impl From<SendDatagramErrorIncoming> for Error {
+    fn from(err: SendDatagramErrorIncoming) -> Self {
+        Self::Datagram(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn router_close_session_test() {
+        let mut router = Router::default();
+        let session_ids: Vec<_> = (0..=MAX_CLOSED_SESSIONS as u64)
+            .map(|id| SessionId::try_from(id * 4).unwrap())
+            .collect();
+
+        for session_id in &session_ids {
+            router.accept_session(*session_id);
+            router.close_session(*session_id);
+        }
+
+        // closed sessions are removed and only the most recent ones are remembered
+        assert!(router.sessions.is_empty());
+        assert_eq!(router.closed.len(), MAX_CLOSED_SESSIONS);
+        assert!(router.stream_queue(session_ids[1]).is_none());
+        assert!(router.stream_queue(session_ids[0]).is_some());
+    }
+
+    #[test]
+    fn capsule_round_trip_test() {
+        let capsules = [
+            Capsule::Drain,
+            Capsule::Close {
+                code: 0,
+                reason: String::new(),
+            },
+            Capsule::Close {
+                code: u32::MAX,
+                reason: "x".repeat(MAX_REASON_LEN),
+            },
+        ];
+
+        let mut buffer = BytesMut::new();
+        for capsule in &capsules {
+            buffer.put_slice(&capsule.encode().unwrap());
+        }
+
+        for capsule in capsules {
+            assert_eq!(Capsule::decode(&mut buffer).unwrap(), Some(capsule));
+        }
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn capsule_partial_test() {
+        let encoded = Capsule::Close {
+            code: 42,
+            reason: "bye".to_string(),
+        }
+        .encode()
+        .unwrap();
+
+        for len in 0..encoded.len() {
+            let mut buffer = BytesMut::from(&encoded[..len]);
+            assert_eq!(Capsule::decode(&mut buffer).unwrap(), None);
+            assert_eq!(
+                buffer.len(),
+                len,
+                "incomplete capsules should not be consumed"
+            );
+        }
+    }
+
+    #[test]
+    fn capsule_unknown_test() {
+        let mut buffer = BytesMut::new();
+        VarInt::from_u64(0x1234).unwrap().encode(&mut buffer);
+        VarInt::from_u64(3).unwrap().encode(&mut buffer);
+        buffer.put_slice(b"abc");
+        buffer.put_slice(&Capsule::Drain.encode().unwrap());
+
+        let mut decoder = CapsuleDecoder::default();
+        for byte in buffer {
+            assert_eq!(decoder.next().unwrap(), None);
+            decoder.push(&[byte][..]);
+        }
+        assert_eq!(decoder.next().unwrap(), Some(Capsule::Drain));
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn capsule_unknown_large_test() {
+        let mut buffer = BytesMut::new();
+        VarInt::from_u64(0x1234).unwrap().encode(&mut buffer);
+        VarInt::from_u64(VarInt::MAX.into_inner())
+            .unwrap()
+            .encode(&mut buffer);
+
+        let mut decoder = CapsuleDecoder::default();
+        decoder.push(buffer);
+        decoder.push(&[0; 1024][..]);
+
+        // the value of the unknown capsule is discarded instead of buffered
+        assert_eq!(decoder.next().unwrap(), None);
+        assert!(decoder.buffer.is_empty());
+        assert_eq!(decoder.skip, VarInt::MAX.into_inner() - 1024);
+    }
+
+    #[test]
+    fn capsule_malformed_test() {
+        assert!(Capsule::Close {
+            code: 0,
+            reason: "x".repeat(MAX_REASON_LEN + 1),
+        }
+        .encode()
+        .is_err());
+
+        // DRAIN_WEBTRANSPORT_SESSION must be empty
+        let mut buffer = BytesMut::new();
+        VarInt::from_u64(DRAIN_WEBTRANSPORT_SESSION)
+            .unwrap()
+            .encode(&mut buffer);
+        VarInt::from_u64(1).unwrap().encode(&mut buffer);
+        buffer.put_u8(0);
+        assert!(matches!(
+            Capsule::decode(&mut buffer),
+            Err(Error::MalformedCapsule)
+        ));
+
+        // CLOSE_WEBTRANSPORT_SESSION requires an error code
+        let mut buffer = BytesMut::new();
+        VarInt::from_u64(CLOSE_WEBTRANSPORT_SESSION)
+            .unwrap()
+            .encode(&mut buffer);
+        VarInt::from_u64(2).unwrap().encode(&mut buffer);
+        buffer.put_u16(0);
+        assert!(matches!(
+            Capsule::decode(&mut buffer),
+            Err(Error::MalformedCapsule)
+        ));
+
+        // lengths are rejected before the value is received
+        for ty in [DRAIN_WEBTRANSPORT_SESSION, CLOSE_WEBTRANSPORT_SESSION] {
+            let mut buffer = BytesMut::new();
+            VarInt::from_u64(ty).unwrap().encode(&mut buffer);
+            VarInt::from_u64(VarInt::MAX.into_inner())
+                .unwrap()
+                .encode(&mut buffer);
+            assert!(matches!(
+                Capsule::decode(&mut buffer),
+                Err(Error::MalformedCapsule)
+            ));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::c_int;
+use s2n_quic_core::inet::icmp;
+
+#[cfg(s2n_quic_platform_recverr)]
+mod recverr_enabled {
+    use super::*;
+    use libc::{
+        sock_extended_err, IPPROTO_IP, IPPROTO_IPV6, IPV6_RECVERR, IP_RECVERR, MSG_ERRQUEUE,
+        SO_EE_ORIGIN_ICMP, SO_EE_ORIGIN_ICMP6,
+    };
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = Some((IPPROTO_IP as _, IP_RECVERR as _));
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = Some((IPPROTO_IPV6 as _, IPV6_RECVERR as _));
+    /// The extended error is followed by the address of the node that generated the error
+    pub const CMSG_SPACE: usize = crate::message::cmsg::size_of_cmsg::<sock_extended_err>()
+        + core::mem::size_of::<libc::sockaddr_in6>();
+    /// The flags passed to `recvmsg` to read from the socket error queue
+    pub const RECV_FLAGS: c_int = MSG_ERRQUEUE as _;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        (level == IPPROTO_IP as c_int && ty == IP_RECVERR as c_int)
+            || (level == IPPROTO_IPV6 as c_int && ty == IPV6_RECVERR as c_int)
+    }
+
+    /// Returns `true` if the `msg_flags` indicate the message was read from the error queue
+    #[inline]
+    pub const fn is_error_queue(flags: c_int) -> bool {
+        flags & MSG_ERRQUEUE as c_int != 0
+    }
+
+    #[inline]
+    pub fn decode(value: &[u8]) -> Option<icmp::Error> {
+        s2n_quic_core::ensure!(
+            value.len() >= core::mem::size_of::<sock_extended_err>(),
+            None
+        );
+
+        // SAFETY: the length was checked above and the read doesn't require alignment
+        let err = unsafe { (value.as_ptr() as *const sock_extended_err).read_unaligned() };
+
+        // Dual-stack sockets report ICMPv4 errors with the IPv6 cmsg level so the
+        // origin is used to determine how to interpret the type and code
+        match (err.ee_origin, err.ee_type, err.ee_code) {
+            // Destination Unreachable - Fragmentation Needed
+            (SO_EE_ORIGIN_ICMP, 3, 4) => Some(icmp::Error::PacketTooBig {
+                mtu: err.ee_info.try_into().ok()?,
+            }),
+            // Destination Unreachable - Port Unreachable
+            (SO_EE_ORIGIN_ICMP, 3, 3) => Some(icmp::Error::PortUnreachable),
+            // Packet Too Big
+            (SO_EE_ORIGIN_ICMP6, 2, 0) => Some(icmp::Error::PacketTooBig {
+                mtu: err.ee_info.try_into().ok()?,
+            }),
+            // Destination Unreachable - Port Unreachable
+            (SO_EE_ORIGIN_ICMP6, 1, 4) => Some(icmp::Error::PortUnreachable),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(not(s2n_quic_platform_recverr), test))]
+mod recverr_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = None;
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = None;
+    pub const CMSG_SPACE: usize = 0;
+    pub const RECV_FLAGS: c_int = 0;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        let _ = level;
+        let _ = ty;
+        false
+    }
+
+    #[inline]
+    pub const fn is_error_queue(flags: c_int) -> bool {
+        let _ = flags;
+        false
+    }
+
+    #[inline]
+    pub fn decode(value: &[u8]) -> Option<icmp::Error> {
+        let _ = value;
+        None
+    }
+}
+
+mod recverr_impl {
+    #[cfg(not(s2n_quic_platform_recverr))]
+    pub use super::recverr_disabled::*;
+    #[cfg(s2n_quic_platform_recverr)]
+    pub use super::recverr_enabled::*;
+}
+
+pub use recverr_impl::*;
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_recverr);
@@ -0,0 +1,149 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::c_int;
+use core::time::Duration;
+use s2n_quic_core::time::Timestamp;
+
+#[cfg(s2n_quic_platform_txtime)]
+mod txtime_enabled {
+    use super::*;
+    use libc::{SCM_TXTIME, SOL_SOCKET, SO_TXTIME};
+
+    pub const LEVEL: Option<c_int> = Some(SOL_SOCKET as _);
+    pub const TYPE: Option<c_int> = Some(SCM_TXTIME as _);
+    pub const SOCKOPT: Option<(c_int, c_int)> = Some((SOL_SOCKET as _, SO_TXTIME as _));
+    pub const CMSG_SPACE: usize = crate::message::cmsg::size_of_cmsg::<super::Cmsg>();
+
+    /// The `fq` qdisc requires departure times from `CLOCK_MONOTONIC`
+    pub const CONFIG: Option<super::Config> = Some(super::Config {
+        clock_id: libc::CLOCK_MONOTONIC as _,
+        flags: 0,
+    });
+
+    /// Returns the `CLOCK_MONOTONIC` time at which the endpoint clock read zero, given its
+    /// current time
+    #[inline]
+    pub fn clock_epoch(now: Timestamp) -> Duration {
+        let mut monotonic = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+
+        // Safety: `monotonic` is a valid pointer and CLOCK_MONOTONIC is always supported on Linux
+        unsafe {
+            libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut monotonic);
+        }
+
+        let monotonic = Duration::new(monotonic.tv_sec as _, monotonic.tv_nsec as _);
+
+        // Safety: the duration is only compared to the clock that produced the timestamp
+        let now = unsafe { now.as_duration() };
+
+        monotonic.saturating_sub(now)
+    }
+}
+
+#[cfg(any(not(s2n_quic_platform_txtime), test))]
+mod txtime_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const LEVEL: Option<c_int> = None;
+    pub const TYPE: Option<c_int> = None;
+    pub const SOCKOPT: Option<(c_int, c_int)> = None;
+    pub const CMSG_SPACE: usize = 0;
+    pub const CONFIG: Option<super::Config> = None;
+
+    #[inline]
+    pub fn clock_epoch(now: Timestamp) -> Duration {
+        let _ = now;
+        panic!("platform does not support SO_TXTIME");
+    }
+}
+
+mod txtime_impl {
+    #[cfg(not(s2n_quic_platform_txtime))]
+    pub use super::txtime_disabled::*;
+    #[cfg(s2n_quic_platform_txtime)]
+    pub use super::txtime_enabled::*;
+}
+
+pub use txtime_impl::*;
+
+/// The departure time of a message, in nanoseconds
+///
+/// This is encoded as bytes, since the alignment of a `u64` can exceed that of `cmsghdr` on
+/// 32-bit platforms.
+pub type Cmsg = [u8; 8];
+
+/// The value passed to the `SO_TXTIME` socket option
+///
+/// This mirrors `struct sock_txtime` in `linux/net_tstamp.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub clock_id: c_int,
+    pub flags: u32,
+}
+
+/// How far ahead of its departure time a message is queued when pacing is offloaded
+///
+/// This needs to be long enough to cover the granularity of the endpoint timers, so the socket
+/// always has the next burst of packets ready to go.
+pub const HORIZON: Duration = Duration::from_millis(2);
+
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_txtime);
+
+/// Converts an endpoint timestamp into a `CLOCK_MONOTONIC` departure time, in nanoseconds
+///
+/// `epoch` is the value returned by [`clock_epoch`] for the same clock, so every message is
+/// converted with the same offset instead of reading the time again.
+#[inline]
+pub fn departure_time(epoch: Duration, timestamp: Timestamp) -> u64 {
+    // Safety: the duration is only compared to the clock that produced the timestamp
+    let timestamp = unsafe { timestamp.as_duration() };
+    (epoch + timestamp).as_nanos() as _
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{clock::testing::Clock, Clock as _};
+
+    #[test]
+    fn departure_time_test() {
+        let mut clock = Clock::default();
+        let epoch = Duration::from_secs(100);
+
+        let now = clock.get_time();
+        let departure_time = super::departure_time(epoch, now);
+
+        // messages for the same timestamp always depart at the same time
+        assert_eq!(departure_time, super::departure_time(epoch, now));
+
+        // the departure time follows the endpoint clock instead of the time of the conversion
+        clock.inc_by(Duration::from_millis(1));
+        let later = super::departure_time(epoch, clock.get_time());
+        assert_eq!(later - departure_time, 1_000_000);
+        assert_eq!(departure_time, super::departure_time(epoch, now));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // miri doesn't support clock_gettime
+    fn clock_epoch_test() {
+        if !IS_SUPPORTED {
+            return;
+        }
+
+        let clock = Clock::default();
+        let now = clock.get_time();
+
+        let epoch = clock_epoch(now);
+
+        // converting the current time gives a time that has just passed
+        let departure_time = super::departure_time(epoch, now);
+        let current_time = super::departure_time(clock_epoch(now), now);
+        assert!(departure_time <= current_time);
+    }
+}
@@ -4,6 +4,12 @@
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub mod uring;
+
+#[cfg(feature = "pcap")]
+pub mod pcap;
+
 #[cfg(any(test, feature = "io-testing"))]
 pub mod testing;
 
@@ -0,0 +1,739 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records the datagrams sent and received by an endpoint into a
+//! [pcapng](https://datatracker.ietf.org/doc/draft-ietf-opsawg-pcapng/) capture file
+//!
+//! The capture wraps the [`Endpoint`](s2n_quic_core::endpoint::Endpoint) that is passed to an IO
+//! provider, which means it works with any provider. Each datagram is written as an Ethernet frame
+//! with synthesized IP and UDP headers, which carry the addresses and ECN markings of the datagram.
+//!
+//! Any TLS secrets written to the capture's [`KeyLog`] are stored in Decryption Secrets Blocks.
+//! This allows tools like Wireshark to decrypt the capture without a separate key log file.
+
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
+use s2n_codec::{Encoder as _, EncoderBuffer};
+use s2n_quic_core::{
+    endpoint::{self, CloseError},
+    inet::{
+        datagram, ethernet, ExplicitCongestionNotification, IpAddress, IpV4Address,
+        Unspecified as _,
+    },
+    io::{rx, tx},
+    path::{self, mtu},
+    time::{Clock, Timestamp},
+    xdp::{encoder, path as xdp_path},
+};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+mod pcapng;
+
+pub use pcapng::Direction;
+
+/// How often the recorded blocks are flushed to the writer
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum number of writes that can be queued for the writer thread
+///
+/// Blocks are dropped once the queue is full rather than holding up the endpoint.
+const MAX_PENDING_WRITES: usize = 256;
+
+/// A handle to a pcapng capture file
+///
+/// The handle can be cloned to record multiple endpoints into the same capture.
+#[derive(Clone)]
+pub struct Capture {
+    shared: Arc<Shared>,
+}
+
+impl core::fmt::Debug for Capture {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Capture").finish_non_exhaustive()
+    }
+}
+
+impl Capture {
+    /// Creates a capture file at the given path
+    ///
+    /// Writes to the file are buffered and flushed periodically, as well as when the last handle
+    /// to the capture is dropped.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file))
+    }
+
+    /// Creates a capture which is written to `writer`
+    ///
+    /// `writer` is written to from a separate thread so the endpoints aren't blocked on IO. It
+    /// is flushed periodically, as well as when the last handle to the capture is dropped.
+    pub fn new<W: 'static + Write + Send>(mut writer: W) -> io::Result<Self> {
+        let mut header = vec![];
+        pcapng::section_header(&mut header);
+        pcapng::interface_description(&mut header, pcapng::LINKTYPE_ETHERNET);
+        writer.write_all(&header)?;
+        writer.flush()?;
+
+        let (sender, receiver) = mpsc::sync_channel(MAX_PENDING_WRITES);
+        let is_stopped = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let is_stopped = is_stopped.clone();
+            thread::Builder::new()
+                .name("s2n-quic-pcap".to_string())
+                .spawn(move || write_blocks(writer, receiver, &is_stopped))?
+        };
+
+        let shared = Shared {
+            sender: Some(sender),
+            thread: Some(thread),
+            secrets: Mutex::new(vec![]),
+            is_stopped,
+        };
+
+        Ok(Self {
+            shared: Arc::new(shared),
+        })
+    }
+
+    /// Returns a writer for the TLS key log of the endpoint
+    ///
+    /// The writer accepts lines in the
+    /// [NSS Key Log Format](https://datatracker.ietf.org/doc/draft-ietf-tls-keylogfile/), which
+    /// is produced by `with_key_log` on the `s2n-quic-tls` builders.
+    pub fn key_log(&self) -> KeyLog {
+        KeyLog {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Flushes the recorded blocks to the writer
+    ///
+    /// Waits for the writer thread to write all of the blocks that were recorded before the call.
+    pub fn flush(&self) -> io::Result<()> {
+        let Some(sender) = self.shared.sender.as_ref() else {
+            return Ok(());
+        };
+
+        let (result_sender, result) = mpsc::sync_channel(1);
+
+        // the thread only exits after the writer failed, in which case there's nothing to flush
+        if sender.send(Command::Flush(result_sender)).is_err() {
+            return Ok(());
+        }
+
+        result.recv().unwrap_or(Ok(()))
+    }
+
+    /// Wraps `endpoint` so all of its datagrams are recorded in the capture
+    pub fn endpoint<E: endpoint::Endpoint>(&self, endpoint: E) -> Endpoint<E> {
+        Endpoint {
+            endpoint,
+            capture: self.clone(),
+            recorder: Recorder::default(),
+        }
+    }
+
+    /// Queues the recorded blocks to be written, preceded by any pending secrets
+    #[inline]
+    fn write(&self, blocks: &mut Vec<u8>) {
+        if blocks.is_empty() {
+            return;
+        }
+
+        if !self.shared.is_stopped.load(Ordering::Relaxed) {
+            self.shared.write(blocks);
+        }
+
+        blocks.clear();
+    }
+}
+
+struct Shared {
+    /// Queues writes for the writer thread, which is dropped to stop the thread
+    sender: Option<mpsc::SyncSender<Command>>,
+    thread: Option<thread::JoinHandle<()>>,
+    /// Key log lines which haven't been written to the capture yet
+    secrets: Mutex<Vec<u8>>,
+    /// Set once the writer fails
+    is_stopped: Arc<AtomicBool>,
+}
+
+impl Shared {
+    #[inline]
+    fn write(&self, blocks: &mut Vec<u8>) {
+        let Some(sender) = self.sender.as_ref() else {
+            return;
+        };
+
+        // secrets are written before the packets so they are available to readers that process
+        // the capture in a single pass
+        if let Ok(mut secrets) = self.secrets.lock() {
+            if !secrets.is_empty() {
+                let mut block = vec![];
+                pcapng::decryption_secrets(&mut block, pcapng::SECRETS_TYPE_TLS, &secrets);
+
+                // the packets can't be decrypted without the secrets so they're both dropped and
+                // the secrets are retried with the next blocks
+                if sender.try_send(Command::Write(block)).is_err() {
+                    return;
+                }
+
+                secrets.clear();
+            }
+        }
+
+        // the blocks are dropped if the writer thread has fallen behind
+        let capacity = blocks.capacity();
+        let blocks = core::mem::replace(blocks, Vec::with_capacity(capacity));
+        let _ = sender.try_send(Command::Write(blocks));
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        // closing the channel stops the thread once it has flushed the queued blocks
+        self.sender = None;
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+enum Command {
+    Write(Vec<u8>),
+    Flush(mpsc::SyncSender<io::Result<()>>),
+}
+
+/// Writes the queued blocks until every handle to the capture is dropped
+///
+/// The writer is flushed periodically, and the capture stops at the first error rather than
+/// producing a corrupted file.
+fn write_blocks<W: Write>(
+    mut writer: W,
+    receiver: mpsc::Receiver<Command>,
+    is_stopped: &AtomicBool,
+) {
+    let mut last_flush = Instant::now();
+
+    loop {
+        let timeout = FLUSH_INTERVAL.saturating_sub(last_flush.elapsed());
+
+        let result = match receiver.recv_timeout(timeout) {
+            Ok(Command::Write(blocks)) => writer.write_all(&blocks),
+            Ok(Command::Flush(result)) => {
+                last_flush = Instant::now();
+                let flush = writer.flush();
+                let is_ok = flush.is_ok();
+                let _ = result.send(flush);
+                if is_ok {
+                    continue;
+                }
+                is_stopped.store(true, Ordering::Relaxed);
+                return;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                let _ = writer.flush();
+                return;
+            }
+        };
+
+        let result = result.and_then(|_| {
+            if last_flush.elapsed() < FLUSH_INTERVAL {
+                return Ok(());
+            }
+            last_flush = Instant::now();
+            writer.flush()
+        });
+
+        if result.is_err() {
+            is_stopped.store(true, Ordering::Relaxed);
+            return;
+        }
+    }
+}
+
+/// Writes TLS key log lines into the capture
+///
+/// The lines are buffered and written in a Decryption Secrets Block before the next recorded
+/// datagrams.
+#[derive(Clone)]
+pub struct KeyLog {
+    shared: Arc<Shared>,
+}
+
+impl core::fmt::Debug for KeyLog {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyLog").finish_non_exhaustive()
+    }
+}
+
+impl Write for KeyLog {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // don't accumulate secrets if the capture has been stopped
+        if self.shared.is_stopped.load(Ordering::Relaxed) {
+            return Ok(buf.len());
+        }
+
+        self.shared
+            .secrets
+            .lock()
+            .map_err(|_| io::Error::other("capture lock poisoned"))?
+            .extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An endpoint wrapper which records all of the datagrams that pass through it
+pub struct Endpoint<E> {
+    endpoint: E,
+    capture: Capture,
+    recorder: Recorder,
+}
+
+impl<E: endpoint::Endpoint> endpoint::Endpoint for Endpoint<E> {
+    type PathHandle = E::PathHandle;
+    type Subscriber = E::Subscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = E::ENDPOINT_TYPE;
+
+    #[inline]
+    fn receive<Rx, C>(&mut self, rx: &mut Rx, clock: &C)
+    where
+        Rx: rx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        let mut queue = RxQueue {
+            queue: rx,
+            recorder: &mut self.recorder,
+            now: clock.get_time(),
+        };
+
+        self.endpoint.receive(&mut queue, clock);
+
+        // the packets are written after the endpoint processed them so any secrets that were
+        // derived from the packets are written first
+        self.capture.write(&mut self.recorder.blocks);
+    }
+
+    #[inline]
+    fn transmit<Tx, C>(&mut self, tx: &mut Tx, clock: &C)
+    where
+        Tx: tx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        let mut queue = TxQueue {
+            queue: tx,
+            recorder: &mut self.recorder,
+            now: clock.get_time(),
+        };
+
+        self.endpoint.transmit(&mut queue, clock);
+
+        self.capture.write(&mut self.recorder.blocks);
+    }
+
+    #[inline]
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        cx: &mut Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        self.endpoint.poll_wakeups(cx, clock)
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        self.endpoint.timeout()
+    }
+
+    #[inline]
+    fn set_mtu_config(&mut self, mtu_config: mtu::Config) {
+        self.endpoint.set_mtu_config(mtu_config)
+    }
+
+    #[inline]
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        self.endpoint.subscriber()
+    }
+}
+
+/// The largest frame that can be encoded, including the Ethernet header
+const MAX_FRAME_LEN: usize = core::mem::size_of::<ethernet::Header>() + u16::MAX as usize;
+
+struct Recorder {
+    /// Encoded Enhanced Packet Blocks which haven't been written to the capture yet
+    blocks: Vec<u8>,
+    /// Scratch space for encoding a frame
+    frame: Vec<u8>,
+    /// Scratch space for the payload of a transmitted datagram
+    payload: Vec<u8>,
+    encoder: encoder::State,
+    /// Maps the endpoint clock to the system clock
+    epoch: Option<(Timestamp, Duration)>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self {
+            blocks: vec![],
+            frame: vec![0; MAX_FRAME_LEN],
+            payload: vec![],
+            encoder: Default::default(),
+            epoch: None,
+        }
+    }
+}
+
+impl Recorder {
+    #[inline]
+    fn record<H: path::Handle>(
+        &mut self,
+        timestamp: Timestamp,
+        direction: Direction,
+        handle: &H,
+        ecn: ExplicitCongestionNotification,
+        ipv6_flow_label: u32,
+        payload: &[u8],
+    ) {
+        let mut message = Frame {
+            path: tuple(handle, direction),
+            ecn,
+            ipv6_flow_label,
+            payload,
+        };
+
+        let mut buffer = EncoderBuffer::new(&mut self.frame);
+        if encoder::encode_packet(&mut buffer, &mut message, &mut self.encoder).is_err() {
+            return;
+        }
+        let len = buffer.len();
+
+        let timestamp = self.unix_time(timestamp);
+        pcapng::enhanced_packet(
+            &mut self.blocks,
+            0,
+            timestamp,
+            direction,
+            &self.frame[..len],
+        );
+    }
+
+    /// Converts an endpoint timestamp into the time since the UNIX epoch
+    ///
+    /// The endpoint clock is used instead of reading the system clock for each datagram so the
+    /// capture is consistent with the endpoint's view of time.
+    #[inline]
+    fn unix_time(&mut self, timestamp: Timestamp) -> Duration {
+        let (start, unix_start) = *self.epoch.get_or_insert_with(|| {
+            let unix_start = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            (timestamp, unix_start)
+        });
+
+        // `saturating_duration_since` rounds a zero duration up to 1us so the timestamps are
+        // subtracted directly
+        unix_start + (timestamp.max(start) - start)
+    }
+}
+
+/// Converts a path handle into the addresses used for the synthesized headers
+///
+/// The encoder uses the local address as the source of the frame so inbound datagrams swap the
+/// addresses.
+#[inline]
+fn tuple<H: path::Handle>(handle: &H, direction: Direction) -> xdp_path::Tuple {
+    let remote_address = xdp_path::RemoteAddress::from(handle.remote_address()).unmap();
+    let mut local_address = xdp_path::LocalAddress::from(handle.local_address()).unmap();
+
+    // dual-stack sockets that don't know the local address of an IPv4 datagram report the
+    // unspecified IPv6 address
+    if matches!(
+        (remote_address.ip, local_address.ip),
+        (IpAddress::Ipv4(_), IpAddress::Ipv6(local_ip)) if local_ip.is_unspecified()
+    ) {
+        local_address.ip = IpV4Address::UNSPECIFIED.into();
+    }
+
+    let mut tuple = xdp_path::Tuple {
+        remote_address,
+        local_address,
+    };
+
+    if direction == Direction::Inbound {
+        tuple.swap();
+    }
+
+    tuple
+}
+
+/// A recorded datagram which is passed to the encoder
+struct Frame<'a> {
+    path: xdp_path::Tuple,
+    ecn: ExplicitCongestionNotification,
+    ipv6_flow_label: u32,
+    payload: &'a [u8],
+}
+
+impl tx::Message for Frame<'_> {
+    type Handle = xdp_path::Tuple;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        &self.path
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        self.ecn
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        Duration::ZERO
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.ipv6_flow_label
+    }
+
+    #[inline]
+    fn can_gso(&self, _segment_len: usize, _segment_count: usize) -> bool {
+        false
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.payload)
+    }
+}
+
+struct RxQueue<'a, Q> {
+    queue: &'a mut Q,
+    recorder: &'a mut Recorder,
+    now: Timestamp,
+}
+
+impl<Q: rx::Queue> rx::Queue for RxQueue<'_, Q> {
+    type Handle = Q::Handle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        let recorder = &mut *self.recorder;
+        let now = self.now;
+
+        // the packet is recorded before the endpoint decrypts it in place
+        self.queue.for_each(|header, payload| {
+            recorder.record(
+                now,
+                Direction::Inbound,
+                &header.path,
+                header.ecn,
+                0,
+                payload,
+            );
+            on_packet(header, payload)
+        })
+    }
+
+    #[inline]
+    fn for_each_entry<F: FnMut(rx::Entry<Self::Handle>)>(&mut self, mut on_entry: F) {
+        let recorder = &mut *self.recorder;
+        let now = self.now;
+
+        self.queue.for_each_entry(|entry| {
+            // ICMP errors only quote a previously transmitted datagram so they aren't recorded
+            if let rx::Entry::Packet { header, payload } = &entry {
+                recorder.record(
+                    now,
+                    Direction::Inbound,
+                    &header.path,
+                    header.ecn,
+                    0,
+                    payload,
+                );
+            }
+            on_entry(entry)
+        })
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+struct TxQueue<'a, Q> {
+    queue: &'a mut Q,
+    recorder: &'a mut Recorder,
+    now: Timestamp,
+}
+
+impl<Q: tx::Queue> tx::Queue for TxQueue<'_, Q> {
+    type Handle = Q::Handle;
+
+    const SUPPORTS_ECN: bool = Q::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Q::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Q::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let handle = *message.path_handle();
+        let mut payload = core::mem::take(&mut self.recorder.payload);
+        payload.clear();
+
+        let mut message = TxMessage {
+            message,
+            payload,
+            ecn: Default::default(),
+            delay: Duration::ZERO,
+            ipv6_flow_label: 0,
+        };
+
+        let result = self.queue.push(&mut message);
+
+        let TxMessage {
+            payload,
+            ecn,
+            delay,
+            ipv6_flow_label,
+            ..
+        } = message;
+
+        if result.is_ok() {
+            // only the values that were read by the queue are reflected in the transmitted
+            // datagram, e.g. queues that don't support ECN never send ECN markings
+            self.recorder.record(
+                self.now + delay,
+                Direction::Outbound,
+                &handle,
+                ecn,
+                ipv6_flow_label,
+                &payload,
+            );
+        }
+
+        self.recorder.payload = payload;
+
+        result
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.queue.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    #[inline]
+    fn has_capacity(&self) -> bool {
+        self.queue.has_capacity()
+    }
+
+    #[inline]
+    fn pacing_horizon(&self) -> Duration {
+        self.queue.pacing_horizon()
+    }
+}
+
+/// Wraps a transmitted message to record the values that the queue reads from it
+struct TxMessage<M> {
+    message: M,
+    payload: Vec<u8>,
+    ecn: ExplicitCongestionNotification,
+    delay: Duration,
+    ipv6_flow_label: u32,
+}
+
+impl<M: tx::Message> tx::Message for &mut TxMessage<M> {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        self.message.path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        self.ecn = self.message.ecn();
+        self.ecn
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        self.delay = self.message.delay();
+        self.delay
+    }
+
+    #[inline]
+    fn departure_time(&mut self) -> Option<Timestamp> {
+        self.message.departure_time()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.ipv6_flow_label = self.message.ipv6_flow_label();
+        self.ipv6_flow_label
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        self.message.can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        buffer: tx::PayloadBuffer,
+        gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        // Safety: the buffer is passed directly to the wrapped message, which performs the
+        //         checks for writing
+        let buffer = unsafe { buffer.into_mut_slice() };
+        let len = self
+            .message
+            .write_payload(tx::PayloadBuffer::new(&mut buffer[..]), gso_offset)?;
+
+        if let Some(payload) = buffer.get(..len) {
+            self.payload.extend_from_slice(payload);
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests;
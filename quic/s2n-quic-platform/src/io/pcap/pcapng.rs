@@ -0,0 +1,249 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Block encoders for the [pcapng](https://datatracker.ietf.org/doc/draft-ietf-opsawg-pcapng/)
+//! capture file format
+//!
+//! All of the blocks are written in little-endian byte order, which is indicated to readers by the
+//! byte-order magic in the section header.
+
+use core::time::Duration;
+
+/// Link type for frames which start with an Ethernet header
+pub const LINKTYPE_ETHERNET: u16 = 1;
+
+/// Secrets type for the [NSS Key Log Format](https://datatracker.ietf.org/doc/draft-ietf-tls-keylogfile/)
+pub const SECRETS_TYPE_TLS: u32 = 0x544c_534b;
+
+const SECTION_HEADER_BLOCK: u32 = 0x0a0d_0d0a;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const DECRYPTION_SECRETS_BLOCK: u32 = 0x0000_000a;
+
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+const OPT_END_OF_OPT: u16 = 0;
+const OPT_SHB_USERAPPL: u16 = 4;
+const OPT_IF_TSRESOL: u16 = 9;
+const OPT_EPB_FLAGS: u16 = 2;
+
+/// Timestamps are written with nanosecond resolution
+const TSRESOL_NANOS: u8 = 9;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Direction {
+    /// Returns the value of the direction bits in the `epb_flags` option
+    #[inline]
+    fn flags(self) -> u32 {
+        match self {
+            Self::Inbound => 0b01,
+            Self::Outbound => 0b10,
+        }
+    }
+}
+
+/// Writes a Section Header Block, which starts a new capture section
+pub fn section_header(out: &mut Vec<u8>) {
+    block(out, SECTION_HEADER_BLOCK, |out| {
+        out.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        // major version
+        out.extend_from_slice(&1u16.to_le_bytes());
+        // minor version
+        out.extend_from_slice(&0u16.to_le_bytes());
+        // the section length isn't known ahead of time
+        out.extend_from_slice(&(-1i64).to_le_bytes());
+
+        option(out, OPT_SHB_USERAPPL, b"s2n-quic");
+        option(out, OPT_END_OF_OPT, &[]);
+    });
+}
+
+/// Writes an Interface Description Block
+///
+/// Interfaces are numbered by the order in which they appear in the section, starting at 0.
+pub fn interface_description(out: &mut Vec<u8>, link_type: u16) {
+    block(out, INTERFACE_DESCRIPTION_BLOCK, |out| {
+        out.extend_from_slice(&link_type.to_le_bytes());
+        // reserved
+        out.extend_from_slice(&0u16.to_le_bytes());
+        // a snap length of 0 means packets are never truncated
+        out.extend_from_slice(&0u32.to_le_bytes());
+
+        option(out, OPT_IF_TSRESOL, &[TSRESOL_NANOS]);
+        option(out, OPT_END_OF_OPT, &[]);
+    });
+}
+
+/// Writes an Enhanced Packet Block containing `packet`
+///
+/// The `timestamp` is the time elapsed since the UNIX epoch.
+pub fn enhanced_packet(
+    out: &mut Vec<u8>,
+    interface_id: u32,
+    timestamp: Duration,
+    direction: Direction,
+    packet: &[u8],
+) {
+    block(out, ENHANCED_PACKET_BLOCK, |out| {
+        let timestamp = timestamp.as_nanos() as u64;
+        let len = packet.len() as u32;
+
+        out.extend_from_slice(&interface_id.to_le_bytes());
+        out.extend_from_slice(&((timestamp >> 32) as u32).to_le_bytes());
+        out.extend_from_slice(&(timestamp as u32).to_le_bytes());
+        // captured length
+        out.extend_from_slice(&len.to_le_bytes());
+        // original length
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(packet);
+        pad(out, packet.len());
+
+        option(out, OPT_EPB_FLAGS, &direction.flags().to_le_bytes());
+        option(out, OPT_END_OF_OPT, &[]);
+    });
+}
+
+/// Writes a Decryption Secrets Block
+///
+/// The secrets apply to all of the packets that follow the block in the section.
+pub fn decryption_secrets(out: &mut Vec<u8>, secrets_type: u32, secrets: &[u8]) {
+    block(out, DECRYPTION_SECRETS_BLOCK, |out| {
+        out.extend_from_slice(&secrets_type.to_le_bytes());
+        out.extend_from_slice(&(secrets.len() as u32).to_le_bytes());
+        out.extend_from_slice(secrets);
+        pad(out, secrets.len());
+    });
+}
+
+/// Writes a block with the given type
+///
+/// The block body is surrounded by the block type and the total block length, which is repeated
+/// at the end of the block to allow for backwards navigation.
+#[inline]
+fn block<F: FnOnce(&mut Vec<u8>)>(out: &mut Vec<u8>, block_type: u32, body: F) {
+    let start = out.len();
+    out.extend_from_slice(&block_type.to_le_bytes());
+    // write a placeholder for the length until the body has been written
+    out.extend_from_slice(&0u32.to_le_bytes());
+
+    body(out);
+
+    let len = (out.len() - start + 4) as u32;
+    debug_assert_eq!(len % 4, 0, "blocks should be 32-bit aligned");
+    out[start + 4..start + 8].copy_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(&len.to_le_bytes());
+}
+
+#[inline]
+fn option(out: &mut Vec<u8>, code: u16, value: &[u8]) {
+    out.extend_from_slice(&code.to_le_bytes());
+    out.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    out.extend_from_slice(value);
+    pad(out, value.len());
+}
+
+/// Pads a field of length `len` to a 32-bit boundary
+#[inline]
+fn pad(out: &mut Vec<u8>, len: usize) {
+    let padding = (4 - len % 4) % 4;
+    out.resize(out.len() + padding, 0);
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Splits the blocks in `bytes` into their types and bodies
+    pub(crate) fn blocks(mut bytes: &[u8]) -> Vec<(u32, &[u8])> {
+        let mut blocks = vec![];
+
+        while !bytes.is_empty() {
+            let block_type = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+            let len = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+            assert_eq!(len % 4, 0);
+
+            let (block, remaining) = bytes.split_at(len);
+            let trailer = u32::from_le_bytes(block[len - 4..].try_into().unwrap()) as usize;
+            assert_eq!(len, trailer);
+
+            blocks.push((block_type, &block[8..len - 4]));
+            bytes = remaining;
+        }
+
+        blocks
+    }
+
+    #[test]
+    fn section_test() {
+        let mut out = vec![];
+        section_header(&mut out);
+        interface_description(&mut out, LINKTYPE_ETHERNET);
+
+        let blocks = blocks(&out);
+        assert_eq!(blocks.len(), 2);
+
+        let (block_type, body) = blocks[0];
+        assert_eq!(block_type, SECTION_HEADER_BLOCK);
+        assert_eq!(&body[..4], &BYTE_ORDER_MAGIC.to_le_bytes());
+
+        let (block_type, body) = blocks[1];
+        assert_eq!(block_type, INTERFACE_DESCRIPTION_BLOCK);
+        assert_eq!(&body[..2], &LINKTYPE_ETHERNET.to_le_bytes());
+    }
+
+    #[test]
+    fn enhanced_packet_test() {
+        for len in 0..8 {
+            let packet: Vec<u8> = (0..len).collect();
+            let timestamp = Duration::from_secs(u32::MAX as u64 + 1) + Duration::from_nanos(3);
+
+            let mut out = vec![];
+            enhanced_packet(&mut out, 0, timestamp, Direction::Outbound, &packet);
+
+            let blocks = blocks(&out);
+            assert_eq!(blocks.len(), 1);
+
+            let (block_type, body) = blocks[0];
+            assert_eq!(block_type, ENHANCED_PACKET_BLOCK);
+
+            let field = |index: usize| {
+                let offset = index * 4;
+                u32::from_le_bytes(body[offset..offset + 4].try_into().unwrap())
+            };
+
+            let nanos = ((field(1) as u64) << 32) | field(2) as u64;
+            assert_eq!(nanos, timestamp.as_nanos() as u64);
+            assert_eq!(field(3), len as u32);
+            assert_eq!(field(4), len as u32);
+            assert_eq!(&body[20..20 + packet.len()], &packet[..]);
+
+            // the flags option follows the padded packet
+            let options = &body[20 + packet.len().next_multiple_of(4)..];
+            assert_eq!(&options[..4], &[2, 0, 4, 0]);
+            assert_eq!(&options[4..8], &Direction::Outbound.flags().to_le_bytes());
+            assert_eq!(&options[8..], &[0; 4]);
+        }
+    }
+
+    #[test]
+    fn decryption_secrets_test() {
+        let secrets = b"CLIENT_HANDSHAKE_TRAFFIC_SECRET 00 11\n";
+
+        let mut out = vec![];
+        decryption_secrets(&mut out, SECRETS_TYPE_TLS, secrets);
+
+        let blocks = blocks(&out);
+        assert_eq!(blocks.len(), 1);
+
+        let (block_type, body) = blocks[0];
+        assert_eq!(block_type, DECRYPTION_SECRETS_BLOCK);
+        assert_eq!(&body[..4], &SECRETS_TYPE_TLS.to_le_bytes());
+        assert_eq!(&body[4..8], &(secrets.len() as u32).to_le_bytes());
+        assert_eq!(&body[8..8 + secrets.len()], secrets);
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::io::tokio::tests::NoopSubscriber;
+use s2n_quic_core::{
+    endpoint::Endpoint as _,
+    inet::{SocketAddressV4, SocketAddressV6},
+    path::Tuple,
+    time::clock::testing as time,
+};
+
+const SECRETS: &[u8] = b"CLIENT_HANDSHAKE_TRAFFIC_SECRET 0011 2233\n";
+
+/// Block types defined by the pcapng format
+const SECTION_HEADER_BLOCK: u32 = 0x0a0d_0d0a;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+const ENHANCED_PACKET_BLOCK: u32 = 6;
+const DECRYPTION_SECRETS_BLOCK: u32 = 0xa;
+
+const ETHERNET_HEADER_LEN: usize = 14;
+
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Echoes received packets back to the peer and writes a key log line when it receives a packet
+struct EchoEndpoint {
+    key_log: KeyLog,
+    pending: Vec<(Tuple, Vec<u8>)>,
+    subscriber: NoopSubscriber,
+}
+
+impl endpoint::Endpoint for EchoEndpoint {
+    type PathHandle = Tuple;
+    type Subscriber = NoopSubscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
+
+    fn receive<Rx: rx::Queue<Handle = Tuple>, C: Clock>(&mut self, queue: &mut Rx, _clock: &C) {
+        queue.for_each(|header, payload| {
+            self.key_log.write_all(SECRETS).unwrap();
+            // mimic the endpoint decrypting the packet in place
+            let echo = payload.to_vec();
+            payload.fill(0);
+            self.pending.push((header.path, echo));
+        });
+    }
+
+    fn transmit<Tx: tx::Queue<Handle = Tuple>, C: Clock>(&mut self, queue: &mut Tx, _clock: &C) {
+        for (path, payload) in self.pending.drain(..) {
+            let ecn = ExplicitCongestionNotification::Ect0;
+            queue.push((path, ecn, payload)).unwrap();
+        }
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        _cx: &mut Context<'_>,
+        _clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        Poll::Pending
+    }
+
+    fn timeout(&self) -> Option<Timestamp> {
+        None
+    }
+
+    fn set_mtu_config(&mut self, _mtu_config: mtu::Config) {
+        // noop
+    }
+
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        &mut self.subscriber
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    messages: Vec<(datagram::Header<Tuple>, Vec<u8>)>,
+}
+
+impl rx::Queue for Queue {
+    type Handle = Tuple;
+
+    fn for_each<F: FnMut(datagram::Header<Tuple>, &mut [u8])>(&mut self, mut on_packet: F) {
+        for (header, mut payload) in self.messages.drain(..) {
+            on_packet(header, &mut payload);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl tx::Queue for Queue {
+    type Handle = Tuple;
+
+    const SUPPORTS_ECN: bool = true;
+
+    fn push<M: tx::Message<Handle = Tuple>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let header = datagram::Header {
+            path: *message.path_handle(),
+            ecn: message.ecn(),
+        };
+
+        let mut payload = vec![0; 1500];
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+        payload.truncate(len);
+
+        self.messages.push((header, payload));
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+}
+
+fn ipv4_tuple() -> Tuple {
+    Tuple {
+        remote_address: SocketAddressV4::new([192, 168, 0, 2], 4433).into(),
+        local_address: SocketAddressV4::new([192, 168, 0, 1], 443).into(),
+    }
+}
+
+#[test]
+fn echo_test() {
+    let output = Output::default();
+    let capture = Capture::new(output.clone()).unwrap();
+
+    let mut endpoint = capture.endpoint(EchoEndpoint {
+        key_log: capture.key_log(),
+        pending: vec![],
+        subscriber: NoopSubscriber,
+    });
+
+    let path = ipv4_tuple();
+    let payload = [1u8, 2, 3, 4, 5];
+    let clock = time::Clock::default();
+
+    let mut rx = Queue::default();
+    rx.messages.push((
+        datagram::Header {
+            path,
+            ecn: ExplicitCongestionNotification::Ect1,
+        },
+        payload.to_vec(),
+    ));
+    endpoint.receive(&mut rx, &clock);
+
+    time::advance(Duration::from_millis(10));
+
+    let mut tx = Queue::default();
+    endpoint.transmit(&mut tx, &clock);
+    assert_eq!(tx.messages.len(), 1);
+    assert_eq!(tx.messages[0].1, payload);
+
+    capture.flush().unwrap();
+    let output = output.0.lock().unwrap();
+    let blocks = pcapng::tests::blocks(&output);
+    let block_types: Vec<_> = blocks.iter().map(|(block_type, _)| *block_type).collect();
+    assert_eq!(
+        block_types,
+        [
+            SECTION_HEADER_BLOCK,
+            INTERFACE_DESCRIPTION_BLOCK,
+            // the secrets derived while receiving the packet are written before it
+            DECRYPTION_SECRETS_BLOCK,
+            ENHANCED_PACKET_BLOCK,
+            ENHANCED_PACKET_BLOCK,
+        ]
+    );
+
+    let (_, secrets) = blocks[2];
+    assert_eq!(&secrets[8..8 + SECRETS.len()], SECRETS);
+
+    let timestamp = |body: &[u8]| {
+        let high = u32::from_le_bytes(body[4..8].try_into().unwrap()) as u64;
+        let low = u32::from_le_bytes(body[8..12].try_into().unwrap()) as u64;
+        Duration::from_nanos((high << 32) | low)
+    };
+    let frame = |body: &[u8]| {
+        let len = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+        body[20..20 + len].to_vec()
+    };
+
+    let (_, inbound) = blocks[3];
+    let (_, outbound) = blocks[4];
+
+    assert_eq!(
+        timestamp(outbound) - timestamp(inbound),
+        Duration::from_millis(10)
+    );
+
+    for (body, source, destination, ecn) in [
+        // the packet is recorded before the endpoint modifies it
+        (inbound, [192, 168, 0, 2], [192, 168, 0, 1], 0b01),
+        (outbound, [192, 168, 0, 1], [192, 168, 0, 2], 0b10),
+    ] {
+        let frame = frame(body);
+        let ip = &frame[ETHERNET_HEADER_LEN..];
+        let udp = &ip[20..];
+
+        // EtherType
+        assert_eq!(&frame[12..14], &[0x08, 0x00]);
+        // ECN bits in the TOS field
+        assert_eq!(ip[1] & 0b11, ecn);
+        assert_eq!(&ip[12..16], &source);
+        assert_eq!(&ip[16..20], &destination);
+        assert_eq!(&udp[8..], &payload);
+    }
+}
+
+#[test]
+fn dual_stack_tuple_test() {
+    let path = Tuple {
+        remote_address: SocketAddressV4::new([192, 168, 0, 2], 4433)
+            .to_ipv6_mapped()
+            .into(),
+        local_address: SocketAddressV6::UNSPECIFIED.into(),
+    };
+
+    let tuple = tuple(&path, Direction::Outbound);
+
+    assert_eq!(
+        tuple.remote_address.ip,
+        IpAddress::Ipv4([192, 168, 0, 2].into())
+    );
+    assert_eq!(
+        tuple.local_address.ip,
+        IpAddress::Ipv4(IpV4Address::UNSPECIFIED)
+    );
+}
+
+#[test]
+fn failed_writer_test() {
+    struct Failing(Output, usize);
+
+    impl Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            // accept the section header and fail after that
+            self.1 = self.1.checked_sub(1).ok_or(io::ErrorKind::Other)?;
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let output = Output::default();
+    let capture = Capture::new(Failing(output.clone(), 1)).unwrap();
+    let len = output.0.lock().unwrap().len();
+
+    let mut blocks = vec![1, 2, 3, 4];
+    capture.write(&mut blocks);
+    assert!(blocks.is_empty());
+    // the flush is ignored once the writer thread has stopped
+    capture.flush().unwrap();
+    assert!(capture.shared.is_stopped.load(Ordering::Relaxed));
+
+    // secrets are discarded after the capture is stopped
+    capture.key_log().write_all(SECRETS).unwrap();
+    assert!(capture.shared.secrets.lock().unwrap().is_empty());
+    assert_eq!(output.0.lock().unwrap().len(), len);
+}
+
+#[test]
+fn flush_test() {
+    #[derive(Clone, Default)]
+    struct Flushes(Output, Arc<Mutex<usize>>);
+
+    impl Write for Flushes {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            *self.1.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    let writer = Flushes::default();
+    let capture = Capture::new(writer.clone()).unwrap();
+    // the header is flushed right away
+    assert_eq!(*writer.1.lock().unwrap(), 1);
+
+    // the blocks are written without flushing until the interval elapses
+    let mut blocks = vec![1, 2, 3, 4];
+    capture.write(&mut blocks);
+    assert_eq!(*writer.1.lock().unwrap(), 1);
+
+    capture.flush().unwrap();
+    assert_eq!(*writer.1.lock().unwrap(), 2);
+
+    // the writer is flushed once the last handle is dropped
+    let key_log = capture.key_log();
+    drop(capture);
+    assert_eq!(*writer.1.lock().unwrap(), 2);
+    drop(key_log);
+    assert_eq!(*writer.1.lock().unwrap(), 3);
+}
+
+#[test]
+fn full_queue_test() {
+    /// Blocks writes until the gate is released
+    struct Gated(Output, Arc<Mutex<()>>);
+
+    impl Write for Gated {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let _gate = self.1.lock().unwrap();
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let output = Output::default();
+    let gate = Arc::new(Mutex::new(()));
+    let capture = Capture::new(Gated(output.clone(), gate.clone())).unwrap();
+    let header_len = output.0.lock().unwrap().len();
+
+    // the endpoint isn't blocked by a writer that has fallen behind
+    let closed = gate.lock().unwrap();
+    let writes = MAX_PENDING_WRITES + 2;
+    for _ in 0..writes {
+        capture.write(&mut vec![1, 2, 3, 4]);
+    }
+    drop(closed);
+
+    capture.flush().unwrap();
+    let len = output.0.lock().unwrap().len() - header_len;
+    assert!(len > 0);
+    assert!(
+        len < writes * 4,
+        "blocks should be dropped once the queue is full"
+    );
+}
@@ -98,8 +98,7 @@ impl Buffers {
 
             lock.addr_to_host.insert(addr, host);
             let host_to_addr = lock.host_to_addr.get_mut(&host).unwrap();
-            let prev = host_to_addr.pop().unwrap();
-            host_to_addr.push(addr);
+            let prev = core::mem::replace(&mut host_to_addr[0], addr);
 
             lock.addr_to_host.remove(&prev);
 
@@ -110,6 +109,27 @@ impl Buffers {
         }
     }
 
+    /// Adds an address on which the host can also send and receive packets
+    pub fn add_address(&self, host: HostId, addr: std::net::SocketAddr) {
+        if let Ok(mut lock) = self.inner.lock() {
+            let addr = addr.into();
+            // can't add an already used address
+            if lock.addr_to_host.contains_key(&addr) {
+                return;
+            }
+
+            lock.addr_to_host.insert(addr, host);
+            lock.host_to_addr.get_mut(&host).unwrap().push(addr);
+            lock.tx
+                .get_mut(&host)
+                .unwrap()
+                .additional_addresses
+                .push(addr.into());
+
+            debug!("add address {addr}");
+        }
+    }
+
     pub fn tx<F: FnOnce(&mut Queue)>(&self, handle: SocketAddress, f: F) {
         if let Ok(mut lock) = self.inner.lock() {
             let lock = &mut *lock;
@@ -335,6 +355,7 @@ pub struct Queue {
     mtu: u16,
     packets: VecDeque<Packet>,
     local_address: LocalAddress,
+    additional_addresses: Vec<LocalAddress>,
     waker: Option<Waker>,
     is_open: bool,
 }
@@ -347,6 +368,7 @@ impl Queue {
             mtu,
             packets: VecDeque::new(),
             local_address,
+            additional_addresses: Vec::new(),
             waker: None,
             is_open: true,
         }
@@ -422,8 +444,14 @@ impl Queue {
     }
 
     pub fn send_packet(&mut self, mut packet: Packet) {
-        // update the path with the latest address
-        packet.path.local_address = self.local_address;
+        // update the path with the latest address, unless the packet was sent from one of the
+        // additional addresses
+        if !self
+            .additional_addresses
+            .contains(&packet.path.local_address)
+        {
+            packet.path.local_address = self.local_address;
+        }
 
         let _span = debug_span!(
             "packet",
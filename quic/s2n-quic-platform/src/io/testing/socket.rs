@@ -89,6 +89,11 @@ impl Socket {
         self.0.buffers.rebind(self.0.host, addr);
     }
 
+    /// Adds an address on which the socket can also send and receive packets
+    pub fn add_address(&self, addr: std::net::SocketAddr) {
+        self.0.buffers.add_address(self.0.host, addr);
+    }
+
     /// Sends a packet to the provided destination
     pub fn send_to(
         &self,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{features::gso, message::default as message, socket, syscall};
+use core::future::Future;
 use s2n_quic_core::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher as _},
@@ -18,7 +19,7 @@ mod builder;
 mod clock;
 pub(crate) mod task;
 #[cfg(test)]
-mod tests;
+pub(super) mod tests;
 
 pub type PathHandle = message::Handle;
 pub use builder::Builder;
@@ -42,8 +43,43 @@ impl Io {
 
     pub fn start<E: Endpoint<PathHandle = PathHandle>>(
         self,
-        mut endpoint: E,
+        endpoint: E,
     ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
+        self.start_with(
+            endpoint,
+            |socket, producer, cooldown, stats| Ok(task::rx(socket, producer, cooldown, stats)),
+            |socket, consumer, gso, cooldown, stats| {
+                Ok(task::tx(socket, consumer, gso, cooldown, stats))
+            },
+        )
+    }
+
+    /// Starts the endpoint event loop, using the provided functions to create the tasks that
+    /// read from and write to the sockets
+    pub(crate) fn start_with<E, Rx, RxTask, Tx, TxTask>(
+        self,
+        mut endpoint: E,
+        mut rx_task: Rx,
+        mut tx_task: Tx,
+    ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)>
+    where
+        E: Endpoint<PathHandle = PathHandle>,
+        Rx: FnMut(
+            socket2::Socket,
+            socket::ring::Producer<message::Message>,
+            Cooldown,
+            socket::stats::Sender,
+        ) -> io::Result<RxTask>,
+        RxTask: 'static + Future<Output = io::Result<()>> + Send,
+        Tx: FnMut(
+            socket2::Socket,
+            socket::ring::Consumer<message::Message>,
+            crate::features::Gso,
+            Cooldown,
+            socket::stats::Sender,
+        ) -> io::Result<TxTask>,
+        TxTask: 'static + Future<Output = io::Result<()>> + Send,
+    {
         let Builder {
             handle,
             rx_socket,
@@ -57,6 +93,8 @@ impl Io {
             mtu_config_builder,
             max_segments,
             gro_enabled,
+            icmp_errors_enabled,
+            txtime_enabled,
             reuse_address,
             reuse_port,
             only_v6,
@@ -161,6 +199,11 @@ impl Io {
         // Configure packet info CMSG
         syscall::configure_pktinfo(&rx_socket);
 
+        // Configure ICMP errors to be queued on the socket
+        if icmp_errors_enabled.unwrap_or(true) {
+            syscall::configure_recverr(&rx_socket);
+        }
+
         // Configure TOS/ECN
         let tos_enabled = syscall::configure_tos(&rx_socket);
 
@@ -170,6 +213,11 @@ impl Io {
             },
         });
 
+        // Configure SO_TXTIME pacing offload
+        let txtime_enabled = txtime_enabled
+            && <message::Message as crate::message::Message>::SUPPORTS_TXTIME
+            && syscall::configure_txtime(&tx_socket);
+
         let (stats_sender, stats_recv) = crate::socket::stats::channel();
 
         let rx = {
@@ -205,21 +253,21 @@ impl Io {
 
                 // spawn a task that actually reads from the socket into the ring buffer
                 if idx + 1 == rx_socket_count {
-                    handle.spawn(task::rx(
+                    handle.spawn(rx_task(
                         rx_socket,
                         producer,
                         rx_cooldown,
                         stats_sender.clone(),
-                    ));
+                    )?);
                     break;
                 } else {
                     let rx_socket = rx_socket.try_clone()?;
-                    handle.spawn(task::rx(
+                    handle.spawn(rx_task(
                         rx_socket,
                         producer,
                         rx_cooldown.clone(),
                         stats_sender.clone(),
-                    ));
+                    )?);
                 }
             }
 
@@ -262,28 +310,34 @@ impl Io {
 
                 // spawn a task that actually flushes the ring buffer to the socket
                 if idx + 1 == tx_socket_count {
-                    handle.spawn(task::tx(
+                    handle.spawn(tx_task(
                         tx_socket,
                         consumer,
                         gso.clone(),
                         tx_cooldown,
                         stats_sender.clone(),
-                    ));
+                    )?);
                     break;
                 } else {
                     let tx_socket = tx_socket.try_clone()?;
-                    handle.spawn(task::tx(
+                    handle.spawn(tx_task(
                         tx_socket,
                         consumer,
                         gso.clone(),
                         tx_cooldown.clone(),
                         stats_sender.clone(),
-                    ));
+                    )?);
                 }
             }
 
             // construct the TX side for the endpoint event loop
-            socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu())
+            let tx = socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu());
+
+            if txtime_enabled {
+                tx.with_txtime(crate::features::txtime::clock_epoch(clock.get_time()))
+            } else {
+                tx
+            }
         };
 
         // Notify the endpoint of the MTU that we chose
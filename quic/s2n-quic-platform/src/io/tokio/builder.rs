@@ -17,6 +17,8 @@ pub struct Builder {
     pub(super) mtu_config_builder: mtu::Builder,
     pub(super) max_segments: gso::MaxSegments,
     pub(super) gro_enabled: Option<bool>,
+    pub(super) icmp_errors_enabled: Option<bool>,
+    pub(super) txtime_enabled: bool,
     pub(super) reuse_address: bool,
     pub(super) reuse_port: bool,
     pub(super) only_v6: bool,
@@ -219,6 +221,36 @@ impl Builder {
         }
     }
 
+    /// Configures reading ICMP errors from the socket error queue (default: enabled)
+    ///
+    /// When enabled, the `IP_RECVERR` and `IPV6_RECVERR` socket options are set so that ICMP
+    /// "packet too big" and "port unreachable" messages for transmitted datagrams are passed to
+    /// the endpoint. These are used to quickly reduce the path MTU and detect failed paths,
+    /// rather than waiting for loss detection. The option is ignored on platforms that don't
+    /// support it.
+    pub fn with_icmp_errors(mut self, enabled: bool) -> io::Result<Self> {
+        self.icmp_errors_enabled = Some(enabled);
+        Ok(self)
+    }
+
+    /// Configures pacing offload with the `SO_TXTIME` socket option (default: disabled)
+    ///
+    /// When enabled, each message is tagged with the departure time computed by the congestion
+    /// controller's pacer, and the kernel is responsible for holding it until that time. This
+    /// requires the `fq` qdisc to be configured on the egress interface; otherwise the departure
+    /// times are ignored and packets are sent immediately. If the socket option cannot be set,
+    /// pacing falls back to the endpoint timers.
+    pub fn with_txtime(mut self, enabled: bool) -> io::Result<Self> {
+        if enabled && !crate::features::txtime::IS_SUPPORTED {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "SO_TXTIME is not supported on the current platform",
+            ));
+        }
+        self.txtime_enabled = enabled;
+        Ok(self)
+    }
+
     /// Enables the address reuse (SO_REUSEADDR) socket option
     pub fn with_reuse_address(mut self, enabled: bool) -> io::Result<Self> {
         self.reuse_address = enabled;
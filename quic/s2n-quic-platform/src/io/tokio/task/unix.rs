@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    features::Gso,
+    features::{self, Gso},
     socket::{
         ring, stats,
         task::{rx, tx},
@@ -126,6 +126,29 @@ impl<S: AsRawFd, M: UnixMessage> rx::Socket<M> for AsyncFd<S> {
             stats,
         );
 
+        // The socket reports an error when an ICMP message is queued for a previously
+        // transmitted datagram so drain the error queue into the remaining entries
+        if features::recverr::IS_SUPPORTED && events.take_errored() {
+            let count = events.count();
+            if let Some(entries) = entries.get_mut(count..) {
+                let is_blocked = events.is_blocked();
+
+                M::recv(
+                    self.get_ref().as_raw_fd(),
+                    SocketType::ErrorQueue,
+                    entries,
+                    events,
+                    stats,
+                );
+
+                // an empty error queue doesn't mean the socket isn't readable
+                if !is_blocked {
+                    events.take_blocked();
+                }
+                events.take_errored();
+            }
+        }
+
         // yield back if we weren't blocked
         if !events.is_blocked() {
             return Ok(());
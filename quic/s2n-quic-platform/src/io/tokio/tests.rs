@@ -16,7 +16,7 @@ use s2n_quic_core::{
 };
 use std::{collections::BTreeMap, net::ToSocketAddrs};
 
-struct TestEndpoint<const IS_SERVER: bool> {
+pub(crate) struct TestEndpoint<const IS_SERVER: bool> {
     handle: PathHandle,
     messages: BTreeMap<u32, Option<Timestamp>>,
     now: Option<Timestamp>,
@@ -24,8 +24,11 @@ struct TestEndpoint<const IS_SERVER: bool> {
 }
 
 impl<const IS_SERVER: bool> TestEndpoint<IS_SERVER> {
-    fn new(handle: PathHandle) -> Self {
-        let messages = if IS_SERVER { 0 } else { 30 };
+    pub(crate) fn new(handle: PathHandle) -> Self {
+        Self::with_messages(handle, if IS_SERVER { 0 } else { 30 })
+    }
+
+    pub(crate) fn with_messages(handle: PathHandle, messages: u32) -> Self {
         let messages = (0..messages).map(|id| (id, None)).collect();
         Self {
             handle,
@@ -37,7 +40,7 @@ impl<const IS_SERVER: bool> TestEndpoint<IS_SERVER> {
 }
 
 #[derive(Debug, Default)]
-struct NoopSubscriber;
+pub(crate) struct NoopSubscriber;
 
 impl event::Subscriber for NoopSubscriber {
     type ConnectionContext = ();
@@ -138,12 +141,18 @@ impl<const IS_SERVER: bool> Endpoint for TestEndpoint<IS_SERVER> {
     }
 }
 
-async fn runtime<A: ToSocketAddrs>(
+pub(crate) async fn runtime<A: ToSocketAddrs>(
     receive_addr: A,
     send_addr: Option<A>,
 ) -> io::Result<(super::Io, SocketAddress)> {
-    let mut io_builder = Io::builder();
+    runtime_with_builder(Io::builder(), receive_addr, send_addr).await
+}
 
+pub(crate) async fn runtime_with_builder<A: ToSocketAddrs>(
+    mut io_builder: Builder,
+    receive_addr: A,
+    send_addr: Option<A>,
+) -> io::Result<(super::Io, SocketAddress)> {
     let rx_socket = syscall::bind_udp(receive_addr, false, false, false)?;
     rx_socket.set_nonblocking(true)?;
     let rx_socket: std::net::UdpSocket = rx_socket.into();
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An IO provider that drives the UDP sockets with [io_uring](https://man7.org/linux/man-pages/man7/io_uring.7.html)
+//!
+//! The provider is configured the same way as the [`tokio`](super::tokio) provider and shares its
+//! endpoint event loop and message rings. Instead of `recvmmsg` and `sendmmsg`:
+//!
+//! * Packets are received by a single multishot `recvmsg` operation, which selects the free
+//!   messages in the ring from a buffer ring registered with the kernel.
+//! * Packets are sent by submitting a batch of `sendmsg` operations, which point directly at the
+//!   messages in the ring, including any GSO control messages.
+//!
+//! This requires Linux 6.0 or later.
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
+use std::io;
+
+mod ring;
+mod rx;
+#[cfg(test)]
+mod tests;
+mod tx;
+
+pub use super::tokio::{Builder, PathHandle};
+pub(crate) use rx::PAYLOAD_PREFIX_LEN;
+
+#[derive(Debug, Default)]
+pub struct Io {
+    io: super::tokio::Io,
+}
+
+impl Io {
+    /// Returns a builder for configuring the provider
+    ///
+    /// The built [`tokio::Io`](super::tokio::Io) can be converted into this provider with [`From`].
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    pub fn new<A: std::net::ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        super::tokio::Io::new(addr).map(Self::from)
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
+        self.io.start_with(endpoint, rx::task, tx::task)
+    }
+}
+
+impl From<super::tokio::Io> for Io {
+    #[inline]
+    fn from(io: super::tokio::Io) -> Self {
+        Self { io }
+    }
+}
@@ -0,0 +1,675 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal io_uring instance
+//!
+//! Only the pieces of the interface needed by the IO tasks are implemented here. The structures
+//! follow [io_uring.h](https://github.com/torvalds/linux/blob/v6.0/include/uapi/linux/io_uring.h).
+
+use core::{
+    mem::size_of,
+    ptr::NonNull,
+    sync::atomic::{AtomicU16, AtomicU32, Ordering},
+};
+use libc::c_void;
+use std::{
+    io,
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+};
+
+const IORING_OP_SENDMSG: u8 = 9;
+const IORING_OP_RECVMSG: u8 = 10;
+const IORING_OP_ASYNC_CANCEL: u8 = 14;
+
+const IOSQE_BUFFER_SELECT: u8 = 1 << 5;
+const IORING_RECV_MULTISHOT: u16 = 1 << 1;
+const IORING_ASYNC_CANCEL_ANY: u32 = 1 << 2;
+
+const IORING_CQE_F_BUFFER: u32 = 1 << 0;
+const IORING_CQE_F_MORE: u32 = 1 << 1;
+const IORING_CQE_BUFFER_SHIFT: u32 = 16;
+
+const IORING_SETUP_CQSIZE: u32 = 1 << 3;
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+const IORING_SQ_CQ_OVERFLOW: u32 = 1 << 1;
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+const IORING_REGISTER_PBUF_RING: libc::c_uint = 22;
+
+const IORING_OFF_SQ_RING: i64 = 0;
+const IORING_OFF_SQES: i64 = 0x10000000;
+
+/// The `user_data` value used for cancellation requests
+const CANCEL_USER_DATA: u64 = u64::MAX;
+
+/// A submission queue entry
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_group: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    _pad: u64,
+}
+
+impl Sqe {
+    /// Sends the message on the socket
+    #[inline]
+    pub fn sendmsg(fd: RawFd, msg: *const libc::msghdr, user_data: u64) -> Self {
+        Self {
+            opcode: IORING_OP_SENDMSG,
+            fd,
+            addr: msg as u64,
+            user_data,
+            ..Default::default()
+        }
+    }
+
+    /// Receives messages on the socket until the operation is cancelled or fails
+    ///
+    /// Each message is written to a buffer selected from the given group, prefixed by a
+    /// [`RecvmsgOut`] header and followed by the address and control message regions sized by
+    /// `msg`.
+    #[inline]
+    pub fn recvmsg_multishot(
+        fd: RawFd,
+        msg: *const libc::msghdr,
+        buf_group: u16,
+        user_data: u64,
+    ) -> Self {
+        Self {
+            opcode: IORING_OP_RECVMSG,
+            flags: IOSQE_BUFFER_SELECT,
+            ioprio: IORING_RECV_MULTISHOT,
+            fd,
+            addr: msg as u64,
+            user_data,
+            buf_group,
+            ..Default::default()
+        }
+    }
+
+    /// Cancels all of the in-flight operations on the ring
+    #[inline]
+    fn cancel_any(user_data: u64) -> Self {
+        Self {
+            opcode: IORING_OP_ASYNC_CANCEL,
+            fd: -1,
+            op_flags: IORING_ASYNC_CANCEL_ANY,
+            user_data,
+            ..Default::default()
+        }
+    }
+}
+
+/// A completion queue entry
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct Cqe {
+    pub user_data: u64,
+    pub res: i32,
+    pub flags: u32,
+}
+
+impl Cqe {
+    /// Returns the result of the operation
+    #[inline]
+    pub fn result(&self) -> io::Result<u32> {
+        if self.res < 0 {
+            Err(io::Error::from_raw_os_error(-self.res))
+        } else {
+            Ok(self.res as u32)
+        }
+    }
+
+    /// Returns true if the operation will produce more completions
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.flags & IORING_CQE_F_MORE != 0
+    }
+
+    /// Returns the provided buffer that was selected for the operation, if any
+    #[inline]
+    pub fn buffer_id(&self) -> Option<u16> {
+        if self.flags & IORING_CQE_F_BUFFER != 0 {
+            Some((self.flags >> IORING_CQE_BUFFER_SHIFT) as u16)
+        } else {
+            None
+        }
+    }
+}
+
+/// The header written by a multishot `recvmsg` at the start of each provided buffer
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct RecvmsgOut {
+    pub namelen: u32,
+    pub controllen: u32,
+    pub payloadlen: u32,
+    pub flags: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct BufReg {
+    ring_addr: u64,
+    ring_entries: u32,
+    bgid: u16,
+    flags: u16,
+    resv: [u64; 3],
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct Buf {
+    addr: u64,
+    len: u32,
+    bid: u16,
+    // the tail of the ring overlaps with this field in the first entry
+    resv: u16,
+}
+
+/// Calls the given libc function and wraps the result in an `io::Result`.
+macro_rules! libc {
+    ($fn: ident ( $($arg: expr),* $(,)* ) ) => {{
+        let res = libc::$fn($($arg, )*);
+        if res < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(res)
+        }
+    }};
+}
+
+/// A mmap'd region in memory
+#[derive(Debug)]
+struct Mmap {
+    addr: NonNull<c_void>,
+    len: usize,
+}
+
+impl Mmap {
+    /// Maps a region of the io_uring instance into memory
+    fn ring(fd: RawFd, len: usize, offset: i64) -> io::Result<Self> {
+        Self::new(len, libc::MAP_SHARED | libc::MAP_POPULATE, fd, offset)
+    }
+
+    /// Maps a zeroed, page-aligned region into memory
+    fn anonymous(len: usize) -> io::Result<Self> {
+        Self::new(len, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+    }
+
+    fn new(len: usize, flags: libc::c_int, fd: RawFd, offset: i64) -> io::Result<Self> {
+        let addr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                flags,
+                fd,
+                offset as _,
+            )
+        };
+
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        let addr = NonNull::new(addr).ok_or_else(|| io::Error::other("mmap returned null"))?;
+
+        Ok(Self { addr, len })
+    }
+
+    /// Returns a pointer to the value at the given byte offset
+    ///
+    /// # Safety
+    ///
+    /// The offset must be within the bounds of the region and aligned for `T`
+    #[inline]
+    unsafe fn at<T>(&self, offset: u32) -> NonNull<T> {
+        debug_assert!(offset as usize + size_of::<T>() <= self.len);
+        NonNull::new_unchecked(self.addr.as_ptr().add(offset as usize) as *mut T)
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            // Safety: the len is the same value as on creation
+            libc::munmap(self.addr.as_ptr(), self.len);
+        }
+    }
+}
+
+/// An io_uring instance with a submission and completion queue
+#[derive(Debug)]
+pub struct Ring {
+    sq_head: NonNull<AtomicU32>,
+    sq_tail: NonNull<AtomicU32>,
+    sq_flags: NonNull<AtomicU32>,
+    sq_array: NonNull<u32>,
+    sq_mask: u32,
+    sq_entries: u32,
+    sqes: NonNull<Sqe>,
+    cq_head: NonNull<AtomicU32>,
+    cq_tail: NonNull<AtomicU32>,
+    cq_mask: u32,
+    cqes: NonNull<Cqe>,
+    /// The local copy of the submission tail, which is published on [`Ring::submit`]
+    local_sq_tail: u32,
+    _sqes_mmap: Mmap,
+    _ring_mmap: Mmap,
+    // the fd is declared last so it's closed after the memory is unmapped
+    fd: OwnedFd,
+}
+
+/// Safety: the ring pointers are only accessed through `&mut self`
+unsafe impl Send for Ring {}
+/// Safety: the ring pointers are only accessed through `&mut self`
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    /// Creates a new io_uring instance with the given queue sizes
+    pub fn new(sq_entries: u32, cq_entries: u32) -> io::Result<Self> {
+        let mut params = Params {
+            flags: IORING_SETUP_CQSIZE,
+            cq_entries,
+            ..Default::default()
+        };
+
+        let fd = unsafe {
+            libc!(syscall(
+                libc::SYS_io_uring_setup,
+                sq_entries,
+                &mut params as *mut Params
+            ))?
+        };
+        let fd = unsafe {
+            // Safety: the fd was just returned by the kernel
+            OwnedFd::from_raw_fd(fd as RawFd)
+        };
+
+        // the SQ and CQ rings share a single mapping since Linux 5.4
+        if params.features & IORING_FEAT_SINGLE_MMAP == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "io_uring instance does not support IORING_FEAT_SINGLE_MMAP",
+            ));
+        }
+
+        let sq_len = params.sq_off.array as usize + params.sq_entries as usize * size_of::<u32>();
+        let cq_len = params.cq_off.cqes as usize + params.cq_entries as usize * size_of::<Cqe>();
+        let ring_mmap = Mmap::ring(fd.as_raw_fd(), sq_len.max(cq_len), IORING_OFF_SQ_RING)?;
+
+        let sqes_len = params.sq_entries as usize * size_of::<Sqe>();
+        let sqes_mmap = Mmap::ring(fd.as_raw_fd(), sqes_len, IORING_OFF_SQES)?;
+
+        unsafe {
+            // Safety: the offsets were provided by the kernel for the mapped region
+            let sq_off = &params.sq_off;
+            let cq_off = &params.cq_off;
+            let sq_tail = ring_mmap.at::<AtomicU32>(sq_off.tail);
+            let local_sq_tail = sq_tail.as_ref().load(Ordering::Relaxed);
+
+            Ok(Self {
+                sq_head: ring_mmap.at(sq_off.head),
+                sq_tail,
+                sq_flags: ring_mmap.at(sq_off.flags),
+                sq_array: ring_mmap.at(sq_off.array),
+                sq_mask: *ring_mmap.at::<u32>(sq_off.ring_mask).as_ptr(),
+                sq_entries: params.sq_entries,
+                sqes: sqes_mmap.at(0),
+                cq_head: ring_mmap.at(cq_off.head),
+                cq_tail: ring_mmap.at(cq_off.tail),
+                cq_mask: *ring_mmap.at::<u32>(cq_off.ring_mask).as_ptr(),
+                cqes: ring_mmap.at(cq_off.cqes),
+                local_sq_tail,
+                _sqes_mmap: sqes_mmap,
+                _ring_mmap: ring_mmap,
+                fd,
+            })
+        }
+    }
+
+    /// Returns the number of entries in the submission queue
+    #[inline]
+    pub fn sq_entries(&self) -> u32 {
+        self.sq_entries
+    }
+
+    /// Pushes an entry onto the submission queue
+    ///
+    /// Returns `false` if the queue is full.
+    ///
+    /// # Safety
+    ///
+    /// Any memory referenced by the entry must remain valid until the operation completes.
+    #[inline]
+    pub unsafe fn push(&mut self, sqe: Sqe) -> bool {
+        let head = self.sq_head.as_ref().load(Ordering::Acquire);
+        if self.local_sq_tail.wrapping_sub(head) == self.sq_entries {
+            return false;
+        }
+
+        let idx = self.local_sq_tail & self.sq_mask;
+        self.sqes.as_ptr().add(idx as usize).write(sqe);
+        self.sq_array.as_ptr().add(idx as usize).write(idx);
+        self.local_sq_tail = self.local_sq_tail.wrapping_add(1);
+
+        true
+    }
+
+    /// Submits all of the pushed entries to the kernel
+    #[inline]
+    pub fn submit(&mut self) -> io::Result<u32> {
+        self.enter(0, 0)
+    }
+
+    /// Submits all of the pushed entries and waits for at least `min_complete` completions
+    #[inline]
+    pub fn submit_and_wait(&mut self, min_complete: u32) -> io::Result<u32> {
+        self.enter(min_complete, IORING_ENTER_GETEVENTS)
+    }
+
+    #[inline]
+    fn enter(&mut self, min_complete: u32, flags: u32) -> io::Result<u32> {
+        let to_submit = unsafe {
+            self.sq_tail
+                .as_ref()
+                .store(self.local_sq_tail, Ordering::Release);
+            let head = self.sq_head.as_ref().load(Ordering::Acquire);
+            self.local_sq_tail.wrapping_sub(head)
+        };
+
+        if to_submit == 0 && flags == 0 {
+            return Ok(0);
+        }
+
+        let res = unsafe {
+            libc!(syscall(
+                libc::SYS_io_uring_enter,
+                self.fd.as_raw_fd(),
+                to_submit,
+                min_complete,
+                flags,
+                core::ptr::null::<c_void>(),
+                0usize
+            ))?
+        };
+
+        Ok(res as u32)
+    }
+
+    /// Pops an entry from the completion queue
+    #[inline]
+    pub fn pop(&mut self) -> Option<Cqe> {
+        if let Some(cqe) = self.pop_cqe() {
+            return Some(cqe);
+        }
+
+        // flush any completions that overflowed the queue
+        let sq_flags = unsafe { self.sq_flags.as_ref().load(Ordering::Acquire) };
+        if sq_flags & IORING_SQ_CQ_OVERFLOW != 0 && self.enter(0, IORING_ENTER_GETEVENTS).is_ok() {
+            return self.pop_cqe();
+        }
+
+        None
+    }
+
+    #[inline]
+    fn pop_cqe(&mut self) -> Option<Cqe> {
+        unsafe {
+            // we're the only consumer so the head doesn't need to be synchronized
+            let head = self.cq_head.as_ref().load(Ordering::Relaxed);
+            let tail = self.cq_tail.as_ref().load(Ordering::Acquire);
+
+            if head == tail {
+                return None;
+            }
+
+            let cqe = self
+                .cqes
+                .as_ptr()
+                .add((head & self.cq_mask) as usize)
+                .read();
+            self.cq_head
+                .as_ref()
+                .store(head.wrapping_add(1), Ordering::Release);
+            Some(cqe)
+        }
+    }
+
+    /// Returns true if there are no entries in the completion queue
+    #[inline]
+    pub fn is_cq_empty(&self) -> bool {
+        unsafe {
+            let head = self.cq_head.as_ref().load(Ordering::Relaxed);
+            let tail = self.cq_tail.as_ref().load(Ordering::Acquire);
+            head == tail
+        }
+    }
+
+    /// Cancels all of the in-flight operations and waits for them to complete
+    ///
+    /// `in_flight` is the number of operations that are still expected to complete, where a
+    /// multishot operation is considered complete once it stops returning `IORING_CQE_F_MORE`. It
+    /// is updated as the operations complete.
+    ///
+    /// Returns `false` if the operations could not be cancelled, in which case any memory
+    /// referenced by them must not be freed.
+    pub fn cancel_all(&mut self, in_flight: &mut u32) -> bool {
+        if *in_flight == 0 {
+            return true;
+        }
+
+        let sqe = Sqe::cancel_any(CANCEL_USER_DATA);
+
+        // make room in the queue if needed
+        while !unsafe {
+            // Safety: the cancellation entry doesn't reference any memory
+            self.push(sqe)
+        } {
+            if self.submit().is_err() {
+                return false;
+            }
+        }
+
+        let mut is_cancelled = false;
+
+        while *in_flight > 0 || !is_cancelled {
+            while let Some(cqe) = self.pop() {
+                if cqe.user_data == CANCEL_USER_DATA {
+                    // ENOENT means the operations completed before the cancellation was processed
+                    if cqe.res < 0 && cqe.res != -libc::ENOENT {
+                        return false;
+                    }
+                    is_cancelled = true;
+                } else if !cqe.has_more() {
+                    *in_flight = in_flight.saturating_sub(1);
+                }
+            }
+
+            if *in_flight == 0 && is_cancelled {
+                break;
+            }
+
+            match self.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Registers a ring of provided buffers with the given buffer group
+    fn register_buffer_ring(&self, ring: &Mmap, entries: u32, group: u16) -> io::Result<()> {
+        let reg = BufReg {
+            ring_addr: ring.addr.as_ptr() as u64,
+            ring_entries: entries,
+            bgid: group,
+            ..Default::default()
+        };
+
+        unsafe {
+            libc!(syscall(
+                libc::SYS_io_uring_register,
+                self.fd.as_raw_fd(),
+                IORING_REGISTER_PBUF_RING,
+                &reg as *const BufReg,
+                1u32
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AsRawFd for Ring {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+/// A ring of buffers that the kernel selects from when completing operations
+///
+/// The ring doesn't own any of the buffers it points to. The caller provides them with
+/// [`Self::push`] and must keep them alive until the kernel has completed or the operations
+/// selecting from the ring have been cancelled.
+///
+/// See [io_uring_register_buf_ring](https://man7.org/linux/man-pages/man3/io_uring_register_buf_ring.3.html).
+#[derive(Debug)]
+pub struct BufferRing {
+    ring: Mmap,
+    mask: u16,
+    tail: u16,
+    group: u16,
+}
+
+/// Safety: the ring pointers are only accessed through `&mut self`
+unsafe impl Send for BufferRing {}
+/// Safety: the ring pointers are only accessed through `&mut self`
+unsafe impl Sync for BufferRing {}
+
+impl BufferRing {
+    /// Registers an empty ring with room for `entries` buffers
+    ///
+    /// `entries` must be a power of two and no larger than 32768.
+    pub fn new(ring: &Ring, entries: u16, group: u16) -> io::Result<Self> {
+        debug_assert!(entries.is_power_of_two());
+        debug_assert!(entries <= 1 << 15);
+
+        let buffer_ring = Mmap::anonymous(entries as usize * size_of::<Buf>())?;
+
+        ring.register_buffer_ring(&buffer_ring, entries as u32, group)?;
+
+        Ok(Self {
+            ring: buffer_ring,
+            mask: entries - 1,
+            tail: 0,
+            group,
+        })
+    }
+
+    /// Returns the buffer group identifier
+    #[inline]
+    pub fn group(&self) -> u16 {
+        self.group
+    }
+
+    /// Provides a buffer to the ring, which is made available to the kernel on [`Self::publish`]
+    ///
+    /// # Safety
+    ///
+    /// The buffer must remain valid until it's selected by a completion or the ring is rewound
+    /// past it while no operations are selecting from the ring.
+    #[inline]
+    pub unsafe fn push(&mut self, addr: *mut u8, len: u32, id: u16) {
+        let idx = self.tail & self.mask;
+
+        // Safety: the index is masked to the number of entries
+        let entry = self.ring.at::<Buf>(idx as u32 * size_of::<Buf>() as u32);
+        let entry = &mut *entry.as_ptr();
+        entry.addr = addr as u64;
+        entry.len = len;
+        entry.bid = id;
+
+        self.tail = self.tail.wrapping_add(1);
+    }
+
+    /// Takes back the last `count` buffers that were provided
+    ///
+    /// # Safety
+    ///
+    /// No operations may be selecting from the ring and the buffers must not have been selected
+    /// by the kernel.
+    #[inline]
+    pub unsafe fn rewind(&mut self, count: u16) {
+        self.tail = self.tail.wrapping_sub(count);
+        self.publish();
+    }
+
+    /// Makes all of the provided buffers available to the kernel
+    #[inline]
+    pub fn publish(&mut self) {
+        unsafe {
+            // Safety: the tail overlaps with the `resv` field of the first entry
+            let tail = self.ring.at::<AtomicU16>(14);
+            tail.as_ref().store(self.tail, Ordering::Release);
+        }
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::ring::{BufferRing, RecvmsgOut, Ring, Sqe};
+use crate::{
+    features,
+    message::{self, cmsg, mmsg::Message, Message as _},
+    socket::{ring, stats, task::rx},
+    syscall::{SocketEvents as _, SocketType, UnixMessage as _},
+};
+use core::{
+    future::Future,
+    mem::{size_of, ManuallyDrop},
+    task::{Context, Poll},
+};
+use s2n_quic_core::task::cooldown::Cooldown;
+use std::{io, net::UdpSocket, os::unix::io::AsRawFd, sync::Arc};
+use tokio::io::unix::AsyncFd;
+
+/// The buffer group used for receiving packets
+const BUFFER_GROUP: u16 = 0;
+/// The `user_data` value for the multishot `recvmsg` operation
+const RECV_USER_DATA: u64 = 0;
+/// The size of the address region in each buffer
+const NAME_CAPACITY: usize = size_of::<libc::sockaddr_in6>();
+/// The size of the control message region in each buffer
+const CONTROL_CAPACITY: usize = cmsg::MAX_LEN;
+/// The number of bytes the kernel writes in front of each payload
+pub(crate) const PAYLOAD_PREFIX_LEN: usize =
+    size_of::<RecvmsgOut>() + NAME_CAPACITY + CONTROL_CAPACITY;
+/// The maximum number of buffers that can be registered in a single buffer ring
+const MAX_BUFFERS: u32 = 1 << 15;
+
+pub fn task(
+    socket: socket2::Socket,
+    mut producer: ring::Producer<Message>,
+    cooldown: Cooldown,
+    stats: stats::Sender,
+) -> io::Result<impl Future<Output = io::Result<()>>> {
+    // all of the messages are free before the task starts
+    let entries = producer.acquire(u32::MAX);
+
+    // all of the messages in the ring have the same payload capacity
+    let payload_len = producer
+        .data()
+        .first()
+        .map_or(u16::MAX as usize, |message| message.payload_len());
+
+    let socket = Socket::new(
+        socket.into(),
+        producer.storage().clone(),
+        entries,
+        payload_len,
+    )?;
+
+    Ok(async move {
+        let result = rx::Receiver::new(producer, socket, cooldown, stats).await;
+        if let Some(err) = result {
+            Err(err)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Receives packets with a single multishot `recvmsg` operation
+///
+/// The free messages in the ring are provided to the kernel in order, which writes each packet
+/// directly into the next one. The message ring reserves [`PAYLOAD_PREFIX_LEN`] bytes in front of
+/// each payload for the header and the address and control message regions, which are copied
+/// into the message's own address and control message storage once the packet is completed.
+pub struct Socket {
+    ring: AsyncFd<Ring>,
+    buffers: ManuallyDrop<BufferRing>,
+    /// The memory backing the messages, which is kept alive until the kernel stops writing to it
+    storage: ManuallyDrop<Arc<message::Storage>>,
+    /// Describes the size of the address and control message regions in each buffer, which are
+    /// always reserved regardless of the received lengths
+    msghdr: ManuallyDrop<Box<libc::msghdr>>,
+    payload_len: usize,
+    /// The maximum number of messages that can be provided to the kernel at once
+    max_provided: usize,
+    /// The number of free messages, from the start of the entries, provided to the kernel
+    provided: usize,
+    /// The buffer identifier of the first provided message
+    buffer_id: u16,
+    is_armed: bool,
+    is_errored: bool,
+    socket: UdpSocket,
+}
+
+/// Safety: the `msghdr` pointers don't reference any memory
+unsafe impl Send for Socket {}
+
+impl Socket {
+    fn new(
+        socket: UdpSocket,
+        storage: Arc<message::Storage>,
+        entries: u32,
+        payload_len: usize,
+    ) -> io::Result<Self> {
+        // io_uring waits for the socket to be ready, while a non-blocking socket would complete
+        // the operation with `EAGAIN` instead
+        socket.set_nonblocking(false)?;
+
+        let mut msghdr: Box<libc::msghdr> = Box::new(unsafe {
+            // Safety: msghdr is zeroable
+            core::mem::zeroed()
+        });
+        msghdr.msg_namelen = NAME_CAPACITY as _;
+        msghdr.msg_controllen = CONTROL_CAPACITY as _;
+
+        let entries = entries.clamp(1, MAX_BUFFERS).next_power_of_two();
+
+        // leave enough room in the completion queue for every buffer to be filled
+        let ring = Ring::new(4, entries * 2)?;
+        let buffers = BufferRing::new(&ring, entries as u16, BUFFER_GROUP)?;
+
+        Ok(Self {
+            ring: AsyncFd::new(ring)?,
+            buffers: ManuallyDrop::new(buffers),
+            storage: ManuallyDrop::new(storage),
+            msghdr: ManuallyDrop::new(msghdr),
+            payload_len,
+            max_provided: entries as usize,
+            provided: 0,
+            buffer_id: 0,
+            is_armed: false,
+            is_errored: false,
+            socket,
+        })
+    }
+
+    /// Submits the multishot `recvmsg` operation
+    #[inline]
+    fn arm(&mut self) -> io::Result<()> {
+        let sqe = Sqe::recvmsg_multishot(
+            self.socket.as_raw_fd(),
+            &**self.msghdr,
+            self.buffers.group(),
+            RECV_USER_DATA,
+        );
+
+        let ring = self.ring.get_mut();
+
+        // Safety: the msghdr and buffers live until the operation is cancelled
+        if unsafe { ring.push(sqe) } {
+            ring.submit()?;
+            self.is_armed = true;
+        }
+
+        Ok(())
+    }
+
+    /// Provides the free messages that the kernel doesn't already have
+    #[inline]
+    fn provide(&mut self, entries: &mut [Message]) {
+        let len = entries.len().min(self.max_provided);
+
+        if self.provided >= len {
+            return;
+        }
+
+        for (offset, message) in entries[self.provided..len].iter_mut().enumerate() {
+            let id = self.buffer_id.wrapping_add((self.provided + offset) as u16);
+
+            unsafe {
+                // Safety: the prefix is reserved in front of each payload and the storage is kept
+                // alive until the kernel stops writing to it
+                let addr = message.payload_ptr_mut().sub(PAYLOAD_PREFIX_LEN);
+                let len = (PAYLOAD_PREFIX_LEN + self.payload_len) as u32;
+                self.buffers.push(addr, len, id);
+            }
+        }
+
+        self.provided = len;
+        self.buffers.publish();
+    }
+
+    /// Moves the header, address and control messages written in front of the payload into the
+    /// message
+    ///
+    /// Returns `false` if the packet was truncated or is otherwise invalid.
+    #[inline]
+    fn read(&self, len: u32, message: &mut Message) -> bool {
+        let len = (len as usize).min(PAYLOAD_PREFIX_LEN + self.payload_len);
+
+        let buffer = unsafe {
+            // Safety: the kernel wrote `len` bytes starting at the prefix of the message
+            let ptr = message.payload_ptr_mut().sub(PAYLOAD_PREFIX_LEN);
+            core::slice::from_raw_parts(ptr as *const u8, len)
+        };
+
+        let Some((header, buffer)) = buffer.split_at_checked(size_of::<RecvmsgOut>()) else {
+            return false;
+        };
+
+        let header = unsafe {
+            // Safety: the kernel writes the header at the start of each buffer
+            core::ptr::read_unaligned(header.as_ptr() as *const RecvmsgOut)
+        };
+
+        if header.flags & (libc::MSG_TRUNC | libc::MSG_CTRUNC) as u32 != 0 {
+            return false;
+        }
+
+        let name_len = (header.namelen as usize).min(NAME_CAPACITY);
+        let control_len = header.controllen as usize;
+        let payload_len = header.payloadlen as usize;
+
+        if payload_len > self.payload_len
+            || buffer.len() < NAME_CAPACITY + CONTROL_CAPACITY + payload_len
+        {
+            return false;
+        }
+
+        let (Some(name), Some(control)) = (
+            buffer.get(..name_len),
+            buffer.get(NAME_CAPACITY..NAME_CAPACITY + control_len),
+        ) else {
+            return false;
+        };
+
+        unsafe {
+            // Safety: the lengths were checked against the capacities of each region
+            let msghdr = &mut message.msg_hdr;
+
+            core::ptr::copy_nonoverlapping(name.as_ptr(), msghdr.msg_name as *mut u8, name_len);
+            msghdr.msg_namelen = name_len as _;
+
+            core::ptr::copy_nonoverlapping(
+                control.as_ptr(),
+                msghdr.msg_control as *mut u8,
+                control_len,
+            );
+            msghdr.msg_controllen = control_len as _;
+            msghdr.msg_flags = header.flags as _;
+
+            // the payload was written in place
+            message.set_payload_len(payload_len);
+        }
+
+        true
+    }
+}
+
+impl rx::Socket<Message> for Socket {
+    type Error = io::Error;
+
+    #[inline]
+    fn recv(
+        &mut self,
+        cx: &mut Context,
+        entries: &mut [Message],
+        events: &mut rx::Events,
+        stats: &stats::Sender,
+    ) -> io::Result<()> {
+        self.provide(entries);
+
+        if !self.is_armed {
+            self.arm()?;
+        }
+
+        let mut count = 0;
+
+        while count < self.provided {
+            let Some(cqe) = self.ring.get_mut().pop() else {
+                break;
+            };
+
+            if !cqe.has_more() {
+                self.is_armed = false;
+            }
+
+            match cqe.result() {
+                Ok(len) => {
+                    if let Some(buffer_id) = cqe.buffer_id() {
+                        // the kernel selects the buffers in the order they were provided
+                        debug_assert_eq!(buffer_id, self.buffer_id.wrapping_add(count as u16));
+
+                        let message = &mut entries[count];
+                        if !self.read(len, message) {
+                            // the message still needs to be released to keep the ring in the same
+                            // order as the provided buffers so drop the address to skip it
+                            message.msg_hdr.msg_namelen = 0;
+                            message.msg_hdr.msg_flags = 0;
+                        }
+                        count += 1;
+                    }
+                }
+                // all of the provided messages are filled so the operation is armed again once more
+                // messages are freed
+                Err(err) if err.raw_os_error() == Some(libc::ENOBUFS) => {}
+                Err(err) => {
+                    if let Some(errno) = err.raw_os_error() {
+                        stats.recv().on_error(errno);
+                    }
+
+                    // the kernel doesn't support multishot `recvmsg` with provided buffers
+                    if matches!(err.raw_os_error(), Some(libc::EINVAL | libc::EOPNOTSUPP)) {
+                        return Err(err);
+                    }
+
+                    self.is_errored = true;
+                }
+            }
+        }
+
+        self.provided -= count;
+        self.buffer_id = self.buffer_id.wrapping_add(count as u16);
+
+        // The socket reports an error when an ICMP message is queued for a previously
+        // transmitted datagram so drain the error queue into the remaining entries. This can only
+        // be done once the operation has stopped and the kernel no longer owns the messages.
+        if self.is_errored && !self.is_armed {
+            self.is_errored = false;
+
+            if features::recverr::IS_SUPPORTED && count < entries.len() {
+                unsafe {
+                    // Safety: the operation has completed so the kernel won't select any more
+                    // buffers
+                    self.buffers.rewind(self.provided as u16);
+                }
+                self.provided = 0;
+
+                let mut error_events = rx::Events::default();
+                Message::recv(
+                    self.socket.as_raw_fd(),
+                    SocketType::ErrorQueue,
+                    &mut entries[count..],
+                    &mut error_events,
+                    stats,
+                );
+                count += error_events.take_count();
+            }
+        }
+
+        if count > 0 {
+            stats.recv().on_operation_ready(count);
+            let _ = events.on_complete(count);
+            return Ok(());
+        }
+
+        if !self.is_armed {
+            self.arm()?;
+        }
+
+        stats.recv().on_operation_pending();
+        events.blocked();
+
+        // * First iteration we need to clear the ring readiness since the queue was empty.
+        // * Second iteration we need to register the waker, assuming the readiness was cleared.
+        //   * If we got a `Ready` anyway, then clear the blocked status and have the caller try
+        //   again.
+        for i in 0..2 {
+            match self.ring.poll_read_ready(cx) {
+                Poll::Ready(guard) => {
+                    let mut guard = guard?;
+                    if i == 0 {
+                        guard.clear_ready();
+
+                        // a completion may have been posted before the readiness was cleared
+                        if !guard.get_inner().is_cq_empty() {
+                            events.take_blocked();
+                            return Ok(());
+                        }
+                    } else {
+                        events.take_blocked();
+                    }
+                }
+                Poll::Pending => {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        let mut in_flight = self.is_armed as u32;
+
+        // the messages can only be freed once the kernel is no longer writing to them
+        if self.ring.get_mut().cancel_all(&mut in_flight) {
+            unsafe {
+                // Safety: the fields aren't used after this point
+                ManuallyDrop::drop(&mut self.buffers);
+                ManuallyDrop::drop(&mut self.msghdr);
+                ManuallyDrop::drop(&mut self.storage);
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::io::tokio::tests::{runtime, runtime_with_builder, TestEndpoint};
+use s2n_quic_core::path::Handle as _;
+use std::net::ToSocketAddrs;
+
+/// Returns true if the environment doesn't allow creating io_uring instances with the required
+/// features
+fn is_unsupported(err: &io::Error) -> bool {
+    matches!(
+        err.raw_os_error(),
+        Some(libc::ENOSYS | libc::EPERM | libc::EINVAL)
+    )
+}
+
+async fn test<A: ToSocketAddrs>(
+    server_rx_addr: A,
+    server_tx_addr: Option<A>,
+    client_rx_addr: A,
+    client_tx_addr: Option<A>,
+) -> io::Result<()> {
+    let server = runtime(server_rx_addr, server_tx_addr).await?;
+    let client = runtime(client_rx_addr, client_tx_addr).await?;
+    exchange(server, client, 1_000).await
+}
+
+/// Sends `messages` packets from the client and waits for the server to echo all of them back
+async fn exchange(
+    (server_io, server_addr): (crate::io::tokio::Io, SocketAddress),
+    (client_io, client_addr): (crate::io::tokio::Io, SocketAddress),
+    messages: u32,
+) -> io::Result<()> {
+    let server_endpoint = {
+        let mut handle = PathHandle::from_remote_address(client_addr.into());
+        handle.local_address = server_addr.into();
+        TestEndpoint::<true>::new(handle)
+    };
+
+    let client_endpoint = {
+        let mut handle = PathHandle::from_remote_address(server_addr.into());
+        handle.local_address = client_addr.into();
+        TestEndpoint::<false>::with_messages(handle, messages)
+    };
+
+    let (server_task, actual_server_addr) = match Io::from(server_io).start(server_endpoint) {
+        Ok(value) => value,
+        Err(err) if is_unsupported(&err) => {
+            eprintln!("The current environment does not support io_uring; skipping");
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    assert_eq!(actual_server_addr, server_addr);
+
+    let (client_task, actual_client_addr) = Io::from(client_io).start(client_endpoint)?;
+    assert_eq!(actual_client_addr, client_addr);
+
+    tokio::time::timeout(core::time::Duration::from_secs(60), client_task).await??;
+
+    server_task.abort();
+
+    Ok(())
+}
+
+static IPV4_LOCALHOST: &str = "127.0.0.1:0";
+static IPV6_LOCALHOST: &str = "[::1]:0";
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_test() -> io::Result<()> {
+    test(IPV4_LOCALHOST, None, IPV4_LOCALHOST, None).await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_two_socket_test() -> io::Result<()> {
+    test(
+        IPV4_LOCALHOST,
+        Some(IPV4_LOCALHOST),
+        IPV4_LOCALHOST,
+        Some(IPV4_LOCALHOST),
+    )
+    .await
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ipv6_test() -> io::Result<()> {
+    let result = test(IPV6_LOCALHOST, None, IPV6_LOCALHOST, None).await;
+
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::AddrNotAvailable => {
+            eprintln!("The current environment does not support IPv6; skipping");
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(miri, ignore)]
+async fn ipv4_multi_thread_test() -> io::Result<()> {
+    test(IPV4_LOCALHOST, None, IPV4_LOCALHOST, None).await
+}
+
+/// Receives many more packets than there are messages in the ring so the messages are provided
+/// to the kernel multiple times
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn ring_wraparound_test() -> io::Result<()> {
+    let builder = || Builder::default().with_internal_recv_buffer_size(1 << 20);
+    let server = runtime_with_builder(builder()?, IPV4_LOCALHOST, None).await?;
+    let client = runtime_with_builder(builder()?, IPV4_LOCALHOST, None).await?;
+    exchange(server, client, 1_000).await
+}
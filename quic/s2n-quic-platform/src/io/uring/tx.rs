@@ -0,0 +1,209 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::ring::{Ring, Sqe};
+use crate::{
+    features::Gso,
+    message::mmsg::Message,
+    socket::{ring, stats, task::tx},
+    syscall::SocketEvents as _,
+};
+use core::{
+    future::Future,
+    task::{Context, Poll},
+};
+use s2n_quic_core::task::cooldown::Cooldown;
+use std::{io, net::UdpSocket, os::unix::io::AsRawFd};
+use tokio::io::unix::AsyncFd;
+
+/// The maximum number of `sendmsg` operations submitted in a single batch
+const MAX_BATCH_SIZE: u32 = 256;
+
+pub fn task(
+    socket: socket2::Socket,
+    consumer: ring::Consumer<Message>,
+    gso: Gso,
+    cooldown: Cooldown,
+    stats: stats::Sender,
+) -> io::Result<impl Future<Output = io::Result<()>>> {
+    let socket = Socket::new(socket.into())?;
+
+    Ok(async move {
+        let result = tx::Sender::new(consumer, socket, gso, cooldown, stats).await;
+        if let Some(err) = result {
+            Err(err)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Sends packets with a batch of `sendmsg` operations
+///
+/// Each operation points directly at a message in the ring, including any GSO control messages
+/// that were encoded by the endpoint. The messages aren't released back to the endpoint until
+/// every operation in the batch has completed.
+pub struct Socket {
+    ring: AsyncFd<Ring>,
+    /// The number of submitted operations that haven't completed
+    in_flight: u32,
+    /// The number of operations in the current batch that completed successfully
+    sent: usize,
+    /// The errors returned by operations in the current batch
+    errors: Vec<io::Error>,
+    socket: UdpSocket,
+}
+
+impl Socket {
+    fn new(socket: UdpSocket) -> io::Result<Self> {
+        // io_uring waits for the socket to be ready, while a non-blocking socket would complete
+        // the operation with `EAGAIN` instead
+        socket.set_nonblocking(false)?;
+
+        let ring = Ring::new(MAX_BATCH_SIZE, MAX_BATCH_SIZE * 2)?;
+
+        Ok(Self {
+            ring: AsyncFd::new(ring)?,
+            in_flight: 0,
+            sent: 0,
+            errors: vec![],
+            socket,
+        })
+    }
+
+    /// Reports the results of the current batch once all of its operations have completed
+    ///
+    /// Returns `false` if operations are still in flight.
+    #[inline]
+    fn complete(&mut self, events: &mut tx::Events, stats: &stats::Sender) -> bool {
+        let ring = self.ring.get_mut();
+
+        while let Some(cqe) = ring.pop() {
+            self.in_flight -= 1;
+            match cqe.result() {
+                Ok(_) => self.sent += 1,
+                Err(err) => self.errors.push(err),
+            }
+        }
+
+        if self.in_flight > 0 {
+            return false;
+        }
+
+        let sent = core::mem::take(&mut self.sent);
+        if sent > 0 {
+            stats.send().on_operation_ready(sent);
+            let _ = events.on_complete(sent);
+        }
+
+        for err in self.errors.drain(..) {
+            if let Some(errno) = err.raw_os_error() {
+                stats.send().on_error(errno);
+            }
+
+            match err.kind() {
+                // the operations in the batch can't be retried individually so consider the
+                // packet dropped
+                io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => {
+                    let _ = events.on_complete(1);
+                }
+                _ => {
+                    let _ = events.on_error(err);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl tx::Socket<Message> for Socket {
+    type Error = io::Error;
+
+    #[inline]
+    fn send(
+        &mut self,
+        cx: &mut Context,
+        entries: &mut [Message],
+        events: &mut tx::Events,
+        stats: &stats::Sender,
+    ) -> io::Result<()> {
+        if self.in_flight == 0 {
+            let fd = self.socket.as_raw_fd();
+            let ring = self.ring.get_mut();
+            let len = entries.len().min(ring.sq_entries() as usize);
+
+            for (idx, entry) in entries[..len].iter_mut().enumerate() {
+                let sqe = Sqe::sendmsg(fd, &entry.msg_hdr, idx as _);
+
+                // Safety: the messages aren't released until all of the operations complete
+                let is_pushed = unsafe { ring.push(sqe) };
+                debug_assert!(is_pushed);
+            }
+
+            self.in_flight = len as _;
+        }
+
+        // submit any entries that haven't been consumed by the kernel
+        match self.ring.get_mut().submit() {
+            Ok(_) => {}
+            // the kernel is busy flushing completions so try again later
+            Err(err)
+                if matches!(
+                    err.raw_os_error(),
+                    Some(libc::EBUSY | libc::EAGAIN | libc::EINTR)
+                ) => {}
+            Err(err) => return Err(err),
+        }
+
+        if self.complete(events, stats) {
+            return Ok(());
+        }
+
+        stats.send().on_operation_pending();
+        events.blocked();
+
+        // * First iteration we need to clear the ring readiness since the batch hasn't completed.
+        // * Second iteration we need to register the waker, assuming the readiness was cleared.
+        //   * If we got a `Ready` anyway, then clear the blocked status and have the caller try
+        //   again.
+        for i in 0..2 {
+            match self.ring.poll_read_ready(cx) {
+                Poll::Ready(guard) => {
+                    let mut guard = guard?;
+                    if i == 0 {
+                        guard.clear_ready();
+
+                        // a completion may have been posted before the readiness was cleared
+                        if !guard.get_inner().is_cq_empty() {
+                            events.take_blocked();
+                            return Ok(());
+                        }
+                    } else {
+                        events.take_blocked();
+                    }
+                }
+                Poll::Pending => {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        let ring = self.ring.get_mut();
+
+        // the operations reference the message storage so they need to complete before it's freed
+        if !ring.cancel_all(&mut self.in_flight) {
+            while self.in_flight > 0 && ring.submit_and_wait(1).is_ok() {
+                while ring.pop().is_some() {
+                    self.in_flight -= 1;
+                }
+            }
+        }
+    }
+}
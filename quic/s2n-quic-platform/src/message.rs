@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::{alloc::Layout, ptr::NonNull};
-use s2n_quic_core::{inet::datagram, io::tx, path};
+use s2n_quic_core::{
+    inet::{datagram, icmp},
+    io::{rx, tx},
+    path,
+};
 
 #[cfg(s2n_quic_platform_cmsg)]
 pub mod cmsg;
@@ -80,6 +84,7 @@ pub trait Message: 'static + Copy {
     const SUPPORTS_GSO: bool;
     const SUPPORTS_ECN: bool;
     const SUPPORTS_FLOW_LABELS: bool;
+    const SUPPORTS_TXTIME: bool = false;
 
     /// Allocates `entries` messages, each with `payload_len` bytes
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> Storage;
@@ -115,6 +120,11 @@ pub trait Message: 'static + Copy {
         panic!("cannot use GSO on the current platform");
     }
 
+    /// Sets the `SO_TXTIME` departure time for the message, in nanoseconds
+    fn set_departure_time(&mut self, _departure_time: u64) {
+        panic!("cannot use SO_TXTIME on the current platform");
+    }
+
     /// Resets the message for future use
     ///
     /// # Safety
@@ -141,18 +151,43 @@ pub struct RxMessage<'a, Handle: Copy> {
     pub segment_size: usize,
     /// The full payload of the message
     pub payload: &'a mut [u8],
+    /// The ICMP error for the message, if it was read from the socket error queue
+    ///
+    /// In this case, the payload contains the datagram that triggered the error.
+    pub icmp_error: Option<icmp::Error>,
 }
 
 impl<Handle: Copy> RxMessage<'_, Handle> {
     #[inline]
     pub fn for_each<F: FnMut(datagram::Header<Handle>, &mut [u8])>(self, mut on_packet: F) {
+        self.for_each_entry(|entry| {
+            if let rx::Entry::Packet { header, payload } = entry {
+                on_packet(header, payload);
+            }
+        })
+    }
+
+    #[inline]
+    pub fn for_each_entry<F: FnMut(rx::Entry<Handle>)>(self, mut on_entry: F) {
+        if let Some(error) = self.icmp_error {
+            on_entry(rx::Entry::Error {
+                path: self.header.path,
+                error,
+                payload: self.payload,
+            });
+            return;
+        }
+
         // `chunks_mut` doesn't know what to do with zero-sized segments so return early
         if self.segment_size == 0 {
             return;
         }
 
         for segment in self.payload.chunks_mut(self.segment_size) {
-            on_packet(self.header, segment);
+            on_entry(rx::Entry::Packet {
+                header: self.header,
+                payload: segment,
+            });
         }
     }
 }
@@ -180,6 +215,7 @@ mod tests {
                     header: datagram::Header { path, ecn },
                     segment_size,
                     payload: &mut payload,
+                    icmp_error: None,
                 };
 
                 rx_message.for_each(|header, segment| {
@@ -95,6 +95,13 @@ unsafe fn collect_item(data: &mut AncillaryData, cmsg: &cmsghdr, value: &[u8]) {
                 decode_error!("invalid gro value");
             }
         }
+        (level, ty) if features::recverr::is_match(level, ty) => {
+            if let Some(error) = features::recverr::decode(value) {
+                data.icmp_error = Some(error);
+            } else {
+                decode_error!("unsupported extended error");
+            }
+        }
         _ => {
             decode_error!("unexpected cmsghdr");
         }
@@ -59,6 +59,17 @@ pub trait Encoder {
         }
     }
 
+    /// Encodes the SO_TXTIME departure time, in nanoseconds, into the cmsg encoder
+    #[inline]
+    fn encode_txtime(&mut self, departure_time: u64) -> Result<usize, Error> {
+        if let (Some(level), Some(ty)) = (features::txtime::LEVEL, features::txtime::TYPE) {
+            let departure_time: features::txtime::Cmsg = departure_time.to_ne_bytes();
+            self.encode_cmsg(level, ty, departure_time)
+        } else {
+            panic!("platform does not support SO_TXTIME");
+        }
+    }
+
     #[inline]
     fn encode_local_address(&mut self, address: &SocketAddress) -> Result<usize, Error> {
         use s2n_quic_core::inet::Unspecified;
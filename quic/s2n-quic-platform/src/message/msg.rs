@@ -28,12 +28,22 @@ pub use ext::Ext;
 pub use handle::Handle;
 pub use libc::msghdr as Message;
 
+/// The number of bytes reserved in front of each payload
+///
+/// The io_uring receiver hands each free slot in the ring to the kernel, which writes a header and
+/// the address and control message regions in front of the payload.
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub(crate) const PAYLOAD_PREFIX_LEN: usize = crate::io::uring::PAYLOAD_PREFIX_LEN;
+#[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+pub(crate) const PAYLOAD_PREFIX_LEN: usize = 0;
+
 impl MessageTrait for msghdr {
     type Handle = Handle;
 
     const SUPPORTS_GSO: bool = features::gso::IS_SUPPORTED;
     const SUPPORTS_ECN: bool = features::tos::IS_SUPPORTED;
     const SUPPORTS_FLOW_LABELS: bool = true;
+    const SUPPORTS_TXTIME: bool = features::txtime::IS_SUPPORTED;
 
     #[inline]
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> super::Storage {
@@ -61,6 +71,11 @@ impl MessageTrait for msghdr {
         self.cmsg_encoder().encode_gso(size as _).unwrap();
     }
 
+    #[inline]
+    fn set_departure_time(&mut self, departure_time: u64) {
+        self.cmsg_encoder().encode_txtime(departure_time).unwrap();
+    }
+
     #[inline]
     unsafe fn reset(&mut self, mtu: usize) {
         // reset the payload
@@ -139,6 +154,13 @@ impl MessageTrait for msghdr {
 
         let (mut header, cmsg) = self.header()?;
 
+        // Messages from the error queue contain a datagram that was previously transmitted by
+        // this endpoint. If the error wasn't recognized, the message is dropped rather than being
+        // processed as a packet from the peer.
+        if features::recverr::is_error_queue(self.msg_flags) && cmsg.icmp_error.is_none() {
+            return None;
+        }
+
         // only copy the port if we are told the IP address
         if !header.path.local_address.ip().is_unspecified() {
             header.path.local_address.set_port(local_address.port());
@@ -158,6 +180,7 @@ impl MessageTrait for msghdr {
             header,
             segment_size,
             payload,
+            icmp_error: cmsg.icmp_error,
         };
 
         Some(message)
@@ -199,7 +222,7 @@ impl MessageTrait for msghdr {
 }
 
 /// Allocates a region of memory holding `entries` number of `T` messages, each with `payload_len`
-/// payloads.
+/// payloads preceded by [`PAYLOAD_PREFIX_LEN`] bytes.
 ///
 /// # Safety
 ///
@@ -224,23 +247,24 @@ pub(super) unsafe fn alloc<T: Copy + Sized, F: Fn(&mut T) -> &mut msghdr>(
         // calculate each of the pointers we need to set up a message
         let mut entry_ptr = ptr.add(entry_offset) as *mut T;
         let mut header_ptr = ptr.add(header_offset) as *mut Header;
-        let mut payload_ptr = ptr.add(payload_offset);
+        let mut slot_ptr = ptr.add(payload_offset);
 
         for _ in 0..entries {
             // for each message update all of the pointers to the correct locations
 
             let entry = on_entry(&mut *entry_ptr);
+            let payload_ptr = slot_ptr.add(PAYLOAD_PREFIX_LEN);
             (*header_ptr).update(entry, payload_ptr, payload_len);
 
             // increment the pointers for the next iteration
             entry_ptr = entry_ptr.add(1);
             header_ptr = header_ptr.add(1);
-            payload_ptr = payload_ptr.add(payload_len as _);
+            slot_ptr = payload_ptr.add(payload_len as _);
 
             // make sure the pointers are within the bounds of the allocation
             storage.check_bounds(entry_ptr);
             storage.check_bounds(header_ptr);
-            storage.check_bounds(payload_ptr);
+            storage.check_bounds(slot_ptr);
         }
 
         // replicate the primary messages into the secondary region
@@ -259,7 +283,7 @@ pub(super) unsafe fn alloc<T: Copy + Sized, F: Fn(&mut T) -> &mut msghdr>(
 /// struct Storage {
 ///    cursor: Cursor,
 ///    headers: [Header; entries],
-///    payloads: [[u8; payload_len]; entries],
+///    payloads: [([u8; PAYLOAD_PREFIX_LEN], [u8; payload_len]); entries],
 ///    entries: [T; entries * 2],
 /// }
 /// ```
@@ -270,7 +294,9 @@ fn layout<T: Copy + Sized>(
 ) -> (Layout, usize, usize, usize) {
     let cursor = Layout::array::<u8>(offset).unwrap();
     let headers = Layout::array::<Header>(entries as _).unwrap();
-    let payloads = Layout::array::<u8>(entries as usize * payload_len as usize).unwrap();
+    let payloads =
+        Layout::array::<u8>(entries as usize * (PAYLOAD_PREFIX_LEN + payload_len as usize))
+            .unwrap();
     // double the number of entries we allocate to support the primary/secondary regions
     let entries = Layout::array::<T>((entries * 2) as usize).unwrap();
     let (layout, entry_offset) = cursor.extend(entries).unwrap();
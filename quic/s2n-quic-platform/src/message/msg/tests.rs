@@ -75,9 +75,10 @@ fn handle_get_set_test() {
         .with_generator((
             produce::<Handle>(),
             1..=crate::features::gso::MaxSegments::MAX.into(),
+            produce::<u64>(),
         ))
         .cloned()
-        .for_each(|(handle, segment_size)| {
+        .for_each(|(handle, segment_size, departure_time)| {
             test_msghdr(|message| {
                 handle.update_msg_hdr(message);
 
@@ -85,6 +86,10 @@ fn handle_get_set_test() {
                     message.set_segment_size(segment_size);
                 }
 
+                if features::txtime::IS_SUPPORTED {
+                    message.set_departure_time(departure_time);
+                }
+
                 let (header, _cmsg) = message.header().unwrap();
 
                 assert_eq!(header.path.remote_address, handle.remote_address);
@@ -97,6 +102,16 @@ fn handle_get_set_test() {
                     {
                         assert_eq!(header.path.local_address.ip(), handle.local_address.ip());
                     }
+
+                    if features::txtime::IS_SUPPORTED {
+                        let txtime = unsafe { cmsg::decode::Iter::from_msghdr(message) }
+                            .find(|(cmsg, _)| {
+                                Some(cmsg.cmsg_level) == features::txtime::LEVEL
+                                    && Some(cmsg.cmsg_type) == features::txtime::TYPE
+                            })
+                            .map(|(_, value)| value);
+                        assert_eq!(txtime, Some(&departure_time.to_ne_bytes()[..]));
+                    }
                 }
 
                 // reset the message and ensure everything is zeroed
@@ -109,3 +124,71 @@ fn handle_get_set_test() {
             });
         });
 }
+
+#[cfg(all(s2n_quic_platform_recverr, not(kani)))]
+mod recverr {
+    use super::*;
+    use libc::{
+        sock_extended_err, EHOSTUNREACH, EMSGSIZE, IPPROTO_IP, IP_RECVERR, MSG_ERRQUEUE,
+        SO_EE_ORIGIN_ICMP, SO_EE_ORIGIN_LOCAL,
+    };
+    use s2n_quic_core::{inet::icmp, io::rx};
+
+    /// Reads a message from the error queue with the given extended error and returns the
+    /// entries that would be passed to the endpoint
+    fn entries(errno: i32, origin: u8, ty: u8, code: u8) -> Vec<Option<icmp::Error>> {
+        let mut entries = vec![];
+
+        test_msghdr(|message| {
+            let remote_address: SocketAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+            message.set_remote_address(&remote_address);
+            message.msg_flags = MSG_ERRQUEUE;
+            message.msg_controllen = 0;
+
+            let mut error = unsafe { zeroed::<sock_extended_err>() };
+            error.ee_errno = errno as _;
+            error.ee_origin = origin;
+            error.ee_type = ty;
+            error.ee_code = code;
+            message
+                .cmsg_encoder()
+                .encode_cmsg(IPPROTO_IP, IP_RECVERR, error)
+                .unwrap();
+
+            if let Some(message) = message.rx_read(&Default::default()) {
+                message.for_each_entry(|entry| {
+                    entries.push(match entry {
+                        rx::Entry::Packet { .. } => None,
+                        rx::Entry::Error { error, .. } => Some(error),
+                    });
+                });
+            }
+        });
+
+        entries
+    }
+
+    #[test]
+    fn recognized_error_test() {
+        // Destination Unreachable - Port Unreachable
+        let entries = entries(libc::ECONNREFUSED, SO_EE_ORIGIN_ICMP, 3, 3);
+        assert_eq!(entries, [Some(icmp::Error::PortUnreachable)]);
+    }
+
+    #[test]
+    fn unrecognized_error_test() {
+        for (errno, origin, ty, code) in [
+            // local MTU errors for oversized probes
+            (EMSGSIZE, SO_EE_ORIGIN_LOCAL, 0, 0),
+            // Destination Unreachable - Host Unreachable
+            (EHOSTUNREACH, SO_EE_ORIGIN_ICMP, 3, 1),
+            // Destination Unreachable - Communication Administratively Prohibited
+            (EHOSTUNREACH, SO_EE_ORIGIN_ICMP, 3, 13),
+            // Time Exceeded
+            (EHOSTUNREACH, SO_EE_ORIGIN_ICMP, 11, 0),
+        ] {
+            // the quoted datagram should never be passed to the endpoint as a packet
+            assert!(entries(errno, origin, ty, code).is_empty());
+        }
+    }
+}
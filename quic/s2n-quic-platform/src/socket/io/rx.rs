@@ -114,6 +114,16 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
 
     #[inline]
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        self.for_each_entry(|entry| {
+            // ICMP errors are dropped for callers that are only interested in packets
+            if let rx::Entry::Packet { header, payload } = entry {
+                on_packet(header, payload);
+            }
+        })
+    }
+
+    #[inline]
+    fn for_each_entry<F: FnMut(rx::Entry<Self::Handle>)>(&mut self, mut on_entry: F) {
         for channel in self.channels.iter_mut() {
             // one last effort to acquire items if some were received since we last polled
             let len = channel.acquire(u32::MAX);
@@ -122,12 +132,12 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
             debug_assert_eq!(data.len(), len as usize);
 
             for message in data {
-                // call the `on_packet` function for each message received
+                // call the `on_entry` function for each message received
                 //
                 // NOTE: it's important that we process all of the messages in the queue as the
                 //       channel is completely drained here.
                 if let Some(message) = message.rx_read(self.local_address) {
-                    message.for_each(&mut on_packet);
+                    message.for_each_entry(&mut on_entry);
                 }
 
                 unsafe {
@@ -1,14 +1,22 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{features::Gso, message::Message, socket::ring::Producer};
-use core::task::{Context, Poll};
+use crate::{
+    features::{self, Gso},
+    message::Message,
+    socket::ring::Producer,
+};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_quic_core::{
     event,
     inet::ExplicitCongestionNotification,
     io::tx,
     path::{Handle as _, MaxMtu},
     task::waker,
+    time::Timestamp,
 };
 
 /// Structure for sending messages to producer channels
@@ -16,6 +24,8 @@ pub struct Tx<T: Message> {
     channels: Vec<Producer<T>>,
     gso: Gso,
     max_mtu: usize,
+    pacing_horizon: Duration,
+    clock_epoch: Duration,
     is_full: bool,
 }
 
@@ -26,9 +36,24 @@ impl<T: Message> Tx<T> {
             channels,
             gso,
             max_mtu: max_mtu.into(),
+            pacing_horizon: Duration::ZERO,
+            clock_epoch: Duration::ZERO,
             is_full: true,
         }
     }
+
+    /// Offloads pacing to the socket by setting the `SO_TXTIME` departure time on each message
+    ///
+    /// The socket must be configured with the `SO_TXTIME` option before any messages are sent.
+    /// `clock_epoch` is the value of [`features::txtime::clock_epoch`] for the endpoint clock,
+    /// which is used to convert the departure times of the messages.
+    #[inline]
+    pub fn with_txtime(mut self, clock_epoch: Duration) -> Self {
+        assert!(T::SUPPORTS_TXTIME, "platform does not support SO_TXTIME");
+        self.pacing_horizon = features::txtime::HORIZON;
+        self.clock_epoch = clock_epoch;
+        self
+    }
 }
 
 impl<T: Message> tx::Tx for Tx<T> {
@@ -131,6 +156,8 @@ impl<T: Message> tx::Tx for Tx<T> {
             gso_segment: None,
             max_segments,
             max_mtu: this.max_mtu,
+            pacing_horizon: this.pacing_horizon,
+            clock_epoch: this.clock_epoch,
             capacity,
             is_full: &mut this.is_full,
         };
@@ -159,6 +186,11 @@ pub struct GsoSegment<Handle> {
     /// This is used to determine if future messages should be included in this payload or need a
     /// separate packet.
     ecn: ExplicitCongestionNotification,
+    /// The departure time for the current GSO segment being written.
+    ///
+    /// All of the segments in a GSO payload are sent at the same time, so future messages with a
+    /// different departure time need a separate packet.
+    departure_time: Option<Timestamp>,
     /// The number of segments that have been written
     count: usize,
     /// The size of each segment.
@@ -189,6 +221,12 @@ pub struct TxQueue<'a, T: Message> {
     max_segments: usize,
     /// The maximum MTU for any given packet
     max_mtu: usize,
+    /// How far ahead of their departure time messages can be queued
+    ///
+    /// This is non-zero if pacing is offloaded to the socket with `SO_TXTIME`.
+    pacing_horizon: Duration,
+    /// The `CLOCK_MONOTONIC` time at which the endpoint clock read zero
+    clock_epoch: Duration,
     /// The maximum number of packets that can be sent in the current iteration
     capacity: usize,
     /// Used to track if we have filled up the producer queue and waiting on free slots to be
@@ -211,6 +249,7 @@ impl<T: Message> TxQueue<'_, T> {
         }
 
         let max_segments = self.max_segments;
+        let is_paced = !self.pacing_horizon.is_zero();
 
         let (prev_message, gso) = if let Some(gso) = self.gso_message() {
             gso
@@ -227,7 +266,8 @@ impl<T: Message> TxQueue<'_, T> {
         // GSO payload as the previous message
         let can_gso = message.can_gso(gso.size, gso.count)
             && message.path_handle().strict_eq(&gso.handle)
-            && message.ecn() == gso.ecn;
+            && message.ecn() == gso.ecn
+            && (!is_paced || message.departure_time() == gso.departure_time);
 
         // if we can't use GSO then flush the current message
         if !can_gso {
@@ -425,16 +465,32 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
         let handle = *message.path_handle();
         let ecn = message.ecn();
         let can_gso = message.can_gso(self.max_mtu, 0);
+        // only query the departure time if pacing is offloaded to the socket
+        let departure_time = if self.pacing_horizon.is_zero() {
+            None
+        } else {
+            message.departure_time()
+        };
 
         // write the message to the entry
         let payload_len = entry.tx_write(message)?;
 
+        // messages without a departure time are sent immediately, so it's only needed for the
+        // ones that the pacer has scheduled in the future
+        if let Some(departure_time) = departure_time {
+            entry.set_departure_time(features::txtime::departure_time(
+                self.clock_epoch,
+                departure_time,
+            ));
+        }
+
         // if GSO is supported and we are allowed to have additional segments, store the GSO state
         // for another potential message to be written later
         if T::SUPPORTS_GSO && self.max_segments > 1 && can_gso {
             self.gso_segment = Some(GsoSegment {
                 handle,
                 ecn,
+                departure_time,
                 count: 1,
                 size: payload_len,
             });
@@ -462,6 +518,11 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
     fn capacity(&self) -> usize {
         self.capacity
     }
+
+    #[inline]
+    fn pacing_horizon(&self) -> Duration {
+        self.pacing_horizon
+    }
 }
 
 impl<T: Message> Drop for TxQueue<'_, T> {
@@ -473,3 +534,147 @@ impl<T: Message> Drop for TxQueue<'_, T> {
         self.flush_channel();
     }
 }
+
+#[cfg(all(test, s2n_quic_platform_txtime, s2n_quic_platform_socket_msg))]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{cmsg, msg},
+        socket::ring,
+    };
+    use s2n_quic_core::{
+        inet::SocketAddress,
+        io::tx::{Queue as _, Tx as _},
+        path::RemoteAddress,
+        time::{clock::testing::Clock, Clock as _},
+    };
+
+    const PAYLOAD_LEN: usize = 100;
+    const CLOCK_EPOCH: Duration = Duration::from_secs(100);
+
+    /// A message that was scheduled by the pacer
+    struct Paced {
+        handle: msg::Handle,
+        departure_time: Option<Timestamp>,
+    }
+
+    impl tx::Message for Paced {
+        type Handle = msg::Handle;
+
+        fn path_handle(&self) -> &Self::Handle {
+            &self.handle
+        }
+
+        fn ecn(&mut self) -> ExplicitCongestionNotification {
+            Default::default()
+        }
+
+        fn delay(&mut self) -> Duration {
+            Default::default()
+        }
+
+        fn departure_time(&mut self) -> Option<Timestamp> {
+            self.departure_time
+        }
+
+        fn ipv6_flow_label(&mut self) -> u32 {
+            0
+        }
+
+        fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+            segment_len >= PAYLOAD_LEN
+        }
+
+        fn write_payload(
+            &mut self,
+            mut buffer: tx::PayloadBuffer,
+            _gso_offset: usize,
+        ) -> Result<usize, tx::Error> {
+            buffer.write(&[1; PAYLOAD_LEN])
+        }
+    }
+
+    /// Pushes messages with the given departure times and returns the payload length and
+    /// `SO_TXTIME` value of each packet released to the socket
+    fn send(departure_times: &[Option<Timestamp>]) -> Vec<(usize, Option<u64>)> {
+        let max_mtu = MaxMtu::default();
+        let (producer, mut consumer) = ring::pair::<msg::Message>(16, usize::from(max_mtu) as _);
+        let mut tx = Tx::new(vec![producer], Gso::default(), max_mtu).with_txtime(CLOCK_EPOCH);
+        let handle =
+            msg::Handle::from_remote_address(RemoteAddress::from(SocketAddress::default()));
+
+        tx.queue(|queue| {
+            for &departure_time in departure_times {
+                queue
+                    .push(Paced {
+                        handle,
+                        departure_time,
+                    })
+                    .unwrap();
+            }
+        });
+
+        consumer.acquire(u32::MAX);
+        consumer
+            .data()
+            .iter()
+            .map(|message| {
+                // Safety: the control messages were written by the TX queue
+                let txtime = unsafe { cmsg::decode::Iter::from_msghdr(message) }
+                    .find(|(cmsg, _)| {
+                        Some(cmsg.cmsg_level) == features::txtime::LEVEL
+                            && Some(cmsg.cmsg_type) == features::txtime::TYPE
+                    })
+                    .map(|(_, value)| u64::from_ne_bytes(value.try_into().unwrap()));
+
+                (message.payload_len(), txtime)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn departure_time_test() {
+        let now = Clock::default().get_time();
+        let later = now + Duration::from_millis(1);
+
+        let packets = send(&[None, Some(later)]);
+
+        // messages that aren't paced don't carry a departure time
+        let departure_times: Vec<_> = packets.iter().map(|(_, txtime)| *txtime).collect();
+        assert_eq!(
+            departure_times,
+            [
+                None,
+                Some(features::txtime::departure_time(CLOCK_EPOCH, later))
+            ]
+        );
+    }
+
+    #[test]
+    fn gso_departure_time_test() {
+        if !msg::Message::SUPPORTS_GSO || Gso::default().max_segments() == 1 {
+            return;
+        }
+
+        let now = Clock::default().get_time();
+        let later = now + Duration::from_millis(1);
+        let now_txtime = features::txtime::departure_time(CLOCK_EPOCH, now);
+        let later_txtime = features::txtime::departure_time(CLOCK_EPOCH, later);
+
+        // messages with the same departure time share a GSO payload
+        assert_eq!(
+            send(&[Some(now), Some(now), Some(now)]),
+            [(PAYLOAD_LEN * 3, Some(now_txtime))]
+        );
+
+        // the batch is split each time the departure time changes
+        assert_eq!(
+            send(&[Some(now), Some(now), Some(later), None, None]),
+            [
+                (PAYLOAD_LEN * 2, Some(now_txtime)),
+                (PAYLOAD_LEN, Some(later_txtime)),
+                (PAYLOAD_LEN * 2, None),
+            ]
+        );
+    }
+}
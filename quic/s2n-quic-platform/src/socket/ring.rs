@@ -215,7 +215,6 @@ pub struct Producer<T: Message> {
     cursor: Cursor<T>,
     wakers: atomic_waker::Handle,
     drop_waker: atomic_waker::Handle,
-    #[allow(dead_code)]
     storage: Arc<message::Storage>,
 }
 
@@ -314,6 +313,15 @@ impl<T: Message> Producer<T> {
     pub fn is_open(&self) -> bool {
         self.wakers.is_open()
     }
+
+    /// Returns the memory backing the messages
+    ///
+    /// Holding onto the storage keeps the payloads valid after both halves of the ring are dropped.
+    #[inline]
+    #[cfg_attr(not(all(feature = "io-uring", target_os = "linux")), allow(dead_code))]
+    pub(crate) fn storage(&self) -> &Arc<message::Storage> {
+        &self.storage
+    }
 }
 
 /// Copies messages from the primary to secondary memory regions
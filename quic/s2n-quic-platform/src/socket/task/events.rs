@@ -89,6 +89,7 @@ impl crate::syscall::SocketEvents for TxEvents {
 pub struct RxEvents {
     count: usize,
     is_blocked: bool,
+    is_errored: bool,
 }
 
 impl RxEvents {
@@ -115,6 +116,20 @@ impl RxEvents {
     pub fn take_count(&mut self) -> usize {
         core::mem::take(&mut self.count)
     }
+
+    /// Returns the number of messages received since the count was last reset
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns if the socket reported an error and resets the value
+    ///
+    /// This typically means an ICMP error was queued for a previously transmitted datagram.
+    #[inline]
+    pub fn take_errored(&mut self) -> bool {
+        core::mem::take(&mut self.is_errored)
+    }
 }
 
 impl crate::syscall::SocketEvents for RxEvents {
@@ -141,6 +156,8 @@ impl crate::syscall::SocketEvents for RxEvents {
                 ControlFlow::Break(())
             }
             _ => {
+                // record the error so the task can check the socket error queue
+                self.is_errored = true;
                 // ignore all other errors and have the task try again
                 ControlFlow::Break(())
             }
@@ -28,9 +28,12 @@ pub trait Socket<T: Message> {
 }
 
 pub struct Sender<T: Message, S: Socket<T>> {
-    ring: Consumer<T>,
     /// Implementation of a socket that transmits filled slots in the ring buffer
+    ///
+    /// This is declared before the ring so it's dropped first, since some implementations may
+    /// still reference the ring messages while operations are in flight.
     tx: S,
+    ring: Consumer<T>,
     events: Events,
     ring_cooldown: Cooldown,
     io_cooldown: Cooldown,
@@ -31,6 +31,8 @@ pub mod msg;
 pub enum SocketType {
     Blocking,
     NonBlocking,
+    /// Reads ICMP errors from the socket error queue without blocking
+    ErrorQueue,
 }
 
 pub trait SocketEvents {
@@ -245,6 +247,66 @@ pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Configures the socket to queue ICMP errors for previously transmitted datagrams
+///
+/// The errors are read from the socket error queue with [`SocketType::ErrorQueue`].
+pub fn configure_recverr(rx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V4 {
+            success |= libc!(setsockopt(
+                rx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V6 {
+            success |= libc!(setsockopt(
+                rx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+    }
+
+    success
+}
+
+pub fn configure_txtime(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    if let (Some((level, ty)), Some(config)) = (
+        crate::features::txtime::SOCKOPT,
+        crate::features::txtime::CONFIG,
+    ) {
+        use std::os::unix::io::AsRawFd;
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            level as _,
+            ty as _,
+            &config as *const _ as _,
+            core::mem::size_of_val(&config) as _
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
 pub fn configure_gro(rx_socket: &Socket) -> bool {
     let mut success = false;
 
@@ -127,6 +127,7 @@ pub fn recv<Sock: AsRawFd, E: SocketEvents>(
     let flags = match socket_type {
         SocketType::Blocking => libc::MSG_WAITFORONE,
         SocketType::NonBlocking => libc::MSG_DONTWAIT,
+        SocketType::ErrorQueue => libc::MSG_DONTWAIT | crate::features::recverr::RECV_FLAGS,
     };
 
     // some platforms have a mismatch in types for the flag values and the actual parameter so cast
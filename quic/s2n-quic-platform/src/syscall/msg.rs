@@ -117,6 +117,7 @@ pub fn recv<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
     let mut flags = match socket_type {
         SocketType::Blocking => Default::default(),
         SocketType::NonBlocking => libc::MSG_DONTWAIT,
+        SocketType::ErrorQueue => libc::MSG_DONTWAIT | crate::features::recverr::RECV_FLAGS,
     };
 
     for packet in packets {
@@ -87,26 +87,42 @@ impl tls::Endpoint for Client {
         let rustls_server_name = rustls::pki_types::ServerName::try_from(server_name.to_string())
             .expect("invalid server name");
 
-        let session = rustls::quic::ClientConnection::new(
-            self.config.clone(),
-            crate::QUIC_VERSION,
-            rustls_server_name,
-            transport_parameters,
-        )
-        .expect("could not create rustls client session");
-
-        Session::new(session.into(), Some(server_name))
+        let config = self.config.clone();
+        let new_connection = move |version| {
+            rustls::quic::ClientConnection::new(
+                config.clone(),
+                version,
+                rustls_server_name.clone(),
+                transport_parameters.clone(),
+            )
+            .expect("could not create rustls client session")
+            .into()
+        };
+
+        let session = new_connection(crate::QUIC_VERSION);
+
+        // Early data requires a session ticket from the server, which is sent after the handshake
+        let receive_session_ticket = self.config.enable_early_data;
+
+        Session::new(session, Some(server_name))
+            .with_version_change(new_connection)
+            .with_session_ticket(receive_session_ticket)
     }
 
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_zero_rtt(&self) -> bool {
+        self.config.enable_early_data
+    }
 }
 
 pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    early_data: bool,
 }
 
 impl Default for Builder {
@@ -121,6 +137,7 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            early_data: false,
         }
     }
 
@@ -157,6 +174,16 @@ impl Builder {
         Ok(self)
     }
 
+    /// Enables sending application data in 0-RTT packets when resuming a session
+    ///
+    /// Session tickets are stored in memory and shared by all of the connections created from
+    /// the same client. The transport parameters the server sent on the original connection
+    /// are remembered with the ticket and used to limit the early data.
+    pub fn with_early_data(mut self, enabled: bool) -> Result<Self, Error> {
+        self.early_data = enabled;
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         // TODO load system root store?
         if self.cert_store.is_empty() {
@@ -173,6 +200,7 @@ impl Builder {
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
+        config.enable_early_data = self.early_data;
 
         if let Some(key_log) = self.key_log {
             config.key_log = key_log;
@@ -38,9 +38,20 @@ pub use server::Server;
 //# Clients MUST NOT offer TLS versions older than 1.3.
 static PROTOCOL_VERSIONS: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS13];
 
-/// The supported version of quic
+/// The version of quic sessions are created with
 const QUIC_VERSION: rustls::quic::Version = rustls::quic::Version::V1;
 
+/// Maps a QUIC version to the rustls equivalent
+fn quic_version(version: s2n_quic_core::version::Version) -> Option<rustls::quic::Version> {
+    use s2n_quic_core::version::{VERSION_1, VERSION_2};
+
+    match version {
+        VERSION_1 => Some(rustls::quic::Version::V1),
+        VERSION_2 => Some(rustls::quic::Version::V2),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,6 +80,34 @@ mod tests {
         pair.finish();
     }
 
+    #[test]
+    fn client_server_version_2_test() {
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::with_version(
+            &mut server,
+            &mut client,
+            "localhost".into(),
+            s2n_quic_core::version::VERSION_2,
+        );
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+    }
+
     #[test]
     fn client_server_der_test() {
         let mut client = client::Builder::new()
@@ -74,14 +74,20 @@ impl tls::Endpoint for Server {
         //# Endpoints MUST send the quic_transport_parameters extension;
         let transport_parameters = transport_parameters.encode_to_vec();
 
-        let session = rustls::quic::ServerConnection::new(
-            self.config.clone(),
-            crate::QUIC_VERSION,
-            transport_parameters,
-        )
-        .expect("could not create rustls server session");
+        let config = self.config.clone();
+        let new_connection = move |version| {
+            rustls::quic::ServerConnection::new(
+                config.clone(),
+                version,
+                transport_parameters.clone(),
+            )
+            .expect("could not create rustls server session")
+            .into()
+        };
+
+        let session = new_connection(crate::QUIC_VERSION);
 
-        Session::new(session.into(), None)
+        Session::new(session, None).with_version_change(new_connection)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -102,6 +108,7 @@ pub struct Builder {
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
+    early_data: bool,
 }
 
 impl Default for Builder {
@@ -117,6 +124,7 @@ impl Builder {
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
+            early_data: false,
         }
     }
 
@@ -162,6 +170,16 @@ impl Builder {
         Ok(self)
     }
 
+    /// Enables accepting application data in 0-RTT packets when a client resumes a session
+    ///
+    /// Session tickets are only valid for the server that issued them and can only be used once.
+    /// The transport still consults the server's early data provider for each ClientHello before
+    /// the data is accepted.
+    pub fn with_early_data(mut self, enabled: bool) -> Result<Self, Error> {
+        self.early_data = enabled;
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, Error> {
         let builder = default_config_builder()?.with_no_client_auth();
 
@@ -178,6 +196,13 @@ impl Builder {
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
 
+        if self.early_data {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+            //# Servers MUST NOT send the early_data extension with a
+            //# max_early_data_size field set to any value other than 0xffffffff.
+            config.max_early_data_size = u32::MAX;
+        }
+
         if let Some(key_log) = self.key_log {
             config.key_log = key_log;
         }
@@ -16,8 +16,14 @@ use s2n_quic_core::{
     transport,
 };
 
+/// Creates a new connection for the given QUIC version
+type NewConnection = Box<dyn FnOnce(quic::Version) -> Connection + Send>;
+
 pub struct Session {
     connection: Connection,
+    /// Recreates the connection if the transport uses a QUIC version other than the one the
+    /// session was created with. This is only possible before any handshake data is exchanged.
+    new_connection: Option<NewConnection>,
     rx_phase: HandshakePhase,
     tx_phase: HandshakePhase,
     emitted_zero_rtt_keys: bool,
@@ -26,6 +32,8 @@ pub struct Session {
     emitted_application_protocol: bool,
     emitted_key_exchange_group: bool,
     server_name: Option<ServerName>,
+    /// Set if the client keeps the session after the handshake to receive a session ticket
+    receive_session_ticket: bool,
 }
 
 impl tls::TlsSession for Session {
@@ -88,6 +96,7 @@ impl Session {
     pub fn new(connection: Connection, server_name: Option<ServerName>) -> Self {
         Self {
             connection,
+            new_connection: None,
             rx_phase: Default::default(),
             tx_phase: Default::default(),
             emitted_zero_rtt_keys: false,
@@ -96,9 +105,42 @@ impl Session {
             emitted_application_protocol: false,
             emitted_key_exchange_group: false,
             server_name,
+            receive_session_ticket: false,
         }
     }
 
+    /// Keeps the session after the handshake completes until the server sends a session ticket
+    pub(crate) fn with_session_ticket(mut self, enabled: bool) -> Self {
+        self.receive_session_ticket = enabled;
+        self
+    }
+
+    pub(crate) fn with_version_change<F>(mut self, new_connection: F) -> Self
+    where
+        F: 'static + FnOnce(quic::Version) -> Connection + Send,
+    {
+        self.new_connection = Some(Box::new(new_connection));
+        self
+    }
+
+    /// Recreates the connection on the first poll if the transport is using a QUIC version other
+    /// than the default
+    fn on_quic_version(&mut self, version: u32) -> Result<(), transport::Error> {
+        let Some(new_connection) = self.new_connection.take() else {
+            return Ok(());
+        };
+
+        if version == s2n_quic_core::version::VERSION_1 {
+            return Ok(());
+        }
+
+        let version = crate::quic_version(version)
+            .ok_or(transport::Error::INTERNAL_ERROR.with_reason("unsupported QUIC version"))?;
+        self.connection = new_connection(version);
+
+        Ok(())
+    }
+
     fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
         self.connection
             .read_hs(crypto_data)
@@ -233,6 +275,8 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
+        self.on_quic_version(context.quic_version())?;
+
         // Tracks if we have attempted to receive data at least once
         let mut has_tried_receive = false;
 
@@ -245,6 +289,15 @@ impl Session {
 
             // receive anything in the incoming buffer
             if let Some(crypto_data) = crypto_data {
+                // the transport decides on the early data once the ClientHello is received, so
+                // it needs to be rejected before rustls processes the message
+                if let Connection::Server(server) = &mut self.connection {
+                    if self.rx_phase == HandshakePhase::Initial && !context.can_accept_early_data()
+                    {
+                        server.reject_early_data();
+                    }
+                }
+
                 self.receive(&crypto_data)?;
             } else if has_tried_receive {
                 return self.poll_complete_handshake(context);
@@ -252,6 +305,8 @@ impl Session {
             }
 
             if let Poll::Ready(()) = self.poll_complete_handshake(context)? {
+                // the server sends session tickets after the handshake completes
+                self.poll_write(context)?;
                 return Poll::Ready(Ok(()));
             }
 
@@ -268,71 +323,87 @@ impl Session {
                 context.on_zero_rtt_keys(key, header_key, self.application_parameters()?)?;
             }
 
-            loop {
-                // make sure we can send data before pulling it out of rustls
-                let can_send = match self.tx_phase {
-                    HandshakePhase::Initial => context.can_send_initial(),
-                    HandshakePhase::Handshake => context.can_send_handshake(),
-                    HandshakePhase::Application => context.can_send_application(),
-                };
+            self.poll_write(context)?;
+        }
+    }
 
-                if !can_send {
-                    break;
-                }
+    /// Sends any handshake messages buffered by rustls and installs the keys as they change
+    fn poll_write<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        loop {
+            // make sure we can send data before pulling it out of rustls
+            let can_send = match self.tx_phase {
+                HandshakePhase::Initial => context.can_send_initial(),
+                HandshakePhase::Handshake => context.can_send_handshake(),
+                HandshakePhase::Application => context.can_send_application(),
+            };
 
-                let mut transmission_buffer = vec![];
+            if !can_send {
+                break;
+            }
 
-                let key_change = self.connection.write_hs(&mut transmission_buffer);
+            let mut transmission_buffer = vec![];
 
-                // if we didn't upgrade the key or transmit anything then we're waiting for
-                // more reads
-                if key_change.is_none() && transmission_buffer.is_empty() {
-                    break;
-                }
+            let key_change = self.connection.write_hs(&mut transmission_buffer);
 
-                // fill the correct buffer according to the handshake phase
-                match self.tx_phase {
-                    HandshakePhase::Initial => context.send_initial(transmission_buffer.into()),
-                    HandshakePhase::Handshake => context.send_handshake(transmission_buffer.into()),
-                    HandshakePhase::Application => {
-                        context.send_application(transmission_buffer.into())
-                    }
-                }
+            // if we didn't upgrade the key or transmit anything then we're waiting for
+            // more reads
+            if key_change.is_none() && transmission_buffer.is_empty() {
+                break;
+            }
 
-                if let Some(key_change) = key_change {
-                    let cipher_suite = self
-                        .connection
-                        .negotiated_cipher_suite()
-                        .expect("cipher_suite should be negotiated")
-                        .suite();
-                    match key_change {
-                        quic::KeyChange::Handshake { keys } => {
-                            let (key, header_key) = PacketKeys::new(keys, cipher_suite);
-
-                            context.on_handshake_keys(key, header_key)?;
-
-                            // Transition both phases to Handshake
-                            self.tx_phase.transition();
-                            self.rx_phase.transition();
-                        }
-                        quic::KeyChange::OneRtt { keys, next } => {
-                            let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
+            // fill the correct buffer according to the handshake phase
+            match self.tx_phase {
+                HandshakePhase::Initial => context.send_initial(transmission_buffer.into()),
+                HandshakePhase::Handshake => context.send_handshake(transmission_buffer.into()),
+                HandshakePhase::Application => context.send_application(transmission_buffer.into()),
+            }
 
-                            // at this point we should have both SNI and ALPN values
-                            self.emit_events(context)?;
+            if let Some(key_change) = key_change {
+                let cipher_suite = self
+                    .connection
+                    .negotiated_cipher_suite()
+                    .expect("cipher_suite should be negotiated")
+                    .suite();
+                match key_change {
+                    quic::KeyChange::Handshake { keys } => {
+                        let (key, header_key) = PacketKeys::new(keys, cipher_suite);
+
+                        context.on_handshake_keys(key, header_key)?;
+
+                        // Transition both phases to Handshake
+                        self.tx_phase.transition();
+                        self.rx_phase.transition();
+                    }
+                    quic::KeyChange::OneRtt { keys, next } => {
+                        let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
+
+                        // at this point we should have both SNI and ALPN values
+                        self.emit_events(context)?;
+
+                        // let the client know if the server accepted the early data before
+                        // installing the 1-RTT keys
+                        if self.emitted_zero_rtt_keys {
+                            if let Connection::Client(client) = &self.connection {
+                                context.on_zero_rtt_status(client.is_early_data_accepted())?;
+                            }
+                        }
 
-                            let application_parameters = self.application_parameters()?;
+                        let application_parameters = self.application_parameters()?;
 
-                            context.on_one_rtt_keys(key, header_key, application_parameters)?;
+                        context.on_one_rtt_keys(key, header_key, application_parameters)?;
 
-                            // Transition the tx_phase to Application
-                            // Note: the rx_phase is transitioned when the handshake is complete
-                            self.tx_phase.transition();
-                        }
+                        // Transition the tx_phase to Application
+                        // Note: the rx_phase is transitioned when the handshake is complete
+                        self.tx_phase.transition();
                     }
                 }
             }
         }
+
+        Ok(())
     }
 
     fn emit_events<C: tls::Context<Self>>(
@@ -389,6 +460,25 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn process_post_handshake_message<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        while let Some(crypto_data) = context.receive_application(None) {
+            self.receive(&crypto_data)?;
+        }
+        Ok(())
+    }
+
+    fn should_discard_session(&self) -> bool {
+        match &self.connection {
+            Connection::Client(client) if self.receive_session_ticket => {
+                client.tls13_tickets_received() > 0
+            }
+            _ => true,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
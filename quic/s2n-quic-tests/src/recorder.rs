@@ -103,6 +103,18 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    LocalMigrationUpdated,
+    LocalMigrationUpdated,
+    on_local_migration_updated,
+    (events::LocalMigrationStatus, SocketAddr),
+    |event: &events::LocalMigrationUpdated,
+     storage: &mut Vec<(events::LocalMigrationStatus, SocketAddr)>| {
+        let addr: SocketAddr = event.path.local_addr.to_string().parse().unwrap();
+        storage.push((event.status.clone(), addr));
+    }
+);
+
 event_recorder!(
     PacketDropped,
     PacketDropped,
@@ -125,6 +137,17 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    ConnectionIdUpdated,
+    ConnectionIdUpdated,
+    on_connection_id_updated,
+    (s2n_quic_core::endpoint::Location, Vec<u8>),
+    |event: &events::ConnectionIdUpdated,
+     storage: &mut Vec<(s2n_quic_core::endpoint::Location, Vec<u8>)>| {
+        storage.push((event.cid_consumer, event.current.bytes.to_vec()));
+    }
+);
+
 event_recorder!(
     TlsClientHello,
     TlsClientHello,
@@ -23,13 +23,16 @@ use std::{
     time::Duration,
 };
 
+mod ack_frequency;
 mod blackhole;
 mod buffer_limit;
+mod congestion_controller;
 mod connection_limits;
 mod connection_migration;
 mod deduplicate;
 mod endpoint_limits;
 mod exporter;
+mod h3;
 mod handshake_cid_rotation;
 mod initial_rtt;
 mod interceptor;
@@ -38,16 +41,30 @@ mod issue_1427;
 mod issue_1464;
 mod issue_1717;
 mod issue_954;
+mod local_migration;
 mod mtu;
 mod no_tls;
 mod offload;
+mod openmetrics;
 mod platform_events;
+mod preferred_address;
 mod pto;
+mod qlog;
+mod quic_lb;
 mod resumption;
 mod self_test;
+#[cfg(target_os = "linux")]
+mod sharded;
+mod shutdown;
 mod skip_packets;
 mod slow_tls;
+mod stateless_reset;
+mod stats;
+mod stream_copy;
+mod stream_priority;
 mod tls_context;
+mod version_negotiation;
+mod zero_rtt;
 // quiche does not currently build on 32-bit platforms
 // see https://github.com/cloudflare/quiche/issues/2097
 #[cfg(not(target_arch = "x86"))]
@@ -68,3 +85,5 @@ mod dc;
 mod fips;
 #[cfg(not(target_os = "windows"))]
 mod mtls;
+#[cfg(not(target_os = "windows"))]
+mod pcap;
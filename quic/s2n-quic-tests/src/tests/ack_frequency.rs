@@ -0,0 +1,69 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::congestion_controller::Bbr;
+use s2n_quic_core::event::api::Frame;
+
+/// Sends a large amount of data from a BBR client over a capacity-limited network and returns the number of ACK_FREQUENCY
+/// frames the client transmitted
+fn ack_frequency_frames_sent(min_ack_delay: Option<Duration>) -> usize {
+    let model = Model::default();
+    model
+        .set_delay(Duration::from_millis(10))
+        .set_max_inflight(100);
+
+    let subscriber = recorder::FrameSent::new();
+    let frame_sent_events = subscriber.events();
+
+    let limits = || {
+        let limits = provider::limits::Limits::default();
+        match min_ack_delay {
+            Some(delay) => limits.with_min_ack_delay(delay).unwrap(),
+            None => limits,
+        }
+    };
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits())?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_congestion_controller(Bbr::default())?
+            .with_limits(limits())?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let frame_sent_events = frame_sent_events.lock().unwrap();
+    frame_sent_events
+        .iter()
+        .filter(|event| matches!(event.frame, Frame::AckFrequency { .. }))
+        .count()
+}
+
+/// The sender requests a less frequent acknowledgement rate once BBR has filled the pipe
+#[test]
+fn ack_frequency_negotiated() {
+    assert!(ack_frequency_frames_sent(Some(Duration::from_millis(1))) > 0);
+}
+
+/// ACK_FREQUENCY frames are not sent unless both peers advertise min_ack_delay
+#[test]
+fn ack_frequency_not_negotiated() {
+    assert_eq!(ack_frequency_frames_sent(None), 0);
+}
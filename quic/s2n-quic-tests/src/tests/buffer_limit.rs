@@ -9,8 +9,8 @@ use s2n_quic::{
 use s2n_quic_core::{crypto::tls::Error as TlsError, transport};
 
 // It helps to expand the Client Hello size to excced 64 KB, by filling
-// the alpn extension in Client Hello with 65310 bytes.
-static FAKE_PROTOCOL_COUNT: u16 = 4665;
+// the alpn extension in Client Hello with 65296 bytes.
+static FAKE_PROTOCOL_COUNT: u16 = 4664;
 // Maximum handshake message size is 64KB in S2N-TLS and Rustls.
 static MAXIMUM_HANDSHAKE_MESSAGE_SIZE: usize = 65536;
 
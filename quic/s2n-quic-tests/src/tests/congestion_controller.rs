@@ -0,0 +1,109 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::congestion_controller::{
+    bbr,
+    dynamic::{Algorithm, DynamicCongestionController},
+    Dynamic,
+};
+use s2n_quic_core::{event::api::Frame, query};
+
+/// Sends a large amount of data from a client using the dynamic congestion controller and returns
+/// the number of ACK_FREQUENCY frames the client transmitted
+///
+/// Only BBR requests a less frequent acknowledgement rate, so ACK_FREQUENCY frames are only sent
+/// if the client switches from the default CUBIC algorithm to BBR.
+fn ack_frequency_frames_sent(switch_to_bbr: bool) -> usize {
+    let model = Model::default();
+    model
+        .set_delay(Duration::from_millis(10))
+        .set_max_inflight(100);
+
+    let subscriber = recorder::FrameSent::new();
+    let frame_sent_events = subscriber.events();
+
+    let limits = || {
+        provider::limits::Limits::default()
+            .with_min_ack_delay(Duration::from_millis(1))
+            .unwrap()
+    };
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits())?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_congestion_controller(Dynamic::new(Algorithm::default()))?
+            .with_limits(limits())?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the query fails if the provider type does not match
+            assert!(matches!(
+                connection
+                    .handle()
+                    .congestion_controller_mut(|_: &mut u32| ()),
+                Err(query::Error::ContextTypeMismatch)
+            ));
+
+            if switch_to_bbr {
+                connection
+                    .handle()
+                    .congestion_controller_mut(|cc: &mut DynamicCongestionController| {
+                        cc.set_algorithm(bbr::Builder::default().build())
+                    })
+                    .unwrap();
+            }
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            let mut data = Data::new(10_000_000);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.finish().unwrap();
+
+            while stream.receive().await.unwrap().is_some() {}
+
+            let pending = connection
+                .handle()
+                .congestion_controller_mut(|cc: &mut DynamicCongestionController| {
+                    cc.is_algorithm_pending()
+                })
+                .unwrap();
+            assert!(!pending);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let frame_sent_events = frame_sent_events.lock().unwrap();
+    frame_sent_events
+        .iter()
+        .filter(|event| matches!(event.frame, Frame::AckFrequency { .. }))
+        .count()
+}
+
+#[test]
+fn dynamic_switch_to_bbr() {
+    assert!(ack_frequency_frames_sent(true) > 0);
+}
+
+#[test]
+fn dynamic_default_cubic() {
+    assert_eq!(ack_frequency_frames_sent(false), 0);
+}
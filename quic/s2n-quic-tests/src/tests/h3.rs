@@ -0,0 +1,260 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bytes::{BufMut as _, BytesMut};
+use h3_datagram::{datagram_handler::HandleDatagramsExt as _, quic_traits::DatagramConnectionExt};
+use s2n_quic::{connection::Handle as ConnectionHandle, provider::datagram::default};
+use s2n_quic_h3::{
+    h3::{
+        self,
+        ext::Protocol,
+        proto::coding::Encode as _,
+        quic::RecvStream as _,
+        stream::{BidiStreamHeader, UniStreamHeader},
+        webtransport::SessionId,
+    },
+    webtransport,
+};
+
+/// The streams accepted by each session, identified by the session and the stream ID
+type Accepted = Arc<Mutex<Vec<(SessionId, u64)>>>;
+
+fn datagram_endpoint() -> default::Endpoint {
+    default::Endpoint::builder().build().unwrap()
+}
+
+/// Starts a server that accepts every WebTransport session, records the streams accepted by
+/// each session and echoes back session datagrams
+fn start_webtransport_server(handle: &Handle, accepted: Accepted) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_datagram(datagram_endpoint())?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(connection) = server.accept().await {
+            let accepted = accepted.clone();
+            spawn(async move {
+                let conn = h3::server::builder()
+                    .enable_webtransport(true)
+                    .enable_extended_connect(true)
+                    .enable_datagram(true)
+                    .max_webtransport_sessions(2)
+                    .build(s2n_quic_h3::Connection::new(connection))
+                    .await
+                    .unwrap();
+                let server = webtransport::Server::new(conn).unwrap();
+
+                while let Ok(Some((request, stream))) = server.accept().await {
+                    let session = Arc::new(server.accept_session(request, stream).await.unwrap());
+                    let session_id = session.session_id();
+
+                    let datagrams = session.clone();
+                    spawn(async move {
+                        while let Ok(Some(datagram)) = datagrams.read_datagram().await {
+                            let _ = datagrams.send_datagram(datagram);
+                        }
+                    });
+
+                    let uni = session.clone();
+                    let uni_accepted = accepted.clone();
+                    spawn(async move {
+                        while let Ok(Some(stream)) = uni.accept_uni().await {
+                            let stream_id = stream.recv_id().into_inner();
+                            uni_accepted.lock().unwrap().push((session_id, stream_id));
+                        }
+                    });
+
+                    let accepted = accepted.clone();
+                    spawn(async move {
+                        while let Ok(Some(stream)) = session.accept_bi().await {
+                            let stream_id = stream.recv_id().into_inner();
+                            accepted.lock().unwrap().push((session_id, stream_id));
+                        }
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Opens a WebTransport session with an extended CONNECT request
+async fn open_session(
+    send_request: &mut h3::client::SendRequest<s2n_quic_h3::OpenStreams, Bytes>,
+) -> (
+    SessionId,
+    h3::client::RequestStream<s2n_quic_h3::BidiStream<Bytes>, Bytes>,
+) {
+    let mut request = http::Request::builder()
+        .method(http::Method::CONNECT)
+        .uri("https://localhost/session")
+        .body(())
+        .unwrap();
+    request.extensions_mut().insert(Protocol::WEB_TRANSPORT);
+
+    let mut stream = send_request.send_request(request).await.unwrap();
+    let response = stream.recv_response().await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+
+    let session_id = SessionId::try_from(stream.id().into_inner()).unwrap();
+    (session_id, stream)
+}
+
+/// Opens a raw unidirectional stream for the session and returns its ID
+async fn open_uni(handle: &mut ConnectionHandle, session_id: SessionId) -> u64 {
+    let mut stream = handle.open_send_stream().await.unwrap();
+    let mut header = BytesMut::new();
+    UniStreamHeader::WebTransportUni(session_id).encode(&mut header);
+    // h3 drops streams that finish right after a one byte session ID so some data is sent too
+    header.put_slice(b"data");
+    stream.send(header.freeze()).await.unwrap();
+    stream.id()
+}
+
+/// Waits for the server to accept the stream
+async fn wait_for(accepted: &Accepted, stream: (SessionId, u64)) {
+    while !accepted.lock().unwrap().contains(&stream) {
+        delay(Duration::from_millis(10)).await;
+    }
+}
+
+/// Streams are routed to the session they were opened for, and a stream with a stalled header
+/// doesn't hold up the streams opened after it
+#[test]
+fn webtransport_session_routing_test() {
+    let model = Model::default();
+    let accepted = Accepted::default();
+
+    test(model, |handle| {
+        let server_addr = start_webtransport_server(handle, accepted.clone())?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let accepted = accepted.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            let mut handle = connection.handle();
+
+            let (mut driver, mut send_request) =
+                h3::client::new(s2n_quic_h3::Connection::new(connection))
+                    .await
+                    .unwrap();
+            spawn(async move {
+                let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+            });
+
+            let (first, _first_stream) = open_session(&mut send_request).await;
+            let (second, _second_stream) = open_session(&mut send_request).await;
+
+            // only the first byte of the stream type is sent so the header can't be decoded yet
+            let mut stalled = handle.open_bidirectional_stream().await.unwrap();
+            let mut header = BytesMut::new();
+            BidiStreamHeader::WebTransportBidi(first).encode(&mut header);
+            let mut header = header.freeze();
+            stalled.send(header.split_to(1)).await.unwrap();
+
+            let mut bidi = handle.open_bidirectional_stream().await.unwrap();
+            let mut bidi_header = BytesMut::new();
+            BidiStreamHeader::WebTransportBidi(second).encode(&mut bidi_header);
+            bidi.send(bidi_header.freeze()).await.unwrap();
+
+            let first_uni = open_uni(&mut handle, first).await;
+            let second_uni = open_uni(&mut handle, second).await;
+
+            wait_for(&accepted, (second, bidi.id())).await;
+            wait_for(&accepted, (first, first_uni)).await;
+            wait_for(&accepted, (second, second_uni)).await;
+            assert!(!accepted
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(_, stream_id)| *stream_id == stalled.id()));
+
+            // the stream is accepted by its session once the rest of the header arrives
+            stalled.send(header).await.unwrap();
+            wait_for(&accepted, (first, stalled.id())).await;
+
+            assert_eq!(accepted.lock().unwrap().len(), 4);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Session datagrams are echoed back by the server with the stream ID of their session
+#[test]
+fn webtransport_datagram_round_trip_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_webtransport_server(handle, Accepted::default())?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_datagram(datagram_endpoint())?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = s2n_quic_h3::Connection::new(client.connect(connect).await.unwrap());
+            let mut sender = DatagramConnectionExt::<Bytes>::send_datagram_handler(&connection);
+
+            let (mut driver, mut send_request) = h3::client::builder()
+                .enable_datagram(true)
+                .build(connection)
+                .await
+                .unwrap();
+
+            let (first, first_stream) = open_session(&mut send_request).await;
+            let (second, second_stream) = open_session(&mut send_request).await;
+
+            let mut reader = driver.get_datagram_reader();
+            spawn(async move {
+                let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+            });
+
+            sender
+                .send_datagram_for(first_stream.id(), Bytes::from_static(b"first"))
+                .unwrap();
+            sender
+                .send_datagram_for(second_stream.id(), Bytes::from_static(b"second"))
+                .unwrap();
+
+            let mut echoed = vec![];
+            for _ in 0..2 {
+                let datagram = reader.read_datagram().await.unwrap();
+                let session_id = SessionId::try_from(datagram.stream_id().into_inner()).unwrap();
+                echoed.push((session_id, datagram.into_payload()));
+            }
+            echoed.sort_by_key(|(_, payload)| payload.clone());
+
+            assert_eq!(
+                echoed,
+                [
+                    (first, Bytes::from_static(b"first")),
+                    (second, Bytes::from_static(b"second")),
+                ]
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::{connection::migration, provider::event::events::LocalMigrationStatus};
+
+/// Migrates a client to a new local address after the handshake is confirmed
+#[test]
+fn client_migrates_to_new_local_address() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let client_socket = Arc::new(Mutex::new(None));
+    let on_socket = {
+        let client_socket = client_socket.clone();
+        move |socket: io::Socket| {
+            *client_socket.lock().unwrap() = Some(socket);
+        }
+    };
+
+    let client_migration = recorder::LocalMigrationUpdated::new();
+    let client_migration_sub = client_migration.clone();
+    let server_paths = recorder::ActivePathUpdated::new();
+    let server_paths_sub = server_paths.clone();
+    let new_local_addr = Arc::new(Mutex::new(None));
+    let new_local_addr_pub = new_local_addr.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_paths_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_migration_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            // wait for a round trip so the handshake is confirmed
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(&chunk[..], &b"A"[..]);
+
+            let socket = client_socket.lock().unwrap().take().unwrap();
+            let mut local_addr = socket.local_addr().unwrap();
+            local_addr.set_port(local_addr.port() + 1);
+
+            conn.migrate(local_addr).unwrap();
+            assert_eq!(conn.migrate(local_addr), Err(migration::Error::InProgress));
+            socket.rebind(local_addr);
+            *new_local_addr_pub.lock().unwrap() = Some(local_addr);
+
+            stream.send(Bytes::from_static(b"B")).await.unwrap();
+            stream.finish().unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(&chunk[..], &b"B"[..]);
+            assert!(stream.receive().await.unwrap().is_none());
+
+            assert_eq!(conn.local_addr().unwrap(), local_addr);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let new_local_addr = new_local_addr.lock().unwrap().unwrap();
+
+    let events = client_migration.events();
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(
+        matches!(events[0], (LocalMigrationStatus::Started { .. }, addr) if addr == new_local_addr)
+    );
+    assert!(
+        matches!(events[1], (LocalMigrationStatus::Completed { .. }, addr) if addr == new_local_addr)
+    );
+
+    // the server switched to the client's new address
+    assert_eq!(&*server_paths.events().lock().unwrap(), &[new_local_addr]);
+}
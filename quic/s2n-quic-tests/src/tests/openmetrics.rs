@@ -0,0 +1,47 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::event::openmetrics;
+
+#[test]
+fn openmetrics_registry() {
+    let registry = openmetrics::Registry::default().with_prefix("s2n_quic");
+
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((
+                tracing_events(),
+                openmetrics::Subscriber::new(registry.clone()),
+            ))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((
+                tracing_events(),
+                openmetrics::Subscriber::new(registry.clone()),
+            ))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let output = registry.to_string();
+
+    // both endpoints record into the same registry
+    assert!(output.contains("s2n_quic_connection_started_total 2\n"));
+    assert!(output
+        .contains("# TYPE s2n_quic_handshake_status_updated_complete_latency_seconds histogram\n"));
+    assert!(output.ends_with("# EOF\n"));
+}
@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::{io::pcap, tls};
+use std::io::Write;
+
+const ENHANCED_PACKET_BLOCK: u32 = 6;
+const DECRYPTION_SECRETS_BLOCK: u32 = 0xa;
+
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Splits the capture into its block types and bodies
+fn blocks(mut bytes: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut blocks = vec![];
+
+    while !bytes.is_empty() {
+        let block_type = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let len = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        let (block, remaining) = bytes.split_at(len);
+        blocks.push((block_type, &block[8..len - 4]));
+        bytes = remaining;
+    }
+
+    blocks
+}
+
+#[test]
+fn pcap_capture() {
+    let server_output = Output::default();
+    let client_output = Output::default();
+
+    let model = Model::default();
+    test(model, |handle| {
+        let capture = pcap::Capture::new(server_output.clone())?;
+        let server_tls = tls::default::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_key_log(capture.key_log())?
+            .build()?;
+        let server = Server::builder()
+            .with_io(pcap::Provider::new(handle.builder().build()?, capture)?)?
+            .with_tls(server_tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let capture = pcap::Capture::new(client_output.clone())?;
+        let client_tls = tls::default::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_key_log(capture.key_log())?
+            .build()?;
+        let client = Client::builder()
+            .with_io(pcap::Provider::new(handle.builder().build()?, capture)?)?
+            .with_tls(client_tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    for output in [server_output, client_output] {
+        let output = output.0.lock().unwrap();
+        let blocks = blocks(&output);
+
+        let secrets: Vec<u8> = blocks
+            .iter()
+            .filter(|(block_type, _)| *block_type == DECRYPTION_SECRETS_BLOCK)
+            .flat_map(|(_, body)| {
+                let len = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+                body[8..8 + len].iter().copied()
+            })
+            .collect();
+        let secrets = String::from_utf8(secrets).unwrap();
+
+        for label in [
+            "CLIENT_HANDSHAKE_TRAFFIC_SECRET",
+            "SERVER_HANDSHAKE_TRAFFIC_SECRET",
+            "CLIENT_TRAFFIC_SECRET_0",
+            "SERVER_TRAFFIC_SECRET_0",
+        ] {
+            assert!(secrets.contains(label), "capture is missing {label}");
+        }
+
+        // both directions should be recorded
+        let mut directions = [0usize; 2];
+        for (block_type, body) in &blocks {
+            if *block_type != ENHANCED_PACKET_BLOCK {
+                continue;
+            }
+
+            let captured_len = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+            let options = &body[20 + captured_len.next_multiple_of(4)..];
+            let flags = u32::from_le_bytes(options[4..8].try_into().unwrap());
+            directions[(flags & 0b11) as usize - 1] += 1;
+        }
+        assert!(directions.iter().all(|count| *count > 0), "{directions:?}");
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::limits::Limits;
+
+/// Migrates a client to the server's preferred address after the handshake is confirmed
+#[test]
+fn client_migrates_to_preferred_address() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let preferred_addr: SocketAddr = "10.0.0.1:4433".parse().unwrap();
+    let on_socket = move |socket: io::Socket| {
+        socket.add_address(preferred_addr);
+    };
+
+    let client_paths = recorder::ActivePathUpdated::new();
+    let client_paths_sub = client_paths.clone();
+
+    test(model, move |handle| {
+        let limits = Limits::new().with_preferred_address(preferred_addr)?;
+        let server = Server::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_limits(limits)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_paths_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            assert_eq!(conn.remote_addr().unwrap(), addr);
+
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            // wait for a round trip so the handshake is confirmed
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(&chunk[..], &b"A"[..]);
+
+            // give the client time to validate the preferred address
+            delay(rtt * 4).await;
+
+            stream.send(Bytes::from_static(b"B")).await.unwrap();
+            stream.finish().unwrap();
+
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(&chunk[..], &b"B"[..]);
+            assert!(stream.receive().await.unwrap().is_none());
+
+            assert_eq!(conn.remote_addr().unwrap(), preferred_addr);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(&*client_paths.events().lock().unwrap(), &[preferred_addr]);
+}
@@ -138,7 +138,9 @@ fn pto_jitter() {
                 (tracing_events(), pto_subscriber_jitter),
                 datagram_sent_subscriber_jitter,
             ))?
-            .with_random(Random::with_seed(123))?
+            // the jittered PTOs are drawn from this seed and all of them need to fit within
+            // the handshake duration
+            .with_random(Random::with_seed(124))?
             .start()?;
 
         primary::spawn(async move {
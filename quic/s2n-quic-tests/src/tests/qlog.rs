@@ -0,0 +1,120 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::event::qlog;
+
+#[test]
+fn qlog_traces() {
+    let dir = std::env::temp_dir().join(format!("s2n-quic-qlog-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), qlog::Subscriber::new(&dir)))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), qlog::Subscriber::new(&dir)))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let mut traces = vec![];
+
+    for endpoint in ["client", "server"] {
+        let paths: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                let name = path.file_name().unwrap().to_str().unwrap();
+                name.starts_with(&format!("{endpoint}-")) && name.ends_with(".sqlog")
+            })
+            .collect();
+        assert_eq!(paths.len(), 1, "expected a single {endpoint} trace");
+
+        // traces are named `<endpoint>-<odcid>-<trace id>.sqlog`
+        let name = paths[0].file_stem().unwrap().to_str().unwrap();
+        let mut parts = name.split('-').skip(1);
+        let odcid = parts.next().unwrap().to_string();
+        assert!(parts.next().unwrap().parse::<u64>().is_ok());
+
+        let trace = std::fs::read_to_string(&paths[0]).unwrap();
+        let mut records = trace.lines().map(|record| {
+            // every record is prefixed with the JSON-SEQ record separator
+            let record = record.strip_prefix('\u{1e}').unwrap();
+            serde_json::from_str::<serde_json::Value>(record).unwrap()
+        });
+
+        let header = records.next().unwrap();
+        assert_eq!(header["qlog_format"], "JSON-SEQ");
+        assert_eq!(header["trace"]["vantage_point"]["type"], endpoint);
+        assert_eq!(header["trace"]["common_fields"]["group_id"], odcid.as_str());
+
+        let events: Vec<_> = records.collect();
+        let mut time = 0.0;
+        for event in &events {
+            let event_time = event["time"].as_f64().unwrap();
+            assert!(
+                event_time >= time,
+                "{endpoint} event times should not decrease"
+            );
+            time = event_time;
+        }
+
+        let named = |name: &'static str| events.iter().filter(move |event| event["name"] == name);
+
+        for name in [
+            "connectivity:connection_started",
+            "transport:packet_sent",
+            "transport:packet_received",
+            "recovery:metrics_updated",
+            "security:key_updated",
+        ] {
+            assert!(
+                named(name).next().is_some(),
+                "{endpoint} trace is missing {name}"
+            );
+        }
+
+        let started = &named("connectivity:connection_started").next().unwrap()["data"];
+        if endpoint == "client" {
+            // the client's first destination connection ID is the ODCID
+            assert_eq!(started["dst_cid"], odcid.as_str());
+        }
+
+        let stream_bytes: u64 = named("transport:packet_sent")
+            .inspect(|event| {
+                let packet_type = event["data"]["header"]["packet_type"].as_str().unwrap();
+                assert!(!packet_type.is_empty());
+            })
+            .flat_map(|event| event["data"]["frames"].as_array().unwrap())
+            .filter(|frame| frame["frame_type"] == "stream")
+            .map(|frame| frame["length"].as_u64().unwrap())
+            .sum();
+        assert!(stream_bytes >= 10_000, "{endpoint} should send the data");
+
+        traces.push((odcid, started.clone()));
+    }
+
+    let (client_odcid, client) = &traces[0];
+    let (server_odcid, server) = &traces[1];
+
+    // both endpoints are grouped by the same original destination connection ID
+    assert_eq!(client_odcid, server_odcid);
+    assert_eq!(client["dst_port"], server["src_port"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
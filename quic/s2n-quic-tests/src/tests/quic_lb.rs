@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::connection_id::quic_lb::{Config, Decoder, Format};
+use s2n_quic_core::endpoint::Location;
+
+const SERVER_ID: &[u8] = &[1, 2, 3];
+
+fn run_test(config: Config) {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    let rebind_rate = rtt * 2;
+    let rebind_count = 2;
+
+    model.set_delay(rtt / 2);
+
+    // the client changes its port, which the server needs to follow using only the connection ID
+    let on_socket = move |socket: io::Socket| {
+        spawn(async move {
+            let mut local_addr = socket.local_addr().unwrap();
+            for _ in 0..rebind_count {
+                delay(rebind_rate).await;
+                local_addr.set_port(local_addr.port() + 1);
+                socket.rebind(local_addr);
+            }
+        });
+    };
+
+    let connection_ids = recorder::ConnectionIdUpdated::new();
+    let connection_ids_sub = connection_ids.clone();
+
+    let format = Format::builder(config.clone(), SERVER_ID)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_connection_id(format)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), connection_ids_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            for _ in 0..=rebind_count {
+                stream.send(Bytes::from_static(b"A")).await.unwrap();
+                delay(rebind_rate).await;
+            }
+
+            stream.finish().unwrap();
+
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+            assert_eq!(&received[..], &b"AAA"[..]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // every connection ID the client used to reach the server should be routable
+    let decoder = Decoder::default().with_config(config);
+    let connection_ids = connection_ids.events();
+    let connection_ids = connection_ids.lock().unwrap();
+    let server_ids = connection_ids
+        .iter()
+        .filter(|(consumer, _)| *consumer == Location::Local)
+        .map(|(_, id)| decoder.decode(id).expect("id should be routable"))
+        .collect::<Vec<_>>();
+
+    assert!(!server_ids.is_empty());
+    for server_id in server_ids {
+        assert_eq!(server_id.as_bytes(), SERVER_ID);
+    }
+}
+
+#[test]
+fn plaintext_test() {
+    run_test(Config::new(1, SERVER_ID.len(), 8).unwrap());
+}
+
+#[test]
+fn single_pass_test() {
+    run_test(
+        Config::new(2, SERVER_ID.len(), 13)
+            .unwrap()
+            .with_key([7; 16]),
+    );
+}
+
+#[test]
+fn four_pass_test() {
+    run_test(
+        Config::new(3, SERVER_ID.len(), 10)
+            .unwrap()
+            .with_key([7; 16]),
+    );
+}
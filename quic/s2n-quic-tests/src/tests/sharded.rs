@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::server::sharded;
+
+const WORKERS: usize = 4;
+const CLIENTS: usize = 16;
+
+/// Connects multiple clients to a sharded server over the loopback interface
+///
+/// Each worker can only complete handshakes for the connections it owns, so this ensures the
+/// packets for each connection are steered to a single worker.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn sharded_server_test() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let mut server = sharded::Builder::default()
+            .with_address("127.0.0.1:0")
+            .unwrap()
+            .with_workers(WORKERS)
+            .unwrap()
+            .start(|_worker| Server::builder().with_tls(SERVER_CERTS))
+            .unwrap();
+
+        let server_addr = server.local_addr().unwrap();
+
+        // accept connections from all of the workers and echo back
+        tokio::spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                tokio::spawn(async move {
+                    while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await
+                    {
+                        tokio::spawn(async move {
+                            while let Ok(Some(chunk)) = stream.receive().await {
+                                stream.send(chunk).await.unwrap();
+                            }
+                            let _ = stream.finish();
+                        });
+                    }
+                });
+            }
+        });
+
+        let mut clients = vec![];
+
+        for idx in 0..CLIENTS {
+            clients.push(tokio::spawn(async move {
+                let client = Client::builder()
+                    .with_tls(certificates::CERT_PEM)
+                    .unwrap()
+                    .with_io("127.0.0.1:0")
+                    .unwrap()
+                    .start()
+                    .unwrap();
+
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let message = Bytes::from(format!("hello from client {idx}"));
+                stream.send(message.clone()).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut response = vec![];
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    response.extend_from_slice(&chunk);
+                }
+
+                assert_eq!(response, message);
+            }));
+        }
+
+        for client in clients {
+            tokio::time::timeout(Duration::from_secs(30), client)
+                .await
+                .expect("client timed out")
+                .unwrap();
+        }
+    });
+}
@@ -0,0 +1,124 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bytes::Bytes;
+use s2n_quic::{connection, stream};
+
+const SHUTDOWN_ERROR_CODE: u32 = 123;
+
+/// Starts a server which accepts a single connection and then gracefully shuts down
+///
+/// If `respond` is set, the server finishes its stream once the connection is notified of the
+/// shutdown. Otherwise, the connection is left open until the shutdown deadline passes.
+fn start_shutdown_server(mut server: Server, respond: bool) -> Result<SocketAddr> {
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let mut stream = connection
+            .accept_bidirectional_stream()
+            .await
+            .unwrap()
+            .unwrap();
+
+        spawn(async move {
+            connection.handle().endpoint_shutdown().await.unwrap();
+
+            if respond {
+                stream.send(Bytes::from_static(b"goodbye")).await.unwrap();
+                stream.finish().unwrap();
+            }
+
+            while let Ok(Some(_)) = stream.receive().await {
+                // noop
+            }
+        });
+
+        server
+            .shutdown(Duration::from_secs(5), SHUTDOWN_ERROR_CODE.into())
+            .await;
+
+        // the server no longer accepts connections once it has shut down
+        assert!(server.accept().await.is_none());
+    });
+
+    Ok(server_addr)
+}
+
+fn shutdown_test<F>(respond: bool, f: fn(Client, SocketAddr) -> F)
+where
+    F: 'static + core::future::Future<Output = ()> + Send,
+{
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_shutdown_server(server, respond)?;
+        primary::spawn(f(client, addr));
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn shutdown_notifies_connections_test() {
+    shutdown_test(true, |client, addr| async move {
+        let connect = Connect::new(addr).with_server_name("localhost");
+        let mut connection = client.connect(connect.clone()).await.unwrap();
+
+        let mut stream = connection.open_bidirectional_stream().await.unwrap();
+        stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+        // the server responds once it's notified of the shutdown
+        let chunk = stream.receive().await.unwrap().unwrap();
+        assert_eq!(chunk, Bytes::from_static(b"goodbye"));
+        assert!(stream.receive().await.unwrap().is_none());
+
+        // new connections are refused while the server is shutting down
+        assert!(client.connect(connect).await.is_err());
+
+        stream.finish().unwrap();
+    });
+}
+
+#[test]
+fn shutdown_deadline_closes_connections_test() {
+    shutdown_test(false, |client, addr| async move {
+        let connect = Connect::new(addr).with_server_name("localhost");
+        let mut connection = client.connect(connect).await.unwrap();
+
+        let mut stream = connection.open_bidirectional_stream().await.unwrap();
+        stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+        // the server closes the connection once the shutdown deadline passes
+        let start = s2n_quic::provider::io::testing::now();
+        let error = stream.receive().await.unwrap_err();
+        assert!(s2n_quic::provider::io::testing::now() - start >= Duration::from_secs(5));
+        assert!(
+            matches!(
+                error,
+                stream::Error::ConnectionError {
+                    error: connection::Error::Application { error, .. },
+                    ..
+                } if error == SHUTDOWN_ERROR_CODE.into()
+            ),
+            "{error:?}"
+        );
+    });
+}
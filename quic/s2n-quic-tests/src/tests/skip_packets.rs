@@ -91,8 +91,8 @@ fn optimistic_ack_mitigation() {
     //
     // The exact number of skipped packets depends on randomness, so this test may be changed by
     // unrelated changes. The important thing is that both numbers are non-zero.
-    assert_eq!(server_skip_count, 5);
-    assert_eq!(client_skip_count, 5);
+    assert_eq!(server_skip_count, 4);
+    assert_eq!(client_skip_count, 4);
 }
 
 // Mimic an Optimistic Ack attack and confirm the connection is closed with
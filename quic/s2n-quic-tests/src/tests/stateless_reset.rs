@@ -0,0 +1,133 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::{connection, provider::stateless_reset_token, stream::Error as StreamError};
+
+const KEY: &[u8] = &[42; 32];
+
+fn keyed_provider() -> stateless_reset_token::Default {
+    stateless_reset_token::Default::builder()
+        .with_key(KEY)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Simulates a server that loses all of its state and restarts on the same address
+///
+/// The client establishes a connection with the first server. The first server is then moved
+/// to a different address and the second server takes over the original address. Packets
+/// from the client for the existing connection are routed to the second server, which has
+/// no state for the connection.
+fn restart<P>(provider: impl Fn() -> P + 'static) -> connection::Error
+where
+    P: stateless_reset_token::Provider,
+{
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    let error = Arc::new(Mutex::new(None));
+    let error_result = error.clone();
+
+    test(model, move |handle| {
+        let sockets = Arc::new(Mutex::new(vec![]));
+
+        let mut servers = vec![];
+        for _ in 0..2 {
+            let sockets = sockets.clone();
+            let server = Server::builder()
+                .with_io(
+                    handle
+                        .builder()
+                        .on_socket(move |socket| sockets.lock().unwrap().push(socket))
+                        .build()?,
+                )?
+                .with_tls(SERVER_CERTS)?
+                .with_event(tracing_events())?
+                .with_random(Random::with_seed(456))?
+                .with_stateless_reset_token(provider())?
+                .start()?;
+            servers.push(start_server(server)?);
+        }
+
+        let addr = servers[0];
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            assert_eq!(&chunk[..], &b"A"[..]);
+
+            // "restart" the server by routing the original address to the second server
+            {
+                let sockets = sockets.lock().unwrap();
+                sockets[0].rebind("10.0.0.1:4433".parse().unwrap());
+                sockets[1].add_address(addr);
+            }
+
+            // the triggering packet needs to be large enough for the server to respond
+            // with a stateless reset
+            let _ = stream.send(Bytes::from(vec![42; 1000])).await;
+
+            match stream.receive().await {
+                Err(StreamError::ConnectionError { error: err, .. }) => {
+                    *error.lock().unwrap() = Some(err);
+                }
+                result => panic!("unexpected result: {result:?}"),
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let error = error_result.lock().unwrap().take();
+    error.expect("the client stream should be closed with an error")
+}
+
+#[test]
+fn keyed_server_restart_test() {
+    let error = restart(keyed_provider);
+    assert!(
+        matches!(error, connection::Error::StatelessReset { .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn unkeyed_server_restart_test() {
+    let error = restart(stateless_reset_token::Default::default);
+    assert!(
+        matches!(error, connection::Error::IdleTimerExpired { .. }),
+        "{error:?}"
+    );
+}
+
+/// Servers with different keys should not be able to reset each other's connections
+#[test]
+fn different_key_restart_test() {
+    let keys = Arc::new(Mutex::new(vec![KEY, &[7; 32]]));
+    let error = restart(move || {
+        let key = keys.lock().unwrap().remove(0);
+        stateless_reset_token::Default::builder()
+            .with_key(key)
+            .unwrap()
+            .build()
+            .unwrap()
+    });
+    assert!(
+        matches!(error, connection::Error::IdleTimerExpired { .. }),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,75 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic_core::{event::api::EcnState, path::MINIMUM_MAX_DATAGRAM_SIZE};
+
+#[test]
+fn stats_test() {
+    let model = Model::default();
+    model
+        .set_delay(Duration::from_millis(50))
+        .set_drop_rate(0.01);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let len = 1_000_000;
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            let mut data = Data::new(len);
+            while let Some(chunk) = data.send_one(usize::MAX) {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.finish().unwrap();
+
+            while stream.receive().await.unwrap().is_some() {}
+
+            let stats = connection.handle().stats().unwrap();
+
+            // the data is echoed back, so it's counted in both directions
+            assert!(stats.bytes_sent > len);
+            assert!(stats.bytes_received > len);
+            assert!(stats.packets_sent > 0);
+            assert!(stats.packets_lost > 0);
+            assert!(stats.packets_retransmitted <= stats.packets_lost);
+            assert!(stats.send_credit > 0);
+            assert!(stats.receive_credit > 0);
+
+            assert_eq!(stats.paths.len(), 1);
+            let path = stats.active_path().unwrap();
+            assert_eq!(path.remote_address, addr.into());
+            assert!(path.is_validated);
+            // the simulated round trip is 100ms, minus timer granularity
+            let rtt = Duration::from_millis(99);
+            assert!(path.smoothed_rtt >= rtt);
+            assert!(path.min_rtt >= rtt);
+            assert!(path.latest_rtt >= rtt);
+            assert!(path.congestion_window > 0);
+            assert!(path.pacing_rate.is_some());
+            assert!(path.mtu >= MINIMUM_MAX_DATAGRAM_SIZE);
+            assert!(matches!(path.ecn_state, EcnState::Capable { .. }));
+            assert_eq!(path.packets_sent, stats.packets_sent);
+            assert_eq!(path.bytes_sent, stats.bytes_sent);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
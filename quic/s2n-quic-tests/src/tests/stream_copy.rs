@@ -0,0 +1,83 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bytes::BytesMut;
+
+/// Echoes data back to the client using the `tokio::io` adapters, which copy directly between
+/// the caller's buffers and the stream
+fn start_copy_server(mut server: Server) -> s2n_quic::provider::io::testing::Result<SocketAddr> {
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+                    spawn(async move {
+                        let (mut rx, mut tx) = stream.split();
+                        tokio::io::copy(&mut rx, &mut tx).await.unwrap();
+                        tx.close().await.unwrap();
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+#[test]
+fn send_from_receive_into_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_copy_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut rx, mut tx) = stream.split();
+
+            let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+            let expected = data.clone();
+            primary::spawn(async move {
+                tx.send_from(&data).await.unwrap();
+                tx.finish().unwrap();
+            });
+
+            let mut received = BytesMut::new();
+            loop {
+                // receive into a small buffer to exercise partial reads
+                let mut buf = BytesMut::with_capacity(1000);
+                let len = rx.receive_into(&mut buf).await.unwrap();
+                assert_eq!(len, buf.len());
+
+                if len == 0 {
+                    break;
+                }
+
+                received.extend_from_slice(&buf);
+            }
+
+            assert_eq!(&received[..], &expected[..]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::stream::{PeerStream, Priority};
+
+const LEN: usize = 200_000;
+
+/// Sends two streams at once and records the order in which the server finishes receiving them
+fn completion_order(urgent_first: bool) -> Vec<u8> {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    let completed = Arc::new(Mutex::new(vec![]));
+    let server_completed = completed.clone();
+
+    test(model, move |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            while let Ok(Some(PeerStream::Receive(mut stream))) = connection.accept().await {
+                let completed = server_completed.clone();
+                spawn(async move {
+                    let mut id = None;
+                    let mut len = 0;
+                    while let Some(chunk) = stream.receive().await.unwrap() {
+                        id = id.or_else(|| chunk.first().copied());
+                        len += chunk.len();
+                    }
+                    assert_eq!(len, LEN);
+                    completed.lock().unwrap().push(id.unwrap());
+                });
+            }
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut low = connection.open_send_stream().await.unwrap();
+            low.set_priority(Priority::new(7, false).unwrap()).unwrap();
+
+            let mut high = connection.open_send_stream().await.unwrap();
+            let high_priority = if urgent_first { 0 } else { 7 };
+            high.set_priority(Priority::new(high_priority, false).unwrap())
+                .unwrap();
+
+            // the `low` stream is written first so it would be sent first without priorities
+            low.send(Bytes::from(vec![1u8; LEN])).await.unwrap();
+            high.send(Bytes::from(vec![2u8; LEN])).await.unwrap();
+            low.finish().unwrap();
+            high.finish().unwrap();
+
+            // wait for the peer to receive everything before closing
+            delay(Duration::from_secs(5)).await;
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let completed = completed.lock().unwrap().clone();
+    completed
+}
+
+#[test]
+fn urgent_stream_completes_first() {
+    assert_eq!(completion_order(true), [2, 1]);
+}
+
+#[test]
+fn equal_non_incremental_streams_complete_in_order() {
+    assert_eq!(completion_order(false), [1, 2]);
+}
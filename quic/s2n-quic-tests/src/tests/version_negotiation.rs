@@ -0,0 +1,186 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::provider::version::{self, VERSION_1, VERSION_2};
+use s2n_quic_core::event::api::PacketHeader;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct VersionInformation {
+    server_versions: Vec<u32>,
+    client_versions: Vec<u32>,
+    chosen_version: Option<u32>,
+}
+
+#[derive(Clone, Default)]
+struct VersionRecorder {
+    version_information: Arc<Mutex<Vec<VersionInformation>>>,
+    /// The versions of the long header packets sent by the endpoint
+    packet_versions: Arc<Mutex<Vec<u32>>>,
+}
+
+impl events::Subscriber for VersionRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_version_information(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::VersionInformation,
+    ) {
+        self.version_information
+            .lock()
+            .unwrap()
+            .push(VersionInformation {
+                server_versions: event.server_versions.to_vec(),
+                client_versions: event.client_versions.to_vec(),
+                chosen_version: event.chosen_version,
+            });
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        let version = match event.packet_header {
+            PacketHeader::Initial { version, .. } | PacketHeader::Handshake { version, .. } => {
+                version
+            }
+            _ => return,
+        };
+        self.packet_versions.lock().unwrap().push(version);
+    }
+}
+
+fn versions(version_2: bool, preferred: u32) -> version::Config {
+    version::Config::builder()
+        .with_version_2(version_2)
+        .unwrap()
+        .with_preferred_version(preferred)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+fn version_negotiation_test(
+    server_versions: version::Config,
+    client_versions: version::Config,
+) -> (VersionRecorder, VersionRecorder) {
+    let model = Model::default();
+    let server_recorder = VersionRecorder::default();
+    let client_recorder = VersionRecorder::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_recorder.clone()))?
+            .with_random(Random::with_seed(456))?
+            .with_version(server_versions)?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_recorder.clone()))?
+            .with_random(Random::with_seed(456))?
+            .with_version(client_versions)?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    (server_recorder, client_recorder)
+}
+
+#[test]
+fn compatible_version_upgrade_test() {
+    let (server, client) =
+        version_negotiation_test(versions(true, VERSION_2), versions(true, VERSION_1));
+
+    // The client starts with version 1 and switches to version 2 after the server's
+    // first Initial packet
+    let client_packets = client.packet_versions.lock().unwrap();
+    assert_eq!(client_packets.first(), Some(&VERSION_1));
+    assert_eq!(client_packets.last(), Some(&VERSION_2));
+
+    // The server only sends version 2 packets
+    let server_packets = server.packet_versions.lock().unwrap();
+    assert!(server_packets.iter().all(|version| *version == VERSION_2));
+
+    let expected = VersionInformation {
+        server_versions: vec![VERSION_2, VERSION_1],
+        client_versions: vec![VERSION_1, VERSION_2],
+        chosen_version: Some(VERSION_2),
+    };
+    assert!(server
+        .version_information
+        .lock()
+        .unwrap()
+        .contains(&expected));
+    assert_eq!(
+        client.version_information.lock().unwrap().as_slice(),
+        &[expected]
+    );
+}
+
+#[test]
+fn compatible_version_not_available_test() {
+    // The client doesn't enable version 2 so the server keeps using version 1
+    let (server, client) =
+        version_negotiation_test(versions(true, VERSION_2), versions(false, VERSION_1));
+
+    assert!(client
+        .packet_versions
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|version| *version == VERSION_1));
+    assert!(server
+        .packet_versions
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|version| *version == VERSION_1));
+
+    // The client only offers the version it started with
+    assert_eq!(
+        client.version_information.lock().unwrap().as_slice(),
+        &[VersionInformation {
+            server_versions: vec![VERSION_2, VERSION_1],
+            client_versions: vec![VERSION_1],
+            chosen_version: Some(VERSION_1),
+        }]
+    );
+}
+
+#[test]
+fn compatible_version_server_prefers_original_test() {
+    let (server, client) =
+        version_negotiation_test(versions(true, VERSION_1), versions(true, VERSION_1));
+
+    assert!(server
+        .packet_versions
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|version| *version == VERSION_1));
+
+    assert_eq!(
+        client.version_information.lock().unwrap().as_slice(),
+        &[VersionInformation {
+            server_versions: vec![VERSION_1, VERSION_2],
+            client_versions: vec![VERSION_1, VERSION_2],
+            chosen_version: Some(VERSION_1),
+        }]
+    );
+}
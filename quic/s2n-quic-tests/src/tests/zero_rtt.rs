@@ -0,0 +1,379 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic::{
+    connection,
+    provider::{early_data, limits::Limits, tls::rustls},
+};
+use s2n_quic_core::event::api::PacketHeader;
+
+#[derive(Clone, Default)]
+struct EarlyDataRecorder {
+    /// The server's decision on the early data for each connection
+    statuses: Arc<Mutex<Vec<events::ZeroRttStatus>>>,
+    /// The number of 0-RTT packets sent by the client
+    zero_rtt_packets: Arc<Mutex<usize>>,
+}
+
+impl events::Subscriber for EarlyDataRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_zero_rtt_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::ZeroRttStatusUpdated,
+    ) {
+        self.statuses.lock().unwrap().push(event.status.clone());
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        if matches!(event.packet_header, PacketHeader::ZeroRtt { .. }) {
+            *self.zero_rtt_packets.lock().unwrap() += 1;
+        }
+    }
+}
+
+/// Builds a rustls server that issues session tickets which allow early data
+fn server_tls() -> rustls::Server {
+    rustls::Server::builder()
+        .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+        .unwrap()
+        .with_early_data(true)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+fn client_tls() -> rustls::Client {
+    rustls::Client::builder()
+        .with_certificate(certificates::CERT_PEM)
+        .unwrap()
+        .with_early_data(true)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Starts a server that echoes back each stream once it was fully received
+///
+/// For each stream, the server records if any of the data was received as early data.
+fn start_early_data_server<E: early_data::Provider>(
+    handle: &Handle,
+    early_data: E,
+    early_data_streams: Arc<Mutex<Vec<bool>>>,
+    limits: Limits,
+) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(server_tls())?
+        .with_early_data(early_data)?
+        .with_limits(limits)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            let early_data_streams = early_data_streams.clone();
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    let early_data_streams = early_data_streams.clone();
+                    spawn(async move {
+                        let mut chunks = vec![];
+                        loop {
+                            match stream.receive().await {
+                                Ok(Some(chunk)) => chunks.push(chunk),
+                                Ok(None) => break,
+                                // streams that were never finished aren't echoed
+                                Err(_) => return,
+                            }
+                        }
+
+                        let is_early_data = stream.is_early_data().unwrap();
+                        early_data_streams.lock().unwrap().push(is_early_data);
+
+                        for chunk in chunks {
+                            let _ = stream.send(chunk).await;
+                        }
+                        let _ = stream.finish();
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Opens a connection with early data enabled and checks the stream is echoed back
+async fn echo(client: &Client, server_addr: SocketAddr, data: Data) {
+    let connect = Connect::new(server_addr)
+        .with_server_name("localhost")
+        .with_zero_rtt(true);
+    let mut connection = client.connect(connect).await.unwrap();
+
+    let stream = connection.open_bidirectional_stream().await.unwrap();
+    let (mut recv, mut send) = stream.split();
+
+    let mut send_data = data;
+    while let Some(chunk) = send_data.send_one(usize::MAX) {
+        send.send(chunk).await.unwrap();
+    }
+    send.finish().unwrap();
+
+    let mut recv_data = data;
+    while let Some(chunk) = recv.receive().await.unwrap() {
+        recv_data.receive(&[chunk]);
+    }
+    assert!(recv_data.is_finished());
+}
+
+/// The result of connecting twice to the early data servers
+#[derive(Default)]
+struct Outcome {
+    client: EarlyDataRecorder,
+    /// If each stream received by the servers contained early data
+    early_data_streams: Arc<Mutex<Vec<bool>>>,
+}
+
+/// Connects twice, resuming the session of the first connection with early data
+///
+/// If `same_server` is false, the second connection is made to a server that doesn't know
+/// about the session ticket.
+fn zero_rtt_test<E: early_data::Provider>(same_server: bool, early_data: fn() -> E) -> Outcome {
+    let model = Model::default();
+    let outcome = Outcome::default();
+
+    test(model, |handle| {
+        let streams = outcome.early_data_streams.clone();
+        let first_addr =
+            start_early_data_server(handle, early_data(), streams.clone(), Limits::default())?;
+        let second_addr = if same_server {
+            first_addr
+        } else {
+            start_early_data_server(handle, early_data(), streams, Limits::default())?
+        };
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls())?
+            .with_event((tracing_events(), outcome.client.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let recorder = outcome.client.clone();
+        primary::spawn(async move {
+            // there isn't a session ticket yet so the first connection does a full handshake
+            echo(&client, first_addr, Data::new(1000)).await;
+            assert_eq!(*recorder.zero_rtt_packets.lock().unwrap(), 0);
+            assert!(recorder.statuses.lock().unwrap().is_empty());
+
+            echo(&client, second_addr, Data::new(10_000)).await;
+        });
+
+        Ok(first_addr)
+    })
+    .unwrap();
+
+    // the stream was written before the handshake completed
+    assert!(*outcome.client.zero_rtt_packets.lock().unwrap() > 0);
+
+    outcome
+}
+
+#[test]
+fn zero_rtt_accepted_test() {
+    let outcome = zero_rtt_test(true, early_data::allow_all::Validator::default);
+
+    let statuses = outcome.client.statuses.lock().unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert!(matches!(
+        statuses[0],
+        events::ZeroRttStatus::Accepted { .. }
+    ));
+
+    // only the stream on the resumed connection was received as early data
+    let early_data_streams = outcome.early_data_streams.lock().unwrap();
+    assert_eq!(*early_data_streams, [false, true]);
+}
+
+#[test]
+fn zero_rtt_rejected_test() {
+    let outcome = zero_rtt_test(false, early_data::allow_all::Validator::default);
+
+    // the data from the 0-RTT packets was retransmitted after the server rejected it
+    let statuses = outcome.client.statuses.lock().unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert!(matches!(
+        statuses[0],
+        events::ZeroRttStatus::Rejected { .. }
+    ));
+
+    let early_data_streams = outcome.early_data_streams.lock().unwrap();
+    assert_eq!(*early_data_streams, [false, false]);
+}
+
+#[test]
+fn zero_rtt_refused_test() {
+    // the TLS provider can resume the session but the early data provider refuses the data
+    let outcome = zero_rtt_test(true, early_data::disabled::Validator::default);
+
+    let statuses = outcome.client.statuses.lock().unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert!(matches!(
+        statuses[0],
+        events::ZeroRttStatus::Rejected { .. }
+    ));
+
+    let early_data_streams = outcome.early_data_streams.lock().unwrap();
+    assert_eq!(*early_data_streams, [false, false]);
+}
+
+#[test]
+fn zero_rtt_default_provider_test() {
+    // the default provider accepts early data from fresh ClientHello messages it hasn't seen before
+    // for tickets it issued
+    let outcome = zero_rtt_test(true, early_data::Default::default);
+
+    let statuses = outcome.client.statuses.lock().unwrap();
+    assert!(matches!(
+        statuses[..],
+        [events::ZeroRttStatus::Accepted { .. }]
+    ));
+}
+
+#[test]
+fn zero_rtt_rejected_with_reduced_limits_test() {
+    let model = Model::default();
+    let outcome = Outcome::default();
+
+    test(model, |handle| {
+        let streams = outcome.early_data_streams.clone();
+        let first_addr = start_early_data_server(
+            handle,
+            early_data::allow_all::Validator,
+            streams.clone(),
+            Limits::default(),
+        )?;
+
+        // the second server doesn't know about the session ticket and allows for less data on
+        // each stream than the client remembered from the first server
+        let limits = Limits::default().with_bidirectional_remote_data_window(1000)?;
+        let second_addr =
+            start_early_data_server(handle, early_data::allow_all::Validator, streams, limits)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls())?
+            .with_event((tracing_events(), outcome.client.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            echo(&client, first_addr, Data::new(1000)).await;
+
+            let connect = Connect::new(second_addr)
+                .with_server_name("localhost")
+                .with_zero_rtt(true);
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the stream sends more data in 0-RTT than the second server allows
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+            let mut send_data = Data::new(10_000);
+            while let Some(chunk) = send_data.send_one(usize::MAX) {
+                if send.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+            let _ = send.finish();
+
+            // the data can't be retransmitted within the new limits so the stream is reset
+            loop {
+                match recv.receive().await {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => panic!("the stream should be reset"),
+                    Err(_) => break,
+                }
+            }
+
+            // the connection is still usable within the new limits
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+            let data = Data::new(500);
+            let mut send_data = data;
+            while let Some(chunk) = send_data.send_one(usize::MAX) {
+                send.send(chunk).await.unwrap();
+            }
+            send.finish().unwrap();
+
+            let mut recv_data = data;
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+        });
+
+        Ok(first_addr)
+    })
+    .unwrap();
+
+    assert!(*outcome.client.zero_rtt_packets.lock().unwrap() > 0);
+
+    let statuses = outcome.client.statuses.lock().unwrap();
+    assert!(matches!(
+        statuses[..],
+        [events::ZeroRttStatus::Rejected { .. }]
+    ));
+
+    // only the stream opened after the rejection was echoed by the second server
+    let early_data_streams = outcome.early_data_streams.lock().unwrap();
+    assert_eq!(*early_data_streams, [false, false]);
+}
+
+#[test]
+fn zero_rtt_unsupported_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        // the client's TLS provider isn't configured to send early data
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr)
+                .with_server_name("localhost")
+                .with_zero_rtt(true);
+            let error = client.connect(connect).await.unwrap_err();
+            assert_eq!(
+                error,
+                connection::Error::invalid_configuration(
+                    "Requested 0-RTT which is not supported by the configured TLS provider",
+                )
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -211,9 +211,6 @@ where
 
                 match self.state.tx_phase {
                     HandshakePhase::Initial => {
-                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
-                            .expect("invalid cipher");
-
                         if !self.server_params.is_empty() {
                             debug_assert!(self.endpoint.is_server());
 
@@ -244,13 +241,29 @@ where
                             self.server_params.clear();
                         }
 
+                        // The handshake keys are derived after processing the client transport
+                        // parameters, which may have switched the connection to a compatible
+                        // version
+                        let (key, header_key) = HandshakeKey::new(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
+
                         self.context.on_handshake_keys(key, header_key)?;
                         self.state.tx_phase.transition();
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
-                            OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        let (key, header_key) = OneRttKey::new(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
                         // At this point the server is done writing Handshake messages
                         if self.endpoint.is_server() {
                             self.state.tx_phase.transition();
@@ -3,7 +3,7 @@
 
 use crate::{
     certificate::{IntoCertificate, IntoPrivateKey},
-    keylog::KeyLogHandle,
+    keylog::{KeyLog, KeyLogHandle},
     params::Params,
     session::Session,
     ConfigLoader,
@@ -16,7 +16,7 @@ use s2n_tls::{
     enums::ClientAuthType,
     error::Error,
 };
-use std::sync::Arc;
+use std::{io::Write, sync::Arc};
 
 pub struct Client<L: ConfigLoader = Config> {
     loader: L,
@@ -165,11 +165,22 @@ impl Builder {
         Ok(self)
     }
 
+    /// Writes the TLS secrets to the file specified by the `SSLKEYLOGFILE` environment variable
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
-        use crate::keylog::KeyLog;
-
         self.keylog = KeyLog::try_open();
+        self.set_key_log_callback()
+    }
 
+    /// Writes the TLS secrets to the provided writer in the
+    /// [NSS Key Log Format](https://datatracker.ietf.org/doc/draft-ietf-tls-keylogfile/)
+    ///
+    /// Each line is followed by a call to [`Write::flush`].
+    pub fn with_key_log<W: 'static + Write + Send>(mut self, writer: W) -> Result<Self, Error> {
+        self.keylog = Some(KeyLog::new(writer));
+        self.set_key_log_callback()
+    }
+
+    fn set_key_log_callback(mut self) -> Result<Self, Error> {
         unsafe {
             // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
             if let Some(keylog) = self.keylog.as_ref() {
@@ -220,4 +231,8 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        true
+    }
 }
@@ -4,14 +4,14 @@
 use libc::{c_int, c_void};
 use s2n_tls::ffi::*;
 use std::{
-    fs::{File, OpenOptions},
+    fs::OpenOptions,
     io::{BufWriter, Write},
     sync::{Arc, Mutex},
 };
 
 pub type KeyLogHandle = Arc<KeyLog>;
 
-pub struct KeyLog(Mutex<BufWriter<File>>);
+pub struct KeyLog(Mutex<Box<dyn Write + Send>>);
 
 impl KeyLog {
     pub fn try_open() -> Option<KeyLogHandle> {
@@ -22,10 +22,14 @@ impl KeyLog {
             .open(path)
             .ok()?;
         let file = BufWriter::new(file);
-        let file = Mutex::new(file);
-        let keylog = Self(file);
-        let keylog = Arc::new(keylog);
-        Some(keylog)
+        Some(Self::new(file))
+    }
+
+    pub fn new<W: 'static + Write + Send>(writer: W) -> KeyLogHandle {
+        let writer: Box<dyn Write + Send> = Box::new(writer);
+        let writer = Mutex::new(writer);
+        let keylog = Self(writer);
+        Arc::new(keylog)
     }
 
     pub unsafe extern "C" fn callback(
@@ -44,12 +48,12 @@ impl KeyLog {
     }
 
     fn on_logline(&mut self, logline: &[u8]) -> Option<()> {
-        let mut file = self.0.lock().ok()?;
-        file.write_all(logline).ok()?;
-        file.write_all(b"\n").ok()?;
+        let mut writer = self.0.lock().ok()?;
+        writer.write_all(logline).ok()?;
+        writer.write_all(b"\n").ok()?;
 
         // ensure keys are immediately written so tools can use them
-        file.flush().ok()?;
+        writer.flush().ok()?;
 
         Some(())
     }
@@ -15,6 +15,7 @@ use s2n_quic_core::{
         Endpoint,
     },
     transport,
+    version::{Version, VERSION_1, VERSION_2},
 };
 #[cfg(any(test, feature = "unstable_client_hello"))]
 use s2n_tls::callbacks::ClientHelloCallback;
@@ -338,6 +339,15 @@ fn s2n_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_version_2_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server();
+
+    run_result_with_version(&mut server_endpoint, &mut client_endpoint, None, VERSION_2).unwrap();
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_resumption_test() {
@@ -373,6 +383,15 @@ fn rustls_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_client_s2n_server_version_2_test() {
+    let mut client_endpoint = rustls_client();
+    let mut server_endpoint = s2n_server();
+
+    run_result_with_version(&mut server_endpoint, &mut client_endpoint, None, VERSION_2).unwrap();
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_rustls_server_test() {
@@ -480,7 +499,16 @@ fn run_result<S: Endpoint, C: Endpoint>(
     client: &mut C,
     client_hello_cb_done: Option<Arc<AtomicBool>>,
 ) -> Result<tls::testing::Pair<S::Session, C::Session>, transport::Error> {
-    let mut pair = tls::testing::Pair::new(server, client, "localhost".into());
+    run_result_with_version(server, client, client_hello_cb_done, VERSION_1)
+}
+
+fn run_result_with_version<S: Endpoint, C: Endpoint>(
+    server: &mut S,
+    client: &mut C,
+    client_hello_cb_done: Option<Arc<AtomicBool>>,
+    version: Version,
+) -> Result<tls::testing::Pair<S::Session, C::Session>, transport::Error> {
+    let mut pair = tls::testing::Pair::with_version(server, client, "localhost".into(), version);
 
     while pair.is_handshaking() {
         pair.poll(client_hello_cb_done.as_ref())?;
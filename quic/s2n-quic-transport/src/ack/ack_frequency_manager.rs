@@ -0,0 +1,285 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Requests the peer adjusts how often it acknowledges packets
+//!
+//! If the peer advertised support for the ACK frequency extension with the `min_ack_delay`
+//! transport parameter, the congestion controller may request an ACK-eliciting threshold
+//! other than the default. When it does, an ACK_FREQUENCY frame is passively transmitted in
+//! the next outgoing packet.
+
+use crate::{contexts::WriteContext, transmission};
+use core::time::Duration;
+use s2n_quic_core::{
+    ack,
+    frame::AckFrequency,
+    packet::number::PacketNumber,
+    transport::parameters::{MaxAckDelay, MinAckDelay},
+    varint::VarInt,
+};
+
+/// The ACK-eliciting threshold a peer uses when it hasn't received an ACK_FREQUENCY frame
+const DEFAULT_ACK_ELICITING_THRESHOLD: VarInt = VarInt::from_u8(1);
+
+/// The reordering threshold a peer uses when it hasn't received an ACK_FREQUENCY frame
+const DEFAULT_REORDERING_THRESHOLD: VarInt = VarInt::from_u8(1);
+
+#[derive(Debug, Default)]
+pub struct AckFrequencyManager {
+    /// The `max_ack_delay` the peer will be asked to use, or `None` if the peer does not
+    /// support the ACK frequency extension
+    request_max_ack_delay: Option<Duration>,
+
+    /// The sequence number of the next ACK_FREQUENCY frame
+    next_sequence_number: VarInt,
+
+    /// The ACK-eliciting threshold most recently sent to the peer
+    ack_eliciting_threshold: VarInt,
+
+    state: State,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum State {
+    /// The peer is up to date with the most recently sent threshold
+    #[default]
+    Idle,
+
+    /// An ACK_FREQUENCY frame has been sent and is waiting to be acknowledged
+    InFlight(PacketNumber),
+
+    /// The most recent ACK_FREQUENCY frame was lost and needs to be sent again
+    Lost,
+}
+
+impl AckFrequencyManager {
+    /// Creates a manager for a peer with the given transport parameters
+    pub fn new(max_ack_delay: MaxAckDelay, min_ack_delay: Option<MinAckDelay>) -> Self {
+        // The peer validated `min_ack_delay` is not greater than `max_ack_delay` so
+        // requesting the peer's own `max_ack_delay` is always permitted
+        let request_max_ack_delay = min_ack_delay.map(|_| max_ack_delay.as_duration());
+
+        Self {
+            request_max_ack_delay,
+            next_sequence_number: VarInt::ZERO,
+            ack_eliciting_threshold: DEFAULT_ACK_ELICITING_THRESHOLD,
+            state: State::Idle,
+        }
+    }
+
+    /// Returns `true` if the peer supports receiving ACK_FREQUENCY frames
+    pub fn is_enabled(&self) -> bool {
+        self.request_max_ack_delay.is_some()
+    }
+
+    /// Called when an outgoing packet is being assembled
+    ///
+    /// `ack_eliciting_threshold` is the threshold requested by the congestion controller,
+    /// or `None` to use the default threshold.
+    pub fn on_transmit<W: WriteContext>(
+        &mut self,
+        ack_eliciting_threshold: Option<u32>,
+        context: &mut W,
+    ) {
+        let Some(request_max_ack_delay) = self.request_max_ack_delay else {
+            return;
+        };
+
+        let ack_eliciting_threshold = ack_eliciting_threshold
+            .map(VarInt::from_u32)
+            .unwrap_or(DEFAULT_ACK_ELICITING_THRESHOLD);
+
+        let constraint = context.transmission_constraint();
+        let should_transmit = match self.state {
+            State::Idle => {
+                constraint.can_transmit() && ack_eliciting_threshold != self.ack_eliciting_threshold
+            }
+            // only send a new threshold after the previous one has been acknowledged
+            State::InFlight(_) => false,
+            State::Lost => constraint.can_retransmit(),
+        };
+
+        if !should_transmit {
+            return;
+        }
+
+        let frame = AckFrequency {
+            sequence_number: self.next_sequence_number,
+            ack_eliciting_threshold,
+            request_max_ack_delay: request_max_ack_delay
+                .as_micros()
+                .try_into()
+                .unwrap_or(VarInt::MAX),
+            reordering_threshold: DEFAULT_REORDERING_THRESHOLD,
+        };
+
+        if let Some(packet_number) = context.write_frame(&frame) {
+            // Sequence numbers are never reused so the peer can discard stale frames
+            self.next_sequence_number = self
+                .next_sequence_number
+                .checked_add_usize(1)
+                .expect("sequence number cannot exceed VarInt::MAX");
+            self.ack_eliciting_threshold = ack_eliciting_threshold;
+            self.state = State::InFlight(packet_number);
+        }
+    }
+
+    /// Called when a set of packets was acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_number) = self.state {
+            if ack_set.contains(packet_number) {
+                self.state = State::Idle;
+            }
+        }
+    }
+
+    /// Called when a set of packets was reported lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_number) = self.state {
+            if ack_set.contains(packet_number) {
+                self.state = State::Lost;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for AckFrequencyManager {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        if self.state == State::Lost {
+            query.on_lost_data()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        contexts::testing::{MockWriteContext, OutgoingFrameBuffer},
+        transmission::interest::Provider as _,
+    };
+    use s2n_quic_core::{
+        endpoint, frame::Frame, packet::number::PacketNumberRange, time::clock::testing as time,
+    };
+
+    fn min_ack_delay() -> Option<MinAckDelay> {
+        Some(MinAckDelay::new(Duration::from_millis(1)).unwrap())
+    }
+
+    #[test]
+    fn disabled_without_min_ack_delay() {
+        let mut manager = AckFrequencyManager::new(MaxAckDelay::RECOMMENDED, None);
+        assert!(!manager.is_enabled());
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+
+        manager.on_transmit(Some(4), &mut context);
+        assert!(context.frame_buffer.is_empty());
+    }
+
+    #[test]
+    fn on_transmit() {
+        let mut manager = AckFrequencyManager::new(MaxAckDelay::RECOMMENDED, min_ack_delay());
+        assert!(manager.is_enabled());
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+
+        // nothing is sent while the default threshold is requested
+        manager.on_transmit(None, &mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        // nothing is sent if only retransmissions are allowed
+        context.transmission_constraint = transmission::Constraint::RetransmissionOnly;
+        manager.on_transmit(Some(4), &mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        context.transmission_constraint = transmission::Constraint::None;
+        let pn = context.packet_number();
+        manager.on_transmit(Some(4), &mut context);
+        let expected = AckFrequency {
+            sequence_number: VarInt::ZERO,
+            ack_eliciting_threshold: VarInt::from_u8(4),
+            request_max_ack_delay: VarInt::from_u16(25_000),
+            reordering_threshold: VarInt::from_u8(1),
+        };
+        assert_eq!(
+            Frame::AckFrequency(expected),
+            context.frame_buffer.pop_front().unwrap().as_frame()
+        );
+
+        // a new threshold isn't sent until the previous frame is acknowledged
+        manager.on_transmit(Some(8), &mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        manager.on_packet_ack(&PacketNumberRange::new(pn, pn));
+        manager.on_transmit(Some(4), &mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        manager.on_transmit(Some(8), &mut context);
+        assert_eq!(
+            Frame::AckFrequency(AckFrequency {
+                sequence_number: VarInt::from_u8(1),
+                ack_eliciting_threshold: VarInt::from_u8(8),
+                ..expected
+            }),
+            context.frame_buffer.pop_front().unwrap().as_frame()
+        );
+    }
+
+    #[test]
+    fn on_packet_loss() {
+        let mut manager = AckFrequencyManager::new(MaxAckDelay::RECOMMENDED, min_ack_delay());
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+
+        let pn = context.packet_number();
+        manager.on_transmit(Some(4), &mut context);
+        assert_eq!(1, context.frame_buffer.len());
+        context.frame_buffer.clear();
+        assert!(!manager.has_transmission_interest());
+
+        manager.on_packet_loss(&PacketNumberRange::new(pn, pn));
+        assert!(manager.has_transmission_interest());
+
+        // the threshold is retransmitted with a new sequence number
+        context.transmission_constraint = transmission::Constraint::RetransmissionOnly;
+        manager.on_transmit(Some(4), &mut context);
+        assert_eq!(
+            Frame::AckFrequency(AckFrequency {
+                sequence_number: VarInt::from_u8(1),
+                ack_eliciting_threshold: VarInt::from_u8(4),
+                request_max_ack_delay: VarInt::from_u16(25_000),
+                reordering_threshold: VarInt::from_u8(1),
+            }),
+            context.frame_buffer.pop_front().unwrap().as_frame()
+        );
+        assert!(!manager.has_transmission_interest());
+    }
+}
@@ -13,12 +13,24 @@ use s2n_quic_core::{
         builder::{AckAction, AckProcessed},
         IntoEvent as _,
     },
-    frame::{ack::EcnCounts, Ack, Ping},
+    frame::{ack::EcnCounts, Ack, AckFrequency, Ping},
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timer, Timestamp},
+    transport,
     varint::VarInt,
 };
 
+// TODO update to draft link after published
+// https://github.com/quicwg/base-drafts/pull/3623
+// An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
+/// The number of processed packets before an ACK frame is sent immediately, unless the
+/// peer requested otherwise with an ACK_FREQUENCY frame
+const DEFAULT_PACKET_TOLERANCE: u8 = 10;
+
+/// The number of missing packets before an ACK frame is sent immediately, unless the
+/// peer requested otherwise with an ACK_FREQUENCY frame
+const DEFAULT_REORDERING_THRESHOLD: VarInt = VarInt::from_u8(1);
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2
 //# Endpoints acknowledge all packets they receive and process.  However,
 //# only ack-eliciting packets cause an ACK frame to be sent within the
@@ -66,6 +78,17 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// The number of processed packets before an ACK frame is sent immediately
+    packet_tolerance: u8,
+
+    /// The number of missing packets before an ACK frame is sent immediately
+    ///
+    /// A value of `0` disables immediate acknowledgements of out-of-order packets.
+    reordering_threshold: VarInt,
+
+    /// The sequence number of the most recently applied ACK_FREQUENCY frame
+    ack_frequency_sequence_number: Option<VarInt>,
 }
 
 impl AckManager {
@@ -82,6 +105,9 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            packet_tolerance: DEFAULT_PACKET_TOLERANCE,
+            reordering_threshold: DEFAULT_REORDERING_THRESHOLD,
+            ack_frequency_sequence_number: None,
         }
     }
 
@@ -208,19 +234,21 @@ impl AckManager {
         let now = processed_packet.datagram.timestamp;
 
         // perform some checks before inserting into the ack_ranges
-        let (is_ordered, is_largest) = self
+        let (missing_packets, is_largest) = self
             .ack_ranges
             .max_value()
             .and_then(|max_value| {
-                // check to see if the packet number is the next one in the sequence
-                let is_ordered = packet_number == max_value.next()?;
+                // count the packet numbers skipped between the largest packet number and this one
+                let missing_packets = packet_number
+                    .as_u64()
+                    .saturating_sub(max_value.next()?.as_u64());
 
                 // check to see if the packet number is the largest we've seen
                 let is_largest = packet_number > max_value;
 
-                Some((is_ordered, is_largest))
+                Some((missing_packets, is_largest))
             })
-            .unwrap_or((true, true));
+            .unwrap_or((0, true));
 
         // This will fail if `packet_number` is less than `ack_ranges.min_value()`
         // and `ack_ranges` is at capacity.
@@ -295,14 +323,19 @@ impl AckManager {
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
 
-            should_activate |= !is_largest;
+            // The peer can disable immediate acknowledgements of out-of-order packets by
+            // sending a reordering threshold of 0 in an ACK_FREQUENCY frame.
+            let is_reordering_enabled = self.reordering_threshold != VarInt::ZERO;
+
+            should_activate |= is_reordering_enabled && !is_largest;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            should_activate |=
+                is_reordering_enabled && missing_packets >= self.reordering_threshold.as_u64();
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
@@ -310,15 +343,10 @@ impl AckManager {
             //# reduce the peer's response time to congestion events.
             should_activate |= processed_packet.datagram.ecn.congestion_experienced();
 
-            // TODO update to draft link after published
-            // https://github.com/quicwg/base-drafts/pull/3623
-            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
-
-            // TODO support delayed ack proposal
-            // https://tools.ietf.org/html/draft-iyengar-quic-delayed-ack-00
-            let packet_tolerance = 10;
+            should_activate |= self.processed_packets_since_transmission >= self.packet_tolerance;
 
-            should_activate |= self.processed_packets_since_transmission >= packet_tolerance;
+            // The peer requested an acknowledgement for this packet with an IMMEDIATE_ACK frame
+            should_activate |= processed_packet.immediate_ack;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.3
             //# An endpoint that receives a PATH_CHALLENGE on an active path SHOULD
@@ -345,6 +373,48 @@ impl AckManager {
         }
     }
 
+    /// Called when an ACK_FREQUENCY frame is received from the peer
+    ///
+    /// Returns an error if the frame is not allowed by the locally configured `min_ack_delay`.
+    pub fn on_ack_frequency_frame(&mut self, frame: &AckFrequency) -> Result<(), transport::Error> {
+        // The peer may only send ACK_FREQUENCY frames if the `min_ack_delay` transport
+        // parameter was sent
+        let min_ack_delay = self.ack_settings.min_ack_delay.ok_or_else(|| {
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("ACK_FREQUENCY frames are not supported")
+                .with_frame_type(frame.tag())
+        })?;
+
+        // The requested delay can't be less than the delay the endpoint is willing to use
+        if frame.request_max_ack_delay() < min_ack_delay {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("requested max ack delay is less than min_ack_delay")
+                .with_frame_type(frame.tag()));
+        }
+
+        // Frames may arrive out of order so only the frame with the largest sequence
+        // number is used
+        if self
+            .ack_frequency_sequence_number
+            .is_some_and(|sequence_number| frame.sequence_number <= sequence_number)
+        {
+            return Ok(());
+        }
+        self.ack_frequency_sequence_number = Some(frame.sequence_number);
+
+        // An ACK frame is sent once more than `ack_eliciting_threshold` packets are received
+        self.packet_tolerance = frame
+            .ack_eliciting_threshold
+            .as_u64()
+            .saturating_add(1)
+            .try_into()
+            .unwrap_or(u8::MAX);
+        self.reordering_threshold = frame.reordering_threshold;
+        self.ack_settings.max_ack_delay = frame.request_max_ack_delay();
+
+        Ok(())
+    }
+
     /// Called when the connection timer expired
     pub fn on_timeout(&mut self, timestamp: Timestamp) {
         // NOTE: ack_elicitation_timer is not actively polled
@@ -446,6 +516,138 @@ mod tests {
         assert!(manager.transmission_state.is_active());
     }
 
+    #[test]
+    fn immediate_ack() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+
+        let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+        let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+        processed_packet.immediate_ack = true;
+        processed_packet.ack_elicitation = AckElicitation::Eliciting;
+
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        manager.on_processed_packet(
+            &processed_packet,
+            path_event!(path, path_id),
+            &mut Publisher::snapshot(),
+        );
+
+        assert!(manager.transmission_state.is_active());
+    }
+
+    #[test]
+    fn ack_frequency_frame() {
+        let min_ack_delay = Duration::from_millis(1);
+        let mut manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            ack::Settings {
+                min_ack_delay: Some(min_ack_delay),
+                ..Default::default()
+            },
+        );
+
+        let frame = AckFrequency {
+            sequence_number: VarInt::from_u8(1),
+            ack_eliciting_threshold: VarInt::from_u8(3),
+            request_max_ack_delay: VarInt::from_u16(50_000),
+            reordering_threshold: VarInt::ZERO,
+        };
+        assert!(manager.on_ack_frequency_frame(&frame).is_ok());
+        assert_eq!(4, manager.packet_tolerance);
+        assert_eq!(VarInt::ZERO, manager.reordering_threshold);
+        assert_eq!(
+            Duration::from_millis(50),
+            manager.ack_settings.max_ack_delay
+        );
+
+        // a frame with an older sequence number is ignored
+        let stale = AckFrequency {
+            sequence_number: VarInt::ZERO,
+            ack_eliciting_threshold: VarInt::from_u8(1),
+            ..frame
+        };
+        assert!(manager.on_ack_frequency_frame(&stale).is_ok());
+        assert_eq!(4, manager.packet_tolerance);
+
+        // large thresholds are saturated
+        let large = AckFrequency {
+            sequence_number: VarInt::from_u8(2),
+            ack_eliciting_threshold: VarInt::from_u32(1000),
+            ..frame
+        };
+        assert!(manager.on_ack_frequency_frame(&large).is_ok());
+        assert_eq!(u8::MAX, manager.packet_tolerance);
+
+        // the requested delay can't be less than min_ack_delay
+        let too_small = AckFrequency {
+            sequence_number: VarInt::from_u8(3),
+            request_max_ack_delay: VarInt::from_u16(999),
+            ..frame
+        };
+        assert!(manager.on_ack_frequency_frame(&too_small).is_err());
+
+        // the frame isn't allowed if min_ack_delay wasn't sent
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        assert!(manager.on_ack_frequency_frame(&frame).is_err());
+    }
+
+    #[test]
+    fn reordering_threshold() {
+        let mut manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            ack::Settings {
+                min_ack_delay: Some(Duration::from_millis(1)),
+                ..Default::default()
+            },
+        );
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::snapshot();
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+
+        let mut on_packet = |manager: &mut AckManager, pn: u8| {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            manager.transmission_state.is_active()
+        };
+
+        let frame = AckFrequency {
+            sequence_number: VarInt::ZERO,
+            ack_eliciting_threshold: VarInt::from_u8(100),
+            request_max_ack_delay: VarInt::from_u16(25_000),
+            reordering_threshold: VarInt::from_u8(3),
+        };
+        manager.on_ack_frequency_frame(&frame).unwrap();
+
+        assert!(!on_packet(&mut manager, 1));
+        // 2 packets are missing, which is below the threshold
+        assert!(!on_packet(&mut manager, 4));
+        // 3 packets are missing
+        assert!(on_packet(&mut manager, 8));
+
+        manager.transmission_state = AckTransmissionState::default();
+        let frame = AckFrequency {
+            sequence_number: VarInt::from_u8(1),
+            reordering_threshold: VarInt::ZERO,
+            ..frame
+        };
+        manager.on_ack_frequency_frame(&frame).unwrap();
+
+        // out-of-order packets are not immediately acknowledged when the threshold is 0
+        assert!(!on_packet(&mut manager, 20));
+        assert!(!on_packet(&mut manager, 2));
+    }
+
     #[test]
     fn ecn_counts() {
         // Setup:
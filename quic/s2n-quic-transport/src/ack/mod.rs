@@ -1,9 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub use ack_frequency_manager::*;
 pub use ack_manager::*;
 pub use s2n_quic_core::ack::*;
 
+mod ack_frequency_manager;
 mod ack_manager;
 mod ack_transmission_state;
 
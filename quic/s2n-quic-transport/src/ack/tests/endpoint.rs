@@ -76,6 +76,8 @@ impl Endpoint {
             path_validation_probing: Default::default(),
             bytes_progressed: 0,
             contains_crypto: false,
+            is_zero_rtt: false,
+            immediate_ack: false,
         };
 
         let path = helper_path_server();
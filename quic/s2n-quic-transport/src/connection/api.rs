@@ -18,6 +18,7 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::StreamType,
 };
@@ -193,6 +194,11 @@ impl Connection {
         self.api.keep_alive(enabled)
     }
 
+    #[inline]
+    pub fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api.migrate(local_address)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -203,6 +209,11 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        self.api.stats()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -220,4 +231,18 @@ impl Connection {
     pub fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error> {
         self.api.datagram_mut(query)
     }
+
+    #[inline]
+    pub fn congestion_controller_mut(
+        &self,
+        query: &mut dyn QueryMut,
+    ) -> Result<(), connection::Error> {
+        self.api.congestion_controller_mut(query)
+    }
+
+    /// Polls for the endpoint to start shutting down
+    #[inline]
+    pub fn poll_endpoint_shutdown(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_endpoint_shutdown(context)
+    }
 }
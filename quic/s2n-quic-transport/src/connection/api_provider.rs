@@ -19,6 +19,7 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
 };
@@ -67,13 +68,21 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
 
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
 
     fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
+
+    fn congestion_controller_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
+
+    fn poll_endpoint_shutdown(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
 }
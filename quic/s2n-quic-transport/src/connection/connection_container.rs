@@ -36,6 +36,7 @@ use s2n_quic_core::{
     application::ServerName,
     event::supervisor,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
@@ -328,6 +329,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api_write_call(|conn| conn.migrate(local_address))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -336,6 +341,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        self.api_read_call(|conn| conn.stats())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -359,6 +368,19 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
             Ok(())
         })
     }
+
+    #[inline]
+    fn congestion_controller_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| {
+            conn.congestion_controller_mut(query);
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn poll_endpoint_shutdown(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_endpoint_shutdown(context))
+    }
 }
 
 /// Contains all secondary lists of Connections.
@@ -843,6 +865,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         let connection = L::new(connection);
         let connection = Arc::new(ConnectionNode::new(connection, internal_connection_id));
 
+        // Increment the inflight handshakes before updating the interests, since a client
+        // sending early data is handed over to the application before the handshake completes
+        self.interest_lists.handshake_connections += 1;
+
         if self
             .interest_lists
             .update_interests(
@@ -854,10 +880,11 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
             .is_ok()
         {
             self.connection_map.insert(connection);
-            // Increment the inflight handshakes and total connection counter because we have accepted a new connection
-            self.interest_lists.handshake_connections += 1;
+            // Increment the total connection counter because we have accepted a new connection
             self.interest_lists.connection_count += 1;
             self.ensure_counter_consistency();
+        } else {
+            self.interest_lists.handshake_connections = self.count_handshaking_connections();
         }
     }
 
@@ -947,6 +974,25 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         Some((result, interests))
     }
 
+    /// Executes the given function on each `Connection` in the container
+    ///
+    /// Each `Connection`'s interests are updated after the function is executed, in the same way
+    /// as [`Self::with_connection`].
+    pub fn for_each_connection<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C),
+    {
+        let ids: Vec<_> = self
+            .connection_map
+            .iter()
+            .map(|node| node.internal_connection_id)
+            .collect();
+
+        for id in ids {
+            self.with_connection(id, &mut func);
+        }
+    }
+
     /// Removes all Connections in the `done` state from the `ConnectionContainer`.
     fn finalize_done_connections(&mut self) {
         debug_assert_eq!(
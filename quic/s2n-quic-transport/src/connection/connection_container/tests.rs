@@ -20,7 +20,7 @@ use core::{
 use s2n_quic_core::{
     application, event,
     event::builder::DatagramDropReason,
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -30,7 +30,7 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::mtu,
+    path::{migration, mtu},
     query,
     time::{Timer, Timestamp},
 };
@@ -133,6 +133,18 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    fn on_icmp_error(
+        &mut self,
+        _path_handle: &<Self::Config as endpoint::Config>::PathHandle,
+        _error: icmp::Error,
+        _payload: &[u8],
+        _timestamp: Timestamp,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
     fn on_wakeup(
         &mut self,
         _timestamp: Timestamp,
@@ -140,6 +152,9 @@ impl connection::Trait for TestConnection {
         _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _conn_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
+        _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        _mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
         _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
     ) -> Result<(), connection::Error> {
         Ok(())
@@ -156,6 +171,7 @@ impl connection::Trait for TestConnection {
         _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _conn_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         Ok(())
     }
@@ -172,6 +188,7 @@ impl connection::Trait for TestConnection {
         _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _conn_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         Ok(())
     }
@@ -188,6 +205,7 @@ impl connection::Trait for TestConnection {
         _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         Ok(())
     }
@@ -204,6 +222,7 @@ impl connection::Trait for TestConnection {
         _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         Ok(())
     }
@@ -226,6 +245,7 @@ impl connection::Trait for TestConnection {
         _datagram: &DatagramInfo,
         _path_id: path::Id,
         _packet: ProtectedZeroRtt,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
@@ -268,6 +288,10 @@ impl connection::Trait for TestConnection {
         123
     }
 
+    fn is_compatible_version(&self, _version: u32) -> bool {
+        false
+    }
+
     fn poll_stream_request(
         &mut self,
         _stream_id: stream::StreamId,
@@ -310,6 +334,10 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn migrate(&mut self, _local_address: SocketAddress) -> Result<(), migration::Error> {
+        Ok(())
+    }
+
     fn keep_alive(&mut self, _enabled: bool) -> Result<(), connection::Error> {
         todo!()
     }
@@ -322,6 +350,10 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        todo!()
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -338,6 +370,21 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn congestion_controller_mut(&mut self, _query: &mut dyn query::QueryMut) {
+        todo!()
+    }
+
+    fn on_endpoint_shutdown(&mut self, _deadline: Timestamp, _error: application::Error) {
+        todo!()
+    }
+
+    fn poll_endpoint_shutdown(
+        &mut self,
+        _context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         _timestamp: Timestamp,
@@ -430,7 +477,8 @@ fn container_test() {
     check!().with_type::<Vec<Operation>>().for_each(|ops| {
         let mut id_gen = InternalConnectionIdGenerator::new();
         let mut connections = vec![];
-        let (handle, acceptor, connector, _close_handle) = endpoint::handle::Handle::new(100);
+        let (handle, acceptor, connector, _close_handle, _shutdown_handle) =
+            endpoint::handle::Handle::new(100);
         let (waker, _wake_count) = futures_test::task::new_count_waker();
         let mut now = unsafe { Timestamp::from_duration(Duration::from_secs(0)) };
 
@@ -84,6 +84,56 @@ impl StatelessResetMap {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct PeerIdMap {
+    /// Maps from peer connection IDs to internal connection IDs
+    map: HashMap<connection::PeerId, InternalConnectionId, HashState>,
+}
+
+impl PeerIdMap {
+    /// Constructs a new `PeerIdMap`
+    fn new(hash_state: HashState) -> Self {
+        Self {
+            map: HashMap::with_hasher(hash_state),
+        }
+    }
+
+    /// Gets the `InternalConnectionId` (if any) associated with the given peer id
+    fn get(&self, peer_id: &connection::PeerId) -> Option<InternalConnectionId> {
+        self.map.get(peer_id).copied()
+    }
+
+    /// Inserts the given `PeerId` and the given internal connection ID into the map
+    ///
+    /// Zero-length connection IDs are shared by all of the connections of peers that use
+    /// them, so they are not inserted.
+    pub(crate) fn insert(
+        &mut self,
+        peer_id: &connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        if !peer_id.as_bytes().is_empty() {
+            self.map.insert(*peer_id, internal_id);
+        }
+    }
+
+    /// Removes the given `PeerId` from the map if it is still associated with `internal_id`
+    ///
+    /// Peers choose their own connection IDs, so another connection may have replaced the
+    /// mapping with the same ID.
+    pub(crate) fn remove(
+        &mut self,
+        peer_id: &connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        if let Entry::Occupied(entry) = self.map.entry(*peer_id) {
+            if *entry.get() == internal_id {
+                entry.remove();
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LocalIdMap {
     /// Maps from external to internal connection IDs
@@ -209,6 +259,8 @@ pub(crate) struct ConnectionIdMapperState {
     pub(crate) local_id_map: LocalIdMap,
     /// Maps from a hash of peer stateless reset token to internal connection IDs
     pub(crate) stateless_reset_map: StatelessResetMap,
+    /// Maps from peer connection IDs to internal connection IDs
+    pub(crate) peer_id_map: PeerIdMap,
     /// Maps from initial id to internal connection IDs
     pub(crate) initial_id_map: InitialIdMap,
     /// Maps from connection open request to internal connection IDs
@@ -222,6 +274,7 @@ impl ConnectionIdMapperState {
         Self {
             local_id_map: LocalIdMap::new(HashState::new(random_generator)),
             stateless_reset_map: StatelessResetMap::new(HashState::new(random_generator)),
+            peer_id_map: PeerIdMap::new(HashState::new(random_generator)),
             initial_id_map: InitialIdMap::new(
                 HashState::new(random_generator),
                 HashState::new(random_generator),
@@ -279,6 +332,21 @@ impl ConnectionIdMapper {
             })
     }
 
+    /// Looks up the internal Connection ID which is associated with a peer connection ID
+    ///
+    /// This is used to associate packets that were sent to the peer, such as the packets quoted
+    /// in ICMP errors, with the connection that sent them.
+    pub fn lookup_internal_connection_id_by_peer_id(
+        &self,
+        peer_id: &connection::PeerId,
+    ) -> Option<InternalConnectionId> {
+        let guard = self
+            .state
+            .lock()
+            .expect("should succeed unless the lock is poisoned");
+        guard.peer_id_map.get(peer_id)
+    }
+
     /// Inserts the given `InitialId` into the map if it is not already in the map,
     /// otherwise returns an Err
     pub fn try_insert_initial_id(
@@ -482,6 +550,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn peer_id_map() {
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let mut id_generator = InternalConnectionIdGenerator::new();
+        let internal_id_1 = id_generator.generate_id();
+        let internal_id_2 = id_generator.generate_id();
+        let peer_id_1 = id(b"id01");
+        let peer_id_2 = id(b"id02");
+        let empty_id = connection::PeerId::try_from_bytes(&[]).unwrap();
+
+        let mut registry_1 = mapper.create_server_peer_id_registry(internal_id_1, peer_id_1, true);
+        registry_1
+            .on_new_connection_id(&peer_id_2, 1, 0, &TEST_TOKEN_1)
+            .unwrap();
+        let registry_2 = mapper.create_server_peer_id_registry(internal_id_2, empty_id, true);
+
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id_1)
+        );
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id_2)
+        );
+        // zero-length connection IDs don't identify a connection
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&empty_id)
+        );
+
+        // dropping another connection with the same peer ID doesn't remove the mapping
+        mapper
+            .state
+            .lock()
+            .unwrap()
+            .peer_id_map
+            .remove(&peer_id_1, internal_id_2);
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id_1)
+        );
+
+        drop(registry_1);
+        drop(registry_2);
+
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id_1)
+        );
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id_2)
+        );
+    }
+
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
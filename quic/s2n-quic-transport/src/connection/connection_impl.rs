@@ -43,7 +43,7 @@ use s2n_quic_core::{
         builder::{DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TxStreamProgress},
         supervisor, ConnectionPublisher as _, IntoEvent as _, Subscriber,
     },
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -54,7 +54,7 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{mtu, Handle as _},
+    path::{migration, mtu, Handle as _},
     query,
     recovery::CongestionController,
     stateless_reset::token::Generator as _,
@@ -73,6 +73,9 @@ pub(crate) enum AcceptState {
     Handshaking,
     /// The connection has completed the handshake but hasn't been handed over
     /// to the application yet.
+    ///
+    /// A client sending early data enters this state once the 0-RTT keys are
+    /// available, before the handshake completes.
     HandshakeCompleted,
     /// The connection has been handed over to the application and can be
     /// actively utilized from there.
@@ -181,6 +184,12 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     wakeup_handle: Arc<WakeupHandle<InternalConnectionId>>,
     /// A Waker to the connection.
     waker: Waker,
+    /// The error used to close the connection once the endpoint shutdown deadline passes
+    ///
+    /// This is set once the endpoint has started shutting down.
+    endpoint_shutdown: Option<application::Error>,
+    /// Woken once the endpoint has started shutting down or the connection has closed
+    endpoint_shutdown_waker: Option<Waker>,
     event_context: EventContext<Config>,
 }
 
@@ -277,12 +286,13 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
         limits: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
         random_generator: &mut Config::RandomGenerator,
     ) -> Result<(), connection::Error> {
         let mut publisher = self.event_context.publisher(timestamp, subscriber);
         let space_manager = &mut self.space_manager;
 
-        match space_manager.poll_crypto(
+        let result = space_manager.poll_crypto(
             &mut self.path_manager,
             &mut self.local_id_registry,
             &mut self.limits,
@@ -292,12 +302,39 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             datagram,
             dc,
             limits,
+            early_data_validator,
             random_generator,
-        ) {
+        );
+
+        // The server may have switched to a compatible version after receiving the
+        // client's transport parameters
+        let version_negotiation = space_manager.version_negotiation_mut();
+        self.event_context.quic_version = version_negotiation.negotiated_version();
+        version_negotiation.on_publish(&mut event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: Config::ENDPOINT_TYPE,
+                timestamp,
+            },
+            Some(self.event_context.quic_version),
+            subscriber,
+        ));
+
+        match result {
             Poll::Ready(Ok(())) => {}
             // use `from` instead of `into` so the location is correctly captured
             Poll::Ready(Err(err)) => return Err(connection::Error::from(err)),
-            Poll::Pending => return Ok(()),
+            Poll::Pending => {
+                // A client sending early data hands the connection to the application before
+                // the handshake completes so it can open streams
+                if self.accept_state == AcceptState::Handshaking
+                    && space_manager
+                        .application()
+                        .is_some_and(|space| space.is_zero_rtt())
+                {
+                    self.accept_state = AcceptState::HandshakeCompleted;
+                }
+                return Ok(());
+            }
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.1
@@ -327,7 +364,10 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         {
             // Move into the HandshakeCompleted state. This will signal the
             // necessary interest to hand over the connection to the application.
-            self.accept_state = AcceptState::HandshakeCompleted;
+            // Connections sending early data may have already been handed over.
+            if self.accept_state == AcceptState::Handshaking {
+                self.accept_state = AcceptState::HandshakeCompleted;
+            }
             // Move the connection into the active state.
             self.state = ConnectionState::Active;
 
@@ -401,6 +441,11 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             //# confirmed (Section 4.1.2).
             self.space_manager
                 .discard_handshake(&mut self.path_manager, &mut publisher);
+
+            // the path to the server's preferred address is created on the next wakeup
+            if self.path_manager.on_handshake_confirmed() {
+                self.wakeup_handle.wakeup();
+            }
         }
 
         // check to see if we're flushing and should now close the connection
@@ -632,6 +677,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 id: path_manager.active_path_id().into_event(),
                 is_active: true,
             },
+            original_destination_connection_id: parameters
+                .original_destination_connection_id
+                .into_event(),
         });
 
         publisher.on_mtu_updated(event::builder::MtuUpdated {
@@ -662,6 +710,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             space_manager: parameters.space_manager,
             wakeup_handle,
             waker,
+            endpoint_shutdown: None,
+            endpoint_shutdown_waker: None,
             event_context,
         };
 
@@ -672,6 +722,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 parameters.datagram_endpoint,
                 parameters.dc_endpoint,
                 parameters.limits_endpoint,
+                parameters.early_data_validator,
                 parameters.random_generator,
             ) {
                 connection.with_event_publisher(
@@ -724,6 +775,22 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.event_context.quic_version
     }
 
+    fn is_compatible_version(&self, version: u32) -> bool {
+        let version_negotiation = self.space_manager.version_negotiation();
+
+        match Config::ENDPOINT_TYPE {
+            // The client may switch to a compatible version chosen by the server, up until
+            // the first valid Initial packet is received
+            endpoint::Type::Client => {
+                !self.path_manager.valid_initial_received()
+                    && version_negotiation.is_compatible(version)
+            }
+            // The client may still be sending the original version while the server's
+            // Initial packets are in flight
+            endpoint::Type::Server => version == version_negotiation.original_version(),
+        }
+    }
+
     /// Initiates closing the connection as described in
     /// https://www.rfc-editor.org/rfc/rfc9000#section-10
     fn close(
@@ -754,6 +821,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.state = error.into();
         self.error = Err(error);
 
+        // Notify the application, if it's waiting for the endpoint to shut down
+        if let Some(waker) = self.endpoint_shutdown_waker.take() {
+            waker.wake();
+        }
+
         // Disable access to the connection from concurrent open attempts.
         self.open_registry = None;
 
@@ -896,11 +968,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let mut outcome = transmission::Outcome::default();
                 let path_id = self.path_manager.active_path_id();
 
+                // If the queue offloads pacing, packets can be written ahead of their departure
+                // time and the queue holds them until they're due.
+                let pacing_horizon = queue.pacing_horizon();
+                let departure_timestamp = timestamp + pacing_horizon;
+
                 // Send an MTU probe if necessary and the handshake has been confirmed
                 // MTU probes are prioritized over other data so they are not blocked by the
                 // congestion controller, as they are critical to achieving maximum throughput.
                 if self.state == ConnectionState::Active
-                    && self.path_manager.active_path().can_transmit(timestamp)
+                    && self
+                        .path_manager
+                        .active_path()
+                        .can_transmit(departure_timestamp)
                     && self.space_manager.is_handshake_confirmed()
                     && self
                         .path_manager
@@ -927,7 +1007,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 }
 
                 // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
+                while self
+                    .path_manager
+                    .active_path()
+                    .can_transmit(departure_timestamp)
                     && queue
                         .push(ConnectionTransmission {
                             context: transmission_context!(
@@ -951,24 +1034,21 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     self.on_ack_eliciting_packet_sent(timestamp);
                 }
 
-                if let Some(edt) = self
+                if let Some(target) = self
                     .path_manager
                     .active_path()
-                    .congestion_controller
-                    .earliest_departure_time()
+                    .pacing_timer_target(timestamp, pacing_horizon)
                 {
-                    if !edt.has_elapsed(timestamp) {
-                        // We can't transmit more until a future time, so arm the pacing
-                        // timer to pause transmission until the earliest departure time.
+                    // We can't transmit more until a future time, so arm the pacing
+                    // timer to pause transmission until the earliest departure time.
 
-                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
-                        //# A sender SHOULD pace sending of all in-flight packets based on input
-                        //# from the congestion controller.
+                    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
+                    //# A sender SHOULD pace sending of all in-flight packets based on input
+                    //# from the congestion controller.
 
-                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
-                        //# Senders MUST either use pacing or limit such bursts.
-                        self.timers.pacing_timer.set(edt);
-                    }
+                    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
+                    //# Senders MUST either use pacing or limit such bursts.
+                    self.timers.pacing_timer.set(target);
                 }
 
                 let meta = event::builder::ConnectionMeta {
@@ -1125,6 +1205,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             self.on_supervisor_timeout(timestamp, subscriber, supervisor_context)?;
         }
 
+        if self
+            .timers
+            .shutdown_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+        {
+            if let Some(error) = self.endpoint_shutdown {
+                // the connection didn't finish before the endpoint shutdown deadline
+                return Err(connection::Error::application(error));
+            }
+        }
+
         // check to see if we're flushing the connection
         if self.poll_flush().is_ready() {
             return self.error;
@@ -1153,6 +1245,71 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Handles an ICMP error reported for a datagram sent on one of the [`Connection`]'s paths
+    fn on_icmp_error(
+        &mut self,
+        path_handle: &Config::PathHandle,
+        error: icmp::Error,
+        payload: &[u8],
+        timestamp: Timestamp,
+        random_generator: &mut Config::RandomGenerator,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        // the peer is no longer being sent anything other than close frames
+        if matches!(
+            self.state,
+            ConnectionState::Closing | ConnectionState::Draining | ConnectionState::Finished
+        ) {
+            return Ok(());
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# ICMP message validation MUST include matching IP addresses and UDP
+        //# ports [RFC8085] and, when possible, connection IDs to an active QUIC
+        //# session.
+        let Some((path_id, path)) = self.path_manager.path_mut(path_handle) else {
+            return Ok(());
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# The endpoint SHOULD ignore all ICMP messages that fail
+        //# validation.
+        if !path.is_quoted_packet(payload) {
+            return Ok(());
+        }
+
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+
+        match error {
+            icmp::Error::PacketTooBig { mtu } => {
+                if let mtu::MtuResult::MtuUpdated(max_datagram_size) =
+                    path.on_packet_too_big(mtu, timestamp, path_id, &mut publisher)
+                {
+                    if path_id == self.path_manager.active_path_id() {
+                        if let Some((space, _)) = self.space_manager.application_mut() {
+                            space.dc_manager.on_mtu_updated(max_datagram_size);
+                        }
+                    }
+                }
+            }
+            icmp::Error::PortUnreachable => {
+                let amplification_outcome = self
+                    .path_manager
+                    .on_path_unreachable(path_id, &mut publisher)?;
+
+                if amplification_outcome.is_active_path_unblocked() {
+                    self.space_manager.on_amplification_unblocked(
+                        &self.path_manager,
+                        random_generator,
+                        timestamp,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Handles all external wakeups on the [`Connection`].
     fn on_wakeup(
         &mut self,
@@ -1161,6 +1318,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
         conn_limits: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
         random_generator: &mut Config::RandomGenerator,
     ) -> Result<(), connection::Error> {
         // reset the queued state first so that new wakeup request are not missed
@@ -1173,6 +1333,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             datagram,
             dc,
             conn_limits,
+            early_data_validator,
             random_generator,
         )?;
 
@@ -1184,6 +1345,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             //# confirmed (Section 4.1.2).
             self.space_manager
                 .discard_handshake(&mut self.path_manager, &mut publisher);
+            self.path_manager.on_handshake_confirmed();
+        }
+
+        // create the path for a migration initiated by the client
+        {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            self.path_manager.on_migration_request(
+                congestion_controller_endpoint,
+                mtu,
+                &self.limits,
+                random_generator,
+                &mut publisher,
+            );
         }
 
         // return an error if the application set one
@@ -1276,6 +1450,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram_endpoint: &mut Config::DatagramEndpoint,
         dc_endpoint: &mut Config::DcEndpoint,
         connection_limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
         //= type=TODO
@@ -1291,15 +1466,34 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# subsequent Initial packets include a different Source Connection ID,
         //# they MUST be discarded.
 
+        let quic_version = self.quic_version();
+        let initial_key_cid = self.space_manager.version_negotiation().initial_key_cid();
+        let initial_key_cid = connection::id::UnboundedId::try_from_bytes(initial_key_cid)
+            .expect("connection ID already validated");
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
         if let Some((space, _status)) = self.space_manager.initial_mut() {
-            let packet = space.validate_and_decrypt_packet(
-                packet,
-                path_id,
-                &self.path_manager[path_id],
-                &mut publisher,
-            )?;
+            let packet = if Config::ENDPOINT_TYPE.is_client() && packet.version != quic_version {
+                // The server switched the connection to a compatible version
+                space.validate_and_decrypt_compatible_packet(
+                    packet,
+                    initial_key_cid.as_bytes(),
+                    path_id,
+                    &self.path_manager[path_id],
+                    &mut publisher,
+                )?
+            } else {
+                space.validate_and_decrypt_packet(
+                    packet,
+                    path_id,
+                    &self.path_manager[path_id],
+                    &mut publisher,
+                )?
+            };
+
+            if Config::ENDPOINT_TYPE.is_server() && packet.version == quic_version {
+                space.discard_original_keys();
+            }
 
             publisher.on_packet_received(event::builder::PacketReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -1308,6 +1502,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 ),
             });
 
+            if Config::ENDPOINT_TYPE.is_client() && packet.version != quic_version {
+                self.space_manager
+                    .version_negotiation_mut()
+                    .on_compatible_version(packet.version);
+                self.event_context.quic_version = packet.version;
+            }
+
             self.handle_cleartext_initial_packet(
                 datagram,
                 path_id,
@@ -1318,6 +1519,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
             )?;
         } else {
             let path = &self.path_manager[path_id];
@@ -1344,6 +1546,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram_endpoint: &mut Config::DatagramEndpoint,
         dc_endpoint: &mut Config::DcEndpoint,
         connection_limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
         if let Some((space, handshake_status)) = self.space_manager.initial_mut() {
@@ -1389,6 +1592,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 packet.packet_number,
                 packet.payload,
                 datagram,
+                false,
                 path_id,
                 &mut self.path_manager,
                 handshake_status,
@@ -1405,6 +1609,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
                 random_generator,
             )?;
 
@@ -1435,6 +1640,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram_endpoint: &mut Config::DatagramEndpoint,
         dc_endpoint: &mut Config::DcEndpoint,
         connection_limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
@@ -1488,6 +1694,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 packet.packet_number,
                 packet.payload,
                 datagram,
+                false,
                 path_id,
                 &mut self.path_manager,
                 handshake_status,
@@ -1522,6 +1729,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
                 random_generator,
             )?;
 
@@ -1552,6 +1760,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut Config::DcEndpoint,
         limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
     ) -> Result<(), ProcessingError> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
@@ -1628,6 +1837,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 packet.packet_number,
                 packet.payload,
                 datagram,
+                false,
                 path_id,
                 &mut self.path_manager,
                 handshake_status,
@@ -1650,11 +1860,25 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     datagram_endpoint,
                     dc_endpoint,
                     limits_endpoint,
+                    early_data_validator,
                     random_generator,
                 )?;
             }
             // notify the connection a packet was processed
             self.on_processed_packet(&processed_packet, subscriber)?;
+
+            if Config::ENDPOINT_TYPE.is_server() {
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
+                //# Additionally, a server MAY discard 0-RTT keys as soon as it receives
+                //# a 1-RTT packet.
+
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
+                //# After receiving
+                //# a 1-RTT packet, servers MUST discard 0-RTT keys within a short time;
+                //# the RECOMMENDED time period is three times the Probe Timeout (PTO,
+                //# see [QUIC-RECOVERY]).
+                self.space_manager.discard_zero_rtt_crypto();
+            }
         } else {
             let path = &self.path_manager[path_id];
             publisher.on_packet_dropped(event::builder::PacketDropped {
@@ -1731,20 +1955,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     fn handle_zero_rtt_packet(
         &mut self,
         datagram: &DatagramInfo,
-        _path_id: path::Id,
-        _packet: ProtectedZeroRtt,
+        path_id: path::Id,
+        packet: ProtectedZeroRtt,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
-        _packet_interceptor: &mut Config::PacketInterceptor,
+        packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
-        publisher.on_packet_received(event::builder::PacketReceived {
-            packet_header: event::builder::PacketHeader::ZeroRtt {
-                // FIXME: replace with PacketHeader::new when we support zero-rtt.
-                number: 0,
-                version: publisher.quic_version(),
-            },
-        });
         //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
         //= type=TODO
         //= tracking-issue=339
@@ -1752,7 +1970,58 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# number of these packets in anticipation of a late-arriving Initial
         //# packet.
 
-        // TODO
+        // Only the server is supposed to receive 0-RTT packets. The keys are only available if
+        // the early data was accepted.
+        let zero_rtt = if Config::ENDPOINT_TYPE.is_server() {
+            self.space_manager.zero_rtt_mut()
+        } else {
+            None
+        };
+
+        let Some((space, zero_rtt_crypto, handshake_status)) = zero_rtt else {
+            let path = &self.path_manager[path_id];
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::PacketSpaceDoesNotExist {
+                    path: path_event!(path, path_id),
+                    packet_type: event::builder::PacketType::ZeroRtt,
+                },
+            });
+            return Ok(());
+        };
+
+        let packet = space.validate_and_decrypt_zero_rtt_packet(
+            packet,
+            zero_rtt_crypto,
+            datagram,
+            path_id,
+            &self.path_manager[path_id],
+            &mut publisher,
+        )?;
+
+        publisher.on_packet_received(event::builder::PacketReceived {
+            packet_header: event::builder::PacketHeader::new(
+                packet.packet_number,
+                publisher.quic_version(),
+            ),
+        });
+
+        let processed_packet = space.handle_cleartext_payload(
+            packet.packet_number,
+            packet.payload,
+            datagram,
+            true,
+            path_id,
+            &mut self.path_manager,
+            handshake_status,
+            &mut self.local_id_registry,
+            random_generator,
+            &mut publisher,
+            packet_interceptor,
+        )?;
+
+        // notify the connection a packet was processed
+        self.on_processed_packet(&processed_packet, subscriber)?;
+
         Ok(())
     }
 
@@ -1880,6 +2149,22 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             );
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+        //# A client MAY reattempt 0-RTT if it receives a Retry or Version
+        //# Negotiation packet.
+        if let Some((space, handshake_status)) = self.space_manager.application_mut() {
+            if space.is_zero_rtt() {
+                // The server didn't process any of the 0-RTT packets so the data is
+                // retransmitted to the new connection ID
+                space.on_zero_rtt_discarded(
+                    &mut self.path_manager,
+                    handshake_status,
+                    &mut self.local_id_registry,
+                    &mut publisher,
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -2082,6 +2367,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn migrate(&mut self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.error?;
+
+        self.path_manager.request_migration(
+            local_address.into(),
+            self.space_manager.is_handshake_confirmed(),
+        )?;
+
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -2090,6 +2388,27 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        let mut stats = connection::Stats::default();
+
+        for path in self.path_manager.stats() {
+            stats.packets_sent += path.packets_sent;
+            stats.packets_lost += path.packets_lost;
+            stats.packets_retransmitted += path.packets_retransmitted;
+            stats.bytes_sent += path.bytes_sent;
+            stats.bytes_received += path.bytes_received;
+            stats.paths.push(path);
+        }
+
+        // Flow control credit is only available once the application space has been created
+        if let Some(space) = self.space_manager.application() {
+            stats.send_credit = space.stream_manager.outgoing_flow_control_credit().as_u64();
+            stats.receive_credit = space.stream_manager.incoming_flow_control_credit().as_u64();
+        }
+
+        Ok(stats)
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -2116,6 +2435,41 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
     }
 
+    #[inline]
+    fn congestion_controller_mut(&mut self, query: &mut dyn query::QueryMut) {
+        let path = self.path_manager.active_path_mut();
+        query.execute_mut(&mut path.congestion_controller);
+
+        // The query may have changed the congestion control algorithm
+        // to or from one that marks packets for L4S
+        path.ecn_controller
+            .set_l4s(path.congestion_controller.is_l4s());
+    }
+
+    fn on_endpoint_shutdown(&mut self, deadline: Timestamp, error: application::Error) {
+        if self.error.is_err() || self.endpoint_shutdown.is_some() {
+            return;
+        }
+
+        self.endpoint_shutdown = Some(error);
+        self.timers.shutdown_timer.set(deadline);
+
+        if let Some(waker) = self.endpoint_shutdown_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn poll_endpoint_shutdown(&mut self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        if self.endpoint_shutdown.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.error?;
+
+        self.endpoint_shutdown_waker = Some(context.waker().clone());
+        Poll::Pending
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
@@ -24,7 +24,7 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -35,7 +35,7 @@ use s2n_quic_core::{
         zero_rtt::ProtectedZeroRtt,
         ProtectedPacket,
     },
-    path::{mtu, Handle as _},
+    path::{migration, mtu, Handle as _},
     query,
     time::Timestamp,
 };
@@ -109,6 +109,20 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<(), connection::Error>;
 
+    /// Handles an ICMP error reported for a datagram sent on one of the [`Connection`]'s paths
+    ///
+    /// `payload` contains the datagram quoted by the ICMP message, which is used to ensure
+    /// the error is associated with the connection.
+    fn on_icmp_error(
+        &mut self,
+        path_handle: &<Self::Config as endpoint::Config>::PathHandle,
+        error: icmp::Error,
+        payload: &[u8],
+        timestamp: Timestamp,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> Result<(), connection::Error>;
+
     /// Handles all external wakeups on the [`Connection`].
     fn on_wakeup(
         &mut self,
@@ -117,6 +131,9 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         conn_limits: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
+        congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
         random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
     ) -> Result<(), connection::Error>;
 
@@ -134,6 +151,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError>;
 
     /// Is called when an unprotected initial packet had been received
@@ -148,6 +166,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError>;
 
     /// Is called when a handshake packet had been received
@@ -162,6 +181,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError>;
 
     /// Is called when a short packet had been received
@@ -176,6 +196,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
     ) -> Result<(), ProcessingError>;
 
     /// Is called when a version negotiation packet had been received
@@ -194,6 +215,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram: &DatagramInfo,
         path_id: path::Id,
         packet: ProtectedZeroRtt,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError>;
@@ -226,6 +248,10 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     /// Returns the QUIC version selected for the current connection
     fn quic_version(&self) -> u32;
 
+    /// Returns `true` if Initial packets with the given version can be processed, as a
+    /// result of compatible version negotiation
+    fn is_compatible_version(&self, version: u32) -> bool;
+
     /// Handles reception of a single QUIC packet
     fn handle_packet(
         &mut self,
@@ -238,6 +264,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
         check_for_stateless_reset: &mut bool,
     ) -> Result<(), connection::Error> {
         macro_rules! emit_drop_reason {
@@ -258,7 +285,14 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         //# If a client receives a packet that uses a different version than it
         //# initially selected, it MUST discard that packet.
         if let Some(version) = packet.version() {
-            if version != self.quic_version() {
+            // Compatible version negotiation allows Initial packets to use a different
+            // version while the connection is switching versions.
+            // See https://www.rfc-editor.org/rfc/rfc9368#section-2.3
+            let is_compatible_initial = || {
+                matches!(packet, ProtectedPacket::Initial(_)) && self.is_compatible_version(version)
+            };
+
+            if version != self.quic_version() && !is_compatible_initial() {
                 emit_drop_reason!(|path| event::builder::PacketDropReason::VersionMismatch {
                     version,
                     path: path_event!(path, path_id),
@@ -300,6 +334,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
             ),
             ProtectedPacket::VersionNegotiation(packet) => self.handle_version_negotiation_packet(
                 datagram,
@@ -318,11 +353,13 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
             ),
             ProtectedPacket::ZeroRtt(packet) => self.handle_zero_rtt_packet(
                 datagram,
                 path_id,
                 packet,
+                random_generator,
                 subscriber,
                 packet_interceptor,
             ),
@@ -336,6 +373,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
             ),
             ProtectedPacket::Retry(packet) => {
                 self.handle_retry_packet(datagram, path_id, packet, subscriber, packet_interceptor)
@@ -395,6 +433,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         connection_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        early_data_validator: &mut <Self::Config as endpoint::Config>::EarlyDataValidator,
         check_for_stateless_reset: &mut bool,
     ) -> Result<(), connection::Error> {
         macro_rules! emit_drop_reason {
@@ -450,6 +489,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram_endpoint,
                 dc_endpoint,
                 connection_limits_endpoint,
+                early_data_validator,
                 check_for_stateless_reset,
             );
 
@@ -498,10 +538,15 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn migrate(&mut self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    /// Returns a snapshot of the statistics for the connection and its paths
+    fn stats(&self) -> Result<connection::Stats, connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
@@ -510,6 +555,17 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn datagram_mut(&mut self, query: &mut dyn query::QueryMut);
 
+    /// Executes the query on the congestion controller of the active path
+    fn congestion_controller_mut(&mut self, query: &mut dyn query::QueryMut);
+
+    /// Notifies the connection that the endpoint has started shutting down
+    ///
+    /// The connection is closed with `error` if it is still open at `deadline`.
+    fn on_endpoint_shutdown(&mut self, deadline: Timestamp, error: application::Error);
+
+    /// Polls for the endpoint to start shutting down
+    fn poll_endpoint_shutdown(&mut self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
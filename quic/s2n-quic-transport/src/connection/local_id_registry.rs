@@ -385,6 +385,43 @@ impl LocalIdRegistry {
         Ok(())
     }
 
+    /// Registers the connection ID sent to the peer in the `preferred_address` transport parameter
+    ///
+    /// The connection ID is delivered during the handshake, so it starts in the `Active` status.
+    pub fn register_preferred_address_connection_id(
+        &mut self,
+        id: &connection::LocalId,
+        expiration: Option<Timestamp>,
+        stateless_reset_token: stateless_reset::Token,
+    ) -> Result<(), LocalIdRegistrationError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# The value of the
+        //# active_connection_id_limit parameter MUST be at least 2.
+        //
+        // The peer's limit isn't known yet, but it always has room for this connection ID.
+        self.active_connection_id_limit = self.active_connection_id_limit.max(2);
+
+        self.register_connection_id(id, expiration, stateless_reset_token)?;
+
+        let id_info = self
+            .registered_ids
+            .last_mut()
+            .expect("preferred address id added above");
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        debug_assert_eq!(id_info.sequence_number, 1);
+
+        id_info.status = Active;
+        self.transmission_interest.clear();
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Unregisters connection IDs that have expired
     fn unregister_expired_ids(&mut self, timestamp: Timestamp) {
         {
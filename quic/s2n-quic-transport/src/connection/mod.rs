@@ -59,6 +59,8 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub peer_connection_id: PeerId,
     /// The last utilized local Connection ID
     pub local_connection_id: LocalId,
+    /// The destination Connection ID of the client's first Initial packet
+    pub original_destination_connection_id: InitialId,
     /// The path handle on which the connection was created
     pub path_handle: Cfg::PathHandle,
     /// The space manager created for the connection
@@ -89,6 +91,8 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub event_subscriber: &'a mut Cfg::EventSubscriber,
     /// The connection limits provider
     pub limits_endpoint: &'a mut Cfg::ConnectionLimits,
+    /// The early data validator for the endpoint
+    pub early_data_validator: &'a mut Cfg::EarlyDataValidator,
     /// The random generator
     pub random_generator: &'a mut Cfg::RandomGenerator,
 }
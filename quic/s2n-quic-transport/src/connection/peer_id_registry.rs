@@ -68,6 +68,8 @@ pub struct PeerIdRegistry {
     /// If true, the connection ID used during the the handshake will be retired
     /// when the peer sends a NEW_CONNECTION_ID frame.
     rotate_handshake_connection_id: bool,
+    /// The random connection ID a client uses before the server selects a connection ID
+    original_destination_connection_id: Option<connection::PeerId>,
 }
 
 type RegisteredIds = SmallVec<[PeerIdInfo; NR_STATIC_REGISTRABLE_IDS]>;
@@ -231,13 +233,17 @@ impl From<PeerIdRegistrationError> for transport::Error {
 impl Drop for PeerIdRegistry {
     fn drop(&mut self) {
         if let Ok(mut guard) = self.state.lock() {
-            // Stop tracking all associated stateless reset tokens
-            for token in self
-                .registered_ids
-                .iter()
-                .flat_map(|id_info| id_info.stateless_reset_token)
-            {
-                guard.stateless_reset_map.remove(&token);
+            for id_info in self.registered_ids.iter() {
+                // Stop tracking all associated stateless reset tokens
+                if let Some(token) = id_info.stateless_reset_token {
+                    guard.stateless_reset_map.remove(&token);
+                }
+
+                guard.peer_id_map.remove(&id_info.id, self.internal_id);
+            }
+
+            if let Some(id) = self.original_destination_connection_id {
+                guard.peer_id_map.remove(&id, self.internal_id);
             }
         }
     }
@@ -274,9 +280,30 @@ impl PeerIdRegistry {
                 interest
             }),
             rotate_handshake_connection_id,
+            original_destination_connection_id: None,
         }
     }
 
+    /// Used by a Client endpoint to register the random DestinationConnectionId of its
+    /// first Initial packets.
+    ///
+    /// The ID isn't used to send packets once the server responds, so it is only tracked
+    /// to associate packets quoted in ICMP errors with the connection.
+    pub(crate) fn register_original_destination_connection_id(
+        &mut self,
+        peer_id: connection::PeerId,
+    ) {
+        debug_assert!(self.original_destination_connection_id.is_none());
+
+        self.original_destination_connection_id = Some(peer_id);
+
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .insert(&peer_id, self.internal_id);
+    }
+
     /// Used to register the initial peer DestinationConnectionId.
     ///
     /// For a Server endpoint this happens immediately after creation of the
@@ -304,6 +331,12 @@ impl PeerIdRegistry {
             status,
         });
 
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .insert(&peer_id, self.internal_id);
+
         self.check_consistency();
     }
 
@@ -331,6 +364,54 @@ impl PeerIdRegistry {
             .insert(stateless_reset_token, self.internal_id);
     }
 
+    /// Used to register the connection ID the server provided in the `preferred_address`
+    /// transport parameter.
+    ///
+    /// Unlike connection IDs received in NEW_CONNECTION_ID frames, this connection ID does not
+    /// cause the connection ID used during the handshake to be rotated, since it is reserved for
+    /// the migration to the server's preferred address.
+    pub(crate) fn register_preferred_address_connection_id(
+        &mut self,
+        peer_id: &connection::PeerId,
+        stateless_reset_token: &stateless_reset::Token,
+    ) -> Result<(), PeerIdRegistrationError> {
+        debug_assert!(!self.is_empty());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        let sequence_number = 1;
+
+        for id_info in self.registered_ids.iter() {
+            if id_info.validate_new_connection_id(
+                peer_id,
+                stateless_reset_token,
+                sequence_number,
+            )? {
+                // the transport parameters are only received once
+                return Err(InvalidNewConnectionId);
+            }
+        }
+
+        self.registered_ids.push(PeerIdInfo {
+            id: *peer_id,
+            sequence_number,
+            stateless_reset_token: Some(*stateless_reset_token),
+            status: New,
+        });
+
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .insert(peer_id, self.internal_id);
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Check if registered_ids is empty.
     ///
     /// This is only expected to be true when an endpoint creates a new
@@ -423,6 +504,12 @@ impl PeerIdRegistry {
 
             self.registered_ids.push(new_id_info);
 
+            self.state
+                .lock()
+                .expect("should succeed unless the lock is poisoned")
+                .peer_id_map
+                .insert(new_id, self.internal_id);
+
             self.check_active_connection_id_limit(active_id_count)?;
         }
 
@@ -492,6 +579,10 @@ impl PeerIdRegistry {
                         mapper_state.stateless_reset_map.remove(&token);
                     }
 
+                    mapper_state
+                        .peer_id_map
+                        .remove(&id_info.id, self.internal_id);
+
                     self.ack_interest.clear();
 
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
@@ -589,6 +680,22 @@ impl PeerIdRegistry {
         self.consume_new_id_inner()
     }
 
+    /// Retires a peer_id that is no longer used by any path
+    pub fn retire_id(&mut self, peer_id: &connection::PeerId) {
+        for id_info in self.registered_ids.iter_mut() {
+            if id_info.id == *peer_id
+                && matches!(id_info.status, InUse | InUsePendingNewConnectionId)
+            {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
+                //# An endpoint MUST NOT forget a connection ID without retiring it
+                id_info.status = PendingRetirement;
+                self.transmission_interest.clear();
+            }
+        }
+
+        self.check_consistency();
+    }
+
     // Validate that the ACTIVE_CONNECTION_ID_LIMIT has not been exceeded
     fn check_active_connection_id_limit(
         &self,
@@ -67,6 +67,17 @@ impl<Config: endpoint::Config> tx::Message for ConnectionTransmission<'_, '_, Co
         Default::default()
     }
 
+    #[inline]
+    fn departure_time(&mut self) -> Option<Timestamp> {
+        // The pacer's earliest departure time applies to the packet being written. This is only
+        // in the future if the queue offloads pacing and allows packets to be queued early.
+        self.context
+            .path()
+            .congestion_controller
+            .earliest_departure_time()
+            .filter(|edt| !edt.has_elapsed(self.context.timestamp))
+    }
+
     #[inline]
     fn ipv6_flow_label(&mut self) -> u32 {
         // TODO compute flow label from connection id
@@ -5,8 +5,8 @@
 
 use crate::{connection, stream};
 use s2n_quic_core::{
-    crypto::tls, datagram, dc, endpoint, event, packet, path, path::mtu, random,
-    recovery::congestion_controller, stateless_reset,
+    crypto::tls, datagram, dc, early_data, endpoint, event, packet, path, path::mtu, random,
+    recovery::congestion_controller, stateless_reset, version,
 };
 
 /// Configuration parameters for a QUIC endpoint
@@ -32,8 +32,12 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type ConnectionLimits: connection::limits::Limiter;
     /// The path specific mtu config
     type Mtu: mtu::Endpoint;
+    /// The stream scheduler endpoint
+    type StreamSchedulerEndpoint: stream::scheduler::Endpoint;
     /// The type of stream
-    type StreamManager: stream::Manager;
+    type StreamManager: stream::Manager<
+        Scheduler = <Self::StreamSchedulerEndpoint as stream::scheduler::Endpoint>::Scheduler,
+    >;
     /// The connection close formatter
     type ConnectionCloseFormatter: connection::close::Formatter;
     /// The event subscriber
@@ -42,6 +46,8 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type PathHandle: path::Handle;
     /// The path migration validator for the endpoint
     type PathMigrationValidator: path::migration::Validator;
+    /// The early data validator for the endpoint
+    type EarlyDataValidator: early_data::Validator;
     /// The packet_interceptor implementation for the endpoint
     type PacketInterceptor: packet::interceptor::Interceptor;
     /// The datagram implementation for the endpoint
@@ -86,15 +92,22 @@ pub struct Context<'a, Cfg: Config> {
     /// on a path
     pub mtu: &'a mut mtu::Manager<Cfg::Mtu>,
 
+    /// The QUIC versions supported by the endpoint
+    pub version: &'a version::Config,
+
     pub connection_close_formatter: &'a mut Cfg::ConnectionCloseFormatter,
 
     pub event_subscriber: &'a mut Cfg::EventSubscriber,
 
     pub path_migration: &'a mut Cfg::PathMigrationValidator,
 
+    pub early_data: &'a mut Cfg::EarlyDataValidator,
+
     pub packet_interceptor: &'a mut Cfg::PacketInterceptor,
 
     pub datagram: &'a mut Cfg::DatagramEndpoint,
 
+    pub stream_scheduler: &'a mut Cfg::StreamSchedulerEndpoint,
+
     pub dc: &'a mut Cfg::DcEndpoint,
 }
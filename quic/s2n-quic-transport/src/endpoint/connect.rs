@@ -26,6 +26,7 @@ pub struct Connect {
     pub(crate) remote_address: RemoteAddress,
     pub(crate) server_name: Option<ServerName>,
     pub(crate) deduplicate: bool,
+    pub(crate) zero_rtt: bool,
 }
 
 impl fmt::Display for Connect {
@@ -51,6 +52,7 @@ impl Connect {
             remote_address: addr.into().into(),
             server_name: None,
             deduplicate: false,
+            zero_rtt: false,
         }
     }
 
@@ -78,6 +80,26 @@ impl Connect {
             ..self
         }
     }
+
+    /// Specifies whether to send application data in 0-RTT packets when resuming a session
+    ///
+    /// When enabled and the TLS provider is able to resume a previous session with early data,
+    /// the connection attempt completes as soon as the 0-RTT keys are available, before the
+    /// handshake completes. Streams opened on the connection at that point are sent in 0-RTT
+    /// packets using the transport parameters the server provided on the previous connection.
+    /// If the server rejects the early data, it is retransmitted once the handshake completes.
+    /// Otherwise, the attempt completes after the handshake, as usual.
+    ///
+    /// The connection attempt fails with an invalid configuration error if the TLS provider
+    /// is unable to send early data. Currently only the `rustls` provider supports it, and
+    /// only when configured with `with_early_data(true)`.
+    ///
+    /// Note that early data can be replayed by an attacker and should only be used for
+    /// requests that are safe to process more than once.
+    #[must_use]
+    pub fn with_zero_rtt(self, zero_rtt: bool) -> Self {
+        Self { zero_rtt, ..self }
+    }
 }
 
 /// Make it easy for applications to create a connection attempt without importing the `Connect` struct
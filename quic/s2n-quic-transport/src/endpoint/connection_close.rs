@@ -177,7 +177,7 @@ impl<Path: path::Handle> Transmission<Path> {
         };
 
         let (mut initial_key, initial_header_key) =
-            C::new_server(packet.destination_connection_id());
+            C::new_server(packet.version, packet.destination_connection_id());
 
         // There is no packet acknowledged yet, since no packet is taken from the peer.
         // The endpoint just close the connection immediately.
@@ -6,14 +6,16 @@
 use crate::{
     connection,
     connection::Connection,
-    endpoint::{close, close::CloseHandle, connect},
+    endpoint::{close, close::CloseHandle, connect, shutdown, shutdown::ShutdownHandle},
 };
 use core::{
     pin::Pin,
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 use futures_channel::mpsc;
 use futures_core::Stream;
+use s2n_quic_core::application;
 
 /// Held by application. Used to accept new connections.
 pub(crate) type AcceptorReceiver = mpsc::UnboundedReceiver<Connection>;
@@ -30,6 +32,11 @@ pub(crate) type CloseReceiver = mpsc::Receiver<Waker>;
 /// Held by the application. Used to submit connection close attempts to the library.
 pub(crate) type CloseSender = mpsc::Sender<Waker>;
 
+/// Held by library. Used to receive shutdown requests from the application.
+pub(crate) type ShutdownReceiver = mpsc::UnboundedReceiver<shutdown::Request>;
+/// Held by the application. Used to submit shutdown requests to the library.
+pub(crate) type ShutdownSender = mpsc::UnboundedSender<shutdown::Request>;
+
 /// The [`Handle`] allows applications to accept and open QUIC connections on an `Endpoint`.
 #[derive(Debug)]
 pub(crate) struct Handle {
@@ -41,17 +48,25 @@ impl Handle {
     /// Creates a new `Handle` with a limit opening connection limit.
     pub(crate) fn new(
         max_opening_connections: usize,
-    ) -> (Self, AcceptorSender, ConnectorReceiver, CloseHandle) {
+    ) -> (
+        Self,
+        AcceptorSender,
+        ConnectorReceiver,
+        CloseHandle,
+        ShutdownHandle,
+    ) {
         let (acceptor_sender, acceptor_receiver) = mpsc::unbounded();
         let (connector_sender, connector_receiver) = mpsc::channel(max_opening_connections);
 
         let (close_sender, close_receiver) = mpsc::channel(max_opening_connections);
+        let (shutdown_sender, shutdown_receiver) = mpsc::unbounded();
 
         let endpoint_state = close::EndpointState::default();
         let closer = close::Closer::new(close_sender, endpoint_state.clone());
         let handle = Self {
             acceptor: Acceptor {
                 acceptor: acceptor_receiver,
+                shutdown: shutdown::Requester::new(shutdown_sender, closer.clone()),
             },
             connector: Connector {
                 connector: connector_sender,
@@ -63,6 +78,7 @@ impl Handle {
             acceptor_sender,
             connector_receiver,
             CloseHandle::new(close_receiver, endpoint_state),
+            ShutdownHandle::new(shutdown_receiver),
         )
     }
 }
@@ -70,6 +86,7 @@ impl Handle {
 #[derive(Debug)]
 pub struct Acceptor {
     acceptor: AcceptorReceiver,
+    shutdown: shutdown::Requester,
 }
 
 impl Acceptor {
@@ -92,6 +109,25 @@ impl Acceptor {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    /// Polls to gracefully shut down the endpoint
+    ///
+    /// Once the shutdown starts, new connection attempts are refused and all of the existing
+    /// connections are notified. Any connections which are still open after `timeout` are closed
+    /// with the provided `error`.
+    ///
+    /// The method will return
+    /// - `Poll::Ready(())` once all of the connections have closed and the endpoint has shut down.
+    /// - `Poll::Pending` if there are still connections open on the endpoint.
+    pub fn poll_shutdown(
+        &mut self,
+        timeout: Duration,
+        error: application::Error,
+        context: &mut Context,
+    ) -> Poll<()> {
+        self.shutdown
+            .poll_shutdown(context, shutdown::Request { timeout, error })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -10,7 +10,7 @@ use crate::{
     },
     endpoint,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{EarlyData, PacketSpaceManager, VersionNegotiation},
 };
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
@@ -22,7 +22,11 @@ use s2n_quic_core::{
     packet::initial::ProtectedInitial,
     path::Handle as _,
     stateless_reset::token::Generator as _,
-    transport::{self, parameters::ServerTransportParameters},
+    stream::scheduler::Endpoint as _,
+    transport::{
+        self,
+        parameters::{PreferredAddress, ServerTransportParameters},
+    },
 };
 
 impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
@@ -106,6 +110,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
+                packet.version,
                 datagram.destination_connection_id.as_bytes(),
             );
 
@@ -138,7 +143,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .stateless_reset_token_generator
             .generate(initial_connection_id.as_bytes());
 
-        let local_id_registry = self.connection_id_mapper.create_local_id_registry(
+        let mut local_id_registry = self.connection_id_mapper.create_local_id_registry(
             internal_connection_id,
             &initial_connection_id,
             initial_connection_id_expiration_time,
@@ -172,6 +177,44 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         transport_parameters.load_limits(&limits);
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6
+        //# QUIC allows servers to accept connections on one IP address and
+        //# attempt to transfer these connections to a more preferred address
+        //# shortly after the handshake.
+        if limits.preferred_ipv4_address().is_some() || limits.preferred_ipv6_address().is_some() {
+            let connection_id = self
+                .config
+                .context()
+                .connection_id_format
+                .generate(&ConnectionInfo::new(&remote_address));
+            let stateless_reset_token = self
+                .config
+                .context()
+                .stateless_reset_token_generator
+                .generate(connection_id.as_bytes());
+
+            local_id_registry
+                .register_preferred_address_connection_id(
+                    &connection_id,
+                    initial_connection_id_expiration_time,
+                    stateless_reset_token,
+                )
+                .map_err(|_| {
+                    transport::Error::INTERNAL_ERROR
+                        .with_reason("preferred address connection id already in use")
+                })?;
+
+            transport_parameters.preferred_address = Some(PreferredAddress {
+                ipv4_address: limits.preferred_ipv4_address(),
+                ipv6_address: limits.preferred_ipv6_address(),
+                connection_id: connection_id
+                    .as_bytes()
+                    .try_into()
+                    .expect("connection ID already validated"),
+                stateless_reset_token,
+            });
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# A server includes the Destination Connection ID field from the first
         //# Initial packet it received from the client in the
@@ -226,12 +269,23 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
+        let quic_version = packet.version;
+
+        let version_negotiation = VersionNegotiation::new(
+            Config::ENDPOINT_TYPE,
+            *endpoint_context.version,
+            quic_version,
+            datagram.destination_connection_id.as_bytes(),
+            endpoint_context
+                .tls
+                .supports_compatible_version_negotiation(),
+        );
+        transport_parameters.version_information = version_negotiation.local_information();
+
         let tls_session = endpoint_context
             .tls
             .new_server_session(&transport_parameters);
 
-        let quic_version = packet.version;
-
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Config::ENDPOINT_TYPE,
             id: internal_connection_id.into(),
@@ -288,6 +342,9 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             tls_session,
             initial_key,
             initial_header_key,
+            version_negotiation,
+            EarlyData::new(false),
+            endpoint_context.stream_scheduler.new_scheduler(),
             datagram.timestamp,
             &mut publisher,
         );
@@ -303,6 +360,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             //# uses for sending packets based on the first received Initial packet.
             peer_connection_id: source_connection_id,
             local_connection_id: initial_connection_id,
+            original_destination_connection_id,
             path_handle: header.path,
             congestion_controller,
             timestamp: datagram.timestamp,
@@ -316,6 +374,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             dc_endpoint: endpoint_context.dc,
             open_registry: None,
             limits_endpoint: endpoint_context.connection_limits,
+            early_data_validator: endpoint_context.early_data,
             random_generator: endpoint_context.random_generator,
         };
 
@@ -371,6 +430,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                         endpoint_context.datagram,
                         endpoint_context.dc,
                         endpoint_context.connection_limits,
+                        endpoint_context.early_data,
                     )
                     .map_err(|err| {
                         use connection::ProcessingError;
@@ -396,6 +456,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                     endpoint_context.datagram,
                     endpoint_context.dc,
                     endpoint_context.connection_limits,
+                    endpoint_context.early_data,
                     &mut false,
                 )?;
 
@@ -11,9 +11,9 @@ use crate::{
         InternalConnectionId, InternalConnectionIdGenerator, Trait as _,
     },
     endpoint,
-    endpoint::close::CloseHandle,
+    endpoint::{close::CloseHandle, shutdown::ShutdownHandle},
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{EarlyData, PacketSpaceManager, VersionNegotiation},
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
@@ -35,13 +35,14 @@ use s2n_quic_core::{
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
-    inet::{datagram, DatagramInfo},
+    inet::{datagram, icmp, DatagramInfo},
     io::{rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
     path::{mtu, Handle as _},
     random::Generator as _,
     stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
+    stream::scheduler::Endpoint as _,
     time::{Clock, Timestamp},
     token::{self, Format},
     transport::parameters::{ClientTransportParameters, DcSupportedVersions},
@@ -55,6 +56,7 @@ pub mod handle;
 mod initial;
 mod packet_buffer;
 mod retry;
+mod shutdown;
 mod stateless_reset;
 mod version;
 
@@ -80,6 +82,8 @@ pub struct Endpoint<Cfg: Config> {
     wakeup_queue: WakeupQueue<InternalConnectionId>,
     /// Used to receive close attempts and track close state.
     close_handle: CloseHandle,
+    /// Used to receive shutdown requests and track shutdown state.
+    shutdown_handle: ShutdownHandle,
     /// This queue contains wakeups we retrieved from the [`Self::wakeup_queue`] earlier.
     /// This is not a local variable in order to reuse the allocated queue capacity in between
     /// [`Endpoint`] interactions.
@@ -104,7 +108,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
     {
         let mut now: Option<Timestamp> = None;
 
-        queue.for_each(|mut header, payload| {
+        queue.for_each_entry(|entry| {
             let timestamp = match now {
                 Some(time) => time,
                 None => {
@@ -113,7 +117,17 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                 }
             };
 
-            self.receive_datagram(&mut header, payload, timestamp)
+            match entry {
+                rx::Entry::Packet {
+                    mut header,
+                    payload,
+                } => self.receive_datagram(&mut header, payload, timestamp),
+                rx::Entry::Error {
+                    path,
+                    error,
+                    payload,
+                } => self.on_icmp_error(&path, error, payload, timestamp),
+            }
         });
     }
 
@@ -220,6 +234,9 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                     endpoint_context.datagram,
                     endpoint_context.dc,
                     endpoint_context.connection_limits,
+                    endpoint_context.early_data,
+                    endpoint_context.congestion_controller,
+                    endpoint_context.mtu,
                     endpoint_context.random_generator,
                 ) {
                     conn.close(
@@ -235,6 +252,16 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             });
         }
 
+        // start shutting down if the application requested it
+        if let Poll::Ready(request) = self.shutdown_handle.poll_request(cx) {
+            wakeup_count += 1;
+
+            let deadline = clock.get_time() + request.timeout;
+            self.connections.for_each_connection(|conn| {
+                conn.on_endpoint_shutdown(deadline, request.error);
+            });
+        }
+
         // try to open connection requests from the application
         if Cfg::ENDPOINT_TYPE.is_client() {
             loop {
@@ -305,11 +332,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
     fn new(mut config: Cfg) -> (Self, handle::Handle) {
         // TODO make this limit configurable
         let max_opening_connections = 1000;
-        let (handle, acceptor_sender, connector_receiver, close_handle) =
+        let (handle, acceptor_sender, connector_receiver, close_handle, shutdown_handle) =
             handle::Handle::new(max_opening_connections);
 
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
+        let version_negotiator =
+            version::Negotiator::new(DEFAULT_MAX_PEERS, *config.context().version);
 
         let endpoint = Self {
             config,
@@ -318,8 +347,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
             connection_id_mapper,
             wakeup_queue: WakeupQueue::new(),
             close_handle,
+            shutdown_handle,
             dequeued_wakeups: VecDeque::new(),
-            version_negotiator: version::Negotiator::default(),
+            version_negotiator,
             retry_dispatch: retry::Dispatch::default(),
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             connection_close_dispatch: connection_close::Dispatch::new(
@@ -354,7 +384,12 @@ impl<Cfg: Config> Endpoint<Cfg> {
         );
 
         let context = self.config.context();
-        let outcome = context.endpoint_limits.on_connection_attempt(&attempt);
+        let outcome = if self.shutdown_handle.is_shutting_down() {
+            // refuse any new connections while the endpoint is shutting down
+            Outcome::close()
+        } else {
+            context.endpoint_limits.on_connection_attempt(&attempt)
+        };
         let mut publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -436,6 +471,65 @@ impl<Cfg: Config> Endpoint<Cfg> {
         }
     }
 
+    /// Notifies connections of an ICMP error for a previously transmitted datagram
+    fn on_icmp_error(
+        &mut self,
+        path: &Cfg::PathHandle,
+        error: icmp::Error,
+        payload: &[u8],
+        timestamp: Timestamp,
+    ) {
+        let endpoint_context = self.config.context();
+
+        let mut publisher = event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: Cfg::ENDPOINT_TYPE,
+                timestamp,
+            },
+            None,
+            endpoint_context.event_subscriber,
+        );
+
+        let remote_address = path.remote_address();
+        publisher.on_platform_icmp_error(event::builder::PlatformIcmpError {
+            remote_address: remote_address.into_event(),
+            error: error.into_event(),
+        });
+
+        // The quoted packet was sent by this endpoint so its destination connection ID was chosen
+        // by the peer, which identifies the connection without having to query each of them.
+        let Some(internal_id) = quoted_peer_ids(payload).find_map(|peer_id| {
+            self.connection_id_mapper
+                .lookup_internal_connection_id_by_peer_id(&peer_id)
+        }) else {
+            return;
+        };
+
+        let close_packet_buffer = &mut self.close_packet_buffer;
+
+        // The connection validates the rest of the quoted packet and the path
+        let _ = self.connections.with_connection(internal_id, |conn| {
+            if let Err(close_error) = conn.on_icmp_error(
+                path,
+                error,
+                payload,
+                timestamp,
+                endpoint_context.random_generator,
+                endpoint_context.event_subscriber,
+            ) {
+                conn.close(
+                    close_error,
+                    endpoint_context.connection_close_formatter,
+                    close_packet_buffer,
+                    endpoint_context.random_generator,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
+            }
+        });
+    }
+
     /// Ingests a single datagram
     fn receive_datagram(
         &mut self,
@@ -631,6 +725,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     endpoint_context.datagram,
                     endpoint_context.dc,
                     endpoint_context.connection_limits,
+                    endpoint_context.early_data,
                     &mut check_for_stateless_reset,
                 ) {
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-10.2.1
@@ -659,6 +754,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     endpoint_context.datagram,
                     endpoint_context.dc,
                     endpoint_context.connection_limits,
+                    endpoint_context.early_data,
                     &mut check_for_stateless_reset,
                 ) {
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-10.2.1
@@ -844,6 +940,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# as sending a Stateless Reset.
                 if !is_stateless_reset
                     && Cfg::StatelessResetTokenGenerator::ENABLED
+                    && self
+                        .config
+                        .context()
+                        .stateless_reset_token_generator
+                        .is_enabled()
                     && is_short_header_packet
                 {
                     self.enqueue_stateless_reset(header, &datagram, &destination_connection_id);
@@ -999,6 +1100,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     remote_address,
                     server_name: hostname,
                     deduplicate,
+                    zero_rtt,
                 },
             sender,
         } = request;
@@ -1017,11 +1119,18 @@ impl<Cfg: Config> Endpoint<Cfg> {
             ));
         }
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-15
-        //# This version of the specification is identified by the number
-        //# 0x00000001.
-        let quic_version = 0x00000001;
+        if zero_rtt && !self.config.context().tls.supports_zero_rtt() {
+            let error = connection::Error::invalid_configuration(
+                "Requested 0-RTT which is not supported by the configured TLS provider",
+            );
+            // Notify the application so the attempt fails with the reason instead of an
+            // unspecified error
+            let _ = sender.send(Err(error));
+            return Err(error);
+        }
+
         let endpoint_context = self.config.context();
+        let quic_version = endpoint_context.version.preferred_version();
         let mut endpoint_publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -1038,6 +1147,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     remote_address,
                     server_name: hostname.clone(),
                     deduplicate,
+                    zero_rtt,
                 },
             ) {
                 Ok(existing) => {
@@ -1140,9 +1250,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
         // The original_destination_connection_id is a random value used to establish the
         // connection. Since the connection is not yet secured, the client must not set a
         // stateless_reset_token.
-        let peer_id_registry = self
+        let mut peer_id_registry = self
             .connection_id_mapper
             .create_client_peer_id_registry(internal_connection_id, rotate_handshake_connection_id);
+        peer_id_registry
+            .register_original_destination_connection_id(original_destination_connection_id.into());
 
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -1232,8 +1344,20 @@ impl<Cfg: Config> Endpoint<Cfg> {
         // protection keys.
         let (initial_key, initial_header_key) =
             <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                quic_version,
                 original_destination_connection_id.as_bytes(),
             );
+        let version_negotiation = VersionNegotiation::new(
+            Cfg::ENDPOINT_TYPE,
+            *endpoint_context.version,
+            quic_version,
+            original_destination_connection_id.as_bytes(),
+            endpoint_context
+                .tls
+                .supports_compatible_version_negotiation(),
+        );
+        transport_parameters.version_information = version_negotiation.local_information();
+
         let tls_session = endpoint_context
             .tls
             // TODO should SNI be optional? rustls expects a SNI but other tls providers dont seem
@@ -1247,6 +1371,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
             tls_session,
             initial_key,
             initial_header_key,
+            version_negotiation,
+            // dc connections derive their secrets from the handshake, which isn't
+            // available to early data
+            EarlyData::new(zero_rtt && !Cfg::DcEndpoint::ENABLED),
+            endpoint_context.stream_scheduler.new_scheduler(),
             timestamp,
             &mut publisher,
         );
@@ -1268,6 +1397,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             wakeup_handle,
             peer_connection_id: original_destination_connection_id.into(),
             local_connection_id,
+            original_destination_connection_id,
             path_handle,
             congestion_controller,
             timestamp,
@@ -1281,6 +1411,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             dc_endpoint: endpoint_context.dc,
             open_registry,
             limits_endpoint: endpoint_context.connection_limits,
+            early_data_validator: endpoint_context.early_data,
             random_generator: endpoint_context.random_generator,
         };
         let connection = <Cfg as crate::endpoint::Config>::Connection::new(connection_parameters)?;
@@ -1290,6 +1421,24 @@ impl<Cfg: Config> Endpoint<Cfg> {
     }
 }
 
+/// Returns the candidate destination connection IDs of a packet quoted in an ICMP error
+fn quoted_peer_ids(payload: &[u8]) -> impl Iterator<Item = PeerId> + '_ {
+    let (offset, lens) = match payload.first() {
+        // the most significant bit of the first byte is set for long header packets, where the
+        // connection id follows the 4 byte version and the connection id length
+        Some(tag) if tag & 0x80 != 0 => {
+            let len = payload.get(5).map_or(0, |len| *len as usize);
+            (6, len..len + 1)
+        }
+        // short header packets don't include the length of the connection id so each of the
+        // possible lengths is tried
+        Some(_) => (1, 1..s2n_quic_core::connection::id::MAX_LEN + 1),
+        None => (0, 0..0),
+    };
+
+    lens.filter_map(move |len| PeerId::try_from_bytes(payload.get(offset..offset + len)?))
+}
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
     use super::*;
@@ -1312,10 +1461,12 @@ pub mod testing {
         type TokenFormat = s2n_quic_core::token::testing::Format;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type Mtu = s2n_quic_core::path::mtu::Config;
+        type StreamSchedulerEndpoint = s2n_quic_core::stream::scheduler::StrictPriority;
         type StreamManager = crate::stream::DefaultStreamManager;
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
         type EventSubscriber = Subscriber;
         type PathMigrationValidator = path::migration::allow_all::Validator;
+        type EarlyDataValidator = s2n_quic_core::early_data::allow_all::Validator;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
@@ -1344,10 +1495,12 @@ pub mod testing {
         type TokenFormat = s2n_quic_core::token::testing::Format;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type Mtu = s2n_quic_core::path::mtu::Config;
+        type StreamSchedulerEndpoint = s2n_quic_core::stream::scheduler::StrictPriority;
         type StreamManager = crate::stream::DefaultStreamManager;
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
         type EventSubscriber = Subscriber;
         type PathMigrationValidator = path::migration::allow_all::Validator;
+        type EarlyDataValidator = s2n_quic_core::early_data::allow_all::Validator;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
@@ -0,0 +1,101 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows the application to gracefully shut down a server endpoint
+
+use crate::endpoint::{
+    close::Closer,
+    handle::{ShutdownReceiver, ShutdownSender},
+};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use futures_core::Stream;
+use s2n_quic_core::application;
+
+/// A request from the application to gracefully shut down the endpoint
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Request {
+    /// The amount of time connections have to finish before they are closed
+    pub timeout: Duration,
+    /// The error used to close any connections remaining after the timeout
+    pub error: application::Error,
+}
+
+/// Held by library. Used to receive shutdown requests and track the shutdown state.
+#[derive(Debug)]
+pub(crate) struct ShutdownHandle {
+    /// A channel which is used to receive shutdown requests
+    receiver: ShutdownReceiver,
+    /// Set once the endpoint has started shutting down
+    is_shutting_down: bool,
+}
+
+impl ShutdownHandle {
+    pub fn new(receiver: ShutdownReceiver) -> Self {
+        Self {
+            receiver,
+            is_shutting_down: false,
+        }
+    }
+
+    /// Returns `true` if the endpoint has started shutting down
+    pub fn is_shutting_down(&self) -> bool {
+        self.is_shutting_down
+    }
+
+    /// Polls for a shutdown request from the application
+    ///
+    /// Only the first request is returned, since the endpoint can only be shut down once.
+    pub fn poll_request(&mut self, context: &mut Context) -> Poll<Request> {
+        if self.is_shutting_down {
+            return Poll::Pending;
+        }
+
+        match Stream::poll_next(Pin::new(&mut self.receiver), context) {
+            Poll::Ready(Some(request)) => {
+                self.is_shutting_down = true;
+                Poll::Ready(request)
+            }
+            // the application handle was dropped without requesting a shutdown
+            Poll::Ready(None) => Poll::Pending,
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Held by application. Used to request the endpoint to shut down and wait for it to close.
+#[derive(Debug)]
+pub(crate) struct Requester {
+    request_sent: bool,
+    sender: ShutdownSender,
+    closer: Closer,
+}
+
+impl Requester {
+    pub fn new(sender: ShutdownSender, closer: Closer) -> Self {
+        Self {
+            request_sent: false,
+            sender,
+            closer,
+        }
+    }
+
+    /// Polls for the endpoint to shut down
+    ///
+    /// The request is only sent on the first call. Subsequent calls wait for the endpoint to
+    /// close.
+    pub fn poll_shutdown(&mut self, context: &mut Context, request: Request) -> Poll<()> {
+        if !self.request_sent {
+            self.request_sent = true;
+
+            // an error means the endpoint has already closed, which is checked by the closer
+            let _ = self.sender.unbounded_send(request);
+        }
+
+        // the endpoint closes once all of the remaining connections have finished
+        self.closer.poll_close(context).map(|_| ())
+    }
+}
@@ -12,34 +12,30 @@ use s2n_quic_core::{
     packet,
     packet::ProtectedPacket,
     path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    version,
 };
 
 #[derive(Debug)]
 pub struct Negotiator<Config: endpoint::Config> {
     transmissions: VecDeque<Transmission<Config::PathHandle>>,
     max_peers: usize,
+    versions: version::Config,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Error;
 
-const SUPPORTED_VERSIONS: &[u32] = &[
-    0x1, // Draft 34 / Version 1 (https://github.com/quicwg/base-drafts/wiki/21st-Implementation-Draft)
-];
-
 macro_rules! is_supported {
-    ($packet:ident, $publisher:ident) => {{
-        let supported = SUPPORTED_VERSIONS
-            .iter()
-            .cloned()
-            .any(|v| v == $packet.version);
+    ($self:ident, $packet:ident, $publisher:ident) => {{
+        let supported = $self.versions.is_supported($packet.version);
+        let server_versions = $self.versions.supported_versions();
 
         if supported {
             //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.1
             //# Upon receiving a client initial with a supported version, the
             //# server logs this event with server_versions and chosen_version set
             $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
+                server_versions,
                 client_versions: &[],
                 chosen_version: Some($packet.version),
             });
@@ -51,7 +47,7 @@ macro_rules! is_supported {
             //# client's attempted version.  The absence of chosen_version implies
             //# no overlap was found.
             $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
+                server_versions,
                 client_versions: &[$packet.version],
                 chosen_version: None,
             });
@@ -63,12 +59,12 @@ macro_rules! is_supported {
 
 impl<Config: endpoint::Config> Default for Negotiator<Config> {
     fn default() -> Self {
-        Self::new(endpoint::DEFAULT_MAX_PEERS)
+        Self::new(endpoint::DEFAULT_MAX_PEERS, Default::default())
     }
 }
 
 impl<Config: endpoint::Config> Negotiator<Config> {
-    pub fn new(max_peers: usize) -> Self {
+    pub fn new(max_peers: usize, versions: version::Config) -> Self {
         Self {
             transmissions: if Config::ENDPOINT_TYPE.is_server() {
                 VecDeque::with_capacity(max_peers)
@@ -76,6 +72,7 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 VecDeque::new()
             },
             max_peers,
+            versions,
         }
     }
 
@@ -93,13 +90,13 @@ impl<Config: endpoint::Config> Negotiator<Config> {
 
         let packet = match packet {
             ProtectedPacket::Initial(packet) => {
-                if is_supported!(packet, publisher) {
+                if is_supported!(self, packet, publisher) {
                     return Ok(());
                 }
                 packet
             }
             ProtectedPacket::ZeroRtt(packet) => {
-                if is_supported!(packet, publisher) {
+                if is_supported!(self, packet, publisher) {
                     return Ok(());
                 }
 
@@ -150,8 +147,11 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
                 //# Servers SHOULD respond with a Version
                 //# Negotiation packet, provided that the datagram is sufficiently long.
-                self.transmissions
-                    .push_back(Transmission::new(*path, packet));
+                self.transmissions.push_back(Transmission::new(
+                    *path,
+                    packet,
+                    SupportedVersions(self.versions.supported_versions()),
+                ));
             }
         }
 
@@ -211,11 +211,15 @@ impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
 }
 
 impl<Path: path::Handle> Transmission<Path> {
-    pub fn new(path: Path, initial_packet: &packet::initial::ProtectedInitial) -> Self {
+    pub fn new(
+        path: Path,
+        initial_packet: &packet::initial::ProtectedInitial,
+        supported_versions: SupportedVersions,
+    ) -> Self {
         let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
         let version_packet = packet::version_negotiation::VersionNegotiation::from_initial(
             initial_packet,
-            SupportedVersions,
+            supported_versions,
         );
 
         let mut buffer = EncoderBuffer::new(&mut packet_buf);
@@ -273,12 +277,19 @@ impl<Path: path::Handle> tx::Message for &Transmission<Path> {
     }
 }
 
+/// The versions listed in a Version Negotiation packet, ordered by preference
 #[derive(Clone, Copy, Debug)]
-pub struct SupportedVersions;
+pub struct SupportedVersions(pub &'static [u32]);
+
+impl Default for SupportedVersions {
+    fn default() -> Self {
+        Self(version::Config::default().supported_versions())
+    }
+}
 
 impl EncoderValue for SupportedVersions {
     fn encode<E: Encoder>(&self, encoder: &mut E) {
-        for version in SUPPORTED_VERSIONS {
+        for version in self.0 {
             encoder.encode(version);
         }
 
@@ -477,7 +488,7 @@ mod tests {
                 tag: 0,
                 destination_connection_id: &[1u8, 2, 3][..],
                 source_connection_id: &[4u8, 5, 6][..],
-                supported_versions: SupportedVersions,
+                supported_versions: SupportedVersions::default(),
             }
         )
     }
@@ -567,6 +578,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn server_version_2_test() {
+        let mut publisher = Publisher::snapshot();
+
+        // version 2 is disabled by default
+        let mut server = Server::default();
+        assert_eq!(
+            on_initial_packet(
+                datagram_info(1200),
+                version::VERSION_2,
+                &mut server,
+                &mut publisher
+            ),
+            Err(Error),
+            "server implementations should error on disabled versions"
+        );
+        assert!(!server.transmissions.is_empty());
+
+        let versions = version::Config::builder()
+            .with_version_2(true)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut server = Server::new(endpoint::DEFAULT_MAX_PEERS, versions);
+        for version in [version::VERSION_1, version::VERSION_2] {
+            assert_eq!(
+                on_initial_packet(datagram_info(1200), version, &mut server, &mut publisher),
+                Ok(()),
+                "server implementations should accept enabled versions"
+            );
+        }
+        assert!(server.transmissions.is_empty());
+    }
+
     #[test]
     fn server_future_version_initial_test() {
         let mut server = Server::default();
@@ -635,7 +680,7 @@ mod tests {
 
     #[test]
     fn server_max_peers_test() {
-        let mut server = Server::new(2);
+        let mut server = Server::new(2, Default::default());
         let mut publisher = Publisher::snapshot();
 
         for _ in 0..5 {
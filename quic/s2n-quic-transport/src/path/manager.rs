@@ -9,8 +9,10 @@ use crate::{
     path::{challenge, Path},
     transmission,
 };
+use bytes::Bytes;
 use s2n_quic_core::{
     ack,
+    application::ServerName,
     connection::{self, Limits, PeerId},
     event::{
         self,
@@ -19,17 +21,23 @@ use s2n_quic_core::{
     },
     frame,
     frame::path_validation,
-    inet::DatagramInfo,
+    inet::{self, DatagramInfo},
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        mtu, Handle as _, Id,
+        mtu, Handle as _, Id, LocalAddress, RemoteAddress,
     },
     random,
-    recovery::congestion_controller::{self, Endpoint as _},
+    recovery::{
+        congestion_controller::{self, Endpoint as _},
+        CongestionController as _,
+    },
     stateless_reset,
     time::{timer, Timestamp},
-    transport,
+    transport::{
+        self,
+        parameters::{MigrationSupport, PreferredAddress},
+    },
 };
 use smallvec::SmallVec;
 
@@ -69,6 +77,53 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// A migration to a new path initiated by the client
+    ///
+    /// The path is created the next time the connection is woken up, since it requires access to
+    /// the endpoint's congestion controller and MTU configuration.
+    migration_request: Option<MigrationRequest<Config::PathHandle>>,
+
+    /// The path that is being validated for a migration initiated by the client
+    pending_local_migration: Option<PendingMigration>,
+
+    /// Set if the peer sent the `disable_active_migration` transport parameter
+    peer_migration_disabled: bool,
+
+    /// The server's preferred address, which the client migrates to once the handshake is
+    /// confirmed
+    preferred_address: Option<RemoteAddress>,
+
+    /// Set once the client has acted on the server's preferred address
+    preferred_address_used: bool,
+
+    /// The application protocol negotiated during the handshake, which is passed to the
+    /// congestion controller endpoint for new paths
+    application_protocol: Option<Bytes>,
+
+    /// The server name negotiated during the handshake
+    server_name: Option<ServerName>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct MigrationRequest<Handle> {
+    handle: Handle,
+    peer_connection_id: PeerId,
+    cause: MigrationCause,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PendingMigration {
+    path: u8,
+    cause: MigrationCause,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MigrationCause {
+    /// The application requested a migration to a new local address
+    Application,
+    /// The client is migrating to the server's preferred address
+    PreferredAddress,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -79,6 +134,13 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            migration_request: None,
+            pending_local_migration: None,
+            peer_migration_disabled: false,
+            preferred_address: None,
+            preferred_address_used: false,
+            application_protocol: None,
+            server_name: None,
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
@@ -158,6 +220,14 @@ impl<Config: endpoint::Config> Manager<Config> {
         path_id(self.active)
     }
 
+    /// Returns a snapshot of the statistics for each path
+    pub fn stats(&self) -> impl Iterator<Item = connection::PathStats> + '_ {
+        self.paths
+            .iter()
+            .enumerate()
+            .map(|(idx, path)| path.stats(path_id(idx as u8)))
+    }
+
     pub fn check_active_path_is_synced(&self) {
         if cfg!(debug_assertions) {
             for (idx, path) in self.paths.iter().enumerate() {
@@ -204,21 +274,35 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path(&self, handle: &Config::PathHandle) -> Option<(Id, &Path<Config>)> {
-        self.paths
-            .iter()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+        let idx = self.path_index(handle)?;
+        Some((path_id(idx as u8), &self.paths[idx]))
     }
 
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path_mut(&mut self, handle: &Config::PathHandle) -> Option<(Id, &mut Path<Config>)> {
+        let idx = self.path_index(handle)?;
+        Some((path_id(idx as u8), &mut self.paths[idx]))
+    }
+
+    #[inline]
+    fn path_index(&self, handle: &Config::PathHandle) -> Option<usize> {
+        // A client that migrated to a new local address has multiple paths to the same server
+        // address, which are only distinguished by the local address. The same applies to a
+        // server that is reached on its preferred address.
+        if self.paths.len() > 1 {
+            if let Some(idx) = self
+                .paths
+                .iter()
+                .position(|path| path.handle.unmapped_eq(handle))
+            {
+                return Some(idx);
+            }
+        }
+
         self.paths
-            .iter_mut()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+            .iter()
+            .position(|path| Path::eq_by_handle(path, handle))
     }
 
     /// Returns an iterator over all paths pending path_challenge or path_response
@@ -249,7 +333,16 @@ impl<Config: endpoint::Config> Manager<Config> {
         let valid_initial_received = self.valid_initial_received();
 
         let matched_path = if handshake_confirmed {
-            self.path_mut(path_handle)
+            self.path_mut(path_handle).filter(|(_, path)| {
+                // A client migrating to the server's preferred address keeps its own address, so
+                // the path is only distinguished by the server's local address.
+                !(Config::ENDPOINT_TYPE.is_server()
+                    && limits.is_preferred_address(&path_handle.local_address().0)
+                    && !path
+                        .handle
+                        .local_address()
+                        .unmapped_eq(&path_handle.local_address()))
+            })
         } else {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9
             //# The design of QUIC relies on endpoints retaining a stable address
@@ -422,8 +515,11 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
         })?;
 
-        let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
-        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+        let cc = self.new_congestion_controller(
+            congestion_controller_endpoint,
+            &mtu_config,
+            &remote_address,
+        );
 
         let peer_connection_id = {
             if self.active_path().local_connection_id != datagram.destination_connection_id {
@@ -498,6 +594,428 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok((new_path_id, amplification_outcome))
     }
 
+    /// Called when the peer's `disable_active_migration` transport parameter is received
+    #[inline]
+    pub fn on_peer_migration_support(&mut self, migration_support: MigrationSupport) {
+        self.peer_migration_disabled = migration_support == MigrationSupport::Disabled;
+    }
+
+    /// Called when the application requests the connection to migrate to a new local address
+    ///
+    /// The new path is created on the next call to [`Self::on_migration_request`].
+    pub fn request_migration(
+        &mut self,
+        local_address: LocalAddress,
+        handshake_confirmed: bool,
+    ) -> Result<(), migration::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(migration::Error::NotSupported);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !handshake_confirmed {
+            return Err(migration::Error::HandshakeNotConfirmed);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# If the peer sent the disable_active_migration transport parameter, an endpoint also MUST NOT send
+        //# packets (including probing packets; see Section 9.1) from a different local address to the address
+        //# the peer used during the handshake, unless the endpoint has acted on a preferred_address transport
+        //# parameter from the peer.
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# This transport
+        //# parameter does not prohibit connection migration after a client
+        //# has acted on a preferred_address transport parameter.
+        if self.peer_migration_disabled && !self.preferred_address_used {
+            return Err(migration::Error::DisabledByPeer);
+        }
+
+        if self.migration_request.is_some() || self.pending_local_migration.is_some() {
+            return Err(migration::Error::InProgress);
+        }
+
+        let mut handle = self.active_path().handle;
+        handle.set_local_address(local_address);
+
+        if self.active_path().handle.unmapped_eq(&handle) {
+            return Err(migration::Error::AddressUnchanged);
+        }
+
+        // Paths that were previously used are replaced so a client can move back and forth
+        // between addresses without exhausting the path limit
+        if self.path_index_for_migration(&handle) >= MAX_ALLOWED_PATHS {
+            return Err(migration::Error::PathLimitExceeded);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+        //# An endpoint MUST NOT reuse a connection ID when sending from more
+        //# than one local address -- for example, when initiating connection
+        //# migration as described in Section 9.2 or when probing a new network
+        //# path as described in Section 9.1.
+        let peer_connection_id = self
+            .peer_id_registry
+            .consume_new_id_for_new_path()
+            .ok_or(migration::Error::InsufficientConnectionIds)?;
+
+        self.migration_request = Some(MigrationRequest {
+            handle,
+            peer_connection_id,
+            cause: MigrationCause::Application,
+        });
+
+        Ok(())
+    }
+
+    /// Called when the server's `preferred_address` transport parameter is received
+    pub fn on_preferred_address(
+        &mut self,
+        preferred_address: &PreferredAddress,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# A server
+        //# that chooses a zero-length connection ID MUST NOT provide a
+        //# preferred address.  Similarly, a server MUST NOT include a zero-
+        //# length connection ID in this transport parameter.  A client MUST
+        //# treat a violation of these requirements as a connection error of
+        //# type TRANSPORT_PARAMETER_ERROR.
+        if preferred_address.connection_id.is_empty()
+            || self.active_path().peer_connection_id.is_empty()
+        {
+            return Err(transport::Error::TRANSPORT_PARAMETER_ERROR
+                .with_reason("zero-length connection id with preferred_address"));
+        }
+
+        let connection_id = PeerId::try_from_bytes(preferred_address.connection_id.as_bytes())
+            .ok_or_else(|| {
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("invalid preferred_address connection id")
+            })?;
+
+        self.peer_id_registry
+            .register_preferred_address_connection_id(
+                &connection_id,
+                &preferred_address.stateless_reset_token,
+            )?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.3
+        //# A client that migrates to a new address SHOULD use a preferred
+        //# address from the same address family for the server.
+        let remote_address = self.active_path().remote_address();
+        let preferred: Option<inet::SocketAddress> = match remote_address.0.unmap() {
+            inet::SocketAddress::IpV4(_) => preferred_address.ipv4_address.map(Into::into),
+            inet::SocketAddress::IpV6(_) => preferred_address.ipv6_address.map(Into::into),
+        };
+
+        self.preferred_address = preferred
+            .map(RemoteAddress)
+            .filter(|preferred| !preferred.unmapped_eq(&remote_address));
+
+        Ok(())
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+    //# Once the handshake is confirmed, the client SHOULD select one of the
+    //# two addresses provided by the server and initiate path validation
+    //# (see Section 8.2).
+    /// Called on the client when the handshake is confirmed
+    ///
+    /// Returns `true` if a migration to the server's preferred address was requested, in which
+    /// case the path is created on the next call to [`Self::on_migration_request`].
+    pub fn on_handshake_confirmed(&mut self) -> bool {
+        let Some(remote_address) = self.preferred_address.take() else {
+            return false;
+        };
+
+        if self.migration_request.is_some() || self.pending_local_migration.is_some() {
+            return false;
+        }
+
+        let mut handle = self.active_path().handle;
+        handle.set_remote_address(remote_address);
+
+        if self.path_index_for_migration(&handle) >= MAX_ALLOWED_PATHS {
+            return false;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+        //# Similarly, an endpoint MUST NOT reuse a connection ID when sending to
+        //# more than one destination address.
+        let Some(peer_connection_id) = self.peer_id_registry.consume_new_id_for_new_path() else {
+            return false;
+        };
+
+        self.migration_request = Some(MigrationRequest {
+            handle,
+            peer_connection_id,
+            cause: MigrationCause::PreferredAddress,
+        });
+
+        true
+    }
+
+    /// Called when the application protocol and server name have been negotiated
+    pub fn on_handshake_info(
+        &mut self,
+        application_protocol: &Bytes,
+        server_name: Option<&ServerName>,
+    ) {
+        self.application_protocol = Some(application_protocol.clone());
+        self.server_name = server_name.cloned();
+
+        for path in self.paths.iter_mut() {
+            path.congestion_controller
+                .on_handshake_info(application_protocol, self.server_name.as_deref());
+        }
+    }
+
+    /// Creates the congestion controller for a new path
+    ///
+    /// Any settings the application changed on the active path's congestion controller, such
+    /// as the congestion control algorithm, are carried over to the new path.
+    fn new_congestion_controller(
+        &self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu_config: &mtu::Config,
+        remote_address: &inet::SocketAddress,
+    ) -> <Config::CongestionControllerEndpoint as congestion_controller::Endpoint>::CongestionController
+    {
+        let path_info = congestion_controller::PathInfo::new(mtu_config, remote_address)
+            .with_application_protocol(self.application_protocol.as_deref())
+            .with_server_name(self.server_name.as_deref());
+        let mut cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+        cc.inherit_settings(&self.active_path().congestion_controller);
+        cc
+    }
+
+    /// Creates the path for a migration initiated by the client, if any
+    pub fn on_migration_request<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        limits: &Limits,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) {
+        let Some(request) = self.migration_request.take() else {
+            return;
+        };
+
+        let handle = request.handle;
+        let remote_address = handle.remote_address();
+
+        let Ok(mtu_config) = mtu.config(&remote_address) else {
+            // the configuration was already valid for the active path, so this can only happen
+            // for the server's preferred address, in which case the client stays on the active
+            // path
+            debug_assert_eq!(request.cause, MigrationCause::PreferredAddress);
+            return;
+        };
+
+        let new_path_idx = self.path_index_for_migration(&handle);
+        debug_assert!(new_path_idx < MAX_ALLOWED_PATHS);
+        let new_path_id = path_id(new_path_idx as u8);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.4
+        //# Because port-only changes are commonly the
+        //# result of NAT rebinding or other middlebox activity, the endpoint MAY
+        //# instead retain its congestion control state and round-trip estimate
+        //# in those cases instead of reverting to initial values.
+        //
+        // A new local address usually means a new network interface, so the path starts with a
+        // new congestion controller and round-trip time estimate.
+        let rtt = self
+            .active_path()
+            .rtt_estimator
+            .for_new_path(limits.initial_round_trip_time());
+        let cc = self.new_congestion_controller(
+            congestion_controller_endpoint,
+            &mtu_config,
+            &remote_address,
+        );
+
+        let path = Path::new(
+            handle,
+            request.peer_connection_id,
+            self.active_path().local_connection_id,
+            rtt,
+            cc,
+            true,
+            mtu_config,
+            limits.anti_amplification_multiplier(),
+            limits.pto_jitter_percentage(),
+        );
+
+        let active_path = self.active_path();
+        let active_path_id = self.active_path_id();
+        publisher.on_path_created(event::builder::PathCreated {
+            active: path_event!(active_path, active_path_id),
+            new: path_event!(path, new_path_id),
+        });
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: new_path_id.into_event(),
+            mtu: path.mtu_controller.max_datagram_size() as u16,
+            cause: MtuUpdatedCause::NewPath,
+            search_complete: path.mtu_controller.is_search_completed(),
+        });
+
+        if new_path_idx < self.paths.len() {
+            self.replace_path(new_path_idx, path);
+        } else {
+            self.paths.push(path);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# Before an endpoint can
+        //# send non-probing frames on a validated path to a new local address, it
+        //# needs to validate the new path using the process described in Section 8.2.
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.2
+        //# A client that migrates to a preferred address MUST validate the
+        //# address it chooses before migrating; see Section 21.5.3.
+        self.set_challenge(new_path_id, random_generator);
+        self.pending_local_migration = Some(PendingMigration {
+            path: new_path_idx as u8,
+            cause: request.cause,
+        });
+
+        self.publish_migration_status(
+            request.cause,
+            new_path_id,
+            event::builder::LocalMigrationStatus::Started,
+            publisher,
+        );
+    }
+
+    /// Returns the index of the path that will be used for a migration to the given handle
+    fn path_index_for_migration(&self, handle: &Config::PathHandle) -> usize {
+        self.paths
+            .iter()
+            .position(|path| !path.is_active() && path.handle.unmapped_eq(handle))
+            .unwrap_or(self.paths.len())
+    }
+
+    /// Replaces an inactive path with the path for a migration
+    ///
+    /// The previous path's connection ID is retired if no other path uses it.
+    fn replace_path(&mut self, index: usize, path: Path<Config>) {
+        let previous = core::mem::replace(&mut self.paths[index], path);
+
+        // The previous path can no longer be used as a fallback
+        if self.last_known_active_validated_path == Some(index as u8) {
+            self.last_known_active_validated_path = None;
+        }
+
+        if self.pending_packet_authentication == Some(index as u8) {
+            self.pending_packet_authentication = None;
+        }
+
+        let peer_connection_id = previous.peer_connection_id;
+        if !self
+            .paths
+            .iter()
+            .any(|path| path.peer_connection_id == peer_connection_id)
+        {
+            self.peer_id_registry.retire_id(&peer_connection_id);
+        }
+    }
+
+    /// Switches to the path of a migration initiated by the client once it is validated
+    fn on_local_migration_validated<Pub: event::ConnectionPublisher>(
+        &mut self,
+        new_path_id: Id,
+        cause: MigrationCause,
+        publisher: &mut Pub,
+    ) -> AmplificationOutcome {
+        self.pending_local_migration = None;
+
+        // The connection ID might have been retired while the path was being validated
+        let peer_connection_id = self[new_path_id].peer_connection_id;
+        if !self.peer_id_registry.is_active(&peer_connection_id) {
+            match self.peer_id_registry.consume_new_id_for_existing_path(
+                new_path_id,
+                peer_connection_id,
+                publisher,
+            ) {
+                Some(peer_connection_id) => {
+                    self[new_path_id].peer_connection_id = peer_connection_id;
+                }
+                None => {
+                    self.publish_migration_status(
+                        cause,
+                        new_path_id,
+                        event::builder::LocalMigrationStatus::Abandoned,
+                        publisher,
+                    );
+                    return AmplificationOutcome::Unchanged;
+                }
+            }
+        }
+
+        if self.active_path().is_validated() {
+            self.last_known_active_validated_path = Some(self.active);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# An endpoint can migrate a connection to a new local address by
+        //# sending packets containing non-probing frames from that address.
+        let prev_path_id = self.active_path_id();
+        let amplification_outcome = self.activate_path(publisher, prev_path_id, new_path_id);
+
+        // Restart ECN validation to check that the path supports ECN
+        let path = self.active_path_mut();
+        path.ecn_controller
+            .restart(path_event!(path, new_path_id), publisher);
+
+        self.on_local_migration_completed(cause, new_path_id, publisher);
+
+        amplification_outcome
+    }
+
+    fn on_local_migration_completed<Pub: event::ConnectionPublisher>(
+        &mut self,
+        cause: MigrationCause,
+        path_id: Id,
+        publisher: &mut Pub,
+    ) {
+        if cause == MigrationCause::PreferredAddress {
+            self.preferred_address_used = true;
+        }
+
+        self.publish_migration_status(
+            cause,
+            path_id,
+            event::builder::LocalMigrationStatus::Completed,
+            publisher,
+        );
+    }
+
+    /// Publishes the status of a migration if it was requested by the application
+    fn publish_migration_status<Pub: event::ConnectionPublisher>(
+        &self,
+        cause: MigrationCause,
+        path_id: Id,
+        status: event::builder::LocalMigrationStatus,
+        publisher: &mut Pub,
+    ) {
+        if cause != MigrationCause::Application {
+            return;
+        }
+
+        let path = &self[path_id];
+        publisher.on_local_migration_updated(event::builder::LocalMigrationUpdated {
+            status,
+            path: path_event!(path, path_id),
+        });
+    }
+
     fn set_challenge(&mut self, path_id: Id, random_generator: &mut dyn random::Generator) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
         //# The endpoint MUST use unpredictable data in every PATH_CHALLENGE
@@ -616,6 +1134,18 @@ impl<Config: endpoint::Config> Manager<Config> {
                     path: path_event!(path, id),
                     challenge_data: path.challenge.challenge_data().into_event(),
                 });
+
+                if let Some(migration) = self
+                    .pending_local_migration
+                    .filter(|migration| migration.path == id as u8)
+                {
+                    return self.on_local_migration_validated(
+                        path_id(id as u8),
+                        migration.cause,
+                        publisher,
+                    );
+                }
+
                 // A path was validated so check if it becomes the new
                 // last_known_active_validated_path
                 if path.is_activated() {
@@ -685,6 +1215,16 @@ impl<Config: endpoint::Config> Manager<Config> {
         if !path_validation_probing.is_probing() && self.active_path_id() != path_id {
             amplification_outcome =
                 self.update_active_path(path_id, random_generator, publisher)?;
+
+            // The peer switched to the migrated path before the path challenge was answered
+            if let Some(migration) = self
+                .pending_local_migration
+                .filter(|migration| migration.path == path_id.as_u8())
+            {
+                self.pending_local_migration = None;
+                self.on_local_migration_completed(migration.cause, path_id, publisher);
+            }
+
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3
             //# After changing the address to which it sends non-probing packets, an
             //# endpoint can abandon any path validation for other addresses.
@@ -780,6 +1320,25 @@ impl<Config: endpoint::Config> Manager<Config> {
             path.on_timeout(timestamp, path_id(id as u8), random_generator, publisher);
         }
 
+        if let Some(migration) = self.pending_local_migration {
+            let path_id = path_id(migration.path);
+            if !self[path_id].is_challenge_pending() {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+                //# If path validation
+                //# fails, the client MUST continue sending all future packets to the
+                //# server's original IP address.
+                //
+                // The path challenge was abandoned so the connection stays on the active path
+                self.pending_local_migration = None;
+                self.publish_migration_status(
+                    migration.cause,
+                    path_id,
+                    event::builder::LocalMigrationStatus::Abandoned,
+                    publisher,
+                );
+            }
+        }
+
         let mut amplification_outcome = AmplificationOutcome::Unchanged;
 
         if self.active_path().failed_validation() {
@@ -823,6 +1382,67 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(amplification_outcome)
     }
 
+    /// Called when the network reported that the peer is unreachable on the given path
+    ///
+    /// Returns `Ok(true)` if the active path changed from a path blocked by amplification limits
+    /// to a path not blocked by amplification limits.
+    pub fn on_path_unreachable<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_id: Id,
+        publisher: &mut Pub,
+    ) -> Result<AmplificationOutcome, connection::Error> {
+        if path_id != self.active_path_id() {
+            // Stop validating a path that won't receive a response
+            self[path_id].abandon_challenge(publisher, path_id.as_u8() as u64);
+
+            if let Some(migration) = self
+                .pending_local_migration
+                .filter(|migration| migration.path == path_id.as_u8())
+            {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+                //# If path validation
+                //# fails, the client MUST continue sending all future packets to the
+                //# server's original IP address.
+                self.pending_local_migration = None;
+                self.publish_migration_status(
+                    migration.cause,
+                    path_id,
+                    event::builder::LocalMigrationStatus::Abandoned,
+                    publisher,
+                );
+            }
+
+            return Ok(AmplificationOutcome::Unchanged);
+        }
+
+        // Treat the error as a loss signal until it is repeated for a probe, rather than
+        // abandoning the active path on a single error
+        if !self[path_id].on_unreachable() {
+            return Ok(AmplificationOutcome::Unchanged);
+        }
+
+        match self.last_known_active_validated_path {
+            Some(last_known_active_validated_path)
+                if last_known_active_validated_path != self.active =>
+            {
+                // Fall back to the last path the peer was reachable on rather than
+                // waiting for the active path to time out
+                let prev_path_id = path_id;
+                let new_path_id = self::path_id(last_known_active_validated_path);
+                let amplification_outcome =
+                    self.activate_path(publisher, prev_path_id, new_path_id);
+                self.last_known_active_validated_path = None;
+                Ok(amplification_outcome)
+            }
+            _ => {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-10
+                //# An endpoint MAY discard connection state if it does not have a
+                //# validated path on which it can send packets; see Section 8.2
+                Err(connection::Error::no_valid_path())
+            }
+        }
+    }
+
     /// true if ALL paths are amplification_limited
     #[inline]
     pub fn is_amplification_limited(&self) -> bool {
@@ -887,6 +887,143 @@ fn do_not_add_new_path_if_client() {
     assert_eq!(manager.paths.len(), 1);
 }
 
+#[test]
+fn reject_migration_request() {
+    let local_address: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let local_address = LocalAddress::from(SocketAddress::from(local_address));
+    let peer_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+
+    // Setup:
+    let mut manager = manager_server(helper_path(peer_id));
+
+    // Expectation:
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+    //= type=test
+    //# Clients are responsible for initiating all migrations.
+    assert_eq!(
+        manager.request_migration(local_address, true),
+        Err(migration::Error::NotSupported)
+    );
+
+    // Setup:
+    let first_path = ClientPath::new(
+        Default::default(),
+        peer_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+        0, // pto_jitter_percentage
+    );
+    let mut manager = manager_client(first_path);
+
+    // Expectation:
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+    //= type=test
+    //# An endpoint MUST NOT initiate
+    //# connection migration before the handshake is confirmed, as defined
+    //# in section 4.1.2 of [QUIC-TLS].
+    assert_eq!(
+        manager.request_migration(local_address, false),
+        Err(migration::Error::HandshakeNotConfirmed)
+    );
+
+    // The testing path handle doesn't track the local address
+    assert_eq!(
+        manager.request_migration(local_address, true),
+        Err(migration::Error::AddressUnchanged)
+    );
+
+    // Trigger:
+    manager.on_peer_migration_support(MigrationSupport::Disabled);
+
+    // Expectation:
+    assert_eq!(
+        manager.request_migration(local_address, true),
+        Err(migration::Error::DisabledByPeer)
+    );
+    assert!(manager.migration_request.is_none());
+    assert_eq!(manager.paths.len(), 1);
+}
+
+#[test]
+fn preferred_address_migration_request() {
+    let peer_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let preferred_id = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    let preferred_addr: SocketAddr = "127.0.0.2:4433".parse().unwrap();
+    let preferred_addr = SocketAddress::from(preferred_addr);
+    let mut preferred_address = PreferredAddress {
+        ipv4_address: None,
+        ipv6_address: None,
+        connection_id: connection::UnboundedId::try_from_bytes(&[]).unwrap(),
+        stateless_reset_token: TEST_TOKEN_2,
+    };
+
+    // Setup:
+    let first_path = ClientPath::new(
+        Default::default(),
+        peer_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+        0, // pto_jitter_percentage
+    );
+    let mut manager = manager_client(first_path);
+    manager
+        .peer_id_registry
+        .register_initial_connection_id(peer_id);
+
+    // Expectation:
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+    //= type=test
+    //# Similarly, a server MUST NOT include a zero-
+    //# length connection ID in this transport parameter.
+    assert_eq!(
+        manager
+            .on_preferred_address(&preferred_address)
+            .unwrap_err()
+            .code,
+        transport::Error::TRANSPORT_PARAMETER_ERROR.code
+    );
+
+    // Trigger:
+    preferred_address.connection_id = preferred_id.into();
+    preferred_address.ipv4_address = match preferred_addr {
+        SocketAddress::IpV4(addr) => Some(addr),
+        SocketAddress::IpV6(_) => unreachable!(),
+    };
+    manager.on_preferred_address(&preferred_address).unwrap();
+
+    // Expectation:
+    assert_eq!(
+        manager.preferred_address,
+        Some(RemoteAddress(preferred_addr))
+    );
+    assert!(manager.migration_request.is_none());
+
+    // Trigger:
+    assert!(manager.on_handshake_confirmed());
+
+    // Expectation:
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+    //= type=test
+    //# Once the handshake is confirmed, the client SHOULD select one of the
+    //# two addresses provided by the server and initiate path validation
+    //# (see Section 8.2).
+    let request = manager.migration_request.unwrap();
+    assert_eq!(request.handle, RemoteAddress(preferred_addr));
+    assert_eq!(request.peer_connection_id, preferred_id);
+    assert_eq!(request.cause, MigrationCause::PreferredAddress);
+
+    // The preferred address is only acted on once
+    assert!(!manager.on_handshake_confirmed());
+}
+
 #[test]
 //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
 //= type=test
@@ -1878,8 +2015,158 @@ fn last_known_validated_path_should_update_on_path_response() {
     assert_eq!(manager.last_known_active_validated_path, None);
 }
 
+#[test]
+// An unreachable active path should fall back to the last known validated path
+fn on_path_unreachable_reverts_to_last_known_validated_path() {
+    // Setup:
+    let mut publisher = Publisher::no_snapshot();
+    let mut helper = helper_manager_with_paths(&mut publisher);
+    assert_eq!(helper.manager.active_path_id(), helper.first_path_id);
+
+    // Trigger 1:
+    let amplification_outcome = helper
+        .manager
+        .on_path_unreachable(helper.first_path_id, &mut publisher)
+        .unwrap();
+
+    // Expectation 1:
+    assert!(amplification_outcome.is_unchanged());
+    assert_eq!(helper.manager.active_path_id(), helper.first_path_id);
+
+    // Trigger 2:
+    helper.manager[helper.first_path_id].pto_backoff *= 2;
+    let result = helper
+        .manager
+        .on_path_unreachable(helper.first_path_id, &mut publisher);
+
+    // Expectation 2:
+    assert!(result.is_ok());
+    assert_eq!(helper.manager.active_path_id(), helper.zero_path_id);
+    assert_eq!(helper.manager.last_known_active_validated_path, None);
+}
+
+#[test]
+// An unreachable active path without a validated path to fall back to should close the connection
+fn on_path_unreachable_without_validated_path() {
+    // Setup:
+    let mut publisher = Publisher::no_snapshot();
+    let mut helper = helper_manager_with_paths_base(true, false, &mut publisher);
+
+    // Trigger:
+    for _ in 0..2 {
+        assert!(helper
+            .manager
+            .on_path_unreachable(helper.first_path_id, &mut publisher)
+            .is_ok());
+    }
+    helper.manager[helper.first_path_id].pto_backoff *= 2;
+    let result = helper
+        .manager
+        .on_path_unreachable(helper.first_path_id, &mut publisher);
+
+    // Expectation:
+    assert_eq!(result, Err(connection::Error::no_valid_path()));
+    assert_eq!(helper.manager.active_path_id(), helper.first_path_id);
+}
+
+#[test]
+// Unreachable errors should be forgotten once the peer acknowledges a packet on the path
+fn on_path_unreachable_reset_by_ack() {
+    // Setup:
+    let mut publisher = Publisher::no_snapshot();
+    let mut helper = helper_manager_with_paths_base(true, false, &mut publisher);
+    assert!(helper
+        .manager
+        .on_path_unreachable(helper.first_path_id, &mut publisher)
+        .is_ok());
+    helper.manager[helper.first_path_id].pto_backoff *= 2;
+
+    // Trigger:
+    helper.manager[helper.first_path_id].reset_pto_backoff();
+    helper.manager[helper.first_path_id].pto_backoff *= 2;
+    let result = helper
+        .manager
+        .on_path_unreachable(helper.first_path_id, &mut publisher);
+
+    // Expectation:
+    assert!(result.is_ok());
+    assert_eq!(helper.manager.active_path_id(), helper.first_path_id);
+}
+
+#[test]
+// An unreachable path that isn't active should stop being validated
+fn on_path_unreachable_abandons_challenge() {
+    // Setup:
+    let mut publisher = Publisher::no_snapshot();
+    let mut helper = helper_manager_with_paths(&mut publisher);
+    assert!(helper.manager[helper.second_path_id].is_challenge_pending());
+
+    // Trigger:
+    let amplification_outcome = helper
+        .manager
+        .on_path_unreachable(helper.second_path_id, &mut publisher)
+        .unwrap();
+
+    // Expectation:
+    assert!(amplification_outcome.is_unchanged());
+    assert!(!helper.manager[helper.second_path_id].is_challenge_pending());
+    assert!(helper.manager[helper.second_path_id].failed_validation());
+    assert_eq!(helper.manager.active_path_id(), helper.first_path_id);
+}
+
 // creates a test path_manager. also check out `helper_manager_with_paths`
 // which calls this helper with preset options
+#[test]
+// Reusing the slot of an inactive path for a migration should retire its connection ID and
+// stop using it as a fallback
+fn replace_path_for_migration() {
+    // Setup:
+    let mut publisher = Publisher::no_snapshot();
+    let mut helper = helper_manager_with_paths(&mut publisher);
+    let first_conn_id = helper.manager[helper.first_path_id].peer_connection_id;
+    let second_conn_id = helper.manager[helper.second_path_id].peer_connection_id;
+    assert_eq!(
+        helper
+            .manager
+            .peer_id_registry
+            .consume_new_id_for_new_path(),
+        Some(second_conn_id)
+    );
+
+    // Trigger 1:
+    helper.manager.replace_path(
+        helper.second_path_id.as_u8() as usize,
+        helper_path(first_conn_id),
+    );
+
+    // Expectation 1:
+    assert!(!helper.manager.peer_id_registry.is_active(&second_conn_id));
+    assert!(helper.manager.peer_id_registry.is_active(&first_conn_id));
+
+    // Trigger 2:
+    helper.manager.replace_path(
+        helper.second_path_id.as_u8() as usize,
+        helper_path(first_conn_id),
+    );
+
+    // Expectation 2:
+    // the connection ID is still used by the active path
+    assert!(helper.manager.peer_id_registry.is_active(&first_conn_id));
+
+    // Trigger 3:
+    assert_eq!(
+        helper.manager.last_known_active_validated_path,
+        Some(helper.zero_path_id.as_u8())
+    );
+    helper.manager.replace_path(
+        helper.zero_path_id.as_u8() as usize,
+        helper_path(first_conn_id),
+    );
+
+    // Expectation 3:
+    assert_eq!(helper.manager.last_known_active_validated_path, None);
+}
+
 pub fn helper_manager_with_paths_base(
     register_second_conn_id: bool,
     validate_path_zero: bool,
@@ -40,6 +40,17 @@ enum State {
     },
 }
 
+/// Running totals of the packets and bytes exchanged on a path
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counters {
+    pub packets_sent: u64,
+    pub packets_lost: u64,
+    /// Lost packets whose contents were queued for retransmission
+    pub packets_retransmitted: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
 #[derive(Debug)]
 pub struct Path<Config: endpoint::Config> {
     /// The peer's socket address
@@ -54,12 +65,17 @@ pub struct Path<Config: endpoint::Config> {
     pub congestion_controller: <Config::CongestionControllerEndpoint as congestion_controller::Endpoint>::CongestionController,
     /// Probe timeout backoff multiplier
     pub pto_backoff: u32,
+    /// The probe timeout backoff multiplier when the network first reported that the peer is
+    /// unreachable on the path
+    unreachable_pto_backoff: Option<u32>,
     /// Tracks whether this path has passed Address or Path validation
     state: State,
     /// Controller for determining the maximum transmission unit of the path
     pub mtu_controller: mtu::Controller,
     /// Controller for determining the ECN capability of the path
     pub ecn_controller: ecn::Controller,
+    /// Packet and byte counters for the path
+    pub counters: Counters,
 
     /// True if the path has been validated by the peer
     peer_validated: bool,
@@ -94,9 +110,11 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             rtt_estimator: self.rtt_estimator,
             congestion_controller: self.congestion_controller.clone(),
             pto_backoff: self.pto_backoff,
+            unreachable_pto_backoff: self.unreachable_pto_backoff,
             state: self.state,
             mtu_controller: self.mtu_controller.clone(),
             ecn_controller: self.ecn_controller.clone(),
+            counters: self.counters,
             peer_validated: self.peer_validated,
             challenge: self.challenge.clone(),
             response_data: self.response_data,
@@ -137,6 +155,11 @@ impl<Config: endpoint::Config> Path<Config> {
             Type::Client => State::Validated,
         };
         let peer_socket_address = handle.remote_address();
+        let ecn_controller = if congestion_controller.is_l4s() {
+            ecn::Controller::l4s()
+        } else {
+            ecn::Controller::default()
+        };
         Path {
             handle,
             peer_connection_id,
@@ -144,9 +167,11 @@ impl<Config: endpoint::Config> Path<Config> {
             rtt_estimator,
             congestion_controller,
             pto_backoff: INITIAL_PTO_BACKOFF,
+            unreachable_pto_backoff: None,
             state,
             mtu_controller: mtu::Controller::new(mtu_config, &peer_socket_address),
-            ecn_controller: ecn::Controller::default(),
+            ecn_controller,
+            counters: Counters::default(),
             peer_validated,
             challenge: Challenge::disabled(),
             response_data: None,
@@ -157,6 +182,34 @@ impl<Config: endpoint::Config> Path<Config> {
         }
     }
 
+    /// Returns a snapshot of the statistics for the path
+    pub fn stats(&self, path_id: Id) -> connection::PathStats {
+        let mut stats = connection::PathStats::default();
+        stats.id = path_id.into_event();
+        stats.local_address = *self.local_address();
+        stats.remote_address = *self.remote_address();
+        stats.is_active = self.is_active;
+        stats.is_validated = self.is_validated();
+        stats.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        stats.min_rtt = self.rtt_estimator.min_rtt();
+        stats.latest_rtt = self.rtt_estimator.latest_rtt();
+        stats.congestion_window = self.congestion_controller.congestion_window();
+        stats.bytes_in_flight = self.congestion_controller.bytes_in_flight();
+        stats.pacing_rate = self
+            .congestion_controller
+            .pacing_rate()
+            .map(|rate| rate.as_bytes_per_second());
+        stats.mtu = self.mtu_controller.max_datagram_size() as u16;
+        stats.mtu_search_complete = self.mtu_controller.is_search_completed();
+        stats.ecn_state = self.ecn_controller.state();
+        stats.packets_sent = self.counters.packets_sent;
+        stats.packets_lost = self.counters.packets_lost;
+        stats.packets_retransmitted = self.counters.packets_retransmitted;
+        stats.bytes_sent = self.counters.bytes_sent;
+        stats.bytes_received = self.counters.bytes_received;
+        stats
+    }
+
     #[inline]
     pub fn remote_address(&self) -> RemoteAddress {
         self.handle.remote_address()
@@ -194,6 +247,8 @@ impl<Config: endpoint::Config> Path<Config> {
             return;
         }
 
+        self.counters.bytes_sent += bytes as u64;
+
         debug_assert_ne!(
             self.clamp_datagram_size(bytes, transmission::Mode::Normal),
             0,
@@ -212,6 +267,8 @@ impl<Config: endpoint::Config> Path<Config> {
     pub fn on_bytes_received(&mut self, bytes: usize) -> AmplificationOutcome {
         let was_at_amplification_limit = self.at_amplification_limit();
 
+        self.counters.bytes_received += bytes as u64;
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1
         //# For the purposes of
         //# avoiding amplification prior to address validation, servers MUST
@@ -304,6 +361,56 @@ impl<Config: endpoint::Config> Path<Config> {
         );
     }
 
+    /// Returns true if the packet quoted in an ICMP error was sent on this path
+    ///
+    /// The destination connection ID of the quoted packet is compared with the peer's connection
+    /// ID, which an off-path attacker is unable to observe.
+    #[inline]
+    pub fn is_quoted_packet(&self, payload: &[u8]) -> bool {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# This validation SHOULD use the quoted packet supplied in
+        //# the payload of an ICMP message to associate the message with a
+        //# corresponding transport connection (see Section 4.6.1 of [DPLPMTUD]).
+        let peer_connection_id = self.peer_connection_id.as_bytes();
+
+        let Some(tag) = payload.first() else {
+            return false;
+        };
+
+        // the most significant bit of the first byte is set for long header packets
+        let quoted_connection_id = if tag & 0x80 != 0 {
+            // the connection id follows the 4 byte version and the connection id length
+            payload
+                .get(5)
+                .and_then(|len| payload.get(6..6 + *len as usize))
+        } else {
+            // short header packets don't include a length so use the expected length
+            payload.get(1..1 + peer_connection_id.len())
+        };
+
+        quoted_connection_id == Some(peer_connection_id)
+    }
+
+    /// Called when the network reported that a datagram sent on this path was too big
+    #[inline]
+    pub fn on_packet_too_big<Pub: event::ConnectionPublisher>(
+        &mut self,
+        mtu: u16,
+        timestamp: Timestamp,
+        path_id: Id,
+        publisher: &mut Pub,
+    ) -> mtu::MtuResult {
+        let peer_socket_address = self.handle.remote_address();
+        self.mtu_controller.on_packet_too_big(
+            mtu,
+            &peer_socket_address,
+            timestamp,
+            &mut self.congestion_controller,
+            path_id,
+            publisher,
+        )
+    }
+
     /// Returns true if this path is able to transmit packets at the given timestamp
     #[inline]
     pub fn can_transmit(&self, timestamp: Timestamp) -> bool {
@@ -314,6 +421,27 @@ impl<Config: endpoint::Config> Path<Config> {
                 .is_none_or(|edt| edt.has_elapsed(timestamp))
     }
 
+    /// Returns the time at which the pacing timer should be armed, if the pacer doesn't allow
+    /// any more packets to be written ahead of `timestamp`
+    ///
+    /// Queues that offload pacing accept packets up to `pacing_horizon` ahead of their departure
+    /// time, so the timer is armed that much earlier to refill the queue before it runs out.
+    #[inline]
+    pub fn pacing_timer_target(
+        &self,
+        timestamp: Timestamp,
+        pacing_horizon: core::time::Duration,
+    ) -> Option<Timestamp> {
+        let edt = self.congestion_controller.earliest_departure_time()?;
+
+        if edt.has_elapsed(timestamp + pacing_horizon) {
+            return None;
+        }
+
+        // This can't underflow since the EDT is later than the horizon
+        Some(edt - pacing_horizon)
+    }
+
     /// Only PATH_CHALLENGE and PATH_RESPONSE frames should be transmitted here.
     #[inline]
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
@@ -592,6 +720,26 @@ impl<Config: endpoint::Config> Path<Config> {
     #[inline]
     pub fn reset_pto_backoff(&mut self) {
         self.pto_backoff = INITIAL_PTO_BACKOFF;
+        // the peer acknowledged a packet so any previous unreachable errors are stale
+        self.unreachable_pto_backoff = None;
+    }
+
+    /// Called when the network reported that the peer is unreachable on this path
+    ///
+    /// ICMP errors are not authenticated and may be transient, so the path is only considered
+    /// unreachable once an error is also reported after a probe timeout has expired without an
+    /// acknowledgement from the peer.
+    ///
+    /// Returns `true` if the path should be considered unreachable.
+    #[inline]
+    pub fn on_unreachable(&mut self) -> bool {
+        match self.unreachable_pto_backoff {
+            Some(pto_backoff) => self.pto_backoff > pto_backoff,
+            None => {
+                self.unreachable_pto_backoff = Some(self.pto_backoff);
+                false
+            }
+        }
     }
 
     /// Returns `true` if the congestion window does not have sufficient space for a packet of the maximum
@@ -716,6 +864,42 @@ mod tests {
 
     type Path = super::Path<Config>;
 
+    #[test]
+    fn pacing_horizon_test() {
+        let mut path = helper_path_client();
+        let now = NoopClock.get_time();
+        let horizon = Duration::from_millis(2);
+
+        // nothing is paced without an earliest departure time
+        assert!(path.can_transmit(now));
+        assert_eq!(path.pacing_timer_target(now, Duration::ZERO), None);
+        assert_eq!(path.pacing_timer_target(now, horizon), None);
+
+        // packets due within the horizon can be written ahead of time
+        let edt = now + Duration::from_millis(1);
+        path.congestion_controller.earliest_departure_time = Some(edt);
+        assert!(!path.can_transmit(now));
+        assert!(path.can_transmit(now + horizon));
+        assert_eq!(path.pacing_timer_target(now, Duration::ZERO), Some(edt));
+        assert_eq!(path.pacing_timer_target(now, horizon), None);
+
+        // packets due past the horizon wait for the timer, which fires a horizon ahead of the EDT
+        let edt = now + Duration::from_millis(5);
+        path.congestion_controller.earliest_departure_time = Some(edt);
+        assert!(!path.can_transmit(now + horizon));
+        assert_eq!(path.pacing_timer_target(now, Duration::ZERO), Some(edt));
+        assert_eq!(
+            path.pacing_timer_target(now, horizon),
+            Some(now + Duration::from_millis(3))
+        );
+
+        // the cutoff is inclusive of the horizon
+        let edt = now + horizon;
+        path.congestion_controller.earliest_departure_time = Some(edt);
+        assert!(path.can_transmit(now + horizon));
+        assert_eq!(path.pacing_timer_target(now, horizon), None);
+    }
+
     #[test]
     fn custom_anti_amplification_multiplier() {
         let bytes_received = 100;
@@ -898,6 +1082,24 @@ mod tests {
         assert!(!path.challenge.is_pending());
     }
 
+    #[test]
+    fn is_quoted_packet() {
+        let mut path = testing::helper_path_server();
+        path.peer_connection_id = connection::PeerId::try_from_bytes(&[1, 2, 3, 4]).unwrap();
+
+        // short header packets
+        assert!(path.is_quoted_packet(&[0x40, 1, 2, 3, 4, 0xff]));
+        assert!(!path.is_quoted_packet(&[0x40, 1, 2, 3, 5, 0xff]));
+        assert!(!path.is_quoted_packet(&[0x40, 1, 2]));
+
+        // long header packets
+        assert!(path.is_quoted_packet(&[0xc0, 0, 0, 0, 1, 4, 1, 2, 3, 4, 0]));
+        assert!(!path.is_quoted_packet(&[0xc0, 0, 0, 0, 1, 3, 1, 2, 3, 4, 0]));
+        assert!(!path.is_quoted_packet(&[0xc0, 0, 0, 0, 1, 4, 1, 2]));
+
+        assert!(!path.is_quoted_packet(&[]));
+    }
+
     #[test]
     fn on_path_challenge_should_set_response_data() {
         // Setup:
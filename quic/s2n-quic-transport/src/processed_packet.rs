@@ -18,6 +18,8 @@ pub struct ProcessedPacket<'a> {
     pub(crate) path_validation_probing: path_validation::Probe,
     pub(crate) bytes_progressed: usize,
     pub(crate) contains_crypto: bool,
+    pub(crate) is_zero_rtt: bool,
+    pub(crate) immediate_ack: bool,
 }
 
 impl<'a> ProcessedPacket<'a> {
@@ -32,6 +34,8 @@ impl<'a> ProcessedPacket<'a> {
             path_validation_probing: path_validation::Probe::default(),
             bytes_progressed: 0,
             contains_crypto: false,
+            is_zero_rtt: false,
+            immediate_ack: false,
         }
     }
 
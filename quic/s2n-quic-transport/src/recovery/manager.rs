@@ -154,6 +154,47 @@ impl<Config: endpoint::Config> Manager<Config> {
         *self = Self::new(self.space);
     }
 
+    /// Invoked when the Client learns that the server discarded its 0-RTT packets
+    ///
+    /// The packets no longer count toward bytes in flight and are reported as lost so the
+    /// data they contained is retransmitted.
+    pub fn on_zero_rtt_discarded<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(
+            Config::ENDPOINT_TYPE.is_client(),
+            "only a Client sends 0-RTT packets"
+        );
+        debug_assert_eq!(self.space, PacketNumberSpace::ApplicationData);
+
+        if self.sent_packets.is_empty() {
+            return;
+        }
+
+        let path_id = context.path_id();
+        let discarded_packets = self.sent_packets.get_range();
+        let mut discarded_bytes = 0;
+        for (_, unacked_sent_info) in self.sent_packets.remove_range(discarded_packets) {
+            discarded_bytes += unacked_sent_info.sent_bytes as usize;
+        }
+        context
+            .path_mut()
+            .congestion_controller
+            .on_packet_discarded(
+                discarded_bytes,
+                &mut congestion_controller::PathPublisher::new(publisher, path_id),
+            );
+
+        self.sent_packet_ecn_counts = EcnCounts::default();
+        self.time_of_last_ack_eliciting_packet = None;
+        self.loss_timer.cancel();
+        self.pto.cancel();
+
+        context.on_packet_loss(&discarded_packets, publisher);
+    }
+
     pub fn on_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         timestamp: Timestamp,
@@ -265,6 +306,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         path.ecn_controller
             .on_packet_sent(ecn, path_event!(path, path_id), publisher);
         self.sent_packet_ecn_counts.increment(ecn);
+        path.counters.packets_sent += 1;
 
         if outcome.ack_elicitation.is_ack_eliciting() {
             self.time_of_last_ack_eliciting_packet = Some(time_sent);
@@ -1013,6 +1055,14 @@ impl<Config: endpoint::Config> Manager<Config> {
                 is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
             });
 
+            path.counters.packets_lost += 1;
+            if sent_info.ack_elicitation.is_ack_eliciting()
+                && !sent_info.transmission_mode.is_mtu_probing()
+            {
+                // The frames in the packet will be retransmitted; MTU probes are not
+                path.counters.packets_retransmitted += 1;
+            }
+
             let path_id = sent_info.path_id;
 
             // Notify the ECN controller of packet loss for blackhole detection.
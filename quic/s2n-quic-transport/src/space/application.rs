@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencyManager, AckManager},
     connection::{self, ConnectionTransmissionContext, ProcessingError},
     dc, endpoint, path,
     path::{path_event, Path},
@@ -11,7 +11,7 @@ use crate::{
     recovery::CongestionController,
     space::{
         datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace,
-        TxPacketNumbers,
+        TxPacketNumbers, ZeroRttCrypto,
     },
     stream::Manager as _,
     sync::flag,
@@ -27,15 +27,17 @@ use s2n_quic_core::{
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone,
+        ImmediateAck, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge,
+        PathResponse, ResetStream, RetireConnectionId, StopSending, StreamDataBlocked,
+        StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
         encoding::{PacketEncoder, PacketEncodingError},
         number::{PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow},
         short::{CleartextShort, ProtectedShort, Short, SpinBit},
+        zero_rtt::{CleartextZeroRtt, ProtectedZeroRtt, ZeroRtt},
     },
     random::Generator,
     recovery::MAX_BURST_PACKETS,
@@ -51,23 +53,16 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     pub tx_packet_numbers: TxPacketNumbers,
     /// Ack manager
     pub ack_manager: AckManager,
+    /// Requests the peer adjusts how often it sends ACK frames
+    pub ack_frequency_manager: AckFrequencyManager,
     /// All streams that are managed through this connection
     pub stream_manager: Config::StreamManager,
     /// The current state of the Spin bit
     /// TODO: Spin me
     pub spin_bit: SpinBit,
     pub crypto_stream: CryptoStream,
-    //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
-    //# For this reason, endpoints MUST be able to retain two sets of packet
-    //# protection keys for receiving packets: the current and the next.
-
-    //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
-    //# An endpoint MUST NOT initiate a key update prior to having confirmed
-    //# the handshake (Section 4.1.2).
     /// The crypto suite for application data
-    /// TODO: What about ZeroRtt?
-    key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
-    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    keys: Keys<Config>,
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
@@ -82,6 +77,29 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     pub buffer_crypto_frames: bool,
 }
 
+/// The packet protection keys for the application data space
+enum Keys<Config: endpoint::Config> {
+    /// Used by a client to send early data until the handshake provides 1-RTT keys
+    ZeroRtt {
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+        header_key:
+            <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+    },
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
+    //# For this reason, endpoints MUST be able to retain two sets of packet
+    //# protection keys for receiving packets: the current and the next.
+
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+    //# An endpoint MUST NOT initiate a key update prior to having confirmed
+    //# the handshake (Section 4.1.2).
+    OneRtt {
+        key_set:
+            KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
+        header_key:
+            <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    },
+}
+
 impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ApplicationSpace")
@@ -102,20 +120,75 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         now: Timestamp,
         stream_manager: Config::StreamManager,
         ack_manager: AckManager,
+        ack_frequency_manager: AckFrequencyManager,
         keep_alive: KeepAlive,
         datagram_manager: datagram::Manager<Config>,
         dc_manager: dc::Manager<Config>,
     ) -> Self {
-        let key_set = KeySet::new(key, Self::key_limits());
+        let keys = Keys::OneRtt {
+            key_set: KeySet::new(key, Self::key_limits()),
+            header_key,
+        };
+
+        Self::with_keys(
+            keys,
+            now,
+            stream_manager,
+            ack_manager,
+            ack_frequency_manager,
+            keep_alive,
+            datagram_manager,
+            dc_manager,
+        )
+    }
+
+    /// Creates an application space for a client to send early data in 0-RTT packets
+    ///
+    /// The 1-RTT keys are installed with [`Self::on_one_rtt_keys`] once the handshake
+    /// provides them.
+    pub fn new_zero_rtt(
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+        now: Timestamp,
+        stream_manager: Config::StreamManager,
+        ack_manager: AckManager,
+        keep_alive: KeepAlive,
+        datagram_manager: datagram::Manager<Config>,
+    ) -> Self {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        Self::with_keys(
+            Keys::ZeroRtt { key, header_key },
+            now,
+            stream_manager,
+            ack_manager,
+            // The peer's support for ACK_FREQUENCY frames isn't known until the handshake
+            // completes
+            AckFrequencyManager::default(),
+            keep_alive,
+            datagram_manager,
+            dc::Manager::disabled(),
+        )
+    }
 
+    fn with_keys(
+        keys: Keys<Config>,
+        now: Timestamp,
+        stream_manager: Config::StreamManager,
+        ack_manager: AckManager,
+        ack_frequency_manager: AckFrequencyManager,
+        keep_alive: KeepAlive,
+        datagram_manager: datagram::Manager<Config>,
+        dc_manager: dc::Manager<Config>,
+    ) -> Self {
         Self {
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
             ack_manager,
+            ack_frequency_manager,
             spin_bit: SpinBit::Zero,
             stream_manager,
             crypto_stream: CryptoStream::new(),
-            key_set,
-            header_key,
+            keys,
             ping: flag::Ping::default(),
             keep_alive,
             processed_packet_numbers: SlidingWindow::default(),
@@ -127,6 +200,45 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         }
     }
 
+    /// Returns `true` if the client is sending 0-RTT packets
+    #[inline]
+    pub fn is_zero_rtt(&self) -> bool {
+        matches!(self.keys, Keys::ZeroRtt { .. })
+    }
+
+    /// Replaces the 0-RTT keys with the 1-RTT keys from the handshake
+    pub fn on_one_rtt_keys(
+        &mut self,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    ) {
+        debug_assert!(self.is_zero_rtt());
+        self.keys = Keys::OneRtt {
+            key_set: KeySet::new(key, Self::key_limits()),
+            header_key,
+        };
+    }
+
+    /// Called by the client when the server discarded the 0-RTT packets
+    ///
+    /// This happens when the server rejects the early data or responds with a Retry packet.
+    /// The packets are declared lost so their stream data is retransmitted.
+    pub fn on_zero_rtt_discarded<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_manager: &mut path::Manager<Config>,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        publisher: &mut Pub,
+    ) {
+        let (recovery_manager, mut context) = self.recovery(
+            handshake_status,
+            local_id_registry,
+            path_manager.active_path_id(),
+            path_manager,
+        );
+        recovery_manager.on_zero_rtt_discarded(&mut context, publisher);
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -203,20 +315,34 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let mut outcome = transmission::Outcome::default();
 
         let destination_connection_id = context.path().peer_connection_id;
+        let source_connection_id = context.path().local_connection_id;
         let transmission_mode = context.transmission_mode;
         let min_packet_len = context.min_packet_len;
         let bytes_progressed = self.stream_manager.outgoing_bytes_progressed();
 
-        let payload = transmission::Transmission {
-            config: PhantomData::<Config>,
-            outcome: &mut outcome,
-            packet_number,
-            payload: transmission::application::Payload::<Config>::new(
+        let payload = if self.is_zero_rtt() {
+            // Only early data is sent before the handshake completes
+            if !matches!(
+                transmission_mode,
+                transmission::Mode::Normal | transmission::Mode::LossRecoveryProbing
+            ) {
+                return Err(PacketEncodingError::EmptyPayload(buffer));
+            }
+
+            transmission::application::Payload::<Config>::zero_rtt(
+                &mut self.ping,
+                &mut self.stream_manager,
+                &mut self.recovery_manager,
+                &mut self.datagram_manager,
+            )
+        } else {
+            transmission::application::Payload::<Config>::new(
                 context.path_id,
                 context.path_manager,
                 context.local_id_registry,
                 context.transmission_mode,
                 &mut self.ack_manager,
+                &mut self.ack_frequency_manager,
                 handshake_status,
                 &mut self.ping,
                 &mut self.stream_manager,
@@ -224,7 +350,14 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 &mut self.crypto_stream,
                 &mut self.datagram_manager,
                 &mut self.dc_manager,
-            ),
+            )
+        };
+
+        let payload = transmission::Transmission {
+            config: PhantomData::<Config>,
+            outcome: &mut outcome,
+            packet_number,
+            payload,
             timestamp: context.timestamp,
             transmission_constraint,
             transmission_mode,
@@ -234,26 +367,49 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             packet_interceptor: context.packet_interceptor,
         };
 
-        let spin_bit = self.spin_bit;
-        let header_key = &self.header_key;
-        let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+        let buffer = match &mut self.keys {
+            Keys::ZeroRtt { key, header_key } => {
+                let packet = ZeroRtt {
+                    version: context.quic_version,
+                    destination_connection_id,
+                    source_connection_id,
+                    packet_number,
+                    payload,
+                };
+                let (_protected_packet, buffer) = packet.encode_packet(
+                    key,
+                    header_key,
+                    packet_number_encoder,
+                    min_packet_len,
+                    buffer,
+                )?;
+                buffer
+            }
+            Keys::OneRtt {
+                key_set,
+                header_key,
+            } => {
+                let spin_bit = self.spin_bit;
+                let (_protected_packet, buffer) =
+                    key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
+                        let packet = Short {
+                            spin_bit,
+                            key_phase,
+                            destination_connection_id,
+                            packet_number,
+                            payload,
+                        };
+                        packet.encode_packet(
+                            key,
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )
+                    })?;
+                buffer
+            }
+        };
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
@@ -306,19 +462,31 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             *skip_counter -= 1_u32;
         }
 
+        let packet_header = if self.is_zero_rtt() {
+            event::builder::PacketHeader::ZeroRtt {
+                number: packet_number.as_u64(),
+                version: context.publisher.quic_version(),
+            }
+        } else {
+            event::builder::PacketHeader::new(packet_number, context.publisher.quic_version())
+        };
         context
             .publisher
             .on_packet_sent(event::builder::PacketSent {
-                packet_header: event::builder::PacketHeader::new(
-                    packet_number,
-                    context.publisher.quic_version(),
-                ),
+                packet_header,
                 packet_len: outcome.bytes_sent,
             });
 
+        let space = if self.is_zero_rtt() {
+            event::builder::KeySpace::ZeroRtt
+        } else {
+            event::builder::KeySpace::OneRtt
+        };
+
         if let Some(skip_packet_number) = skipped_packet_number.pto {
             Self::packet_skipped_event(
                 context,
+                space.clone(),
                 skip_packet_number,
                 event::builder::PacketSkipReason::PtoProbe,
             );
@@ -329,6 +497,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 .set_skip_packet_number(skip_packet_number);
             Self::packet_skipped_event(
                 context,
+                space,
                 skip_packet_number,
                 event::builder::PacketSkipReason::OptimisticAckMitigation,
             );
@@ -337,6 +506,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
     fn packet_skipped_event(
         context: &mut ConnectionTransmissionContext<Config>,
+        space: event::builder::KeySpace,
         skip_packet_number: PacketNumber,
         reason: event::builder::PacketSkipReason,
     ) {
@@ -344,7 +514,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             .publisher
             .on_packet_skipped(event::builder::PacketSkipped {
                 number: skip_packet_number.into_event(),
-                space: event::builder::KeySpace::OneRtt,
+                space,
                 reason,
             });
     }
@@ -370,9 +540,18 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         connection_close: &ConnectionClose,
         buffer: EncoderBuffer<'a>,
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        // The connection is closed in Initial or Handshake packets until 1-RTT keys are available
+        let Keys::OneRtt {
+            key_set,
+            header_key,
+        } = &mut self.keys
+        else {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        };
+
         let packet_number = self.tx_packet_numbers.next();
 
-        let packet_number_encoder = self.packet_number_encoder();
+        let packet_number_encoder = self.tx_packet_numbers.largest_sent_packet_number_acked();
 
         let mut outcome = transmission::Outcome::default();
         let destination_connection_id = context.path().peer_connection_id;
@@ -393,25 +572,23 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         let spin_bit = self.spin_bit;
         let min_packet_len = context.min_packet_len;
-        let header_key = &self.header_key;
         let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+            key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
+                let packet = Short {
+                    spin_bit,
+                    key_phase,
+                    destination_connection_id,
+                    packet_number,
+                    payload,
+                };
+                packet.encode_packet(
+                    key,
+                    header_key,
+                    packet_number_encoder,
+                    min_packet_len,
+                    buffer,
+                )
+            })?;
 
         context
             .publisher
@@ -466,7 +643,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_timeout(timestamp);
-        self.key_set.on_timeout(timestamp);
+        if let Keys::OneRtt { key_set, .. } = &mut self.keys {
+            key_set.on_timeout(timestamp);
+        }
 
         let (recovery_manager, mut context) = self.recovery(
             handshake_status,
@@ -566,6 +745,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             &mut self.recovery_manager,
             RecoveryContext {
                 ack_manager: &mut self.ack_manager,
+                ack_frequency_manager: &mut self.ack_frequency_manager,
                 crypto_stream: &mut self.crypto_stream,
                 handshake_status,
                 ping: &mut self.ping,
@@ -596,9 +776,19 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextShort<'a>, ProcessingError> {
+        // 1-RTT packets are only processed once the handshake is complete
+        let Keys::OneRtt {
+            key_set,
+            header_key,
+        } = &mut self.keys
+        else {
+            debug_assert!(false, "1-RTT keys should be available");
+            return Err(ProcessingError::Other);
+        };
+
         let largest_acked = self.ack_manager.largest_received_packet_number_acked();
         let packet = protected
-            .unprotect(&self.header_key, largest_acked)
+            .unprotect(header_key, largest_acked)
             .inspect_err(|_err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -611,7 +801,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let packet_number = packet.packet_number;
         let packet_header =
             event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = self.key_set.decrypt_packet(
+        let decrypted = key_set.decrypt_packet(
             packet,
             largest_acked,
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
@@ -634,7 +824,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             Ok((_, Some(generation))) => {
                 publisher.on_key_update(event::builder::KeyUpdate {
                     key_type: event::builder::KeyType::OneRtt { generation },
-                    cipher_suite: self.key_set.cipher_suite().into_event(),
+                    cipher_suite: key_set.cipher_suite().into_event(),
                 });
             }
             Ok(_) => {}
@@ -667,6 +857,54 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         decrypted.map(|x| x.0)
     }
 
+    /// Validate 0-RTT packets, which are processed in the Application packet space
+    pub fn validate_and_decrypt_zero_rtt_packet<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        protected: ProtectedZeroRtt<'a>,
+        (key, header_key): &ZeroRttCrypto<Config>,
+        datagram: &DatagramInfo,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<CleartextZeroRtt<'a>, ProcessingError> {
+        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+        let packet = protected
+            .unprotect(header_key, largest_acked)
+            .inspect_err(|_err| {
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::UnprotectFailed {
+                        space: event::builder::KeySpace::ZeroRtt,
+                        path: path_event!(path, path_id),
+                    },
+                });
+            })?;
+
+        let packet_number = packet.packet_number;
+        let packet_header =
+            event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
+        let decrypted = packet.decrypt(key).inspect_err(|_err| {
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::DecryptionFailed {
+                    packet_header,
+                    path: path_event!(path, path_id),
+                },
+            });
+        });
+
+        // We perform decryption prior to checking for duplicate to avoid short-circuiting
+        // and maintain constant-time operation.
+        if self.is_duplicate(packet_number, path_id, path, publisher) {
+            return Err(ProcessingError::Other);
+        }
+
+        if decrypted.is_ok() {
+            // reset the keep alive timer after receiving a packet
+            self.keep_alive.reset(datagram.timestamp);
+        }
+
+        Ok(decrypted?)
+    }
+
     fn key_limits() -> limited::Limits {
         limited::Limits::default()
     }
@@ -677,7 +915,9 @@ impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.ack_manager.timers(query)?;
         self.recovery_manager.timers(query)?;
-        self.key_set.timers(query)?;
+        if let Keys::OneRtt { key_set, .. } = &self.keys {
+            key_set.timers(query)?;
+        }
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
 
@@ -692,6 +932,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        self.ack_frequency_manager.transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
         self.crypto_stream.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
@@ -710,6 +951,7 @@ impl<Config: endpoint::Config> connection::finalization::Provider for Applicatio
 
 struct RecoveryContext<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    ack_frequency_manager: &'a mut AckFrequencyManager,
     handshake_status: &'a mut HandshakeStatus,
     crypto_stream: &'a mut CryptoStream,
     ping: &'a mut flag::Ping,
@@ -779,6 +1021,8 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
         self.dc_manager
             .on_packet_ack(packet_number_range, publisher);
         self.crypto_stream.on_packet_ack(packet_number_range);
+        self.ack_frequency_manager
+            .on_packet_ack(packet_number_range);
         self.ping.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
@@ -796,6 +1040,8 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_packet_loss(packet_number_range);
+        self.ack_frequency_manager
+            .on_packet_loss(packet_number_range);
         self.crypto_stream.on_packet_loss(packet_number_range);
         self.handshake_status
             .on_packet_loss(packet_number_range, publisher);
@@ -919,7 +1165,11 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
     ) -> Result<(), transport::Error> {
         let bytes_progressed = self.stream_manager.incoming_bytes_progressed();
 
-        self.stream_manager.on_data(&frame)?;
+        if packet.is_zero_rtt {
+            self.stream_manager.on_early_data(&frame)?;
+        } else {
+            self.stream_manager.on_data(&frame)?;
+        }
 
         packet.bytes_progressed +=
             (self.stream_manager.incoming_bytes_progressed() - bytes_progressed).as_u64() as usize;
@@ -1145,6 +1395,22 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        self.ack_manager.on_ack_frequency_frame(&frame)
+    }
+
+    fn handle_immediate_ack_frame(&mut self, frame: ImmediateAck) -> Result<(), transport::Error> {
+        // The peer may only send IMMEDIATE_ACK frames if the `min_ack_delay` transport
+        // parameter was sent
+        if self.ack_manager.ack_settings.min_ack_delay.is_none() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("IMMEDIATE_ACK frames are not supported")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        Ok(())
+    }
+
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
         processed_packet: ProcessedPacket,
@@ -41,6 +41,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         }
     }
 
+    /// Called when the server rejected early data and the limit from its transport parameters
+    /// replaces the remembered one
+    pub fn on_early_data_rejected(&mut self, max_datagram_payload: u64) {
+        self.max_datagram_payload = max_datagram_payload;
+    }
+
     /// A callback that allows users to write datagrams directly to the packet.
     pub fn on_transmit<W: WriteContext>(
         &mut self,
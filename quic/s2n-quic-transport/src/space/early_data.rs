@@ -0,0 +1,336 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Application data in 0-RTT packets, as defined in
+//! <https://www.rfc-editor.org/rfc/rfc9001#section-4.6>
+//!
+//! When a client resumes a session, the TLS provider can derive 0-RTT keys along with the
+//! transport parameters the server sent on the previous connection. The client uses the
+//! remembered parameters to send streams and datagrams before the handshake completes. Once the
+//! server's parameters from the handshake are available, they replace the remembered ones.
+//!
+//! The server consults the early data validator when it receives a ClientHello that attempts
+//! to send early data. If the validator rejects it, the TLS provider is asked to reject the
+//! early data and the 0-RTT packets are dropped.
+
+use s2n_quic_core::{early_data, event, transport, transport::parameters::ZeroRttParameters};
+
+#[derive(Debug)]
+pub struct EarlyData {
+    state: State,
+}
+
+#[derive(Debug, PartialEq)]
+enum State {
+    /// The application didn't request to send early data
+    Disabled,
+    /// Early data can be sent once the TLS session provides 0-RTT keys
+    Enabled,
+    /// 0-RTT packets are being sent with the remembered transport parameters
+    Sending {
+        remembered: ZeroRttParameters,
+        accepted: bool,
+    },
+    /// The server processed the 0-RTT packets
+    Accepted,
+    /// The server discarded the 0-RTT packets
+    Rejected,
+    /// The client requested to send early data and the validator allowed the TLS provider to
+    /// accept it
+    Allowed,
+    /// The client requested to send early data and the validator refused it
+    Refused,
+    /// The server is processing 0-RTT packets
+    Receiving,
+}
+
+impl EarlyData {
+    pub fn new(enabled: bool) -> Self {
+        let state = if enabled {
+            State::Enabled
+        } else {
+            State::Disabled
+        };
+        Self { state }
+    }
+
+    /// Returns `true` if the client should start sending 0-RTT packets once keys are available
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.state == State::Enabled
+    }
+
+    /// Returns `true` if the client is sending 0-RTT packets
+    #[inline]
+    pub fn is_sending(&self) -> bool {
+        matches!(self.state, State::Sending { .. })
+    }
+
+    /// Returns `true` if the server discarded the 0-RTT packets
+    #[inline]
+    pub fn is_rejected(&self) -> bool {
+        self.state == State::Rejected
+    }
+
+    /// Returns `true` if the server may accept the early data requested by the client
+    #[inline]
+    pub fn can_accept(&self) -> bool {
+        self.state != State::Refused
+    }
+
+    /// Returns `true` if the server is processing 0-RTT packets
+    #[inline]
+    pub fn is_receiving(&self) -> bool {
+        self.state == State::Receiving
+    }
+
+    /// Called on the server with the validator's decision on a ClientHello that requested to
+    /// send early data
+    pub fn on_request(&mut self, outcome: early_data::Outcome) {
+        debug_assert_eq!(self.state, State::Disabled);
+        self.state = match outcome {
+            early_data::Outcome::Accept => State::Allowed,
+            _ => State::Refused,
+        };
+    }
+
+    /// Called when the server installs the 0-RTT keys
+    pub fn on_server_zero_rtt_keys(&mut self) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+        //# When rejecting 0-RTT, a server MUST NOT
+        //# process any 0-RTT packets, even if it could.
+        if self.state != State::Allowed {
+            return Err(transport::Error::INTERNAL_ERROR
+                .with_reason("0-RTT keys provided for refused early data"));
+        }
+
+        self.state = State::Receiving;
+        Ok(())
+    }
+
+    /// Called when the server installs the 1-RTT keys
+    ///
+    /// Returns the server's decision if the client requested to send early data.
+    pub fn on_server_one_rtt_keys(&mut self) -> Option<event::builder::ZeroRttStatus> {
+        match self.state {
+            State::Receiving => {
+                self.state = State::Accepted;
+                Some(event::builder::ZeroRttStatus::Accepted)
+            }
+            State::Allowed | State::Refused => {
+                self.state = State::Rejected;
+                Some(event::builder::ZeroRttStatus::Rejected)
+            }
+            _ => None,
+        }
+    }
+
+    /// Called when the 0-RTT keys are installed with the remembered transport parameters
+    pub fn on_zero_rtt_keys(&mut self, remembered: ZeroRttParameters) {
+        debug_assert!(self.is_enabled());
+        self.state = State::Sending {
+            remembered,
+            accepted: false,
+        };
+    }
+
+    /// Called with the server's decision on the early data, as reported by the TLS session
+    pub fn on_status(&mut self, status: bool) {
+        if let State::Sending { accepted, .. } = &mut self.state {
+            *accepted = status;
+        }
+    }
+
+    /// Called with the transport parameters the server sent in the handshake
+    ///
+    /// Returns the server's decision if 0-RTT packets were sent.
+    pub fn on_server_params(
+        &mut self,
+        current: &ZeroRttParameters,
+    ) -> Result<Option<event::builder::ZeroRttStatus>, transport::Error> {
+        let State::Sending {
+            remembered,
+            accepted,
+        } = self.state
+        else {
+            return Ok(None);
+        };
+
+        // The server is free to choose any values when rejecting the early data, in which case
+        // the streams that were opened with the remembered limits are limited or reset to
+        // accommodate the new values.
+        if accepted {
+            //= https://www.rfc-editor.org/rfc/rfc9221#section-3
+            //# If a client stores the value of the
+            //# max_datagram_frame_size transport parameter with their 0-RTT state,
+            //# they MUST validate that the new value of the max_datagram_frame_size
+            //# transport parameter sent by the server in the handshake is greater
+            //# than or equal to the stored value; if not, the client MUST terminate
+            //# the connection with error PROTOCOL_VIOLATION.
+            if current.max_datagram_frame_size < remembered.max_datagram_frame_size {
+                return Err(transport::Error::PROTOCOL_VIOLATION
+                    .with_reason("max_datagram_frame_size reduced after 0-RTT"));
+            }
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+            //# In particular, a server that accepts 0-RTT data
+            //# MUST NOT set values for the following parameters (Section 18.2) that
+            //# are smaller than the remembered values of the parameters.
+            if is_reduced(&remembered, current) {
+                return Err(transport::Error::PROTOCOL_VIOLATION
+                    .with_reason("accepted 0-RTT with reduced transport parameters"));
+            }
+
+            self.state = State::Accepted;
+            Ok(Some(event::builder::ZeroRttStatus::Accepted))
+        } else {
+            self.state = State::Rejected;
+            Ok(Some(event::builder::ZeroRttStatus::Rejected))
+        }
+    }
+}
+
+/// Returns `true` if any of the limits in `current` are smaller than the remembered values
+fn is_reduced(remembered: &ZeroRttParameters, current: &ZeroRttParameters) -> bool {
+    current.active_connection_id_limit < remembered.active_connection_id_limit
+        || current.initial_max_data < remembered.initial_max_data
+        || current.initial_max_stream_data_bidi_local
+            < remembered.initial_max_stream_data_bidi_local
+        || current.initial_max_stream_data_bidi_remote
+            < remembered.initial_max_stream_data_bidi_remote
+        || current.initial_max_stream_data_uni < remembered.initial_max_stream_data_uni
+        || current.initial_max_streams_bidi < remembered.initial_max_streams_bidi
+        || current.initial_max_streams_uni < remembered.initial_max_streams_uni
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::varint::VarInt;
+
+    fn parameters(max_data: u32) -> ZeroRttParameters {
+        ZeroRttParameters {
+            active_connection_id_limit: VarInt::from_u8(2),
+            initial_max_data: VarInt::from_u32(max_data),
+            initial_max_streams_bidi: VarInt::from_u8(10),
+            max_datagram_frame_size: VarInt::from_u16(1200),
+            ..Default::default()
+        }
+    }
+
+    fn sending(accepted: bool) -> EarlyData {
+        let mut early_data = EarlyData::new(true);
+        assert!(early_data.is_enabled());
+        early_data.on_zero_rtt_keys(parameters(1000));
+        assert!(early_data.is_sending());
+        early_data.on_status(accepted);
+        early_data
+    }
+
+    #[test]
+    fn disabled_test() {
+        let mut early_data = EarlyData::new(false);
+        assert!(!early_data.is_enabled());
+        assert!(matches!(
+            early_data.on_server_params(&parameters(1000)),
+            Ok(None)
+        ));
+        assert!(!early_data.is_rejected());
+    }
+
+    #[test]
+    fn accepted_test() {
+        let mut early_data = sending(true);
+        assert!(matches!(
+            early_data.on_server_params(&parameters(2000)),
+            Ok(Some(event::builder::ZeroRttStatus::Accepted))
+        ));
+        assert!(!early_data.is_sending());
+        assert!(!early_data.is_rejected());
+    }
+
+    #[test]
+    fn rejected_test() {
+        let mut early_data = sending(false);
+        assert!(matches!(
+            early_data.on_server_params(&parameters(1000)),
+            Ok(Some(event::builder::ZeroRttStatus::Rejected))
+        ));
+        assert!(!early_data.is_sending());
+        assert!(early_data.is_rejected());
+    }
+
+    #[test]
+    fn server_accepted_test() {
+        let mut early_data = EarlyData::new(false);
+        early_data.on_request(early_data::Outcome::Accept);
+        assert!(early_data.can_accept());
+        early_data.on_server_zero_rtt_keys().unwrap();
+        assert!(early_data.is_receiving());
+        assert!(matches!(
+            early_data.on_server_one_rtt_keys(),
+            Some(event::builder::ZeroRttStatus::Accepted)
+        ));
+        assert!(!early_data.is_receiving());
+    }
+
+    #[test]
+    fn server_rejected_test() {
+        // the TLS provider rejected the early data
+        let mut early_data = EarlyData::new(false);
+        early_data.on_request(early_data::Outcome::Accept);
+        assert!(matches!(
+            early_data.on_server_one_rtt_keys(),
+            Some(event::builder::ZeroRttStatus::Rejected)
+        ));
+
+        // the validator rejected the early data
+        let mut early_data = EarlyData::new(false);
+        early_data.on_request(early_data::Outcome::Reject);
+        assert!(!early_data.can_accept());
+        assert_eq!(
+            early_data.on_server_zero_rtt_keys().unwrap_err().code,
+            transport::Error::INTERNAL_ERROR.code
+        );
+        assert!(matches!(
+            early_data.on_server_one_rtt_keys(),
+            Some(event::builder::ZeroRttStatus::Rejected)
+        ));
+
+        // the client didn't request to send early data
+        let mut early_data = EarlyData::new(false);
+        assert!(early_data.can_accept());
+        assert!(early_data.on_server_one_rtt_keys().is_none());
+    }
+
+    #[test]
+    fn reduced_parameters_test() {
+        let mut early_data = sending(true);
+        assert_eq!(
+            early_data
+                .on_server_params(&parameters(999))
+                .unwrap_err()
+                .code,
+            transport::Error::PROTOCOL_VIOLATION.code
+        );
+
+        let mut early_data = sending(true);
+        let mut current = parameters(1000);
+        current.max_datagram_frame_size = VarInt::from_u16(1000);
+        assert_eq!(
+            early_data.on_server_params(&current).unwrap_err().code,
+            transport::Error::PROTOCOL_VIOLATION.code
+        );
+
+        // the server can choose any values when rejecting the early data
+        let mut early_data = sending(false);
+        let mut current = parameters(999);
+        current.initial_max_streams_bidi = VarInt::from_u8(1);
+        current.max_datagram_frame_size = VarInt::from_u16(1000);
+        assert!(matches!(
+            early_data.on_server_params(&current),
+            Ok(Some(event::builder::ZeroRttStatus::Rejected))
+        ));
+        assert!(early_data.is_rejected());
+    }
+}
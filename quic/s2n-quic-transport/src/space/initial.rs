@@ -26,6 +26,7 @@ use s2n_quic_core::{
     },
     time::{timer, Timestamp},
     transport,
+    version::Version,
 };
 use smallvec::SmallVec;
 
@@ -37,6 +38,9 @@ pub struct InitialSpace<Config: endpoint::Config> {
     pub key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
     pub header_key:
         <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+    /// The keys for the client's original version, which are retained by a server after
+    /// switching to a compatible version until the client starts using the negotiated version
+    original_keys: Option<Box<OriginalKeys<Config>>>,
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9
     //# If packets from a lower encryption level contain
     //# CRYPTO frames, frames that retransmit that data MUST be sent at the
@@ -52,6 +56,12 @@ pub struct InitialSpace<Config: endpoint::Config> {
     recovery_manager: recovery::Manager<Config>,
 }
 
+type OriginalKeys<Config> = (
+    Version,
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+);
+
 impl<Config: endpoint::Config> fmt::Debug for InitialSpace<Config> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("InitialSpace")
@@ -74,6 +84,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             ack_manager,
             key,
             header_key,
+            original_keys: None,
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
@@ -103,6 +114,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
                             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                                publisher.quic_version(),
                                 retry_source_connection_id.as_bytes(),
                             );
 
@@ -122,6 +134,30 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             .on_retry_packet(path, path_id, publisher);
     }
 
+    /// Called by the server when switching the connection to a compatible version
+    ///
+    /// The keys for the original version are retained to process any Initial packets the
+    /// client sent before receiving the server's Initial packets.
+    pub fn on_compatible_version(
+        &mut self,
+        original_version: Version,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+        let key = core::mem::replace(&mut self.key, key);
+        let header_key = core::mem::replace(&mut self.header_key, header_key);
+        self.original_keys = Some(Box::new((original_version, key, header_key)));
+    }
+
+    /// Called by the server once an Initial packet in the negotiated version is processed
+    ///
+    /// At this point the client has switched to the negotiated version so the keys for the
+    /// original version are no longer needed.
+    pub fn discard_original_keys(&mut self) {
+        self.original_keys = None;
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -394,9 +430,62 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextInitial<'a>, ProcessingError> {
+        match self.original_keys.as_deref() {
+            Some((version, key, header_key)) if *version == protected.version => {
+                self.decrypt_packet(key, header_key, protected, path_id, path, publisher)
+            }
+            _ => self.decrypt_packet(
+                &self.key,
+                &self.header_key,
+                protected,
+                path_id,
+                path,
+                publisher,
+            ),
+        }
+    }
+
+    /// Validate an Initial packet sent by the server in a compatible version
+    ///
+    /// The client switches its Initial keys to the new version if the packet is successfully
+    /// decrypted.
+    pub fn validate_and_decrypt_compatible_packet<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        protected: ProtectedInitial<'a>,
+        initial_key_cid: &[u8],
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<CleartextInitial<'a>, ProcessingError> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        let (key, header_key) =
+            <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                protected.version,
+                initial_key_cid,
+            );
+
+        let packet = self.decrypt_packet(&key, &header_key, protected, path_id, path, publisher)?;
+
+        self.key = key;
+        self.header_key = header_key;
+
+        Ok(packet)
+    }
+
+    fn decrypt_packet<'a, Pub: event::ConnectionPublisher>(
+        &self,
+        key: &<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+        header_key: &<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        protected: ProtectedInitial<'a>,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<CleartextInitial<'a>, ProcessingError> {
+        let version = protected.version;
         let packet_number_decoder = self.packet_number_decoder();
         let packet = protected
-            .unprotect(&self.header_key, packet_number_decoder)
+            .unprotect(header_key, packet_number_decoder)
             .inspect_err(|_err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -410,9 +499,8 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             return Err(ProcessingError::Other);
         }
 
-        let packet_header =
-            event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = packet.decrypt(&self.key).inspect_err(|_err| {
+        let packet_header = event::builder::PacketHeader::new(packet.packet_number, version);
+        let decrypted = packet.decrypt(key).inspect_err(|_err| {
             publisher.on_packet_dropped(event::builder::PacketDropped {
                 reason: event::builder::PacketDropReason::DecryptionFailed {
                     packet_header,
@@ -7,7 +7,7 @@ use crate::{
     connection, endpoint, path,
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
-    stream::Manager as _,
+    stream::{scheduler, Manager as _},
     transmission,
 };
 use bytes::Bytes;
@@ -25,9 +25,10 @@ use s2n_quic_core::{
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone,
+        ImmediateAck, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge,
+        PathResponse, ResetStream, RetireConnectionId, StopSending, StreamDataBlocked,
+        StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
@@ -39,21 +40,35 @@ use s2n_quic_core::{
 mod application;
 mod crypto_stream;
 pub(crate) mod datagram;
+mod early_data;
 mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
 mod session_context;
 mod tx_packet_numbers;
+mod version_negotiation;
 
 pub(crate) use application::ApplicationSpace;
 pub(crate) use crypto_stream::CryptoStream;
+pub(crate) use early_data::EarlyData;
 pub(crate) use handshake::HandshakeSpace;
 pub(crate) use handshake_status::HandshakeStatus;
 pub(crate) use initial::InitialSpace;
 pub(crate) use session_context::SessionContext;
 pub(crate) use tx_packet_numbers::TxPacketNumbers;
 
+/// The 0-RTT packet protection keys
+pub(crate) type ZeroRttCrypto<Config> = (
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+);
+pub(crate) use version_negotiation::VersionNegotiation;
+
+/// The scheduler used by the stream manager of a connection
+pub(crate) type StreamScheduler<Config> =
+    <<Config as endpoint::Config>::StreamSchedulerEndpoint as scheduler::Endpoint>::Scheduler;
+
 struct SessionInfo<Config: endpoint::Config> {
     session: <Config::TLSEndpoint as tls::Endpoint>::Session,
     initial_cid: InitialId,
@@ -66,9 +81,12 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     handshake: Option<Box<HandshakeSpace<Config>>>,
     pub tls_context: Option<Box<dyn Any + Send>>,
     application: Option<Box<ApplicationSpace<Config>>>,
-    zero_rtt_crypto:
-        Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
+    zero_rtt_crypto: Option<Box<ZeroRttCrypto<Config>>>,
     handshake_status: HandshakeStatus,
+    version_negotiation: VersionNegotiation,
+    early_data: EarlyData,
+    /// The scheduler for the stream manager, which is taken once the application space is created
+    stream_scheduler: Option<StreamScheduler<Config>>,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7
@@ -112,6 +130,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         session: <Config::TLSEndpoint as tls::Endpoint>::Session,
         initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        version_negotiation: VersionNegotiation,
+        early_data: EarlyData,
+        stream_scheduler: StreamScheduler<Config>,
         now: Timestamp,
         publisher: &mut Pub,
     ) -> Self {
@@ -138,6 +159,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             application: None,
             zero_rtt_crypto: None,
             handshake_status: HandshakeStatus::default(),
+            version_negotiation,
+            early_data,
+            stream_scheduler: Some(stream_scheduler),
             server_name: None,
             application_protocol: Bytes::new(),
         }
@@ -149,11 +173,25 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     packet_space_api!(ApplicationSpace<Config>, application, application_mut);
 
-    #[allow(dead_code)] // 0RTT hasn't been started yet
-    pub fn zero_rtt_crypto(
-        &self,
-    ) -> Option<&<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey> {
-        self.zero_rtt_crypto.as_ref().map(Box::as_ref)
+    pub fn version_negotiation(&self) -> &VersionNegotiation {
+        &self.version_negotiation
+    }
+
+    pub fn version_negotiation_mut(&mut self) -> &mut VersionNegotiation {
+        &mut self.version_negotiation
+    }
+
+    /// Returns the application space and the 0-RTT keys, if both are available
+    pub fn zero_rtt_mut(
+        &mut self,
+    ) -> Option<(
+        &mut ApplicationSpace<Config>,
+        &ZeroRttCrypto<Config>,
+        &mut HandshakeStatus,
+    )> {
+        let application = self.application.as_deref_mut()?;
+        let zero_rtt_crypto = self.zero_rtt_crypto.as_deref()?;
+        Some((application, zero_rtt_crypto, &mut self.handshake_status))
     }
 
     /// Discard the initial packet space
@@ -258,6 +296,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
         limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
         random_generator: &mut Config::RandomGenerator,
     ) -> Poll<Result<(), transport::Error>> {
         if let Some(session_info) = self.session_info.as_mut() {
@@ -272,6 +311,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 tls_context: &mut self.tls_context,
                 path_manager,
                 handshake_status: &mut self.handshake_status,
+                version_negotiation: &mut self.version_negotiation,
+                early_data: &mut self.early_data,
+                stream_scheduler: &mut self.stream_scheduler,
                 local_id_registry,
                 limits,
                 server_name: &mut self.server_name,
@@ -281,6 +323,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 datagram,
                 dc,
                 limits_endpoint,
+                early_data_validator,
                 random_generator,
             };
 
@@ -310,6 +353,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
         limits_endpoint: &mut Config::ConnectionLimits,
+        early_data_validator: &mut Config::EarlyDataValidator,
         random_generator: &mut Config::RandomGenerator,
     ) -> Result<(), transport::Error> {
         if let Some(session_info) = self.session_info.as_mut() {
@@ -324,6 +368,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 zero_rtt_crypto: &mut self.zero_rtt_crypto,
                 path_manager,
                 handshake_status: &mut self.handshake_status,
+                version_negotiation: &mut self.version_negotiation,
+                early_data: &mut self.early_data,
+                stream_scheduler: &mut self.stream_scheduler,
                 local_id_registry,
                 limits,
                 server_name: &mut self.server_name,
@@ -333,6 +380,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 datagram,
                 dc,
                 limits_endpoint,
+                early_data_validator,
                 random_generator,
             };
 
@@ -620,6 +668,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     pub fn on_retry_packet(&mut self, retry_source_connection_id: PeerId) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.version_negotiation
+            .on_retry_packet(retry_source_connection_id.as_bytes());
         self.retry_cid = Some(Box::new(retry_source_connection_id));
     }
 
@@ -845,6 +895,8 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
     default_frame_handler!(handle_new_token_frame, NewToken);
+    default_frame_handler!(handle_ack_frequency_frame, AckFrequency);
+    default_frame_handler!(handle_immediate_ack_frame, ImmediateAck);
 
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -859,6 +911,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
         packet_number: PacketNumber,
         payload: DecoderBufferMut<'a>,
         datagram: &'a DatagramInfo,
+        is_zero_rtt: bool,
         path_id: path::Id,
         path_manager: &mut path::Manager<Config>,
         handshake_status: &mut HandshakeStatus,
@@ -884,6 +937,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
         };
 
         let mut processed_packet = ProcessedPacket::new(packet_number, datagram);
+        processed_packet.is_zero_rtt = is_zero_rtt;
 
         macro_rules! on_frame_processed {
             ($frame:ident) => {{
@@ -930,6 +984,29 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                 frame: frame.into_event(),
             });
 
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-12.5
+            //# A server MAY treat receipt
+            //# of these frames in 0-RTT packets as a connection error of type
+            //# PROTOCOL_VIOLATION.
+            if is_zero_rtt {
+                let tag = match &frame {
+                    Frame::Ack(frame) => Some(frame.tag()),
+                    Frame::Crypto(frame) => Some(frame.tag()),
+                    Frame::HandshakeDone(frame) => Some(frame.tag()),
+                    Frame::NewToken(frame) => Some(frame.tag()),
+                    Frame::PathResponse(frame) => Some(frame.tag()),
+                    Frame::RetireConnectionId(frame) => Some(frame.tag()),
+                    _ => None,
+                };
+
+                if let Some(tag) = tag {
+                    return Err(transport::Error::PROTOCOL_VIOLATION
+                        .with_reason("frame is not allowed in 0-RTT packets")
+                        .with_frame_type(tag.into())
+                        .into());
+                }
+            }
+
             match frame {
                 Frame::Padding(frame) => {
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-19.1
@@ -1102,6 +1179,15 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     self.handle_dc_stateless_reset_tokens_frame(frame, publisher)
                         .map_err(on_error)?;
                 }
+                Frame::AckFrequency(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_ack_frequency_frame(frame).map_err(on_error)?;
+                }
+                Frame::ImmediateAck(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_immediate_ack_frame(frame).map_err(on_error)?;
+                    processed_packet.immediate_ack = true;
+                }
             }
 
             payload = remaining;
@@ -2,18 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencyManager, AckManager},
     connection::{self, limits::Limits},
     endpoint, path,
     space::{
-        datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        datagram, keep_alive::KeepAlive, ApplicationSpace, EarlyData, HandshakeSpace,
+        HandshakeStatus, InitialSpace, StreamScheduler, VersionNegotiation, ZeroRttCrypto,
     },
-    stream,
+    stream::{self, Manager as _},
 };
 use bytes::Bytes;
 use core::{any::Any, ops::Not, task::Waker};
-use s2n_codec::{DecoderBuffer, DecoderValue};
+use s2n_codec::{DecoderBuffer, DecoderValue, EncoderValue};
 use s2n_quic_core::{
     ack,
     application::ServerName,
@@ -24,11 +24,12 @@ use s2n_quic_core::{
     crypto::{
         self,
         tls::{self, ApplicationParameters, NamedGroup},
-        CryptoSuite, Key,
+        CryptoSuite, InitialKey as _, Key,
     },
     ct::ConstantTimeEq,
     datagram::{ConnectionInfo, Endpoint},
     dc::{self, Endpoint as _},
+    early_data::{self, Validator as _},
     event::{
         self,
         builder::{DcPathCreated, DcState, DcStateChanged},
@@ -41,12 +42,22 @@ use s2n_quic_core::{
         parameters::{
             ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
             DcSupportedVersions, InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay,
-            ServerTransportParameters, TransportParameter as _,
+            MinAckDelay, ServerTransportParameters, TransportParameter as _,
         },
         Error,
     },
 };
 
+/// The peer transport parameters needed to initialize the application space
+type PeerParameters = (
+    InitialFlowControlLimits,
+    ActiveConnectionIdLimit,
+    DatagramLimits,
+    MaxAckDelay,
+    Option<MinAckDelay>,
+    Option<dc::Version>,
+);
+
 pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher> {
     pub now: Timestamp,
     pub initial_cid: &'a InitialId,
@@ -55,10 +66,11 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub initial: &'a mut Option<Box<InitialSpace<Config>>>,
     pub handshake: &'a mut Option<Box<HandshakeSpace<Config>>>,
     pub application: &'a mut Option<Box<ApplicationSpace<Config>>>,
-    pub zero_rtt_crypto: &'a mut Option<
-        Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>,
-    >,
+    pub zero_rtt_crypto: &'a mut Option<Box<ZeroRttCrypto<Config>>>,
     pub handshake_status: &'a mut HandshakeStatus,
+    pub version_negotiation: &'a mut VersionNegotiation,
+    pub early_data: &'a mut EarlyData,
+    pub stream_scheduler: &'a mut Option<StreamScheduler<Config>>,
     pub local_id_registry: &'a mut connection::LocalIdRegistry,
     pub limits: &'a mut Limits,
     pub server_name: &'a mut Option<ServerName>,
@@ -68,25 +80,25 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub datagram: &'a mut Config::DatagramEndpoint,
     pub dc: &'a mut Config::DcEndpoint,
     pub limits_endpoint: &'a mut Config::ConnectionLimits,
+    pub early_data_validator: &'a mut Config::EarlyDataValidator,
     pub tls_context: &'a mut Option<Box<dyn Any + Send>>,
     pub random_generator: &'a mut Config::RandomGenerator,
 }
 
 impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'_, Config, Pub> {
+    /// Takes the scheduler for the stream manager, which is only created once per connection
+    fn take_stream_scheduler(&mut self) -> Result<StreamScheduler<Config>, transport::Error> {
+        self.stream_scheduler.take().ok_or_else(|| {
+            transport::Error::INTERNAL_ERROR
+                .with_reason("stream manager initialized more than once")
+        })
+    }
+
     // This is called by the client
     fn on_server_params(
         &mut self,
         decoder: DecoderBuffer,
-    ) -> Result<
-        (
-            InitialFlowControlLimits,
-            ActiveConnectionIdLimit,
-            DatagramLimits,
-            MaxAckDelay,
-            Option<dc::Version>,
-        ),
-        transport::Error,
-    > {
+    ) -> Result<PeerParameters, transport::Error> {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
 
         let (peer_parameters, remaining) =
@@ -150,6 +162,17 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             (None, None) => {}
         }
 
+        self.version_negotiation
+            .on_server_information(&peer_parameters.version_information)?;
+
+        if let Some(status) = self
+            .early_data
+            .on_server_params(&peer_parameters.zero_rtt_parameters())?
+        {
+            self.publisher
+                .on_zero_rtt_status_updated(event::builder::ZeroRttStatusUpdated { status });
+        }
+
         if let Some(peer_value) = peer_parameters.original_destination_connection_id {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
             //# The values provided by a peer for these transport parameters MUST
@@ -190,6 +213,13 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
                 .register_initial_stateless_reset_token(stateless_reset_token);
         }
 
+        self.path_manager
+            .on_peer_migration_support(peer_parameters.migration_support);
+
+        if let Some(preferred_address) = &peer_parameters.preferred_address {
+            self.path_manager.on_preferred_address(preferred_address)?;
+        }
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -214,6 +244,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            peer_parameters.min_ack_delay,
             dc_version,
         ))
     }
@@ -222,16 +253,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
     fn on_client_params(
         &mut self,
         decoder: DecoderBuffer,
-    ) -> Result<
-        (
-            InitialFlowControlLimits,
-            ActiveConnectionIdLimit,
-            DatagramLimits,
-            MaxAckDelay,
-            Option<dc::Version>,
-        ),
-        transport::Error,
-    > {
+    ) -> Result<PeerParameters, transport::Error> {
         debug_assert!(Config::ENDPOINT_TYPE.is_server());
 
         let (peer_parameters, remaining) =
@@ -262,6 +284,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
                 .as_bytes(),
         )?;
 
+        // The version information is usually validated when the ClientHello is received.
+        // This covers TLS providers that don't notify the transport at that point.
+        self.version_negotiation
+            .on_client_information(&peer_parameters.version_information)?;
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -280,6 +307,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            peer_parameters.min_ack_delay,
             dc_version,
         ))
     }
@@ -326,12 +354,190 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
 
         Ok(())
     }
+
+    // This is called by the client when resuming a session with early data
+    fn on_early_data_keys(
+        &mut self,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+        application_parameters: tls::ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# A client that attempts to send 0-RTT data MUST remember all other
+        //# transport parameters used by the server that it is able to process.
+
+        // The TLS provider stores the server's transport parameters with the session ticket
+        let (remembered, _remaining) = ServerTransportParameters::decode(DecoderBuffer::new(
+            application_parameters.transport_parameters,
+        ))
+        .map_err(|_| {
+            transport::Error::INTERNAL_ERROR.with_reason("Invalid remembered transport parameters")
+        })?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# When sending frames in 0-RTT packets, a client MUST only use
+        //# remembered transport parameters;
+        let stream_scheduler = self.take_stream_scheduler()?;
+        let stream_manager = <Config::StreamManager as stream::Manager>::new(
+            self.limits,
+            Config::ENDPOINT_TYPE,
+            self.limits.initial_flow_control_limits(),
+            remembered.flow_control_limits(),
+            self.path_manager.active_path().rtt_estimator.min_rtt(),
+            stream_scheduler,
+        );
+
+        let ack_manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            self.limits.ack_settings(),
+        );
+
+        let keep_alive = KeepAlive::new(
+            self.limits.max_idle_timeout(),
+            self.limits.max_keep_alive_period(),
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9221#section-3
+        //# When clients use 0-RTT, they MAY store the value of the server's
+        //# max_datagram_frame_size transport parameter. Doing so allows the
+        //# client to send DATAGRAM frames in 0-RTT packets.
+        let datagram_limits = remembered.datagram_limits();
+        let conn_info =
+            ConnectionInfo::new(datagram_limits.max_datagram_payload, self.waker.clone());
+        let (datagram_sender, datagram_receiver) = self.datagram.create_connection(&conn_info);
+        let datagram_manager = datagram::Manager::new(
+            datagram_sender,
+            datagram_receiver,
+            datagram_limits.max_datagram_payload,
+        );
+
+        self.early_data
+            .on_zero_rtt_keys(remembered.zero_rtt_parameters());
+
+        *self.application = Some(Box::new(ApplicationSpace::new_zero_rtt(
+            key,
+            header_key,
+            self.now,
+            stream_manager,
+            ack_manager,
+            keep_alive,
+            datagram_manager,
+        )));
+
+        Ok(())
+    }
+
+    // This is called by the client when the handshake provides 1-RTT keys after sending
+    // early data
+    fn on_early_data_complete(
+        &mut self,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+        peer_flow_control_limits: InitialFlowControlLimits,
+        max_datagram_payload: u64,
+        max_ack_delay: MaxAckDelay,
+        ack_frequency_manager: AckFrequencyManager,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        let space = self
+            .application
+            .as_mut()
+            .expect("early data requires an application space");
+
+        let cipher_suite = key.cipher_suite().into_event();
+        space.on_one_rtt_keys(key, header_key);
+        space.ack_frequency_manager = ack_frequency_manager;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# importantly, it MUST NOT use updated
+        //# values that it learns from the server's updated transport parameters
+        //# or from frames received in 1-RTT packets.
+
+        // 0-RTT packets are no longer sent so the limits from the handshake can be used
+        if self.early_data.is_rejected() {
+            // The server may have reduced the limits, which resets the streams that can't
+            // retransmit their early data
+            space
+                .stream_manager
+                .on_early_data_rejected(peer_flow_control_limits);
+            space
+                .datagram_manager
+                .on_early_data_rejected(max_datagram_payload);
+
+            // Instead of resetting the remaining streams, the data that was sent in 0-RTT
+            // packets is retransmitted in 1-RTT packets.
+            space.on_zero_rtt_discarded(
+                self.path_manager,
+                self.handshake_status,
+                self.local_id_registry,
+                self.publisher,
+            );
+        } else {
+            space
+                .stream_manager
+                .on_peer_limits_update(peer_flow_control_limits)?;
+        }
+
+        self.path_manager
+            .active_path_mut()
+            .rtt_estimator
+            .on_max_ack_delay(max_ack_delay);
+
+        self.publisher.on_key_update(event::builder::KeyUpdate {
+            key_type: event::builder::KeyType::OneRtt { generation: 0 },
+            cipher_suite,
+        });
+
+        Ok(())
+    }
+
+    // This is called by the server when switching to a compatible version
+    fn on_compatible_version(
+        &mut self,
+        server_params: &mut Vec<u8>,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        let version = self.version_negotiation.negotiated_version();
+
+        // All of the Initial packets sent by the server use the negotiated version
+        if let Some(initial) = self.initial.as_mut() {
+            let (key, header_key) =
+                <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
+                    version,
+                    self.version_negotiation.initial_key_cid(),
+                );
+            initial.on_compatible_version(
+                self.version_negotiation.original_version(),
+                key,
+                header_key,
+            );
+        }
+
+        // Authenticate the negotiated version in the server's transport parameters
+        let (mut params, _remaining) =
+            ServerTransportParameters::decode(DecoderBuffer::new(server_params)).map_err(|_| {
+                transport::Error::INTERNAL_ERROR.with_reason("Invalid server transport parameters")
+            })?;
+        params.version_information = self.version_negotiation.local_information();
+        *server_params = params.encode_to_vec();
+
+        Ok(())
+    }
 }
 
 impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
     tls::Context<<Config::TLSEndpoint as tls::Endpoint>::Session>
     for SessionContext<'_, Config, Pub>
 {
+    #[inline]
+    fn quic_version(&self) -> u32 {
+        self.version_negotiation.negotiated_version()
+    }
+
     fn on_handshake_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeKey,
@@ -367,18 +573,24 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
     fn on_zero_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
-        _header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
-        _application_parameters: tls::ApplicationParameters,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+        application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.zero_rtt_crypto.is_some() {
+        if self.zero_rtt_crypto.is_some() || self.application.is_some() {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("zero rtt keys initialized more than once"));
         }
 
         let cipher_suite = key.cipher_suite().into_event();
 
-        // TODO: also store the header_key
-        *self.zero_rtt_crypto = Some(Box::new(key));
+        if Config::ENDPOINT_TYPE.is_server() {
+            self.early_data.on_server_zero_rtt_keys()?;
+            *self.zero_rtt_crypto = Some(Box::new((key, header_key)));
+        } else if self.early_data.is_enabled() {
+            self.on_early_data_keys(key, header_key, application_parameters)?;
+        } else {
+            *self.zero_rtt_crypto = Some(Box::new((key, header_key)));
+        }
 
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::ZeroRtt,
@@ -387,13 +599,21 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_zero_rtt_status(&mut self, accepted: bool) -> Result<(), transport::Error> {
+        self.early_data.on_status(accepted);
+        Ok(())
+    }
+
     fn on_one_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
         application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.application.is_some() {
+        // A client sending early data already created the application space
+        let is_early_data = self.early_data.is_sending();
+
+        if self.application.is_some() != is_early_data {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("application keys initialized more than once"));
         }
@@ -413,6 +633,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             max_ack_delay,
+            min_ack_delay,
             dc_version,
         ) = match Config::ENDPOINT_TYPE {
             endpoint::Type::Client => self.on_server_params(param_decoder)?,
@@ -432,12 +653,27 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
 
+        let ack_frequency_manager = AckFrequencyManager::new(max_ack_delay, min_ack_delay);
+
+        if is_early_data {
+            return self.on_early_data_complete(
+                key,
+                header_key,
+                peer_flow_control_limits,
+                datagram_limits.max_datagram_payload,
+                max_ack_delay,
+                ack_frequency_manager,
+            );
+        }
+
+        let stream_scheduler = self.take_stream_scheduler()?;
         let stream_manager = <Config::StreamManager as stream::Manager>::new(
             self.limits,
             Config::ENDPOINT_TYPE,
             self.limits.initial_flow_control_limits(),
             peer_flow_control_limits,
             self.path_manager.active_path().rtt_estimator.min_rtt(),
+            stream_scheduler,
         );
 
         let ack_manager = AckManager::new(
@@ -504,6 +740,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             self.now,
             stream_manager,
             ack_manager,
+            ack_frequency_manager,
             keep_alive,
             datagram_manager,
             dc_manager,
@@ -513,6 +750,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             cipher_suite,
         });
 
+        if let Some(status) = self.early_data.on_server_one_rtt_keys() {
+            self.publisher
+                .on_zero_rtt_status_updated(event::builder::ZeroRttStatusUpdated { status });
+        }
+
         Ok(())
     }
 
@@ -604,6 +846,9 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             return Err(err);
         }
 
+        self.path_manager
+            .on_handshake_info(self.application_protocol, self.server_name.as_ref());
+
         self.handshake_status
             .on_handshake_complete(Config::ENDPOINT_TYPE, self.publisher);
 
@@ -637,6 +882,31 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             return None;
         }
 
+        if Config::ENDPOINT_TYPE.is_server() && space.crypto_stream.rx.consumed_len() == 0 {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-9.2
+            //# Endpoints MUST implement and use the replay protections described in
+            //# [TLS13], however it is recognized that these protections are
+            //# imperfect.
+            let message = space
+                .crypto_stream
+                .rx
+                .iter()
+                .fold(vec![], |mut message, chunk| {
+                    message.extend_from_slice(chunk);
+                    message
+                });
+
+            // The validator is called before the TLS provider verifies the PSK binder, since
+            // rustls needs the decision before it processes the ClientHello. Validators key their
+            // registers on the binder so a forged ClientHello can't displace a legitimate one.
+            if let Some(hello) = early_data::ClientHello::decode(&message) {
+                let remote_address = self.path_manager.active_path().remote_address().0;
+                let request = early_data::Request::new(&remote_address, &hello, self.now);
+                let outcome = self.early_data_validator.on_early_data_request(&request);
+                self.early_data.on_request(outcome);
+            }
+        }
+
         space
             .crypto_stream
             .rx
@@ -662,6 +932,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             .map(|bytes| bytes.freeze())
     }
 
+    fn can_accept_early_data(&self) -> bool {
+        self.early_data.can_accept()
+    }
+
     fn can_send_initial(&self) -> bool {
         self.initial
             .as_ref()
@@ -703,6 +977,14 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
 
     fn send_application(&mut self, transmission: Bytes) {
         if cfg!(any(test, feature = "unstable_resumption")) {
+            if Config::ENDPOINT_TYPE.is_server() {
+                // The validator needs the `ticket_age_add` of each ticket to check the
+                // freshness of ClientHello messages that resume the session
+                early_data::SessionTicket::decode_all(&transmission, self.now, |ticket| {
+                    self.early_data_validator.on_session_ticket_issued(ticket)
+                });
+            }
+
             self.application
                 .as_mut()
                 .expect("can_send_application should be called before sending")
@@ -723,20 +1005,30 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
     ) -> Result<(), Error> {
         debug_assert!(Config::ENDPOINT_TYPE.is_server());
 
-        if Config::DcEndpoint::ENABLED {
-            let param_decoder = DecoderBuffer::new(client_params.transport_parameters);
-            let (client_params, remaining) = ClientTransportParameters::decode(param_decoder)
-                .map_err(|_| {
-                    //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4
-                    //# An endpoint SHOULD treat receipt of
-                    //# duplicate transport parameters as a connection error of type
-                    //# TRANSPORT_PARAMETER_ERROR.
-                    transport::Error::TRANSPORT_PARAMETER_ERROR
-                        .with_reason("Invalid transport parameters")
-                })?;
+        let param_decoder = DecoderBuffer::new(client_params.transport_parameters);
+        let (client_params, remaining) =
+            ClientTransportParameters::decode(param_decoder).map_err(|_| {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4
+                //# An endpoint SHOULD treat receipt of
+                //# duplicate transport parameters as a connection error of type
+                //# TRANSPORT_PARAMETER_ERROR.
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("Invalid transport parameters")
+            })?;
 
-            debug_assert_eq!(remaining.len(), 0);
+        debug_assert_eq!(remaining.len(), 0);
+
+        // The client's version information is available before the handshake keys are derived,
+        // which allows the server to switch the connection to a compatible version.
+        if self
+            .version_negotiation
+            .on_client_information(&client_params.version_information)?
+            .is_some()
+        {
+            self.on_compatible_version(server_params)?;
+        }
 
+        if Config::DcEndpoint::ENABLED {
             if let Some(selected_version) = dc::select_version(client_params.dc_supported_versions)
             {
                 DcSupportedVersions::for_server(selected_version).append_to_buffer(server_params)
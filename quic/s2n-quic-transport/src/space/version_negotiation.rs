@@ -0,0 +1,375 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compatible version negotiation, as defined in <https://www.rfc-editor.org/rfc/rfc9368>
+//!
+//! Both endpoints exchange a `version_information` transport parameter. A server that prefers
+//! a different version than the one the client started with can switch the connection to that
+//! version when responding to the client's first flight, without an extra round trip. Since
+//! the transport parameters are authenticated by the handshake, each endpoint can validate
+//! the peer's view of the negotiation to detect downgrade attempts.
+
+use s2n_quic_core::{
+    connection::id::UnboundedId,
+    endpoint, event, transport,
+    transport::parameters::VersionInformation,
+    version::{self, Version},
+};
+
+#[derive(Debug)]
+pub struct VersionNegotiation {
+    endpoint_type: endpoint::Type,
+    config: version::Config,
+    /// The version of the first Initial packet sent by the client
+    original_version: Version,
+    /// The version currently in use by the connection
+    negotiated_version: Version,
+    /// The connection ID used to derive the Initial packet protection keys
+    initial_key_cid: UnboundedId,
+    /// Set if the TLS session is able to switch versions in the middle of the handshake
+    can_switch_version: bool,
+    /// The version information received from the peer, once it's been validated
+    peer_information: Option<VersionInformation>,
+    event_pending: bool,
+}
+
+impl VersionNegotiation {
+    pub fn new(
+        endpoint_type: endpoint::Type,
+        config: version::Config,
+        original_version: Version,
+        initial_key_cid: &[u8],
+        can_switch_version: bool,
+    ) -> Self {
+        Self {
+            endpoint_type,
+            config,
+            original_version,
+            negotiated_version: original_version,
+            initial_key_cid: UnboundedId::try_from_bytes(initial_key_cid)
+                .expect("connection ID already validated"),
+            can_switch_version,
+            peer_information: None,
+            event_pending: false,
+        }
+    }
+
+    /// The version of the first Initial packet sent by the client
+    #[inline]
+    pub fn original_version(&self) -> Version {
+        self.original_version
+    }
+
+    /// The version currently in use by the connection
+    #[inline]
+    pub fn negotiated_version(&self) -> Version {
+        self.negotiated_version
+    }
+
+    /// The connection ID used to derive the Initial packet protection keys
+    #[inline]
+    pub fn initial_key_cid(&self) -> &[u8] {
+        self.initial_key_cid.as_bytes()
+    }
+
+    /// Called by the client when a Retry packet changes the Initial packet protection keys
+    pub fn on_retry_packet(&mut self, retry_source_connection_id: &[u8]) {
+        debug_assert!(self.endpoint_type.is_client());
+        self.initial_key_cid = UnboundedId::try_from_bytes(retry_source_connection_id)
+            .expect("connection ID already validated");
+    }
+
+    /// The version information sent to the peer
+    ///
+    /// The parameter is always sent, even with a single version enabled, so the peer can
+    /// validate the negotiated version as described in
+    /// <https://www.rfc-editor.org/rfc/rfc9368#section-3>.
+    pub fn local_information(&self) -> VersionInformation {
+        let versions = self.config.supported_versions();
+
+        if self.can_switch_version {
+            VersionInformation::new(self.negotiated_version, versions.iter().copied())
+        } else {
+            // The TLS session can't be moved to a different version, so only offer the
+            // version in use
+            VersionInformation::new(self.negotiated_version, [self.negotiated_version])
+        }
+    }
+
+    /// Called by the server with the version information from the client's transport parameters
+    ///
+    /// Returns the version the connection should switch to, if any.
+    pub fn on_client_information(
+        &mut self,
+        information: &VersionInformation,
+    ) -> Result<Option<Version>, transport::Error> {
+        debug_assert!(self.endpoint_type.is_server());
+
+        // The client's parameters are processed when the ClientHello is received and again
+        // when the handshake completes. Only the first one is able to change the version.
+        if self.peer_information.is_some() {
+            return Ok(None);
+        }
+
+        if let Some(chosen_version) = information.chosen_version() {
+            // The client's chosen version must match the version of its first Initial packet.
+            // Otherwise the version in the long header was modified by an attacker.
+            if chosen_version != self.original_version {
+                return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("client chosen version mismatch"));
+            }
+        }
+
+        self.on_peer_information(information);
+
+        if !self.can_switch_version {
+            return Ok(None);
+        }
+
+        let version = self
+            .config
+            .select_compatible_version(self.original_version, information.available_versions());
+
+        if let Some(version) = version {
+            self.negotiated_version = version;
+        }
+
+        Ok(version)
+    }
+
+    /// Called by the client with the version information from the server's transport parameters
+    pub fn on_server_information(
+        &mut self,
+        information: &VersionInformation,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(self.endpoint_type.is_client());
+
+        match information.chosen_version() {
+            // The server's chosen version must match the version the connection is using,
+            // otherwise the versions were modified by an attacker.
+            Some(chosen_version) if chosen_version != self.negotiated_version => {
+                return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("server chosen version mismatch"));
+            }
+            // A server that switched versions must also authenticate it
+            None if self.negotiated_version != self.original_version => {
+                return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("missing server version information"));
+            }
+            _ => {}
+        }
+
+        self.on_peer_information(information);
+
+        Ok(())
+    }
+
+    /// Returns `true` if the client is able to switch to the version of an Initial packet
+    /// sent by the server
+    pub fn is_compatible(&self, version: Version) -> bool {
+        debug_assert!(self.endpoint_type.is_client());
+
+        // the client only switches once; any other versions are dropped
+        self.negotiated_version == self.original_version
+            && version != self.original_version
+            && version::is_compatible(self.original_version, version)
+            && self
+                .local_information()
+                .available_versions()
+                .contains(&version)
+    }
+
+    /// Called by the client when the server switches the connection to a compatible version
+    pub fn on_compatible_version(&mut self, version: Version) {
+        debug_assert!(self.endpoint_type.is_client());
+        debug_assert!(self.is_compatible(version));
+        self.negotiated_version = version;
+    }
+
+    fn on_peer_information(&mut self, information: &VersionInformation) {
+        self.peer_information = Some(*information);
+        self.event_pending = true;
+    }
+
+    /// Emits a `VersionInformation` event once the peer's version information is processed
+    pub fn on_publish<Pub: event::EndpointPublisher>(&mut self, publisher: &mut Pub) {
+        if !core::mem::take(&mut self.event_pending) {
+            return;
+        }
+
+        let local = self.local_information();
+        let peer = self.peer_information.unwrap_or_default();
+
+        let (server, client) = match self.endpoint_type {
+            endpoint::Type::Server => (&local, &peer),
+            endpoint::Type::Client => (&peer, &local),
+        };
+
+        publisher.on_version_information(event::builder::VersionInformation {
+            server_versions: server.available_versions(),
+            client_versions: client.available_versions(),
+            chosen_version: Some(self.negotiated_version),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::version::{VERSION_1, VERSION_2};
+
+    const CID: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn config(preferred: Version) -> version::Config {
+        version::Config::builder()
+            .with_version_1(true)
+            .unwrap()
+            .with_version_2(true)
+            .unwrap()
+            .with_preferred_version(preferred)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn server(preferred: Version, original_version: Version) -> VersionNegotiation {
+        VersionNegotiation::new(
+            endpoint::Type::Server,
+            config(preferred),
+            original_version,
+            CID,
+            true,
+        )
+    }
+
+    fn client(preferred: Version) -> VersionNegotiation {
+        VersionNegotiation::new(
+            endpoint::Type::Client,
+            config(preferred),
+            preferred,
+            CID,
+            true,
+        )
+    }
+
+    #[test]
+    fn upgrade_test() {
+        let mut client = client(VERSION_1);
+        let mut server = server(VERSION_2, VERSION_1);
+
+        let client_information = client.local_information();
+        assert_eq!(client_information.chosen_version(), Some(VERSION_1));
+        assert_eq!(
+            client_information.available_versions(),
+            &[VERSION_1, VERSION_2]
+        );
+
+        assert_eq!(
+            server.on_client_information(&client_information),
+            Ok(Some(VERSION_2))
+        );
+        assert_eq!(server.negotiated_version(), VERSION_2);
+        // processing the parameters again doesn't change the version
+        assert_eq!(server.on_client_information(&client_information), Ok(None));
+
+        let server_information = server.local_information();
+        assert_eq!(server_information.chosen_version(), Some(VERSION_2));
+
+        // the server hasn't switched the client yet
+        assert!(client.on_server_information(&server_information).is_err());
+
+        assert!(client.is_compatible(VERSION_2));
+        assert!(!client.is_compatible(VERSION_1));
+        client.on_compatible_version(VERSION_2);
+        assert!(!client.is_compatible(VERSION_2));
+        assert_eq!(client.on_server_information(&server_information), Ok(()));
+        assert_eq!(client.negotiated_version(), VERSION_2);
+    }
+
+    #[test]
+    fn no_upgrade_test() {
+        let mut client = client(VERSION_1);
+        let mut server = server(VERSION_1, VERSION_1);
+
+        assert_eq!(
+            server.on_client_information(&client.local_information()),
+            Ok(None)
+        );
+        assert_eq!(server.negotiated_version(), VERSION_1);
+        assert_eq!(
+            client.on_server_information(&server.local_information()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn unsupported_upgrade_test() {
+        let mut server = server(VERSION_2, VERSION_1);
+        let client = VersionNegotiation::new(
+            endpoint::Type::Client,
+            config(VERSION_1),
+            VERSION_1,
+            CID,
+            false,
+        );
+
+        // the client can only offer the original version
+        let client_information = client.local_information();
+        assert_eq!(client_information.available_versions(), &[VERSION_1]);
+        assert!(!client.is_compatible(VERSION_2));
+        assert_eq!(server.on_client_information(&client_information), Ok(None));
+    }
+
+    #[test]
+    fn downgrade_test() {
+        // the version in the client's first Initial was modified
+        let mut server = server(VERSION_1, VERSION_1);
+        let client_information = client(VERSION_2).local_information();
+        assert_eq!(
+            server
+                .on_client_information(&client_information)
+                .unwrap_err()
+                .code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+
+        // the server claims a different version than the one in use
+        let mut client = client(VERSION_2);
+        let server_information = self::server(VERSION_1, VERSION_1).local_information();
+        assert_eq!(
+            client
+                .on_server_information(&server_information)
+                .unwrap_err()
+                .code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+
+        // the server switched versions without sending version information
+        let mut client = self::client(VERSION_1);
+        client.on_compatible_version(VERSION_2);
+        assert_eq!(
+            client
+                .on_server_information(&VersionInformation::default())
+                .unwrap_err()
+                .code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+    }
+
+    #[test]
+    fn single_version_test() {
+        let negotiation = VersionNegotiation::new(
+            endpoint::Type::Client,
+            version::Config::default(),
+            VERSION_1,
+            CID,
+            true,
+        );
+
+        // the version in use is still sent if there's only a single version
+        let information = negotiation.local_information();
+        assert_eq!(information.chosen_version(), Some(VERSION_1));
+        assert_eq!(information.available_versions(), &[VERSION_1]);
+        assert!(!negotiation.is_compatible(VERSION_2));
+    }
+}
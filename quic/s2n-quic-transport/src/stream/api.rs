@@ -4,7 +4,7 @@
 //! Defines the Stream objects that applications are interacting with
 
 use crate::connection::Connection;
-use bytes::Bytes;
+use bytes::{buf::UninitSlice, BufMut, Bytes};
 use core::{
     fmt,
     future::Future,
@@ -13,7 +13,7 @@ use core::{
 };
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{ops, Priority, StreamError, StreamId, StreamType},
 };
 
 #[derive(Clone)]
@@ -22,6 +22,8 @@ struct State {
     stream_id: StreamId,
     rx: ops::Status,
     tx: ops::Status,
+    /// Set once the stream reports receiving data in 0-RTT packets
+    early_data: bool,
 }
 
 impl State {
@@ -31,6 +33,7 @@ impl State {
             stream_id,
             rx: ops::Status::Open,
             tx: ops::Status::Open,
+            early_data: false,
         }
     }
 
@@ -173,6 +176,34 @@ macro_rules! tx_stream_apis {
             }
         }
 
+        /// Copies data from a slice onto the stream for sending it towards the peer.
+        ///
+        /// The method will return:
+        /// - `Poll::Ready(Ok(len))` if `len` bytes from the start of the slice were copied for
+        ///   sending. If `len` is less than the length of the slice, the remaining data should be
+        ///   sent in a subsequent call.
+        /// - `Poll::Ready(Err(stream_error))` if the data could not be sent, because the stream
+        ///   had previously entered an error state.
+        /// - `Poll::Pending` if the send buffer capacity is currently exhausted. In this case, the
+        ///   caller should retry sending after the `Waker` on the provided `Context` is notified.
+        pub fn poll_send_from(
+            &mut self,
+            data: &[u8],
+            cx: &mut Context,
+        ) -> Poll<Result<usize, StreamError>> {
+            if data.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let response = ready!(self
+                .tx_request()?
+                .send_from(data)
+                .poll(Some(cx))?
+                .into_poll());
+
+            Ok(response.tx().expect("invalid response").bytes.consumed).into()
+        }
+
         /// Flushes the send buffer and waits for acknowledgement from the peer.
         ///
         /// The method will return:
@@ -215,6 +246,12 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Sets the priority of the stream when scheduling data for transmission
+        pub fn set_priority(&mut self, priority: Priority) -> Result<(), StreamError> {
+            self.tx_request()?.with_priority(priority).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -279,6 +316,44 @@ macro_rules! rx_stream_apis {
             Poll::Ready(Ok((consumed, is_open)))
         }
 
+        /// Receives data from the stream directly into the provided buffer.
+        ///
+        /// The method will return:
+        /// - `Poll::Ready(Ok(len))` if `len` bytes were copied into the buffer. The buffer is
+        ///   advanced past the copied bytes. If `len == 0`, either the stream has finished and all
+        ///   of the data was consumed, or the buffer has no remaining capacity.
+        /// - `Poll::Ready(Err(stream_error))` if the stream could not be read, because the stream
+        ///   had previously entered an error state.
+        /// - `Poll::Pending` if the stream is waiting to receive data from the peer. In this case, the
+        ///   caller should retry receiving after the `Waker` on the provided `Context` is notified.
+        pub fn poll_receive_into<B>(
+            &mut self,
+            buf: &mut B,
+            cx: &mut Context,
+        ) -> Poll<Result<usize, StreamError>>
+        where
+            B: BufMut + ?Sized,
+        {
+            if !buf.has_remaining_mut() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let response = ready!(self
+                .rx_request()?
+                .receive_into(buf.chunk_mut())
+                .poll(Some(cx))?
+                .into_poll());
+
+            let len = response.rx().expect("invalid response").bytes.consumed;
+
+            // SAFETY: the stream initialized `len` bytes at the start of the chunk
+            unsafe {
+                buf.advance_mut(len);
+            }
+
+            Poll::Ready(Ok(len))
+        }
+
         /// Sends a `STOP_SENDING` message to the peer. This requests the peer to
         /// finish the `Stream` as soon as possible by issuing a `RESET` with the
         /// provided `error_code`.
@@ -463,6 +538,11 @@ macro_rules! tx_request_apis {
             self
         }
 
+        pub fn send_from(&mut self, data: &'chunks [u8]) -> &mut Self {
+            self.request.send_from(data);
+            self
+        }
+
         pub fn send_readiness(&mut self) -> &mut Self {
             // express interest in tx
             if self.request.tx.is_none() {
@@ -485,6 +565,11 @@ macro_rules! tx_request_apis {
             self.request.flush();
             self
         }
+
+        pub fn with_priority(&mut self, priority: Priority) -> &mut Self {
+            self.request.with_priority(priority);
+            self
+        }
     };
 }
 
@@ -495,6 +580,11 @@ macro_rules! rx_request_apis {
             self
         }
 
+        pub fn receive_into(&mut self, buffer: &'chunks mut UninitSlice) -> &mut Self {
+            self.request.receive_into(buffer);
+            self
+        }
+
         pub fn with_watermark(&mut self, low: usize, high: usize) -> &mut Self {
             self.request.with_watermark(low, high);
             self
@@ -547,6 +637,7 @@ impl<'chunks> Request<'_, 'chunks> {
                 }),
                 rx: Some(ops::rx::Response {
                     status: ops::Status::Finished,
+                    early_data: self.state.early_data,
                     ..Default::default()
                 }),
             });
@@ -556,6 +647,7 @@ impl<'chunks> Request<'_, 'chunks> {
 
         if let Some(rx) = response.rx() {
             self.state.rx = rx.status;
+            self.state.early_data |= rx.early_data;
         }
 
         if let Some(tx) = response.tx() {
@@ -632,6 +724,7 @@ impl<'chunks> RxRequest<'_, 'chunks> {
             // removed from the stream container already
             return Ok(ops::rx::Response {
                 status: ops::Status::Finished,
+                early_data: self.state.early_data,
                 ..Default::default()
             });
         }
@@ -643,6 +736,7 @@ impl<'chunks> RxRequest<'_, 'chunks> {
             .expect("invalid response");
 
         self.state.rx = response.status;
+        self.state.early_data |= response.early_data;
 
         Ok(response)
     }
@@ -61,6 +61,7 @@ pub mod tx {
 
     pub struct Request {
         chunks: Option<Vec<Bytes>>,
+        data_len: Option<usize>,
         reset: Option<application::Error>,
         flush: bool,
         finish: bool,
@@ -70,6 +71,7 @@ pub mod tx {
         fn from(tx: &'a ops::tx::Request) -> Self {
             Self {
                 chunks: tx.chunks.as_ref().map(|chunks| chunks.to_vec()),
+                data_len: tx.data.map(|data| data.len()),
                 reset: tx.reset,
                 flush: tx.flush,
                 finish: tx.finish,
@@ -145,6 +147,7 @@ pub mod tx {
 
             // the request is interested in push availability
             if self.chunks.as_ref().is_none_or(|chunks| chunks.is_empty())
+                && self.data_len.is_none_or(|len| len == 0)
                 && !self.finish
                 && !self.flush
                 && context.is_some()
@@ -255,6 +258,19 @@ pub mod tx {
                 }
             }
 
+            if let Some(data_len) = self.data_len {
+                if let Ok(response) = response {
+                    assert!(
+                        response.bytes.consumed <= data_len,
+                        "consumed bytes should not exceed the length of the provided data"
+                    );
+                    assert_eq!(
+                        response.chunks.consumed, 0,
+                        "copying from a slice should never consume chunks"
+                    );
+                }
+            }
+
             if self.finish {
                 if let Ok(response) = response {
                     // finishing only happens after we don't consume anything
@@ -312,6 +328,7 @@ pub mod rx {
 
     pub struct Request {
         chunks: Option<Vec<Bytes>>,
+        buffer_len: Option<usize>,
         low_watermark: usize,
         high_watermark: usize,
         stop_sending: Option<application::Error>,
@@ -321,6 +338,7 @@ pub mod rx {
         fn from(rx: &'a ops::rx::Request) -> Self {
             Self {
                 chunks: rx.chunks.as_ref().map(|chunks| chunks.to_vec()),
+                buffer_len: rx.buffer.as_ref().map(|buffer| buffer.len()),
                 high_watermark: rx.high_watermark,
                 low_watermark: rx.low_watermark,
                 stop_sending: rx.stop_sending,
@@ -451,6 +469,19 @@ pub mod rx {
                     }
                 }
             }
+
+            if let Some(buffer_len) = self.buffer_len {
+                if let Ok(response) = response {
+                    assert!(
+                        response.bytes.consumed <= buffer_len,
+                        "consumed bytes should not exceed the capacity of the provided buffer"
+                    );
+                    assert_eq!(
+                        response.chunks.consumed, 0,
+                        "copying into a buffer should never consume chunks"
+                    );
+                }
+            }
         }
     }
 }
@@ -98,6 +98,19 @@ impl Controller {
         }
     }
 
+    /// This method is called when the peer rejected early data and the limits from its
+    /// transport parameters replace the ones the controller was created with.
+    pub fn on_early_data_rejected(&mut self, stream_type: StreamType, maximum_streams: VarInt) {
+        match stream_type {
+            StreamType::Bidirectional => self
+                .local_bidi_controller
+                .on_early_data_rejected(maximum_streams),
+            StreamType::Unidirectional => self
+                .local_uni_controller
+                .on_early_data_rejected(maximum_streams),
+        }
+    }
+
     /// This method is called when the local application wishes to open the next stream
     /// of a type (Bidirectional/Unidirectional).
     ///
@@ -83,6 +83,21 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         self.wake_unblocked();
     }
 
+    /// Replaces the peer's stream limit after the peer rejected early data
+    ///
+    /// Unlike [`Self::on_max_streams`], the limit may be reduced. Streams that were already
+    /// opened past the new limit remain counted, so no new streams can be opened until the peer
+    /// raises the limit past them.
+    pub fn on_early_data_rejected(&mut self, maximum_streams: VarInt) {
+        let increased = maximum_streams > self.peer_cumulative_stream_limit;
+        self.peer_cumulative_stream_limit = maximum_streams;
+
+        if increased {
+            self.streams_blocked_sync.stop_sync();
+            self.wake_unblocked();
+        }
+    }
+
     pub fn update_sync_period(&mut self, blocked_sync_period: Duration) {
         self.streams_blocked_sync
             .update_sync_period(blocked_sync_period);
@@ -177,9 +177,8 @@ impl IncomingConnectionFlowController {
         self.inner.borrow().acquired_window
     }
 
-    #[cfg(test)]
     pub fn remaining_window(&self) -> VarInt {
-        self.inner.borrow_mut().remaining_window()
+        self.inner.borrow().remaining_window()
     }
 
     /// Returns the MAX_DATA window that is currently synchronized
@@ -23,7 +23,7 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    ack,
+    ack, application,
     connection::error::Error,
     endpoint,
     frame::{
@@ -31,7 +31,12 @@ use s2n_quic_core::{
         StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
-    stream::{iter::StreamIter, ops, StreamId, StreamType},
+    stream::{
+        iter::StreamIter,
+        ops,
+        scheduler::{self, Scheduler},
+        StreamId, StreamType,
+    },
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
     varint::VarInt,
@@ -159,7 +164,7 @@ impl AcceptState {
 
 /// Manages all active `Stream`s inside a connection
 #[derive(Debug)]
-pub struct StreamManagerState<S> {
+pub struct StreamManagerState<S, Sched> {
     /// Flow control credit manager for receiving data
     pub(super) incoming_connection_flow_controller: IncomingConnectionFlowController,
     /// Flow control credit manager for sending data
@@ -167,7 +172,7 @@ pub struct StreamManagerState<S> {
     /// Controller for managing streams concurrency limits
     stream_controller: stream::Controller,
     /// A container which contains all Streams
-    streams: StreamContainer<S>,
+    streams: StreamContainer<S, Sched>,
     /// The next Stream ID which was not yet used for an initiated stream
     /// for each stream type
     pub(super) next_stream_ids: StreamIdSet,
@@ -189,7 +194,7 @@ pub struct StreamManagerState<S> {
     stream_limits: stream::Limits,
 }
 
-impl<S: StreamTrait> StreamManagerState<S> {
+impl<S: StreamTrait, Sched: Scheduler> StreamManagerState<S, Sched> {
     /// Performs the given transaction on the `StreamManagerState`.
     /// If an error occurs, all Streams will be reset with an internal reset.
     pub fn reset_streams_on_error<F, R>(&mut self, func: F) -> Result<R, transport::Error>
@@ -414,10 +419,11 @@ impl<S: StreamTrait> StreamManagerState<S> {
 }
 
 /// Manages all active `Stream`s inside a connection.
-/// `AbstractStreamManager` is parameterized over the `Stream` type.
+/// `AbstractStreamManager` is parameterized over the `Stream` type and the
+/// `Scheduler` which orders streams for transmission.
 #[derive(Debug)]
-pub struct AbstractStreamManager<S> {
-    pub(super) inner: StreamManagerState<S>,
+pub struct AbstractStreamManager<S, Sched = scheduler::StrictPriority> {
+    pub(super) inner: StreamManagerState<S, Sched>,
     last_blocked_sync_period: Duration,
     last_min_rtt: Duration,
 }
@@ -425,9 +431,9 @@ pub struct AbstractStreamManager<S> {
 // Sending the `AbstractStreamManager` between threads is safe, since we never expose the `Rc`s
 // outside of the container
 #[allow(unknown_lints, clippy::non_send_fields_in_send_ty)]
-unsafe impl<S> Send for AbstractStreamManager<S> {}
+unsafe impl<S, Sched: Send> Send for AbstractStreamManager<S, Sched> {}
 
-impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
+impl<S: 'static + StreamTrait, Sched: Scheduler> AbstractStreamManager<S, Sched> {
     fn accept_stream_with_type(
         &mut self,
         stream_type: StreamType,
@@ -558,13 +564,18 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
     }
 }
 
-impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
+impl<S: 'static + StreamTrait, Sched: Scheduler> stream::Manager
+    for AbstractStreamManager<S, Sched>
+{
+    type Scheduler = Sched;
+
     fn new(
         connection_limits: &connection::Limits,
         local_endpoint_type: endpoint::Type,
         initial_local_limits: InitialFlowControlLimits,
         initial_peer_limits: InitialFlowControlLimits,
         min_rtt: Duration,
+        scheduler: Sched,
     ) -> Self {
         // We limit the initial data limit to u32::MAX (4GB), which far
         // exceeds the reasonable amount of data a connection is
@@ -593,7 +604,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                     connection_limits.stream_limits(),
                     min_rtt,
                 ),
-                streams: StreamContainer::new(connection_limits),
+                streams: StreamContainer::new(connection_limits, scheduler),
                 next_stream_ids: StreamIdSet::initial(),
                 local_endpoint_type,
                 initial_local_limits,
@@ -619,6 +630,18 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             .acquired_window()
     }
 
+    fn incoming_flow_control_credit(&self) -> VarInt {
+        self.inner
+            .incoming_connection_flow_controller
+            .remaining_window()
+    }
+
+    fn outgoing_flow_control_credit(&self) -> VarInt {
+        self.inner
+            .outgoing_connection_flow_controller
+            .available_window()
+    }
+
     fn poll_accept(
         &mut self,
         stream_type: Option<StreamType>,
@@ -893,6 +916,13 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         self.handle_stream_frame(stream_id, |stream, events| stream.on_data(frame, events))
     }
 
+    fn on_early_data(&mut self, frame: &StreamRef) -> Result<(), transport::Error> {
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_early_data(frame, events)
+        })
+    }
+
     fn on_data_blocked(&mut self, _frame: DataBlocked) -> Result<(), transport::Error> {
         Ok(()) // This is currently ignored
     }
@@ -975,6 +1005,130 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         Ok(())
     }
 
+    fn on_peer_limits_update(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error> {
+        // Streams opened in the meantime use the new limits
+        self.inner.initial_peer_limits = peer_limits;
+
+        let local_endpoint_type = self.inner.local_endpoint_type;
+        let mut events = StreamEvents::new();
+        let mut result = Ok(());
+
+        // Raise the send window of the streams that were opened with the previous limits
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                let stream_id = stream.stream_id();
+                if stream_id.initiator() != local_endpoint_type || result.is_err() {
+                    return;
+                }
+
+                let frame = MaxStreamData {
+                    stream_id: stream_id.into(),
+                    maximum_stream_data: peer_limits
+                        .stream_limits
+                        .max_data(local_endpoint_type.peer_type(), stream_id),
+                };
+                result = stream.on_max_stream_data(&frame, &mut events);
+            });
+
+        events.wake_all();
+        result?;
+
+        self.on_max_streams(&MaxStreams {
+            stream_type: StreamType::Bidirectional,
+            maximum_streams: peer_limits.max_open_remote_bidirectional_streams,
+        })?;
+        self.on_max_streams(&MaxStreams {
+            stream_type: StreamType::Unidirectional,
+            maximum_streams: peer_limits.max_open_remote_unidirectional_streams,
+        })?;
+        self.on_max_data(MaxData {
+            maximum_data: peer_limits.max_data,
+        })
+    }
+
+    fn on_early_data_rejected(&mut self, peer_limits: InitialFlowControlLimits) {
+        // Streams opened in the meantime use the new limits
+        self.inner.initial_peer_limits = peer_limits;
+
+        let local_endpoint_type = self.inner.local_endpoint_type;
+
+        // Returns the new stream data limit if the stream can retransmit its early data
+        let stream_limit = |stream: &S| {
+            let stream_id = stream.stream_id();
+            let maximum_streams = match stream_id.stream_type() {
+                StreamType::Bidirectional => peer_limits.max_open_remote_bidirectional_streams,
+                StreamType::Unidirectional => peer_limits.max_open_remote_unidirectional_streams,
+            };
+
+            // stream IDs of each type are numbered in increments of 4
+            if stream_id.as_varint().as_u64() / 4 >= maximum_streams.as_u64() {
+                return None;
+            }
+
+            let max_stream_data = peer_limits
+                .stream_limits
+                .max_data(local_endpoint_type.peer_type(), stream_id);
+
+            Some(max_stream_data).filter(|max| stream.acquired_connection_window() <= *max)
+        };
+
+        // The peer didn't process any of the early data, so only the streams which retransmit
+        // it count against the new connection limit
+        let mut acquired = VarInt::from_u8(0);
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                if stream.stream_id().initiator() == local_endpoint_type
+                    && stream_limit(stream).is_some()
+                {
+                    acquired = acquired.saturating_add(stream.acquired_connection_window());
+                }
+            });
+
+        // If the streams exceed the connection limit together, all of them are reset
+        let connection_fits = acquired <= peer_limits.max_data;
+        if !connection_fits {
+            acquired = VarInt::from_u8(0);
+        }
+
+        let mut events = StreamEvents::new();
+
+        self.inner
+            .streams
+            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+                if stream.stream_id().initiator() != local_endpoint_type {
+                    return;
+                }
+
+                match stream_limit(stream).filter(|_| connection_fits) {
+                    Some(max_stream_data) => stream.on_early_data_rejected(max_stream_data),
+                    // The peer never saw the stream, so it is reset without notifying it
+                    None => stream.on_internal_reset(
+                        StreamError::stream_reset(application::Error::UNKNOWN),
+                        &mut events,
+                    ),
+                }
+            });
+
+        events.wake_all();
+
+        self.inner.stream_controller.on_early_data_rejected(
+            StreamType::Bidirectional,
+            peer_limits.max_open_remote_bidirectional_streams,
+        );
+        self.inner.stream_controller.on_early_data_rejected(
+            StreamType::Unidirectional,
+            peer_limits.max_open_remote_unidirectional_streams,
+        );
+        self.inner
+            .outgoing_connection_flow_controller
+            .on_early_data_rejected(peer_limits.max_data, acquired);
+    }
+
     fn poll_request(
         &mut self,
         stream_id: StreamId,
@@ -995,7 +1149,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     }
 }
 
-impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
+impl<S: StreamTrait, Sched: Scheduler> timer::Provider for AbstractStreamManager<S, Sched> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.inner.stream_controller.timers(query)?;
@@ -1007,7 +1161,9 @@ impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
     }
 }
 
-impl<S: StreamTrait> transmission::interest::Provider for AbstractStreamManager<S> {
+impl<S: StreamTrait, Sched: Scheduler> transmission::interest::Provider
+    for AbstractStreamManager<S, Sched>
+{
     #[inline]
     fn transmission_interest<Q: transmission::interest::Query>(
         &self,
@@ -1026,7 +1182,9 @@ impl<S: StreamTrait> transmission::interest::Provider for AbstractStreamManager<
     }
 }
 
-impl<S: StreamTrait> connection::finalization::Provider for AbstractStreamManager<S> {
+impl<S: StreamTrait, Sched: Scheduler> connection::finalization::Provider
+    for AbstractStreamManager<S, Sched>
+{
     fn finalization_status(&self) -> connection::finalization::Status {
         if self.inner.close_reason.is_some() && self.inner.streams.nr_active_streams() == 0 {
             connection::finalization::Status::Final
@@ -1044,7 +1202,7 @@ impl<S: StreamTrait> connection::finalization::Provider for AbstractStreamManage
 // due to being allowed to panic! when invariants are violated.
 
 #[cfg(test)]
-impl<S: StreamTrait> AbstractStreamManager<S> {
+impl<S: StreamTrait, Sched: Scheduler> AbstractStreamManager<S, Sched> {
     /// Executes the given function using the outgoing flow controller
     pub fn with_outgoing_connection_flow_controller<F, R>(&mut self, func: F) -> R
     where
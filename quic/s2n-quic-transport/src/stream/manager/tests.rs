@@ -40,7 +40,7 @@ use s2n_quic_core::{
         StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
-    stream::{ops, StreamId, StreamType},
+    stream::{ops, scheduler, Priority, StreamId, StreamType},
     time::{
         clock::testing as time,
         timer::{self, Provider as _},
@@ -60,9 +60,12 @@ struct MockStream {
     last_on_stream_data_blocked: Option<StreamDataBlocked>,
     last_max_stream_data: Option<MaxStreamData>,
     last_stop_sending: Option<StopSending>,
+    last_early_data_rejected: Option<VarInt>,
+    acquired_window: VarInt,
     read_waker_to_return: Option<Waker>,
     write_waker_to_return: Option<Waker>,
     interests: StreamInterests,
+    priority: Priority,
     on_connection_window_available_count: usize,
     on_connection_window_available_retrieve_window: u64,
     on_packet_ack_count: usize,
@@ -130,10 +133,13 @@ impl StreamTrait for MockStream {
             last_on_stream_data_blocked: None,
             last_max_stream_data: None,
             last_stop_sending: None,
+            last_early_data_rejected: None,
+            acquired_window: VarInt::from_u8(0),
             interests: StreamInterests {
                 retained: true,
                 ..Default::default()
             },
+            priority: Priority::default(),
             on_connection_window_available_count: 0,
             on_connection_window_available_retrieve_window: 0,
             on_packet_ack_count: 0,
@@ -168,6 +174,10 @@ impl StreamTrait for MockStream {
         self.config.stream_id
     }
 
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
     fn on_data(
         &mut self,
         frame: &StreamRef,
@@ -182,6 +192,14 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn on_early_data(
+        &mut self,
+        frame: &StreamRef,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        self.on_data(frame, events)
+    }
+
     fn on_stream_data_blocked(
         &mut self,
         frame: &StreamDataBlocked,
@@ -242,6 +260,14 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn acquired_connection_window(&self) -> VarInt {
+        self.acquired_window
+    }
+
+    fn on_early_data_rejected(&mut self, max_stream_data: VarInt) {
+        self.last_early_data_rejected = Some(max_stream_data);
+    }
+
     fn on_packet_ack<A: AckSet>(&mut self, _ack_set: &A, events: &mut StreamEvents) {
         self.on_packet_ack_count += 1;
         self.store_wakers(events);
@@ -301,6 +327,7 @@ impl StreamTrait for MockStream {
                 VarInt::new(self.on_connection_window_available_retrieve_window).unwrap(),
             );
         self.on_connection_window_available_retrieve_window -= Into::<u64>::into(acquired_window);
+        self.acquired_window += acquired_window;
     }
 
     fn poll_request(
@@ -323,6 +350,10 @@ impl StreamTrait for MockStream {
                 self.reset_count += 1;
             }
 
+            if let Some(priority) = tx.priority {
+                self.priority = priority;
+            }
+
             response.tx = Some(ops::tx::Response::default());
         }
 
@@ -406,12 +437,13 @@ fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager
         initial_local_limits,
         initial_peer_limits,
         DEFAULT_INITIAL_RTT,
+        Default::default(),
     )
 }
 
 /// Tries to open a new stream and returns an error if we're at the limit
-fn try_open(
-    manager: &mut AbstractStreamManager<MockStream>,
+fn try_open<Sched: Scheduler>(
+    manager: &mut AbstractStreamManager<MockStream, Sched>,
     stream_type: StreamType,
 ) -> Result<StreamId, connection::Error> {
     let (accept_waker, _accept_wake_counter) = new_count_waker();
@@ -652,6 +684,39 @@ fn max_data_replenishes_connection_flow_control_window() {
     }
 }
 
+#[test]
+fn peer_limits_update_replenishes_early_streams() {
+    // a client sending early data creates the manager with the remembered limits
+    let mut manager = create_stream_manager(endpoint::Type::Client);
+    let stream_id = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    let mut peer_limits = create_default_initial_flow_control_limits();
+    peer_limits.stream_limits.max_data_bidi_remote = VarInt::from_u32(8192);
+    peer_limits.max_data = VarInt::from_u32(128 * 1024);
+    peer_limits.max_open_remote_unidirectional_streams = VarInt::from_u32(256);
+
+    assert!(manager.on_peer_limits_update(peer_limits).is_ok());
+
+    manager.with_asserted_stream(stream_id, |stream| {
+        assert_eq!(
+            Some(VarInt::from_u32(8192)),
+            stream
+                .last_max_stream_data
+                .map(|frame| frame.maximum_stream_data)
+        );
+    });
+    assert_eq!(
+        VarInt::from_u32(128 * 1024),
+        manager.with_outgoing_connection_flow_controller(|ctrl| ctrl.total_window())
+    );
+    assert_eq!(
+        VarInt::from_u32(256),
+        manager.with_stream_controller(|ctrl| {
+            ctrl.available_local_initiated_stream_capacity(StreamType::Unidirectional)
+        })
+    );
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
 //= type=test
 //# MAX_STREAMS frames that do not increase the stream limit MUST be ignored.
@@ -1400,6 +1465,7 @@ fn asymmetric_stream_limits_remote_initiated() {
                     initial_local_limits,
                     initial_peer_limits,
                     DEFAULT_INITIAL_RTT,
+                    Default::default(),
                 );
 
                 // The peer opens streams up to the limit we have given them
@@ -1474,6 +1540,7 @@ fn asymmetric_stream_limits_local_initiated() {
                     initial_local_limits,
                     initial_peer_limits,
                     DEFAULT_INITIAL_RTT,
+                    Default::default(),
                 );
 
                 // Local endpoint opens streams up to the limit
@@ -1552,6 +1619,7 @@ fn rate_limited_stream_credits() {
                 initial_local_limits,
                 initial_peer_limits,
                 DEFAULT_INITIAL_RTT,
+                Default::default(),
             );
 
             let mut stream_ids =
@@ -3251,6 +3319,7 @@ fn stream_batching_test() {
             create_default_initial_flow_control_limits(),
             create_default_initial_flow_control_limits(),
             DEFAULT_INITIAL_RTT,
+            Default::default(),
         );
 
         // Create some open Streams
@@ -3317,3 +3386,149 @@ fn stream_batching_test() {
         }
     }
 }
+
+#[test]
+fn stream_priority_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let streams: Vec<_> = (0..4)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+
+    for stream_id in &streams {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 100;
+            stream.on_transmit_limit = Some(1);
+        });
+    }
+
+    assert_eq!(streams, manager.streams_waiting_for_transmission());
+
+    // More urgent streams are moved ahead of less urgent streams
+    let urgent = Priority::default().with_urgency(0).unwrap();
+    manager.with_asserted_stream(streams[2], |stream| stream.priority = urgent);
+    assert_eq!(
+        [streams[2], streams[0], streams[1], streams[3]],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    // Non-incremental streams are sent ahead of incremental streams of the same urgency
+    let (_wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
+    let mut request = ops::Request::default();
+    request.with_priority(Priority::default().with_incremental(false));
+    manager
+        .poll_request(
+            streams[3],
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            &mut request,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        [streams[2], streams[3], streams[0], streams[1]],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    write_context.frame_buffer.set_error_write_after_n_frames(1);
+
+    // The urgent stream keeps its position, even after exceeding the batch size
+    for _ in 0..4 {
+        let _ = manager.on_transmit(&mut write_context);
+        write_context.frame_buffer.flush();
+        write_context.frame_buffer.set_error_write_after_n_frames(1);
+
+        assert_eq!(
+            [streams[2], streams[3], streams[0], streams[1]],
+            *manager.streams_waiting_for_transmission()
+        );
+    }
+
+    manager.with_asserted_stream(streams[2], |stream| {
+        assert_eq!(stream.on_transmit_count, 4);
+    });
+    manager.with_asserted_stream(streams[0], |stream| {
+        assert_eq!(stream.on_transmit_count, 0);
+    });
+}
+
+#[test]
+fn stream_weighted_round_robin_test() {
+    let limits = ConnectionLimits::default()
+        .with_stream_batch_size(1)
+        .unwrap();
+
+    let mut manager = AbstractStreamManager::<MockStream, scheduler::WeightedRoundRobin>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+        DEFAULT_INITIAL_RTT,
+        Default::default(),
+    );
+
+    let a = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let b = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    // `a` is given twice the share of `b`
+    for (stream_id, urgency) in [(a, 6), (b, 7)] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 100;
+            stream.on_transmit_limit = Some(1);
+            stream.priority = Priority::default().with_urgency(urgency).unwrap();
+        });
+    }
+
+    // urgencies don't affect the order of the streams
+    assert_eq!([a, b], *manager.streams_waiting_for_transmission());
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    // The stream controller's frames are transmitted ahead of any stream data
+    write_context.frame_buffer.set_error_write_after_n_frames(1);
+    let _ = manager.on_transmit(&mut write_context);
+    write_context.frame_buffer.clear();
+
+    // `a` rotates to the back after using its quantum of 2, `b` after its quantum of 1
+    for (sender, expected) in [
+        (a, [a, b]),
+        (a, [a, b]),
+        (b, [b, a]),
+        (a, [a, b]),
+        (a, [a, b]),
+        (b, [b, a]),
+    ] {
+        let mut remaining = [0; 2];
+        for (stream_id, remaining) in [a, b].into_iter().zip(remaining.iter_mut()) {
+            manager.with_asserted_stream(stream_id, |stream| {
+                *remaining = stream.on_transmit_try_write_frames;
+            });
+        }
+
+        write_context.frame_buffer.set_error_write_after_n_frames(1);
+        let _ = manager.on_transmit(&mut write_context);
+        write_context.frame_buffer.flush();
+
+        assert_eq!(expected, *manager.streams_waiting_for_transmission());
+
+        for (stream_id, remaining) in [a, b].into_iter().zip(remaining) {
+            manager.with_asserted_stream(stream_id, |stream| {
+                let expected = if stream_id == sender { 1 } else { 0 };
+                assert_eq!(remaining - stream.on_transmit_try_write_frames, expected);
+            });
+        }
+    }
+}
@@ -18,7 +18,7 @@ use s2n_quic_core::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
         StopSending, StreamDataBlocked, StreamsBlocked,
     },
-    stream::{ops, StreamId, StreamType},
+    stream::{ops, scheduler, StreamId, StreamType},
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
     varint::VarInt,
@@ -32,6 +32,9 @@ pub trait Manager:
     + connection::finalization::Provider
     + core::fmt::Debug
 {
+    /// The scheduler which orders streams for transmission
+    type Scheduler: scheduler::Scheduler;
+
     /// Creates a new stream manager using the provided configuration parameters
     fn new(
         connection_limits: &connection::Limits,
@@ -39,6 +42,7 @@ pub trait Manager:
         initial_local_limits: InitialFlowControlLimits,
         initial_peer_limits: InitialFlowControlLimits,
         min_rtt: Duration,
+        scheduler: Self::Scheduler,
     ) -> Self;
 
     /// The number of bytes of forward progress the peer has made on incoming streams
@@ -47,6 +51,13 @@ pub trait Manager:
     /// The number of bytes of forward progress the local endpoint has made on outgoing streams
     fn outgoing_bytes_progressed(&self) -> VarInt;
 
+    /// The number of bytes the peer can still send before reaching the connection flow control limit
+    fn incoming_flow_control_credit(&self) -> VarInt;
+
+    /// The number of bytes the local endpoint can still send before reaching the connection flow
+    /// control limit
+    fn outgoing_flow_control_credit(&self) -> VarInt;
+
     /// Accepts the next incoming stream of a given type
     fn poll_accept(
         &mut self,
@@ -101,6 +112,9 @@ pub trait Manager:
     /// a stream
     fn on_data(&mut self, frame: &StreamRef) -> Result<(), transport::Error>;
 
+    /// This is called when a `STREAM` frame had been received in a 0-RTT packet
+    fn on_early_data(&mut self, frame: &StreamRef) -> Result<(), transport::Error>;
+
     /// This is called when a `DATA_BLOCKED` frame had been received
     fn on_data_blocked(&mut self, frame: DataBlocked) -> Result<(), transport::Error>;
 
@@ -130,6 +144,23 @@ pub trait Manager:
     /// This is called when a `MAX_STREAMS` frame had been received
     fn on_max_streams(&mut self, frame: &MaxStreams) -> Result<(), transport::Error>;
 
+    /// This is called when the peer's transport parameters from the handshake replace the
+    /// limits the manager was created with
+    ///
+    /// This happens when streams were opened with remembered limits to send early data. The
+    /// limits are only ever increased.
+    fn on_peer_limits_update(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error>;
+
+    /// This is called when the peer rejected early data and its transport parameters from the
+    /// handshake replace the limits the manager was created with
+    ///
+    /// Unlike [`Self::on_peer_limits_update`], the limits may be reduced. Streams that can't
+    /// retransmit the data sent in 0-RTT packets within the new limits are reset.
+    fn on_early_data_rejected(&mut self, peer_limits: InitialFlowControlLimits);
+
     // User APIs
 
     fn poll_request(
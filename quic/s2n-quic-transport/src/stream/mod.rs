@@ -23,11 +23,12 @@ pub use api::*;
 pub use controller::Controller;
 pub use manager::AbstractStreamManager;
 pub use manager_api::Manager;
-pub use s2n_quic_core::stream::limits::Limits;
+pub use s2n_quic_core::stream::{limits::Limits, scheduler};
 pub use stream_events::StreamEvents;
 pub use stream_impl::{StreamImpl, StreamTrait};
 
-pub type DefaultStreamManager = AbstractStreamManager<StreamImpl>;
+pub type DefaultStreamManager<Sched = scheduler::StrictPriority> =
+    AbstractStreamManager<StreamImpl, Sched>;
 
 #[cfg(test)]
 mod testing;
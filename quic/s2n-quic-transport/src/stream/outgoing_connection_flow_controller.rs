@@ -82,6 +82,15 @@ impl OutgoingConnectionFlowControllerImpl {
         // We now have more capacity from the peer so stop sending DATA_BLOCKED frames
         self.data_blocked_sync.stop_sync();
     }
+
+    pub fn on_early_data_rejected(&mut self, maximum_data: VarInt, acquired: VarInt) {
+        self.total_available_window = maximum_data;
+        self.available_window = maximum_data.saturating_sub(acquired);
+
+        if self.available_window > VarInt::from_u8(0) {
+            self.data_blocked_sync.stop_sync();
+        }
+    }
 }
 
 /// Writes the `DATA_BLOCKED` frames.
@@ -154,6 +163,18 @@ impl OutgoingConnectionFlowController {
         self.inner.borrow_mut().on_max_data(frame)
     }
 
+    /// This method is called when the peer rejected early data and the limit from its transport
+    /// parameters replaces the one the window was created with.
+    ///
+    /// Unlike [`Self::on_max_data`], the window may be reduced. `acquired` is the part of the
+    /// window held by `Stream`s which still send their data, since the peer didn't process any
+    /// of the early data.
+    pub fn on_early_data_rejected(&mut self, maximum_data: VarInt, acquired: VarInt) {
+        self.inner
+            .borrow_mut()
+            .on_early_data_rejected(maximum_data, acquired)
+    }
+
     /// This method is called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner
@@ -18,7 +18,7 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    buffer::{self, Reassembler},
+    buffer::{self, reader::storage::Infallible as _, writer::Storage as _, Reassembler},
     frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
     stream::{ops, StreamId},
@@ -347,6 +347,8 @@ pub struct ReceiveStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// Set if any of the data was received in 0-RTT packets
+    early_data: bool,
 }
 
 impl ReceiveStream {
@@ -376,6 +378,7 @@ impl ReceiveStream {
             read_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            early_data: false,
         };
 
         if is_closed {
@@ -388,6 +391,17 @@ impl ReceiveStream {
 
     // These functions are called from the packet delivery thread
 
+    /// Is called when a `STREAM` frame was received in a 0-RTT packet
+    pub fn on_early_data(
+        &mut self,
+        frame: &StreamRef,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.on_data(frame, events)?;
+        self.early_data = true;
+        Ok(())
+    }
+
     pub fn on_data(
         &mut self,
         frame: &StreamRef,
@@ -739,7 +753,10 @@ impl ReceiveStream {
         request: &mut ops::rx::Request,
         context: Option<&Context>,
     ) -> Result<ops::rx::Response, StreamError> {
-        let mut response = ops::rx::Response::default();
+        let mut response = ops::rx::Response {
+            early_data: self.early_data,
+            ..Default::default()
+        };
 
         if let Some(error_code) = request.stop_sending {
             let error = StreamError::stream_reset(error_code);
@@ -876,6 +893,27 @@ impl ReceiveStream {
                         break;
                     }
                 }
+            } else if let Some(buffer) = request.buffer.as_mut().filter(|buffer| buffer.len() > 0) {
+                // copy the data directly out of the receive buffer, without splitting it into chunks
+                let mut buffer = buffer.track_write();
+                self.receive_buffer
+                    .infallible_copy_into(&mut buffer.with_write_limit(*high_watermark));
+                let data_len = buffer.written_len();
+
+                if data_len > 0 {
+                    // Release the flow control window for the consumed data
+                    self.flow_controller.release_window(
+                        VarInt::try_from(data_len)
+                            .expect("data len should always be less than maximum VarInt"),
+                    );
+                    *low_watermark = (*low_watermark).saturating_sub(data_len);
+                    *high_watermark = (*high_watermark).saturating_sub(data_len);
+
+                    response.bytes.consumed += data_len;
+                } else {
+                    // wake the request if we didn't consume anything
+                    should_wake = true;
+                }
             }
         } else {
             // notify when we have at least the requested watermark
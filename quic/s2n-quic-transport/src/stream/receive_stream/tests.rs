@@ -7,7 +7,7 @@ use crate::{
     transmission,
     transmission::interest::Provider,
 };
-use bytes::Bytes;
+use bytes::{buf::UninitSlice, Bytes};
 use core::{convert::TryFrom, task::Poll};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
@@ -2350,6 +2350,7 @@ fn receive_multiple_chunks_and_finishing_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Finished,
+                early_data: false,
             }),
             ..Default::default()
         }),
@@ -2401,6 +2402,7 @@ fn receive_low_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                early_data: false,
             }),
             ..Default::default()
         })),
@@ -2452,6 +2454,7 @@ fn receive_low_watermark_with_data_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                early_data: false,
             }),
             ..Default::default()
         })),
@@ -2493,6 +2496,7 @@ fn receive_high_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                early_data: false,
             }),
             ..Default::default()
         })),
@@ -2517,6 +2521,7 @@ fn receive_high_watermark_test() {
                 },
                 will_wake: false,
                 status: ops::Status::Open,
+                early_data: false,
             }),
             ..Default::default()
         })),
@@ -2548,3 +2553,81 @@ fn receiving_into_non_empty_buffers_returns_an_error() {
         "data should not be lost when returning an error"
     );
 }
+
+#[test]
+fn receive_early_data_test() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let is_early_data = |test_env: &mut TestEnvironment| {
+        test_env
+            .run_request(ops::Request::default().receive(&mut []), false)
+            .unwrap()
+            .rx
+            .unwrap()
+            .early_data
+    };
+
+    assert!(!is_early_data(&mut test_env));
+
+    let mut events = StreamEvents::new();
+    test_env
+        .stream
+        .on_early_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u8(0),
+                &[0, 1, 2, 3],
+                false,
+            ),
+            &mut events,
+        )
+        .unwrap();
+    assert!(is_early_data(&mut test_env));
+
+    // the stream still reports early data after receiving data in 1-RTT packets
+    test_env.feed_data(VarInt::from_u8(4), 100);
+    assert!(is_early_data(&mut test_env));
+}
+
+#[test]
+fn receive_into_buffer_test() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let mut events = StreamEvents::new();
+    let data = gen_pattern_test_data(VarInt::from_u8(0), 100);
+    test_env
+        .stream
+        .on_data(
+            &stream_data(test_env.stream.stream_id, VarInt::from_u8(0), &data, true),
+            &mut events,
+        )
+        .unwrap();
+
+    let mut buffer = [0u8; 60];
+    let receive_into = |test_env: &mut TestEnvironment, buffer: &mut [u8]| {
+        test_env
+            .run_request(
+                ops::Request::default().receive_into(UninitSlice::new(buffer)),
+                true,
+            )
+            .unwrap()
+            .rx
+            .unwrap()
+    };
+
+    // only the data that fits into the buffer is copied
+    let response = receive_into(&mut test_env, &mut buffer);
+    assert_eq!(response.bytes.consumed, 60);
+    assert_eq!(response.chunks.consumed, 0);
+    assert!(!response.status.is_finished());
+    assert_eq!(&buffer[..], &data[..60]);
+
+    let response = receive_into(&mut test_env, &mut buffer);
+    assert_eq!(response.bytes.consumed, 40);
+    assert_eq!(&buffer[..40], &data[60..]);
+
+    // the stream is finished once all of the data has been received
+    let response = receive_into(&mut test_env, &mut buffer);
+    assert_eq!(response.bytes.consumed, 0);
+    assert!(response.status.is_finished());
+}
@@ -16,7 +16,7 @@ use crate::{
     transmission,
     transmission::interest::Provider as _,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::{
     convert::TryFrom,
     task::{Context, Waker},
@@ -26,7 +26,7 @@ use s2n_quic_core::{
     ack, application,
     frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
-    stream::{ops, StreamId},
+    stream::{ops, Priority, StreamId},
     time::{timer, Timestamp},
     transport,
     varint::VarInt,
@@ -232,6 +232,20 @@ impl StreamFlowController {
         }
     }
 
+    /// Replaces the `MAXIMUM_STREAM_DATA` value after the peer rejected early data
+    ///
+    /// Unlike [`Self::set_max_stream_data`], the limit may be reduced as long as the stream
+    /// didn't acquire a window past the new limit.
+    pub fn reset_max_stream_data(&mut self, max_stream_data: VarInt) {
+        debug_assert!(self.acquired_connection_flow_controller_window <= max_stream_data);
+
+        if max_stream_data > self.max_stream_data {
+            self.set_max_stream_data(max_stream_data);
+        } else {
+            self.max_stream_data = max_stream_data;
+        }
+    }
+
     /// Tries to acquire as much window from the connection flow control window
     /// as possible.
     pub fn try_acquire_connection_window(&mut self) {
@@ -457,8 +471,18 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// The priority of the stream when scheduling data for transmission
+    priority: Priority,
+    /// The buffer that data copied from the application is written into
+    ///
+    /// Each copied chunk is split off the buffer and shares its allocation, which avoids
+    /// allocating for each individual write.
+    copy_buffer: BytesMut,
 }
 
+/// The minimum size of the allocations for data copied from the application
+const MIN_COPY_BUFFER_LEN: usize = 4096;
+
 impl SendStream {
     pub fn new(
         connection_flow_controller: OutgoingConnectionFlowController,
@@ -485,6 +509,8 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            priority: Priority::default(),
+            copy_buffer: BytesMut::new(),
         };
 
         if is_closed {
@@ -534,6 +560,23 @@ impl SendStream {
         Ok(())
     }
 
+    /// Returns the connection flow control window which was acquired for sending data
+    pub fn acquired_connection_window(&self) -> VarInt {
+        self.data_sender
+            .flow_controller()
+            .acquired_connection_flow_controller_window()
+    }
+
+    /// This is called when the peer rejected early data and the limit from its transport
+    /// parameters replaces the one the stream was opened with
+    pub fn on_early_data_rejected(&mut self, max_stream_data: VarInt) {
+        if let SendStreamState::Sending = self.state {
+            self.data_sender
+                .flow_controller_mut()
+                .reset_max_stream_data(max_stream_data);
+        }
+    }
+
     /// This is called when a `STOP_SENDING` frame had been received for
     /// this stream
     pub fn on_stop_sending(
@@ -755,6 +798,10 @@ impl SendStream {
             self.detach();
         }
 
+        if let Some(priority) = request.priority {
+            self.priority = priority;
+        }
+
         macro_rules! store_waker {
             ($should_flush:expr) => {
                 // Store the waker, in order to be able to wakeup the caller
@@ -835,9 +882,32 @@ impl SendStream {
                 self.data_sender
                     .push(core::mem::replace(chunk, Bytes::new()));
             }
+        } else if let Some(data) = request.data.as_mut().filter(|data| !data.is_empty()) {
+            self.validate_push(data.len())?;
+
+            if !self.can_push() {
+                store_waker!(false);
+
+                // no more progress can be made on the operation
+                return Ok(response);
+            }
+
+            // only copy what the transmission buffer can currently hold
+            let len = data.len().min(self.data_sender.available_buffer_space());
+            let (chunk, remaining) = data.split_at(len);
+            *data = remaining;
+
+            response.bytes.consumed += len;
+
+            // only allocate once the current buffer is exhausted
+            if self.copy_buffer.capacity() < len {
+                self.copy_buffer.reserve(len.max(MIN_COPY_BUFFER_LEN));
+            }
+            self.copy_buffer.extend_from_slice(chunk);
+            self.data_sender.push(self.copy_buffer.split().freeze());
         } else if !request.finish && !request.flush && context.is_some() {
-            // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
-            // the caller is only interested in notifications of state changes.
+            // if `chunks` and `data` are `None` or empty and we're not ending or flushing the
+            // stream, the caller is only interested in notifications of state changes.
 
             // test a potential push of 1 byte
             self.validate_push(1)?;
@@ -903,6 +973,12 @@ impl SendStream {
         Ok(response)
     }
 
+    /// Returns the priority of the stream when scheduling data for transmission
+    #[inline]
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
     fn detach(&mut self) {
         self.detached = true;
         self.write_waiter = None;
@@ -2854,3 +2854,100 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn can_send_from_slice() {
+    let max_send_buffer_size = 3000;
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let data = gen_pattern_test_data(VarInt::from_u8(0), 4000);
+
+    // only the data that fits into the send buffer is copied
+    let response = test_env
+        .run_request(ops::Request::default().send_from(&data), true)
+        .unwrap();
+    let tx = response.tx().unwrap();
+    assert_eq!(tx.bytes.consumed, max_send_buffer_size);
+    assert_eq!(tx.chunks.consumed, 0);
+
+    // the send buffer is full, so nothing else can be copied
+    let response = test_env
+        .run_request(
+            ops::Request::default().send_from(&data[max_send_buffer_size..]),
+            true,
+        )
+        .unwrap();
+    assert_eq!(response.tx().unwrap().bytes.consumed, 0);
+    assert!(response.is_pending());
+
+    let mut offset = 0;
+    let mut idx = 0;
+    while let Some(mut frame) = test_env.transmit() {
+        if let Frame::Stream(stream) = frame.as_frame() {
+            assert_eq!(
+                stream.data.as_less_safe_slice(),
+                &data[offset..][..stream.data.len()]
+            );
+            offset += stream.data.len();
+            test_env.ack_packet(pn(idx), ExpectWakeup(None));
+            idx += 1;
+        } else {
+            panic!("invalid frame");
+        }
+    }
+    assert_eq!(offset, max_send_buffer_size);
+
+    // the remaining data can be sent once the buffer has been acknowledged
+    let response = test_env
+        .run_request(
+            ops::Request::default().send_from(&data[max_send_buffer_size..]),
+            true,
+        )
+        .unwrap();
+    assert_eq!(
+        response.tx().unwrap().bytes.consumed,
+        data.len() - max_send_buffer_size
+    );
+}
+
+#[test]
+fn send_from_reuses_copy_buffer() {
+    let test_env_config = TestEnvironmentConfig {
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let data = gen_pattern_test_data(VarInt::from_u8(0), 1000);
+
+    // small writes are copied into the same allocation
+    for chunk in data.chunks(100) {
+        let response = test_env
+            .run_request(ops::Request::default().send_from(chunk), true)
+            .unwrap();
+        assert_eq!(response.tx().unwrap().bytes.consumed, chunk.len());
+    }
+    assert_eq!(
+        test_env.stream.send_stream.copy_buffer.capacity(),
+        MIN_COPY_BUFFER_LEN - data.len()
+    );
+
+    let mut offset = 0;
+    while let Some(mut frame) = test_env.transmit() {
+        if let Frame::Stream(stream) = frame.as_frame() {
+            assert_eq!(
+                stream.data.as_less_safe_slice(),
+                &data[offset..][..stream.data.len()]
+            );
+            offset += stream.data.len();
+        } else {
+            panic!("invalid frame");
+        }
+    }
+    assert_eq!(offset, data.len());
+}
@@ -13,11 +13,21 @@ use crate::{
     transmission,
 };
 use alloc::rc::Rc;
-use core::{cell::RefCell, ops::Deref};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    ops::Deref,
+};
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
-use s2n_quic_core::{stream::StreamId, time::timer};
+use s2n_quic_core::{
+    stream::{
+        scheduler::{Scheduler, StreamInfo},
+        Priority, StreamId,
+    },
+    time::timer,
+};
 
 // Intrusive list adapter for managing the list of `done` streams
 intrusive_adapter!(DoneStreamsAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -65,6 +75,13 @@ intrusive_adapter!(StreamTreeAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
 struct StreamNode<S> {
     /// This contains the actual implementation of the `Stream`
     inner: RefCell<S>,
+    /// The ID of the `Stream`
+    stream_id: StreamId,
+    /// The last priority reported by the `Stream`
+    ///
+    /// This is stored outside of `inner`, since the priorities of other streams
+    /// need to be queried while a `Stream` is borrowed.
+    priority: Cell<Priority>,
     /// Allows the Stream to be part of the `stream_map` collection
     tree_link: RBTreeLink,
     /// Allows the Stream to be part of the `done_streams` collection
@@ -81,10 +98,12 @@ struct StreamNode<S> {
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
 }
 
-impl<S> StreamNode<S> {
+impl<S: StreamTrait> StreamNode<S> {
     /// Creates a new `StreamNode` which wraps the given Stream implementation of type `S`
     pub fn new(stream_impl: S) -> StreamNode<S> {
         StreamNode {
+            stream_id: stream_impl.stream_id(),
+            priority: Cell::new(stream_impl.priority()),
             inner: RefCell::new(stream_impl),
             tree_link: RBTreeLink::new(),
             done_streams_link: LinkedListLink::new(),
@@ -97,13 +116,20 @@ impl<S> StreamNode<S> {
     }
 }
 
+impl<S> StreamNode<S> {
+    /// Returns the information passed to the `Scheduler` for the `Stream`
+    fn info(&self) -> StreamInfo {
+        StreamInfo::new(self.stream_id, self.priority.get())
+    }
+}
+
 // This is required to build an intrusive `RBTree` of `StreamNode`s which
 // utilizes `StreamId`s as a key.
 impl<'a, S: StreamTrait> KeyAdapter<'a> for StreamTreeAdapter<S> {
     type Key = StreamId;
 
     fn get_key(&self, x: &'a StreamNode<S>) -> StreamId {
-        x.inner.borrow().stream_id()
+        x.stream_id
     }
 }
 
@@ -127,7 +153,7 @@ unsafe fn stream_node_rc_from_ref<S>(stream_node: &StreamNode<S>) -> Rc<StreamNo
 ///
 /// A Stream can be a member in any of those, in addition to being a member of
 /// `StreamContainer::stream_map`.
-struct InterestLists<S> {
+struct InterestLists<S, Sched> {
     /// Streams which have been finalized
     done_streams: LinkedList<DoneStreamsAdapter<S>>,
     /// Streams which are waiting for packet acknowledgements and
@@ -145,13 +171,32 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
-    transmission_counter: u8,
-    retransmission_counter: u8,
+    transmission_counter: u16,
+    retransmission_counter: u16,
     transmission_limit: u8,
+    /// Orders the streams in `waiting_for_transmission` and `waiting_for_retransmission`
+    scheduler: Sched,
 }
 
-impl<S: StreamTrait> InterestLists<S> {
-    fn new(connection_limits: &connection::Limits) -> Self {
+/// Inserts a node into a transmission list, behind all of the nodes which the
+/// scheduler doesn't order after it
+macro_rules! insert_scheduled {
+    ($list:expr, $scheduler:expr, $node:expr) => {{
+        let info = $node.info();
+        let mut cursor = $list.back_mut();
+        while let Some(other) = cursor.get() {
+            if $scheduler.cmp(&other.info(), &info) != Ordering::Greater {
+                break;
+            }
+            cursor.move_prev();
+        }
+        // If the cursor moved past the front of the list, this inserts at the front
+        cursor.insert_after($node);
+    }};
+}
+
+impl<S: StreamTrait, Sched: Scheduler> InterestLists<S, Sched> {
+    fn new(connection_limits: &connection::Limits, scheduler: Sched) -> Self {
         Self {
             done_streams: LinkedList::new(DoneStreamsAdapter::new()),
             waiting_for_frame_delivery: LinkedList::new(WaitingForFrameDeliveryAdapter::new()),
@@ -166,11 +211,17 @@ impl<S: StreamTrait> InterestLists<S> {
             transmission_counter: 0,
             retransmission_counter: 0,
             transmission_limit: connection_limits.stream_batch_size(),
+            scheduler,
         }
     }
 
     /// Update all interest lists based on latest interest reported by a Node
-    fn update_interests(&mut self, node: &Rc<StreamNode<S>>, interests: StreamInterests) -> bool {
+    fn update_interests(
+        &mut self,
+        node: &Rc<StreamNode<S>>,
+        interests: StreamInterests,
+        priority: Priority,
+    ) -> bool {
         // Note that all comparisons start by checking whether the stream is
         // already part of the given list. This is required in order for the
         // following operation to be safe. Inserting an element in a list while
@@ -198,17 +249,39 @@ impl<S: StreamTrait> InterestLists<S> {
             };
         }
 
+        let priority_changed = node.priority.replace(priority) != priority;
+
+        macro_rules! sync_scheduled_interests {
+            ($interest:expr, $link_name:ident, $list_name:ident) => {
+                // Streams which changed priority need to be moved to their new position
+                if node.$link_name.is_linked() && (!$interest || priority_changed) {
+                    // Safety: We know that the node is only ever part of this list.
+                    // While elements are in temporary lists, they always get unlinked
+                    // from those temporary lists while their interest is updated.
+                    let mut cursor = unsafe {
+                        self.$list_name
+                            .cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
+                    };
+                    cursor.remove();
+                }
+                if $interest && !node.$link_name.is_linked() {
+                    insert_scheduled!(self.$list_name, self.scheduler, node.clone());
+                }
+                debug_assert_eq!($interest, node.$link_name.is_linked());
+            };
+        }
+
         sync_interests!(
             interests.delivery_notifications,
             waiting_for_frame_delivery_link,
             waiting_for_frame_delivery
         );
-        sync_interests!(
+        sync_scheduled_interests!(
             matches!(interests.transmission, transmission::Interest::NewData),
             waiting_for_transmission_link,
             waiting_for_transmission
         );
-        sync_interests!(
+        sync_scheduled_interests!(
             matches!(interests.transmission, transmission::Interest::LostData),
             waiting_for_retransmission_link,
             waiting_for_retransmission
@@ -248,17 +321,17 @@ impl<S: StreamTrait> InterestLists<S> {
 ///   be queried for its interests again.
 /// - There exist a variety of iteration methods, which allow to iterate over
 ///   all or a subset of streams in each interest list.
-pub struct StreamContainer<S> {
+pub struct StreamContainer<S, Sched> {
     /// Streams organized as a tree, for lookup by Stream ID
     stream_map: RBTree<StreamTreeAdapter<S>>,
     /// The number of streams which are tracked by the Container.
     /// This needs to be in-sync with Streams that get inserted into `stream_map`.
     nr_active_streams: usize,
     /// Additional interest lists in which Streams will be placed dynamically
-    interest_lists: InterestLists<S>,
+    interest_lists: InterestLists<S, Sched>,
 }
 
-impl<S> core::fmt::Debug for StreamContainer<S> {
+impl<S, Sched> core::fmt::Debug for StreamContainer<S, Sched> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_struct("StreamContainer")
             .field("nr_active_streams", &self.nr_active_streams)
@@ -271,13 +344,14 @@ macro_rules! iterate_uninterruptible {
         for stream in $sel.interest_lists.$list_name.take() {
             debug_assert!(!stream.$link_name.is_linked());
 
-            let interests = {
+            let (interests, priority) = {
                 let mut mut_stream = stream.inner.borrow_mut();
                 $func(&mut *mut_stream);
-                mut_stream.get_stream_interests()
+                (mut_stream.get_stream_interests(), mut_stream.priority())
             };
 
-            $sel.interest_lists.update_interests(&stream, interests);
+            $sel.interest_lists
+                .update_interests(&stream, interests, priority);
         }
 
         if !$sel.interest_lists.done_streams.is_empty() {
@@ -301,7 +375,8 @@ macro_rules! iterate_interruptible {
 
             // Update the interests after the interaction
             let interests = mut_stream.get_stream_interests();
-            $sel.interest_lists.update_interests(&stream, interests);
+            $sel.interest_lists
+                .update_interests(&stream, interests, mut_stream.priority());
 
             match result {
                 StreamContainerIterationResult::BreakAndInsertAtBack => {
@@ -331,11 +406,34 @@ macro_rules! send_on_transmission_list {
         $counter:ident,
         $interest_type:pat,
     ) => {
-        // Head node gets pushed to the back of the list if it has run out of sending credits
-        if $sel.interest_lists.$counter >= $sel.interest_lists.transmission_limit {
-            if let Some(node) = $sel.interest_lists.$list_name.pop_front() {
-                $sel.interest_lists.$list_name.push_back(node);
-                $sel.interest_lists.$counter = 0;
+        // Head node gets pushed behind the nodes of the same order if it has run out of
+        // sending credits
+        let head_info = $sel
+            .interest_lists
+            .$list_name
+            .front()
+            .get()
+            .map(|node| node.info());
+        if let Some(head_info) = head_info {
+            let quantum = $sel
+                .interest_lists
+                .scheduler
+                .quantum(&head_info, $sel.interest_lists.transmission_limit);
+
+            if quantum.map_or(false, |quantum| $sel.interest_lists.$counter >= quantum) {
+                let lists = &mut $sel.interest_lists;
+                if let Some(node) = lists.$list_name.pop_front() {
+                    let mut cursor = lists.$list_name.front_mut();
+                    while let Some(other) = cursor.get() {
+                        if lists.scheduler.cmp(&other.info(), &head_info) == Ordering::Greater {
+                            break;
+                        }
+                        cursor.move_next();
+                    }
+                    // If the cursor moved past the back of the list, this inserts at the back
+                    cursor.insert_before(node);
+                    lists.$counter = 0;
+                }
             }
         }
 
@@ -353,7 +451,8 @@ macro_rules! send_on_transmission_list {
 
             // Update the interests after the interaction
             let interests = mut_stream.get_stream_interests();
-            $sel.interest_lists.update_interests(&stream, interests);
+            $sel.interest_lists
+                .update_interests(&stream, interests, mut_stream.priority());
 
             if head_node {
                 if matches!(result, StreamContainerIterationResult::Continue) {
@@ -384,13 +483,13 @@ macro_rules! send_on_transmission_list {
     };
 }
 
-impl<S: StreamTrait> StreamContainer<S> {
+impl<S: StreamTrait, Sched: Scheduler> StreamContainer<S, Sched> {
     /// Creates a new `StreamContainer`
-    pub fn new(connection_limits: &connection::Limits) -> Self {
+    pub fn new(connection_limits: &connection::Limits, scheduler: Sched) -> Self {
         Self {
             stream_map: RBTree::new(StreamTreeAdapter::new()),
             nr_active_streams: 0,
-            interest_lists: InterestLists::new(connection_limits),
+            interest_lists: InterestLists::new(connection_limits, scheduler),
         }
     }
 
@@ -399,10 +498,12 @@ impl<S: StreamTrait> StreamContainer<S> {
         // Even though it likely might have none, it seems like it
         // would be better to avoid future bugs
         let interests = stream.get_stream_interests();
+        let priority = stream.priority();
 
         let new_stream = Rc::new(StreamNode::new(stream));
 
-        self.interest_lists.update_interests(&new_stream, interests);
+        self.interest_lists
+            .update_interests(&new_stream, interests, priority);
 
         self.stream_map.insert(new_stream);
         self.nr_active_streams += 1;
@@ -442,6 +543,7 @@ impl<S: StreamTrait> StreamContainer<S> {
         let node_ptr: Rc<StreamNode<S>>;
         let result: R;
         let interests;
+        let priority;
 
         // This block is required since we mutably borrow `self` inside the
         // block in order to obtain a Stream reference and to executing the
@@ -461,11 +563,15 @@ impl<S: StreamTrait> StreamContainer<S> {
             let stream: &mut S = &mut node.inner.borrow_mut();
             result = func(stream);
             interests = stream.get_stream_interests();
+            priority = stream.priority();
         }
 
         // Update the interest lists after the interactions and then remove
         // all finalized streams
-        if self.interest_lists.update_interests(&node_ptr, interests) {
+        if self
+            .interest_lists
+            .update_interests(&node_ptr, interests, priority)
+        {
             self.finalize_done_streams(controller);
         }
 
@@ -684,7 +790,7 @@ impl<S: StreamTrait> StreamContainer<S> {
             // stores it's nodes as `Rc`
             let stream_node_rc = unsafe { stream_node_rc_from_ref(stream) };
             self.interest_lists
-                .update_interests(&stream_node_rc, interests);
+                .update_interests(&stream_node_rc, interests, mut_stream.priority());
         }
 
         if !self.interest_lists.done_streams.is_empty() {
@@ -701,7 +807,7 @@ impl<S: StreamTrait> StreamContainer<S> {
     }
 }
 
-impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
+impl<S: StreamTrait, Sched> timer::Provider for StreamContainer<S, Sched> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         // TODO denormalize this into a single value
@@ -716,7 +822,7 @@ impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
     }
 }
 
-impl<S: StreamTrait> transmission::interest::Provider for StreamContainer<S> {
+impl<S: StreamTrait, Sched> transmission::interest::Provider for StreamContainer<S, Sched> {
     #[inline]
     fn transmission_interest<Q: transmission::interest::Query>(
         &self,
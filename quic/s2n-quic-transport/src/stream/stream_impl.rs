@@ -17,7 +17,7 @@ use core::{task::Context, time::Duration};
 use s2n_quic_core::{
     ack, endpoint,
     frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
-    stream::{ops, StreamId},
+    stream::{ops, Priority, StreamId},
     time::{timer, Timestamp},
     transport,
     varint::VarInt,
@@ -52,6 +52,9 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// Returns the Streams ID
     fn stream_id(&self) -> StreamId;
 
+    /// Returns the priority of the Stream when scheduling data for transmission
+    fn priority(&self) -> Priority;
+
     // These functions are called from the packet delivery thread
 
     /// This is called when a `STREAM_DATA` frame had been received for
@@ -62,6 +65,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// This is called when a `STREAM` frame had been received in a 0-RTT packet for
+    /// this stream
+    fn on_early_data(
+        &mut self,
+        frame: &StreamRef,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `STREAM_DATA_BLOCKED` frame had been received for
     /// this stream
     fn on_stream_data_blocked(
@@ -94,6 +105,13 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// Returns the connection flow control window which was acquired for sending data
+    fn acquired_connection_window(&self) -> VarInt;
+
+    /// This is called when the peer rejected early data and the limit from its transport
+    /// parameters replaces the one the stream was opened with
+    fn on_early_data_rejected(&mut self, max_stream_data: VarInt);
+
     /// This method gets called when a packet delivery got acknowledged
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents);
 
@@ -220,6 +238,11 @@ impl StreamTrait for StreamImpl {
         self.stream_id
     }
 
+    #[inline]
+    fn priority(&self) -> Priority {
+        self.send_stream.priority()
+    }
+
     // These functions are called from the packet delivery thread
 
     #[inline]
@@ -231,6 +254,15 @@ impl StreamTrait for StreamImpl {
         self.receive_stream.on_data(frame, events)
     }
 
+    #[inline]
+    fn on_early_data(
+        &mut self,
+        frame: &StreamRef,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_early_data(frame, events)
+    }
+
     #[inline]
     fn on_stream_data_blocked(
         &mut self,
@@ -294,6 +326,16 @@ impl StreamTrait for StreamImpl {
             .update_blocked_sync_period(blocked_sync_period);
     }
 
+    #[inline]
+    fn acquired_connection_window(&self) -> VarInt {
+        self.send_stream.acquired_connection_window()
+    }
+
+    #[inline]
+    fn on_early_data_rejected(&mut self, max_stream_data: VarInt) {
+        self.send_stream.on_early_data_rejected(max_stream_data)
+    }
+
     #[inline]
     fn on_timeout(&mut self, now: Timestamp) {
         self.send_stream.on_timeout(now)
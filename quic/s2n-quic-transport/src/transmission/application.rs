@@ -2,12 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencyManager, AckManager},
     connection,
     contexts::WriteContext,
     dc, endpoint, path,
     path::mtu,
-    recovery,
+    recovery::{self, CongestionController as _},
     space::{datagram, CryptoStream, HandshakeStatus},
     stream::Manager as _,
     sync::{flag, flag::Ping},
@@ -20,6 +20,8 @@ pub enum Payload<'a, Config: endpoint::Config> {
     MtuProbe(MtuProbe<'a>),
     /// For use on non-active paths where only path validation frames are sent.
     PathValidationOnly(PathValidationOnly<'a, Config>),
+    /// For use by clients sending early data before the handshake completes.
+    ZeroRtt(ZeroRtt<'a, Config>),
 }
 
 impl<'a, Config: endpoint::Config> Payload<'a, Config> {
@@ -31,6 +33,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         local_id_registry: &'a mut connection::LocalIdRegistry,
         transmission_mode: transmission::Mode,
         ack_manager: &'a mut AckManager,
+        ack_frequency_manager: &'a mut AckFrequencyManager,
         handshake_status: &'a mut HandshakeStatus,
         ping: &'a mut flag::Ping,
         stream_manager: &'a mut Config::StreamManager,
@@ -47,6 +50,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
             Mode::LossRecoveryProbing | Mode::Normal => {
                 transmission::application::Payload::Normal(Normal {
                     ack_manager,
+                    ack_frequency_manager,
                     handshake_status,
                     ping,
                     stream_manager,
@@ -69,6 +73,21 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
             }
         }
     }
+
+    /// Constructs a transmission::application::Payload for a 0-RTT packet
+    pub fn zero_rtt(
+        ping: &'a mut flag::Ping,
+        stream_manager: &'a mut Config::StreamManager,
+        recovery_manager: &'a mut recovery::Manager<Config>,
+        datagram_manager: &'a mut datagram::Manager<Config>,
+    ) -> Self {
+        transmission::application::Payload::ZeroRtt(ZeroRtt {
+            ping,
+            stream_manager,
+            recovery_manager,
+            datagram_manager,
+        })
+    }
 }
 
 impl<Config: endpoint::Config> super::Payload for Payload<'_, Config> {
@@ -82,6 +101,7 @@ impl<Config: endpoint::Config> super::Payload for Payload<'_, Config> {
             Payload::Normal(inner) => inner.on_transmit(context),
             Payload::MtuProbe(inner) => inner.on_transmit(context),
             Payload::PathValidationOnly(inner) => inner.on_transmit(context),
+            Payload::ZeroRtt(inner) => inner.on_transmit(context),
         }
     }
 }
@@ -95,12 +115,14 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Payload<'_,
             Payload::Normal(inner) => inner.transmission_interest(query),
             Payload::MtuProbe(inner) => inner.transmission_interest(query),
             Payload::PathValidationOnly(inner) => inner.transmission_interest(query),
+            Payload::ZeroRtt(inner) => inner.transmission_interest(query),
         }
     }
 }
 
 pub struct Normal<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    ack_frequency_manager: &'a mut AckFrequencyManager,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut Ping,
     stream_manager: &'a mut Config::StreamManager,
@@ -178,6 +200,16 @@ impl<Config: endpoint::Config> Normal<'_, Config> {
         // complete as soon as possible
         self.dc_manager.on_transmit(context);
 
+        // send ACK_FREQUENCY frames when the congestion controller changes its
+        // ACK-eliciting threshold
+        let ack_eliciting_threshold = self
+            .path_manager
+            .active_path()
+            .congestion_controller
+            .ack_eliciting_threshold();
+        self.ack_frequency_manager
+            .on_transmit(ack_eliciting_threshold, context);
+
         let _ = self.crypto_stream.tx.on_transmit((), context);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2
@@ -198,6 +230,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Normal<'_, C
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        self.ack_frequency_manager.transmission_interest(query)?;
         self.handshake_status.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
@@ -255,3 +288,42 @@ impl<Config: endpoint::Config> transmission::interest::Provider for PathValidati
         self.path.transmission_interest(query)
     }
 }
+
+pub struct ZeroRtt<'a, Config: endpoint::Config> {
+    ping: &'a mut Ping,
+    stream_manager: &'a mut Config::StreamManager,
+    recovery_manager: &'a mut recovery::Manager<Config>,
+    datagram_manager: &'a mut datagram::Manager<Config>,
+}
+
+impl<Config: endpoint::Config> ZeroRtt<'_, Config> {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-12.5
+    //# Note that it is not possible to send the following frames in 0-RTT
+    //# packets for various reasons: ACK, CRYPTO, HANDSHAKE_DONE, NEW_TOKEN,
+    //# PATH_RESPONSE, and RETIRE_CONNECTION_ID.
+    fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        let can_transmit = context.transmission_constraint().can_transmit()
+            || context.transmission_constraint().can_retransmit();
+
+        if can_transmit {
+            self.datagram_manager
+                .on_transmit(context, self.stream_manager, false);
+            let _ = self.stream_manager.on_transmit(context);
+            self.recovery_manager.on_transmit(context);
+            let _ = self.ping.on_transmit(context);
+        }
+    }
+}
+
+impl<Config: endpoint::Config> transmission::interest::Provider for ZeroRtt<'_, Config> {
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        self.stream_manager.transmission_interest(query)?;
+        self.datagram_manager.transmission_interest(query)?;
+        self.recovery_manager.transmission_interest(query)?;
+        self.ping.transmission_interest(query)?;
+        Ok(())
+    }
+}
@@ -166,6 +166,35 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC version provider for the [`Client`]
+        ///
+        /// # Examples
+        ///
+        /// Opens connections using QUIC version 2
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let versions = version::Config::builder()
+        ///     .with_preferred_version(version::VERSION_2)?
+        ///     .build()?;
+        ///
+        /// let client = Client::builder()
+        ///     .with_version(versions)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ClientProviders
+    );
+
     impl_provider_method!(
         /// Sets the event provider for the [`Client`]
         ///
@@ -293,6 +322,14 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-stream-scheduler"))]
+    impl_provider_method!(
+        /// Sets the stream scheduler provider for the [`Client`]
+        with_stream_scheduler,
+        stream_scheduler,
+        ClientProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Client`]
@@ -18,11 +18,13 @@ impl_providers_state! {
         event: Event,
         limits: Limits,
         mtu: Mtu,
+        version: Version,
         io: IO,
         sync: Sync,
         tls: Tls,
         datagram: Datagram,
         dc: Dc,
+        stream_scheduler: StreamScheduler,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -39,11 +41,13 @@ impl<
         Event: event::Provider,
         Limits: limits::Provider,
         Mtu: mtu::Provider,
+        Version: version::Provider,
         IO: io::Provider,
         Sync: sync::Provider,
         Tls: tls::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        StreamScheduler: stream_scheduler::Provider,
     >
     Providers<
         CongestionController,
@@ -55,11 +59,13 @@ impl<
         Event,
         Limits,
         Mtu,
+        Version,
         IO,
         Sync,
         Tls,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -73,11 +79,13 @@ impl<
             event,
             limits,
             mtu,
+            version,
             io,
             sync,
             tls,
             datagram,
             dc,
+            stream_scheduler,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -91,13 +99,16 @@ impl<
         let endpoint_limits = EndpointLimits;
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
+        let version = version.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
         let token = Token;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = PathMigration;
+        let early_data = EarlyData;
         let tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let stream_scheduler = stream_scheduler.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -123,13 +134,16 @@ impl<
             event,
             limits,
             mtu,
+            version,
             sync,
             tls,
             token,
             path_handle: PhantomData,
             path_migration,
+            early_data,
             datagram,
             dc,
+            stream_scheduler,
         };
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
@@ -201,6 +215,18 @@ impl crate::provider::path_migration::Validator for PathMigration {
     }
 }
 
+#[derive(Debug)]
+struct EarlyData;
+
+impl crate::provider::early_data::Validator for EarlyData {
+    fn on_early_data_request(
+        &mut self,
+        _request: &crate::provider::early_data::Request,
+    ) -> crate::provider::early_data::Outcome {
+        unreachable!("early data should not be validated with clients")
+    }
+}
+
 #[allow(dead_code)] // don't warn on unused providers for now
 struct EndpointConfig<
     CongestionController,
@@ -217,6 +243,7 @@ struct EndpointConfig<
     Tls,
     Datagram,
     Dc,
+    StreamScheduler,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -228,13 +255,16 @@ struct EndpointConfig<
     event: Event,
     limits: Limits,
     mtu: path::mtu::Manager<Mtu>,
+    version: s2n_quic_core::version::Config,
     sync: Sync,
     tls: Tls,
     token: Token,
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
+    early_data: EarlyData,
     datagram: Datagram,
     dc: Dc,
+    stream_scheduler: StreamScheduler,
 }
 
 impl<
@@ -252,6 +282,7 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        StreamScheduler: s2n_quic_core::stream::scheduler::Endpoint,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -268,6 +299,7 @@ impl<
         Tls,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -290,6 +322,7 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        StreamScheduler: s2n_quic_core::stream::scheduler::Endpoint,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -306,6 +339,7 @@ impl<
         Tls,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -323,11 +357,13 @@ impl<
     type TokenFormat = Token;
     type ConnectionLimits = Limits;
     type Mtu = Mtu;
-    type StreamManager = stream::DefaultStreamManager;
+    type StreamManager = stream::DefaultStreamManager<StreamScheduler::Scheduler>;
     type PathMigrationValidator = PathMigration;
+    type EarlyDataValidator = EarlyData;
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type StreamSchedulerEndpoint = StreamScheduler;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
 
@@ -344,10 +380,13 @@ impl<
             token: &mut self.token,
             connection_limits: &mut self.limits,
             mtu: &mut self.mtu,
+            version: &self.version,
             event_subscriber: &mut self.event,
             path_migration: &mut self.path_migration,
+            early_data: &mut self.early_data,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            stream_scheduler: &mut self.stream_scheduler,
         }
     }
 }
@@ -11,12 +11,16 @@ mod handle;
 
 pub use acceptor::*;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use s2n_quic_core::connection::{Error, PathStats, Stats};
 
 pub mod error {
     pub use s2n_quic_core::transport::error::Code;
 }
 
+pub mod migration {
+    pub use s2n_quic_core::path::migration::Error;
+}
+
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 pub struct Connection(Inner);
@@ -157,6 +157,31 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns a snapshot of the statistics for the connection and each of its paths
+        ///
+        /// The snapshot includes round-trip time estimates, congestion control state, packet and
+        /// byte counters, flow control credit, and the MTU and ECN state of each path.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let stats = handle.stats()?;
+        /// if let Some(path) = stats.active_path() {
+        ///     println!("smoothed rtt: {:?}", path.smoothed_rtt);
+        /// }
+        /// println!("packets lost: {}", stats.packets_lost);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn stats(&self) -> $crate::connection::Result<$crate::connection::Stats> {
+            self.0.stats()
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -206,6 +231,35 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Migrates the connection to a new local address
+        ///
+        /// The application is responsible for making sure the endpoint can send and receive
+        /// datagrams on `local_addr`. The connection keeps using its current path until the new
+        /// path has been validated with the peer. Progress is reported to the event subscriber
+        /// with the [`LocalMigrationUpdated`](crate::provider::event::events::LocalMigrationUpdated)
+        /// event. If validation fails, the connection remains on the current path.
+        ///
+        /// Only clients can initiate a migration and only once the handshake is confirmed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> Result<(), s2n_quic::connection::migration::Error> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.migrate("192.168.1.2:4433".parse().unwrap())?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn migrate(
+            &mut self,
+            local_addr: std::net::SocketAddr,
+        ) -> core::result::Result<(), $crate::connection::migration::Error> {
+            self.0.migrate(local_addr.into())
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -227,6 +281,51 @@ macro_rules! impl_handle_api {
             self.0.close(error_code)
         }
 
+        /// Waits for the endpoint to start shutting down
+        ///
+        /// Servers notify all of their connections when
+        /// [`Server::shutdown`](crate::Server::shutdown) is called. Once notified, the application
+        /// should finish any outstanding streams before the shutdown deadline, after which the
+        /// connection is closed.
+        ///
+        /// The method will return
+        /// - `Ok(())` once the endpoint has started shutting down
+        /// - `Err(e)` if the connection was closed before the endpoint started shutting down
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// connection.endpoint_shutdown().await?;
+        /// println!("the endpoint is shutting down");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn endpoint_shutdown(&self) -> $crate::connection::Result<()> {
+            futures::future::poll_fn(|cx| self.poll_endpoint_shutdown(cx)).await
+        }
+
+        /// Polls for the endpoint to start shutting down
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` once the endpoint has started shutting down
+        /// - `Poll::Ready(Err(e))` if the connection was closed before the endpoint started
+        ///   shutting down
+        /// - `Poll::Pending` if the endpoint has not started shutting down
+        #[inline]
+        pub fn poll_endpoint_shutdown(
+            &self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                self.0.poll_endpoint_shutdown(cx)
+            })
+        }
+
         /// API for querying the connection's
         /// [`Subscriber::ConnectionContext`](crate::provider::event::Subscriber::ConnectionContext).
         ///
@@ -425,6 +524,39 @@ macro_rules! impl_handle_api {
 
             query.into()
         }
+
+        /// API for querying the congestion controller of the connection's active path.
+        ///
+        /// Provides mutable access to the `CongestionController` created by the
+        /// congestion controller provider. When using the
+        /// [`Dynamic`](crate::provider::congestion_controller::Dynamic) provider, this
+        /// can be used to change the congestion control algorithm after the handshake,
+        /// once the application protocol or server name is known. Paths created afterwards,
+        /// such as after a migration, use the same algorithm.
+        ///
+        /// ```ignore
+        /// let outcome = connection
+        ///     .congestion_controller_mut(
+        ///         |cc: &mut DynamicCongestionController| cc.set_algorithm(bbr::Builder::default().build())
+        ///     );
+        /// ```
+        pub fn congestion_controller_mut<Query, Controller, Outcome>(
+            &self,
+            query: Query,
+        ) -> core::result::Result<Outcome, s2n_quic_core::query::Error>
+        where
+            Query: FnOnce(&mut Controller) -> Outcome,
+            Controller: 'static,
+        {
+            use s2n_quic_core::query;
+            let mut query = query::Once::new_mut(query);
+
+            self.0
+                .congestion_controller_mut(&mut query)
+                .map_err(|_| query::Error::ConnectionLockPoisoned)?;
+
+            query.into()
+        }
     };
 }
 
@@ -19,8 +19,30 @@
 //!
 //! Enables the default address token provider, which
 //! will securely generate address tokens for a single QUIC server. If your deployment requires
-//! that multiple servers handle address tokens, this provider should not be used. Instead, a custom
-//! implementation of [`provider::address_token::Format`] should be specified.
+//! that multiple servers handle address tokens, the servers should share signing keys with
+//! [`provider::address_token::default::SharedKeys`]. Alternatively, a custom
+//! implementation of [`provider::address_token::Format`] can be specified.
+//!
+//! ### `provider-connection-id-quic-lb`
+//!
+//! Enables the [`provider::connection_id::quic_lb`] connection ID format, which generates
+//! connection IDs that can be routed by
+//! [QUIC-LB](https://datatracker.ietf.org/doc/draft-ietf-quic-load-balancers/) compliant load
+//! balancers. A matching [`provider::connection_id::quic_lb::Decoder`] is provided for use in the
+//! load balancer.
+//!
+//! ### `provider-event-openmetrics`
+//!
+//! Enables the [`provider::event::openmetrics::Subscriber`], which records aggregate metrics for
+//! each event in a [`provider::event::openmetrics::Registry`]. The registry renders the metrics
+//! in the [OpenMetrics](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md)
+//! text format.
+//!
+//! ### `provider-event-qlog`
+//!
+//! Enables the [`provider::event::qlog::Subscriber`], which writes a per-connection
+//! [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/) trace that can be
+//! loaded into [qvis](https://qvis.quictools.info).
 //!
 //! ### `provider-event-tracing`
 //!
@@ -29,6 +51,14 @@
 //! endpoint and connection events to the application's configured
 //! [`tracing::Subscriber`](https://docs.rs/tracing/latest/tracing/trait.Subscriber.html).
 //!
+//! ### `provider-stateless-reset-token-default`
+//!
+//! _Enabled by default_
+//!
+//! Enables the default stateless reset token provider, which derives tokens from a static key
+//! configured with [`provider::stateless_reset_token::default::Builder::with_key`]. Stateless
+//! resets are only transmitted once a key has been configured.
+//!
 //! ### `provider-tls-default`
 //!
 //! _Enabled by default_
@@ -10,6 +10,7 @@ mod macros;
 pub mod address_token;
 pub mod congestion_controller;
 pub mod connection_id;
+pub mod early_data;
 pub mod endpoint_limits;
 pub mod event;
 pub mod io;
@@ -17,6 +18,7 @@ pub mod limits;
 pub mod mtu;
 pub mod stateless_reset_token;
 pub mod tls;
+pub mod version;
 
 // These providers are not currently exposed to applications
 #[allow(dead_code)]
@@ -44,6 +46,16 @@ cfg_if!(
     }
 );
 
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-stream-scheduler"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-stream-scheduler")))]
+        pub mod stream_scheduler;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod stream_scheduler;
+    }
+);
+
 cfg_if!(
     if #[cfg(any(test, feature = "unstable-provider-random"))] {
         #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-random")))]
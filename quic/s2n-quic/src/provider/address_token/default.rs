@@ -3,22 +3,153 @@
 
 //! Default provider for Address Validation tokens
 //!
-//! The default provider will randomly generate a 256 bit key. This key will be used to sign and
-//! verify tokens. The key can be rotated at a duration set by the user.
+//! By default, the provider will randomly generate a 256 bit key. This key will be used to sign
+//! and verify tokens. The key can be rotated at a duration set by the user.
+//!
+//! Deployments where multiple servers need to validate each other's tokens can instead push
+//! externally managed key material into [`SharedKeys`] and configure it with
+//! [`Builder::with_key_source`]. Each token carries the ID of the key used to sign it, which
+//! allows several keys to remain valid while keys are rolled over, and the time it was issued,
+//! which limits how long it is accepted.
 //!
 //! The default provider does not support tokens delivered in a NEW_TOKEN frame.
 
-use core::{mem::size_of, time::Duration};
+use core::{fmt, mem::size_of, time::Duration};
 use hash_hasher::HashHasher;
 use s2n_codec::{DecoderBuffer, DecoderBufferMut};
 use s2n_quic_core::{
     connection, event::api::SocketAddress, random, time::Timestamp, token::Source,
 };
 use s2n_quic_crypto::{constant_time, digest, hmac};
-use std::hash::{Hash, Hasher};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock},
+};
 use zerocopy::{FromBytes, IntoBytes, Unaligned};
 use zeroize::Zeroizing;
 
+/// The identifier of a key, which is carried in each token
+pub type KeyId = u32;
+
+/// The minimum length of the key material
+pub const MIN_KEY_LEN: usize = 16;
+
+/// A key used to sign and verify address tokens
+#[derive(Clone)]
+pub struct Key {
+    id: KeyId,
+    key: hmac::Key,
+}
+
+impl Key {
+    /// Creates a key from the given ID and key material
+    ///
+    /// The key material should be at least 32 bytes from a secure random source, and must be
+    /// at least [`MIN_KEY_LEN`] bytes.
+    pub fn new(id: KeyId, material: &[u8]) -> Result<Self, Error> {
+        if material.len() < MIN_KEY_LEN {
+            return Err(Error::KeyTooShort);
+        }
+        let key = hmac::Key::new(hmac::HMAC_SHA256, material);
+        Ok(Self { id, key })
+    }
+
+    /// Returns the ID of the key
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // don't include the key material in the output
+        f.debug_struct("Key").field("id", &self.id).finish()
+    }
+}
+
+/// A source of keys for signing and verifying address tokens
+pub trait KeySource: 'static + Send {
+    /// Returns the key that should be used to sign new tokens
+    ///
+    /// If `None` is returned, no token will be generated.
+    fn signing_key(&mut self, random: &mut dyn random::Generator) -> Option<Key>;
+
+    /// Returns the key with the given ID, if tokens signed with it are still valid
+    fn verification_key(&mut self, id: KeyId) -> Option<Key>;
+}
+
+/// Key material that is managed by the application and shared between servers
+///
+/// The same key material can be pushed to each server in a fleet, which allows tokens generated
+/// by one server to be validated by the others. Keys can be rolled over without invalidating
+/// outstanding tokens:
+///
+/// 1. [`insert`](Self::insert) the new key on every server
+/// 2. switch each server to the new key with [`set_signing_key`](Self::set_signing_key)
+/// 3. [`remove`](Self::remove) the previous key once its tokens should no longer be accepted
+///
+/// `SharedKeys` is a handle and can be cloned to update the keys of a running server.
+#[derive(Clone, Debug, Default)]
+pub struct SharedKeys(Arc<RwLock<SharedKeysState>>);
+
+#[derive(Debug, Default)]
+struct SharedKeysState {
+    keys: BTreeMap<KeyId, Key>,
+    signing_key: Option<KeyId>,
+}
+
+impl SharedKeys {
+    /// Creates an empty set of keys
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds key material for the given key ID
+    ///
+    /// The key is valid for verifying tokens until it is removed. Any existing key with the same
+    /// ID is replaced.
+    pub fn insert(&self, id: KeyId, material: &[u8]) -> Result<(), Error> {
+        let key = Key::new(id, material)?;
+        self.0.write().unwrap().keys.insert(id, key);
+        Ok(())
+    }
+
+    /// Selects the key used to sign new tokens
+    pub fn set_signing_key(&self, id: KeyId) -> Result<(), Error> {
+        let mut state = self.0.write().unwrap();
+        if !state.keys.contains_key(&id) {
+            return Err(Error::UnknownKey);
+        }
+        state.signing_key = Some(id);
+        Ok(())
+    }
+
+    /// Removes the key with the given ID
+    ///
+    /// Tokens signed with the key are no longer valid. If the key was used for signing, no new
+    /// tokens are generated until another signing key is selected.
+    pub fn remove(&self, id: KeyId) {
+        let mut state = self.0.write().unwrap();
+        state.keys.remove(&id);
+        if state.signing_key == Some(id) {
+            state.signing_key = None;
+        }
+    }
+}
+
+impl KeySource for SharedKeys {
+    fn signing_key(&mut self, _random: &mut dyn random::Generator) -> Option<Key> {
+        let state = self.0.read().unwrap();
+        let id = state.signing_key?;
+        state.keys.get(&id).cloned()
+    }
+
+    fn verification_key(&mut self, id: KeyId) -> Option<Key> {
+        self.0.read().unwrap().keys.get(&id).cloned()
+    }
+}
+
 struct BaseKey {
     active_duration: Duration,
 
@@ -78,7 +209,12 @@ impl BaseKey {
 
 const DEFAULT_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
 
-#[derive(Debug)]
+//= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+//# Servers SHOULD ensure that
+//# tokens sent in Retry packets are only accepted for a short time.
+/// The default duration that tokens signed with keys from a [`KeySource`] are accepted
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(5);
+
 pub struct Provider {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
     //# Thus, a token SHOULD have an
@@ -88,21 +224,112 @@ pub struct Provider {
     /// To fulfill this SHOULD, we rotate the key periodically. This allows
     /// customers to control the token lifetime without adding bytes to the token itself.
     key_rotation_period: Duration,
+
+    /// Externally managed keys, which replace the locally generated keys
+    key_source: Option<Box<dyn KeySource>>,
+
+    /// How long tokens signed with keys from the `key_source` are accepted
+    ///
+    /// The keys from the source may be valid for an arbitrary amount of time, so these tokens
+    /// carry the time they were issued.
+    token_lifetime: Duration,
+}
+
+impl fmt::Debug for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Provider")
+            .field("key_rotation_period", &self.key_rotation_period)
+            .field("has_key_source", &self.key_source.is_some())
+            .field("token_lifetime", &self.token_lifetime)
+            .finish()
+    }
 }
 
 impl Default for Provider {
     fn default() -> Self {
         Self {
             key_rotation_period: DEFAULT_KEY_ROTATION_PERIOD,
+            key_source: None,
+            token_lifetime: DEFAULT_TOKEN_LIFETIME,
         }
     }
 }
 
+impl Provider {
+    /// Creates a builder for the provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+/// A builder for the default address token [`Provider`]
+pub struct Builder {
+    key_source: Option<Box<dyn KeySource>>,
+    token_lifetime: Duration,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            key_source: None,
+            token_lifetime: DEFAULT_TOKEN_LIFETIME,
+        }
+    }
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("has_key_source", &self.key_source.is_some())
+            .field("token_lifetime", &self.token_lifetime)
+            .finish()
+    }
+}
+
+impl Builder {
+    /// Sets the source of the keys used to sign and verify tokens
+    ///
+    /// By default, keys are randomly generated by each server and rotated periodically.
+    pub fn with_key_source<K: KeySource>(
+        mut self,
+        key_source: K,
+    ) -> Result<Self, core::convert::Infallible> {
+        self.key_source = Some(Box::new(key_source));
+        Ok(self)
+    }
+
+    /// Sets how long tokens signed with keys from the [`KeySource`] are accepted
+    ///
+    /// The tokens carry the wall clock time they were issued, so the clocks of servers that
+    /// share keys should be synchronized well within this duration. Defaults to 5 seconds.
+    pub fn with_token_lifetime(
+        mut self,
+        token_lifetime: Duration,
+    ) -> Result<Self, core::convert::Infallible> {
+        self.token_lifetime = token_lifetime;
+        Ok(self)
+    }
+
+    /// Builds the [`Provider`]
+    pub fn build(self) -> Result<Provider, core::convert::Infallible> {
+        Ok(Provider {
+            key_source: self.key_source,
+            token_lifetime: self.token_lifetime,
+            ..Default::default()
+        })
+    }
+}
+
 impl super::Provider for Provider {
-    type Format = Format;
+    type Format = ProviderFormat;
     type Error = core::convert::Infallible;
 
     fn start(self) -> Result<Self::Format, Self::Error> {
+        if let Some(keys) = self.key_source {
+            let format = SharedFormat::new(keys, self.token_lifetime);
+            return Ok(ProviderFormat(FormatKind::Shared(format)));
+        }
+
         // The keys must remain valid for two rotation periods or they will regenerate their
         // material and validation will fail.
         let format = Format {
@@ -115,10 +342,88 @@ impl super::Provider for Provider {
             ],
         };
 
-        Ok(format)
+        Ok(ProviderFormat(FormatKind::Local(Box::new(format))))
     }
 }
 
+/// The token format of the default [`Provider`]
+pub struct ProviderFormat(FormatKind);
+
+enum FormatKind {
+    /// Tokens signed with locally generated keys
+    ///
+    /// The format holds the key material inline so it's boxed to keep the provider small.
+    Local(Box<Format>),
+    /// Tokens signed with keys from a [`KeySource`]
+    Shared(SharedFormat),
+}
+
+impl super::Format for ProviderFormat {
+    const TOKEN_LEN: usize = size_of::<Token>();
+
+    fn generate_new_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        source_connection_id: &connection::LocalId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        match &mut self.0 {
+            FormatKind::Local(format) => super::Format::generate_new_token(
+                &mut **format,
+                context,
+                source_connection_id,
+                output_buffer,
+            ),
+            // The default provider does not support NEW_TOKEN frame tokens
+            FormatKind::Shared(_) => None,
+        }
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        match &mut self.0 {
+            FormatKind::Local(format) => super::Format::generate_retry_token(
+                &mut **format,
+                context,
+                original_destination_connection_id,
+                output_buffer,
+            ),
+            FormatKind::Shared(format) => format.generate_retry_token(
+                context,
+                original_destination_connection_id,
+                output_buffer,
+                unix_time(),
+            ),
+        }
+    }
+
+    fn validate_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        token: &[u8],
+    ) -> Option<connection::InitialId> {
+        match &mut self.0 {
+            FormatKind::Local(format) => {
+                super::Format::validate_token(&mut **format, context, token)
+            }
+            FormatKind::Shared(format) => format.validate_token(context, token, unix_time()),
+        }
+    }
+}
+
+/// Returns the wall clock time as a duration since the UNIX epoch
+///
+/// Unlike the monotonic clock, the wall clock can be compared between servers.
+fn unix_time() -> Duration {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
 pub struct Format {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
     //= type=exception
@@ -162,32 +467,8 @@ impl Format {
         token: &Token,
         context: &mut super::Context<'_>,
     ) -> Option<hmac::Tag> {
-        let mut ctx = self.keys[token.header.key_id() as usize].hasher(context.random)?;
-
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-        //# Tokens
-        //# sent in Retry packets SHOULD include information that allows the
-        //# server to verify that the source IP address and port in client
-        //# packets remain constant.
-        ctx.update(&token.original_destination_connection_id);
-        ctx.update(&token.nonce);
-        ctx.update(context.peer_connection_id);
-        match context.remote_address {
-            SocketAddress::IpV4 { ip, port, .. } => {
-                ctx.update(ip);
-                ctx.update(&port.to_be_bytes());
-            }
-            SocketAddress::IpV6 { ip, port, .. } => {
-                ctx.update(ip);
-                ctx.update(&port.to_be_bytes());
-            }
-            _ => {
-                // we are unable to hash the address so bail
-                return None;
-            }
-        };
-
-        Some(ctx.sign())
+        let ctx = self.keys[token.header.key_id() as usize].hasher(context.random)?;
+        sign_retry_token(ctx, token, context)
     }
 
     // Using the key id in the token, verify the token
@@ -210,14 +491,15 @@ impl Format {
             // Only add the token once it has been validated. This will prevent the filter from
             // being filled with garbage tokens.
 
-            // Ignore the outcome of adding a token to the filter because we always want to
-            // continue the connection if the filter fails.
-            let _ = self.keys[token.header.key_id() as usize]
+            // If the filter is full, replays of the token can no longer be detected so it is
+            // rejected.
+            self.keys[token.header.key_id() as usize]
                 .duplicate_filter
                 .get_or_insert_with(|| {
                     cuckoofilter::CuckooFilter::with_capacity(cuckoofilter::DEFAULT_CAPACITY)
                 })
-                .add(token);
+                .add(token)
+                .ok()?;
 
             return token.original_destination_connection_id();
         }
@@ -285,29 +567,17 @@ impl super::Format for Format {
         original_destination_connection_id: &connection::InitialId,
         output_buffer: &mut [u8],
     ) -> Option<()> {
-        let buffer = DecoderBufferMut::new(output_buffer);
-        let (token, _) = buffer
-            .decode::<&mut Token>()
-            .expect("Provided output buffer did not match TOKEN_LEN");
-
         let header = Header::new(Source::RetryPacket, self.current_key());
 
-        token.header = header;
-        token.original_destination_connection_id[..original_destination_connection_id.len()]
-            .copy_from_slice(original_destination_connection_id.as_bytes());
-        token.odcid_len = original_destination_connection_id.len() as u8;
-
-        // ensure the other CID bytes are zeroed out
-        for b in token
-            .original_destination_connection_id
-            .iter_mut()
-            .skip(original_destination_connection_id.len())
-        {
-            *b = 0;
-        }
-
-        // Populate the nonce before signing
-        context.random.public_random_fill(&mut token.nonce[..]);
+        // The key is identified by the header and expires with the key rotation
+        let token = Token::new(
+            output_buffer,
+            header,
+            0,
+            Duration::ZERO,
+            original_destination_connection_id,
+            context,
+        );
 
         let tag = self.tag_retry_token(token, context)?;
 
@@ -356,6 +626,211 @@ impl super::Format for Format {
     }
 }
 
+/// Tokens signed with keys from a [`KeySource`]
+///
+/// The keys may remain valid for an arbitrary amount of time, so each token carries the time it
+/// was issued, which limits how long it is accepted.
+struct SharedFormat {
+    keys: Box<dyn KeySource>,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Servers SHOULD ensure that
+    //# tokens sent in Retry packets are only accepted for a short time.
+    token_lifetime: Duration,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# To protect against such attacks, servers MUST ensure that
+    //# replay of tokens is prevented or limited.
+    duplicate_filter: DuplicateFilter,
+}
+
+impl SharedFormat {
+    fn new(keys: Box<dyn KeySource>, token_lifetime: Duration) -> Self {
+        // Tokens from servers with clocks that are ahead are accepted for up to twice the
+        // lifetime, so they need to be remembered for at least as long.
+        let duplicate_filter =
+            DuplicateFilter::new(token_lifetime * 2, cuckoofilter::DEFAULT_CAPACITY);
+
+        Self {
+            keys,
+            token_lifetime,
+            duplicate_filter,
+        }
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+        now: Duration,
+    ) -> Option<()> {
+        let key = self.keys.signing_key(context.random)?;
+
+        // The key is identified by the ID in the token rather than the header
+        let header = Header::new(Source::RetryPacket, 0);
+
+        let token = Token::new(
+            output_buffer,
+            header,
+            key.id,
+            now,
+            original_destination_connection_id,
+            context,
+        );
+
+        let tag = sign_retry_token(hmac::Context::with_key(&key.key), token, context)?;
+
+        token.hmac.copy_from_slice(tag.as_ref());
+
+        Some(())
+    }
+
+    fn validate_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        token: &[u8],
+        now: Duration,
+    ) -> Option<connection::InitialId> {
+        let buffer = DecoderBuffer::new(token);
+        let (token, remaining) = buffer.decode::<&Token>().ok()?;
+
+        // Verify the provided token doesn't have any additional data
+        remaining.ensure_empty().ok()?;
+
+        if token.header.version() != TOKEN_VERSION {
+            return None;
+        }
+
+        // NEW_TOKEN frame tokens are not supported in the default provider
+        if token.header.token_source() != Source::RetryPacket {
+            return None;
+        }
+
+        // The token may have been issued by a server with a clock that is slightly ahead
+        let issued_at = token.issued_at();
+        let age = now.max(issued_at) - now.min(issued_at);
+        if age > self.token_lifetime {
+            return None;
+        }
+
+        self.duplicate_filter.update(now);
+
+        if self.duplicate_filter.contains(token) {
+            return None;
+        }
+
+        let key = self.keys.verification_key(token.key_id())?;
+        let tag = sign_retry_token(hmac::Context::with_key(&key.key), token, context)?;
+
+        constant_time::verify_slices_are_equal(&token.hmac, tag.as_ref()).ok()?;
+
+        // Only add the token once it has been validated. This will prevent the filter from
+        // being filled with garbage tokens.
+        if !self.duplicate_filter.insert(token) {
+            // replays of the token can no longer be detected
+            return None;
+        }
+
+        token.original_destination_connection_id()
+    }
+}
+
+/// Tracks the tokens that were already validated
+///
+/// The tokens are split into generations by epoch. Tokens from an epoch are remembered until
+/// the end of the following epoch, after which they have expired and no longer need to be
+/// tracked.
+struct DuplicateFilter {
+    epoch_duration: Duration,
+    epoch: u128,
+    capacity: usize,
+    current: Option<cuckoofilter::CuckooFilter<HashHasher>>,
+    previous: Option<cuckoofilter::CuckooFilter<HashHasher>>,
+}
+
+impl DuplicateFilter {
+    fn new(epoch_duration: Duration, capacity: usize) -> Self {
+        Self {
+            epoch_duration,
+            epoch: 0,
+            capacity,
+            current: None,
+            previous: None,
+        }
+    }
+
+    /// Moves the filters to the epoch of the current time
+    fn update(&mut self, now: Duration) {
+        let epoch = now.as_nanos() / self.epoch_duration.as_nanos().max(1);
+
+        match epoch.checked_sub(self.epoch) {
+            // the clock hasn't advanced to the next epoch
+            Some(0) | None => return,
+            Some(1) => {
+                self.previous = self.current.take();
+            }
+            Some(_) => {
+                self.previous = None;
+                self.current = None;
+            }
+        }
+
+        self.epoch = epoch;
+    }
+
+    fn contains(&self, token: &Token) -> bool {
+        [&self.current, &self.previous]
+            .into_iter()
+            .flatten()
+            .any(|filter| filter.contains(token))
+    }
+
+    /// Adds the token to the filter, returning `false` if the filter is full
+    fn insert(&mut self, token: &Token) -> bool {
+        let capacity = self.capacity;
+        self.current
+            .get_or_insert_with(|| cuckoofilter::CuckooFilter::with_capacity(capacity))
+            .add(token)
+            .is_ok()
+    }
+}
+
+/// Computes the tag of a retry token with the given key
+fn sign_retry_token(
+    mut ctx: hmac::Context,
+    token: &Token,
+    context: &mut super::Context<'_>,
+) -> Option<hmac::Tag> {
+    ctx.update(&token.key_id);
+    ctx.update(&token.issued_at);
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Tokens
+    //# sent in Retry packets SHOULD include information that allows the
+    //# server to verify that the source IP address and port in client
+    //# packets remain constant.
+    ctx.update(&token.original_destination_connection_id);
+    ctx.update(&token.nonce);
+    ctx.update(context.peer_connection_id);
+    match context.remote_address {
+        SocketAddress::IpV4 { ip, port, .. } => {
+            ctx.update(ip);
+            ctx.update(&port.to_be_bytes());
+        }
+        SocketAddress::IpV6 { ip, port, .. } => {
+            ctx.update(ip);
+            ctx.update(&port.to_be_bytes());
+        }
+        _ => {
+            // we are unable to hash the address so bail
+            return None;
+        }
+    };
+
+    Some(ctx.sign())
+}
+
 #[derive(Clone, Copy, Debug, FromBytes, IntoBytes, Unaligned)]
 #[repr(C)]
 pub(crate) struct Header(u8);
@@ -421,6 +896,13 @@ impl Header {
 struct Token {
     header: Header,
 
+    /// The ID of the key from the [`KeySource`] used to sign the token
+    key_id: [u8; 4],
+
+    /// The milliseconds since the UNIX epoch when a token signed with a [`KeySource`] key was
+    /// issued
+    issued_at: [u8; 8],
+
     odcid_len: u8,
     original_destination_connection_id: [u8; 20],
 
@@ -452,6 +934,50 @@ impl Hash for Token {
 }
 
 impl Token {
+    /// Populates an unsigned token in the output buffer
+    fn new<'a>(
+        output_buffer: &'a mut [u8],
+        header: Header,
+        key_id: KeyId,
+        issued_at: Duration,
+        original_destination_connection_id: &connection::InitialId,
+        context: &mut super::Context<'_>,
+    ) -> &'a mut Token {
+        let buffer = DecoderBufferMut::new(output_buffer);
+        let (token, _) = buffer
+            .decode::<&mut Token>()
+            .expect("Provided output buffer did not match TOKEN_LEN");
+
+        token.header = header;
+        token.key_id = key_id.to_be_bytes();
+        token.issued_at = (issued_at.as_millis() as u64).to_be_bytes();
+        token.original_destination_connection_id[..original_destination_connection_id.len()]
+            .copy_from_slice(original_destination_connection_id.as_bytes());
+        token.odcid_len = original_destination_connection_id.len() as u8;
+
+        // ensure the other CID bytes are zeroed out
+        for b in token
+            .original_destination_connection_id
+            .iter_mut()
+            .skip(original_destination_connection_id.len())
+        {
+            *b = 0;
+        }
+
+        // Populate the nonce before signing
+        context.random.public_random_fill(&mut token.nonce[..]);
+
+        token
+    }
+
+    fn key_id(&self) -> KeyId {
+        KeyId::from_be_bytes(self.key_id)
+    }
+
+    fn issued_at(&self) -> Duration {
+        Duration::from_millis(u64::from_be_bytes(self.issued_at))
+    }
+
     pub fn original_destination_connection_id(&self) -> Option<connection::InitialId> {
         let dcid = self
             .original_destination_connection_id
@@ -460,6 +986,28 @@ impl Token {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The key material is shorter than [`MIN_KEY_LEN`]
+    KeyTooShort,
+    /// The key ID has not been inserted
+    UnknownKey,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyTooShort => {
+                write!(f, "address token keys must be at least {MIN_KEY_LEN} bytes")
+            }
+            Self::UnknownKey => write!(f, "the key ID has not been inserted"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -759,4 +1307,261 @@ mod tests {
                 assert!(format.validate_token(&mut context, token).is_none())
             });
     }
+
+    #[test]
+    fn test_duplicate_filter_full() {
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        format
+            .generate_retry_token(&mut context, &odcid, &mut buf)
+            .unwrap();
+
+        // Replace the filters with ones that only fit a few tokens
+        for key in &mut format.keys {
+            key.duplicate_filter = Some(cuckoofilter::CuckooFilter::with_capacity(4));
+        }
+
+        // Tokens are rejected once replays can no longer be detected
+        let rejected = (0..1000).any(|_| {
+            format
+                .generate_retry_token(&mut context, &odcid, &mut buf)
+                .unwrap();
+            format.validate_token(&mut context, &buf).is_none()
+        });
+        assert!(rejected);
+    }
+
+    /// The wall clock time used for tests with shared keys
+    const NOW: Duration = Duration::from_secs(1_700_000_000);
+
+    fn get_shared_keys() -> SharedKeys {
+        let keys = SharedKeys::new();
+        keys.insert(1, &[1; 32]).unwrap();
+        keys.set_signing_key(1).unwrap();
+        keys
+    }
+
+    fn get_shared_format(keys: &SharedKeys) -> SharedFormat {
+        SharedFormat::new(Box::new(keys.clone()), DEFAULT_TOKEN_LIFETIME)
+    }
+
+    fn get_shared_provider_format(keys: &SharedKeys) -> ProviderFormat {
+        let provider = Provider::builder()
+            .with_key_source(keys.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        super::super::Provider::start(provider).unwrap()
+    }
+
+    #[test]
+    fn test_shared_keys() {
+        let keys = get_shared_keys();
+
+        // each server has its own format but shares the keys
+        let mut server_a = get_shared_provider_format(&keys);
+        let mut server_b = get_shared_provider_format(&keys);
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        server_a
+            .generate_retry_token(&mut context, &odcid, &mut buf)
+            .unwrap();
+
+        assert_eq!(server_b.validate_token(&mut context, &buf), Some(odcid));
+
+        // servers with different key material reject the token
+        let other_keys = SharedKeys::new();
+        other_keys.insert(1, &[2; 32]).unwrap();
+        other_keys.set_signing_key(1).unwrap();
+        let mut server_c = get_shared_provider_format(&other_keys);
+        assert!(server_c.validate_token(&mut context, &buf).is_none());
+    }
+
+    #[test]
+    fn test_shared_key_rollover() {
+        let keys = get_shared_keys();
+        let mut format = get_shared_format(&keys);
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        let mut old_token = [0; Format::TOKEN_LEN];
+        format
+            .generate_retry_token(&mut context, &odcid, &mut old_token, NOW)
+            .unwrap();
+
+        // roll over to a new key
+        keys.insert(2, &[2; 32]).unwrap();
+        keys.set_signing_key(2).unwrap();
+
+        let mut new_token = [0; Format::TOKEN_LEN];
+        format
+            .generate_retry_token(&mut context, &odcid, &mut new_token, NOW)
+            .unwrap();
+
+        // the key ID is carried in the token
+        let (token, _) = DecoderBuffer::new(&old_token).decode::<&Token>().unwrap();
+        assert_eq!(token.key_id(), 1);
+        let (token, _) = DecoderBuffer::new(&new_token).decode::<&Token>().unwrap();
+        assert_eq!(token.key_id(), 2);
+
+        // both keys are valid during the rollover
+        let mut other_format = get_shared_format(&keys);
+        assert_eq!(
+            other_format.validate_token(&mut context, &old_token, NOW),
+            Some(odcid)
+        );
+
+        // tokens for removed keys are rejected
+        keys.remove(1);
+        assert!(format
+            .validate_token(&mut context, &old_token, NOW)
+            .is_none());
+        assert_eq!(
+            format.validate_token(&mut context, &new_token, NOW),
+            Some(odcid)
+        );
+
+        // tokens are not generated without a signing key
+        keys.remove(2);
+        assert!(format
+            .generate_retry_token(&mut context, &odcid, &mut new_token, NOW)
+            .is_none());
+    }
+
+    #[test]
+    fn test_shared_keys_errors() {
+        let keys = SharedKeys::new();
+        assert_eq!(keys.set_signing_key(1), Err(Error::UnknownKey));
+        assert_eq!(
+            keys.insert(1, &[1; MIN_KEY_LEN - 1]),
+            Err(Error::KeyTooShort)
+        );
+        assert_eq!(keys.set_signing_key(1), Err(Error::UnknownKey));
+    }
+
+    #[test]
+    fn test_shared_token_lifetime() {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //= type=test
+        //# Servers SHOULD ensure that
+        //# tokens sent in Retry packets are only accepted for a short time.
+        let mut format = get_shared_format(&get_shared_keys());
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        let mut tokens = [[0; Format::TOKEN_LEN]; 5];
+        for token in &mut tokens {
+            format
+                .generate_retry_token(&mut context, &odcid, token, NOW)
+                .unwrap();
+        }
+        let millisecond = Duration::from_millis(1);
+
+        // The token is accepted until the lifetime has elapsed
+        assert_eq!(
+            format.validate_token(&mut context, &tokens[0], NOW + DEFAULT_TOKEN_LIFETIME),
+            Some(odcid)
+        );
+        assert!(format
+            .validate_token(
+                &mut context,
+                &tokens[1],
+                NOW + DEFAULT_TOKEN_LIFETIME + millisecond
+            )
+            .is_none());
+
+        // Tokens issued by servers with a clock that is ahead are accepted within the lifetime
+        assert_eq!(
+            format.validate_token(&mut context, &tokens[2], NOW - DEFAULT_TOKEN_LIFETIME),
+            Some(odcid)
+        );
+        assert!(format
+            .validate_token(
+                &mut context,
+                &tokens[3],
+                NOW - DEFAULT_TOKEN_LIFETIME - millisecond
+            )
+            .is_none());
+
+        // The issue time is covered by the integrity protection
+        let issued_at = size_of::<Header>() + size_of::<KeyId>();
+        tokens[4][issued_at + 7] ^= 1;
+        assert!(format
+            .validate_token(&mut context, &tokens[4], NOW)
+            .is_none());
+    }
+
+    #[test]
+    fn test_shared_duplicate_token_detection() {
+        let mut format = get_shared_format(&get_shared_keys());
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        format
+            .generate_retry_token(&mut context, &odcid, &mut buf, NOW)
+            .unwrap();
+
+        assert_eq!(format.validate_token(&mut context, &buf, NOW), Some(odcid));
+
+        // Replays are detected for as long as the token is accepted
+        assert!(format.validate_token(&mut context, &buf, NOW).is_none());
+        assert!(format
+            .validate_token(&mut context, &buf, NOW + DEFAULT_TOKEN_LIFETIME)
+            .is_none());
+
+        // The token is remembered for the following epoch
+        let (token, _) = DecoderBuffer::new(&buf).decode::<&Token>().unwrap();
+        let epoch = DEFAULT_TOKEN_LIFETIME * 2;
+        format.duplicate_filter.update(NOW + epoch);
+        assert!(format.duplicate_filter.contains(token));
+
+        // The filters are cleared once the tokens in them have expired
+        format.duplicate_filter.update(NOW + epoch * 3);
+        assert!(!format.duplicate_filter.contains(token));
+        assert!(format.duplicate_filter.current.is_none());
+        assert!(format.duplicate_filter.previous.is_none());
+    }
+
+    #[test]
+    fn test_shared_duplicate_filter_full() {
+        let mut format = get_shared_format(&get_shared_keys());
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut buf = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        // Replace the filter with one that only fits a few tokens
+        format.duplicate_filter = DuplicateFilter::new(DEFAULT_TOKEN_LIFETIME * 2, 4);
+
+        // Tokens are rejected once replays can no longer be detected
+        let rejected = (0..1000).any(|_| {
+            format
+                .generate_retry_token(&mut context, &odcid, &mut buf, NOW)
+                .unwrap();
+            format.validate_token(&mut context, &buf, NOW).is_none()
+        });
+        assert!(rejected);
+    }
 }
@@ -24,9 +24,16 @@ cfg_if! {
     }
 }
 
-pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
+pub use s2n_quic_core::recovery::{
+    bbr::Endpoint as Bbr, cubic::Endpoint as Cubic, dynamic::Endpoint as Dynamic,
+    prague::Endpoint as Prague, reno::Endpoint as Reno,
+};
+// Select the congestion control algorithm for each connection
+pub use s2n_quic_core::recovery::dynamic;
 // Build congestion controllers with application provided overrides
-pub use s2n_quic_core::recovery::{bbr::builder as bbr, cubic::builder as cubic};
+pub use s2n_quic_core::recovery::{
+    bbr::builder as bbr, cubic::builder as cubic, prague::builder as prague, reno::builder as reno,
+};
 pub type Default = Cubic;
 
 impl_provider_utils!();
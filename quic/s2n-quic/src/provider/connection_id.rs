@@ -14,6 +14,9 @@ pub trait Provider: 'static {
 
 pub use default::Provider as Default;
 
+#[cfg(feature = "provider-connection-id-quic-lb")]
+pub mod quic_lb;
+
 impl_provider_utils!();
 
 impl<T: 'static + Format> Provider for T {
@@ -0,0 +1,790 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routable connection IDs for [QUIC-LB](https://datatracker.ietf.org/doc/draft-ietf-quic-load-balancers/)
+//! compliant load balancers
+//!
+//! Each generated connection ID is made up of:
+//!
+//! * a first octet containing the config rotation bits and, optionally, the length of the
+//!   connection ID
+//! * the server ID of the local endpoint
+//! * a random nonce
+//!
+//! The server ID and nonce are either transmitted in plaintext or, if a key is configured,
+//! encrypted with the single-pass (when they make up exactly 16 bytes) or four-pass algorithm.
+//!
+//! A load balancer using the same [`Config`] can recover the server ID with a [`Decoder`], which
+//! allows connections to be routed to the same server even after the client address changes.
+//!
+//! ```rust,ignore
+//! use s2n_quic::provider::connection_id::quic_lb::{Config, Format};
+//!
+//! let config = Config::new(0, 3, 12)?.with_key([0; 16]);
+//! let format = Format::builder(config, &[1, 2, 3])?.build()?;
+//!
+//! let server = s2n_quic::Server::builder()
+//!     .with_connection_id(format)?
+//!     ...
+//!     .start()?;
+//! ```
+
+use core::{fmt, time::Duration};
+use rand::prelude::*;
+use s2n_quic_core::connection::{
+    self,
+    id::{ConnectionInfo, Generator, Validator},
+};
+use s2n_quic_crypto::cipher::{self, DecryptingKey, EncryptingKey, UnboundCipherKey};
+
+/// The maximum value of the config rotation bits
+///
+/// The value `0b111` is reserved for unroutable connection IDs.
+pub const MAX_CONFIG_ID: u8 = 6;
+
+/// The range of allowed server ID lengths
+pub const SERVER_ID_LEN: core::ops::RangeInclusive<usize> = 1..=15;
+
+/// The range of allowed nonce lengths
+pub const NONCE_LEN: core::ops::RangeInclusive<usize> = 4..=18;
+
+/// The length of the AES-128 key used to encrypt connection IDs
+pub const KEY_LEN: usize = 16;
+
+/// The maximum combined length of the server ID and nonce
+const MAX_PLAINTEXT_LEN: usize = connection::id::MAX_LEN - 1;
+
+const BLOCK_LEN: usize = 16;
+
+/// The maximum length of each half in the four-pass algorithm
+const MAX_HALF_LEN: usize = MAX_PLAINTEXT_LEN.div_ceil(2);
+
+/// A QUIC-LB configuration shared between servers and the load balancer
+#[derive(Clone, PartialEq, Eq)]
+pub struct Config {
+    config_id: u8,
+    server_id_len: usize,
+    nonce_len: usize,
+    key: Option<[u8; KEY_LEN]>,
+    length_self_encoding: bool,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // don't include the key in the output
+        f.debug_struct("Config")
+            .field("config_id", &self.config_id)
+            .field("server_id_len", &self.server_id_len)
+            .field("nonce_len", &self.nonce_len)
+            .field("is_encrypted", &self.key.is_some())
+            .field("length_self_encoding", &self.length_self_encoding)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Creates a plaintext configuration
+    ///
+    /// The combined length of the server ID and nonce may not exceed 19 bytes.
+    pub fn new(config_id: u8, server_id_len: usize, nonce_len: usize) -> Result<Self, Error> {
+        if config_id > MAX_CONFIG_ID {
+            return Err(Error::InvalidConfigId);
+        }
+        if !SERVER_ID_LEN.contains(&server_id_len) {
+            return Err(Error::InvalidServerIdLen);
+        }
+        if !NONCE_LEN.contains(&nonce_len) || server_id_len + nonce_len > MAX_PLAINTEXT_LEN {
+            return Err(Error::InvalidNonceLen);
+        }
+
+        Ok(Self {
+            config_id,
+            server_id_len,
+            nonce_len,
+            key: None,
+            length_self_encoding: false,
+        })
+    }
+
+    /// Encrypts the server ID and nonce with the given key
+    ///
+    /// The single-pass algorithm is used when the server ID and nonce are 16 bytes in total,
+    /// otherwise the four-pass algorithm is used.
+    pub fn with_key(mut self, key: [u8; KEY_LEN]) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Encodes the length of the connection ID in the first octet (default: disabled)
+    ///
+    /// When disabled, the bits following the config rotation bits are random.
+    pub fn with_length_self_encoding(mut self, enabled: bool) -> Self {
+        self.length_self_encoding = enabled;
+        self
+    }
+
+    /// Returns the config rotation bits
+    pub fn config_id(&self) -> u8 {
+        self.config_id
+    }
+
+    /// Returns the length of the server ID
+    pub fn server_id_len(&self) -> usize {
+        self.server_id_len
+    }
+
+    /// Returns the length of the connection IDs using this configuration
+    pub fn connection_id_len(&self) -> usize {
+        1 + self.plaintext_len()
+    }
+
+    #[inline]
+    fn plaintext_len(&self) -> usize {
+        self.server_id_len + self.nonce_len
+    }
+}
+
+/// Encodes and decodes connection IDs for a [`Config`]
+struct Cipher {
+    config: Config,
+    mode: Mode,
+}
+
+enum Mode {
+    Plaintext,
+    SinglePass {
+        encrypt: EncryptingKey,
+        decrypt: Box<DecryptingKey>,
+    },
+    FourPass {
+        encrypt: EncryptingKey,
+    },
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            Mode::Plaintext => "plaintext",
+            Mode::SinglePass { .. } => "single-pass",
+            Mode::FourPass { .. } => "four-pass",
+        };
+        f.debug_struct("Cipher")
+            .field("config", &self.config)
+            .field("mode", &mode)
+            .finish()
+    }
+}
+
+impl Cipher {
+    fn new(config: Config) -> Self {
+        let key = |key: &[u8; KEY_LEN]| {
+            UnboundCipherKey::new(&cipher::AES_128, key).expect("key length is valid")
+        };
+
+        let mode = match &config.key {
+            None => Mode::Plaintext,
+            Some(k) if config.plaintext_len() == BLOCK_LEN => Mode::SinglePass {
+                encrypt: EncryptingKey::ecb(key(k)).expect("ECB is supported for AES"),
+                decrypt: Box::new(DecryptingKey::ecb(key(k)).expect("ECB is supported for AES")),
+            },
+            Some(k) => Mode::FourPass {
+                encrypt: EncryptingKey::ecb(key(k)).expect("ECB is supported for AES"),
+            },
+        };
+
+        Self { config, mode }
+    }
+
+    /// Writes a connection ID containing the server ID and nonce into `out`
+    fn encode(&self, server_id: &[u8], nonce: &[u8], out: &mut [u8]) {
+        debug_assert_eq!(server_id.len(), self.config.server_id_len);
+        debug_assert_eq!(nonce.len(), self.config.nonce_len);
+        debug_assert_eq!(out.len(), self.config.connection_id_len());
+
+        let len_bits = if self.config.length_self_encoding {
+            (out.len() - 1) as u8
+        } else {
+            rand::rng().random()
+        };
+        out[0] = self.config.config_id << 5 | (len_bits & 0b1_1111);
+
+        let (server_id_out, nonce_out) = out[1..].split_at_mut(server_id.len());
+        server_id_out.copy_from_slice(server_id);
+        nonce_out.copy_from_slice(nonce);
+
+        let plaintext = &mut out[1..];
+        match &self.mode {
+            Mode::Plaintext => {}
+            Mode::SinglePass { encrypt, .. } => {
+                encrypt.encrypt(plaintext).expect("input is a single block");
+            }
+            Mode::FourPass { encrypt } => four_pass(encrypt, plaintext, true),
+        }
+    }
+
+    /// Returns the plaintext server ID and nonce for the given connection ID
+    ///
+    /// `None` is returned if the connection ID doesn't belong to this configuration.
+    fn decode(&self, connection_id: &[u8]) -> Option<([u8; MAX_PLAINTEXT_LEN], usize)> {
+        let len = self.config.connection_id_len();
+        let connection_id = connection_id.get(..len)?;
+
+        if connection_id[0] >> 5 != self.config.config_id {
+            return None;
+        }
+
+        let mut plaintext = [0u8; MAX_PLAINTEXT_LEN];
+        let plaintext_len = self.config.plaintext_len();
+        plaintext[..plaintext_len].copy_from_slice(&connection_id[1..]);
+
+        match &self.mode {
+            Mode::Plaintext => {}
+            Mode::SinglePass { decrypt, .. } => {
+                decrypt
+                    .decrypt(
+                        &mut plaintext[..plaintext_len],
+                        cipher::DecryptionContext::None,
+                    )
+                    .ok()?;
+            }
+            Mode::FourPass { encrypt } => {
+                four_pass(encrypt, &mut plaintext[..plaintext_len], false)
+            }
+        }
+
+        Some((plaintext, len))
+    }
+}
+
+/// Applies the four-pass Feistel network to `buffer` in place
+///
+/// When the length is odd, the middle octet is split between the two halves: the left half
+/// holds its four most significant bits and the right half its four least significant bits.
+fn four_pass(key: &EncryptingKey, buffer: &mut [u8], encrypt: bool) {
+    let len = buffer.len();
+    let network = FourPass {
+        key,
+        len,
+        half_len: len.div_ceil(2),
+    };
+    let half_len = network.half_len;
+
+    let mut left = network.truncate_left(buffer);
+    let mut right = network.truncate_right(&buffer[len - half_len..]);
+
+    let passes = if encrypt { [1, 2, 3, 4] } else { [4, 3, 2, 1] };
+
+    for pass in passes {
+        // odd passes modify the right half and even passes modify the left half
+        if pass % 2 == 1 {
+            let block = network.encrypt(network.expand_left(&left, pass));
+            xor(&mut right, &network.truncate_right(&block));
+        } else {
+            let block = network.encrypt(network.expand_right(&right, pass));
+            xor(&mut left, &network.truncate_left(&block));
+        }
+    }
+
+    buffer[..half_len].copy_from_slice(&left[..half_len]);
+    if len % 2 == 1 {
+        buffer[half_len - 1] |= right[0];
+        buffer[half_len..].copy_from_slice(&right[1..half_len]);
+    } else {
+        buffer[half_len..].copy_from_slice(&right[..half_len]);
+    }
+}
+
+/// The functions used by each pass of the four-pass algorithm
+struct FourPass<'a> {
+    key: &'a EncryptingKey,
+    /// The combined length of the server ID and nonce
+    len: usize,
+    half_len: usize,
+}
+
+impl FourPass<'_> {
+    #[inline]
+    fn is_odd(&self) -> bool {
+        self.len % 2 == 1
+    }
+
+    /// Returns the first `half_len` octets of `input`, clearing the four least significant bits
+    /// of the last octet if the length is odd
+    fn truncate_left(&self, input: &[u8]) -> [u8; MAX_HALF_LEN] {
+        let mut half = [0u8; MAX_HALF_LEN];
+        half[..self.half_len].copy_from_slice(&input[..self.half_len]);
+        if self.is_odd() {
+            half[self.half_len - 1] &= 0xf0;
+        }
+        half
+    }
+
+    /// Returns the first `half_len` octets of `input`, clearing the four most significant bits
+    /// of the first octet if the length is odd
+    fn truncate_right(&self, input: &[u8]) -> [u8; MAX_HALF_LEN] {
+        let mut half = [0u8; MAX_HALF_LEN];
+        half[..self.half_len].copy_from_slice(&input[..self.half_len]);
+        if self.is_odd() {
+            half[0] &= 0x0f;
+        }
+        half
+    }
+
+    /// Expands the left half into a block, followed by zero padding, the length and the pass
+    fn expand_left(&self, left: &[u8], pass: u8) -> [u8; BLOCK_LEN] {
+        self.expand(&self.truncate_left(left), pass)
+    }
+
+    /// Expands the right half into a block, followed by zero padding, the length and the pass
+    fn expand_right(&self, right: &[u8], pass: u8) -> [u8; BLOCK_LEN] {
+        self.expand(&self.truncate_right(right), pass)
+    }
+
+    #[inline]
+    fn expand(&self, half: &[u8], pass: u8) -> [u8; BLOCK_LEN] {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..self.half_len].copy_from_slice(&half[..self.half_len]);
+        block[BLOCK_LEN - 2] = self.len as u8;
+        block[BLOCK_LEN - 1] = pass;
+        block
+    }
+
+    #[inline]
+    fn encrypt(&self, mut block: [u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+        self.key
+            .encrypt(&mut block)
+            .expect("input is a single block");
+        block
+    }
+}
+
+#[inline]
+fn xor(target: &mut [u8], mask: &[u8]) {
+    for (target, mask) in target.iter_mut().zip(mask) {
+        *target ^= mask;
+    }
+}
+
+/// The server ID recovered from a connection ID
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ServerId {
+    bytes: [u8; *SERVER_ID_LEN.end()],
+    len: u8,
+}
+
+impl ServerId {
+    /// Returns the server ID as a slice
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl AsRef<[u8]> for ServerId {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for ServerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ServerId(")?;
+        for byte in self.as_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Extracts server IDs from connection IDs on the load balancer
+///
+/// A decoder holds one [`Config`] for each of the config rotation values in use.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    ciphers: [Option<Cipher>; MAX_CONFIG_ID as usize + 1],
+}
+
+impl Decoder {
+    /// Adds a configuration, replacing any existing configuration with the same config ID
+    pub fn with_config(mut self, config: Config) -> Self {
+        let config_id = config.config_id as usize;
+        self.ciphers[config_id] = Some(Cipher::new(config));
+        self
+    }
+
+    /// Removes the configuration with the given config ID
+    pub fn without_config(mut self, config_id: u8) -> Self {
+        if let Some(cipher) = self.ciphers.get_mut(config_id as usize) {
+            *cipher = None;
+        }
+        self
+    }
+
+    /// Returns the server ID encoded in the connection ID
+    ///
+    /// `None` is returned if the connection ID is unroutable or uses an unknown configuration.
+    pub fn decode(&self, connection_id: &[u8]) -> Option<ServerId> {
+        let config_id = *connection_id.first()? >> 5;
+        let cipher = self.ciphers.get(config_id as usize)?.as_ref()?;
+        let (plaintext, _len) = cipher.decode(connection_id)?;
+
+        let mut server_id = ServerId {
+            len: cipher.config.server_id_len as u8,
+            ..Default::default()
+        };
+        server_id.bytes[..cipher.config.server_id_len]
+            .copy_from_slice(&plaintext[..cipher.config.server_id_len]);
+        Some(server_id)
+    }
+}
+
+/// Generates QUIC-LB connection IDs for the local server
+///
+/// Only connection IDs containing the local server ID are considered valid.
+#[derive(Debug)]
+pub struct Format {
+    cipher: Cipher,
+    server_id: ServerId,
+    lifetime: Option<Duration>,
+    rotate_handshake_connection_id: bool,
+}
+
+impl Format {
+    /// Creates a builder for the format with the local server ID
+    pub fn builder(config: Config, server_id: &[u8]) -> Result<Builder, Error> {
+        if server_id.len() != config.server_id_len {
+            return Err(Error::InvalidServerIdLen);
+        }
+
+        let mut id = ServerId {
+            len: server_id.len() as u8,
+            ..Default::default()
+        };
+        id.bytes[..server_id.len()].copy_from_slice(server_id);
+
+        Ok(Builder {
+            config,
+            server_id: id,
+            lifetime: None,
+            rotate_handshake_connection_id: true,
+        })
+    }
+}
+
+/// A builder for [`Format`] providers
+#[derive(Debug)]
+pub struct Builder {
+    config: Config,
+    server_id: ServerId,
+    lifetime: Option<Duration>,
+    rotate_handshake_connection_id: bool,
+}
+
+impl Builder {
+    /// Sets the lifetime of each generated connection Id
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Result<Self, connection::id::Error> {
+        if !(connection::id::MIN_LIFETIME..=connection::id::MAX_LIFETIME).contains(&lifetime) {
+            return Err(connection::id::Error::InvalidLifetime);
+        }
+        self.lifetime = Some(lifetime);
+        Ok(self)
+    }
+
+    /// Enables/disables rotation of the connection Id used during the handshake (default: enabled)
+    pub fn with_handshake_connection_id_rotation(
+        mut self,
+        enabled: bool,
+    ) -> Result<Self, core::convert::Infallible> {
+        self.rotate_handshake_connection_id = enabled;
+        Ok(self)
+    }
+
+    /// Builds the [`Format`] into a provider
+    pub fn build(self) -> Result<Format, core::convert::Infallible> {
+        Ok(Format {
+            cipher: Cipher::new(self.config),
+            server_id: self.server_id,
+            lifetime: self.lifetime,
+            rotate_handshake_connection_id: self.rotate_handshake_connection_id,
+        })
+    }
+}
+
+impl Generator for Format {
+    fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
+        let mut nonce = [0u8; *NONCE_LEN.end()];
+        let nonce = &mut nonce[..self.cipher.config.nonce_len];
+        rand::rng().fill_bytes(nonce);
+
+        let mut id = [0u8; connection::id::MAX_LEN];
+        let id = &mut id[..self.cipher.config.connection_id_len()];
+        self.cipher.encode(self.server_id.as_bytes(), nonce, id);
+        (&*id).try_into().expect("length already checked")
+    }
+
+    fn lifetime(&self) -> Option<Duration> {
+        self.lifetime
+    }
+
+    fn rotate_handshake_connection_id(&self) -> bool {
+        self.rotate_handshake_connection_id
+    }
+
+    fn is_first_byte_reserved(&self) -> bool {
+        // load balancers decode the server ID with the config rotation bits
+        true
+    }
+}
+
+impl Validator for Format {
+    fn validate(&self, _connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+        let (plaintext, len) = self.cipher.decode(buffer)?;
+        let server_id = self.server_id.as_bytes();
+
+        if &plaintext[..server_id.len()] == server_id {
+            Some(len)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The config ID is larger than [`MAX_CONFIG_ID`]
+    InvalidConfigId,
+    /// The server ID length is outside of [`SERVER_ID_LEN`] or doesn't match the [`Config`]
+    InvalidServerIdLen,
+    /// The nonce length is outside of [`NONCE_LEN`] or the connection ID would be too long
+    InvalidNonceLen,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidConfigId => write!(f, "config IDs must be at most {MAX_CONFIG_ID}"),
+            Self::InvalidServerIdLen => write!(f, "invalid server ID length"),
+            Self::InvalidNonceLen => write!(f, "invalid nonce length"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::inet::SocketAddress;
+
+    const KEY: [u8; KEY_LEN] = [
+        0x8f, 0x95, 0xf0, 0x92, 0x45, 0x76, 0x5f, 0x80, 0x25, 0x69, 0x34, 0xe5, 0x0c, 0x66, 0x20,
+        0x7f,
+    ];
+
+    fn configs() -> impl Iterator<Item = Config> {
+        (0..=MAX_CONFIG_ID).flat_map(|config_id| {
+            SERVER_ID_LEN.flat_map(move |server_id_len| {
+                NONCE_LEN
+                    .filter(move |nonce_len| server_id_len + nonce_len <= MAX_PLAINTEXT_LEN)
+                    .flat_map(move |nonce_len| {
+                        let config = Config::new(config_id, server_id_len, nonce_len).unwrap();
+                        [config.clone(), config.with_key(KEY)]
+                    })
+            })
+        })
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+
+        for config in configs() {
+            let server_id: Vec<u8> = (1..=config.server_id_len() as u8).collect();
+            let mut format = Format::builder(config.clone(), &server_id)
+                .unwrap()
+                .build()
+                .unwrap();
+            let decoder = Decoder::default().with_config(config.clone());
+
+            let mut other_server_id = server_id.clone();
+            other_server_id[0] ^= 0xff;
+            let mut other = Format::builder(config.clone(), &other_server_id)
+                .unwrap()
+                .build()
+                .unwrap();
+
+            for _ in 0..10 {
+                let id = format.generate(&connection_info);
+                assert_eq!(id.len(), config.connection_id_len());
+                assert_eq!(id.as_bytes()[0] >> 5, config.config_id());
+                assert_eq!(
+                    format.validate(&connection_info, id.as_bytes()),
+                    Some(id.len())
+                );
+                assert_eq!(
+                    decoder.decode(id.as_bytes()).unwrap().as_bytes(),
+                    &server_id[..]
+                );
+
+                // IDs for other servers are rejected
+                let other_id = other.generate(&connection_info);
+                assert_eq!(format.validate(&connection_info, other_id.as_bytes()), None);
+                assert_eq!(
+                    decoder.decode(other_id.as_bytes()).unwrap().as_bytes(),
+                    &other_server_id[..]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encryption_test() {
+        for (server_id_len, nonce_len) in [(3, 13), (3, 10), (4, 11)] {
+            let config = Config::new(0, server_id_len, nonce_len).unwrap();
+            let server_id = vec![0x31; server_id_len];
+            let nonce = vec![0x42; nonce_len];
+
+            let plaintext = Cipher::new(config.clone().with_length_self_encoding(true));
+            let encrypted = Cipher::new(config.with_key(KEY).with_length_self_encoding(true));
+
+            let mut plaintext_id = vec![0; plaintext.config.connection_id_len()];
+            plaintext.encode(&server_id, &nonce, &mut plaintext_id);
+            let mut encrypted_id = vec![0; encrypted.config.connection_id_len()];
+            encrypted.encode(&server_id, &nonce, &mut encrypted_id);
+
+            // the first octet is never encrypted
+            assert_eq!(plaintext_id[0], encrypted_id[0]);
+            assert_eq!(plaintext_id[0], (plaintext_id.len() - 1) as u8);
+            assert_eq!(&plaintext_id[1..=server_id_len], &server_id[..]);
+            assert_ne!(plaintext_id[1..], encrypted_id[1..]);
+
+            let (decrypted, len) = encrypted.decode(&encrypted_id).unwrap();
+            assert_eq!(len, encrypted_id.len());
+            assert_eq!(&decrypted[..len - 1], &plaintext_id[1..]);
+        }
+    }
+
+    /// Decodes a hex string from the test vectors
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_vectors() {
+        // Encrypted CIDs from Appendix B of draft-ietf-quic-load-balancers, followed by
+        // regression values for cases the appendix doesn't cover
+        let vectors = [
+            // four-pass with an odd length
+            (0, true, "ed793a", "ee080dbf", "0720b1d07b359d3c"),
+            (
+                1,
+                false,
+                "ed793a51d49b8f5fab65",
+                "ee080dbf48",
+                "2fcc381bc74cb4fbad2823a3d1f8fed2",
+            ),
+            // single-pass
+            (
+                2,
+                true,
+                "ed793a51d49b8f5f",
+                "ee080dbf48c0d1e5",
+                "504dd2d05a7b0de9b2b9907afb5ecf8cc3",
+            ),
+            // Regression value produced by this implementation rather than taken from the
+            // draft, which covers the four-pass algorithm with an even length
+            (
+                0,
+                true,
+                "ed793a51d49b8f5fab",
+                "ee080dbf48",
+                "0ef70943948d1ca868bf48fa98a528",
+            ),
+        ];
+
+        for (config_id, length_self_encoding, server_id, nonce, expected) in vectors {
+            let (server_id, nonce, expected) = (hex(server_id), hex(nonce), hex(expected));
+            let config = Config::new(config_id, server_id.len(), nonce.len())
+                .unwrap()
+                .with_key(KEY)
+                .with_length_self_encoding(length_self_encoding);
+            let cipher = Cipher::new(config.clone());
+
+            let mut id = vec![0; config.connection_id_len()];
+            cipher.encode(&server_id, &nonce, &mut id);
+
+            // the bits following the config rotation bits are random without length self-encoding
+            if length_self_encoding {
+                assert_eq!(id[0], expected[0]);
+            } else {
+                assert_eq!(id[0] >> 5, expected[0] >> 5);
+            }
+            assert_eq!(id[1..], expected[1..]);
+
+            let decoder = Decoder::default().with_config(config);
+            assert_eq!(
+                decoder.decode(&expected).unwrap().as_bytes(),
+                &server_id[..]
+            );
+        }
+    }
+
+    #[test]
+    fn decoder_test() {
+        let config_a = Config::new(1, 2, 8).unwrap().with_key(KEY);
+        let config_b = Config::new(2, 4, 6).unwrap();
+        let decoder = Decoder::default()
+            .with_config(config_a.clone())
+            .with_config(config_b.clone());
+
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+
+        let mut a = Format::builder(config_a, &[1, 2]).unwrap().build().unwrap();
+        let mut b = Format::builder(config_b, &[3, 4, 5, 6])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let id = a.generate(&connection_info);
+        assert_eq!(decoder.decode(id.as_bytes()).unwrap().as_bytes(), &[1, 2]);
+        let id = b.generate(&connection_info);
+        assert_eq!(
+            decoder.decode(id.as_bytes()).unwrap().as_bytes(),
+            &[3, 4, 5, 6]
+        );
+
+        // unknown and unroutable config IDs are rejected
+        let mut unknown = id.as_bytes().to_vec();
+        unknown[0] = 3 << 5;
+        assert_eq!(decoder.decode(&unknown), None);
+        unknown[0] = 0b111 << 5;
+        assert_eq!(decoder.decode(&unknown), None);
+
+        // truncated IDs are rejected
+        assert_eq!(decoder.decode(&id.as_bytes()[..4]), None);
+
+        let decoder = decoder.without_config(2);
+        assert_eq!(decoder.decode(id.as_bytes()), None);
+    }
+
+    #[test]
+    fn config_test() {
+        assert_eq!(Config::new(7, 1, 4), Err(Error::InvalidConfigId));
+        assert_eq!(Config::new(0, 0, 4), Err(Error::InvalidServerIdLen));
+        assert_eq!(Config::new(0, 16, 4), Err(Error::InvalidServerIdLen));
+        assert_eq!(Config::new(0, 1, 3), Err(Error::InvalidNonceLen));
+        assert_eq!(Config::new(0, 2, 18), Err(Error::InvalidNonceLen));
+        assert!(Config::new(0, 1, 18).is_ok());
+
+        let config = Config::new(0, 2, 4).unwrap();
+        assert_eq!(
+            Format::builder(config, &[1]).err(),
+            Some(Error::InvalidServerIdLen)
+        );
+    }
+}
@@ -0,0 +1,490 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Default provider for accepting early data
+//!
+//! The default provider only accepts early data from ClientHello messages that are fresh and
+//! that it hasn't seen before, as described in
+//! [RFC 8446 Section 8](https://www.rfc-editor.org/rfc/rfc8446#section-8).
+//!
+//! The `ticket_age_add` value of each session ticket issued by the server is recorded, which
+//! allows the age of the ticket reported by a ClientHello to be compared with the time since the
+//! ticket was issued. Early data is rejected if the two differ by more than the window, or if the
+//! ticket wasn't issued by this validator.
+//!
+//! The PSK binder of each fresh ClientHello is recorded and the early data of any ClientHello
+//! that was already seen is rejected. Entries are kept for at least one window and at most two,
+//! which covers every ClientHello that passes the freshness check. The binder can only be
+//! produced with the PSK, so a forged ClientHello can't take the place of a legitimate one.
+//!
+//! The registers are kept in memory, so early data is only accepted for tickets issued by the
+//! same server instance.
+
+use super::{Outcome, Request, SessionTicket};
+use core::{hash::BuildHasher, time::Duration};
+use s2n_quic_core::time::Timestamp;
+use std::collections::{hash_map::RandomState, HashMap};
+
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_TICKET_AGE: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_CAPACITY: usize = 100_000;
+
+#[derive(Debug)]
+pub struct Builder {
+    window: Duration,
+    max_ticket_age: Duration,
+    capacity: usize,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            max_ticket_age: DEFAULT_MAX_TICKET_AGE,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the window for the freshness check of a ClientHello (default: 60 seconds)
+    ///
+    /// Early data is rejected if the ticket age reported by the client differs from the time
+    /// since the ticket was issued by more than the window. ClientHello messages are remembered
+    /// for at least this long.
+    pub fn with_window(mut self, window: Duration) -> Result<Self, core::convert::Infallible> {
+        self.window = window;
+        Ok(self)
+    }
+
+    /// Sets the maximum age of a session ticket that can be used to send early data
+    /// (default: 1 hour)
+    ///
+    /// Session tickets are remembered for at least this long.
+    pub fn with_max_ticket_age(
+        mut self,
+        max_ticket_age: Duration,
+    ) -> Result<Self, core::convert::Infallible> {
+        self.max_ticket_age = max_ticket_age;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of ClientHello messages and session tickets remembered in each
+    /// window (default: 100000)
+    ///
+    /// Early data is rejected once the limit is reached, until the next window starts.
+    pub fn with_capacity(mut self, capacity: usize) -> Result<Self, core::convert::Infallible> {
+        self.capacity = capacity;
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Validator, core::convert::Infallible> {
+        Ok(Validator {
+            window: self.window,
+            max_ticket_age: self.max_ticket_age,
+            capacity: self.capacity,
+            hasher: RandomState::new(),
+            hellos: Register::new(self.window),
+            tickets: Register::new(self.max_ticket_age),
+        })
+    }
+}
+
+/// Rejects early data from stale or replayed ClientHello messages
+#[derive(Debug)]
+pub struct Validator {
+    window: Duration,
+    max_ticket_age: Duration,
+    capacity: usize,
+    hasher: RandomState,
+    /// The PSK binders of the accepted ClientHello messages
+    hellos: Register<()>,
+    /// The session tickets issued by the server
+    tickets: Register<Ticket>,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::builder()
+            .build()
+            .expect("the default builder should be valid")
+    }
+}
+
+impl Validator {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns `true` if the ticket age reported by the client is consistent with the time
+    /// since the ticket was issued
+    fn is_fresh(&self, request: &Request) -> bool {
+        let key = self.hasher.hash_one(request.psk_identity);
+
+        // the freshness can't be checked without the `ticket_age_add` value of the ticket
+        let Some(ticket) = self.tickets.get(&key) else {
+            return false;
+        };
+
+        let elapsed = request
+            .timestamp
+            .saturating_duration_since(ticket.issued_at);
+        if elapsed > self.max_ticket_age {
+            return false;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-8.3
+        //# The server can determine the
+        //# client's view of the age of the ticket by subtracting the ticket's
+        //# "ticket_age_add" value from the "obfuscated_ticket_age" parameter in
+        //# the client's "pre_shared_key" extension.
+        let age = request
+            .obfuscated_ticket_age
+            .wrapping_sub(ticket.ticket_age_add);
+        let age = Duration::from_millis(age as u64);
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-8.3
+        //# When a new ClientHello is received, the expected_arrival_time is then
+        //# compared against the current server wall clock time and if they
+        //# differ by more than a certain amount, 0-RTT is rejected, though the
+        //# 1-RTT handshake can be allowed to complete.
+        let difference = if age > elapsed {
+            age - elapsed
+        } else {
+            elapsed - age
+        };
+
+        difference <= self.window
+    }
+}
+
+impl super::Validator for Validator {
+    fn on_early_data_request(&mut self, request: &Request) -> Outcome {
+        self.hellos.rotate(request.timestamp);
+        self.tickets.rotate(request.timestamp);
+
+        // Stale ClientHello messages are rejected since they may no longer be in the register
+        if !self.is_fresh(request) {
+            return Outcome::Reject;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-8.2
+        //# Servers MAY
+        //# also implement data stores with false positives, such as Bloom
+        //# filters, in which case they MUST respond to apparent replay by
+        //# rejecting 0-RTT but MUST NOT abort the handshake.
+        // the binder is used instead of the random since a ClientHello with a forged binder
+        // could otherwise record the random of a legitimate one before it arrives
+        let entry = self.hasher.hash_one(request.psk_binder);
+
+        if self.hellos.get(&entry).is_some() {
+            return Outcome::Reject;
+        }
+
+        // fail closed if the register is full
+        if self.hellos.len() >= self.capacity {
+            return Outcome::Reject;
+        }
+
+        self.hellos.insert(entry, ());
+
+        Outcome::Accept
+    }
+
+    fn on_session_ticket_issued(&mut self, ticket: &SessionTicket) {
+        self.tickets.rotate(ticket.timestamp);
+
+        // early data for tickets that can't be recorded is rejected
+        if self.tickets.len() >= self.capacity {
+            return;
+        }
+
+        let key = self.hasher.hash_one(ticket.identity);
+        self.tickets.insert(
+            key,
+            Ticket {
+                issued_at: ticket.timestamp,
+                ticket_age_add: ticket.ticket_age_add,
+            },
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Ticket {
+    issued_at: Timestamp,
+    ticket_age_add: u32,
+}
+
+/// Entries that are kept for at least one period and at most two
+#[derive(Debug)]
+struct Register<V> {
+    period: Duration,
+    current: HashMap<u64, V>,
+    previous: HashMap<u64, V>,
+    rotates_at: Option<Timestamp>,
+}
+
+impl<V> Register<V> {
+    fn new(period: Duration) -> Self {
+        Self {
+            period,
+            current: HashMap::new(),
+            previous: HashMap::new(),
+            rotates_at: None,
+        }
+    }
+
+    /// Discards the entries that are older than two periods
+    fn rotate(&mut self, now: Timestamp) {
+        match self.rotates_at {
+            Some(rotates_at) if now < rotates_at => {}
+            Some(rotates_at) if now < rotates_at + self.period => {
+                core::mem::swap(&mut self.current, &mut self.previous);
+                self.current.clear();
+                self.rotates_at = Some(rotates_at + self.period);
+            }
+            _ => {
+                self.current.clear();
+                self.previous.clear();
+                self.rotates_at = Some(now + self.period);
+            }
+        }
+    }
+
+    fn get(&self, key: &u64) -> Option<&V> {
+        self.current.get(key).or_else(|| self.previous.get(key))
+    }
+
+    /// Returns the number of entries added in the current period
+    fn len(&self) -> usize {
+        self.current.len()
+    }
+
+    fn insert(&mut self, key: u64, value: V) {
+        self.current.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::Validator as _, *};
+    use s2n_quic_core::{
+        early_data::ClientHello,
+        inet::SocketAddress,
+        time::{testing::Clock, Clock as _},
+    };
+
+    const TICKET: &[u8] = b"ticket";
+    const TICKET_AGE_ADD: u32 = 0xfedc_ba98;
+
+    /// Issues the ticket used by the requests and returns the time it was issued
+    fn issue_ticket(validator: &mut Validator, clock: &Clock) -> Timestamp {
+        let issued_at = clock.get_time();
+        let ticket = SessionTicket::new(
+            TICKET,
+            TICKET_AGE_ADD,
+            Duration::from_secs(7 * 24 * 60 * 60),
+            issued_at,
+        );
+        validator.on_session_ticket_issued(&ticket);
+        issued_at
+    }
+
+    /// Sends a ClientHello with the given binder that reports the given ticket age
+    fn request_with_age(
+        validator: &mut Validator,
+        clock: &Clock,
+        binder: u8,
+        ticket_age: Duration,
+    ) -> Outcome {
+        let remote_address = SocketAddress::default();
+        let binder = [binder; 32];
+        let hello = ClientHello {
+            random: &[0; 32],
+            psk_identity: TICKET,
+            obfuscated_ticket_age: (ticket_age.as_millis() as u32).wrapping_add(TICKET_AGE_ADD),
+            psk_binder: &binder,
+        };
+        let request = Request::new(&remote_address, &hello, clock.get_time());
+        validator.on_early_data_request(&request)
+    }
+
+    /// Sends a ClientHello that reports the actual age of the ticket
+    fn request(
+        validator: &mut Validator,
+        clock: &Clock,
+        issued_at: Timestamp,
+        binder: u8,
+    ) -> Outcome {
+        let ticket_age = clock.get_time().saturating_duration_since(issued_at);
+        request_with_age(validator, clock, binder, ticket_age)
+    }
+
+    #[test]
+    fn replay_test() {
+        let mut validator = Validator::default();
+        let mut clock = Clock::default();
+        let issued_at = issue_ticket(&mut validator, &clock);
+
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Accept
+        );
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Reject
+        );
+        // the requests share the same random, which could be copied by a forged ClientHello
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 2),
+            Outcome::Accept
+        );
+
+        // entries are remembered for at least one window
+        clock.inc_by(DEFAULT_WINDOW);
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Reject
+        );
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 2),
+            Outcome::Reject
+        );
+
+        // entries are forgotten after two windows
+        clock.inc_by(DEFAULT_WINDOW);
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Accept
+        );
+
+        // all entries are forgotten after a long period of inactivity
+        clock.inc_by(DEFAULT_WINDOW * 10);
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Accept
+        );
+    }
+
+    #[test]
+    fn capacity_test() {
+        let mut validator = Validator::builder()
+            .with_capacity(2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut clock = Clock::default();
+        let issued_at = issue_ticket(&mut validator, &clock);
+
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Accept
+        );
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 2),
+            Outcome::Accept
+        );
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 3),
+            Outcome::Reject
+        );
+
+        // the next window has room for new entries
+        clock.inc_by(DEFAULT_WINDOW);
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 3),
+            Outcome::Accept
+        );
+    }
+
+    #[test]
+    fn replay_after_rotation_test() {
+        let mut validator = Validator::default();
+        let mut clock = Clock::default();
+        let issued_at = issue_ticket(&mut validator, &clock);
+
+        clock.inc_by(Duration::from_secs(5));
+        let ticket_age = clock.get_time().saturating_duration_since(issued_at);
+        assert_eq!(
+            request_with_age(&mut validator, &clock, 1, ticket_age),
+            Outcome::Accept
+        );
+
+        // the ClientHello is no longer in the register once it's replayed two windows later,
+        // but the ticket age it reports is stale
+        clock.inc_by(DEFAULT_WINDOW * 2);
+        assert_eq!(
+            request_with_age(&mut validator, &clock, 1, ticket_age),
+            Outcome::Reject
+        );
+    }
+
+    #[test]
+    fn freshness_test() {
+        let mut validator = Validator::default();
+        let mut clock = Clock::default();
+        let issued_at = issue_ticket(&mut validator, &clock);
+
+        clock.inc_by(Duration::from_secs(100));
+        let ticket_age = clock.get_time().saturating_duration_since(issued_at);
+
+        // the reported age may differ from the actual age by up to the window
+        assert_eq!(
+            request_with_age(&mut validator, &clock, 1, ticket_age - DEFAULT_WINDOW),
+            Outcome::Accept
+        );
+        assert_eq!(
+            request_with_age(&mut validator, &clock, 2, ticket_age + DEFAULT_WINDOW),
+            Outcome::Accept
+        );
+        assert_eq!(
+            request_with_age(
+                &mut validator,
+                &clock,
+                3,
+                ticket_age - DEFAULT_WINDOW - Duration::from_millis(1)
+            ),
+            Outcome::Reject
+        );
+        assert_eq!(
+            request_with_age(
+                &mut validator,
+                &clock,
+                4,
+                ticket_age + DEFAULT_WINDOW + Duration::from_millis(1)
+            ),
+            Outcome::Reject
+        );
+    }
+
+    #[test]
+    fn unknown_ticket_test() {
+        // the freshness of tickets that weren't issued by the validator can't be checked
+        let mut validator = Validator::default();
+        let clock = Clock::default();
+        assert_eq!(
+            request_with_age(&mut validator, &clock, 1, Duration::ZERO),
+            Outcome::Reject
+        );
+    }
+
+    #[test]
+    fn max_ticket_age_test() {
+        let mut validator = Validator::default();
+        let mut clock = Clock::default();
+        let issued_at = issue_ticket(&mut validator, &clock);
+
+        clock.inc_by(DEFAULT_MAX_TICKET_AGE);
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 1),
+            Outcome::Accept
+        );
+
+        clock.inc_by(Duration::from_secs(1));
+        assert_eq!(
+            request(&mut validator, &clock, issued_at, 2),
+            Outcome::Reject
+        );
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to decide if the early data sent by a client in 0-RTT packets is accepted
+//!
+//! Early data is only accepted if the TLS provider is also configured to accept it. Applications
+//! can check if data on a stream was received as early data with
+//! [`ReceiveStream::is_early_data`](crate::stream::ReceiveStream::is_early_data).
+
+pub use s2n_quic_core::early_data::{
+    allow_all, disabled, Outcome, Request, SessionTicket, Validator,
+};
+
+pub trait Provider: 'static {
+    type Validator: 'static + Send + Validator;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    /// Starts the early data provider
+    fn start(self) -> Result<Self::Validator, Self::Error>;
+}
+
+pub mod default;
+
+pub use default::Validator as Default;
+
+impl_provider_utils!();
+
+impl<T: 'static + Send + Validator> Provider for T {
+    type Validator = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Validator, Self::Error> {
+        Ok(self)
+    }
+}
@@ -33,6 +33,14 @@ pub mod tracing;
 #[cfg(feature = "provider-event-console-perf")]
 pub mod console_perf;
 
+/// Provides an implementation to export aggregate metrics in the OpenMetrics format
+#[cfg(feature = "provider-event-openmetrics")]
+pub mod openmetrics;
+
+/// Provides an implementation to write [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/) traces
+#[cfg(feature = "provider-event-qlog")]
+pub mod qlog;
+
 cfg_if! {
     if #[cfg(any(feature = "provider-event-tracing", test))] {
         pub use self::tracing as default;
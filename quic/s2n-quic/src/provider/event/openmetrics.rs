@@ -0,0 +1,26 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records aggregate metrics for each event and renders them in the
+//! [OpenMetrics](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md)
+//! text format
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use s2n_quic::{provider::event::openmetrics, Server};
+//!
+//! let registry = openmetrics::Registry::default().with_prefix("s2n_quic");
+//!
+//! let server = Server::builder()
+//!     .with_event(openmetrics::Subscriber::new(registry.clone()))?
+//!     .start()?;
+//!
+//! // the rendered metrics can be served directly on a `/metrics` endpoint
+//! let body = registry.to_string();
+//! ```
+
+pub use s2n_quic_core::event::metrics::aggregate::openmetrics::Registry;
+
+/// An event subscriber which records aggregate metrics in a [`Registry`]
+pub type Subscriber = s2n_quic_core::event::metrics::aggregate::Subscriber<Registry>;
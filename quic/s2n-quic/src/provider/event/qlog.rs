@@ -0,0 +1,661 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::event::{self, events, Timestamp};
+use core::{
+    fmt::Write as _,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    fs::File,
+    io::Write as _,
+    net::IpAddr,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The qlog schema version written in each trace header
+const QLOG_VERSION: &str = "0.3";
+
+/// The record separator that prefixes each JSON-SEQ record
+///
+/// See [RFC 7464](https://www.rfc-editor.org/rfc/rfc7464)
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// The number of buffered bytes at which a trace is written to its file
+const FLUSH_THRESHOLD: usize = 64 * 1024;
+
+/// Distinguishes traces for connections that share an original destination connection ID
+static TRACE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An event subscriber that writes a [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+/// trace for each connection
+///
+/// Each connection is written to its own file in the configured directory, named
+/// `<prefix><endpoint type>-<original destination connection id>-<trace id>.sqlog`, where the
+/// trace id is unique within the process. The client and server traces for a connection share
+/// the same original destination connection ID, which is also used as the qlog `group_id`.
+/// The traces use the qlog 0.3 JSON-SEQ serialization format and can be loaded directly into
+/// [qvis](https://qvis.quictools.info).
+///
+/// The following events are currently recorded:
+///
+/// * `connectivity:connection_started` and `connectivity:connection_closed`
+/// * `transport:packet_sent` and `transport:packet_received`, including the frames in each packet
+/// * `recovery:metrics_updated`, `recovery:congestion_state_updated` and `recovery:packet_lost`
+/// * `security:key_updated` and `security:key_discarded`
+///
+/// NOTE: The set of recorded events is subject to change. Records are buffered in memory and
+/// written in large chunks, but tracing every connection still has a significant performance
+/// cost and is intended for debugging.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use s2n_quic::{provider::event, Server};
+///
+/// let server = Server::builder()
+///     .with_event(event::qlog::Subscriber::new("/tmp/qlog"))?
+///     .start()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Subscriber {
+    dir: PathBuf,
+    prefix: String,
+}
+
+impl Subscriber {
+    /// Creates a subscriber that writes traces to the given directory
+    ///
+    /// The directory is created if it does not exist.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: String::new(),
+        }
+    }
+
+    /// Creates a subscriber that writes traces to the directory in the `QLOGDIR`
+    /// environment variable, if set
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var_os("QLOGDIR")?;
+        Some(Self::new(dir))
+    }
+
+    /// Sets a prefix for each trace file name
+    pub fn with_prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+/// The qlog trace for a single connection
+pub struct ConnectionContext {
+    output: Output,
+    /// Records that have not been written to the file yet
+    buffer: String,
+    endpoint: &'static str,
+    start: Timestamp,
+    /// The wall clock time corresponding to `start`
+    reference_time: Duration,
+    /// The frames sent in the packet currently being assembled
+    tx_frames: String,
+    /// A received packet whose frames are still being processed
+    rx_packet: Option<RxPacket>,
+}
+
+enum Output {
+    /// The file is created once the original destination connection ID is known
+    Pending,
+    File(File),
+    Closed,
+}
+
+struct RxPacket {
+    timestamp: Timestamp,
+    header: events::PacketHeader,
+    frames: String,
+}
+
+impl ConnectionContext {
+    fn new(meta: &event::ConnectionMeta) -> Self {
+        let reference_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Self {
+            output: Output::Pending,
+            buffer: String::new(),
+            endpoint: endpoint_type(&meta.endpoint_type),
+            start: meta.timestamp,
+            reference_time,
+            tx_frames: String::new(),
+            rx_packet: None,
+        }
+    }
+
+    /// Creates the trace file and writes the header ahead of any buffered records
+    fn open(&mut self, subscriber: &Subscriber, original_destination_connection_id: &[u8]) {
+        let endpoint = self.endpoint;
+        let odcid = hex(original_destination_connection_id);
+        let trace_id = TRACE_ID.fetch_add(1, Ordering::Relaxed);
+        let name = format!("{}{endpoint}-{odcid}-{trace_id}.sqlog", subscriber.prefix);
+
+        self.output = match std::fs::create_dir_all(&subscriber.dir)
+            .and_then(|_| File::create(subscriber.dir.join(name)))
+        {
+            Ok(file) => Output::File(file),
+            Err(_) => Output::Closed,
+        };
+
+        let mut header = String::with_capacity(self.buffer.len() + 256);
+        let _ = writeln!(
+            header,
+            "{RECORD_SEPARATOR}{{\"qlog_version\":\"{QLOG_VERSION}\",\"qlog_format\":\"JSON-SEQ\",\
+            \"title\":\"s2n-quic\",\"trace\":{{\"vantage_point\":{{\"type\":\"{endpoint}\"}},\
+            \"common_fields\":{{\"group_id\":\"{odcid}\",\"time_format\":\"relative\",\
+            \"reference_time\":{}}}}}}}",
+            millis(self.reference_time),
+        );
+        header.push_str(&self.buffer);
+        self.buffer = header;
+    }
+
+    fn write_event(&mut self, timestamp: Timestamp, name: &str, data: &str) {
+        if matches!(self.output, Output::Closed) {
+            return;
+        }
+
+        let time = millis(timestamp.saturating_duration_since(self.start));
+        let _ = writeln!(
+            self.buffer,
+            "{RECORD_SEPARATOR}{{\"time\":{time},\"name\":\"{name}\",\"data\":{{{data}}}}}"
+        );
+
+        if self.buffer.len() >= FLUSH_THRESHOLD {
+            self.flush();
+        }
+    }
+
+    /// Writes the buffered records to the trace file, if it has been created
+    fn flush(&mut self) {
+        match &mut self.output {
+            Output::Pending => return,
+            Output::File(file) => {
+                if file.write_all(self.buffer.as_bytes()).is_err() {
+                    // stop tracing the connection rather than writing a corrupted trace
+                    self.output = Output::Closed;
+                }
+            }
+            Output::Closed => {}
+        }
+        self.buffer.clear();
+    }
+
+    /// Writes the pending received packet, now that all of its frames have been processed
+    fn flush_rx_packet(&mut self) {
+        if let Some(packet) = self.rx_packet.take() {
+            let data = packet_data(&packet.header, None, &packet.frames);
+            self.write_event(packet.timestamp, "transport:packet_received", &data);
+        }
+    }
+
+    fn finish(&mut self) {
+        self.flush_rx_packet();
+        self.flush();
+    }
+}
+
+impl Drop for ConnectionContext {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        _info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        ConnectionContext::new(meta)
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::ConnectionStarted,
+    ) {
+        context.open(self, event.original_destination_connection_id.bytes);
+
+        let path = &event.path;
+        let (local_ip, local_port) = socket_address(&path.local_addr);
+        let (remote_ip, remote_port) = socket_address(&path.remote_addr);
+        let ip_version = if local_ip.is_ipv4() { "ipv4" } else { "ipv6" };
+
+        let data = format!(
+            "\"ip_version\":\"{ip_version}\",\"src_ip\":\"{local_ip}\",\"src_port\":{local_port},\
+            \"dst_ip\":\"{remote_ip}\",\"dst_port\":{remote_port},\
+            \"src_cid\":\"{}\",\"dst_cid\":\"{}\"",
+            hex(path.local_cid.bytes),
+            hex(path.remote_cid.bytes),
+        );
+        context.write_event(meta.timestamp, "connectivity:connection_started", &data);
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        context.flush_rx_packet();
+        let data = format!("\"reason\":\"{}\"", escape(&event.error.to_string()));
+        context.write_event(meta.timestamp, "connectivity:connection_closed", &data);
+        context.finish();
+    }
+
+    fn on_frame_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::FrameSent,
+    ) {
+        push_frame(&mut context.tx_frames, &event.frame);
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        context.flush_rx_packet();
+        let frames = core::mem::take(&mut context.tx_frames);
+        let data = packet_data(&event.packet_header, Some(event.packet_len), &frames);
+        context.write_event(meta.timestamp, "transport:packet_sent", &data);
+    }
+
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        // the packet's frames are reported after the packet itself so it is held until
+        // the next non-frame event
+        context.flush_rx_packet();
+        context.rx_packet = Some(RxPacket {
+            timestamp: meta.timestamp,
+            header: event.packet_header.clone(),
+            frames: String::new(),
+        });
+    }
+
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::FrameReceived,
+    ) {
+        if let Some(packet) = context.rx_packet.as_mut() {
+            push_frame(&mut packet.frames, &event.frame);
+        }
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::PacketLost,
+    ) {
+        context.flush_rx_packet();
+        let data = format!("\"header\":{{{}}}", packet_header(&event.packet_header));
+        context.write_event(meta.timestamp, "recovery:packet_lost", &data);
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        context.flush_rx_packet();
+        let data = format!(
+            "\"min_rtt\":{},\"smoothed_rtt\":{},\"latest_rtt\":{},\"rtt_variance\":{},\
+            \"pto_count\":{},\"congestion_window\":{},\"bytes_in_flight\":{}",
+            millis(event.min_rtt),
+            millis(event.smoothed_rtt),
+            millis(event.latest_rtt),
+            millis(event.rtt_variance),
+            event.pto_count,
+            event.congestion_window,
+            event.bytes_in_flight,
+        );
+        context.write_event(meta.timestamp, "recovery:metrics_updated", &data);
+    }
+
+    fn on_congestion(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::Congestion,
+    ) {
+        context.flush_rx_packet();
+        let trigger = match event.source {
+            events::CongestionSource::Ecn { .. } => "ECN",
+            events::CongestionSource::PacketLoss { .. } => "packet_loss",
+            _ => "unknown",
+        };
+        let data = format!("\"new\":\"recovery\",\"trigger\":\"{trigger}\"");
+        context.write_event(meta.timestamp, "recovery:congestion_state_updated", &data);
+    }
+
+    fn on_slow_start_exited(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        _event: &events::SlowStartExited,
+    ) {
+        context.flush_rx_packet();
+        let data = "\"old\":\"slow_start\",\"new\":\"congestion_avoidance\"";
+        context.write_event(meta.timestamp, "recovery:congestion_state_updated", data);
+    }
+
+    fn on_bbr_state_changed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::BbrStateChanged,
+    ) {
+        context.flush_rx_packet();
+        let state = match event.state {
+            events::BbrState::Startup { .. } => "startup",
+            events::BbrState::Drain { .. } => "drain",
+            events::BbrState::ProbeBwDown { .. } => "probe_bw_down",
+            events::BbrState::ProbeBwCruise { .. } => "probe_bw_cruise",
+            events::BbrState::ProbeBwRefill { .. } => "probe_bw_refill",
+            events::BbrState::ProbeBwUp { .. } => "probe_bw_up",
+            events::BbrState::ProbeRtt { .. } => "probe_rtt",
+            _ => "unknown",
+        };
+        let data = format!("\"new\":\"{state}\"");
+        context.write_event(meta.timestamp, "recovery:congestion_state_updated", &data);
+    }
+
+    fn on_key_update(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::KeyUpdate,
+    ) {
+        context.flush_rx_packet();
+        let (space, generation) = match event.key_type {
+            events::KeyType::Initial { .. } => ("initial", None),
+            events::KeyType::Handshake { .. } => ("handshake", None),
+            events::KeyType::ZeroRtt { .. } => ("0rtt", None),
+            events::KeyType::OneRtt { generation, .. } => ("1rtt", Some(generation)),
+            _ => return,
+        };
+
+        // s2n-quic derives the keys for both directions at the same time
+        for endpoint in ["client", "server"] {
+            let mut data = format!("\"key_type\":\"{endpoint}_{space}_secret\"");
+            if let Some(generation) = generation {
+                let trigger = if generation == 0 {
+                    "tls"
+                } else {
+                    "local_update"
+                };
+                let _ = write!(
+                    data,
+                    ",\"generation\":{generation},\"trigger\":\"{trigger}\""
+                );
+            } else {
+                data.push_str(",\"trigger\":\"tls\"");
+            }
+            context.write_event(meta.timestamp, "security:key_updated", &data);
+        }
+    }
+
+    fn on_key_space_discarded(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::KeySpaceDiscarded,
+    ) {
+        context.flush_rx_packet();
+        let space = match event.space {
+            events::KeySpace::Initial { .. } => "initial",
+            events::KeySpace::Handshake { .. } => "handshake",
+            events::KeySpace::ZeroRtt { .. } => "0rtt",
+            events::KeySpace::OneRtt { .. } => "1rtt",
+            _ => return,
+        };
+
+        for endpoint in ["client", "server"] {
+            let data = format!("\"key_type\":\"{endpoint}_{space}_secret\"");
+            context.write_event(meta.timestamp, "security:key_discarded", &data);
+        }
+    }
+}
+
+fn endpoint_type(endpoint_type: &events::EndpointType) -> &'static str {
+    match endpoint_type {
+        events::EndpointType::Client { .. } => "client",
+        _ => "server",
+    }
+}
+
+/// Formats the `data` of a `packet_sent` or `packet_received` event
+fn packet_data(header: &events::PacketHeader, len: Option<usize>, frames: &str) -> String {
+    let mut data = format!("\"header\":{{{}}}", packet_header(header));
+    if let Some(len) = len {
+        let _ = write!(data, ",\"raw\":{{\"length\":{len}}}");
+    }
+    let _ = write!(data, ",\"frames\":[{frames}]");
+    data
+}
+
+fn packet_header(header: &events::PacketHeader) -> String {
+    use events::PacketHeader::*;
+
+    let (packet_type, number) = match header {
+        Initial { number, .. } => ("initial", Some(number)),
+        Handshake { number, .. } => ("handshake", Some(number)),
+        ZeroRtt { number, .. } => ("0RTT", Some(number)),
+        OneRtt { number, .. } => ("1RTT", Some(number)),
+        Retry { .. } => ("retry", None),
+        VersionNegotiation { .. } => ("version_negotiation", None),
+        StatelessReset { .. } => ("stateless_reset", None),
+        _ => ("unknown", None),
+    };
+
+    match number {
+        Some(number) => format!("\"packet_type\":\"{packet_type}\",\"packet_number\":{number}"),
+        None => format!("\"packet_type\":\"{packet_type}\""),
+    }
+}
+
+/// Appends the qlog representation of `frame` to a comma-separated list of frames
+fn push_frame(frames: &mut String, frame: &events::Frame) {
+    use events::{Frame::*, StreamType};
+
+    fn stream_type(stream_type: &StreamType) -> &'static str {
+        match stream_type {
+            StreamType::Unidirectional { .. } => "unidirectional",
+            _ => "bidirectional",
+        }
+    }
+
+    if !frames.is_empty() {
+        frames.push(',');
+    }
+
+    let _ = match frame {
+        Padding { .. } => write!(frames, "{{\"frame_type\":\"padding\"}}"),
+        Ping { .. } => write!(frames, "{{\"frame_type\":\"ping\"}}"),
+        Ack {
+            largest_acknowledged,
+            ack_range_count,
+            ecn_counts,
+            ..
+        } => {
+            let _ = write!(
+                frames,
+                "{{\"frame_type\":\"ack\",\"largest_acknowledged\":{largest_acknowledged},\
+                \"ack_range_count\":{ack_range_count}"
+            );
+            if let Some(ecn) = ecn_counts {
+                let _ = write!(
+                    frames,
+                    ",\"ect0\":{},\"ect1\":{},\"ce\":{}",
+                    ecn.ect_0_count, ecn.ect_1_count, ecn.ce_count
+                );
+            }
+            write!(frames, "}}")
+        }
+        ResetStream {
+            id,
+            error_code,
+            final_size,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"reset_stream\",\"stream_id\":{id},\"error_code\":{error_code},\
+            \"final_size\":{final_size}}}"
+        ),
+        StopSending { id, error_code, .. } => write!(
+            frames,
+            "{{\"frame_type\":\"stop_sending\",\"stream_id\":{id},\"error_code\":{error_code}}}"
+        ),
+        Crypto { offset, len, .. } => write!(
+            frames,
+            "{{\"frame_type\":\"crypto\",\"offset\":{offset},\"length\":{len}}}"
+        ),
+        NewToken { .. } => write!(frames, "{{\"frame_type\":\"new_token\"}}"),
+        Stream {
+            id,
+            offset,
+            len,
+            is_fin,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"stream\",\"stream_id\":{id},\"offset\":{offset},\
+            \"length\":{len},\"fin\":{is_fin}}}"
+        ),
+        MaxData { value, .. } => {
+            write!(frames, "{{\"frame_type\":\"max_data\",\"maximum\":{value}}}")
+        }
+        MaxStreamData { id, value, .. } => write!(
+            frames,
+            "{{\"frame_type\":\"max_stream_data\",\"stream_id\":{id},\"maximum\":{value}}}"
+        ),
+        MaxStreams {
+            stream_type: ty,
+            value,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"max_streams\",\"stream_type\":\"{}\",\"maximum\":{value}}}",
+            stream_type(ty)
+        ),
+        DataBlocked { data_limit, .. } => write!(
+            frames,
+            "{{\"frame_type\":\"data_blocked\",\"limit\":{data_limit}}}"
+        ),
+        StreamDataBlocked {
+            stream_id,
+            stream_data_limit,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"stream_data_blocked\",\"stream_id\":{stream_id},\
+            \"limit\":{stream_data_limit}}}"
+        ),
+        StreamsBlocked {
+            stream_type: ty,
+            stream_limit,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"streams_blocked\",\"stream_type\":\"{}\",\"limit\":{stream_limit}}}",
+            stream_type(ty)
+        ),
+        NewConnectionId {
+            sequence_number,
+            retire_prior_to,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"new_connection_id\",\"sequence_number\":{sequence_number},\
+            \"retire_prior_to\":{retire_prior_to}}}"
+        ),
+        RetireConnectionId { .. } => {
+            write!(frames, "{{\"frame_type\":\"retire_connection_id\"}}")
+        }
+        PathChallenge { .. } => write!(frames, "{{\"frame_type\":\"path_challenge\"}}"),
+        PathResponse { .. } => write!(frames, "{{\"frame_type\":\"path_response\"}}"),
+        ConnectionClose { .. } => write!(frames, "{{\"frame_type\":\"connection_close\"}}"),
+        HandshakeDone { .. } => write!(frames, "{{\"frame_type\":\"handshake_done\"}}"),
+        Datagram { len, .. } => {
+            write!(frames, "{{\"frame_type\":\"datagram\",\"length\":{len}}}")
+        }
+        AckFrequency {
+            sequence_number,
+            ack_eliciting_threshold,
+            request_max_ack_delay,
+            reordering_threshold,
+            ..
+        } => write!(
+            frames,
+            "{{\"frame_type\":\"ack_frequency\",\"sequence_number\":{sequence_number},\
+            \"ack_eliciting_threshold\":{ack_eliciting_threshold},\
+            \"request_max_ack_delay\":{},\"reordering_threshold\":{reordering_threshold}}}",
+            millis(Duration::from_micros(*request_max_ack_delay))
+        ),
+        ImmediateAck { .. } => write!(frames, "{{\"frame_type\":\"immediate_ack\"}}"),
+        _ => write!(frames, "{{\"frame_type\":\"unknown\"}}"),
+    };
+}
+
+fn socket_address(addr: &events::SocketAddress) -> (IpAddr, u16) {
+    match addr {
+        events::SocketAddress::IpV4 { ip, port, .. } => (IpAddr::from(**ip), *port),
+        events::SocketAddress::IpV6 { ip, port, .. } => (IpAddr::from(**ip), *port),
+        _ => (IpAddr::from([0u8; 4]), 0),
+    }
+}
+
+/// Formats a duration as fractional milliseconds
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+/// Escapes a string for inclusion in a JSON string literal
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -19,12 +19,18 @@ pub trait Provider: 'static {
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
 pub mod testing;
 
+#[cfg(feature = "unstable-provider-io-pcap")]
+pub mod pcap;
+
 #[cfg(feature = "unstable-provider-io-turmoil")]
 pub mod turmoil;
 
 #[cfg(feature = "unstable-provider-io-xdp")]
 pub mod xdp;
 
+#[cfg(all(feature = "unstable-provider-io-uring", target_os = "linux"))]
+pub mod uring;
+
 pub mod tokio;
 
 pub use self::tokio as default;
@@ -0,0 +1,60 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides an [`io::Provider`](crate::provider::io::Provider) which wraps another provider and
+//! records all of the datagrams sent and received by the endpoint into a
+//! [pcapng](https://datatracker.ietf.org/doc/draft-ietf-opsawg-pcapng/) capture file.
+//!
+//! TLS secrets can be embedded in the capture by passing the [`Capture::key_log`] writer to
+//! the TLS provider, which allows Wireshark to decrypt the capture on its own:
+//!
+//! ```rust,ignore
+//! use s2n_quic::{provider::io::pcap, Server};
+//!
+//! let capture = pcap::Capture::create("server.pcapng")?;
+//!
+//! let tls = s2n_quic::provider::tls::default::Server::builder()
+//!     .with_certificate(CERT_PEM, KEY_PEM)?
+//!     .with_key_log(capture.key_log())?
+//!     .build()?;
+//!
+//! let server = Server::builder()
+//!     .with_io(pcap::Provider::new("127.0.0.1:4433", capture)?)?
+//!     .with_tls(tls)?
+//!     .start()?;
+//! ```
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
+
+pub use s2n_quic_platform::io::pcap::{Capture, KeyLog};
+
+/// Wraps an IO provider to record its datagrams into a [`Capture`]
+#[derive(Debug)]
+pub struct Provider<Io> {
+    io: Io,
+    capture: Capture,
+}
+
+impl<Io: super::Provider> Provider<Io> {
+    /// Records the datagrams of the provider converted from `io` into `capture`
+    pub fn new<T: super::TryInto<Provider = Io>>(
+        io: T,
+        capture: Capture,
+    ) -> Result<Self, T::Error> {
+        let io = io.try_into()?;
+        Ok(Self { io, capture })
+    }
+}
+
+impl<Io: super::Provider> super::Provider for Provider<Io> {
+    type PathHandle = Io::PathHandle;
+    type Error = Io::Error;
+
+    fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        let endpoint = self.capture.endpoint(endpoint);
+        self.io.start(endpoint)
+    }
+}
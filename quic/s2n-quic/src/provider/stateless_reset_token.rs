@@ -20,7 +20,8 @@
 /// packet routing, as well as ensuring the connection IDs in use are difficult to guess.
 ///
 /// Take these factors into consideration before enabling the Stateless Reset
-/// Token Generator. By default, stateless resets are not transmitted by s2n-quic endpoints,
+/// Token Generator. By default, stateless resets are only transmitted by s2n-quic endpoints
+/// once a static key has been configured,
 /// see [stateless_reset_token::Default][`crate::provider::stateless_reset_token::Default`].
 pub use s2n_quic_core::stateless_reset::token::Generator;
 
@@ -31,7 +32,15 @@ pub trait Provider: 'static {
     fn start(self) -> Result<Self::Generator, Self::Error>;
 }
 
-pub use random::Provider as Default;
+cfg_if::cfg_if! {
+    if #[cfg(feature = "provider-stateless-reset-token-default")] {
+        pub mod default;
+
+        pub use default::Provider as Default;
+    } else {
+        pub use random::Provider as Default;
+    }
+}
 
 impl_provider_utils!();
 
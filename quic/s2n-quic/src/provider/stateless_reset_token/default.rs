@@ -0,0 +1,195 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Default provider for stateless reset tokens
+//!
+//! Once a static key is configured, tokens are derived as `HMAC-SHA256(key, connection ID)`,
+//! truncated to the length of a stateless reset token. Since the tokens only depend on the key
+//! and the connection ID, an endpoint that restarts with the same key is able to reset
+//! connections that were established before it lost its state.
+//!
+//! Without a key, random tokens are generated and stateless resets are not transmitted.
+//!
+//! The same key must not be shared by endpoints that could be sent packets for each other's
+//! active connections. See [`Generator`](super::Generator) for more details.
+
+use core::{convert::Infallible, fmt};
+use s2n_quic_core::{frame::new_connection_id::STATELESS_RESET_TOKEN_LEN, stateless_reset};
+use s2n_quic_crypto::hmac;
+
+/// The minimum length of the static key material
+pub const MIN_KEY_LEN: usize = 16;
+
+#[derive(Debug, Default)]
+pub struct Builder {
+    key: Option<hmac::Key>,
+}
+
+impl Builder {
+    /// Sets the static key used to derive stateless reset tokens
+    ///
+    /// The key material should be at least 32 bytes from a secure random source, and must be
+    /// at least [`MIN_KEY_LEN`] bytes. Stateless resets are only transmitted once a key is
+    /// configured.
+    pub fn with_key(mut self, key: &[u8]) -> Result<Self, Error> {
+        if key.len() < MIN_KEY_LEN {
+            return Err(Error::KeyTooShort);
+        }
+        self.key = Some(hmac::Key::new(hmac::HMAC_SHA256, key));
+        Ok(self)
+    }
+
+    /// Builds the [`Provider`]
+    pub fn build(self) -> Result<Provider, Infallible> {
+        Ok(Provider(Generator { key: self.key }))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Provider(Generator);
+
+impl Provider {
+    /// Creates a builder for the provider
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl super::Provider for Provider {
+    type Generator = Generator;
+    type Error = Infallible;
+
+    fn start(self) -> Result<Self::Generator, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl super::TryInto for Generator {
+    type Provider = Provider;
+    type Error = Infallible;
+
+    fn try_into(self) -> Result<Self::Provider, Self::Error> {
+        Ok(Provider(self))
+    }
+}
+
+/// Derives stateless reset tokens from a static key
+#[derive(Default)]
+pub struct Generator {
+    key: Option<hmac::Key>,
+}
+
+impl fmt::Debug for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // don't include the key in the output
+        f.debug_struct("Generator")
+            .field("is_keyed", &self.key.is_some())
+            .finish()
+    }
+}
+
+impl stateless_reset::token::Generator for Generator {
+    #[inline]
+    fn is_enabled(&self) -> bool {
+        // Random tokens will not be recognized by the peer so stateless resets
+        // are only transmitted once a key has been configured
+        self.key.is_some()
+    }
+
+    fn generate(&mut self, local_connection_id: &[u8]) -> stateless_reset::Token {
+        let Some(key) = self.key.as_ref() else {
+            return super::random::Generator::default().generate(local_connection_id);
+        };
+
+        let tag = hmac::sign(key, local_connection_id);
+        let mut token = [0u8; STATELESS_RESET_TOKEN_LEN];
+        token.copy_from_slice(&tag.as_ref()[..STATELESS_RESET_TOKEN_LEN]);
+        token.into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The key material is shorter than [`MIN_KEY_LEN`]
+    KeyTooShort,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyTooShort => write!(
+                f,
+                "stateless reset token keys must be at least {MIN_KEY_LEN} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{connection, stateless_reset::token::Generator as _};
+
+    const KEY: &[u8] = &[42; 32];
+
+    fn keyed() -> Generator {
+        let provider = Provider::builder().with_key(KEY).unwrap().build().unwrap();
+        super::super::Provider::start(provider).unwrap()
+    }
+
+    #[test]
+    fn same_key_test() {
+        let id = connection::LocalId::try_from_bytes(b"id01").unwrap();
+
+        // a generator created after a restart derives the same token for the same id
+        let token_1 = keyed().generate(id.as_bytes());
+        let token_2 = keyed().generate(id.as_bytes());
+        assert_eq!(token_1, token_2);
+
+        let mut generator = keyed();
+        assert!(generator.is_enabled());
+        let other_id = connection::LocalId::try_from_bytes(b"id02").unwrap();
+        assert_ne!(token_1, generator.generate(other_id.as_bytes()));
+    }
+
+    #[test]
+    fn different_key_test() {
+        let id = connection::LocalId::try_from_bytes(b"id01").unwrap();
+
+        let mut other = Provider::builder()
+            .with_key(&[7; 32])
+            .unwrap()
+            .build()
+            .unwrap()
+            .0;
+        assert_ne!(
+            keyed().generate(id.as_bytes()),
+            other.generate(id.as_bytes())
+        );
+    }
+
+    #[test]
+    fn unkeyed_test() {
+        let mut generator = Generator::default();
+        assert!(!generator.is_enabled());
+
+        let id = connection::LocalId::try_from_bytes(b"id01").unwrap();
+        assert_ne!(
+            generator.generate(id.as_bytes()),
+            generator.generate(id.as_bytes())
+        );
+    }
+
+    #[test]
+    fn short_key_test() {
+        assert_eq!(
+            Provider::builder()
+                .with_key(&[1; MIN_KEY_LEN - 1])
+                .unwrap_err(),
+            Error::KeyTooShort
+        );
+    }
+}
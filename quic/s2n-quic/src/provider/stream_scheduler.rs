@@ -0,0 +1,31 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides the scheduler which orders the streams on a connection for transmission
+
+// this is only exposed as an unstable provider so we get warnings without this
+#[allow(unused_imports)]
+pub use s2n_quic_core::stream::scheduler::{
+    Endpoint, Fifo, Scheduler, StreamInfo, StrictPriority, WeightedRoundRobin,
+};
+
+/// Provides stream scheduler support for an endpoint
+pub trait Provider: 'static {
+    type Endpoint: Endpoint;
+    type Error: core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error>;
+}
+
+pub type Default = StrictPriority;
+
+impl_provider_utils!();
+
+impl<T: Endpoint> Provider for T {
+    type Endpoint = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides the set of QUIC versions supported by an endpoint
+//!
+//! By default, endpoints only use QUIC version 1. QUIC version 2
+//! ([RFC 9369](https://www.rfc-editor.org/rfc/rfc9369)) can be enabled alongside version 1
+//! and, optionally, preferred when opening new connections.
+//!
+//! When both endpoints enable more than one version, a server switches connections to its
+//! preferred version using compatible version negotiation
+//! ([RFC 9368](https://www.rfc-editor.org/rfc/rfc9368)), without an extra round trip.
+
+pub use s2n_quic_core::version::{Builder, Config, Error, Version, VERSION_1, VERSION_2};
+
+pub trait Provider {
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Config, Self::Error>;
+}
+
+pub use Config as Default;
+
+impl_provider_utils!();
+
+impl Provider for Config {
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Config, Self::Error> {
+        Ok(self)
+    }
+}
@@ -5,11 +5,14 @@ use crate::{connection::Connection, provider::*};
 use core::{
     fmt,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_transport::endpoint::handle::Acceptor;
 
 mod builder;
 mod providers;
+#[cfg(target_os = "linux")]
+pub mod sharded;
 
 pub use builder::*;
 pub use providers::*;
@@ -17,7 +20,10 @@ pub use s2n_quic_core::application::ServerName as Name;
 
 /// A QUIC server endpoint, capable of accepting connections
 pub struct Server {
-    acceptor: Acceptor,
+    /// The acceptors for each of the endpoints, which is more than one for a [`sharded`] server
+    acceptors: Vec<Acceptor>,
+    /// The index of the next acceptor to poll, which keeps workers from being starved
+    next_acceptor: usize,
     local_addr: s2n_quic_core::inet::SocketAddress,
 }
 
@@ -95,14 +101,102 @@ impl Server {
     ///   None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
         s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
-            match self.acceptor.poll_accept(cx) {
-                Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
-                Poll::Ready(None) => Poll::Ready(None),
-                Poll::Pending => Poll::Pending,
+            // poll each of the acceptors once, starting after the last one to return a
+            // connection
+            let mut index = self.next_acceptor;
+            for _ in 0..self.acceptors.len() {
+                if index >= self.acceptors.len() {
+                    index = 0;
+                }
+
+                match self.acceptors[index].poll_accept(cx) {
+                    Poll::Ready(Some(connection)) => {
+                        self.next_acceptor = index + 1;
+                        return Poll::Ready(Some(Connection::new(connection)));
+                    }
+                    Poll::Ready(None) => {
+                        // The endpoint has closed so it no longer needs to be polled. The order of
+                        // the remaining acceptors is preserved so the acceptor that is shifted
+                        // into `index` is polled next.
+                        self.acceptors.remove(index);
+                    }
+                    Poll::Pending => {
+                        index += 1;
+                    }
+                }
+            }
+            self.next_acceptor = index;
+
+            if self.acceptors.is_empty() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
             }
         })
     }
 
+    /// Gracefully shuts down the [`Server`].
+    ///
+    /// New connection attempts are refused, and all of the existing connections are notified
+    /// through [`Handle::endpoint_shutdown`](crate::connection::Handle::endpoint_shutdown), giving
+    /// the application a chance to finish any outstanding streams. Connections which are still
+    /// open after `deadline` are closed with the provided `error_code`.
+    ///
+    /// This function returns once all of the connections have closed. After that,
+    /// [`Server::accept`] will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path, time::Duration};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// const SHUTDOWN_ERROR_CODE: u32 = 1;
+    /// server
+    ///     .shutdown(Duration::from_secs(10), SHUTDOWN_ERROR_CODE.into())
+    ///     .await;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self, deadline: Duration, error_code: crate::application::Error) {
+        futures::future::poll_fn(|cx| self.poll_shutdown(deadline, error_code, cx)).await
+    }
+
+    /// Attempts to gracefully shut down the [`Server`].
+    ///
+    /// See [`Server::shutdown`] for more details.
+    ///
+    /// # Return value
+    ///
+    /// This function returns:
+    ///
+    /// - `Poll::Pending` if there are still connections open on the server.
+    /// - `Poll::Ready(())` once all of the connections have closed and the server has shut down.
+    pub fn poll_shutdown(
+        &mut self,
+        deadline: Duration,
+        error_code: crate::application::Error,
+        cx: &mut Context,
+    ) -> Poll<()> {
+        let mut is_ready = true;
+
+        for acceptor in &mut self.acceptors {
+            is_ready &= acceptor.poll_shutdown(deadline, error_code, cx).is_ready();
+        }
+
+        if is_ready {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
@@ -195,6 +195,35 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC version provider for the [`Server`]
+        ///
+        /// # Examples
+        ///
+        /// Accepts connections using both QUIC version 1 and QUIC version 2
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let versions = version::Config::builder()
+        ///     .with_version_2(true)?
+        ///     .build()?;
+        ///
+        /// let server = Server::builder()
+        ///     .with_version(versions)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the event provider for the [`Server`]
         ///
@@ -245,6 +274,40 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the early data provider for the [`Server`]
+        ///
+        /// The provider is called for each ClientHello that attempts to send early data and can
+        /// reject the data, for example to protect against replay. The data is only accepted if
+        /// the TLS provider is also configured to accept early data.
+        ///
+        /// # Examples
+        ///
+        /// Only accepts early data from ClientHello messages whose ticket age is within 10 seconds
+        /// of the time since the ticket was issued
+        ///
+        /// ```rust,no_run
+        /// # use std::{error::Error, time::Duration};
+        /// use s2n_quic::{Server, provider::early_data};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let early_data = early_data::Default::builder()
+        ///     .with_window(Duration::from_secs(10))?
+        ///     .build()?;
+        ///
+        /// let server = Server::builder()
+        ///     .with_early_data(early_data)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_early_data,
+        early_data,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the IO provider for the [`Server`]
         ///
@@ -347,6 +410,14 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-stream-scheduler"))]
+    impl_provider_method!(
+        /// Sets the stream scheduler provider for the [`Server`]
+        with_stream_scheduler,
+        stream_scheduler,
+        ServerProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Server`]
@@ -3,8 +3,8 @@
 
 use super::*;
 use core::marker::PhantomData;
-use s2n_quic_core::{connection::id::Generator, crypto, path};
-use s2n_quic_transport::{connection, endpoint, stream};
+use s2n_quic_core::{crypto, inet::SocketAddress, path};
+use s2n_quic_transport::{connection, endpoint, endpoint::handle::Acceptor, stream};
 
 impl_providers_state! {
     #[derive(Debug, Default)]
@@ -19,13 +19,16 @@ impl_providers_state! {
         event: Event,
         limits: Limits,
         mtu: Mtu,
+        version: Version,
         io: IO,
         path_migration: PathMigration,
         sync: Sync,
         tls: Tls,
         address_token: AddressToken,
+        early_data: EarlyData,
         datagram: Datagram,
         dc: Dc,
+        stream_scheduler: StreamScheduler,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -43,13 +46,16 @@ impl<
         Event: event::Provider,
         Limits: limits::Provider,
         Mtu: mtu::Provider,
+        Version: version::Provider,
         IO: io::Provider,
         PathMigration: path_migration::Provider,
         Sync: sync::Provider,
         Tls: tls::Provider,
         AddressToken: address_token::Provider,
+        EarlyData: early_data::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        StreamScheduler: stream_scheduler::Provider,
     >
     Providers<
         CongestionController,
@@ -62,16 +68,40 @@ impl<
         Event,
         Limits,
         Mtu,
+        Version,
         IO,
         PathMigration,
         Sync,
         Tls,
         AddressToken,
+        EarlyData,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
+        let (acceptor, local_addr) = self.start_endpoint(Ok)?;
+
+        Ok(Server {
+            acceptors: vec![acceptor],
+            next_acceptor: 0,
+            local_addr,
+        })
+    }
+
+    /// Starts the endpoint with the configured providers
+    ///
+    /// The started connection ID format is passed through `connection_id_format`, which allows
+    /// the IDs to be modified before they're issued, or the format to be rejected.
+    pub(crate) fn start_endpoint<ConnectionIdFormat, F>(
+        self,
+        connection_id_format: F,
+    ) -> Result<(Acceptor, SocketAddress), StartError>
+    where
+        ConnectionIdFormat: connection::id::Format,
+        F: FnOnce(ConnectionID::Format) -> Result<ConnectionIdFormat, StartError>,
+    {
         let Self {
             congestion_controller,
             connection_close_formatter,
@@ -83,33 +113,39 @@ impl<
             event,
             limits,
             mtu,
+            version,
             address_token,
+            early_data,
             io,
             path_migration,
             sync,
             tls,
             datagram,
             dc,
+            stream_scheduler,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
         let connection_close_formatter = connection_close_formatter
             .start()
             .map_err(StartError::new)?;
-        let connection_id = connection_id.start().map_err(StartError::new)?;
+        let connection_id = connection_id_format(connection_id.start().map_err(StartError::new)?)?;
         let packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
         let stateless_reset_token = stateless_reset_token.start().map_err(StartError::new)?;
         let random = random.start().map_err(StartError::new)?;
         let endpoint_limits = endpoint_limits.start().map_err(StartError::new)?;
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
+        let version = version.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
         let address_token = address_token.start().map_err(StartError::new)?;
+        let early_data = early_data.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = path_migration.start().map_err(StartError::new)?;
         let tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let stream_scheduler = stream_scheduler.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -135,13 +171,16 @@ impl<
             event,
             limits,
             mtu,
+            version,
             sync,
             tls,
             address_token,
+            early_data,
             path_handle: PhantomData,
             path_migration,
             datagram,
             dc,
+            stream_scheduler,
         };
 
         let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
@@ -149,10 +188,7 @@ impl<
         // Start the IO last
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
-        Ok(Server {
-            acceptor,
-            local_addr,
-        })
+        Ok((acceptor, local_addr))
     }
 }
 
@@ -173,8 +209,10 @@ struct EndpointConfig<
     Sync,
     Tls,
     AddressToken,
+    EarlyData,
     Datagram,
     Dc,
+    StreamScheduler,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -186,13 +224,16 @@ struct EndpointConfig<
     event: Event,
     limits: Limits,
     mtu: path::mtu::Manager<Mtu>,
+    version: s2n_quic_core::version::Config,
     sync: Sync,
     tls: Tls,
     address_token: AddressToken,
+    early_data: EarlyData,
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    stream_scheduler: StreamScheduler,
 }
 
 impl<
@@ -211,8 +252,10 @@ impl<
         Sync,
         Tls: crypto::tls::Endpoint,
         AddressToken: address_token::Format,
+        EarlyData: early_data::Validator,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        StreamScheduler: s2n_quic_core::stream::scheduler::Endpoint,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -230,8 +273,10 @@ impl<
         Sync,
         Tls,
         AddressToken,
+        EarlyData,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -255,8 +300,10 @@ impl<
         Sync: 'static + Send,
         Tls: crypto::tls::Endpoint,
         AddressToken: address_token::Format,
+        EarlyData: early_data::Validator,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        StreamScheduler: s2n_quic_core::stream::scheduler::Endpoint,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -274,8 +321,10 @@ impl<
         Sync,
         Tls,
         AddressToken,
+        EarlyData,
         Datagram,
         Dc,
+        StreamScheduler,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -293,11 +342,13 @@ impl<
     type TokenFormat = AddressToken;
     type ConnectionLimits = Limits;
     type Mtu = Mtu;
-    type StreamManager = stream::DefaultStreamManager;
+    type StreamManager = stream::DefaultStreamManager<StreamScheduler::Scheduler>;
     type PathMigrationValidator = PathMigration;
+    type EarlyDataValidator = EarlyData;
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type StreamSchedulerEndpoint = StreamScheduler;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
 
@@ -314,10 +365,13 @@ impl<
             token: &mut self.address_token,
             connection_limits: &mut self.limits,
             mtu: &mut self.mtu,
+            version: &self.version,
             event_subscriber: &mut self.event,
             path_migration: &mut self.path_migration,
+            early_data: &mut self.early_data,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            stream_scheduler: &mut self.stream_scheduler,
         }
     }
 }
@@ -0,0 +1,385 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs a [`Server`] across multiple endpoint workers
+//!
+//! Each worker runs its own endpoint event loop on a separate `SO_REUSEPORT` socket, all bound to
+//! the same address. This allows packet processing to be spread across the cores of a
+//! multi-threaded runtime, rather than being limited to the single task of a [`Server`] endpoint.
+//!
+//! A cBPF program is attached to the sockets, which steers each packet to the worker that owns its
+//! destination connection ID. Each worker encodes its index in the first byte of the connection IDs
+//! it issues, so packets continue to be routed to the same worker after the peer migrates to a new
+//! address. Connections from all of the workers are accepted through a single [`Server::accept`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use std::{error::Error, path::Path};
+//! use s2n_quic::{server::sharded, Server};
+//! #
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let mut server = sharded::Builder::default()
+//!     .with_address("127.0.0.1:443")?
+//!     .with_workers(4)?
+//!     .start(|_worker| {
+//!         Server::builder().with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))
+//!     })?;
+//!
+//! while let Some(connection) = server.accept().await {
+//!     println!("new connection: {:?}", connection.remote_addr());
+//! }
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::{
+    provider::{io, StartError},
+    server::{self, Server, ServerProviders},
+};
+use core::{fmt, time::Duration};
+use s2n_quic_core::connection::{
+    self,
+    id::{ConnectionInfo, Generator, Validator},
+};
+use s2n_quic_platform::{
+    bpf::cbpf::*,
+    socket::options::{Options, ReusePort},
+};
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+/// The maximum number of workers, since the worker index is encoded in a single byte
+const MAX_WORKERS: usize = u8::MAX as usize + 1;
+
+/// A builder for a [`Server`] with multiple endpoint workers
+#[derive(Debug)]
+pub struct Builder {
+    addr: Option<SocketAddr>,
+    workers: usize,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |workers| workers.get())
+            .min(MAX_WORKERS);
+
+        Self {
+            addr: None,
+            workers,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the address that each of the worker sockets is bound to
+    pub fn with_address<A: ToSocketAddrs>(mut self, addr: A) -> std::io::Result<Self> {
+        let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid address")
+        })?;
+        self.addr = Some(addr);
+        Ok(self)
+    }
+
+    /// Sets the number of endpoint workers (default: the available parallelism)
+    ///
+    /// The number of workers must be between 1 and 256.
+    pub fn with_workers(mut self, workers: usize) -> std::io::Result<Self> {
+        if !(1..=MAX_WORKERS).contains(&workers) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the number of workers must be between 1 and 256",
+            ));
+        }
+        self.workers = workers;
+        Ok(self)
+    }
+
+    /// Starts the [`Server`] workers
+    ///
+    /// The `builder` function is called with the index of each worker and returns the
+    /// [`server::Builder`] that configures its providers. The IO provider is replaced with the
+    /// worker's socket, and the generated connection IDs are modified to encode the worker index
+    /// in their first byte. Because of this, the connection ID provider should not rely on the
+    /// value of the first byte when validating connection IDs. QUIC-LB connection IDs carry their
+    /// config rotation bits in the first byte, so the QUIC-LB format is rejected.
+    ///
+    /// Any state that needs to be shared between the workers, such as the keys used for address
+    /// tokens, should be shared by the configured providers.
+    pub fn start<F, P, E>(self, mut builder: F) -> Result<Server, StartError>
+    where
+        F: FnMut(usize) -> Result<server::Builder<P>, E>,
+        P: ServerProviders,
+        E: 'static + fmt::Display + Send + Sync,
+    {
+        let addr = self.addr.ok_or_else(|| {
+            StartError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "missing bind address",
+            ))
+        })?;
+
+        let sockets = bind(addr, self.workers).map_err(StartError::new)?;
+        let local_addr = sockets[0].local_addr().map_err(StartError::new)?;
+
+        let mut acceptors = Vec::with_capacity(sockets.len());
+
+        for (index, socket) in sockets.into_iter().enumerate() {
+            let io = io::tokio::Provider::builder()
+                .with_rx_socket(socket)
+                .and_then(|builder| builder.build())
+                .map_err(StartError::new)?;
+
+            let providers = builder(index)
+                .map_err(StartError::new)?
+                .with_io(io)
+                .map_err(StartError::new)?
+                .0
+                .build();
+
+            let (acceptor, _local_addr) =
+                providers.start_endpoint(|format| Format::new(format, index as u8))?;
+
+            acceptors.push(acceptor);
+        }
+
+        Ok(Server {
+            acceptors,
+            next_acceptor: 0,
+            local_addr: local_addr.into(),
+        })
+    }
+}
+
+/// Binds a socket for each of the workers and attaches the steering program
+///
+/// The sockets are added to the `SO_REUSEPORT` group in order, which means the index of each socket
+/// in the returned `Vec` matches the index returned by the steering program.
+fn bind(addr: SocketAddr, workers: usize) -> std::io::Result<Vec<UdpSocket>> {
+    let mut options = Options::new(addr);
+    // set the reuse port option after binding to avoid joining a group owned by another process
+    options.reuse_port = ReusePort::AfterBind;
+
+    let first = options.build_udp()?;
+
+    // bind the remaining sockets to the same address
+    options.addr = first.local_addr()?;
+    // now that we have a concrete port from the OS, we set the option before the bind call
+    options.reuse_port = ReusePort::BeforeBind;
+
+    let mut sockets = Vec::with_capacity(workers);
+    sockets.push(first);
+    for _ in 1..workers {
+        sockets.push(options.build_udp()?);
+    }
+
+    // the program is shared by all of the sockets in the group
+    let instructions = steering_program(workers as u32);
+    Program::new(&instructions).attach(&sockets[0])?;
+
+    Ok(sockets)
+}
+
+/// Returns a program which routes each packet to a worker based on the first byte of its
+/// destination connection ID
+///
+/// The connection IDs issued by the workers always map to their own index. The connection IDs
+/// chosen by clients in Initial packets are arbitrary, but still route consistently to a single
+/// worker. Long header packets with an empty connection ID fall back to the default hashing of
+/// the 4-tuple.
+fn steering_program(workers: u32) -> Vec<Instruction> {
+    vec![
+        // load the first byte of the packet
+        ldb(abs(0)),
+        // IF:
+        // the long header bit is set, jump to the long header handling
+        jset(0x80, 2, 0),
+        // ELSE:
+        // load the first byte of the short header destination connection ID
+        ldb(abs(1)),
+        // jump to the worker selection
+        ja(3),
+        // load the length of the long header destination connection ID
+        ldb(abs(5)),
+        // IF:
+        // the connection ID is empty, fall back to hashing
+        jeq(0, 3, 0),
+        // ELSE:
+        // load the first byte of the long header destination connection ID
+        ldb(abs(6)),
+        // select one of the workers
+        rem(workers),
+        ret_a(),
+        // any index outside of the group falls back to hashing
+        ret(u32::MAX),
+    ]
+}
+
+/// Wraps a connection ID format to encode the worker index in the first byte of each ID
+struct Format<F> {
+    format: F,
+    worker: u8,
+}
+
+impl<F: Generator> Format<F> {
+    /// Wraps the format, unless it relies on the value of the first byte of its connection IDs
+    fn new(format: F, worker: u8) -> Result<Self, StartError> {
+        if format.is_first_byte_reserved() {
+            return Err(StartError::new(
+                "connection ID formats that reserve the first byte can't be used with a sharded server",
+            ));
+        }
+
+        Ok(Self { format, worker })
+    }
+}
+
+impl<F: Generator> Generator for Format<F> {
+    #[inline]
+    fn generate(&mut self, connection_info: &ConnectionInfo) -> connection::LocalId {
+        let id = self.format.generate(connection_info);
+
+        let mut bytes = [0u8; connection::id::MAX_LEN];
+        let bytes = &mut bytes[..id.len()];
+        bytes.copy_from_slice(id.as_bytes());
+        // local IDs always have at least `LocalId::MIN_LEN` bytes
+        bytes[0] = self.worker;
+
+        connection::LocalId::try_from_bytes(bytes).expect("length already checked")
+    }
+
+    #[inline]
+    fn lifetime(&self) -> Option<Duration> {
+        self.format.lifetime()
+    }
+
+    #[inline]
+    fn rotate_handshake_connection_id(&self) -> bool {
+        self.format.rotate_handshake_connection_id()
+    }
+
+    #[inline]
+    fn is_first_byte_reserved(&self) -> bool {
+        // the first byte routes packets to the worker
+        true
+    }
+}
+
+impl<F: Validator> Validator for Format<F> {
+    #[inline]
+    fn validate(&self, connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+        self.format.validate(connection_info, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::connection_id;
+
+    #[test]
+    fn format_test() {
+        let remote_address = s2n_quic_core::inet::SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+
+        for worker in [0, 1, 3, u8::MAX] {
+            let mut format = Format {
+                format: connection_id::default::Format::default(),
+                worker,
+            };
+
+            for _ in 0..10 {
+                let id = format.generate(&connection_info);
+                assert_eq!(id.len(), 16);
+                assert_eq!(id.as_bytes()[0], worker);
+                assert_eq!(format.validate(&connection_info, id.as_bytes()), Some(16));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "provider-connection-id-quic-lb")]
+    fn quic_lb_format_test() {
+        let config = connection_id::quic_lb::Config::new(0, 3, 12).unwrap();
+        let format = connection_id::quic_lb::Format::builder(config, &[1, 2, 3])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // the worker index would overwrite the config rotation bits
+        assert!(Format::new(format, 1).is_err());
+        // the worker index can't be overwritten by another shard either
+        let sharded = Format::new(connection_id::default::Format::default(), 1).unwrap();
+        assert!(Format::new(sharded, 2).is_err());
+        assert!(Format::new(connection_id::default::Format::default(), 1).is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn routing_test() {
+        const WORKERS: usize = 4;
+
+        let sockets = match bind("127.0.0.1:0".parse().unwrap(), WORKERS) {
+            Ok(sockets) => sockets,
+            Err(err)
+                if [
+                    std::io::ErrorKind::PermissionDenied,
+                    std::io::ErrorKind::AddrNotAvailable,
+                ]
+                .contains(&err.kind()) =>
+            {
+                eprintln!("skipping test due to insufficient permissions");
+                return;
+            }
+            Err(err) => panic!("{err}"),
+        };
+
+        let addr = sockets[0].local_addr().unwrap();
+        for socket in &sockets {
+            assert_eq!(socket.local_addr().unwrap(), addr);
+            socket.set_nonblocking(false).unwrap();
+            socket
+                .set_read_timeout(Some(Duration::from_millis(100)))
+                .unwrap();
+        }
+
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        for id in 0..(WORKERS as u8 * 3) {
+            // short header with an 8 byte destination connection ID
+            let mut short = [0u8; 16];
+            short[0] = 0b0100_0000;
+            short[1] = id;
+
+            // long header with an 8 byte destination connection ID
+            let mut long = [0u8; 24];
+            long[0] = 0b1100_0000;
+            long[1..5].copy_from_slice(&1u32.to_be_bytes());
+            long[5] = 8;
+            long[6] = id;
+
+            for packet in [&short[..], &long[..]] {
+                client.send_to(packet, addr).unwrap();
+
+                let mut buffer = [0u8; 32];
+                let expected = &sockets[id as usize % WORKERS];
+                let (len, _) = expected.recv_from(&mut buffer).unwrap_or_else(|err| {
+                    panic!(
+                        "packet for worker {} was not received: {err}",
+                        id as usize % WORKERS
+                    )
+                });
+                assert_eq!(&buffer[..len], packet);
+            }
+        }
+
+        // make sure none of the other sockets received any packets
+        for socket in &sockets {
+            socket.set_nonblocking(true).unwrap();
+            let mut buffer = [0u8; 32];
+            assert!(socket.recv_from(&mut buffer).is_err());
+        }
+    }
+}
@@ -157,6 +157,80 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Receives data from the stream directly into the provided buffer.
+        ///
+        /// Unlike [`receive`](Self::receive), the data is copied straight out of the stream's
+        /// receive buffer into `buf`, without an intermediate [`Bytes`](bytes::Bytes) chunk.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(len)` if `len` bytes were received into `buf`. If `len == 0`, either the stream
+        ///   was finished and all of the data was consumed, or `buf` has no remaining capacity.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// let mut buf = bytes::BytesMut::with_capacity(4096);
+        /// while stream.receive_into(&mut buf).await? > 0 {
+        ///     println!("received: {:?}", buf.split());
+        /// }
+        ///
+        /// println!("finished");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn receive_into<B>(&mut self, buf: &mut B) -> $crate::stream::Result<usize>
+        where
+            B: bytes::BufMut + ?Sized,
+        {
+            ::futures::future::poll_fn(|cx| self.poll_receive_into(buf, cx)).await
+        }
+
+        /// Polls for receiving data from the stream directly into the provided buffer.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Poll::Pending` if the stream is waiting to receive data from the peer. In this case,
+        ///   the caller should retry receiving after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok(len))` if `len` bytes were received into `buf`. If `len == 0`, either
+        ///   the stream was finished and all of the data was consumed, or `buf` has no remaining
+        ///   capacity.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_receive_into<B>(
+            &mut self,
+            buf: &mut B,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<usize>>
+        where
+            B: bytes::BufMut + ?Sized,
+        {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_receive_into(buf, cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Notifies the peer to stop sending data on the stream.
         ///
         /// This requests the peer to finish the stream as soon as possible
@@ -208,6 +282,45 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Returns `true` if any of the data on the stream was received in 0-RTT packets.
+        ///
+        /// Early data can be replayed by an attacker, so applications should only act on requests
+        /// that are safe to process more than once, and refuse any others (for example with
+        /// [`stop_sending`](Self::stop_sending)) until the data is received after the handshake.
+        /// The flag only covers the data received so far, so it should be checked after the
+        /// request was fully received.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(true)` if some of the data on the stream was received in 0-RTT packets.
+        /// - `Ok(false)` if all of the data was received after the handshake.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// while let Some(chunk) = stream.receive().await? {
+        ///     println!("received: {:?}", chunk);
+        /// }
+        ///
+        /// if stream.is_early_data()? {
+        ///     println!("the request may have been replayed");
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn is_early_data(&mut self) -> $crate::stream::Result<bool> {
+            let response = self.rx_request()?.receive(&mut []).poll(None)?;
+            Ok(response.early_data)
+        }
+
         /// Create a batch request for receiving data
         #[inline]
         pub(crate) fn rx_request(
@@ -274,37 +387,9 @@ macro_rules! impl_receive_stream_trait {
                 cx: &mut core::task::Context<'_>,
                 buf: &mut [u8],
             ) -> core::task::Poll<std::io::Result<usize>> {
-                use bytes::Bytes;
-
-                if buf.is_empty() {
-                    return Ok(0).into();
-                }
-
-                // create some chunks on the stack to receive into
-                // TODO investigate a better default number
-                let mut chunks = [
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                ];
-
-                let high_watermark = buf.len();
-
-                let response =
-                    core::task::ready!(self.receive_chunks(cx, &mut chunks, high_watermark))?;
-
-                let chunks = &chunks[..response.chunks.consumed];
-                let mut bufs = [buf];
-                let copied_len = s2n_quic_core::slice::vectored_copy(chunks, &mut bufs);
-
-                debug_assert_eq!(
-                    copied_len, response.bytes.consumed,
-                    "the consumed bytes should always have enough capacity in bufs"
-                );
-
-                Ok(response.bytes.consumed).into()
+                let mut buf = buf;
+                let len = core::task::ready!(self.poll_receive_into(&mut buf, cx))?;
+                Ok(len).into()
             }
 
             fn poll_read_vectored(
@@ -356,30 +441,19 @@ macro_rules! impl_receive_stream_trait {
                 cx: &mut core::task::Context<'_>,
                 buf: &mut tokio::io::ReadBuf,
             ) -> core::task::Poll<std::io::Result<()>> {
-                use bytes::Bytes;
-
                 if buf.remaining() == 0 {
                     return Ok(()).into();
                 }
 
-                // create some chunks on the stack to receive into
-                // TODO investigate a better default number
-                let mut chunks = [
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                    Bytes::new(),
-                ];
-
-                let high_watermark = buf.remaining();
-
-                let response =
-                    core::task::ready!(self.receive_chunks(cx, &mut chunks, high_watermark))?;
+                // SAFETY: the stream only writes initialized bytes into the unfilled region
+                let mut unfilled = unsafe { buf.unfilled_mut() };
+                let len = core::task::ready!(self.poll_receive_into(&mut unfilled, cx))?;
 
-                for chunk in &chunks[..response.chunks.consumed] {
-                    buf.put_slice(chunk);
+                // SAFETY: the stream initialized `len` bytes at the start of the unfilled region
+                unsafe {
+                    buf.assume_init(len);
                 }
+                buf.advance(len);
 
                 Ok(()).into()
             }
@@ -146,6 +146,78 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Copies a slice of data onto the stream for sending it towards the peer.
+        ///
+        /// Unlike [`send`](Self::send), the caller does not need to allocate a
+        /// [`Bytes`](bytes::Bytes) for the data. The data is copied directly into the stream's
+        /// send buffer, waiting for capacity as needed.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(())` if all of the data was enqueued for sending.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let data = [1, 2, 3, 4];
+        /// stream.send_from(&data).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn send_from(&mut self, data: &[u8]) -> $crate::stream::Result<()> {
+            let mut sent_len = 0;
+
+            ::futures::future::poll_fn(|cx| {
+                while sent_len < data.len() {
+                    sent_len += ::core::task::ready!(self.poll_send_from(&data[sent_len..], cx))?;
+                }
+                Ok(()).into()
+            })
+            .await
+        }
+
+        /// Polls copying a slice of data onto the stream for sending it towards the peer.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Poll::Pending` if the stream's send buffer capacity is currently exhausted. In this case,
+        ///   the caller should retry sending after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok(len))` if `len` bytes from the start of `data` were enqueued for
+        ///   sending. If `len` is less than the length of `data`, the remaining data should be
+        ///   sent in a subsequent call.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_send_from(
+            &mut self,
+            data: &[u8],
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<usize>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_send_from(data, cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Polls send readiness for the given stream.
         ///
         /// This method _must_ be called before calling [`send_data`](Self::send_data).
@@ -392,6 +464,50 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Sets the [`Priority`](crate::stream::Priority) of the stream.
+        ///
+        /// The priority is used by the connection's stream scheduler to pick which streams are
+        /// given the opportunity to transmit first. With the default scheduler, streams with a lower
+        /// urgency are sent ahead of streams with a higher urgency.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the priority was updated successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// use s2n_quic::stream::Priority;
+        ///
+        /// // make sure control messages aren't queued behind other streams
+        /// stream.set_priority(Priority::default().with_urgency(0)?)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority(
+            &mut self,
+            priority: $crate::stream::Priority,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_priority(priority)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -442,13 +558,7 @@ macro_rules! impl_send_stream_trait {
                 cx: &mut core::task::Context<'_>,
                 buf: &[u8],
             ) -> core::task::Poll<std::io::Result<usize>> {
-                if buf.is_empty() {
-                    return Ok(0).into();
-                }
-
-                let len = core::task::ready!(self.poll_send_ready(cx))?.min(buf.len());
-                let data = bytes::Bytes::copy_from_slice(&buf[..len]);
-                self.send_data(data)?;
+                let len = core::task::ready!(self.poll_send_from(buf, cx))?;
                 Ok(len).into()
             }
 